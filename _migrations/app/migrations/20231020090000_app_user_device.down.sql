@@ -0,0 +1,2 @@
+DROP TABLE app_user_device_link CASCADE;
+DROP TABLE app_user_device CASCADE;
\ No newline at end of file
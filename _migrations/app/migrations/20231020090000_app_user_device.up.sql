@@ -0,0 +1,54 @@
+CREATE TABLE IF NOT EXISTS app_user_device
+(
+    id              SERIAL PRIMARY KEY,
+    user_id         INTEGER NOT NULL,
+    public_key_hex  VARCHAR(68) NOT NULL,
+    name            VARCHAR(128),
+    created_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    revoked_at      TIMESTAMP WITHOUT TIME ZONE,
+    CONSTRAINT app_user_device_fk_user
+        FOREIGN KEY (user_id)
+            REFERENCES app_user (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT app_user_device_u_public_key UNIQUE (public_key_hex)
+);
+
+CREATE INDEX IF NOT EXISTS app_user_device_idx_user_id
+    ON app_user_device (user_id);
+CREATE INDEX IF NOT EXISTS app_user_device_idx_public_key_hex_revoked_at
+    ON app_user_device (public_key_hex, revoked_at);
+
+-- every existing user gets their registration key as the first device
+INSERT INTO app_user_device (user_id, public_key_hex, created_at)
+SELECT id, public_key_hex, created_at FROM app_user
+WHERE public_key_hex IS NOT NULL
+ON CONFLICT (public_key_hex) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS app_user_device_link
+(
+    id              SERIAL PRIMARY KEY,
+    user_id         INTEGER NOT NULL,
+    device_id       INTEGER NOT NULL,
+    code            VARCHAR(64) NOT NULL,
+    expires_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    linked_device_id INTEGER,
+    created_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT app_user_device_link_fk_user
+        FOREIGN KEY (user_id)
+            REFERENCES app_user (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT app_user_device_link_fk_device
+        FOREIGN KEY (device_id)
+            REFERENCES app_user_device (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT app_user_device_link_fk_linked_device
+        FOREIGN KEY (linked_device_id)
+            REFERENCES app_user_device (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT app_user_device_link_u_code UNIQUE (code)
+);
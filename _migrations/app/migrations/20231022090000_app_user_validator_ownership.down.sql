@@ -0,0 +1 @@
+ALTER TABLE app_user_validator DROP COLUMN IF EXISTS ownership_verified_at;
\ No newline at end of file
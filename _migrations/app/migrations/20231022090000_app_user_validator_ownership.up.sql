@@ -0,0 +1 @@
+ALTER TABLE app_user_validator ADD COLUMN IF NOT EXISTS ownership_verified_at TIMESTAMP WITHOUT TIME ZONE;
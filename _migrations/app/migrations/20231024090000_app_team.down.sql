@@ -0,0 +1,15 @@
+DROP INDEX IF EXISTS app_user_notification_rule_u_team_rule;
+DROP INDEX IF EXISTS app_user_validator_u_team_network_validator;
+ALTER TABLE app_user_notification_rule DROP COLUMN IF EXISTS team_id;
+ALTER TABLE app_user_validator DROP COLUMN IF EXISTS team_id;
+DROP INDEX IF EXISTS app_user_notification_rule_u_rule;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_notification_rule_u_rule
+    ON app_user_notification_rule (user_id, notification_type_code)
+    WHERE deleted_at IS NULL;
+DROP INDEX IF EXISTS app_user_validator_u_user_network_validator;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_validator_u_user_network_validator
+    ON app_user_validator (user_id, network_id, validator_account_id)
+    WHERE deleted_at IS NULL;
+DROP TABLE app_team_member CASCADE;
+DROP TABLE app_team CASCADE;
+DROP TYPE IF EXISTS app_team_role;
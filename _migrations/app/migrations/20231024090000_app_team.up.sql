@@ -0,0 +1,62 @@
+DO $$ BEGIN
+    IF to_regtype('app_team_role') IS NULL THEN
+        CREATE TYPE app_team_role AS ENUM ('viewer', 'editor', 'owner');
+    END IF;
+END $$;
+
+CREATE TABLE IF NOT EXISTS app_team
+(
+    id          SERIAL PRIMARY KEY,
+    name        VARCHAR(128) NOT NULL,
+    created_at  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS app_team_member
+(
+    team_id     INTEGER NOT NULL,
+    user_id     INTEGER NOT NULL,
+    role        app_team_role NOT NULL DEFAULT 'viewer',
+    created_at  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT app_team_member_pk PRIMARY KEY (team_id, user_id),
+    CONSTRAINT app_team_member_fk_team
+        FOREIGN KEY (team_id)
+            REFERENCES app_team (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT app_team_member_fk_user
+        FOREIGN KEY (user_id)
+            REFERENCES app_user (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS app_team_member_idx_user_id
+    ON app_team_member (user_id);
+
+-- team-owned validators and rules keep the id of the creating user in user_id
+ALTER TABLE app_user_validator ADD COLUMN IF NOT EXISTS team_id INTEGER
+    REFERENCES app_team (id) ON DELETE CASCADE ON UPDATE CASCADE;
+ALTER TABLE app_user_notification_rule ADD COLUMN IF NOT EXISTS team_id INTEGER
+    REFERENCES app_team (id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+DROP INDEX IF EXISTS app_user_validator_u_user_network_validator;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_validator_u_user_network_validator
+    ON app_user_validator (user_id, network_id, validator_account_id)
+    WHERE deleted_at IS NULL AND team_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_validator_u_team_network_validator
+    ON app_user_validator (team_id, network_id, validator_account_id)
+    WHERE deleted_at IS NULL AND team_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS app_user_validator_idx_team_id
+    ON app_user_validator (team_id);
+
+DROP INDEX IF EXISTS app_user_notification_rule_u_rule;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_notification_rule_u_rule
+    ON app_user_notification_rule (user_id, notification_type_code)
+    WHERE deleted_at IS NULL AND team_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_notification_rule_u_team_rule
+    ON app_user_notification_rule (team_id, notification_type_code)
+    WHERE deleted_at IS NULL AND team_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS app_user_notification_rule_idx_team_id
+    ON app_user_notification_rule (team_id);
@@ -0,0 +1,30 @@
+-- users join teams only by accepting an invitation from a team owner
+CREATE TABLE IF NOT EXISTS app_team_invitation
+(
+    id                  SERIAL PRIMARY KEY,
+    team_id             INTEGER NOT NULL,
+    user_id             INTEGER NOT NULL,
+    role                app_team_role NOT NULL DEFAULT 'viewer',
+    invited_by_user_id  INTEGER NOT NULL,
+    created_at          TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at          TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT app_team_invitation_u_team_user UNIQUE (team_id, user_id),
+    CONSTRAINT app_team_invitation_fk_team
+        FOREIGN KEY (team_id)
+            REFERENCES app_team (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT app_team_invitation_fk_user
+        FOREIGN KEY (user_id)
+            REFERENCES app_user (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT app_team_invitation_fk_invited_by_user
+        FOREIGN KEY (invited_by_user_id)
+            REFERENCES app_user (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS app_team_invitation_idx_user_id
+    ON app_team_invitation (user_id);
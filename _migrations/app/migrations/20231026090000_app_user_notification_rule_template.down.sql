@@ -0,0 +1 @@
+DROP TABLE app_user_notification_rule_template CASCADE;
\ No newline at end of file
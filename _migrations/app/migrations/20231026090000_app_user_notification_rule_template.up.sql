@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS app_user_notification_rule_template
+(
+    id          SERIAL PRIMARY KEY,
+    user_id     INTEGER NOT NULL,
+    name        VARCHAR(128) NOT NULL,
+    rules_json  text NOT NULL,
+    created_at  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP WITHOUT TIME ZONE,
+    CONSTRAINT app_user_notification_rule_template_fk_user
+        FOREIGN KEY (user_id)
+            REFERENCES app_user (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS app_user_notification_rule_template_idx_user_id
+    ON app_user_notification_rule_template (user_id, deleted_at);
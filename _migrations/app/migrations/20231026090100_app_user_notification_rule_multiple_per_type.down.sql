@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS app_user_notification_rule_idx_user_id_notification_type_code;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_notification_rule_u_rule
+    ON app_user_notification_rule (user_id, notification_type_code)
+    WHERE deleted_at IS NULL AND team_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_notification_rule_u_team_rule
+    ON app_user_notification_rule (team_id, notification_type_code)
+    WHERE deleted_at IS NULL AND team_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS app_user_notification_rule_u_rule_deleted
+    ON app_user_notification_rule (user_id, notification_type_code, deleted_at)
+    WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,8 @@
+-- a user or a team can have more than one rule of a notification type, e.g. for different
+-- validators or with different parameters
+DROP INDEX IF EXISTS app_user_notification_rule_u_rule;
+DROP INDEX IF EXISTS app_user_notification_rule_u_team_rule;
+DROP INDEX IF EXISTS app_user_notification_rule_u_rule_deleted;
+CREATE INDEX IF NOT EXISTS app_user_notification_rule_idx_user_id_notification_type_code
+    ON app_user_notification_rule (user_id, notification_type_code)
+    WHERE deleted_at IS NULL;
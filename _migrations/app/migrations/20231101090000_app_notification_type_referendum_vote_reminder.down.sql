@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code = 'chain_validator_referendum_not_voted';
\ No newline at end of file
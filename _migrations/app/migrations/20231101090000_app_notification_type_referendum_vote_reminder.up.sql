@@ -0,0 +1,41 @@
+INSERT INTO app_notification_type(code) VALUES('chain_validator_referendum_not_voted') ON CONFLICT(code) DO NOTHING;
+
+-- chain_validator_referendum_not_voted
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_referendum_not_voted',
+    'hours_before_deadline',
+    0,
+    'integer',
+    '1',
+    NULL,
+    true,
+    'Send the reminder when the decision deadline of an open referendum is less than this many hours away. Defaults to 24 hours.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_referendum_not_voted',
+    'voting_account',
+    1,
+    'string',
+    '46',
+    '66',
+    true,
+    'Address or account id hex of the account that votes on behalf of the validator. The validator stash account is checked when not set.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
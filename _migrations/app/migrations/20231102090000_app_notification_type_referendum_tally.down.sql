@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code IN ('referendum_threshold_crossed', 'referendum_confirm_started', 'referendum_confirm_aborted', 'referendum_tally_changed');
\ No newline at end of file
@@ -0,0 +1,107 @@
+INSERT INTO app_notification_type(code) VALUES('referendum_threshold_crossed') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('referendum_confirm_started') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('referendum_confirm_aborted') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('referendum_tally_changed') ON CONFLICT(code) DO NOTHING;
+
+-- referendum_threshold_crossed
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'referendum_threshold_crossed',
+    'track_ids',
+    0,
+    'string',
+    '1',
+    '256',
+    true,
+    'Comma-separated list of the ids of the referendum tracks to follow, such as 0,1,30. All tracks are followed when not set.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+
+-- referendum_confirm_started
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'referendum_confirm_started',
+    'track_ids',
+    0,
+    'string',
+    '1',
+    '256',
+    true,
+    'Comma-separated list of the ids of the referendum tracks to follow, such as 0,1,30. All tracks are followed when not set.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+
+-- referendum_confirm_aborted
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'referendum_confirm_aborted',
+    'track_ids',
+    0,
+    'string',
+    '1',
+    '256',
+    true,
+    'Comma-separated list of the ids of the referendum tracks to follow, such as 0,1,30. All tracks are followed when not set.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+
+-- referendum_tally_changed
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'referendum_tally_changed',
+    'track_ids',
+    0,
+    'string',
+    '1',
+    '256',
+    true,
+    'Comma-separated list of the ids of the referendum tracks to follow, such as 0,1,30. All tracks are followed when not set.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'referendum_tally_changed',
+    'change_percent',
+    1,
+    'float',
+    '0.1',
+    NULL,
+    true,
+    'Send the notification when the ayes, nays or support of a referendum change by more than this percentage since the last notification. Defaults to 10%.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
\ No newline at end of file
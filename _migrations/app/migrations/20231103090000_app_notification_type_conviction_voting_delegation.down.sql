@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code IN ('conviction_voting_delegated', 'conviction_voting_undelegated');
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO app_notification_type(code) VALUES('conviction_voting_delegated') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('conviction_voting_undelegated') ON CONFLICT(code) DO NOTHING;
\ No newline at end of file
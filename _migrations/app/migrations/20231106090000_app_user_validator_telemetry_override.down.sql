@@ -0,0 +1 @@
+DROP TABLE IF EXISTS app_user_validator_telemetry_override;
@@ -0,0 +1,17 @@
+-- Telemetry node of an ownership-verified user validator, set by the owner when the node cannot
+-- be matched automatically, identified by its libp2p network id or its node name
+CREATE TABLE IF NOT EXISTS app_user_validator_telemetry_override
+(
+    user_validator_id   INTEGER PRIMARY KEY,
+    node_network_id     TEXT,
+    node_name           TEXT,
+    created_at          TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at          TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT app_user_validator_telemetry_override_c_node
+        CHECK (node_network_id IS NOT NULL OR node_name IS NOT NULL),
+    CONSTRAINT app_user_validator_telemetry_override_fk_user_validator
+        FOREIGN KEY (user_validator_id)
+            REFERENCES app_user_validator (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code = 'telemetry_validator_not_participating_in_finality';
\ No newline at end of file
@@ -0,0 +1,22 @@
+INSERT INTO app_notification_type(code) VALUES('telemetry_validator_not_participating_in_finality') ON CONFLICT(code) DO NOTHING;
+
+-- telemetry_validator_not_participating_in_finality
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'telemetry_validator_not_participating_in_finality',
+    'duration_sec',
+    0,
+    'integer',
+    '60',
+    NULL,
+    false,
+    'Notification happens if the validator''s node has not sent any GRANDPA messages for this many seconds while still importing blocks.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
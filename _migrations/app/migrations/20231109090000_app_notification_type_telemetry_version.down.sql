@@ -0,0 +1,8 @@
+UPDATE app_notification_param_type
+SET description = 'Telemetry binary out-of-date duration for the validator in seconds.'
+WHERE notification_type_code = 'telemetry_validator_binary_out_of_date'
+AND code = 'duration_sec';
+
+UPDATE app_notification_type
+SET is_enabled = false
+WHERE code = 'telemetry_validator_binary_out_of_date';
@@ -0,0 +1,10 @@
+-- telemetry_validator_binary_out_of_date is driven by the latest release determined by the
+-- telemetry processor, duration is the grace period after the release
+UPDATE app_notification_type
+SET is_enabled = true
+WHERE code = 'telemetry_validator_binary_out_of_date';
+
+UPDATE app_notification_param_type
+SET description = 'Grace period in seconds after the latest client release. Notification happens if the validator''s node still runs an older version after this period.'
+WHERE notification_type_code = 'telemetry_validator_binary_out_of_date'
+AND code = 'duration_sec';
@@ -0,0 +1,2 @@
+SELECT remove_retention_policy('sub_validator_snapshot');
+DROP TABLE sub_validator_snapshot CASCADE;
@@ -0,0 +1,30 @@
+CREATE TABLE IF NOT EXISTS sub_validator_snapshot
+(
+    time                    TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    block_number            bigint NOT NULL,
+    validator_account_id    VARCHAR(66) NOT NULL,
+    is_active               boolean NOT NULL,
+    commission_per_billion  bigint NOT NULL,
+    self_stake              VARCHAR(128) NOT NULL,
+    total_stake             VARCHAR(128),
+    nominator_count         INTEGER NOT NULL,
+    return_rate_per_billion bigint,
+    onekv_rank              bigint,
+    CONSTRAINT sub_validator_snapshot_u_time_validator
+        UNIQUE (time, validator_account_id)
+);
+
+SELECT create_hypertable(
+    'sub_validator_snapshot',
+    'time',
+    if_not_exists => TRUE
+);
+SELECT set_chunk_time_interval('sub_validator_snapshot', INTERVAL '7 days');
+SELECT add_retention_policy(
+    'sub_validator_snapshot',
+    INTERVAL '365 days',
+    if_not_exists => TRUE
+);
+
+CREATE INDEX IF NOT EXISTS sub_validator_snapshot_idx_validator_account_id_time
+    ON sub_validator_snapshot (validator_account_id, time DESC);
@@ -0,0 +1 @@
+ALTER TABLE sub_telegram_chat DROP COLUMN IF EXISTS is_group;
@@ -0,0 +1 @@
+ALTER TABLE sub_telegram_chat ADD COLUMN IF NOT EXISTS is_group boolean NOT NULL DEFAULT false;
@@ -0,0 +1,24 @@
+DROP INDEX IF EXISTS sub_referendum_idx_status;
+
+ALTER TABLE sub_referendum
+    DROP COLUMN IF EXISTS content,
+    DROP COLUMN IF EXISTS submitted_block_number,
+    DROP COLUMN IF EXISTS prepare_end_block_number,
+    DROP COLUMN IF EXISTS end_block_number,
+    DROP COLUMN IF EXISTS queue_position,
+    DROP COLUMN IF EXISTS ayes,
+    DROP COLUMN IF EXISTS nays,
+    DROP COLUMN IF EXISTS support,
+    DROP COLUMN IF EXISTS approval_per_billion,
+    DROP COLUMN IF EXISTS support_per_billion,
+    DROP COLUMN IF EXISTS deciding_since_block_number,
+    DROP COLUMN IF EXISTS decision_deadline_block_number,
+    DROP COLUMN IF EXISTS confirm_end_block_number,
+    DROP COLUMN IF EXISTS min_approval_per_billion,
+    DROP COLUMN IF EXISTS min_support_per_billion,
+    DROP COLUMN IF EXISTS is_passing;
+
+DELETE FROM sub_referendum WHERE proposer_account_id IS NULL OR track_id IS NULL OR pa_created_at IS NULL;
+ALTER TABLE sub_referendum ALTER COLUMN proposer_account_id SET NOT NULL;
+ALTER TABLE sub_referendum ALTER COLUMN track_id SET NOT NULL;
+ALTER TABLE sub_referendum ALTER COLUMN pa_created_at SET NOT NULL;
@@ -0,0 +1,26 @@
+-- on-chain referenda don't keep the track or the proposer after they're finished,
+-- and Polkassembly data is optional
+ALTER TABLE sub_referendum ALTER COLUMN proposer_account_id DROP NOT NULL;
+ALTER TABLE sub_referendum ALTER COLUMN track_id DROP NOT NULL;
+ALTER TABLE sub_referendum ALTER COLUMN pa_created_at DROP NOT NULL;
+
+ALTER TABLE sub_referendum
+    ADD COLUMN IF NOT EXISTS content                         TEXT,
+    ADD COLUMN IF NOT EXISTS submitted_block_number          BIGINT,
+    ADD COLUMN IF NOT EXISTS prepare_end_block_number        BIGINT,
+    ADD COLUMN IF NOT EXISTS end_block_number                BIGINT,
+    ADD COLUMN IF NOT EXISTS queue_position                  INTEGER,
+    ADD COLUMN IF NOT EXISTS ayes                            VARCHAR(128),
+    ADD COLUMN IF NOT EXISTS nays                            VARCHAR(128),
+    ADD COLUMN IF NOT EXISTS support                         VARCHAR(128),
+    ADD COLUMN IF NOT EXISTS approval_per_billion            BIGINT,
+    ADD COLUMN IF NOT EXISTS support_per_billion             BIGINT,
+    ADD COLUMN IF NOT EXISTS deciding_since_block_number     BIGINT,
+    ADD COLUMN IF NOT EXISTS decision_deadline_block_number  BIGINT,
+    ADD COLUMN IF NOT EXISTS confirm_end_block_number        BIGINT,
+    ADD COLUMN IF NOT EXISTS min_approval_per_billion        BIGINT,
+    ADD COLUMN IF NOT EXISTS min_support_per_billion         BIGINT,
+    ADD COLUMN IF NOT EXISTS is_passing                      BOOLEAN;
+
+CREATE INDEX IF NOT EXISTS sub_referendum_idx_status
+    ON sub_referendum (status);
@@ -0,0 +1,29 @@
+-- FellowshipReferenda pallet of Kusama, with referendum indices of its own
+CREATE TABLE IF NOT EXISTS sub_fellowship_referendum
+(
+    post_id                         INTEGER PRIMARY KEY,
+    proposer_account_id             VARCHAR(66),
+    type                            TEXT NOT NULL,
+    track_id                        SMALLINT,
+    status                          TEXT NOT NULL,
+    submitted_block_number          BIGINT,
+    prepare_end_block_number        BIGINT,
+    end_block_number                BIGINT,
+    queue_position                  INTEGER,
+    ayes                            VARCHAR(128),
+    nays                            VARCHAR(128),
+    support                         VARCHAR(128),
+    approval_per_billion            BIGINT,
+    support_per_billion             BIGINT,
+    deciding_since_block_number     BIGINT,
+    decision_deadline_block_number  BIGINT,
+    confirm_end_block_number        BIGINT,
+    min_approval_per_billion        BIGINT,
+    min_support_per_billion         BIGINT,
+    is_passing                      BOOLEAN,
+    created_at                      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at                      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS sub_fellowship_referendum_idx_status
+    ON sub_fellowship_referendum (status);
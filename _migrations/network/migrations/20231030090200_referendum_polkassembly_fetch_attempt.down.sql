@@ -0,0 +1,3 @@
+ALTER TABLE sub_referendum
+    DROP COLUMN IF EXISTS pa_fetch_failure_count,
+    DROP COLUMN IF EXISTS pa_last_fetch_at;
@@ -0,0 +1,4 @@
+-- failed fetches and fetches without a title are retried with an exponential backoff
+ALTER TABLE sub_referendum
+    ADD COLUMN IF NOT EXISTS pa_fetch_failure_count  INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS pa_last_fetch_at        TIMESTAMP WITHOUT TIME ZONE;
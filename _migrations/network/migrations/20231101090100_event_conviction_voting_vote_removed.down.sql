@@ -0,0 +1 @@
+DROP TABLE sub_event_conviction_voting_vote_removed CASCADE;
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE sub_event_conviction_voting_undelegated CASCADE;
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE sub_notification_generator_referendum_vote_reminder CASCADE;
\ No newline at end of file
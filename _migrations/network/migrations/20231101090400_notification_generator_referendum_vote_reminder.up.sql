@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS sub_notification_generator_referendum_vote_reminder
+(
+    user_notification_rule_id   bigint NOT NULL,
+    validator_account_id        VARCHAR(66) NOT NULL,
+    referendum_index            bigint NOT NULL,
+    created_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_notification_rule_id, validator_account_id, referendum_index)
+);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS sub_notification_generator_referendum_tally_state
+(
+    referendum_index                bigint PRIMARY KEY,
+    track_id                        smallint NOT NULL,
+    block_number                    bigint NOT NULL,
+    ayes                            VARCHAR(128) NOT NULL,
+    nays                            VARCHAR(128) NOT NULL,
+    support                         VARCHAR(128) NOT NULL,
+    approval_per_billion            bigint NOT NULL,
+    support_per_billion             bigint NOT NULL,
+    min_approval_per_billion        bigint,
+    min_support_per_billion         bigint,
+    confirm_end_block_number        bigint,
+    updated_at                      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
\ No newline at end of file
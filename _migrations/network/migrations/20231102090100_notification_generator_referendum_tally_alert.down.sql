@@ -0,0 +1 @@
+DROP TABLE sub_notification_generator_referendum_tally_alert CASCADE;
\ No newline at end of file
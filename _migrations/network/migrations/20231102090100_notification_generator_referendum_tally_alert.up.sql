@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS sub_notification_generator_referendum_tally_alert
+(
+    user_notification_rule_id   bigint NOT NULL,
+    referendum_index            bigint NOT NULL,
+    ayes                        VARCHAR(128) NOT NULL,
+    nays                        VARCHAR(128) NOT NULL,
+    support                     VARCHAR(128) NOT NULL,
+    created_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_notification_rule_id, referendum_index)
+);
\ No newline at end of file
@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS sub_event_conviction_voting_undelegated_idx_delegate_account;
+
+ALTER TABLE sub_event_conviction_voting_undelegated
+    DROP COLUMN IF EXISTS track_id,
+    DROP COLUMN IF EXISTS delegate_account_id;
+
+ALTER TABLE sub_event_conviction_voting_delegated
+    DROP COLUMN IF EXISTS track_id,
+    DROP COLUMN IF EXISTS conviction,
+    DROP COLUMN IF EXISTS balance;
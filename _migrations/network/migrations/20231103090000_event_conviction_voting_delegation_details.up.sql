@@ -0,0 +1,12 @@
+-- the delegation track, conviction and balance are resolved from the calls
+ALTER TABLE sub_event_conviction_voting_delegated
+    ADD COLUMN IF NOT EXISTS track_id       smallint,
+    ADD COLUMN IF NOT EXISTS conviction     smallint,
+    ADD COLUMN IF NOT EXISTS balance        VARCHAR(128);
+
+ALTER TABLE sub_event_conviction_voting_undelegated
+    ADD COLUMN IF NOT EXISTS track_id               smallint,
+    ADD COLUMN IF NOT EXISTS delegate_account_id    VARCHAR(66);
+
+CREATE INDEX IF NOT EXISTS sub_event_conviction_voting_undelegated_idx_delegate_account
+    ON sub_event_conviction_voting_undelegated (delegate_account_id);
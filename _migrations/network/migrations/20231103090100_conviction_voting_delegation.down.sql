@@ -0,0 +1 @@
+DROP TABLE IF EXISTS sub_conviction_voting_delegation;
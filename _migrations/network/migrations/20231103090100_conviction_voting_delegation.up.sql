@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS sub_conviction_voting_delegation
+(
+    delegator_account_id    VARCHAR(66) NOT NULL,
+    track_id                smallint NOT NULL,
+    delegate_account_id     VARCHAR(66) NOT NULL,
+    conviction              smallint NOT NULL,
+    balance                 VARCHAR(128) NOT NULL,
+    block_hash              VARCHAR(66) NOT NULL,
+    block_number            bigint NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (delegator_account_id, track_id),
+    CONSTRAINT sub_conviction_voting_delegation_fk_delegator_account
+        FOREIGN KEY (delegator_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_conviction_voting_delegation_fk_delegate_account
+        FOREIGN KEY (delegate_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_conviction_voting_delegation_idx_delegate_account
+    ON sub_conviction_voting_delegation (delegate_account_id);
@@ -0,0 +1 @@
+DROP TABLE sub_event_treasury_spend_approved CASCADE;
\ No newline at end of file
@@ -0,0 +1,35 @@
+CREATE TABLE IF NOT EXISTS sub_event_treasury_asset_spend_approved
+(
+    id                        SERIAL PRIMARY KEY,
+    block_hash                VARCHAR(66) NOT NULL,
+    extrinsic_index           INTEGER,
+    nesting_index             TEXT,
+    event_index               INTEGER NOT NULL,
+    spend_index               INTEGER NOT NULL,
+    asset_kind_json           TEXT NOT NULL,
+    amount                    VARCHAR(128) NOT NULL,
+    beneficiary_json          TEXT NOT NULL,
+    beneficiary_account_id    VARCHAR(66),
+    valid_from                BIGINT NOT NULL,
+    expire_at                 BIGINT NOT NULL,
+    created_at                TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_treasury_asset_spend_approved_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_treasury_asset_spend_approved_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_treasury_asset_spend_approved_fk_beneficiary_account
+        FOREIGN KEY (beneficiary_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_treasury_asset_spend_approved_idx_block_hash
+    ON sub_event_treasury_asset_spend_approved (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_treasury_asset_spend_approved_idx_beneficiary_account
+    ON sub_event_treasury_asset_spend_approved (beneficiary_account_id);
+CREATE INDEX IF NOT EXISTS sub_event_treasury_asset_spend_approved_idx_spend_index
+    ON sub_event_treasury_asset_spend_approved (spend_index);
\ No newline at end of file
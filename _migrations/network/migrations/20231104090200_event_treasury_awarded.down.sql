@@ -0,0 +1 @@
+DROP TABLE sub_event_treasury_awarded CASCADE;
\ No newline at end of file
@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS sub_event_treasury_awarded
+(
+    id                        SERIAL PRIMARY KEY,
+    block_hash                VARCHAR(66) NOT NULL,
+    extrinsic_index           INTEGER,
+    nesting_index             TEXT,
+    event_index               INTEGER NOT NULL,
+    proposal_index            INTEGER NOT NULL,
+    amount                    VARCHAR(128) NOT NULL,
+    beneficiary_account_id    VARCHAR(66) NOT NULL,
+    created_at                TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_treasury_awarded_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_treasury_awarded_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_treasury_awarded_fk_beneficiary_account
+        FOREIGN KEY (beneficiary_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_treasury_awarded_idx_block_hash
+    ON sub_event_treasury_awarded (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_treasury_awarded_idx_beneficiary_account
+    ON sub_event_treasury_awarded (beneficiary_account_id);
+CREATE INDEX IF NOT EXISTS sub_event_treasury_awarded_idx_proposal_index
+    ON sub_event_treasury_awarded (proposal_index);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE sub_event_treasury_paid CASCADE;
\ No newline at end of file
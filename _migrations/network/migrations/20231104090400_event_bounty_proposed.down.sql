@@ -0,0 +1 @@
+DROP TABLE sub_event_bounty_proposed CASCADE;
\ No newline at end of file
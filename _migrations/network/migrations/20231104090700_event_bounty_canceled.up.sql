@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS sub_event_bounty_canceled
+(
+    id                 SERIAL PRIMARY KEY,
+    block_hash         VARCHAR(66) NOT NULL,
+    extrinsic_index    INTEGER,
+    nesting_index      TEXT,
+    event_index        INTEGER NOT NULL,
+    bounty_index       INTEGER NOT NULL,
+    created_at         TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_bounty_canceled_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_bounty_canceled_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_bounty_canceled_idx_block_hash
+    ON sub_event_bounty_canceled (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_bounty_canceled_idx_bounty_index
+    ON sub_event_bounty_canceled (bounty_index);
\ No newline at end of file
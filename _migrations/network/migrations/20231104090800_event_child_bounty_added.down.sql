@@ -0,0 +1 @@
+DROP TABLE sub_event_child_bounty_added CASCADE;
\ No newline at end of file
@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS sub_event_child_bounty_added
+(
+    id                     SERIAL PRIMARY KEY,
+    block_hash             VARCHAR(66) NOT NULL,
+    extrinsic_index        INTEGER,
+    nesting_index          TEXT,
+    event_index            INTEGER NOT NULL,
+    parent_bounty_index    INTEGER NOT NULL,
+    child_bounty_index     INTEGER NOT NULL,
+    created_at             TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_child_bounty_added_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_child_bounty_added_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_child_bounty_added_idx_block_hash
+    ON sub_event_child_bounty_added (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_child_bounty_added_idx_bounty_index
+    ON sub_event_child_bounty_added (parent_bounty_index, child_bounty_index);
\ No newline at end of file
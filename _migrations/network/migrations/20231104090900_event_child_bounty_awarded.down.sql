@@ -0,0 +1 @@
+DROP TABLE sub_event_child_bounty_awarded CASCADE;
\ No newline at end of file
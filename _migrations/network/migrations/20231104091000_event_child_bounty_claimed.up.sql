@@ -0,0 +1,32 @@
+CREATE TABLE IF NOT EXISTS sub_event_child_bounty_claimed
+(
+    id                        SERIAL PRIMARY KEY,
+    block_hash                VARCHAR(66) NOT NULL,
+    extrinsic_index           INTEGER,
+    nesting_index             TEXT,
+    event_index               INTEGER NOT NULL,
+    parent_bounty_index       INTEGER NOT NULL,
+    child_bounty_index        INTEGER NOT NULL,
+    payout                    VARCHAR(128) NOT NULL,
+    beneficiary_account_id    VARCHAR(66) NOT NULL,
+    created_at                TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_child_bounty_claimed_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_child_bounty_claimed_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_child_bounty_claimed_fk_beneficiary_account
+        FOREIGN KEY (beneficiary_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_child_bounty_claimed_idx_block_hash
+    ON sub_event_child_bounty_claimed (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_child_bounty_claimed_idx_beneficiary_account
+    ON sub_event_child_bounty_claimed (beneficiary_account_id);
+CREATE INDEX IF NOT EXISTS sub_event_child_bounty_claimed_idx_bounty_index
+    ON sub_event_child_bounty_claimed (parent_bounty_index, child_bounty_index);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE sub_referendum_treasury_spend CASCADE;
\ No newline at end of file
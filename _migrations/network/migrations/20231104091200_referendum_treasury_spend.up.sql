@@ -0,0 +1,26 @@
+-- treasury spend calls decoded from the referendum proposals, in the order they appear in the call
+CREATE TABLE IF NOT EXISTS sub_referendum_treasury_spend
+(
+    id                      SERIAL PRIMARY KEY,
+    referendum_index        INTEGER NOT NULL,
+    call_index              INTEGER NOT NULL,
+    call_name               TEXT NOT NULL,
+    asset_kind_json         TEXT,
+    amount                  VARCHAR(128) NOT NULL,
+    beneficiary_json        TEXT NOT NULL,
+    beneficiary_account_id  VARCHAR(66),
+    valid_from              BIGINT,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_referendum_treasury_spend_u_call
+        UNIQUE (referendum_index, call_index),
+    CONSTRAINT sub_referendum_treasury_spend_fk_referendum
+        FOREIGN KEY (referendum_index)
+            REFERENCES sub_referendum (post_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_referendum_treasury_spend_idx_referendum_index
+    ON sub_referendum_treasury_spend (referendum_index);
+CREATE INDEX IF NOT EXISTS sub_referendum_treasury_spend_idx_beneficiary_account
+    ON sub_referendum_treasury_spend (beneficiary_account_id);
\ No newline at end of file
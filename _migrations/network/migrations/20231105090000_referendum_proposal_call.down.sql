@@ -0,0 +1,2 @@
+ALTER TABLE sub_referendum
+    DROP COLUMN IF EXISTS proposal_call_json;
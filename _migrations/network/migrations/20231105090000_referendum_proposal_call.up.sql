@@ -0,0 +1,4 @@
+-- proposal call decoded from the inline call or the preimage of the referendum, kept after the
+-- preimage is removed from chain
+ALTER TABLE sub_referendum
+    ADD COLUMN IF NOT EXISTS proposal_call_json TEXT;
@@ -0,0 +1,7 @@
+SELECT remove_retention_policy('sub_telemetry_validator_node_stats');
+DROP TABLE IF EXISTS sub_telemetry_validator_node_stats CASCADE;
+DROP TABLE IF EXISTS sub_telemetry_validator_network_id;
+DROP TABLE IF EXISTS sub_telemetry_node_validator;
+DROP INDEX IF EXISTS sub_telemetry_node_idx_network_id;
+ALTER TABLE sub_telemetry_node DROP COLUMN IF EXISTS validator_address;
+ALTER TABLE sub_telemetry_node DROP COLUMN IF EXISTS network_id;
@@ -0,0 +1,63 @@
+ALTER TABLE sub_telemetry_node ADD COLUMN IF NOT EXISTS network_id TEXT;
+ALTER TABLE sub_telemetry_node ADD COLUMN IF NOT EXISTS validator_address VARCHAR(64);
+
+CREATE INDEX IF NOT EXISTS sub_telemetry_node_idx_network_id
+    ON sub_telemetry_node (network_id);
+
+-- current validator of each node, matched by the telemetry processor
+CREATE TABLE IF NOT EXISTS sub_telemetry_node_validator
+(
+    node_id                 bigint PRIMARY KEY,
+    validator_account_id    VARCHAR(66) NOT NULL,
+    match_method            VARCHAR(32) NOT NULL,
+    confidence              SMALLINT NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_telemetry_node_validator_fk_telemetry_node
+        FOREIGN KEY (node_id)
+            REFERENCES sub_telemetry_node (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_telemetry_node_validator_idx_validator_account_id
+    ON sub_telemetry_node_validator (validator_account_id);
+
+-- network ids matched to validators with a reliable method, kept after the nodes disconnect
+-- so that the nodes can be matched by network id when they reconnect
+CREATE TABLE IF NOT EXISTS sub_telemetry_validator_network_id
+(
+    network_id              TEXT PRIMARY KEY,
+    validator_account_id    VARCHAR(66) NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- periodic snapshots of the stats of the nodes mapped to validators, for the history reports
+CREATE TABLE IF NOT EXISTS sub_telemetry_validator_node_stats
+(
+    time                    TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    validator_account_id    VARCHAR(66) NOT NULL,
+    node_name               TEXT NOT NULL,
+    network_id              TEXT,
+    peer_count              INTEGER,
+    best_block_lag          bigint,
+    download_bandwidth      double PRECISION,
+    upload_bandwidth        double PRECISION,
+    client_version          TEXT NOT NULL
+);
+
+SELECT create_hypertable(
+    'sub_telemetry_validator_node_stats',
+    'time',
+    if_not_exists => TRUE
+);
+SELECT set_chunk_time_interval('sub_telemetry_validator_node_stats', INTERVAL '1 days');
+SELECT add_retention_policy(
+    'sub_telemetry_validator_node_stats',
+    INTERVAL '30 days',
+    if_not_exists => TRUE
+);
+
+CREATE INDEX IF NOT EXISTS sub_telemetry_validator_node_stats_idx_validator_account_id_time
+    ON sub_telemetry_validator_node_stats (validator_account_id, time DESC);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS sub_telemetry_node_feed;
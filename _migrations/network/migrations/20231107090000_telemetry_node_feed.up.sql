@@ -0,0 +1,16 @@
+-- Telemetry feeds that report each node, nodes connected to more than one Telemetry server are
+-- merged by network id into a single node
+CREATE TABLE IF NOT EXISTS sub_telemetry_node_feed
+(
+    node_id         bigint NOT NULL,
+    feed_url        TEXT NOT NULL,
+    feed_node_id    bigint NOT NULL,
+    created_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_telemetry_node_feed_pk
+        PRIMARY KEY (node_id, feed_url),
+    CONSTRAINT sub_telemetry_node_feed_fk_telemetry_node
+        FOREIGN KEY (node_id)
+            REFERENCES sub_telemetry_node (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS sub_telemetry_validator_finality_session;
+DROP TABLE IF EXISTS sub_telemetry_validator_address;
+SELECT remove_retention_policy('sub_telemetry_node_finality');
+DROP TABLE IF EXISTS sub_telemetry_node_finality CASCADE;
@@ -0,0 +1,66 @@
+-- GRANDPA messages of the nodes that report a validator address, as received from the Telemetry
+-- feeds. A vote is late if it's received after the node has finalized the voted block.
+CREATE TABLE IF NOT EXISTS sub_telemetry_node_finality
+(
+    time                TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    validator_address   VARCHAR(64) NOT NULL,
+    kind                VARCHAR(16) NOT NULL,
+    block_number        bigint NOT NULL,
+    block_hash          VARCHAR(66) NOT NULL,
+    voter               TEXT,
+    is_late             BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+SELECT create_hypertable(
+    'sub_telemetry_node_finality',
+    'time',
+    if_not_exists => TRUE
+);
+SELECT set_chunk_time_interval('sub_telemetry_node_finality', INTERVAL '6 hours');
+SELECT add_retention_policy(
+    'sub_telemetry_node_finality',
+    INTERVAL '7 days',
+    if_not_exists => TRUE
+);
+
+CREATE INDEX IF NOT EXISTS sub_telemetry_node_finality_idx_validator_address_time
+    ON sub_telemetry_node_finality (validator_address, time DESC);
+
+-- validator addresses of the nodes matched to validators with a reliable method, kept after the
+-- nodes disconnect so that the finality messages can be attributed to the validators
+CREATE TABLE IF NOT EXISTS sub_telemetry_validator_address
+(
+    validator_address       VARCHAR(64) PRIMARY KEY,
+    validator_account_id    VARCHAR(66) NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS sub_telemetry_validator_address_idx_validator_account_id
+    ON sub_telemetry_validator_address (validator_account_id);
+
+-- GRANDPA participation of the validator nodes per session, updated periodically by the
+-- telemetry processor. Target counts are the numbers of distinct voted blocks.
+CREATE TABLE IF NOT EXISTS sub_telemetry_validator_finality_session
+(
+    validator_account_id            VARCHAR(66) NOT NULL,
+    validator_address               VARCHAR(64) NOT NULL,
+    session_index                   bigint NOT NULL,
+    finalized_count                 bigint NOT NULL,
+    prevote_count                   bigint NOT NULL,
+    precommit_count                 bigint NOT NULL,
+    late_prevote_count              bigint NOT NULL,
+    late_precommit_count            bigint NOT NULL,
+    prevote_target_count            bigint NOT NULL,
+    precommit_target_count          bigint NOT NULL,
+    network_prevote_target_count    bigint NOT NULL,
+    network_precommit_target_count  bigint NOT NULL,
+    last_finalized_block_number     bigint,
+    created_at                      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at                      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_telemetry_validator_finality_session_pk
+        PRIMARY KEY (validator_account_id, validator_address, session_index)
+);
+
+CREATE INDEX IF NOT EXISTS sub_telemetry_validator_finality_session_idx_session_index
+    ON sub_telemetry_validator_finality_session (session_index);
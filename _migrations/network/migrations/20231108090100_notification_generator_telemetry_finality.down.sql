@@ -0,0 +1 @@
+DROP TABLE sub_notification_generator_telemetry_finality CASCADE;
\ No newline at end of file
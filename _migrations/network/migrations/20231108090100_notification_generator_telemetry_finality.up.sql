@@ -0,0 +1,11 @@
+-- last finality messages of the validator nodes that have been notified as not participating
+-- in finality, a node gets notified again only after it participates again
+CREATE TABLE IF NOT EXISTS sub_notification_generator_telemetry_finality
+(
+    user_notification_rule_id   bigint NOT NULL,
+    validator_account_id        VARCHAR(66) NOT NULL,
+    validator_address           VARCHAR(64) NOT NULL,
+    last_finality_time          TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    created_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_notification_rule_id, validator_account_id, validator_address, last_finality_time)
+);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS sub_telemetry_release;
+SELECT remove_retention_policy('sub_telemetry_version_distribution');
+DROP TABLE IF EXISTS sub_telemetry_version_distribution CASCADE;
+DROP TABLE IF EXISTS sub_telemetry_node_version;
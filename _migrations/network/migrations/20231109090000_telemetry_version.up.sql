@@ -0,0 +1,48 @@
+-- client versions that the nodes have run, identified by network id, with the first and last
+-- time each version was seen, updated periodically by the telemetry processor
+CREATE TABLE IF NOT EXISTS sub_telemetry_node_version
+(
+    network_id              TEXT NOT NULL,
+    node_name               TEXT NOT NULL,
+    client_implementation   TEXT NOT NULL,
+    client_version          TEXT NOT NULL,
+    first_seen_at           TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    last_seen_at            TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_telemetry_node_version_pk
+        PRIMARY KEY (network_id, client_version)
+);
+
+CREATE INDEX IF NOT EXISTS sub_telemetry_node_version_idx_client_version
+    ON sub_telemetry_node_version (client_version);
+
+-- periodic snapshots of the number of nodes per client version
+CREATE TABLE IF NOT EXISTS sub_telemetry_version_distribution
+(
+    time                    TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    client_version          TEXT NOT NULL,
+    node_count              INTEGER NOT NULL,
+    validator_node_count    INTEGER NOT NULL
+);
+
+SELECT create_hypertable(
+    'sub_telemetry_version_distribution',
+    'time',
+    if_not_exists => TRUE
+);
+SELECT set_chunk_time_interval('sub_telemetry_version_distribution', INTERVAL '7 days');
+SELECT add_retention_policy(
+    'sub_telemetry_version_distribution',
+    INTERVAL '365 days',
+    if_not_exists => TRUE
+);
+
+-- client releases determined as the latest by the telemetry processor, the most recently
+-- updated one is the current latest release. release time is the time the release was first
+-- seen by the processor if the source doesn't provide it.
+CREATE TABLE IF NOT EXISTS sub_telemetry_release
+(
+    version                 TEXT PRIMARY KEY,
+    released_at             TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
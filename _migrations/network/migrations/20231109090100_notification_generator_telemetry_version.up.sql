@@ -0,0 +1,11 @@
+-- validator nodes that have been notified as running an out-of-date client, a node gets
+-- notified once per latest release
+CREATE TABLE IF NOT EXISTS sub_notification_generator_telemetry_version
+(
+    user_notification_rule_id   bigint NOT NULL,
+    validator_account_id        VARCHAR(66) NOT NULL,
+    node_name                   TEXT NOT NULL,
+    latest_version              TEXT NOT NULL,
+    created_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_notification_rule_id, validator_account_id, node_name, latest_version)
+);
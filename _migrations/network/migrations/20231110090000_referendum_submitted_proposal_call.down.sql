@@ -0,0 +1,2 @@
+ALTER TABLE sub_event_referendum_submitted
+    DROP COLUMN IF EXISTS proposal_call_json;
@@ -0,0 +1,4 @@
+-- proposal call decoded by the block processor at the time of submission, so that the
+-- submission notifications don't depend on the referendum updater
+ALTER TABLE sub_event_referendum_submitted
+    ADD COLUMN IF NOT EXISTS proposal_call_json TEXT;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS sub_telemetry_node_finality_idx_voter_time;
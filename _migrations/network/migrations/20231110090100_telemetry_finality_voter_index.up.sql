@@ -0,0 +1,4 @@
+-- last votes of the validator nodes by their voter address, which is reported debug-formatted
+CREATE INDEX IF NOT EXISTS sub_telemetry_node_finality_idx_voter_time
+    ON sub_telemetry_node_finality (btrim(voter, '"'), time DESC)
+    WHERE kind IN ('prevote', 'precommit');
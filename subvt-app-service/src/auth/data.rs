@@ -1,11 +1,11 @@
-//! Contains the wrapper for the `User` struct.
-//! Implements `FromRequest` for the wrapper to be able to pass the authenticated `User`
-//! to the request extensions.
+//! Contains the wrappers for the `User` and `UserDevice` structs.
+//! Implements `FromRequest` for the wrappers to be able to pass the authenticated `User`
+//! and `UserDevice` to the request extensions.
 use crate::auth::error::AuthError;
 use actix_web::dev::Payload;
 use actix_web::{Error, FromRequest, HttpMessage, HttpRequest};
 use futures::future::{ready, Ready};
-use subvt_types::app::User;
+use subvt_types::app::{User, UserDevice};
 
 #[derive(Debug)]
 pub struct AuthenticatedUser(User);
@@ -31,3 +31,29 @@ impl std::ops::Deref for AuthenticatedUser {
         &self.0
     }
 }
+
+/// The device whose key has signed the request.
+#[derive(Debug)]
+pub struct AuthenticatedDevice(UserDevice);
+
+impl FromRequest for AuthenticatedDevice {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let value = request.extensions().get::<UserDevice>().cloned();
+        let result = match value {
+            Some(v) => Ok(AuthenticatedDevice(v)),
+            None => Err(AuthError::DeviceNotFound.into()),
+        };
+        ready(result)
+    }
+}
+
+impl std::ops::Deref for AuthenticatedDevice {
+    type Target = UserDevice;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -10,12 +10,33 @@ pub enum AuthError {
     InvalidSignature,
     NonceMissing,
     InvalidNonce,
+    NonceOutOfWindow,
+    NonceReplayed,
     InternalError,
     UserNotFound,
+    DeviceNotFound,
     InvalidBody,
 }
 
 impl AuthError {
+    /// Label of the error in the rejected authentication attempt metrics.
+    pub(crate) fn get_metric_label(&self) -> &'static str {
+        match self {
+            Self::PublicKeyMissing => "public_key_missing",
+            Self::InvalidPublicKey => "invalid_public_key",
+            Self::SignatureMissing => "signature_missing",
+            Self::InvalidSignature => "invalid_signature",
+            Self::NonceMissing => "nonce_missing",
+            Self::InvalidNonce => "invalid_nonce",
+            Self::NonceOutOfWindow => "nonce_out_of_window",
+            Self::NonceReplayed => "nonce_replayed",
+            Self::InternalError => "internal_error",
+            Self::UserNotFound => "user_not_found",
+            Self::DeviceNotFound => "device_not_found",
+            Self::InvalidBody => "invalid_body",
+        }
+    }
+
     fn display(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::PublicKeyMissing => {
@@ -63,6 +84,24 @@ impl AuthError {
                     serde_json::to_string(&ServiceError::from("Invalid nonce.")).unwrap()
                 )
             }
+            Self::NonceOutOfWindow => {
+                write!(
+                    f,
+                    "{}",
+                    serde_json::to_string(&ServiceError::from(
+                        "Nonce is outside the accepted time window."
+                    ))
+                    .unwrap()
+                )
+            }
+            Self::NonceReplayed => {
+                write!(
+                    f,
+                    "{}",
+                    serde_json::to_string(&ServiceError::from("Nonce has already been used."))
+                        .unwrap()
+                )
+            }
             Self::InternalError => {
                 write!(
                     f,
@@ -77,6 +116,13 @@ impl AuthError {
                     serde_json::to_string(&ServiceError::from("User not found.")).unwrap()
                 )
             }
+            Self::DeviceNotFound => {
+                write!(
+                    f,
+                    "{}",
+                    serde_json::to_string(&ServiceError::from("Device not found.")).unwrap()
+                )
+            }
             Self::InvalidBody => {
                 write!(
                     f,
@@ -0,0 +1,39 @@
+//! secp256k1 public key and signature helpers shared by the authentication service and the
+//! device management endpoints.
+use libsecp256k1::{verify, Message, PublicKey, PublicKeyFormat, Signature};
+use sha2::{Digest, Sha256};
+
+/// Converts the hex public key to the `0x`-prefixed upper-case form that is used in storage.
+pub(crate) fn normalize_public_key_hex(public_key_hex: &str) -> String {
+    format!(
+        "0x{}",
+        public_key_hex.trim_start_matches("0x").to_uppercase()
+    )
+}
+
+/// Parses a compressed public key from its hex representation.
+pub(crate) fn parse_public_key(public_key_hex: &str) -> Option<PublicKey> {
+    hex::decode(public_key_hex.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| PublicKey::parse_slice(&bytes, Some(PublicKeyFormat::Compressed)).ok())
+}
+
+/// Parses a DER-encoded signature from its hex representation.
+pub(crate) fn parse_signature(signature_hex: &str) -> Option<Signature> {
+    hex::decode(signature_hex.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| Signature::parse_der(&bytes).ok())
+}
+
+/// Verifies the signature of the SHA-256 hash of the message.
+pub(crate) fn verify_signature(
+    message: &str,
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> bool {
+    let mut hasher = Sha256::new();
+    hasher.update(message.as_bytes());
+    let hash = hasher.finalize();
+    let message = Message::parse_slice(&hash).unwrap();
+    verify(&message, signature, public_key)
+}
@@ -1,4 +1,5 @@
 //! Application REST service authentication services and data.
 pub mod data;
 mod error;
+pub(crate) mod key;
 pub mod service;
@@ -1,78 +1,83 @@
 //! Authentication service and factory (`Transform`).
 use crate::auth::error::AuthError;
-use crate::{metrics, ServiceState};
+use crate::auth::key::{
+    normalize_public_key_hex, parse_public_key, parse_signature, verify_signature,
+};
+use crate::{metrics, ServiceState, CONFIG};
 use actix_http::h1::Payload;
 use actix_web::web::{BytesMut, Data};
 use actix_web::{
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
-    Error, HttpMessage,
+    Error, HttpMessage, ResponseError,
 };
 use futures::future::{ready, LocalBoxFuture, Ready};
 use futures::{FutureExt, StreamExt};
-use libsecp256k1::{verify, Message, PublicKey, PublicKeyFormat, Signature};
-use sha2::{Digest, Sha256};
 use std::rc::Rc;
-use subvt_types::app::User;
+use subvt_persistence::redis::Redis;
+use subvt_types::app::{User, UserDevice};
+
+/// Nonces below this value cannot be millisecond timestamps of the current era (it is the
+/// timestamp of 2001-09-09), and are the nonces of the clients that predate timestamp nonces.
+const MIN_TIMESTAMP_NONCE: u64 = 1_000_000_000_000;
+
+/// Checks whether the nonce, a millisecond timestamp, is within the given number of seconds of
+/// the current time in milliseconds.
+fn is_nonce_in_window(nonce: u64, now: u64, window_secs: u64) -> bool {
+    nonce.abs_diff(now) <= window_secs * 1000
+}
 
 pub struct AuthService<S> {
     service: Rc<S>,
 }
 
 impl<S> AuthService<S> {
-    async fn authenticate(request: &mut ServiceRequest) -> Result<(), Error> {
+    async fn authenticate(request: &mut ServiceRequest) -> Result<(), AuthError> {
         if !request.path().starts_with("/secure") {
             return Ok(());
         }
         let postgres = if let Some(state) = request.app_data::<Data<ServiceState>>() {
             state.postgres.clone()
         } else {
-            return Err(AuthError::InternalError.into());
+            return Err(AuthError::InternalError);
+        };
+        let redis = if let Some(redis) = request.app_data::<Data<Redis>>() {
+            redis.clone()
+        } else {
+            return Err(AuthError::InternalError);
         };
         let public_key_header = if let Some(header) = request.headers().get("SubVT-Public-Key") {
             header
         } else {
-            return Err(AuthError::PublicKeyMissing.into());
+            return Err(AuthError::PublicKeyMissing);
         };
         let signature_header = if let Some(header) = request.headers().get("SubVT-Signature") {
             header
         } else {
-            return Err(AuthError::SignatureMissing.into());
+            return Err(AuthError::SignatureMissing);
         };
         let nonce_header = if let Some(header) = request.headers().get("SubVT-Nonce") {
             header
         } else {
-            return Err(AuthError::NonceMissing.into());
+            return Err(AuthError::NonceMissing);
         };
         // extract public key
         let public_key_hex = if let Ok(public_key_hex) = public_key_header.to_str() {
-            format!(
-                "0x{}",
-                public_key_hex.trim_start_matches("0x").to_uppercase()
-            )
+            normalize_public_key_hex(public_key_hex)
         } else {
-            return Err(AuthError::InvalidPublicKey.into());
+            return Err(AuthError::InvalidPublicKey);
         };
-        let public_key = if let Some(public_key) =
-            hex::decode(public_key_hex.trim_start_matches("0x"))
-                .ok()
-                .and_then(|bytes| {
-                    PublicKey::parse_slice(&bytes, Some(PublicKeyFormat::Compressed)).ok()
-                }) {
+        let public_key = if let Some(public_key) = parse_public_key(&public_key_hex) {
             public_key
         } else {
-            return Err(AuthError::InvalidPublicKey.into());
+            return Err(AuthError::InvalidPublicKey);
         };
         // extract signature
-        let signature = if let Some(signature) = signature_header
-            .to_str()
-            .ok()
-            .and_then(|hex| hex::decode(hex.trim_start_matches("0x")).ok())
-            .and_then(|bytes| Signature::parse_der(&bytes).ok())
-        {
-            signature
-        } else {
-            return Err(AuthError::InvalidSignature.into());
-        };
+        let signature =
+            if let Some(signature) = signature_header.to_str().ok().and_then(parse_signature) {
+                signature
+            } else {
+                return Err(AuthError::InvalidSignature);
+            };
         // extract nonce
         let nonce = if let Some(nonce) = nonce_header
             .to_str()
@@ -81,17 +86,38 @@ impl<S> AuthService<S> {
         {
             nonce
         } else {
-            return Err(AuthError::InvalidNonce.into());
+            return Err(AuthError::InvalidNonce);
+        };
+        // a nonce is remembered for replay protection for as long as it can be accepted
+        let now = chrono::Utc::now();
+        let nonce_expiry_secs = if nonce >= MIN_TIMESTAMP_NONCE {
+            if !is_nonce_in_window(
+                nonce,
+                now.timestamp_millis() as u64,
+                CONFIG.app_service.auth_nonce_window_secs as u64,
+            ) {
+                return Err(AuthError::NonceOutOfWindow);
+            }
+            // after twice the window, a replayed request would be out of the window anyway
+            2 * CONFIG.app_service.auth_nonce_window_secs as u64
+        } else {
+            // legacy nonce, accepted until the end of the deprecation period
+            let now_secs = now.timestamp() as u64;
+            if now_secs >= CONFIG.app_service.auth_legacy_nonce_deadline {
+                return Err(AuthError::InvalidNonce);
+            }
+            log::debug!("Accepting legacy nonce {nonce} for key {public_key_hex}.");
+            CONFIG.app_service.auth_legacy_nonce_deadline - now_secs
         };
         // extract body
         let mut request_body = BytesMut::new();
         while let Some(chunk) = request.take_payload().next().await {
-            request_body.extend_from_slice(&chunk?);
+            request_body.extend_from_slice(&chunk.map_err(|_| AuthError::InvalidBody)?);
         }
         let body = if let Ok(body) = String::from_utf8(request_body.to_vec()) {
             body
         } else {
-            return Err(AuthError::InvalidBody.into());
+            return Err(AuthError::InvalidBody);
         };
         let mut original_payload = Payload::create(true).1;
         original_payload.unread_data(request_body.freeze());
@@ -104,22 +130,42 @@ impl<S> AuthService<S> {
             body,
             nonce
         );
-        let mut hasher = Sha256::new();
-        hasher.update(message_to_sign.as_bytes());
-        let hash = hasher.finalize();
-        let message = Message::parse_slice(&hash).unwrap();
-        if !verify(&message, &signature, &public_key) {
-            return Err(AuthError::InvalidSignature.into());
+        if !verify_signature(&message_to_sign, &signature, &public_key) {
+            return Err(AuthError::InvalidSignature);
         }
-        // find user and insert into context (if exists)
-        if let Ok(maybe_user) = postgres.get_user_by_public_key(&public_key_hex).await {
-            if let Some(user) = maybe_user {
-                request.extensions_mut().insert::<User>(user);
-            } else if request.path() != "/secure/user" {
-                return Err(AuthError::UserNotFound.into());
+        // reject replays
+        match redis
+            .add_app_service_auth_nonce(&public_key_hex, nonce, nonce_expiry_secs)
+            .await
+        {
+            Ok(true) => (),
+            Ok(false) => return Err(AuthError::NonceReplayed),
+            Err(error) => {
+                log::error!("Cannot save auth nonce to Redis: {:?}", error);
+                return Err(AuthError::InternalError);
+            }
+        }
+        // find the user device and insert it along with the user into context (if exists)
+        if let Ok(maybe_device) = postgres
+            .get_user_device_by_public_key(&public_key_hex)
+            .await
+        {
+            if let Some(device) = maybe_device {
+                request.extensions_mut().insert::<User>(User {
+                    id: device.user_id,
+                    public_key_hex: Some(device.public_key_hex.clone()),
+                });
+                request.extensions_mut().insert::<UserDevice>(device);
+            } else if !(request.path() == "/secure/user"
+                || request.path().starts_with("/secure/user/device/link/")
+                || request.path().starts_with("/secure/user/sign_in"))
+            {
+                // an unregistered key can only create a user, or link itself to or sign in to
+                // an existing one
+                return Err(AuthError::UserNotFound);
             }
         } else {
-            return Err(AuthError::InternalError.into());
+            return Err(AuthError::InternalError);
         };
         Ok(())
     }
@@ -142,11 +188,12 @@ where
         let service = self.service.clone();
         async move {
             if let Err(auth_error) = Self::authenticate(&mut request).await {
-                let status_code = auth_error.as_response_error().status_code();
+                let status_code = auth_error.status_code();
+                metrics::auth_rejection_counter(auth_error.get_metric_label()).inc();
                 metrics::response_time_ms().observe(start.elapsed().as_millis() as f64);
                 metrics::response_status_code_counter(status_code.as_str()).inc();
                 metrics::connection_count().dec();
-                return Err(auth_error);
+                return Err(auth_error.into());
             }
             return match service.call(request).await {
                 Ok(response) => {
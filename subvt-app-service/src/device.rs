@@ -0,0 +1,222 @@
+//! User device services. A user can access their validators and notification rules from
+//! multiple devices, each with its own secp256k1 key:
+//!
+//! - A registered device creates a one-time link code (displayed to the user as a QR code, etc.).
+//! - The new device submits the code in a request signed by its own key, which both approves
+//!   the link and proves that the new device owns the key.
+//! - A device can be revoked by any device of the same user, as long as it's not the last one.
+//! - A device can rotate its key. The new key signs the concatenation of the current and the new
+//!   public key hex strings, to prove its ownership.
+use crate::auth::data::{AuthenticatedDevice, AuthenticatedUser};
+use crate::auth::key::{
+    normalize_public_key_hex, parse_public_key, parse_signature, verify_signature,
+};
+use crate::{IdPathParameter, ResultResponse, ServiceState, CONFIG};
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+use rand::Rng;
+use serde::Deserialize;
+use subvt_types::app::{UserDevice, UserDeviceLink};
+use subvt_types::err::ServiceError;
+use utoipa::{IntoParams, ToSchema};
+
+const LINK_CODE_BYTE_COUNT: usize = 16;
+
+/// `GET`s the list of the user's active (non-revoked) devices.
+#[utoipa::path(
+    get,
+    path = "/secure/user/device",
+    tag = "device",
+    responses((status = 200, description = "Active devices of the user.", body = [UserDevice])),
+)]
+#[get("/secure/user/device")]
+pub async fn get_user_devices(
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    Ok(HttpResponse::Ok().json(state.postgres.get_user_devices(auth.id).await?))
+}
+
+/// Creates a one-time code for a new device to link itself to the user's account.
+#[utoipa::path(
+    post,
+    path = "/secure/user/device/link",
+    tag = "device",
+    responses((status = 201, description = "Link code created.", body = UserDeviceLink)),
+)]
+#[post("/secure/user/device/link")]
+pub async fn create_user_device_link(
+    state: web::Data<ServiceState>,
+    device: AuthenticatedDevice,
+) -> ResultResponse {
+    let code = hex::encode(rand::thread_rng().gen::<[u8; LINK_CODE_BYTE_COUNT]>());
+    let link = state
+        .postgres
+        .save_user_device_link(
+            &device,
+            &code,
+            CONFIG.app_service.device_link_code_expiry_mins,
+        )
+        .await?;
+    Ok(HttpResponse::Created().json(link))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct LinkCodePathParameter {
+    pub code: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct LinkUserDeviceRequest {
+    pub name: Option<String>,
+}
+
+/// Links the device that has signed the request to the user who created the link code.
+#[utoipa::path(
+    post,
+    path = "/secure/user/device/link/{code}",
+    tag = "device",
+    params(LinkCodePathParameter),
+    request_body = LinkUserDeviceRequest,
+    responses(
+        (status = 201, description = "Device linked.", body = UserDevice),
+        (status = 404, description = "Link code not found or expired.", body = ServiceError),
+        (status = 409, description = "Device exists with the public key.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/device/link/{code}")]
+pub async fn link_user_device(
+    path_params: web::Path<LinkCodePathParameter>,
+    input: web::Json<LinkUserDeviceRequest>,
+    state: web::Data<ServiceState>,
+    request: HttpRequest,
+) -> ResultResponse {
+    // the header is validated by the authentication service
+    let public_key_hex = normalize_public_key_hex(
+        request
+            .headers()
+            .get("SubVT-Public-Key")
+            .unwrap()
+            .to_str()
+            .unwrap(),
+    );
+    if state
+        .postgres
+        .user_device_exists_by_public_key(&public_key_hex)
+        .await?
+    {
+        return Ok(HttpResponse::Conflict().json(ServiceError::from(
+            "A device exists with the given public key.",
+        )));
+    }
+    match state
+        .postgres
+        .link_user_device(&path_params.code, &public_key_hex, input.name.as_deref())
+        .await?
+    {
+        Some(device) => Ok(HttpResponse::Created().json(device)),
+        None => Ok(HttpResponse::NotFound()
+            .json(ServiceError::from("Device link code not found or expired."))),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RotateUserDeviceKeyRequest {
+    pub public_key_hex: String,
+    pub signature: String,
+}
+
+/// Replaces the key of the device that has signed the request with a new key. The old key
+/// stops working immediately.
+#[utoipa::path(
+    post,
+    path = "/secure/user/device/rotate",
+    tag = "device",
+    request_body = RotateUserDeviceKeyRequest,
+    responses(
+        (status = 201, description = "Device key rotated.", body = UserDevice),
+        (status = 400, description = "Invalid public key or signature.", body = ServiceError),
+        (status = 409, description = "Device exists with the public key.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/device/rotate")]
+pub async fn rotate_user_device_key(
+    input: web::Json<RotateUserDeviceKeyRequest>,
+    state: web::Data<ServiceState>,
+    device: AuthenticatedDevice,
+) -> ResultResponse {
+    let new_public_key_hex = normalize_public_key_hex(&input.public_key_hex);
+    let new_public_key = if let Some(public_key) = parse_public_key(&new_public_key_hex) {
+        public_key
+    } else {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Invalid public key.")));
+    };
+    let signature = if let Some(signature) = parse_signature(&input.signature) {
+        signature
+    } else {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Invalid signature.")));
+    };
+    if !verify_signature(
+        &format!("{}{}", device.public_key_hex, new_public_key_hex),
+        &signature,
+        &new_public_key,
+    ) {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Invalid signature.")));
+    }
+    if state
+        .postgres
+        .user_device_exists_by_public_key(&new_public_key_hex)
+        .await?
+    {
+        return Ok(HttpResponse::Conflict().json(ServiceError::from(
+            "A device exists with the given public key.",
+        )));
+    }
+    match state
+        .postgres
+        .rotate_user_device_key(&device, &new_public_key_hex)
+        .await?
+    {
+        Some(device) => Ok(HttpResponse::Created().json(device)),
+        None => Ok(HttpResponse::NotFound().json(ServiceError::from("Device not found."))),
+    }
+}
+
+/// Revokes one of the user's devices. The last active device of the user cannot be revoked,
+/// the user should be deleted instead.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/device/{id}",
+    tag = "device",
+    params(IdPathParameter),
+    responses(
+        (status = 204, description = "Device revoked."),
+        (status = 404, description = "User device not found.", body = ServiceError),
+        (status = 409, description = "Device is the only device of the user.", body = ServiceError),
+    ),
+)]
+#[delete("/secure/user/device/{id}")]
+pub async fn revoke_user_device(
+    path_params: web::Path<IdPathParameter>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    if !state
+        .postgres
+        .user_device_exists(auth.id, path_params.id)
+        .await?
+    {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from("User device not found.")));
+    }
+    // the revocation is skipped if the device is the only one, checked in the same statement
+    match state
+        .postgres
+        .revoke_user_device(auth.id, path_params.id)
+        .await?
+    {
+        true => Ok(HttpResponse::NoContent().finish()),
+        false => Ok(HttpResponse::Conflict().json(ServiceError::from(
+            "Cannot revoke the only device of the user.",
+        ))),
+    }
+}
@@ -1,8 +1,12 @@
 //! Application REST interface. Contains services such as user registration, network list,
 //! notification channels, user validator registration, user notification rules persistence
-//! and deletion, etc.
+//! and deletion, user device linking and key rotation, validator ownership verification, sign-in
+//! with a validator stash account, etc.
+//! The OpenAPI specification of the interface is served at `/openapi.json`.
 #![warn(clippy::disallowed_types)]
-use crate::auth::{data::AuthenticatedUser, service::AuthServiceFactory};
+use crate::auth::{
+    data::AuthenticatedUser, key::normalize_public_key_hex, service::AuthServiceFactory,
+};
 use actix_web::{delete, get, post, web, App, HttpRequest, HttpResponse, HttpServer};
 use async_trait::async_trait;
 use lazy_static::lazy_static;
@@ -11,17 +15,31 @@ use serde::Deserialize;
 use std::sync::Arc;
 use subvt_config::Config;
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::redis::Redis;
+use subvt_service_common::rate_limit::{get_client_ip, http::RateLimitFactory, RateLimiter};
 use subvt_service_common::{err::InternalServerError, Service};
 use subvt_types::app::{
     notification::{
-        NotificationPeriodType, UserNotificationChannel, UserNotificationRuleParameter,
+        NotificationChannel, NotificationPeriodType, NotificationType, UserNotificationChannel,
+        UserNotificationRule, UserNotificationRuleParameter,
     },
-    User, UserValidator,
+    Network, User, UserDataExport, UserValidator,
 };
 use subvt_types::err::ServiceError;
+use utoipa::{IntoParams, ToSchema};
 
-mod auth;
+pub mod auth;
+pub mod device;
 pub(crate) mod metrics;
+pub mod openapi;
+pub(crate) mod purge;
+pub(crate) mod rule;
+pub mod rule_template;
+pub mod sign_in;
+pub mod team;
+pub mod validator_import;
+pub mod validator_ownership;
+pub mod validator_telemetry;
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
@@ -35,31 +53,68 @@ pub struct ServiceState {
 }
 
 /// `GET`s the list of networks supported by SubVT.
+#[utoipa::path(
+    get,
+    path = "/network",
+    tag = "network",
+    responses((status = 200, description = "Supported networks.", body = [Network])),
+)]
 #[get("/network")]
 pub async fn get_networks(state: web::Data<ServiceState>) -> ResultResponse {
     Ok(HttpResponse::Ok().json(state.postgres.get_networks().await?))
 }
 
 /// `GET`s the list of supported notification channels, such as email, push notification, SMS, etc.
+#[utoipa::path(
+    get,
+    path = "/notification/channel",
+    tag = "notification",
+    responses((status = 200, description = "Supported notification channels.", body = [NotificationChannel])),
+)]
 #[get("/notification/channel")]
 async fn get_notification_channels(state: web::Data<ServiceState>) -> ResultResponse {
     Ok(HttpResponse::Ok().json(state.postgres.get_notification_channels().await?))
 }
 
 /// `GET`s the list of notification types and their parameters supported by SubVT.
+#[utoipa::path(
+    get,
+    path = "/notification/type",
+    tag = "notification",
+    responses((status = 200, description = "Supported notification types.", body = [NotificationType])),
+)]
 #[get("/notification/type")]
 async fn get_notification_types(state: web::Data<ServiceState>) -> ResultResponse {
     Ok(HttpResponse::Ok().json(state.postgres.get_notification_types().await?))
 }
 
 /// Validates and creates a new user.
+#[utoipa::path(
+    post,
+    path = "/secure/user",
+    tag = "user",
+    responses(
+        (status = 201, description = "User created.", body = User),
+        (status = 409, description = "Public key exists or is revoked.", body = ServiceError),
+        (status = 429, description = "Too many users created from the IP address.", body = ServiceError),
+    ),
+)]
 #[post("/secure/user")]
-async fn create_user(state: web::Data<ServiceState>, request: HttpRequest) -> ResultResponse {
+pub async fn create_user(state: web::Data<ServiceState>, request: HttpRequest) -> ResultResponse {
     // rate limit per IP address
-    let maybe_registration_ip = request
-        .connection_info()
-        .realip_remote_addr()
-        .map(str::to_string);
+    let maybe_registration_ip = request.peer_addr().map(|peer_addr| {
+        get_client_ip(
+            peer_addr.ip(),
+            &CONFIG.rate_limit.get_trusted_proxy_ips(),
+            |name| {
+                request
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            },
+        )
+        .to_string()
+    });
     if let Some(registration_ip) = &maybe_registration_ip {
         let count = state
             .postgres
@@ -71,17 +126,14 @@ async fn create_user(state: web::Data<ServiceState>, request: HttpRequest) -> Re
             )));
         }
     }
-    let public_key_hex = request
-        .headers()
-        .get("SubVT-Public-Key")
-        .unwrap()
-        .to_str()
-        .unwrap();
-    let public_key_hex = if !public_key_hex.starts_with("0x") {
-        format!("0x{}", public_key_hex.to_uppercase())
-    } else {
-        public_key_hex.to_uppercase()
-    };
+    let public_key_hex = normalize_public_key_hex(
+        request
+            .headers()
+            .get("SubVT-Public-Key")
+            .unwrap()
+            .to_str()
+            .unwrap(),
+    );
     // check duplicate public key
     if state
         .postgres
@@ -92,18 +144,88 @@ async fn create_user(state: web::Data<ServiceState>, request: HttpRequest) -> Re
             "A user exists with the given public key.",
         )));
     }
+    // revoked and rotated device keys cannot be used again
+    if state
+        .postgres
+        .user_device_is_revoked_by_public_key(&public_key_hex)
+        .await?
+    {
+        return Ok(HttpResponse::Conflict()
+            .json(ServiceError::from("The given public key has been revoked.")));
+    }
     let mut user = User {
         id: 0,
         public_key_hex: Some(public_key_hex),
     };
-    user.id = state
+    user.id = match state
         .postgres
         .save_user(&user, maybe_registration_ip.as_deref())
-        .await?;
+        .await?
+    {
+        Some(user_id) => user_id,
+        None => {
+            return Ok(HttpResponse::Conflict().json(ServiceError::from(
+                "The given public key belongs to another user.",
+            )))
+        }
+    };
     Ok(HttpResponse::Created().json(user))
 }
 
+/// `GET`s all the data of the user as a single JSON document: devices, notification channels,
+/// validators, notification rules with their parameters, and the notification history.
+#[utoipa::path(
+    get,
+    path = "/secure/user/export",
+    tag = "user",
+    responses((status = 200, description = "All the data of the user.", body = UserDataExport)),
+)]
+#[get("/secure/user/export")]
+pub async fn export_user_data(
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    Ok(HttpResponse::Ok().json(UserDataExport {
+        user: User::clone(&auth),
+        devices: state.postgres.get_user_devices(auth.id).await?,
+        notification_channels: state
+            .postgres
+            .get_user_notification_channels(auth.id)
+            .await?,
+        validators: state.postgres.get_user_validators(auth.id).await?,
+        notification_rules: state.postgres.get_user_notification_rules(auth.id).await?,
+        notifications: state.postgres.get_user_notifications(auth.id).await?,
+        exported_at: chrono::Utc::now().naive_utc(),
+    }))
+}
+
+/// Deletes the user. The user's data is kept for the retention period, and then purged
+/// permanently.
+#[utoipa::path(
+    delete,
+    path = "/secure/user",
+    tag = "user",
+    responses((status = 204, description = "User deleted.")),
+)]
+#[delete("/secure/user")]
+pub async fn delete_user(
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    match state.postgres.delete_user(auth.id).await? {
+        true => Ok(HttpResponse::NoContent().finish()),
+        false => Ok(HttpResponse::InternalServerError()
+            .json(ServiceError::from("There was an error deleting the user."))),
+    }
+}
+
 /// `GET`s the list of notification channels that the user has created for herself so far.
+#[utoipa::path(
+    get,
+    path = "/secure/user/notification/channel",
+    tag = "notification",
+    responses((status = 200, description = "User notification channels.", body = [UserNotificationChannel])),
+)]
 #[get("/secure/user/notification/channel")]
 async fn get_user_notification_channels(
     state: web::Data<ServiceState>,
@@ -118,6 +240,18 @@ async fn get_user_notification_channels(
 }
 
 /// Creates a new notification channel for the user.
+#[utoipa::path(
+    post,
+    path = "/secure/user/notification/channel",
+    tag = "notification",
+    request_body = UserNotificationChannel,
+    responses(
+        (status = 201, description = "Notification channel created.", body = UserNotificationChannel),
+        (status = 400, description = "Invalid notification target.", body = ServiceError),
+        (status = 404, description = "Notification channel not found.", body = ServiceError),
+        (status = 409, description = "Target exists for the user.", body = ServiceError),
+    ),
+)]
 #[post("/secure/user/notification/channel")]
 async fn add_user_notification_channel(
     mut input: web::Json<UserNotificationChannel>,
@@ -170,13 +304,24 @@ async fn add_user_notification_channel(
     Ok(HttpResponse::Created().json(input))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 struct IdPathParameter {
     pub id: u32,
 }
 
 /// `DELETE`s the notification channel from the user's list of notification channels.
 /// A soft delete, but the user will no longer receive notifications on this channel.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/notification/channel/{id}",
+    tag = "notification",
+    params(IdPathParameter),
+    responses(
+        (status = 204, description = "Notification channel deleted."),
+        (status = 404, description = "User notification channel not found.", body = ServiceError),
+    ),
+)]
 #[delete("/secure/user/notification/channel/{id}")]
 async fn delete_user_notification_channel(
     path_params: web::Path<IdPathParameter>,
@@ -203,23 +348,46 @@ async fn delete_user_notification_channel(
     }
 }
 
-/// `GET`s the list of all validators registered to the user.
+/// `GET`s the list of all validators registered to the user, and to the user's teams.
+#[utoipa::path(
+    get,
+    path = "/secure/user/validator",
+    tag = "validator",
+    responses((status = 200, description = "User and team validators.", body = [UserValidator])),
+)]
 #[get("/secure/user/validator")]
-async fn get_user_validators(
+pub async fn get_user_validators(
     state: web::Data<ServiceState>,
     auth: AuthenticatedUser,
 ) -> ResultResponse {
-    Ok(HttpResponse::Ok().json(state.postgres.get_user_validators(auth.id).await?))
+    let mut user_validators = state.postgres.get_user_validators(auth.id).await?;
+    user_validators.extend(state.postgres.get_user_team_validators(auth.id).await?);
+    Ok(HttpResponse::Ok().json(user_validators))
 }
 
-/// Adds a new validator to the user's list of validators.
+/// Adds a new validator to the user's list of validators, or to the team's list of validators
+/// if a team id is given.
+#[utoipa::path(
+    post,
+    path = "/secure/user/validator",
+    tag = "validator",
+    request_body = UserValidator,
+    responses(
+        (status = 201, description = "Validator added.", body = UserValidator),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "Network or team not found.", body = ServiceError),
+        (status = 409, description = "User validator exists.", body = ServiceError),
+    ),
+)]
 #[post("/secure/user/validator")]
-async fn add_user_validator(
+pub async fn add_user_validator(
     mut input: web::Json<UserValidator>,
     state: web::Data<ServiceState>,
     auth: AuthenticatedUser,
 ) -> ResultResponse {
     input.user_id = auth.id;
+    // ownership can only be verified through the challenge
+    input.is_ownership_verified = false;
     // check network exists
     if !state
         .postgres
@@ -228,6 +396,18 @@ async fn add_user_validator(
     {
         return Ok(HttpResponse::NotFound().json(ServiceError::from("Network not found.")));
     }
+    // check team access
+    if let Some(team_id) = input.team_id {
+        match state.postgres.get_user_team_role(auth.id, team_id).await? {
+            None => return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found."))),
+            Some(role) if !role.can_edit() => {
+                return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+                    "Only team owners and editors can edit team validators.",
+                )))
+            }
+            _ => (),
+        }
+    }
     // check user validator exists
     if state.postgres.user_validator_exists(&input).await? {
         return Ok(HttpResponse::Conflict().json(ServiceError::from("User validator exists.")));
@@ -236,21 +416,39 @@ async fn add_user_validator(
     Ok(HttpResponse::Created().json(input))
 }
 
-/// `DELETE`s a validator from the user's list of validators.
+/// `DELETE`s a validator from the user's or the team's list of validators.
 /// A soft delete, i.e. only marks the validator as deleted.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/validator/{id}",
+    tag = "validator",
+    params(IdPathParameter),
+    responses(
+        (status = 204, description = "Validator deleted."),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "User validator not found.", body = ServiceError),
+    ),
+)]
 #[delete("/secure/user/validator/{id}")]
-async fn delete_user_validator(
+pub async fn delete_user_validator(
     path_params: web::Path<IdPathParameter>,
     state: web::Data<ServiceState>,
     auth: AuthenticatedUser,
 ) -> ResultResponse {
     // check validator exists
-    if !state
+    let user_validator = if let Some(user_validator) = state
         .postgres
-        .user_validator_exists_by_id(auth.id, path_params.id)
+        .get_user_validator_by_id(auth.id, path_params.id)
         .await?
     {
+        user_validator
+    } else {
         return Ok(HttpResponse::NotFound().json(ServiceError::from("User validator not found.")));
+    };
+    if !team::can_edit(&state, auth.id, user_validator.team_id).await? {
+        return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+            "Only team owners and editors can edit team validators.",
+        )));
     }
     match state.postgres.delete_user_validator(path_params.id).await? {
         true => Ok(HttpResponse::NoContent().finish()),
@@ -260,11 +458,18 @@ async fn delete_user_validator(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateDefaultUserNotificationRulesRequest {
     pub user_notification_channel_id: u32,
 }
 
+#[utoipa::path(
+    post,
+    path = "/secure/user/notification/rule/default",
+    tag = "notification_rule",
+    request_body = CreateDefaultUserNotificationRulesRequest,
+    responses((status = 204, description = "Default rules created.")),
+)]
 #[post("/secure/user/notification/rule/default")]
 async fn create_default_user_notification_rules(
     input: web::Json<CreateDefaultUserNotificationRulesRequest>,
@@ -277,7 +482,7 @@ async fn create_default_user_notification_rules(
         state
             .postgres
             .save_user_notification_rule(
-                auth.id,
+                (auth.id, None),
                 &rule.0.to_string(),
                 (None, None),
                 (None, true),
@@ -289,18 +494,35 @@ async fn create_default_user_notification_rules(
     Ok(HttpResponse::NoContent().finish())
 }
 
-/// `GET`s the list of the user's non-deleted notification rules.
+/// `GET`s the list of the user's and the user's teams' non-deleted notification rules.
+#[utoipa::path(
+    get,
+    path = "/secure/user/notification/rule",
+    tag = "notification_rule",
+    responses((status = 200, description = "User and team notification rules.", body = [UserNotificationRule])),
+)]
 #[get("/secure/user/notification/rule")]
-async fn get_user_notification_rules(
+pub async fn get_user_notification_rules(
     state: web::Data<ServiceState>,
     auth: AuthenticatedUser,
 ) -> ResultResponse {
-    Ok(HttpResponse::Ok().json(state.postgres.get_user_notification_rules(auth.id).await?))
+    let mut rules = state.postgres.get_user_notification_rules(auth.id).await?;
+    rules.extend(
+        state
+            .postgres
+            .get_user_team_notification_rules(auth.id)
+            .await?,
+    );
+    Ok(HttpResponse::Ok().json(rules))
 }
 
-#[derive(Deserialize)]
-struct CreateUserNotificationRuleRequest {
+#[derive(Deserialize, ToSchema)]
+pub struct CreateUserNotificationRuleRequest {
     pub notification_type_code: String,
+    /// Creates a team rule when set. Team rules can only select the team's validators, and have
+    /// no channels of their own.
+    #[serde(default)]
+    pub team_id: Option<u32>,
     pub name: Option<String>,
     pub network_id: Option<u32>,
     pub is_for_all_validators: bool,
@@ -312,28 +534,34 @@ struct CreateUserNotificationRuleRequest {
     pub notes: Option<String>,
 }
 
-/// Creates a new notification rule for the user. The new rule starts getting evaluated for possible
-/// notifications as soon as it gets created.
+/// Creates a new notification rule for the user or the team. The new rule starts getting evaluated
+/// for possible notifications as soon as it gets created.
+#[utoipa::path(
+    post,
+    path = "/secure/user/notification/rule",
+    tag = "notification_rule",
+    request_body = CreateUserNotificationRuleRequest,
+    responses(
+        (status = 201, description = "Notification rule created.", body = UserNotificationRule),
+        (status = 400, description = "Invalid rule.", body = ServiceError),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "Network, team, validator or channel not found.", body = ServiceError),
+    ),
+)]
 #[post("/secure/user/notification/rule")]
-async fn create_user_notification_rule(
+pub async fn create_user_notification_rule(
     mut input: web::Json<CreateUserNotificationRuleRequest>,
     state: web::Data<ServiceState>,
     auth: AuthenticatedUser,
 ) -> ResultResponse {
-    // check notification type exists
-    if let Some(notification_type) = state
-        .postgres
-        .get_notification_type_by_code(&input.notification_type_code)
-        .await?
+    if let Err((status_code, description)) = rule::validate_notification_type_and_parameters(
+        &state,
+        &input.notification_type_code,
+        &input.parameters,
+    )
+    .await?
     {
-        if !notification_type.is_enabled {
-            return Ok(HttpResponse::BadRequest()
-                .json(ServiceError::from("Notification type is not enabled.")));
-        }
-    } else {
-        return Ok(
-            HttpResponse::NotFound().json(ServiceError::from("Notification type not found."))
-        );
+        return Ok(HttpResponse::build(status_code).json(ServiceError::from(&description)));
     }
     // check network exists
     if let Some(network_id) = input.network_id {
@@ -341,6 +569,18 @@ async fn create_user_notification_rule(
             return Ok(HttpResponse::NotFound().json(ServiceError::from("Network not found.")));
         }
     }
+    // check team access
+    if let Some(team_id) = input.team_id {
+        match state.postgres.get_user_team_role(auth.id, team_id).await? {
+            None => return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found."))),
+            Some(role) if !role.can_edit() => {
+                return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+                    "Only team owners and editors can edit team notification rules.",
+                )))
+            }
+            _ => (),
+        }
+    }
     // check validators
     if input.is_for_all_validators {
         input.user_validator_ids.clear();
@@ -349,98 +589,29 @@ async fn create_user_notification_rule(
             "At least 1 user validator should be selected.",
         )));
     }
-    for user_validator_id in &input.user_validator_ids {
-        if !state
-            .postgres
-            .user_validator_exists_by_id(auth.id, *user_validator_id)
-            .await?
-        {
-            return Ok(
-                HttpResponse::NotFound().json(ServiceError::from("User validator not found."))
-            );
-        }
-    }
-    // check if there is at least one notification channel
-    if input.user_notification_channel_ids.is_empty() {
-        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
-            "There should be at least 1 notification channel selected.",
-        )));
-    }
-    // check user notification channel ids
-    for user_notification_channel_id in &input.user_notification_channel_ids {
-        if !state
-            .postgres
-            .user_notification_channel_exists(auth.id, *user_notification_channel_id)
-            .await?
-        {
-            return Ok(HttpResponse::NotFound()
-                .json(ServiceError::from("User notification channel not found.")));
-        }
-    }
-    let notification_parameter_types = state
-        .postgres
-        .get_notification_parameter_types(&input.notification_type_code)
-        .await?;
-    let notification_parameter_type_ids: Vec<u32> = notification_parameter_types
-        .iter()
-        .map(|parameter_type| parameter_type.id)
-        .collect();
-    let irrelevant_parameter_type_ids: Vec<u32> = input
-        .parameters
-        .iter()
-        .map(|parameter| parameter.parameter_type_id)
-        .filter(|id| !notification_parameter_type_ids.contains(id))
-        .collect();
-    if !irrelevant_parameter_type_ids.is_empty() {
-        return Ok(HttpResponse::NotFound().json(ServiceError::from(
-            format!(
-                "Posted parameter(s) with id(s) {:?} not found for notification type '{}'.",
-                irrelevant_parameter_type_ids, input.notification_type_code
-            )
-            .as_ref(),
-        )));
-    }
-    let posted_parameter_type_ids: Vec<u32> = input
-        .parameters
-        .iter()
-        .map(|parameter| parameter.parameter_type_id)
-        .collect();
-    // check if all non-optional parameters are sent
-    let missing_non_optional_parameter_type_ids: Vec<u32> = notification_parameter_types
-        .iter()
-        .filter(|parameter_type| {
-            !parameter_type.is_optional && !posted_parameter_type_ids.contains(&parameter_type.id)
-        })
-        .map(|parameter_type| parameter_type.id)
-        .collect();
-    if !missing_non_optional_parameter_type_ids.is_empty() {
-        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
-            format!(
-                "Missing non-optional parameter type ids: {missing_non_optional_parameter_type_ids:?}",
-            )
-            .as_ref(),
-        )));
+    if let Err((status_code, description)) = rule::validate_rule_validators(
+        &state,
+        (auth.id, input.team_id),
+        &input.notification_type_code,
+        &input.user_validator_ids,
+    )
+    .await?
+    {
+        return Ok(HttpResponse::build(status_code).json(ServiceError::from(&description)));
     }
-    // validate parameters
-    for parameter in &input.parameters {
-        let parameter_type = notification_parameter_types
-            .iter()
-            .find(|parameter_type| parameter_type.id == parameter.parameter_type_id)
-            .unwrap();
-        if let (false, Some(validation_error_message)) = parameter.validate(parameter_type) {
-            return Ok(HttpResponse::BadRequest().json(ServiceError::from(
-                format!(
-                    "Invalid '{}': {validation_error_message}",
-                    parameter_type.code,
-                )
-                .as_ref(),
-            )));
-        }
+    if let Err((status_code, description)) = rule::validate_rule_channels(
+        &state,
+        (auth.id, input.team_id),
+        &input.user_notification_channel_ids,
+    )
+    .await?
+    {
+        return Ok(HttpResponse::build(status_code).json(ServiceError::from(&description)));
     }
     let rule_id = state
         .postgres
         .save_user_notification_rule(
-            auth.id,
+            (auth.id, input.team_id),
             &input.notification_type_code,
             (input.name.as_deref(), input.notes.as_deref()),
             (input.network_id, input.is_for_all_validators),
@@ -461,24 +632,42 @@ async fn create_user_notification_rule(
     ))
 }
 
-/// `DELETE` a rule from the list of the user's notification rules.
+/// `DELETE` a rule from the list of the user's or the team's notification rules.
 /// A soft delete, the rule will not be able to generate new notifications as soon as
 /// it gets deleted.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/notification/rule/{id}",
+    tag = "notification_rule",
+    params(IdPathParameter),
+    responses(
+        (status = 204, description = "Notification rule deleted."),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "User notification rule not found.", body = ServiceError),
+    ),
+)]
 #[delete("/secure/user/notification/rule/{id}")]
-async fn delete_user_notification_rule(
+pub async fn delete_user_notification_rule(
     path_params: web::Path<IdPathParameter>,
     state: web::Data<ServiceState>,
     auth: AuthenticatedUser,
 ) -> ResultResponse {
     // check rule exists
-    if !state
+    let rule = if let Some(rule) = state
         .postgres
-        .user_notification_rule_exists_by_id(auth.id, path_params.id)
+        .get_user_accessible_notification_rule_by_id(auth.id, path_params.id)
         .await?
     {
+        rule
+    } else {
         return Ok(
             HttpResponse::NotFound().json(ServiceError::from("User notification rule not found."))
         );
+    };
+    if !team::can_edit(&state, auth.id, rule.team_id).await? {
+        return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+            "Only team owners and editors can edit team notification rules.",
+        )));
     }
     match state
         .postgres
@@ -513,12 +702,17 @@ impl Service for AppService {
         // persistence instance
         let postgres =
             Arc::new(PostgreSQLAppStorage::new(&CONFIG, CONFIG.get_app_postgres_url()).await?);
+        // nonce store for replay protection, and validator ownership challenge store
+        let redis = Arc::new(Redis::new()?);
+        let rate_limiter = RateLimiter::new(&CONFIG, "subvt_app_service").await?;
+        tokio::spawn(purge::run_purge_job(postgres.clone()));
         log::debug!("Starting HTTP service.");
         let server = HttpServer::new(move || {
             App::new()
                 .app_data(web::Data::new(ServiceState {
                     postgres: postgres.clone(),
                 }))
+                .app_data(web::Data::from(redis.clone()))
                 .app_data(web::JsonConfig::default().error_handler(|err, _req| {
                     actix_web::error::InternalError::from_response(
                         "",
@@ -527,21 +721,54 @@ impl Service for AppService {
                     )
                     .into()
                 }))
+                // the public key budget is checked after the authentication middleware has
+                // verified the key, and the IP address and global budgets before it, so that
+                // floods are rejected before the signatures are verified
+                .wrap(RateLimitFactory::per_key(rate_limiter.clone(), &[]))
                 .wrap(AuthServiceFactory {})
+                .wrap(RateLimitFactory::new(rate_limiter.clone(), &[]))
                 .service(get_networks)
                 .service(get_notification_channels)
                 .service(get_notification_types)
                 .service(create_user)
+                .service(export_user_data)
+                .service(delete_user)
                 .service(add_user_notification_channel)
                 .service(get_user_notification_channels)
                 .service(delete_user_notification_channel)
                 .service(get_user_validators)
                 .service(add_user_validator)
+                .service(validator_import::import_user_validators)
                 .service(delete_user_validator)
                 .service(create_user_notification_rule)
                 .service(get_user_notification_rules)
                 .service(delete_user_notification_rule)
                 .service(create_default_user_notification_rules)
+                .service(rule_template::get_user_notification_rule_templates)
+                .service(rule_template::create_user_notification_rule_template)
+                .service(rule_template::delete_user_notification_rule_template)
+                .service(rule_template::apply_user_notification_rule_template)
+                .service(device::get_user_devices)
+                .service(device::create_user_device_link)
+                .service(device::link_user_device)
+                .service(device::rotate_user_device_key)
+                .service(device::revoke_user_device)
+                .service(sign_in::create_user_sign_in_challenge)
+                .service(sign_in::sign_in_user)
+                .service(validator_ownership::create_user_validator_ownership_challenge)
+                .service(validator_ownership::verify_user_validator_ownership)
+                .service(validator_telemetry::set_user_validator_telemetry_override)
+                .service(validator_telemetry::delete_user_validator_telemetry_override)
+                .service(team::create_team)
+                .service(team::get_user_teams)
+                .service(team::get_team_members)
+                .service(team::save_team_member)
+                .service(team::create_team_invitation)
+                .service(team::get_user_team_invitations)
+                .service(team::accept_team_invitation)
+                .service(team::delete_team_invitation)
+                .service(team::delete_team_member)
+                .service(openapi::get_openapi_spec)
         })
         .workers(10)
         .disable_signals()
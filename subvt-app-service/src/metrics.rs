@@ -55,3 +55,16 @@ pub(crate) fn response_status_code_counter(status_code: &str) -> IntCounter {
     });
     METER.with_label_values(&[status_code])
 }
+
+pub(crate) fn auth_rejection_counter(reason: &str) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter_vec(
+            METRIC_PREFIX,
+            "auth_rejection_count",
+            "The number of rejected authentication attempts",
+            &["reason"],
+        )
+        .unwrap()
+    });
+    METER.with_label_values(&[reason])
+}
@@ -0,0 +1,195 @@
+//! OpenAPI 3 specification of the application REST interface, generated from the handler
+//! annotations and served at `/openapi.json`. All `/secure` operations require signed requests,
+//! which is documented by `SignedRequestAddon` rather than on each handler.
+use crate::{
+    device, rule_template, sign_in, team, validator_import, validator_ownership,
+    validator_telemetry, CreateDefaultUserNotificationRulesRequest,
+    CreateUserNotificationRuleRequest,
+};
+use actix_web::{get, HttpResponse};
+use subvt_types::app::{
+    notification::{
+        Notification, NotificationChannel, NotificationParamDataType, NotificationParamType,
+        NotificationPeriodType, NotificationType, UserNotificationChannel, UserNotificationRule,
+        UserNotificationRuleParameter, UserNotificationRuleTemplate,
+        UserNotificationRuleTemplateItem,
+    },
+    team::{Team, TeamInvitation, TeamMember, TeamRole},
+    Network, User, UserDataExport, UserDevice, UserDeviceLink, UserSignInChallenge, UserValidator,
+    UserValidatorOwnershipChallenge,
+};
+use subvt_types::crypto::AccountId;
+use subvt_types::err::{BulkItemError, BulkServiceError, ServiceError};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityRequirement, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Ref, ResponseBuilder};
+use utoipa::{Modify, OpenApi};
+
+const SIGNED_REQUEST_SCHEME: &str = "signed_request";
+
+/// Documents the request signature scheme of the `/secure` operations, along with the response
+/// to requests that fail authentication.
+struct SignedRequestAddon;
+
+impl Modify for SignedRequestAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                SIGNED_REQUEST_SCHEME,
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                    "SubVT-Signature",
+                    "secp256k1 signature of the concatenation of the request method, path, body \
+                    and nonce, by the device key. The request should also have the \
+                    `SubVT-Public-Key` header with the public key hex of the device, and the \
+                    `SubVT-Nonce` header with the current timestamp in milliseconds. Nonces \
+                    that are not millisecond timestamps are deprecated.",
+                ))),
+            );
+        }
+        for (path, path_item) in openapi.paths.paths.iter_mut() {
+            if !path.starts_with("/secure") {
+                continue;
+            }
+            for operation in path_item.operations.values_mut() {
+                operation.security = Some(vec![SecurityRequirement::new(
+                    SIGNED_REQUEST_SCHEME,
+                    Vec::<String>::new(),
+                )]);
+                operation
+                    .responses
+                    .responses
+                    .entry("403".to_string())
+                    .or_insert_with(|| {
+                        ResponseBuilder::new()
+                            .description("Missing or invalid request signature.")
+                            .content(
+                                "application/json",
+                                ContentBuilder::new()
+                                    .schema(Ref::from_schema_name("ServiceError"))
+                                    .build(),
+                            )
+                            .build()
+                            .into()
+                    });
+            }
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "SubVT App Service",
+        description = "User, notification channel, validator, notification rule, team and \
+            device management for the SubVT applications.",
+    ),
+    paths(
+        get_openapi_spec,
+        crate::get_networks,
+        crate::get_notification_channels,
+        crate::get_notification_types,
+        crate::create_user,
+        crate::export_user_data,
+        crate::delete_user,
+        crate::get_user_notification_channels,
+        crate::add_user_notification_channel,
+        crate::delete_user_notification_channel,
+        crate::get_user_validators,
+        crate::add_user_validator,
+        crate::delete_user_validator,
+        crate::create_default_user_notification_rules,
+        crate::get_user_notification_rules,
+        crate::create_user_notification_rule,
+        crate::delete_user_notification_rule,
+        validator_import::import_user_validators,
+        validator_ownership::create_user_validator_ownership_challenge,
+        validator_ownership::verify_user_validator_ownership,
+        validator_telemetry::set_user_validator_telemetry_override,
+        validator_telemetry::delete_user_validator_telemetry_override,
+        rule_template::get_user_notification_rule_templates,
+        rule_template::create_user_notification_rule_template,
+        rule_template::delete_user_notification_rule_template,
+        rule_template::apply_user_notification_rule_template,
+        team::create_team,
+        team::get_user_teams,
+        team::get_team_members,
+        team::save_team_member,
+        team::create_team_invitation,
+        team::get_user_team_invitations,
+        team::accept_team_invitation,
+        team::delete_team_invitation,
+        team::delete_team_member,
+        device::get_user_devices,
+        device::create_user_device_link,
+        device::link_user_device,
+        device::rotate_user_device_key,
+        device::revoke_user_device,
+        sign_in::create_user_sign_in_challenge,
+        sign_in::sign_in_user,
+    ),
+    components(schemas(
+        AccountId,
+        BulkItemError,
+        BulkServiceError,
+        Network,
+        Notification,
+        NotificationChannel,
+        NotificationParamDataType,
+        NotificationParamType,
+        NotificationPeriodType,
+        NotificationType,
+        ServiceError,
+        Team,
+        TeamInvitation,
+        TeamMember,
+        TeamRole,
+        User,
+        UserDataExport,
+        UserDevice,
+        UserDeviceLink,
+        UserSignInChallenge,
+        UserNotificationChannel,
+        UserNotificationRule,
+        UserNotificationRuleParameter,
+        UserNotificationRuleTemplate,
+        UserNotificationRuleTemplateItem,
+        UserValidator,
+        UserValidatorOwnershipChallenge,
+        CreateDefaultUserNotificationRulesRequest,
+        CreateUserNotificationRuleRequest,
+        device::LinkUserDeviceRequest,
+        device::RotateUserDeviceKeyRequest,
+        rule_template::ApplyUserNotificationRuleTemplateRequest,
+        rule_template::CreateUserNotificationRuleTemplateRequest,
+        sign_in::CreateUserSignInChallengeRequest,
+        sign_in::SignInUserRequest,
+        team::CreateTeamRequest,
+        team::SaveTeamMemberRequest,
+        validator_import::ImportUserValidatorsRequest,
+        validator_ownership::VerifyUserValidatorOwnershipRequest,
+        validator_telemetry::SetUserValidatorTelemetryOverrideRequest,
+    )),
+    tags(
+        (name = "network", description = "Networks supported by SubVT."),
+        (name = "notification", description = "Notification channels and types."),
+        (name = "user", description = "User registration, data export and deletion."),
+        (name = "validator", description = "Validators of the user and the user's teams."),
+        (name = "notification_rule", description = "Notification rules and rule templates."),
+        (name = "team", description = "Teams sharing validators and notification rules."),
+        (name = "device", description = "Devices of the user and their keys."),
+        (name = "openapi", description = "This specification."),
+    ),
+    modifiers(&SignedRequestAddon),
+)]
+pub struct ApiDoc;
+
+/// `GET`s the OpenAPI 3 specification of the service.
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "openapi",
+    responses((status = 200, description = "OpenAPI specification.", content_type = "application/json")),
+)]
+#[get("/openapi.json")]
+pub async fn get_openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
@@ -0,0 +1,28 @@
+//! Permanent deletion of the soft-deleted users. Users with Telegram notification channels are
+//! purged by the Telegram bot along with their chats.
+use crate::CONFIG;
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+
+const PURGE_PERIOD_SECONDS: u64 = 60 * 60 * 24;
+
+async fn purge_deleted_users(postgres: &PostgreSQLAppStorage) -> anyhow::Result<()> {
+    let user_ids = postgres
+        .get_purgeable_non_telegram_user_ids(CONFIG.app_service.deleted_user_retention_days)
+        .await?;
+    for user_id in &user_ids {
+        postgres.purge_user(*user_id).await?;
+    }
+    log::info!("Purged {} deleted users.", user_ids.len());
+    Ok(())
+}
+
+/// Runs the purge once a day.
+pub(crate) async fn run_purge_job(postgres: Arc<PostgreSQLAppStorage>) {
+    loop {
+        if let Err(error) = purge_deleted_users(&postgres).await {
+            log::error!("Error while purging deleted users: {:?}", error);
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(PURGE_PERIOD_SECONDS)).await;
+    }
+}
@@ -0,0 +1,178 @@
+//! Notification rule validation, shared by the single rule creation and the rule template
+//! services. Each check results in the status code and the description of the first error.
+use crate::ServiceState;
+use actix_web::http::StatusCode;
+use rustc_hash::FxHashSet as HashSet;
+use subvt_types::app::notification::{NotificationTypeCode, UserNotificationRuleParameter};
+
+pub(crate) type RuleValidationResult = Result<(), (StatusCode, String)>;
+
+/// Checks whether the notification type exists and is enabled, and the parameters are valid
+/// for the notification type.
+pub(crate) async fn validate_notification_type_and_parameters(
+    state: &ServiceState,
+    notification_type_code: &str,
+    parameters: &[UserNotificationRuleParameter],
+) -> anyhow::Result<RuleValidationResult> {
+    // check notification type exists
+    if let Some(notification_type) = state
+        .postgres
+        .get_notification_type_by_code(notification_type_code)
+        .await?
+    {
+        if !notification_type.is_enabled {
+            return Ok(Err((
+                StatusCode::BAD_REQUEST,
+                "Notification type is not enabled.".to_string(),
+            )));
+        }
+    } else {
+        return Ok(Err((
+            StatusCode::NOT_FOUND,
+            "Notification type not found.".to_string(),
+        )));
+    }
+    let notification_parameter_types = state
+        .postgres
+        .get_notification_parameter_types(notification_type_code)
+        .await?;
+    let notification_parameter_type_ids: Vec<u32> = notification_parameter_types
+        .iter()
+        .map(|parameter_type| parameter_type.id)
+        .collect();
+    let irrelevant_parameter_type_ids: Vec<u32> = parameters
+        .iter()
+        .map(|parameter| parameter.parameter_type_id)
+        .filter(|id| !notification_parameter_type_ids.contains(id))
+        .collect();
+    if !irrelevant_parameter_type_ids.is_empty() {
+        return Ok(Err((
+            StatusCode::NOT_FOUND,
+            format!(
+                "Posted parameter(s) with id(s) {irrelevant_parameter_type_ids:?} not found for notification type '{notification_type_code}'.",
+            ),
+        )));
+    }
+    let posted_parameter_type_ids: Vec<u32> = parameters
+        .iter()
+        .map(|parameter| parameter.parameter_type_id)
+        .collect();
+    // check if all non-optional parameters are sent
+    let missing_non_optional_parameter_type_ids: Vec<u32> = notification_parameter_types
+        .iter()
+        .filter(|parameter_type| {
+            !parameter_type.is_optional && !posted_parameter_type_ids.contains(&parameter_type.id)
+        })
+        .map(|parameter_type| parameter_type.id)
+        .collect();
+    if !missing_non_optional_parameter_type_ids.is_empty() {
+        return Ok(Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Missing non-optional parameter type ids: {missing_non_optional_parameter_type_ids:?}",
+            ),
+        )));
+    }
+    // validate parameters
+    for parameter in parameters {
+        let parameter_type = notification_parameter_types
+            .iter()
+            .find(|parameter_type| parameter_type.id == parameter.parameter_type_id)
+            .unwrap();
+        if let (false, Some(validation_error_message)) = parameter.validate(parameter_type) {
+            return Ok(Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Invalid '{}': {validation_error_message}",
+                    parameter_type.code,
+                ),
+            )));
+        }
+    }
+    Ok(Ok(()))
+}
+
+/// Checks whether the validators belong to the owner of the rule, i.e. the user or the team, and
+/// are ownership-verified for operator-only notification types. An empty validator set means that
+/// the rule is for all validators.
+pub(crate) async fn validate_rule_validators(
+    state: &ServiceState,
+    (user_id, team_id): (u32, Option<u32>),
+    notification_type_code: &str,
+    user_validator_ids: &HashSet<u32>,
+) -> anyhow::Result<RuleValidationResult> {
+    // rules can only select validators of the same owner
+    for user_validator_id in user_validator_ids {
+        let user_validator_team_id = state
+            .postgres
+            .get_user_validator_by_id(user_id, *user_validator_id)
+            .await?
+            .map(|user_validator| user_validator.team_id);
+        if user_validator_team_id != Some(team_id) {
+            return Ok(Err((
+                StatusCode::NOT_FOUND,
+                "User validator not found.".to_string(),
+            )));
+        }
+    }
+    // operator-only notification types need selected ownership-verified validators
+    if NotificationTypeCode::from(notification_type_code).is_operator_only() {
+        if user_validator_ids.is_empty() {
+            return Ok(Err((
+                StatusCode::BAD_REQUEST,
+                "Operator-only notification types cannot be set for all validators.".to_string(),
+            )));
+        }
+        for user_validator_id in user_validator_ids {
+            let is_ownership_verified = state
+                .postgres
+                .get_user_validator_by_id(user_id, *user_validator_id)
+                .await?
+                .map(|user_validator| user_validator.is_ownership_verified)
+                .unwrap_or(false);
+            if !is_ownership_verified {
+                return Ok(Err((
+                    StatusCode::FORBIDDEN,
+                    "Validator ownership should be verified for operator-only notification types."
+                        .to_string(),
+                )));
+            }
+        }
+    }
+    Ok(Ok(()))
+}
+
+/// Checks whether the channels belong to the user. Personal rules need at least one channel,
+/// and team rules cannot have channels, as they're delivered to the channels of all members.
+pub(crate) async fn validate_rule_channels(
+    state: &ServiceState,
+    (user_id, team_id): (u32, Option<u32>),
+    user_notification_channel_ids: &HashSet<u32>,
+) -> anyhow::Result<RuleValidationResult> {
+    if team_id.is_some() {
+        if !user_notification_channel_ids.is_empty() {
+            return Ok(Err((
+                StatusCode::BAD_REQUEST,
+                "Team notification rules cannot have notification channels.".to_string(),
+            )));
+        }
+    } else if user_notification_channel_ids.is_empty() {
+        return Ok(Err((
+            StatusCode::BAD_REQUEST,
+            "There should be at least 1 notification channel selected.".to_string(),
+        )));
+    }
+    for user_notification_channel_id in user_notification_channel_ids {
+        if !state
+            .postgres
+            .user_notification_channel_exists(user_id, *user_notification_channel_id)
+            .await?
+        {
+            return Ok(Err((
+                StatusCode::NOT_FOUND,
+                "User notification channel not found.".to_string(),
+            )));
+        }
+    }
+    Ok(Ok(()))
+}
@@ -0,0 +1,308 @@
+//! Notification rule template services. A template is a named set of notification rules without
+//! validators or channels. Applying a template to a set of validators creates all of its rules
+//! in a single transaction, for the user or for one of the user's teams. Invalid rules are
+//! reported individually, and no rules get created if any rule is invalid.
+use crate::auth::data::AuthenticatedUser;
+use crate::{rule, IdPathParameter, ResultResponse, ServiceState};
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, web, HttpResponse};
+use rustc_hash::FxHashSet as HashSet;
+use serde::Deserialize;
+use subvt_types::app::notification::{
+    UserNotificationRule, UserNotificationRuleTemplate, UserNotificationRuleTemplateItem,
+};
+use subvt_types::err::{BulkItemError, BulkServiceError, ServiceError};
+use utoipa::ToSchema;
+
+/// `GET`s the list of the user's notification rule templates.
+#[utoipa::path(
+    get,
+    path = "/secure/user/notification/rule/template",
+    tag = "notification_rule",
+    responses((status = 200, description = "Notification rule templates of the user.", body = [UserNotificationRuleTemplate])),
+)]
+#[get("/secure/user/notification/rule/template")]
+pub async fn get_user_notification_rule_templates(
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    Ok(HttpResponse::Ok().json(
+        state
+            .postgres
+            .get_user_notification_rule_templates(auth.id)
+            .await?,
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateUserNotificationRuleTemplateRequest {
+    pub name: String,
+    pub rules: Vec<UserNotificationRuleTemplateItem>,
+}
+
+/// Creates a new notification rule template for the user.
+#[utoipa::path(
+    post,
+    path = "/secure/user/notification/rule/template",
+    tag = "notification_rule",
+    request_body = CreateUserNotificationRuleTemplateRequest,
+    responses(
+        (status = 201, description = "Template created.", body = UserNotificationRuleTemplate),
+        (status = 400, description = "Invalid template or template rules.", body = BulkServiceError),
+    ),
+)]
+#[post("/secure/user/notification/rule/template")]
+pub async fn create_user_notification_rule_template(
+    input: web::Json<CreateUserNotificationRuleTemplateRequest>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Template name is empty.")));
+    }
+    if input.rules.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+            "There should be at least 1 rule in the template.",
+        )));
+    }
+    let mut item_errors = Vec::new();
+    let mut notification_type_codes = HashSet::default();
+    for (index, item) in input.rules.iter().enumerate() {
+        let result = if !notification_type_codes.insert(&item.notification_type_code) {
+            Err("Duplicate notification type.".to_string())
+        } else {
+            rule::validate_notification_type_and_parameters(
+                &state,
+                &item.notification_type_code,
+                &item.parameters,
+            )
+            .await?
+            .map_err(|(_, description)| description)
+        };
+        if let Err(description) = result {
+            item_errors.push(BulkItemError {
+                index,
+                item: item.notification_type_code.clone(),
+                description,
+            });
+        }
+    }
+    if !item_errors.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(BulkServiceError {
+            description: "Invalid notification rules.".to_string(),
+            item_errors,
+        }));
+    }
+    let mut template = UserNotificationRuleTemplate {
+        id: 0,
+        user_id: auth.id,
+        name: name.to_string(),
+        rules: input.rules.clone(),
+    };
+    template.id = state
+        .postgres
+        .save_user_notification_rule_template(&template)
+        .await?;
+    Ok(HttpResponse::Created().json(template))
+}
+
+/// `DELETE`s a notification rule template of the user. The rules created from the template
+/// are not affected.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/notification/rule/template/{id}",
+    tag = "notification_rule",
+    params(IdPathParameter),
+    responses(
+        (status = 204, description = "Template deleted."),
+        (status = 404, description = "Template not found.", body = ServiceError),
+    ),
+)]
+#[delete("/secure/user/notification/rule/template/{id}")]
+pub async fn delete_user_notification_rule_template(
+    path_params: web::Path<IdPathParameter>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    match state
+        .postgres
+        .delete_user_notification_rule_template(auth.id, path_params.id)
+        .await?
+    {
+        true => Ok(HttpResponse::NoContent().finish()),
+        false => Ok(HttpResponse::NotFound()
+            .json(ServiceError::from("Notification rule template not found."))),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ApplyUserNotificationRuleTemplateRequest {
+    pub network_id: Option<u32>,
+    /// Creates team rules when set.
+    #[serde(default)]
+    pub team_id: Option<u32>,
+    /// The rules are for all validators when empty.
+    #[serde(default)]
+    pub user_validator_ids: HashSet<u32>,
+    /// Should be empty for team rules.
+    #[serde(default)]
+    pub user_notification_channel_ids: HashSet<u32>,
+}
+
+/// Whether the existing rule is for the same network, validators and parameters as the rule to
+/// be created from the template item. A template can be applied to other validators, or with
+/// other parameters, alongside the existing rules of the same type.
+fn is_same_rule(
+    rule: &UserNotificationRule,
+    item: &UserNotificationRuleTemplateItem,
+    input: &ApplyUserNotificationRuleTemplateRequest,
+) -> bool {
+    let validator_ids: HashSet<u32> = rule
+        .validators
+        .iter()
+        .map(|validator| validator.id)
+        .collect();
+    let parameters: HashSet<(u32, &str)> = rule
+        .parameters
+        .iter()
+        .map(|parameter| (parameter.parameter_type_id, parameter.value.as_str()))
+        .collect();
+    let item_parameters: HashSet<(u32, &str)> = item
+        .parameters
+        .iter()
+        .map(|parameter| (parameter.parameter_type_id, parameter.value.as_str()))
+        .collect();
+    rule.network.as_ref().map(|network| network.id) == input.network_id
+        && rule.is_for_all_validators == input.user_validator_ids.is_empty()
+        && validator_ids == input.user_validator_ids
+        && parameters == item_parameters
+}
+
+/// Creates the rules of the template for the given validators and channels, in a single
+/// transaction. Returns the created rules.
+#[utoipa::path(
+    post,
+    path = "/secure/user/notification/rule/template/{id}/apply",
+    tag = "notification_rule",
+    params(IdPathParameter),
+    request_body = ApplyUserNotificationRuleTemplateRequest,
+    responses(
+        (status = 201, description = "Rules created from the template.", body = [UserNotificationRule]),
+        (status = 400, description = "Template rules cannot be created.", body = BulkServiceError),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "Template, network or team not found.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/notification/rule/template/{id}/apply")]
+pub async fn apply_user_notification_rule_template(
+    path_params: web::Path<IdPathParameter>,
+    input: web::Json<ApplyUserNotificationRuleTemplateRequest>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let template = if let Some(template) = state
+        .postgres
+        .get_user_notification_rule_template_by_id(auth.id, path_params.id)
+        .await?
+    {
+        template
+    } else {
+        return Ok(HttpResponse::NotFound()
+            .json(ServiceError::from("Notification rule template not found.")));
+    };
+    // check network exists
+    if let Some(network_id) = input.network_id {
+        if !state.postgres.network_exists_by_id(network_id).await? {
+            return Ok(HttpResponse::NotFound().json(ServiceError::from("Network not found.")));
+        }
+    }
+    // check team access
+    if let Some(team_id) = input.team_id {
+        match state.postgres.get_user_team_role(auth.id, team_id).await? {
+            None => return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found."))),
+            Some(role) if !role.can_edit() => {
+                return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+                    "Only team owners and editors can edit team notification rules.",
+                )))
+            }
+            _ => (),
+        }
+    }
+    let owner = (auth.id, input.team_id);
+    if let Err((status_code, description)) =
+        rule::validate_rule_channels(&state, owner, &input.user_notification_channel_ids).await?
+    {
+        return Ok(HttpResponse::build(status_code).json(ServiceError::from(&description)));
+    }
+    // validate each rule against the current notification types and the selected validators
+    let mut item_errors = Vec::new();
+    for (index, item) in template.rules.iter().enumerate() {
+        let mut result = rule::validate_notification_type_and_parameters(
+            &state,
+            &item.notification_type_code,
+            &item.parameters,
+        )
+        .await?;
+        if result.is_ok() {
+            result = rule::validate_rule_validators(
+                &state,
+                owner,
+                &item.notification_type_code,
+                &input.user_validator_ids,
+            )
+            .await?;
+        }
+        if result.is_ok()
+            && state
+                .postgres
+                .get_user_notification_rules_by_type(owner, &item.notification_type_code)
+                .await?
+                .iter()
+                .any(|rule| is_same_rule(rule, item, &input))
+        {
+            result = Err((
+                StatusCode::CONFLICT,
+                "A notification rule exists for the notification type, network, validators and \
+                parameters."
+                    .to_string(),
+            ));
+        }
+        if let Err((_, description)) = result {
+            item_errors.push(BulkItemError {
+                index,
+                item: item.notification_type_code.clone(),
+                description,
+            });
+        }
+    }
+    if !item_errors.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(BulkServiceError {
+            description: "Invalid notification rules.".to_string(),
+            item_errors,
+        }));
+    }
+    let rule_ids = state
+        .postgres
+        .save_user_notification_rules_from_template(
+            owner,
+            &template,
+            input.network_id,
+            (
+                &input.user_validator_ids,
+                &input.user_notification_channel_ids,
+            ),
+        )
+        .await?;
+    let mut rules = Vec::new();
+    for rule_id in rule_ids {
+        if let Some(rule) = state
+            .postgres
+            .get_user_notification_rule_by_id(rule_id)
+            .await?
+        {
+            rules.push(rule);
+        }
+    }
+    Ok(HttpResponse::Created().json(rules))
+}
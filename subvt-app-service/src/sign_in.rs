@@ -0,0 +1,163 @@
+//! Sign-in with a Substrate account (sr25519 or ed25519). A user who has verified the ownership
+//! of a validator can sign a new device in to their account with the validator stash account,
+//! instead of linking it from a registered device:
+//!
+//! - The new device requests a challenge for the stash account, in a request signed by its own
+//!   key. The challenge is bound to the key of the device.
+//! - The new device submits the signature of the challenge by the stash account, and is added
+//!   to the user who has verified the ownership of a validator with the account.
+use crate::auth::key::normalize_public_key_hex;
+use crate::{ResultResponse, ServiceState, CONFIG};
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use rand::Rng;
+use serde::Deserialize;
+use subvt_persistence::redis::Redis;
+use subvt_types::app::{UserDevice, UserSignInChallenge};
+use subvt_types::crypto::AccountId;
+use subvt_types::err::ServiceError;
+use utoipa::ToSchema;
+
+const CHALLENGE_NONCE_BYTE_COUNT: usize = 16;
+
+fn get_public_key_hex(request: &HttpRequest) -> String {
+    // the header is validated by the authentication service
+    normalize_public_key_hex(
+        request
+            .headers()
+            .get("SubVT-Public-Key")
+            .unwrap()
+            .to_str()
+            .unwrap(),
+    )
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateUserSignInChallengeRequest {
+    /// SS58 address or hex of the validator stash account.
+    pub account_id: AccountId,
+}
+
+/// Creates a new sign-in challenge for the stash account to sign, for the device that has
+/// signed the request. Replaces the earlier challenge of the device, if any.
+#[utoipa::path(
+    post,
+    path = "/secure/user/sign_in/challenge",
+    tag = "device",
+    request_body = CreateUserSignInChallengeRequest,
+    responses(
+        (status = 201, description = "Challenge to be signed by the stash account.", body = UserSignInChallenge),
+        (status = 409, description = "Device exists with the public key.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/sign_in/challenge")]
+pub async fn create_user_sign_in_challenge(
+    input: web::Json<CreateUserSignInChallengeRequest>,
+    state: web::Data<ServiceState>,
+    redis: web::Data<Redis>,
+    request: HttpRequest,
+) -> ResultResponse {
+    let public_key_hex = get_public_key_hex(&request);
+    if state
+        .postgres
+        .user_device_exists_by_public_key(&public_key_hex)
+        .await?
+    {
+        return Ok(HttpResponse::Conflict().json(ServiceError::from(
+            "A device exists with the given public key.",
+        )));
+    }
+    let expiry_mins = CONFIG.app_service.sign_in_challenge_expiry_mins;
+    let challenge = UserSignInChallenge {
+        account_id: input.account_id,
+        challenge: format!(
+            "SubVT sign-in for {} with device {}: {}",
+            input.account_id.to_ss58_check(),
+            public_key_hex,
+            hex::encode(rand::thread_rng().gen::<[u8; CHALLENGE_NONCE_BYTE_COUNT]>()),
+        ),
+        expires_at: chrono::Utc::now().naive_utc() + chrono::Duration::minutes(expiry_mins as i64),
+    };
+    redis
+        .set_app_service_sign_in_challenge(&public_key_hex, &challenge, expiry_mins as u64 * 60)
+        .await?;
+    Ok(HttpResponse::Created().json(challenge))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SignInUserRequest {
+    /// Hex-encoded sr25519 or ed25519 signature of the challenge by the stash account.
+    pub signature: String,
+    /// Name of the new device.
+    pub name: Option<String>,
+}
+
+/// Verifies the signature of the device's sign-in challenge by the stash account, and adds the
+/// device to the user who has verified the ownership of a validator with the account. A
+/// challenge can be used only once, whether the signature is valid or not.
+#[utoipa::path(
+    post,
+    path = "/secure/user/sign_in",
+    tag = "device",
+    request_body = SignInUserRequest,
+    responses(
+        (status = 201, description = "Signed in, device added.", body = UserDevice),
+        (status = 400, description = "Invalid signature.", body = ServiceError),
+        (status = 404, description = "Challenge or user with a verified validator not found.", body = ServiceError),
+        (status = 409, description = "Device exists with the public key.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/sign_in")]
+pub async fn sign_in_user(
+    input: web::Json<SignInUserRequest>,
+    state: web::Data<ServiceState>,
+    redis: web::Data<Redis>,
+    request: HttpRequest,
+) -> ResultResponse {
+    let public_key_hex = get_public_key_hex(&request);
+    if state
+        .postgres
+        .user_device_exists_by_public_key(&public_key_hex)
+        .await?
+    {
+        return Ok(HttpResponse::Conflict().json(ServiceError::from(
+            "A device exists with the given public key.",
+        )));
+    }
+    let challenge = if let Some(challenge) = redis
+        .take_app_service_sign_in_challenge(&public_key_hex)
+        .await?
+    {
+        challenge
+    } else {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from(
+            "Sign-in challenge not found or expired.",
+        )));
+    };
+    let signature = if let Ok(signature) = hex::decode(input.signature.trim_start_matches("0x")) {
+        signature
+    } else {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Invalid signature.")));
+    };
+    if !challenge
+        .account_id
+        .verify_signature(challenge.challenge.as_bytes(), &signature)
+    {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Invalid signature.")));
+    }
+    let user_id = if let Some(user_id) = state
+        .postgres
+        .get_user_id_by_verified_validator_account_id(&challenge.account_id)
+        .await?
+    {
+        user_id
+    } else {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from(
+            "No user has verified the ownership of a validator with the account.",
+        )));
+    };
+    let device = state
+        .postgres
+        .save_user_device(user_id, &public_key_hex, input.name.as_deref())
+        .await?;
+    Ok(HttpResponse::Created().json(device))
+}
@@ -0,0 +1,379 @@
+//! Team services. Users can form teams to share validators and notification rules, instead of
+//! adding the same validators and rules individually:
+//!
+//! - Viewers can see the team's validators, rules and members, and receive team notifications.
+//! - Editors can also add and delete the team's validators and rules.
+//! - Owners can also invite users, and update and remove team members. A team always has at
+//!   least one owner. Invited users become members only after accepting the invitation.
+//!
+//! The notifications of team rules are delivered to the notification channels of every member.
+use crate::auth::data::AuthenticatedUser;
+use crate::{IdPathParameter, ResultResponse, ServiceState};
+use actix_web::{delete, get, post, web, HttpResponse};
+use serde::Deserialize;
+use subvt_types::app::team::{Team, TeamInvitation, TeamMember, TeamRole};
+use subvt_types::err::ServiceError;
+use utoipa::{IntoParams, ToSchema};
+
+/// Checks whether the user can edit the validators and rules of the team. Always `true` for
+/// the user's own validators and rules, i.e. when there's no team.
+pub(crate) async fn can_edit(
+    state: &ServiceState,
+    user_id: u32,
+    maybe_team_id: Option<u32>,
+) -> anyhow::Result<bool> {
+    if let Some(team_id) = maybe_team_id {
+        Ok(state
+            .postgres
+            .get_user_team_role(user_id, team_id)
+            .await?
+            .map(|role| role.can_edit())
+            .unwrap_or(false))
+    } else {
+        Ok(true)
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateTeamRequest {
+    pub name: String,
+}
+
+/// Creates a new team with the user as its owner.
+#[utoipa::path(
+    post,
+    path = "/secure/user/team",
+    tag = "team",
+    request_body = CreateTeamRequest,
+    responses(
+        (status = 201, description = "Team created, with the user as its owner.", body = Team),
+        (status = 400, description = "Team name is empty.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/team")]
+pub async fn create_team(
+    input: web::Json<CreateTeamRequest>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Team name is empty.")));
+    }
+    Ok(HttpResponse::Created().json(state.postgres.save_team(auth.id, name).await?))
+}
+
+/// `GET`s the list of the user's teams, along with the user's role in each team.
+#[utoipa::path(
+    get,
+    path = "/secure/user/team",
+    tag = "team",
+    responses((status = 200, description = "Teams of the user.", body = [Team])),
+)]
+#[get("/secure/user/team")]
+pub async fn get_user_teams(
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    Ok(HttpResponse::Ok().json(state.postgres.get_user_teams(auth.id).await?))
+}
+
+/// `GET`s the list of the members of one of the user's teams.
+#[utoipa::path(
+    get,
+    path = "/secure/user/team/{id}/member",
+    tag = "team",
+    params(IdPathParameter),
+    responses(
+        (status = 200, description = "Team members.", body = [TeamMember]),
+        (status = 404, description = "Team not found.", body = ServiceError),
+    ),
+)]
+#[get("/secure/user/team/{id}/member")]
+pub async fn get_team_members(
+    path_params: web::Path<IdPathParameter>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    if state
+        .postgres
+        .get_user_team_role(auth.id, path_params.id)
+        .await?
+        .is_none()
+    {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found.")));
+    }
+    Ok(HttpResponse::Ok().json(state.postgres.get_team_members(path_params.id).await?))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SaveTeamMemberRequest {
+    pub user_id: u32,
+    pub role: TeamRole,
+}
+
+/// Updates the role of a team member. Only for team owners.
+#[utoipa::path(
+    post,
+    path = "/secure/user/team/{id}/member",
+    tag = "team",
+    params(IdPathParameter),
+    request_body = SaveTeamMemberRequest,
+    responses(
+        (status = 201, description = "Team member updated.", body = TeamMember),
+        (status = 403, description = "Only team owners can manage members.", body = ServiceError),
+        (status = 404, description = "Team or team member not found.", body = ServiceError),
+        (status = 409, description = "The only owner of the team cannot be demoted.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/team/{id}/member")]
+pub async fn save_team_member(
+    path_params: web::Path<IdPathParameter>,
+    input: web::Json<SaveTeamMemberRequest>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let team_id = path_params.id;
+    match state.postgres.get_user_team_role(auth.id, team_id).await? {
+        None => return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found."))),
+        Some(role) if !role.can_manage_members() => {
+            return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+                "Only team owners can manage team members.",
+            )))
+        }
+        _ => (),
+    }
+    match state
+        .postgres
+        .get_user_team_role(input.user_id, team_id)
+        .await?
+    {
+        None => {
+            return Ok(HttpResponse::NotFound().json(ServiceError::from("Team member not found.")))
+        }
+        Some(TeamRole::Owner)
+            if input.role != TeamRole::Owner
+                && state.postgres.get_team_owner_count(team_id).await? < 2 =>
+        {
+            return Ok(HttpResponse::Conflict().json(ServiceError::from(
+                "Cannot change the role of the only owner of the team.",
+            )))
+        }
+        _ => (),
+    }
+    let member = TeamMember {
+        team_id,
+        user_id: input.user_id,
+        role: input.role,
+    };
+    if !state.postgres.save_team_member(&member).await? {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from("Team member not found.")));
+    }
+    Ok(HttpResponse::Created().json(member))
+}
+
+/// Invites a user to the team with the given role, or updates the role of an earlier
+/// invitation. Only for team owners. The user becomes a member after accepting the invitation.
+#[utoipa::path(
+    post,
+    path = "/secure/user/team/{id}/invitation",
+    tag = "team",
+    params(IdPathParameter),
+    request_body = SaveTeamMemberRequest,
+    responses(
+        (status = 201, description = "User invited.", body = TeamInvitation),
+        (status = 403, description = "Only team owners can manage members.", body = ServiceError),
+        (status = 404, description = "Team or user not found.", body = ServiceError),
+        (status = 409, description = "The user is already a team member.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/team/{id}/invitation")]
+pub async fn create_team_invitation(
+    path_params: web::Path<IdPathParameter>,
+    input: web::Json<SaveTeamMemberRequest>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let team_id = path_params.id;
+    match state.postgres.get_user_team_role(auth.id, team_id).await? {
+        None => return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found."))),
+        Some(role) if !role.can_manage_members() => {
+            return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+                "Only team owners can manage team members.",
+            )))
+        }
+        _ => (),
+    }
+    if !state.postgres.user_exists_by_id(input.user_id).await? {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from("User not found.")));
+    }
+    if state
+        .postgres
+        .get_user_team_role(input.user_id, team_id)
+        .await?
+        .is_some()
+    {
+        return Ok(HttpResponse::Conflict().json(ServiceError::from(
+            "The user is already a member of the team.",
+        )));
+    }
+    let invitee = TeamMember {
+        team_id,
+        user_id: input.user_id,
+        role: input.role,
+    };
+    let invitation_id = state
+        .postgres
+        .save_team_invitation(&invitee, auth.id)
+        .await?;
+    match state
+        .postgres
+        .get_user_team_invitations(input.user_id, Some(invitation_id))
+        .await?
+        .pop()
+    {
+        Some(invitation) => Ok(HttpResponse::Created().json(invitation)),
+        None => Ok(HttpResponse::InternalServerError()
+            .json(ServiceError::from("There was an error inviting the user."))),
+    }
+}
+
+/// `GET`s the list of the user's pending team invitations.
+#[utoipa::path(
+    get,
+    path = "/secure/user/team/invitation",
+    tag = "team",
+    responses((status = 200, description = "Pending team invitations of the user.", body = [TeamInvitation])),
+)]
+#[get("/secure/user/team/invitation")]
+pub async fn get_user_team_invitations(
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    Ok(HttpResponse::Ok().json(
+        state
+            .postgres
+            .get_user_team_invitations(auth.id, None)
+            .await?,
+    ))
+}
+
+/// Accepts one of the user's team invitations, adding the user to the team.
+#[utoipa::path(
+    post,
+    path = "/secure/user/team/invitation/{id}",
+    tag = "team",
+    params(IdPathParameter),
+    responses(
+        (status = 201, description = "Invitation accepted, user added to the team.", body = TeamMember),
+        (status = 404, description = "Team invitation not found.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/team/invitation/{id}")]
+pub async fn accept_team_invitation(
+    path_params: web::Path<IdPathParameter>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    match state
+        .postgres
+        .accept_team_invitation(auth.id, path_params.id)
+        .await?
+    {
+        Some(member) => Ok(HttpResponse::Created().json(member)),
+        None => Ok(HttpResponse::NotFound().json(ServiceError::from("Team invitation not found."))),
+    }
+}
+
+/// Declines one of the user's team invitations.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/team/invitation/{id}",
+    tag = "team",
+    params(IdPathParameter),
+    responses(
+        (status = 204, description = "Invitation declined."),
+        (status = 404, description = "Team invitation not found.", body = ServiceError),
+    ),
+)]
+#[delete("/secure/user/team/invitation/{id}")]
+pub async fn delete_team_invitation(
+    path_params: web::Path<IdPathParameter>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    match state
+        .postgres
+        .delete_team_invitation(auth.id, path_params.id)
+        .await?
+    {
+        true => Ok(HttpResponse::NoContent().finish()),
+        false => {
+            Ok(HttpResponse::NotFound().json(ServiceError::from("Team invitation not found.")))
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct TeamMemberPathParameter {
+    pub id: u32,
+    pub user_id: u32,
+}
+
+/// Removes a member from the team. Owners can remove any member, and any member can leave the
+/// team. The only owner of the team cannot leave.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/team/{id}/member/{user_id}",
+    tag = "team",
+    params(TeamMemberPathParameter),
+    responses(
+        (status = 204, description = "Team member removed."),
+        (status = 403, description = "Only team owners can manage members.", body = ServiceError),
+        (status = 404, description = "Team or team member not found.", body = ServiceError),
+        (status = 409, description = "The only owner of the team cannot be removed.", body = ServiceError),
+    ),
+)]
+#[delete("/secure/user/team/{id}/member/{user_id}")]
+pub async fn delete_team_member(
+    path_params: web::Path<TeamMemberPathParameter>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let team_id = path_params.id;
+    match state.postgres.get_user_team_role(auth.id, team_id).await? {
+        None => return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found."))),
+        Some(role) if !role.can_manage_members() && path_params.user_id != auth.id => {
+            return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+                "Only team owners can manage team members.",
+            )))
+        }
+        _ => (),
+    }
+    match state
+        .postgres
+        .get_user_team_role(path_params.user_id, team_id)
+        .await?
+    {
+        None => {
+            return Ok(HttpResponse::NotFound().json(ServiceError::from("Team member not found.")))
+        }
+        Some(TeamRole::Owner) if state.postgres.get_team_owner_count(team_id).await? < 2 => {
+            return Ok(HttpResponse::Conflict().json(ServiceError::from(
+                "Cannot remove the only owner of the team.",
+            )))
+        }
+        _ => (),
+    }
+    match state
+        .postgres
+        .delete_team_member(team_id, path_params.user_id)
+        .await?
+    {
+        true => Ok(HttpResponse::NoContent().finish()),
+        false => Ok(HttpResponse::InternalServerError().json(ServiceError::from(
+            "There was an error removing the team member.",
+        ))),
+    }
+}
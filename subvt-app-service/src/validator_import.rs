@@ -0,0 +1,183 @@
+//! Bulk validator import. Adds a list of validators to the user's or the team's validators in a
+//! single transaction, either by address or by an identity parent, in which case all the
+//! validators with sub-identities of the parent get imported. Validators that are already in the
+//! list are not duplicated.
+use crate::auth::data::AuthenticatedUser;
+use crate::{ResultResponse, ServiceState};
+use actix_web::{post, web, HttpResponse};
+use rustc_hash::FxHashSet as HashSet;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+use subvt_types::app::{Network, UserValidator};
+use subvt_types::crypto::AccountId;
+use subvt_types::err::{BulkItemError, BulkServiceError, ServiceError};
+use subvt_types::report::ValidatorListReport;
+use utoipa::ToSchema;
+
+const MAX_IMPORT_VALIDATOR_COUNT: usize = 500;
+const REPORT_SERVICE_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Deserialize, ToSchema)]
+pub struct ImportUserValidatorsRequest {
+    pub network_id: u32,
+    /// Imports to the team's validators when set.
+    #[serde(default)]
+    pub team_id: Option<u32>,
+    /// SS58 addresses or hex account ids of the validators.
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// SS58 address or hex account id of an identity, whose sub-identity validators get imported.
+    pub identity_parent_address: Option<String>,
+}
+
+/// Gets the account ids of the validators that have a sub-identity of the given parent, from
+/// the validator list of the network's report service.
+async fn get_sub_identity_validator_account_ids(
+    network: &Network,
+    parent_account_id: &AccountId,
+) -> anyhow::Result<Vec<AccountId>> {
+    let (host, port) = match (
+        network.report_service_host.as_ref(),
+        network.report_service_port,
+    ) {
+        (Some(host), Some(port)) => (host, port),
+        _ => anyhow::bail!("Network {} has no report service.", network.display),
+    };
+    let report: ValidatorListReport = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REPORT_SERVICE_TIMEOUT_SECS))
+        .build()?
+        .get(format!("http://{host}:{port}/validator/list"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(report
+        .validators
+        .iter()
+        .filter(|validator| validator.parent_account_id.as_ref() == Some(parent_account_id))
+        .map(|validator| validator.account_id)
+        .collect())
+}
+
+/// Adds the validators to the user's or the team's list of validators. Returns the list of all
+/// the imported validators, including the ones that were already in the list. None of the
+/// validators get imported if any of the addresses is invalid.
+#[utoipa::path(
+    post,
+    path = "/secure/user/validator/import",
+    tag = "validator",
+    request_body = ImportUserValidatorsRequest,
+    responses(
+        (status = 201, description = "Imported validators.", body = [UserValidator]),
+        (status = 400, description = "Invalid addresses or nothing to import.", body = BulkServiceError),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "Network, team or identity not found.", body = ServiceError),
+        (status = 502, description = "Cannot read the identities from the chain.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/validator/import")]
+pub async fn import_user_validators(
+    input: web::Json<ImportUserValidatorsRequest>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    // check network exists
+    if !state
+        .postgres
+        .network_exists_by_id(input.network_id)
+        .await?
+    {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from("Network not found.")));
+    }
+    // check team access
+    if let Some(team_id) = input.team_id {
+        match state.postgres.get_user_team_role(auth.id, team_id).await? {
+            None => return Ok(HttpResponse::NotFound().json(ServiceError::from("Team not found."))),
+            Some(role) if !role.can_edit() => {
+                return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+                    "Only team owners and editors can edit team validators.",
+                )))
+            }
+            _ => (),
+        }
+    }
+    if input.addresses.is_empty() && input.identity_parent_address.is_none() {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("No validators to import.")));
+    }
+    let mut account_ids = Vec::new();
+    let mut item_errors = Vec::new();
+    for (index, address) in input.addresses.iter().enumerate() {
+        match AccountId::from_str(address) {
+            Ok(account_id) => account_ids.push(account_id),
+            Err(_) => item_errors.push(BulkItemError {
+                index,
+                item: address.clone(),
+                description: "Invalid address or account id hex.".to_string(),
+            }),
+        }
+    }
+    if !item_errors.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(BulkServiceError {
+            description: "Invalid validator addresses.".to_string(),
+            item_errors,
+        }));
+    }
+    if let Some(identity_parent_address) = &input.identity_parent_address {
+        let parent_account_id = if let Ok(account_id) = AccountId::from_str(identity_parent_address)
+        {
+            account_id
+        } else {
+            return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+                "Invalid identity parent address or account id hex.",
+            )));
+        };
+        let network = state.postgres.get_network_by_id(input.network_id).await?;
+        match get_sub_identity_validator_account_ids(&network, &parent_account_id).await {
+            Ok(sub_identity_account_ids) if sub_identity_account_ids.is_empty() => {
+                return Ok(HttpResponse::NotFound().json(ServiceError::from(
+                    "No validators found with a sub-identity of the identity parent.",
+                )))
+            }
+            Ok(sub_identity_account_ids) => account_ids.extend(sub_identity_account_ids),
+            Err(error) => {
+                log::error!("Cannot get sub-identity validators: {:?}", error);
+                return Ok(HttpResponse::BadGateway().json(ServiceError::from(
+                    "Cannot get the validator list of the network.",
+                )));
+            }
+        }
+    }
+    // remove duplicates, keeping the order
+    let mut account_id_set = HashSet::default();
+    account_ids.retain(|account_id| account_id_set.insert(*account_id));
+    if account_ids.len() > MAX_IMPORT_VALIDATOR_COUNT {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+            format!("Cannot import more than {MAX_IMPORT_VALIDATOR_COUNT} validators at once.")
+                .as_ref(),
+        )));
+    }
+    let user_validators: Vec<UserValidator> = account_ids
+        .iter()
+        .map(|account_id| UserValidator {
+            id: 0,
+            user_id: auth.id,
+            network_id: input.network_id,
+            validator_account_id: *account_id,
+            is_ownership_verified: false,
+            team_id: input.team_id,
+        })
+        .collect();
+    let ids: HashSet<u32> = state
+        .postgres
+        .save_user_validators(&user_validators)
+        .await?
+        .into_iter()
+        .collect();
+    // re-read to get the ownership verification status of the existing validators
+    let mut imported_user_validators = state.postgres.get_user_validators(auth.id).await?;
+    imported_user_validators.extend(state.postgres.get_user_team_validators(auth.id).await?);
+    imported_user_validators.retain(|user_validator| ids.contains(&user_validator.id));
+    Ok(HttpResponse::Created().json(imported_user_validators))
+}
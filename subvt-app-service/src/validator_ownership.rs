@@ -0,0 +1,155 @@
+//! Validator ownership verification services. The app keys of a user prove nothing about the
+//! validators they add, so the user can verify the ownership of a validator by signing a server
+//! challenge with the validator stash account (sr25519 or ed25519). Operator-only notification
+//! types, such as controller and session keys changes, require verified validators.
+use crate::auth::data::AuthenticatedUser;
+use crate::{team, IdPathParameter, ResultResponse, ServiceState, CONFIG};
+use actix_web::{post, web, HttpResponse};
+use rand::Rng;
+use serde::Deserialize;
+use subvt_persistence::redis::Redis;
+use subvt_types::app::{UserValidator, UserValidatorOwnershipChallenge};
+use subvt_types::err::ServiceError;
+use utoipa::ToSchema;
+
+const CHALLENGE_NONCE_BYTE_COUNT: usize = 16;
+
+/// Creates a new challenge for the validator stash account to sign. Replaces the earlier
+/// challenge of the validator, if any.
+#[utoipa::path(
+    post,
+    path = "/secure/user/validator/{id}/ownership/challenge",
+    tag = "validator",
+    params(IdPathParameter),
+    responses(
+        (status = 201, description = "Challenge to be signed by the stash account.", body = UserValidatorOwnershipChallenge),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "User validator not found.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/validator/{id}/ownership/challenge")]
+pub async fn create_user_validator_ownership_challenge(
+    path_params: web::Path<IdPathParameter>,
+    state: web::Data<ServiceState>,
+    redis: web::Data<Redis>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let user_validator = if let Some(user_validator) = state
+        .postgres
+        .get_user_validator_by_id(auth.id, path_params.id)
+        .await?
+    {
+        user_validator
+    } else {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from("User validator not found.")));
+    };
+    if !team::can_edit(&state, auth.id, user_validator.team_id).await? {
+        return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+            "Only team owners and editors can edit team validators.",
+        )));
+    }
+    let network = state
+        .postgres
+        .get_network_by_id(user_validator.network_id)
+        .await?;
+    let challenge = format!(
+        "SubVT validator ownership verification for {}: {}",
+        user_validator
+            .validator_account_id
+            .to_ss58_check_with_version(network.ss58_prefix as u16),
+        hex::encode(rand::thread_rng().gen::<[u8; CHALLENGE_NONCE_BYTE_COUNT]>()),
+    );
+    let expiry_mins = CONFIG.app_service.validator_ownership_challenge_expiry_mins;
+    redis
+        .set_app_service_validator_ownership_challenge(
+            user_validator.id,
+            &challenge,
+            expiry_mins as u64 * 60,
+        )
+        .await?;
+    Ok(
+        HttpResponse::Created().json(UserValidatorOwnershipChallenge {
+            challenge,
+            expires_at: chrono::Utc::now().naive_utc()
+                + chrono::Duration::minutes(expiry_mins as i64),
+        }),
+    )
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct VerifyUserValidatorOwnershipRequest {
+    /// Hex-encoded sr25519 or ed25519 signature of the challenge by the validator stash account.
+    pub signature: String,
+}
+
+/// Verifies the signature of the challenge by the validator stash account and marks the
+/// validator as ownership-verified. A challenge can be used only once, whether the signature
+/// is valid or not.
+#[utoipa::path(
+    post,
+    path = "/secure/user/validator/{id}/ownership",
+    tag = "validator",
+    params(IdPathParameter),
+    request_body = VerifyUserValidatorOwnershipRequest,
+    responses(
+        (status = 200, description = "Ownership verified.", body = UserValidator),
+        (status = 400, description = "Invalid signature.", body = ServiceError),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "User validator or challenge not found.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/validator/{id}/ownership")]
+pub async fn verify_user_validator_ownership(
+    path_params: web::Path<IdPathParameter>,
+    input: web::Json<VerifyUserValidatorOwnershipRequest>,
+    state: web::Data<ServiceState>,
+    redis: web::Data<Redis>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let mut user_validator = if let Some(user_validator) = state
+        .postgres
+        .get_user_validator_by_id(auth.id, path_params.id)
+        .await?
+    {
+        user_validator
+    } else {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from("User validator not found.")));
+    };
+    if !team::can_edit(&state, auth.id, user_validator.team_id).await? {
+        return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+            "Only team owners and editors can edit team validators.",
+        )));
+    }
+    let challenge = if let Some(challenge) = redis
+        .take_app_service_validator_ownership_challenge(user_validator.id)
+        .await?
+    {
+        challenge
+    } else {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from(
+            "Ownership challenge not found or expired.",
+        )));
+    };
+    let signature = if let Ok(signature) = hex::decode(input.signature.trim_start_matches("0x")) {
+        signature
+    } else {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Invalid signature.")));
+    };
+    if !user_validator
+        .validator_account_id
+        .verify_signature(challenge.as_bytes(), &signature)
+    {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Invalid signature.")));
+    }
+    if !state
+        .postgres
+        .set_user_validator_ownership_verified(user_validator.id)
+        .await?
+    {
+        return Ok(HttpResponse::InternalServerError().json(ServiceError::from(
+            "There was an error verifying the validator ownership.",
+        )));
+    }
+    user_validator.is_ownership_verified = true;
+    Ok(HttpResponse::Ok().json(user_validator))
+}
@@ -0,0 +1,135 @@
+//! Telemetry node overrides of the user validators. The Telemetry processor matches the nodes
+//! to the validators automatically, and the owner of a validator can assign a node to the
+//! validator by its network id or name when the automatic matching fails. Requires a validator
+//! with verified ownership, as the override has the highest confidence.
+use crate::auth::data::AuthenticatedUser;
+use crate::{team, IdPathParameter, ResultResponse, ServiceState};
+use actix_web::{delete, post, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use subvt_types::app::UserValidator;
+use subvt_types::err::ServiceError;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct SetUserValidatorTelemetryOverrideRequest {
+    /// libp2p network id (peer id) of the node.
+    pub node_network_id: Option<String>,
+    /// Exact name of the node on Telemetry.
+    pub node_name: Option<String>,
+}
+
+/// Gets the user validator that the user can edit, or the error response.
+async fn get_editable_user_validator(
+    state: &ServiceState,
+    user_id: u32,
+    user_validator_id: u32,
+) -> anyhow::Result<Result<UserValidator, HttpResponse>> {
+    let user_validator = if let Some(user_validator) = state
+        .postgres
+        .get_user_validator_by_id(user_id, user_validator_id)
+        .await?
+    {
+        user_validator
+    } else {
+        return Ok(Err(
+            HttpResponse::NotFound().json(ServiceError::from("User validator not found."))
+        ));
+    };
+    if !team::can_edit(state, user_id, user_validator.team_id).await? {
+        return Ok(Err(HttpResponse::Forbidden().json(ServiceError::from(
+            "Only team owners and editors can edit team validators.",
+        ))));
+    }
+    Ok(Ok(user_validator))
+}
+
+/// Assigns a Telemetry node to the validator, by the node's network id or name. Replaces the
+/// earlier assignment, if any.
+#[utoipa::path(
+    post,
+    path = "/secure/user/validator/{id}/telemetry/override",
+    tag = "validator",
+    params(IdPathParameter),
+    request_body = SetUserValidatorTelemetryOverrideRequest,
+    responses(
+        (status = 204, description = "Telemetry node override saved."),
+        (status = 400, description = "Neither the network id nor the name of the node is given.", body = ServiceError),
+        (status = 403, description = "No edit access to the team, or validator ownership is not verified.", body = ServiceError),
+        (status = 404, description = "User validator not found.", body = ServiceError),
+    ),
+)]
+#[post("/secure/user/validator/{id}/telemetry/override")]
+pub async fn set_user_validator_telemetry_override(
+    path_params: web::Path<IdPathParameter>,
+    input: web::Json<SetUserValidatorTelemetryOverrideRequest>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let user_validator = match get_editable_user_validator(&state, auth.id, path_params.id).await? {
+        Ok(user_validator) => user_validator,
+        Err(response) => return Ok(response),
+    };
+    if !user_validator.is_ownership_verified {
+        return Ok(HttpResponse::Forbidden().json(ServiceError::from(
+            "Validator ownership should be verified to override the Telemetry node.",
+        )));
+    }
+    let maybe_node_network_id = input
+        .node_network_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|network_id| !network_id.is_empty());
+    let maybe_node_name = input
+        .node_name
+        .as_deref()
+        .filter(|node_name| !node_name.trim().is_empty());
+    if maybe_node_network_id.is_none() && maybe_node_name.is_none() {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+            "Either the network id or the name of the node is required.",
+        )));
+    }
+    state
+        .postgres
+        .save_user_validator_telemetry_override(
+            user_validator.id,
+            maybe_node_network_id,
+            maybe_node_name,
+        )
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Removes the Telemetry node override of the validator, the node gets matched automatically.
+#[utoipa::path(
+    delete,
+    path = "/secure/user/validator/{id}/telemetry/override",
+    tag = "validator",
+    params(IdPathParameter),
+    responses(
+        (status = 204, description = "Telemetry node override removed."),
+        (status = 403, description = "No edit access to the team.", body = ServiceError),
+        (status = 404, description = "User validator or override not found.", body = ServiceError),
+    ),
+)]
+#[delete("/secure/user/validator/{id}/telemetry/override")]
+pub async fn delete_user_validator_telemetry_override(
+    path_params: web::Path<IdPathParameter>,
+    state: web::Data<ServiceState>,
+    auth: AuthenticatedUser,
+) -> ResultResponse {
+    let user_validator = match get_editable_user_validator(&state, auth.id, path_params.id).await? {
+        Ok(user_validator) => user_validator,
+        Err(response) => return Ok(response),
+    };
+    match state
+        .postgres
+        .delete_user_validator_telemetry_override(user_validator.id)
+        .await?
+    {
+        true => Ok(HttpResponse::NoContent().finish()),
+        false => {
+            Ok(HttpResponse::NotFound()
+                .json(ServiceError::from("Telemetry node override not found.")))
+        }
+    }
+}
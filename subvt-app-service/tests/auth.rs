@@ -0,0 +1,149 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{
+    call, get_current_nonce, get_redis, get_service_state, signed_request_with_nonce, Key,
+};
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::create_user;
+
+mod common;
+
+#[actix_rt::test]
+async fn test_replayed_request_is_rejected() {
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(get_service_state().await))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user),
+    )
+    .await;
+    let key = Key::generate();
+    let nonce = get_current_nonce();
+    let response = call(
+        &app,
+        signed_request_with_nonce(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user",
+            "",
+            &key,
+            nonce,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        call(
+            &app,
+            signed_request_with_nonce(
+                test::TestRequest::post(),
+                "POST",
+                "/secure/user",
+                "",
+                &key,
+                nonce,
+            ),
+        )
+        .await
+        .err(),
+        Some(StatusCode::FORBIDDEN),
+    );
+}
+
+#[actix_rt::test]
+async fn test_out_of_window_request_is_rejected() {
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(get_service_state().await))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user),
+    )
+    .await;
+    let key = Key::generate();
+    let window_millis = get_config_nonce_window_millis();
+    for nonce in [
+        get_current_nonce() - window_millis - 1_000,
+        get_current_nonce() + window_millis + 1_000,
+    ] {
+        assert_eq!(
+            call(
+                &app,
+                signed_request_with_nonce(
+                    test::TestRequest::post(),
+                    "POST",
+                    "/secure/user",
+                    "",
+                    &key,
+                    nonce,
+                ),
+            )
+            .await
+            .err(),
+            Some(StatusCode::FORBIDDEN),
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn test_legacy_nonce_is_accepted_until_deadline() {
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(get_service_state().await))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user),
+    )
+    .await;
+    let key = Key::generate();
+    // a seconds timestamp, as sent by the clients that predate millisecond timestamp nonces
+    let nonce = chrono::Utc::now().timestamp() as u64;
+    let is_deprecation_period = nonce
+        < subvt_config::Config::default()
+            .app_service
+            .auth_legacy_nonce_deadline;
+    let result = call(
+        &app,
+        signed_request_with_nonce(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user",
+            "",
+            &key,
+            nonce,
+        ),
+    )
+    .await;
+    if !is_deprecation_period {
+        assert_eq!(result.err(), Some(StatusCode::FORBIDDEN));
+        return;
+    }
+    assert_eq!(result.unwrap().status(), StatusCode::CREATED);
+    // legacy nonces are protected against replays too
+    assert_eq!(
+        call(
+            &app,
+            signed_request_with_nonce(
+                test::TestRequest::post(),
+                "POST",
+                "/secure/user",
+                "",
+                &key,
+                nonce,
+            ),
+        )
+        .await
+        .err(),
+        Some(StatusCode::FORBIDDEN),
+    );
+}
+
+fn get_config_nonce_window_millis() -> u64 {
+    subvt_config::Config::default()
+        .app_service
+        .auth_nonce_window_secs as u64
+        * 1000
+}
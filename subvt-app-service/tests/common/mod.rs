@@ -0,0 +1,114 @@
+//! Helpers to sign app service requests with generated keys.
+#![allow(dead_code)]
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::test;
+use libsecp256k1::{sign, Message, PublicKey, SecretKey};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use subvt_app_service::ServiceState;
+use subvt_config::Config;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::redis::Redis;
+
+pub async fn get_service_state() -> ServiceState {
+    let config = Config::test().unwrap();
+    ServiceState {
+        postgres: Arc::new(
+            PostgreSQLAppStorage::new(&config, config.get_app_postgres_url())
+                .await
+                .unwrap(),
+        ),
+    }
+}
+
+pub fn get_redis() -> Redis {
+    Redis::new().unwrap()
+}
+
+/// The current millisecond timestamp, which is a valid nonce. Bumped by a millisecond when
+/// called more than once in the same millisecond, so that consecutive requests don't get
+/// rejected as replays.
+pub fn get_current_nonce() -> u64 {
+    static LAST_NONCE: AtomicU64 = AtomicU64::new(0);
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let previous = LAST_NONCE
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(now.max(last + 1))
+        })
+        .unwrap();
+    now.max(previous + 1)
+}
+
+pub struct Key {
+    secret_key: SecretKey,
+    pub public_key_hex: String,
+}
+
+impl Key {
+    pub fn generate() -> Self {
+        let secret_key = loop {
+            if let Ok(secret_key) = SecretKey::parse(&rand::thread_rng().gen::<[u8; 32]>()) {
+                break secret_key;
+            }
+        };
+        let public_key_hex = format!(
+            "0x{}",
+            hex::encode_upper(PublicKey::from_secret_key(&secret_key).serialize_compressed())
+        );
+        Self {
+            secret_key,
+            public_key_hex,
+        }
+    }
+
+    pub fn sign(&self, message: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(message.as_bytes());
+        let hash = hasher.finalize();
+        let (signature, _) = sign(&Message::parse_slice(&hash).unwrap(), &self.secret_key);
+        hex::encode(signature.serialize_der().as_ref())
+    }
+}
+
+pub fn signed_request(
+    request: test::TestRequest,
+    method: &str,
+    path: &str,
+    body: &str,
+    key: &Key,
+) -> actix_http::Request {
+    signed_request_with_nonce(request, method, path, body, key, get_current_nonce())
+}
+
+pub fn signed_request_with_nonce(
+    request: test::TestRequest,
+    method: &str,
+    path: &str,
+    body: &str,
+    key: &Key,
+    nonce: u64,
+) -> actix_http::Request {
+    request
+        .uri(path)
+        .insert_header(("SubVT-Public-Key", key.public_key_hex.as_str()))
+        .insert_header((
+            "SubVT-Signature",
+            key.sign(&format!("{method}{path}{body}{nonce}")),
+        ))
+        .insert_header(("SubVT-Nonce", nonce.to_string()))
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(body.to_string())
+        .to_request()
+}
+
+pub async fn call<S>(app: &S, request: actix_http::Request) -> Result<ServiceResponse, StatusCode>
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    app.call(request)
+        .await
+        .map_err(|error| error.as_response_error().status_code())
+}
@@ -0,0 +1,358 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{call, get_redis, get_service_state, signed_request, Key};
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::{create_user, delete_user, device};
+use subvt_types::app::{User, UserDevice, UserDeviceLink};
+
+mod common;
+
+#[actix_rt::test]
+async fn test_link_revoke_and_rotate_user_devices() {
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(get_service_state().await))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(device::get_user_devices)
+            .service(device::create_user_device_link)
+            .service(device::link_user_device)
+            .service(device::rotate_user_device_key)
+            .service(device::revoke_user_device),
+    )
+    .await;
+    let first_key = Key::generate();
+    let second_key = Key::generate();
+    let third_key = Key::generate();
+    // register with the first device
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user",
+            "",
+            &first_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let user: User = test::read_body_json(response).await;
+    // an unregistered key cannot access the user's data
+    assert_eq!(
+        call(
+            &app,
+            signed_request(
+                test::TestRequest::get(),
+                "GET",
+                "/secure/user/device",
+                "",
+                &second_key
+            ),
+        )
+        .await
+        .err(),
+        Some(StatusCode::FORBIDDEN),
+    );
+    // the first device approves the second
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/device/link",
+            "",
+            &first_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let link: UserDeviceLink = test::read_body_json(response).await;
+    let link_path = format!("/secure/user/device/link/{}", link.code);
+    let link_body = r#"{"name":"Second"}"#;
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &link_path,
+            link_body,
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let second_device: UserDevice = test::read_body_json(response).await;
+    assert_eq!(second_device.user_id, user.id);
+    // link codes are single-use
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &link_path,
+            link_body,
+            &third_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    // the second device sees both devices
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::get(),
+            "GET",
+            "/secure/user/device",
+            "",
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    let devices: Vec<UserDevice> = test::read_body_json(response).await;
+    assert_eq!(devices.len(), 2);
+    let first_device = devices
+        .iter()
+        .find(|device| device.public_key_hex == first_key.public_key_hex)
+        .unwrap();
+    // the second device revokes the first
+    let revoke_path = format!("/secure/user/device/{}", first_device.id);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::delete(),
+            "DELETE",
+            &revoke_path,
+            "",
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        call(
+            &app,
+            signed_request(
+                test::TestRequest::get(),
+                "GET",
+                "/secure/user/device",
+                "",
+                &first_key
+            ),
+        )
+        .await
+        .err(),
+        Some(StatusCode::FORBIDDEN),
+    );
+    // the last device cannot be revoked
+    let revoke_path = format!("/secure/user/device/{}", second_device.id);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::delete(),
+            "DELETE",
+            &revoke_path,
+            "",
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    // a rotation signed by a key other than the new one is rejected
+    let rotate_body = format!(
+        r#"{{"public_key_hex":"{}","signature":"{}"}}"#,
+        third_key.public_key_hex,
+        second_key.sign(&format!(
+            "{}{}",
+            second_key.public_key_hex, third_key.public_key_hex
+        )),
+    );
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/device/rotate",
+            &rotate_body,
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    // the second device rotates its key to the third key
+    let rotate_body = format!(
+        r#"{{"public_key_hex":"{}","signature":"{}"}}"#,
+        third_key.public_key_hex,
+        third_key.sign(&format!(
+            "{}{}",
+            second_key.public_key_hex, third_key.public_key_hex
+        )),
+    );
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/device/rotate",
+            &rotate_body,
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let rotated_device: UserDevice = test::read_body_json(response).await;
+    assert_eq!(rotated_device.user_id, user.id);
+    assert_eq!(rotated_device.name.as_deref(), Some("Second"));
+    assert_eq!(
+        call(
+            &app,
+            signed_request(
+                test::TestRequest::get(),
+                "GET",
+                "/secure/user/device",
+                "",
+                &second_key
+            ),
+        )
+        .await
+        .err(),
+        Some(StatusCode::FORBIDDEN),
+    );
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::get(),
+            "GET",
+            "/secure/user/device",
+            "",
+            &third_key,
+        ),
+    )
+    .await
+    .unwrap();
+    let devices: Vec<UserDevice> = test::read_body_json(response).await;
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].public_key_hex, third_key.public_key_hex);
+    // a revoked key cannot register again
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user",
+            "",
+            &first_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_register_with_the_device_key_of_another_user() {
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(get_service_state().await))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(delete_user)
+            .service(device::create_user_device_link)
+            .service(device::link_user_device),
+    )
+    .await;
+    let first_key = Key::generate();
+    let second_key = Key::generate();
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user",
+            "",
+            &first_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/device/link",
+            "",
+            &first_key,
+        ),
+    )
+    .await
+    .unwrap();
+    let link: UserDeviceLink = test::read_body_json(response).await;
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &format!("/secure/user/device/link/{}", link.code),
+            r#"{"name":"Second"}"#,
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::delete(),
+            "DELETE",
+            "/secure/user",
+            "",
+            &first_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    // the linked key still belongs to the deleted user
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user",
+            "",
+            &second_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    // the registration key restores the deleted user
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user",
+            "",
+            &first_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
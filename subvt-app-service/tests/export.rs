@@ -0,0 +1,84 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{call, get_redis, get_service_state, signed_request, Key};
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::{create_user, delete_user, export_user_data};
+use subvt_types::app::User;
+
+mod common;
+
+#[actix_rt::test]
+async fn test_export_and_delete_user() {
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(get_service_state().await))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(export_user_data)
+            .service(delete_user),
+    )
+    .await;
+    let key = Key::generate();
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::post(), "POST", "/secure/user", "", &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let user: User = test::read_body_json(response).await;
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::get(),
+            "GET",
+            "/secure/user/export",
+            "",
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let export: serde_json::Value = test::read_body_json(response).await;
+    assert_eq!(export["user"]["id"], user.id);
+    assert_eq!(export["devices"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        export["devices"][0]["public_key_hex"],
+        key.public_key_hex.as_str()
+    );
+    assert!(export["validators"].as_array().unwrap().is_empty());
+    assert!(export["notifications"].as_array().unwrap().is_empty());
+    // delete the user
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::delete(),
+            "DELETE",
+            "/secure/user",
+            "",
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    // a deleted user cannot access the data anymore
+    assert_eq!(
+        call(
+            &app,
+            signed_request(
+                test::TestRequest::get(),
+                "GET",
+                "/secure/user/export",
+                "",
+                &key
+            ),
+        )
+        .await
+        .err(),
+        Some(StatusCode::FORBIDDEN),
+    );
+}
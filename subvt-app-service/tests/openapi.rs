@@ -0,0 +1,30 @@
+use std::path::Path;
+use subvt_app_service::openapi::ApiDoc;
+use subvt_service_common::openapi::{get_route_diff, get_spec_errors};
+use utoipa::OpenApi;
+
+/// Fails when a handler is added, removed or re-routed without updating the specification.
+#[test]
+fn test_openapi_spec_matches_handlers() {
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let (undocumented, unserved) = get_route_diff(&src_dir, &ApiDoc::openapi()).unwrap();
+    assert!(
+        undocumented.is_empty(),
+        "Routes missing in the OpenAPI specification: {undocumented:?}"
+    );
+    assert!(
+        unserved.is_empty(),
+        "Routes in the OpenAPI specification without a handler: {unserved:?}"
+    );
+}
+
+/// Fails when an operation's path parameters don't match its path, when it has no successful
+/// response, or when a parameter or body schema is missing from the component schemas.
+#[test]
+fn test_openapi_spec_parameters_and_bodies() {
+    let errors = get_spec_errors(&ApiDoc::openapi()).unwrap();
+    assert!(
+        errors.is_empty(),
+        "Errors in the OpenAPI specification: {errors:#?}"
+    );
+}
@@ -0,0 +1,179 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{call, get_redis, get_service_state, signed_request, Key};
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::{create_user, rule_template, validator_import};
+use subvt_types::app::notification::{
+    NotificationChannel, UserNotificationChannel, UserNotificationRuleTemplate,
+};
+use subvt_types::app::{User, UserValidator};
+use subvt_types::crypto::AccountId;
+
+mod common;
+
+#[actix_rt::test]
+async fn test_import_validators_and_apply_rule_template() {
+    let state = get_service_state().await;
+    let postgres = state.postgres.clone();
+    let network_id = postgres.get_networks().await.unwrap()[0].id;
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(state))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(validator_import::import_user_validators)
+            .service(rule_template::get_user_notification_rule_templates)
+            .service(rule_template::create_user_notification_rule_template)
+            .service(rule_template::apply_user_notification_rule_template),
+    )
+    .await;
+    let key = Key::generate();
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::post(), "POST", "/secure/user", "", &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let user: User = test::read_body_json(response).await;
+    let channel_id = postgres
+        .save_user_notification_channel(&UserNotificationChannel {
+            id: 0,
+            user_id: user.id,
+            channel: NotificationChannel::Email,
+            target: "template@subvt.io".to_string(),
+        })
+        .await
+        .unwrap();
+    // no validators get imported if any address is invalid
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/validator/import",
+            &format!(
+                r#"{{"network_id":{network_id},"addresses":["{}","invalid"]}}"#,
+                AccountId::from([11; 32]),
+            ),
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = test::read_body_json(response).await;
+    assert_eq!(error["item_errors"][0]["index"], 1);
+    // duplicate addresses are imported once
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/validator/import",
+            &format!(
+                r#"{{"network_id":{network_id},"addresses":["{0}","{1}","{0}"]}}"#,
+                AccountId::from([11; 32]),
+                AccountId::from([12; 32]),
+            ),
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let user_validators: Vec<UserValidator> = test::read_body_json(response).await;
+    assert_eq!(user_validators.len(), 2);
+    // invalid rules are reported individually
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/notification/rule/template",
+            r#"{"name":"Basic","rules":[{"notification_type_code":"chain_validator_chilled","period_type":"immediate","period":0},{"notification_type_code":"non_existent_type","period_type":"immediate","period":0}]}"#,
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = test::read_body_json(response).await;
+    assert_eq!(error["item_errors"].as_array().unwrap().len(), 1);
+    assert_eq!(error["item_errors"][0]["index"], 1);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/notification/rule/template",
+            r#"{"name":"Basic","rules":[{"notification_type_code":"chain_validator_chilled","period_type":"immediate","period":0}]}"#,
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let template: UserNotificationRuleTemplate = test::read_body_json(response).await;
+    assert_eq!(template.rules.len(), 1);
+    // apply the template to the imported validators
+    let apply_path = format!(
+        "/secure/user/notification/rule/template/{}/apply",
+        template.id
+    );
+    let apply_body = format!(
+        r#"{{"network_id":{network_id},"user_validator_ids":[{},{}],"user_notification_channel_ids":[{channel_id}]}}"#,
+        user_validators[0].id, user_validators[1].id,
+    );
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &apply_path,
+            &apply_body,
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let rules: Vec<serde_json::Value> = test::read_body_json(response).await;
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["validators"].as_array().unwrap().len(), 2);
+    // the rule exists now, so the template cannot be applied again to the same validators
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &apply_path,
+            &apply_body,
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    // but it can be applied to a different set of validators
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &apply_path,
+            &format!(
+                r#"{{"network_id":{network_id},"user_validator_ids":[{}],"user_notification_channel_ids":[{channel_id}]}}"#,
+                user_validators[0].id,
+            ),
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let rules: Vec<serde_json::Value> = test::read_body_json(response).await;
+    assert_eq!(rules[0]["validators"].as_array().unwrap().len(), 1);
+}
@@ -0,0 +1,167 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{call, get_redis, get_service_state, signed_request, Key};
+use sp_core::Pair;
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::{add_user_validator, create_user, sign_in};
+use subvt_types::app::{UserDevice, UserSignInChallenge, UserValidator};
+use subvt_types::crypto::AccountId;
+
+mod common;
+
+#[actix_rt::test]
+async fn test_sign_in_with_verified_validator_account() {
+    let state = get_service_state().await;
+    let postgres = state.postgres.clone();
+    let network_id = postgres.get_networks().await.unwrap()[0].id;
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(state))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(add_user_validator)
+            .service(sign_in::create_user_sign_in_challenge)
+            .service(sign_in::sign_in_user),
+    )
+    .await;
+    let key = Key::generate();
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::post(), "POST", "/secure/user", "", &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let (pair, _) = sp_core::sr25519::Pair::generate();
+    let account_id = AccountId::from(pair.public().0);
+    let body = format!(r#"{{"network_id":{network_id},"validator_account_id":"{account_id}"}}"#);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/validator",
+            &body,
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let user_validator: UserValidator = test::read_body_json(response).await;
+    let new_key = Key::generate();
+    let challenge_body = format!(r#"{{"account_id":"{account_id}"}}"#);
+    let sign_in_body = |challenge: &UserSignInChallenge| {
+        format!(
+            r#"{{"signature":"{}","name":"New Device"}}"#,
+            hex::encode(pair.sign(challenge.challenge.as_bytes()).0)
+        )
+    };
+    // the validator ownership should be verified
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/sign_in/challenge",
+            &challenge_body,
+            &new_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let challenge: UserSignInChallenge = test::read_body_json(response).await;
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/sign_in",
+            &sign_in_body(&challenge),
+            &new_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(postgres
+        .set_user_validator_ownership_verified(user_validator.id)
+        .await
+        .unwrap());
+    // the challenge is used up
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/sign_in",
+            &sign_in_body(&challenge),
+            &new_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    // the challenge of a device cannot be signed in with by another
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/sign_in/challenge",
+            &challenge_body,
+            &new_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let challenge: UserSignInChallenge = test::read_body_json(response).await;
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/sign_in",
+            &sign_in_body(&challenge),
+            &Key::generate(),
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    // signed in to the user of the verified validator
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/sign_in",
+            &sign_in_body(&challenge),
+            &new_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let device: UserDevice = test::read_body_json(response).await;
+    assert_eq!(device.user_id, user_validator.user_id);
+    assert_eq!(device.name.as_deref(), Some("New Device"));
+    // a registered device cannot sign in again
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/sign_in/challenge",
+            &challenge_body,
+            &new_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
@@ -0,0 +1,321 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{call, get_redis, get_service_state, signed_request, Key};
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::{
+    add_user_validator, create_user, create_user_notification_rule, delete_user_validator,
+    get_user_validators, team,
+};
+use subvt_types::app::notification::{NotificationChannel, UserNotificationChannel};
+use subvt_types::app::team::{Team, TeamInvitation};
+use subvt_types::app::{User, UserValidator};
+use subvt_types::crypto::AccountId;
+
+mod common;
+
+#[actix_rt::test]
+async fn test_team_validators_and_rules() {
+    let state = get_service_state().await;
+    let postgres = state.postgres.clone();
+    let network_id = postgres.get_networks().await.unwrap()[0].id;
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(state))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(get_user_validators)
+            .service(add_user_validator)
+            .service(delete_user_validator)
+            .service(create_user_notification_rule)
+            .service(team::create_team)
+            .service(team::get_user_teams)
+            .service(team::get_team_members)
+            .service(team::save_team_member)
+            .service(team::create_team_invitation)
+            .service(team::get_user_team_invitations)
+            .service(team::accept_team_invitation)
+            .service(team::delete_team_member),
+    )
+    .await;
+    let owner_key = Key::generate();
+    let editor_key = Key::generate();
+    let viewer_key = Key::generate();
+    let mut users = Vec::new();
+    for key in [&owner_key, &editor_key, &viewer_key] {
+        let response = call(
+            &app,
+            signed_request(test::TestRequest::post(), "POST", "/secure/user", "", key),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let user: User = test::read_body_json(response).await;
+        users.push(user);
+    }
+    let (owner, editor, viewer) = (&users[0], &users[1], &users[2]);
+    // the viewer has an email channel to receive team notifications
+    let viewer_channel_id = postgres
+        .save_user_notification_channel(&UserNotificationChannel {
+            id: 0,
+            user_id: viewer.id,
+            channel: NotificationChannel::Email,
+            target: "viewer@subvt.io".to_string(),
+        })
+        .await
+        .unwrap();
+    // create the team
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/team",
+            r#"{"name":"Operators"}"#,
+            &owner_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let team: Team = test::read_body_json(response).await;
+    let member_path = format!("/secure/user/team/{}/member", team.id);
+    let invitation_path = format!("/secure/user/team/{}/invitation", team.id);
+    // users cannot be added without their acceptance
+    let body = format!(r#"{{"user_id":{},"role":"editor"}}"#, editor.id);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &member_path,
+            &body,
+            &owner_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    for (user, key, role) in [
+        (editor, &editor_key, "editor"),
+        (viewer, &viewer_key, "viewer"),
+    ] {
+        let body = format!(r#"{{"user_id":{},"role":"{role}"}}"#, user.id);
+        let response = call(
+            &app,
+            signed_request(
+                test::TestRequest::post(),
+                "POST",
+                &invitation_path,
+                &body,
+                &owner_key,
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        // invited users are not members until they accept the invitation
+        let response = call(
+            &app,
+            signed_request(test::TestRequest::get(), "GET", &member_path, "", key),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = call(
+            &app,
+            signed_request(
+                test::TestRequest::get(),
+                "GET",
+                "/secure/user/team/invitation",
+                "",
+                key,
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let invitations: Vec<TeamInvitation> = test::read_body_json(response).await;
+        assert_eq!(invitations.len(), 1);
+        assert_eq!(invitations[0].team_id, team.id);
+        assert_eq!(invitations[0].invited_by_user_id, owner.id);
+        let response = call(
+            &app,
+            signed_request(
+                test::TestRequest::post(),
+                "POST",
+                &format!("/secure/user/team/invitation/{}", invitations[0].id),
+                "",
+                key,
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    // members cannot be invited again
+    let body = format!(r#"{{"user_id":{},"role":"owner"}}"#, viewer.id);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &invitation_path,
+            &body,
+            &owner_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    // viewers cannot manage members
+    let body = format!(r#"{{"user_id":{},"role":"owner"}}"#, viewer.id);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &member_path,
+            &body,
+            &viewer_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    // the only owner cannot leave
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::delete(),
+            "DELETE",
+            &format!("{member_path}/{}", owner.id),
+            "",
+            &owner_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    // viewers cannot add team validators, editors can
+    let validator_body = format!(
+        r#"{{"network_id":{network_id},"validator_account_id":"{}","team_id":{}}}"#,
+        AccountId::from([7; 32]),
+        team.id,
+    );
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/validator",
+            &validator_body,
+            &viewer_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/validator",
+            &validator_body,
+            &editor_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let team_validator: UserValidator = test::read_body_json(response).await;
+    assert_eq!(team_validator.team_id, Some(team.id));
+    // all members see the team validator
+    for key in [&owner_key, &viewer_key] {
+        let response = call(
+            &app,
+            signed_request(
+                test::TestRequest::get(),
+                "GET",
+                "/secure/user/validator",
+                "",
+                key,
+            ),
+        )
+        .await
+        .unwrap();
+        let validators: Vec<UserValidator> = test::read_body_json(response).await;
+        assert!(validators
+            .iter()
+            .any(|validator| validator.id == team_validator.id));
+    }
+    // team rules cannot have channels of their own
+    let rule_body = |channel_ids: &str| {
+        format!(
+            r#"{{"notification_type_code":"chain_validator_chilled","team_id":{},"network_id":{network_id},"is_for_all_validators":false,"user_validator_ids":[{}],"period_type":"immediate","period":0,"user_notification_channel_ids":[{channel_ids}],"parameters":[]}}"#,
+            team.id, team_validator.id,
+        )
+    };
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/notification/rule",
+            &rule_body(&viewer_channel_id.to_string()),
+            &editor_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/notification/rule",
+            &rule_body(""),
+            &editor_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let rule: serde_json::Value = test::read_body_json(response).await;
+    assert_eq!(rule["team_id"], team.id);
+    // team notifications fan out to the channels of all members
+    let rules = postgres
+        .get_notification_rules_for_validator(
+            "chain_validator_chilled",
+            network_id,
+            &team_validator.validator_account_id,
+        )
+        .await
+        .unwrap();
+    let team_rule = rules
+        .iter()
+        .find(|rule| rule.team_id == Some(team.id))
+        .unwrap();
+    assert!(team_rule
+        .notification_channels
+        .iter()
+        .any(|channel| channel.id == viewer_channel_id && channel.user_id == viewer.id));
+    // viewers cannot delete team validators
+    let delete_path = format!("/secure/user/validator/{}", team_validator.id);
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::delete(),
+            "DELETE",
+            &delete_path,
+            "",
+            &viewer_key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
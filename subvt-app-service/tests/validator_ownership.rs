@@ -0,0 +1,118 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{call, get_redis, get_service_state, signed_request, Key};
+use sp_core::Pair;
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::{add_user_validator, create_user, validator_ownership};
+use subvt_types::app::{UserValidator, UserValidatorOwnershipChallenge};
+use subvt_types::crypto::AccountId;
+
+mod common;
+
+#[actix_rt::test]
+async fn test_verify_user_validator_ownership() {
+    let state = get_service_state().await;
+    let network_id = state.postgres.get_networks().await.unwrap()[0].id;
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(state))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(add_user_validator)
+            .service(validator_ownership::create_user_validator_ownership_challenge)
+            .service(validator_ownership::verify_user_validator_ownership),
+    )
+    .await;
+    let key = Key::generate();
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::post(), "POST", "/secure/user", "", &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let (sr25519_pair, _) = sp_core::sr25519::Pair::generate();
+    let (ed25519_pair, _) = sp_core::ed25519::Pair::generate();
+    let signers: [(AccountId, Box<dyn Fn(&[u8]) -> Vec<u8>>); 2] = [
+        (
+            AccountId::from(sr25519_pair.public().0),
+            Box::new(move |message| sr25519_pair.sign(message).0.to_vec()),
+        ),
+        (
+            AccountId::from(ed25519_pair.public().0),
+            Box::new(move |message| ed25519_pair.sign(message).0.to_vec()),
+        ),
+    ];
+    for (account_id, sign) in signers.iter() {
+        // added validators are never verified, even if the client says so
+        let body = format!(
+            r#"{{"network_id":{network_id},"validator_account_id":"{account_id}","is_ownership_verified":true}}"#,
+        );
+        let response = call(
+            &app,
+            signed_request(
+                test::TestRequest::post(),
+                "POST",
+                "/secure/user/validator",
+                &body,
+                &key,
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let user_validator: UserValidator = test::read_body_json(response).await;
+        assert!(!user_validator.is_ownership_verified);
+        let challenge_path = format!(
+            "/secure/user/validator/{}/ownership/challenge",
+            user_validator.id
+        );
+        let verify_path = format!("/secure/user/validator/{}/ownership", user_validator.id);
+        // a signature of something other than the challenge is rejected, and uses up the challenge
+        call(
+            &app,
+            signed_request(test::TestRequest::post(), "POST", &challenge_path, "", &key),
+        )
+        .await
+        .unwrap();
+        let body = format!(r#"{{"signature":"{}"}}"#, hex::encode(sign(b"SubVT")));
+        let response = call(
+            &app,
+            signed_request(test::TestRequest::post(), "POST", &verify_path, &body, &key),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = call(
+            &app,
+            signed_request(test::TestRequest::post(), "POST", &verify_path, &body, &key),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        // the signature of the challenge verifies the ownership
+        let response = call(
+            &app,
+            signed_request(test::TestRequest::post(), "POST", &challenge_path, "", &key),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let challenge: UserValidatorOwnershipChallenge = test::read_body_json(response).await;
+        let body = format!(
+            r#"{{"signature":"{}"}}"#,
+            hex::encode(sign(challenge.challenge.as_bytes()))
+        );
+        let response = call(
+            &app,
+            signed_request(test::TestRequest::post(), "POST", &verify_path, &body, &key),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let user_validator: UserValidator = test::read_body_json(response).await;
+        assert!(user_validator.is_ownership_verified);
+    }
+}
@@ -0,0 +1,114 @@
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{test, App};
+use common::{call, get_redis, get_service_state, signed_request, Key};
+use subvt_app_service::auth::service::AuthServiceFactory;
+use subvt_app_service::{add_user_validator, create_user, validator_telemetry};
+use subvt_types::app::UserValidator;
+use subvt_types::crypto::AccountId;
+
+mod common;
+
+#[actix_rt::test]
+async fn test_user_validator_telemetry_override() {
+    let state = get_service_state().await;
+    let postgres = state.postgres.clone();
+    let network_id = postgres.get_networks().await.unwrap()[0].id;
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::new(state))
+            .app_data(Data::new(get_redis()))
+            .wrap(AuthServiceFactory {})
+            .service(create_user)
+            .service(add_user_validator)
+            .service(validator_telemetry::set_user_validator_telemetry_override)
+            .service(validator_telemetry::delete_user_validator_telemetry_override),
+    )
+    .await;
+    let key = Key::generate();
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::post(), "POST", "/secure/user", "", &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let validator_account_id = AccountId::new(rand::random());
+    let body = format!(
+        r#"{{"network_id":{network_id},"validator_account_id":"{validator_account_id}"}}"#,
+    );
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            "/secure/user/validator",
+            &body,
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let user_validator: UserValidator = test::read_body_json(response).await;
+    let path = format!(
+        "/secure/user/validator/{}/telemetry/override",
+        user_validator.id
+    );
+    let body = r#"{"node_name":"helikon-polkadot-1"}"#;
+    // ownership should be verified
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::post(), "POST", &path, body, &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(postgres
+        .set_user_validator_ownership_verified(user_validator.id)
+        .await
+        .unwrap());
+    // either the network id or the name is required
+    let response = call(
+        &app,
+        signed_request(
+            test::TestRequest::post(),
+            "POST",
+            &path,
+            r#"{"node_network_id":" ","node_name":""}"#,
+            &key,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::post(), "POST", &path, body, &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let overrides = postgres
+        .get_telemetry_node_overrides(network_id)
+        .await
+        .unwrap();
+    assert!(overrides.iter().any(|node_override| {
+        node_override.validator_account_id == validator_account_id
+            && node_override.node_name.as_deref() == Some("helikon-polkadot-1")
+    }));
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::delete(), "DELETE", &path, "", &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = call(
+        &app,
+        signed_request(test::TestRequest::delete(), "DELETE", &path, "", &key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,123 @@
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::event::bounties::BountiesEvent;
+
+pub(crate) async fn process_bounties_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &BountiesEvent,
+) -> anyhow::Result<()> {
+    match event {
+        BountiesEvent::BountyAwarded {
+            extrinsic_index,
+            bounty_index,
+            beneficiary_account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_bounty_awarded_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *bounty_index,
+                    beneficiary_account_id,
+                )
+                .await?;
+        }
+        BountiesEvent::BountyCanceled {
+            extrinsic_index,
+            bounty_index,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_bounty_canceled_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *bounty_index,
+                )
+                .await?;
+        }
+        BountiesEvent::BountyClaimed {
+            extrinsic_index,
+            bounty_index,
+            payout,
+            beneficiary_account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_bounty_claimed_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *bounty_index,
+                    *payout,
+                    beneficiary_account_id,
+                )
+                .await?;
+        }
+        BountiesEvent::BountyProposed {
+            extrinsic_index,
+            bounty_index,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_bounty_proposed_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *bounty_index,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn update_bounties_event_nesting_index(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    maybe_nesting_index: &Option<String>,
+    event_index: i32,
+    event: &BountiesEvent,
+) -> anyhow::Result<()> {
+    match event {
+        BountiesEvent::BountyAwarded { .. } => {
+            postgres
+                .update_bounty_awarded_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        BountiesEvent::BountyCanceled { .. } => {
+            postgres
+                .update_bounty_canceled_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        BountiesEvent::BountyClaimed { .. } => {
+            postgres
+                .update_bounty_claimed_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        BountiesEvent::BountyProposed { .. } => {
+            postgres
+                .update_bounty_proposed_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::event::child_bounties::ChildBountiesEvent;
+
+pub(crate) async fn process_child_bounties_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &ChildBountiesEvent,
+) -> anyhow::Result<()> {
+    match event {
+        ChildBountiesEvent::Added {
+            extrinsic_index,
+            parent_bounty_index,
+            child_bounty_index,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_child_bounty_added_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *parent_bounty_index,
+                    *child_bounty_index,
+                )
+                .await?;
+        }
+        ChildBountiesEvent::Awarded {
+            extrinsic_index,
+            parent_bounty_index,
+            child_bounty_index,
+            beneficiary_account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_child_bounty_awarded_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *parent_bounty_index,
+                    *child_bounty_index,
+                    beneficiary_account_id,
+                )
+                .await?;
+        }
+        ChildBountiesEvent::Canceled {
+            extrinsic_index,
+            parent_bounty_index,
+            child_bounty_index,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_child_bounty_canceled_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *parent_bounty_index,
+                    *child_bounty_index,
+                )
+                .await?;
+        }
+        ChildBountiesEvent::Claimed {
+            extrinsic_index,
+            parent_bounty_index,
+            child_bounty_index,
+            payout,
+            beneficiary_account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_child_bounty_claimed_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *parent_bounty_index,
+                    *child_bounty_index,
+                    *payout,
+                    beneficiary_account_id,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn update_child_bounties_event_nesting_index(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    maybe_nesting_index: &Option<String>,
+    event_index: i32,
+    event: &ChildBountiesEvent,
+) -> anyhow::Result<()> {
+    match event {
+        ChildBountiesEvent::Added { .. } => {
+            postgres
+                .update_child_bounty_added_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        ChildBountiesEvent::Awarded { .. } => {
+            postgres
+                .update_child_bounty_awarded_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        ChildBountiesEvent::Canceled { .. } => {
+            postgres
+                .update_child_bounty_canceled_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        ChildBountiesEvent::Claimed { .. } => {
+            postgres
+                .update_child_bounty_claimed_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
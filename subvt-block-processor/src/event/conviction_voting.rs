@@ -0,0 +1,128 @@
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::democracy::DirectVote;
+use subvt_types::substrate::event::conviction_voting::ConvictionVotingEvent;
+
+pub(crate) async fn process_conviction_voting_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &ConvictionVotingEvent,
+) -> anyhow::Result<()> {
+    match event {
+        ConvictionVotingEvent::Delegated {
+            extrinsic_index,
+            original_account_id,
+            delegate_account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_conviction_voting_delegated_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    original_account_id,
+                    delegate_account_id,
+                )
+                .await?;
+        }
+        ConvictionVotingEvent::Undelegated {
+            extrinsic_index,
+            account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_conviction_voting_undelegated_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    account_id,
+                )
+                .await?;
+        }
+        ConvictionVotingEvent::Voted {
+            extrinsic_index,
+            account_id,
+            vote,
+            maybe_referendum_index,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_conviction_voting_voted_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    account_id,
+                    *maybe_referendum_index,
+                    &DirectVote::from(vote),
+                )
+                .await?;
+        }
+        ConvictionVotingEvent::VoteRemoved {
+            extrinsic_index,
+            account_id,
+            vote,
+            maybe_referendum_index,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_conviction_voting_vote_removed_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    account_id,
+                    *maybe_referendum_index,
+                    &DirectVote::from(vote),
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn update_conviction_voting_event_nesting_index(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    maybe_nesting_index: &Option<String>,
+    event_index: i32,
+    event: &ConvictionVotingEvent,
+) -> anyhow::Result<()> {
+    match event {
+        ConvictionVotingEvent::Delegated { .. } => {
+            postgres
+                .update_conviction_voting_delegated_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        ConvictionVotingEvent::Undelegated { .. } => {
+            postgres
+                .update_conviction_voting_undelegated_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        ConvictionVotingEvent::Voted { .. } => {
+            postgres
+                .update_conviction_voting_voted_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        ConvictionVotingEvent::VoteRemoved { .. } => {
+            postgres
+                .update_conviction_voting_vote_removed_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
@@ -1,17 +1,29 @@
+use crate::event::bounties::{process_bounties_event, update_bounties_event_nesting_index};
+use crate::event::child_bounties::{
+    process_child_bounties_event, update_child_bounties_event_nesting_index,
+};
+use crate::event::conviction_voting::{
+    process_conviction_voting_event, update_conviction_voting_event_nesting_index,
+};
 use crate::event::democracy::{process_democracy_event, update_democracy_event_nesting_index};
 use crate::event::imonline::process_imonline_event;
 use crate::event::referenda::{process_referenda_event, update_referenda_event_nesting_index};
 use crate::event::staking::{process_staking_event, update_staking_event_nesting_index};
 use crate::event::system::{process_system_event, update_system_event_nesting_index};
+use crate::event::treasury::{process_treasury_event, update_treasury_event_nesting_index};
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_substrate_client::SubstrateClient;
 use subvt_types::substrate::event::SubstrateEvent;
 
+mod bounties;
+mod child_bounties;
+mod conviction_voting;
 mod democracy;
 mod imonline;
 mod referenda;
 mod staking;
 mod system;
+mod treasury;
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn process_event(
@@ -25,6 +37,22 @@ pub(crate) async fn process_event(
     event: &SubstrateEvent,
 ) -> anyhow::Result<()> {
     match event {
+        SubstrateEvent::Bounties(bounties_event) => {
+            process_bounties_event(postgres, block_hash, event_index, bounties_event).await?
+        }
+        SubstrateEvent::ChildBounties(child_bounties_event) => {
+            process_child_bounties_event(postgres, block_hash, event_index, child_bounties_event)
+                .await?
+        }
+        SubstrateEvent::ConvictionVoting(conviction_voting_event) => {
+            process_conviction_voting_event(
+                postgres,
+                block_hash,
+                event_index,
+                conviction_voting_event,
+            )
+            .await?
+        }
         SubstrateEvent::Democracy(democracy_event) => {
             process_democracy_event(postgres, block_hash, event_index, democracy_event).await?
         }
@@ -40,7 +68,14 @@ pub(crate) async fn process_event(
             .await?
         }
         SubstrateEvent::Referenda(referenda_event) => {
-            process_referenda_event(postgres, block_hash, event_index, referenda_event).await?
+            process_referenda_event(
+                substrate_client,
+                postgres,
+                block_hash,
+                event_index,
+                referenda_event,
+            )
+            .await?
         }
         SubstrateEvent::Staking(staking_event) => {
             process_staking_event(postgres, block_hash, event_index, staking_event).await?
@@ -56,6 +91,9 @@ pub(crate) async fn process_event(
             )
             .await?
         }
+        SubstrateEvent::Treasury(treasury_event) => {
+            process_treasury_event(postgres, block_hash, event_index, treasury_event).await?
+        }
         SubstrateEvent::Utility(_) => (),
         _ => (),
     }
@@ -70,6 +108,36 @@ pub(crate) async fn update_event_nesting_indices(
 ) -> anyhow::Result<()> {
     for (event_index, event) in events {
         match event {
+            SubstrateEvent::Bounties(bounties_event) => {
+                update_bounties_event_nesting_index(
+                    postgres,
+                    block_hash,
+                    maybe_nesting_index,
+                    *event_index as i32,
+                    bounties_event,
+                )
+                .await?;
+            }
+            SubstrateEvent::ChildBounties(child_bounties_event) => {
+                update_child_bounties_event_nesting_index(
+                    postgres,
+                    block_hash,
+                    maybe_nesting_index,
+                    *event_index as i32,
+                    child_bounties_event,
+                )
+                .await?;
+            }
+            SubstrateEvent::ConvictionVoting(conviction_voting_event) => {
+                update_conviction_voting_event_nesting_index(
+                    postgres,
+                    block_hash,
+                    maybe_nesting_index,
+                    *event_index as i32,
+                    conviction_voting_event,
+                )
+                .await?;
+            }
             SubstrateEvent::Democracy(democracy_event) => {
                 update_democracy_event_nesting_index(
                     postgres,
@@ -110,6 +178,16 @@ pub(crate) async fn update_event_nesting_indices(
                 )
                 .await?
             }
+            SubstrateEvent::Treasury(treasury_event) => {
+                update_treasury_event_nesting_index(
+                    postgres,
+                    block_hash,
+                    maybe_nesting_index,
+                    *event_index as i32,
+                    treasury_event,
+                )
+                .await?;
+            }
             SubstrateEvent::Utility(_) => (),
             SubstrateEvent::Identity(_) => {}
             SubstrateEvent::ImOnline(_) => {}
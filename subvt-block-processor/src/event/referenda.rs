@@ -1,7 +1,54 @@
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_substrate_client::SubstrateClient;
+use subvt_types::governance::call::ReferendumCall;
+use subvt_types::governance::referendum::ProposalCall;
 use subvt_types::substrate::event::referenda::ReferendaEvent;
 
+/// Fetches and decodes the proposal call of a submitted referendum, so that the submission
+/// notification can include it. Unavailable or undecodable proposal calls are logged and
+/// skipped, the referendum updater retries them while the referendum is ongoing.
+async fn get_submitted_proposal_call(
+    substrate_client: &SubstrateClient,
+    block_hash: &str,
+    referendum_index: u32,
+    proposal_call: ProposalCall,
+) -> Option<ReferendumCall> {
+    let bytes = match substrate_client
+        .get_proposal_call_bytes(proposal_call, block_hash)
+        .await
+    {
+        Ok(Some(bytes)) => bytes,
+        Ok(None) => {
+            log::debug!(
+                "Proposal call of submitted referendum #{} is not available.",
+                referendum_index
+            );
+            return None;
+        }
+        Err(error) => {
+            log::warn!(
+                "Cannot fetch the proposal call of submitted referendum #{}: {:?}",
+                referendum_index,
+                error
+            );
+            return None;
+        }
+    };
+    match substrate_client.decode_call_tree(&bytes) {
+        Ok(call_tree) => Some(call_tree),
+        Err(error) => {
+            log::warn!(
+                "Cannot decode the proposal call tree of submitted referendum #{}: {:?}",
+                referendum_index,
+                error
+            );
+            None
+        }
+    }
+}
+
 pub(crate) async fn process_referenda_event(
+    substrate_client: &SubstrateClient,
     postgres: &PostgreSQLNetworkStorage,
     block_hash: &str,
     event_index: usize,
@@ -119,9 +166,16 @@ pub(crate) async fn process_referenda_event(
             extrinsic_index,
             referendum_index,
             track_id,
-            proposal: _,
+            proposal,
         } => {
             let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            let maybe_proposal_call = get_submitted_proposal_call(
+                substrate_client,
+                block_hash,
+                *referendum_index,
+                ProposalCall::from(proposal),
+            )
+            .await;
             postgres
                 .save_referendum_submitted_event(
                     block_hash,
@@ -129,6 +183,7 @@ pub(crate) async fn process_referenda_event(
                     event_index as i32,
                     *referendum_index,
                     *track_id,
+                    maybe_proposal_call.as_ref(),
                 )
                 .await?;
         }
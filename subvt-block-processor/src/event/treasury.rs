@@ -0,0 +1,137 @@
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::event::treasury::TreasuryEvent;
+
+pub(crate) async fn process_treasury_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &TreasuryEvent,
+) -> anyhow::Result<()> {
+    match event {
+        TreasuryEvent::AssetSpendApproved {
+            extrinsic_index,
+            spend_index,
+            asset_kind,
+            amount,
+            beneficiary,
+            maybe_beneficiary_account_id,
+            valid_from,
+            expire_at,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_treasury_asset_spend_approved_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *spend_index,
+                    asset_kind,
+                    *amount,
+                    beneficiary,
+                    maybe_beneficiary_account_id.as_ref(),
+                    *valid_from,
+                    *expire_at,
+                )
+                .await?;
+        }
+        TreasuryEvent::Awarded {
+            extrinsic_index,
+            proposal_index,
+            amount,
+            beneficiary_account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_treasury_awarded_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *proposal_index,
+                    *amount,
+                    beneficiary_account_id,
+                )
+                .await?;
+        }
+        TreasuryEvent::Paid {
+            extrinsic_index,
+            spend_index,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_treasury_paid_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *spend_index,
+                )
+                .await?;
+        }
+        TreasuryEvent::SpendApproved {
+            extrinsic_index,
+            proposal_index,
+            amount,
+            beneficiary_account_id,
+        } => {
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_treasury_spend_approved_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    *proposal_index,
+                    *amount,
+                    beneficiary_account_id,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn update_treasury_event_nesting_index(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    maybe_nesting_index: &Option<String>,
+    event_index: i32,
+    event: &TreasuryEvent,
+) -> anyhow::Result<()> {
+    match event {
+        TreasuryEvent::AssetSpendApproved { .. } => {
+            postgres
+                .update_treasury_asset_spend_approved_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        TreasuryEvent::Awarded { .. } => {
+            postgres
+                .update_treasury_awarded_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        TreasuryEvent::Paid { .. } => {
+            postgres
+                .update_treasury_paid_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+        TreasuryEvent::SpendApproved { .. } => {
+            postgres
+                .update_treasury_spend_approved_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,359 @@
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::democracy::get_democracy_conviction_u8;
+use subvt_types::substrate::event::conviction_voting::ConvictionVotingEvent;
+use subvt_types::substrate::event::SubstrateEvent;
+use subvt_types::substrate::extrinsic::conviction_voting::ConvictionVotingExtrinsic;
+
+/// Older runtimes don't include the referendum index in the `Voted` and `VoteRemoved` events,
+/// so the index gets copied from the call to the events emitted by the call. The delegation
+/// events don't include the track, conviction and balance either, which get copied from the
+/// call, and the delegation state gets updated.
+pub(crate) async fn process_conviction_voting_extrinsic(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    block_number: u64,
+    call_events: &[(usize, SubstrateEvent)],
+    extrinsic: &ConvictionVotingExtrinsic,
+) -> anyhow::Result<()> {
+    match extrinsic {
+        ConvictionVotingExtrinsic::Delegate {
+            class,
+            conviction,
+            balance,
+            ..
+        } => {
+            for (event_index, event) in call_events {
+                if let SubstrateEvent::ConvictionVoting(ConvictionVotingEvent::Delegated {
+                    original_account_id,
+                    delegate_account_id,
+                    ..
+                }) = event
+                {
+                    let conviction = get_democracy_conviction_u8(conviction);
+                    postgres
+                        .set_conviction_voting_delegated_event_details(
+                            block_hash,
+                            *event_index as i32,
+                            *class,
+                            conviction,
+                            *balance,
+                        )
+                        .await?;
+                    postgres
+                        .save_conviction_voting_delegation(
+                            original_account_id,
+                            delegate_account_id,
+                            *class,
+                            conviction,
+                            *balance,
+                            block_hash,
+                            block_number,
+                        )
+                        .await?;
+                }
+            }
+        }
+        ConvictionVotingExtrinsic::Undelegate { class, .. } => {
+            for (event_index, event) in call_events {
+                if let SubstrateEvent::ConvictionVoting(ConvictionVotingEvent::Undelegated {
+                    account_id,
+                    ..
+                }) = event
+                {
+                    let maybe_delegate_account_id = postgres
+                        .delete_conviction_voting_delegation(account_id, *class, block_number)
+                        .await?;
+                    postgres
+                        .set_conviction_voting_undelegated_event_details(
+                            block_hash,
+                            *event_index as i32,
+                            *class,
+                            maybe_delegate_account_id.as_ref(),
+                        )
+                        .await?;
+                }
+            }
+        }
+        ConvictionVotingExtrinsic::Vote { poll_index, .. } => {
+            for (event_index, event) in call_events {
+                if let SubstrateEvent::ConvictionVoting(ConvictionVotingEvent::Voted {
+                    maybe_referendum_index: None,
+                    ..
+                }) = event
+                {
+                    postgres
+                        .set_conviction_voting_voted_event_referendum_index(
+                            block_hash,
+                            *event_index as i32,
+                            poll_index.0,
+                        )
+                        .await?;
+                }
+            }
+        }
+        ConvictionVotingExtrinsic::RemoveVote { index, .. } => {
+            for (event_index, event) in call_events {
+                if let SubstrateEvent::ConvictionVoting(ConvictionVotingEvent::VoteRemoved {
+                    maybe_referendum_index: None,
+                    ..
+                }) = event
+                {
+                    postgres
+                        .set_conviction_voting_vote_removed_event_referendum_index(
+                            block_hash,
+                            *event_index as i32,
+                            *index,
+                        )
+                        .await?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::process_conviction_voting_extrinsic;
+    use crate::CONFIG;
+    use rand::Rng;
+    use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+    use subvt_types::app::event::conviction_voting::ConvictionVotingDelegation;
+    use subvt_types::crypto::AccountId;
+    use subvt_types::substrate::event::conviction_voting::ConvictionVotingEvent;
+    use subvt_types::substrate::event::SubstrateEvent;
+    use subvt_types::substrate::extrinsic::conviction_voting::ConvictionVotingExtrinsic;
+    use subvt_types::substrate::{Balance, DemocracyConviction, MultiAddress};
+
+    fn get_random_account_id() -> AccountId {
+        AccountId::new(rand::thread_rng().gen())
+    }
+
+    async fn delegate(
+        postgres: &PostgreSQLNetworkStorage,
+        delegator_account_id: &AccountId,
+        delegate_account_id: &AccountId,
+        track_id: u16,
+        balance: Balance,
+        block_number: u64,
+    ) -> anyhow::Result<()> {
+        process_conviction_voting_extrinsic(
+            postgres,
+            &format!("0x{block_number:064x}"),
+            block_number,
+            &[(
+                0,
+                SubstrateEvent::ConvictionVoting(ConvictionVotingEvent::Delegated {
+                    extrinsic_index: Some(1),
+                    original_account_id: *delegator_account_id,
+                    delegate_account_id: *delegate_account_id,
+                }),
+            )],
+            &ConvictionVotingExtrinsic::Delegate {
+                maybe_signature: None,
+                class: track_id,
+                to: MultiAddress::Id(*delegate_account_id),
+                conviction: DemocracyConviction::Locked2x,
+                balance,
+            },
+        )
+        .await
+    }
+
+    async fn undelegate(
+        postgres: &PostgreSQLNetworkStorage,
+        delegator_account_id: &AccountId,
+        track_id: u16,
+        block_number: u64,
+    ) -> anyhow::Result<()> {
+        process_conviction_voting_extrinsic(
+            postgres,
+            &format!("0x{block_number:064x}"),
+            block_number,
+            &[(
+                0,
+                SubstrateEvent::ConvictionVoting(ConvictionVotingEvent::Undelegated {
+                    extrinsic_index: Some(1),
+                    account_id: *delegator_account_id,
+                }),
+            )],
+            &ConvictionVotingExtrinsic::Undelegate {
+                maybe_signature: None,
+                class: track_id,
+            },
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_delegate_and_undelegate() {
+        let postgres = PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url())
+            .await
+            .unwrap();
+        let delegator_account_id = get_random_account_id();
+        let delegate_account_id = get_random_account_id();
+        delegate(
+            &postgres,
+            &delegator_account_id,
+            &delegate_account_id,
+            2,
+            1_000,
+            1_000_000,
+        )
+        .await
+        .unwrap();
+        let delegations = postgres
+            .get_conviction_voting_delegations_to(&delegate_account_id)
+            .await
+            .unwrap();
+        assert_eq!(delegations.len(), 1);
+        assert_eq!(delegations[0].delegator_account_id, delegator_account_id);
+        assert_eq!(delegations[0].track_id, 2);
+        assert_eq!(delegations[0].conviction, 2);
+        assert_eq!(delegations[0].balance, 1_000);
+        assert_eq!(delegations[0].votes, 2_000);
+        // undelegation on another track doesn't affect the delegation
+        undelegate(&postgres, &delegator_account_id, 3, 1_000_001)
+            .await
+            .unwrap();
+        assert_eq!(
+            postgres
+                .get_conviction_voting_delegations_to(&delegate_account_id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        undelegate(&postgres, &delegator_account_id, 2, 1_000_002)
+            .await
+            .unwrap();
+        assert!(postgres
+            .get_conviction_voting_delegations_to(&delegate_account_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Reprocessing an older block must not overwrite or delete a newer delegation.
+    #[tokio::test]
+    async fn test_older_block_does_not_overwrite_delegation() {
+        let postgres = PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url())
+            .await
+            .unwrap();
+        let delegator_account_id = get_random_account_id();
+        let delegate_account_id = get_random_account_id();
+        let old_delegate_account_id = get_random_account_id();
+        delegate(
+            &postgres,
+            &delegator_account_id,
+            &delegate_account_id,
+            0,
+            1_000,
+            2_000_010,
+        )
+        .await
+        .unwrap();
+        delegate(
+            &postgres,
+            &delegator_account_id,
+            &old_delegate_account_id,
+            0,
+            500,
+            2_000_005,
+        )
+        .await
+        .unwrap();
+        undelegate(&postgres, &delegator_account_id, 0, 2_000_006)
+            .await
+            .unwrap();
+        assert!(postgres
+            .get_conviction_voting_delegations_to(&old_delegate_account_id)
+            .await
+            .unwrap()
+            .is_empty());
+        let delegations = postgres
+            .get_conviction_voting_delegations_to(&delegate_account_id)
+            .await
+            .unwrap();
+        assert_eq!(delegations.len(), 1);
+        assert_eq!(delegations[0].balance, 1_000);
+    }
+
+    /// The storage snapshot replaces the older delegations, and keeps the newer ones.
+    #[tokio::test]
+    async fn test_delegation_snapshot() {
+        let postgres = PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url())
+            .await
+            .unwrap();
+        let delegate_account_id = get_random_account_id();
+        let stale_delegator_account_id = get_random_account_id();
+        let newer_delegator_account_id = get_random_account_id();
+        let seeded_delegator_account_id = get_random_account_id();
+        delegate(
+            &postgres,
+            &stale_delegator_account_id,
+            &delegate_account_id,
+            1,
+            100,
+            5,
+        )
+        .await
+        .unwrap();
+        delegate(
+            &postgres,
+            &newer_delegator_account_id,
+            &delegate_account_id,
+            1,
+            200,
+            3_000_000,
+        )
+        .await
+        .unwrap();
+        let snapshot: Vec<ConvictionVotingDelegation> = [
+            (newer_delegator_account_id, 50),
+            (seeded_delegator_account_id, 300),
+        ]
+        .iter()
+        .map(
+            |(delegator_account_id, balance)| ConvictionVotingDelegation {
+                delegator_account_id: *delegator_account_id,
+                delegate_account_id,
+                track_id: 1,
+                conviction: 0,
+                balance: *balance,
+                votes: balance / 10,
+                block_number: 10,
+            },
+        )
+        .collect();
+        postgres
+            .save_conviction_voting_delegation_snapshot(&snapshot, &format!("0x{:064x}", 10), 10)
+            .await
+            .unwrap();
+        let delegations = postgres
+            .get_conviction_voting_delegations_to(&delegate_account_id)
+            .await
+            .unwrap();
+        assert_eq!(delegations.len(), 2);
+        let newer_delegation = delegations
+            .iter()
+            .find(|delegation| delegation.delegator_account_id == newer_delegator_account_id)
+            .unwrap();
+        assert_eq!(newer_delegation.balance, 200);
+        assert_eq!(newer_delegation.block_number, 3_000_000);
+        let seeded_delegation = delegations
+            .iter()
+            .find(|delegation| delegation.delegator_account_id == seeded_delegator_account_id)
+            .unwrap();
+        assert_eq!(seeded_delegation.balance, 300);
+        assert_eq!(seeded_delegation.votes, 30);
+        assert!(postgres
+            .conviction_voting_delegate_exists(&delegate_account_id)
+            .await
+            .unwrap());
+        assert!(!postgres
+            .conviction_voting_delegate_exists(&get_random_account_id())
+            .await
+            .unwrap());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::event::update_event_nesting_indices;
+use crate::extrinsic::conviction_voting::process_conviction_voting_extrinsic;
 use crate::extrinsic::imonline::process_imonline_extrinsic;
 use crate::extrinsic::staking::process_staking_extrinsic;
 use crate::BlockProcessor;
@@ -12,6 +13,7 @@ use subvt_types::substrate::event::{
 };
 use subvt_types::substrate::extrinsic::SubstrateExtrinsic;
 
+mod conviction_voting;
 mod imonline;
 mod multisig;
 mod proxy;
@@ -105,6 +107,22 @@ impl BlockProcessor {
         extrinsic: &SubstrateExtrinsic,
     ) -> anyhow::Result<bool> {
         match extrinsic {
+            SubstrateExtrinsic::ConvictionVoting(conviction_voting_extrinsic) => {
+                let call_events = events.clone();
+                let is_successful = !batch_fail
+                    && consume_call_events(postgres, &block_hash, maybe_nesting_index, events)
+                        .await?;
+                let consumed_event_count = call_events.len() - events.len();
+                process_conviction_voting_extrinsic(
+                    postgres,
+                    &block_hash,
+                    block_number,
+                    &call_events[0..consumed_event_count],
+                    conviction_voting_extrinsic,
+                )
+                .await?;
+                Ok(is_successful)
+            }
             SubstrateExtrinsic::ImOnline(imonline_extrinsic) => {
                 let is_successful = !batch_fail
                     && consume_call_events(postgres, &block_hash, maybe_nesting_index, events)
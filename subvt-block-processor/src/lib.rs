@@ -30,6 +30,7 @@ mod metrics;
 lazy_static! {
     static ref CONFIG: Config = Config::default();
     static ref IS_BUSY: AtomicBool = AtomicBool::new(false);
+    static ref CONVICTION_VOTING_DELEGATIONS_ARE_SEEDED: AtomicBool = AtomicBool::new(false);
 }
 
 #[derive(Default)]
@@ -119,6 +120,28 @@ impl BlockProcessor {
         Ok(())
     }
 
+    /// The delegation bookkeeping only follows the `delegate` and `undelegate` calls, so the
+    /// delegations made before the processed block range get seeded from the chain storage.
+    async fn seed_conviction_voting_delegations(
+        &self,
+        substrate_client: &SubstrateClient,
+        postgres: &PostgreSQLNetworkStorage,
+        block_number: u64,
+    ) -> anyhow::Result<()> {
+        let block_hash = substrate_client.get_block_hash(block_number).await?;
+        let delegations = substrate_client
+            .get_conviction_voting_delegations(block_number, &block_hash)
+            .await?;
+        log::info!(
+            "Seed {} conviction voting delegations at block #{}.",
+            delegations.len(),
+            block_number,
+        );
+        postgres
+            .save_conviction_voting_delegation_snapshot(&delegations, &block_hash, block_number)
+            .await
+    }
+
     async fn process_block(
         &self,
         substrate_client: &mut SubstrateClient,
@@ -618,6 +641,20 @@ impl Service for BlockProcessor {
                             match update_result {
                                 Ok(_) => {
                                     metrics::processed_finalized_block_number().set(finalized_block_number as i64);
+                                    if !CONVICTION_VOTING_DELEGATIONS_ARE_SEEDED.load(Ordering::SeqCst) {
+                                        match self.seed_conviction_voting_delegations(
+                                            &block_processor_substrate_client,
+                                            &postgres,
+                                            finalized_block_number,
+                                        ).await {
+                                            Ok(_) => CONVICTION_VOTING_DELEGATIONS_ARE_SEEDED.store(true, Ordering::SeqCst),
+                                            Err(error) => log::error!(
+                                                "Cannot seed conviction voting delegations at block #{}. Will try again with the next block: {:?}",
+                                                finalized_block_number,
+                                                error,
+                                            ),
+                                        }
+                                    }
                                 },
                                 Err(error) => {
                                     log::error!("{:?}", error);
@@ -2,6 +2,7 @@
 
 use serde::Deserialize;
 use std::fmt;
+use std::net::IpAddr;
 
 const DEFAULT_CONFIG_DIR: &str = "./config";
 const DEV_CONFIG_DIR: &str = "../_config";
@@ -139,6 +140,8 @@ pub struct BlockProcessorConfig {
 pub struct ValidatorListUpdaterConfig {
     /// Keep this many records in Redis for the past validator list updates.
     pub history_record_depth: u64,
+    /// Persist a downsampled validator snapshot to PostgreSQL every this many blocks.
+    pub snapshot_period_blocks: u64,
 }
 
 /// 1KV configuration - only used for Polkadot and Kusama.
@@ -156,18 +159,67 @@ pub struct OneKVConfig {
 pub struct ReportConfig {
     pub max_era_index_range: u32,
     pub max_session_index_range: u32,
+    /// Number of past eras evaluated by the nomination optimizer.
+    pub nomination_optimizer_history_era_count: u32,
+    /// Maximum time range of a time series report in days.
+    pub max_time_series_range_days: u32,
 }
 
 /// Telemetry processor configuration.
 #[derive(Clone, Debug, Deserialize)]
 pub struct TelemetryConfig {
-    pub websocket_url: String,
+    /// Comma-separated feed URLs of the Telemetry servers, such as the public servers and the
+    /// private servers of the operator. The first feed is the reference for the network's best
+    /// and finalized blocks. Accepts the single-feed `websocket_url` setting of the earlier
+    /// configurations too.
+    #[serde(alias = "websocket_url")]
+    websocket_urls: String,
+    /// Telemetry nodes get matched to the validators, and the stats of the validator nodes get
+    /// snapshotted, once in this many seconds.
+    pub validator_node_mapping_period_seconds: u64,
+    /// Request the GRANDPA messages (finalized blocks and received votes) of the nodes from the
+    /// feeds, to track the finality participation of the validator nodes.
+    pub receive_finality: bool,
+    /// Source of the latest client release that the node versions get compared to.
+    pub latest_version_source: TelemetryLatestVersionSource,
+    /// Latest client version, e.g. `1.3.0`, used with the `config` source. Empty if not known.
+    pub latest_version: String,
+    /// Path of the local JSON release manifest, used with the `manifest` source. See
+    /// `subvt_types::telemetry::version::TelemetryReleaseManifest` for the format.
+    pub release_manifest_path: String,
+}
+
+/// Source of the latest client release.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TelemetryLatestVersionSource {
+    /// `latest_version` in the Telemetry configuration.
+    Config,
+    /// Release manifest file at `release_manifest_path`, re-read on every check.
+    Manifest,
+}
+
+impl TelemetryConfig {
+    pub fn get_websocket_urls(&self) -> Vec<String> {
+        self.websocket_urls
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect()
+    }
 }
 
 /// Notification generator configuration.
 #[derive(Clone, Debug, Deserialize)]
 pub struct NotificationGeneratorConfig {
     pub unclaimed_payout_check_delay_hours: u32,
+    /// Referendum vote reminders get checked once in this many blocks.
+    pub referendum_vote_reminder_check_period_blocks: u64,
+    /// Finality participation of the validator nodes gets checked once in this many blocks.
+    pub telemetry_finality_check_period_blocks: u64,
+    /// Client versions of the validator nodes get checked against the latest release once in
+    /// this many blocks.
+    pub telemetry_version_check_period_blocks: u64,
 }
 
 /// Notification sender configuration.
@@ -202,7 +254,16 @@ pub struct TelegramBotConfig {
     pub api_token: String,
     admin_chat_ids: String,
     pub max_validators_per_chat: u16,
+    /// Operator groups usually manage more validators than private chats.
+    pub max_validators_per_group_chat: u16,
     pub username: String,
+    /// Deleted (blocked) chats and their app users are purged after this many days.
+    pub deleted_chat_retention_days: u16,
+    /// Validator list searched by the inline queries is reloaded every this many seconds.
+    pub inline_query_cache_seconds: u64,
+    /// An inline query is answered only if no newer query arrives from the same user in this
+    /// many milliseconds, queries are sent as the user types.
+    pub inline_query_debounce_millis: u64,
 }
 
 impl TelegramBotConfig {
@@ -242,6 +303,10 @@ pub struct PlotterConfig {
     pub tmp_dir_path: String,
     pub font_dir_path: String,
     pub font_sans_serif_family: String,
+    /// Number of eras displayed in the era-based validator charts.
+    pub chart_era_count: u32,
+    /// Number of sessions displayed in the para votes chart.
+    pub chart_session_count: u32,
 }
 
 /// Sub.id NFTs API configuration.
@@ -256,12 +321,68 @@ pub struct SubIDConfig {
 pub struct AppServiceConfig {
     pub user_registration_per_ip_limit_time_window_mins: u16,
     pub user_registration_per_ip_limit: u16,
+    /// Expiry of the one-time codes that are created by a registered device to link a new one.
+    pub device_link_code_expiry_mins: u16,
+    /// The nonce of a signed request is its millisecond timestamp, and requests with nonces
+    /// further than this many seconds from the server time are rejected.
+    pub auth_nonce_window_secs: u16,
+    /// Unix timestamp in seconds until which nonces that cannot be millisecond timestamps, sent
+    /// by the clients that predate the timestamp nonces, are still accepted. Zero rejects them.
+    pub auth_legacy_nonce_deadline: u64,
+    /// Expiry of the challenges to be signed by validator stash accounts to verify ownership.
+    pub validator_ownership_challenge_expiry_mins: u16,
+    /// Expiry of the challenges to be signed by validator stash accounts to sign in a new device.
+    pub sign_in_challenge_expiry_mins: u16,
+    /// Deleted users without Telegram chats are purged after this many days.
+    pub deleted_user_retention_days: u16,
+}
+
+/// Rate limiting configuration for the public HTTP and WebSocket RPC services. Every request has
+/// a cost, and the total cost of the requests from an IP address, signed with a public key, and
+/// of all the requests to a service is limited per time window.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RateLimitConfig {
+    pub is_enabled: bool,
+    /// Whether the requests are allowed or rejected when the budgets cannot be checked, e.g.
+    /// when Redis is not available. Rejected requests get `503 Service Unavailable`.
+    pub allow_on_redis_error: bool,
+    pub time_window_secs: u32,
+    /// Total request cost allowed for an IP address per time window.
+    pub per_ip_budget: u32,
+    /// Total request cost allowed for a public key (`SubVT-Public-Key` header) per time window.
+    pub per_key_budget: u32,
+    /// Total request cost allowed for all the clients of a service per time window.
+    pub global_budget: u32,
+    /// Max concurrent WebSocket connections from an IP address to an RPC server.
+    pub max_connections_per_ip: u32,
+    /// Max concurrent subscriptions on a single WebSocket connection.
+    pub max_subscriptions_per_connection: u32,
+    /// Max concurrent subscriptions from an IP address to an RPC server.
+    pub max_subscriptions_per_ip: u32,
+    /// Comma-separated IP addresses of the reverse proxies in front of the services. The client
+    /// address is read from the forwarded headers only for the connections from these addresses.
+    trusted_proxy_ips: String,
+}
+
+impl RateLimitConfig {
+    /// Invalid addresses are ignored.
+    pub fn get_trusted_proxy_ips(&self) -> Vec<IpAddr> {
+        self.trusted_proxy_ips
+            .replace(' ', "")
+            .split(',')
+            .filter_map(|item| item.parse::<IpAddr>().ok())
+            .collect()
+    }
 }
 
-/// Referendum updater configuration - fetches data from Polkassembly.
+/// Referendum updater configuration - reads the referenda from the chain, optionally enriched
+/// with the titles and descriptions from Polkassembly.
 #[derive(Clone, Debug, Deserialize)]
 pub struct ReferendumUpdaterConfig {
     pub refresh_seconds: u64,
+    pub enrich_from_polkassembly: bool,
+    /// Upper limit of the backoff of the Polkassembly fetches that fail or return no title.
+    pub enrich_max_backoff_seconds: u64,
 }
 
 /// Whole configuration.
@@ -288,6 +409,7 @@ pub struct Config {
     pub plotter: PlotterConfig,
     pub sub_id: SubIDConfig,
     pub app_service: AppServiceConfig,
+    pub rate_limit: RateLimitConfig,
     pub referendum_updater: ReferendumUpdaterConfig,
 }
 
@@ -10,6 +10,7 @@ use lazy_static::lazy_static;
 use redis::aio::Connection;
 use std::sync::{Arc, Mutex, RwLock};
 use subvt_config::Config;
+use subvt_service_common::rate_limit::{self, RateLimiter};
 use subvt_service_common::Service;
 use subvt_types::subvt::{NetworkStatus, NetworkStatusDiff, NetworkStatusUpdate};
 
@@ -46,8 +47,15 @@ impl NetworkStatusServer {
     async fn run_rpc_server(
         current_status: &Arc<RwLock<NetworkStatus>>,
         bus: &Arc<Mutex<Bus<BusEvent>>>,
+        rate_limiter: &RateLimiter,
     ) -> anyhow::Result<ServerHandle> {
+        let (rate_limit_middleware, rate_limit_logger) = rate_limit::rpc::build(rate_limiter, &[]);
         let rpc_ws_server = ServerBuilder::default()
+            .max_subscriptions_per_connection(
+                rate_limiter.config().max_subscriptions_per_connection,
+            )
+            .set_middleware(rate_limit_middleware)
+            .set_logger(rate_limit_logger)
             .build(format!(
                 "{}:{}",
                 CONFIG.rpc.host, CONFIG.rpc.network_status_port
@@ -61,6 +69,9 @@ impl NetworkStatusServer {
             "subscribe_networkStatus",
             "unsubscribe_networkStatus",
             move |_params, mut sink, _| {
+                if rate_limit::rpc::reject_if_rate_limited(&mut sink) {
+                    return Ok(());
+                }
                 log::info!("New subscription.");
                 metrics::subscription_count().inc();
                 let mut bus_receiver = bus.lock().unwrap().add_rx();
@@ -149,7 +160,9 @@ impl Service for NetworkStatusServer {
             .await?;
         let mut data_connection = redis_client.get_async_connection().await?;
         metrics::subscription_count().set(0);
-        let server_stop_handle = NetworkStatusServer::run_rpc_server(&current_status, &bus).await?;
+        let rate_limiter = RateLimiter::new(&CONFIG, "subvt_network_status_server").await?;
+        let server_stop_handle =
+            NetworkStatusServer::run_rpc_server(&current_status, &bus, &rate_limiter).await?;
 
         let mut pubsub_stream = pubsub_connection.on_message();
         let error: anyhow::Error = loop {
@@ -0,0 +1,67 @@
+//! Notifies OpenGov delegates of the delegations to and the undelegations from their accounts.
+use crate::{NotificationGenerator, CONFIG};
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::{notification::NotificationTypeCode, Block};
+
+impl NotificationGenerator {
+    pub(crate) async fn inspect_delegation_events(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        block: &Block,
+    ) -> anyhow::Result<()> {
+        log::debug!(
+            "Inspect block #{} for OpenGov delegations and undelegations.",
+            block.number
+        );
+        for event in network_postgres
+            .get_conviction_voting_delegated_events_in_block(&block.hash)
+            .await?
+        {
+            let rules = app_postgres
+                .get_notification_rules_for_validator(
+                    &NotificationTypeCode::ConvictionVotingDelegated.to_string(),
+                    CONFIG.substrate.network_id,
+                    &event.delegate_account_id,
+                )
+                .await?;
+            self.generate_notifications(
+                app_postgres.clone(),
+                &rules,
+                block.number,
+                &Some(event.delegate_account_id),
+                Some(&event.clone()),
+            )
+            .await?;
+        }
+        for event in network_postgres
+            .get_conviction_voting_undelegated_events_in_block(&block.hash)
+            .await?
+        {
+            // the delegate is not known for the delegations made before the indexing
+            let delegate_account_id = if let Some(delegate_account_id) = event.delegate_account_id {
+                delegate_account_id
+            } else {
+                continue;
+            };
+            let rules = app_postgres
+                .get_notification_rules_for_validator(
+                    &NotificationTypeCode::ConvictionVotingUndelegated.to_string(),
+                    CONFIG.substrate.network_id,
+                    &delegate_account_id,
+                )
+                .await?;
+            self.generate_notifications(
+                app_postgres.clone(),
+                &rules,
+                block.number,
+                &Some(delegate_account_id),
+                Some(&event.clone()),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
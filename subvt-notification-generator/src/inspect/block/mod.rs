@@ -11,9 +11,11 @@ use subvt_types::rdb::BlockProcessedNotification;
 
 mod authorship;
 mod chilling;
+mod delegation;
 mod offence;
 mod payout;
 mod referenda;
+mod referendum_tally;
 mod validate;
 
 impl NotificationGenerator {
@@ -45,8 +47,12 @@ impl NotificationGenerator {
             &block,
         )
         .await?;
+        self.inspect_delegation_events(network_postgres.clone(), app_postgres.clone(), &block)
+            .await?;
         self.inspect_referenda_events(network_postgres.clone(), app_postgres.clone(), &block)
             .await?;
+        self.inspect_referendum_tallies(network_postgres.clone(), app_postgres.clone(), &block)
+            .await?;
 
         network_postgres
             .save_notification_generator_state(&block.hash, block_number)
@@ -139,6 +145,7 @@ impl NotificationGenerator {
                     Ok(())
                 })
                 .await;
+            self.reset_substrate_client().await;
             let delay_seconds = CONFIG.common.recovery_retry_seconds;
             log::error!(
                 "Block inspection exited. Will restart after {} seconds.",
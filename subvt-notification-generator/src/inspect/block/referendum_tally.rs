@@ -0,0 +1,389 @@
+//! Tracks the tallies of the ongoing referenda block by block, and notifies of approval and
+//! support threshold crossings, confirmation starts and aborts, and tally changes beyond the
+//! percentage set in the notification rule.
+use crate::inspect::get_rule_param;
+use crate::{NotificationGenerator, CONFIG};
+use rustc_hash::FxHashMap as HashMap;
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::app_event::{
+    ReferendumConfirmingChanged, ReferendumTallyChanged, ReferendumThreshold,
+    ReferendumThresholdCrossed,
+};
+use subvt_types::app::notification::{NotificationTypeCode, UserNotificationRule};
+use subvt_types::app::Block;
+use subvt_types::governance::referendum::{
+    Referendum, ReferendumInfo, ReferendumTallyState, TrackInfo,
+};
+
+const TRACK_IDS_PARAM_CODE: &str = "track_ids";
+const CHANGE_PERCENT_PARAM_CODE: &str = "change_percent";
+const DEFAULT_CHANGE_PERCENT: f64 = 10.0;
+
+/// Whether the rule follows the referenda in the given track. Rules without the track ids
+/// parameter follow all tracks.
+fn rule_follows_track(rule: &UserNotificationRule, track_id: u16) -> bool {
+    if let Some(track_ids) = get_rule_param(rule, TRACK_IDS_PARAM_CODE) {
+        let mut follows_track = false;
+        for value in track_ids.split(',') {
+            match value.trim().parse::<u16>() {
+                Ok(rule_track_id) => follows_track |= rule_track_id == track_id,
+                Err(_) => {
+                    log::error!(
+                        "Invalid {} parameter for rule #{}.",
+                        TRACK_IDS_PARAM_CODE,
+                        rule.id,
+                    );
+                    return false;
+                }
+            }
+        }
+        follows_track
+    } else {
+        true
+    }
+}
+
+fn get_track_rules(rules: &[UserNotificationRule], track_id: u16) -> Vec<UserNotificationRule> {
+    rules
+        .iter()
+        .filter(|rule| rule_follows_track(rule, track_id))
+        .cloned()
+        .collect()
+}
+
+struct ReferendumTallyRules {
+    threshold_crossed: Vec<UserNotificationRule>,
+    confirm_started: Vec<UserNotificationRule>,
+    confirm_aborted: Vec<UserNotificationRule>,
+    tally_changed: Vec<UserNotificationRule>,
+}
+
+impl ReferendumTallyRules {
+    fn is_empty(&self) -> bool {
+        self.threshold_crossed.is_empty()
+            && self.confirm_started.is_empty()
+            && self.confirm_aborted.is_empty()
+            && self.tally_changed.is_empty()
+    }
+}
+
+impl NotificationGenerator {
+    pub(crate) async fn inspect_referendum_tallies(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        block: &Block,
+    ) -> anyhow::Result<()> {
+        let mut rule_lists = Vec::with_capacity(4);
+        for notification_type_code in [
+            NotificationTypeCode::ReferendumThresholdCrossed,
+            NotificationTypeCode::ReferendumConfirmStarted,
+            NotificationTypeCode::ReferendumConfirmAborted,
+            NotificationTypeCode::ReferendumTallyChanged,
+        ] {
+            rule_lists.push(
+                app_postgres
+                    .get_notification_rules_by_type(
+                        &notification_type_code.to_string(),
+                        CONFIG.substrate.network_id,
+                    )
+                    .await?,
+            );
+        }
+        let rules = ReferendumTallyRules {
+            tally_changed: rule_lists.pop().unwrap_or_default(),
+            confirm_aborted: rule_lists.pop().unwrap_or_default(),
+            confirm_started: rule_lists.pop().unwrap_or_default(),
+            threshold_crossed: rule_lists.pop().unwrap_or_default(),
+        };
+        let open_referenda = network_postgres.get_open_referenda(None).await?;
+        if rules.is_empty() || open_referenda.is_empty() {
+            // drop the tracked states, so that stale states don't trigger notifications when
+            // the tracking resumes
+            network_postgres
+                .delete_notification_generator_finished_referendum_tally_states(&[])
+                .await?;
+            return Ok(());
+        }
+        log::debug!(
+            "Inspect block #{} for the tallies of {} open referenda.",
+            block.number,
+            open_referenda.len(),
+        );
+        let mut substrate_client = self.get_substrate_client().await?;
+        // the track definitions and the storage keys are read from the metadata of the client
+        if substrate_client
+            .get_last_runtime_upgrade_info(&block.hash)
+            .await?
+            .spec_version
+            > substrate_client.last_runtime_upgrade_info.spec_version
+        {
+            log::info!("Runtime upgraded. Reset the Substrate client.");
+            self.reset_substrate_client().await;
+            substrate_client = self.get_substrate_client().await?;
+        }
+        let tracks: HashMap<u16, TrackInfo> = substrate_client
+            .get_referendum_tracks()?
+            .into_iter()
+            .collect();
+        let active_issuance = substrate_client.get_active_issuance(&block.hash).await?;
+        let prev_states: HashMap<u32, ReferendumTallyState> = network_postgres
+            .get_notification_generator_referendum_tally_states()
+            .await?
+            .into_iter()
+            .map(|state| (state.index, state))
+            .collect();
+        let indices: Vec<u32> = open_referenda
+            .iter()
+            .map(|referendum| referendum.post_id)
+            .collect();
+        let mut ongoing_indices = Vec::with_capacity(indices.len());
+        for (index, info) in substrate_client
+            .get_referenda_by_index(&indices, &block.hash)
+            .await?
+        {
+            let maybe_track = match &info {
+                ReferendumInfo::Ongoing(referendum) => tracks.get(&referendum.track_id),
+                _ => None,
+            };
+            let referendum = Referendum::new(
+                index,
+                &info,
+                maybe_track,
+                &[],
+                active_issuance,
+                block.number as u32,
+            );
+            let state = if let Some(state) = ReferendumTallyState::new(&referendum, block.number) {
+                state
+            } else {
+                continue;
+            };
+            ongoing_indices.push(index);
+            let maybe_title = open_referenda
+                .iter()
+                .find(|referendum| referendum.post_id == index)
+                .and_then(|referendum| referendum.maybe_title.clone());
+            if let Some(prev_state) = prev_states.get(&index) {
+                if prev_state.block_number < block.number {
+                    self.inspect_referendum_threshold_crossings(
+                        &app_postgres,
+                        &rules.threshold_crossed,
+                        prev_state,
+                        &state,
+                        &maybe_title,
+                    )
+                    .await?;
+                    self.inspect_referendum_confirming_change(
+                        &app_postgres,
+                        &rules,
+                        prev_state,
+                        &state,
+                        &maybe_title,
+                    )
+                    .await?;
+                }
+            }
+            self.inspect_referendum_tally_change(
+                &network_postgres,
+                &app_postgres,
+                &rules.tally_changed,
+                &state,
+                &maybe_title,
+            )
+            .await?;
+            network_postgres
+                .save_notification_generator_referendum_tally_state(&state)
+                .await?;
+        }
+        network_postgres
+            .delete_notification_generator_finished_referendum_tally_states(&ongoing_indices)
+            .await?;
+        Ok(())
+    }
+
+    async fn inspect_referendum_threshold_crossings(
+        &self,
+        app_postgres: &Arc<PostgreSQLAppStorage>,
+        rules: &[UserNotificationRule],
+        prev_state: &ReferendumTallyState,
+        state: &ReferendumTallyState,
+        maybe_title: &Option<String>,
+    ) -> anyhow::Result<()> {
+        let rules = get_track_rules(rules, state.track_id);
+        if rules.is_empty() {
+            return Ok(());
+        }
+        for (threshold, maybe_prev_is_above, maybe_is_above, value, maybe_threshold_value) in [
+            (
+                ReferendumThreshold::Approval,
+                prev_state.is_approval_passing(),
+                state.is_approval_passing(),
+                state.approval_per_billion,
+                state.maybe_min_approval_per_billion,
+            ),
+            (
+                ReferendumThreshold::Support,
+                prev_state.is_support_passing(),
+                state.is_support_passing(),
+                state.support_per_billion,
+                state.maybe_min_support_per_billion,
+            ),
+        ] {
+            if let (Some(prev_is_above), Some(is_above), Some(threshold_value)) =
+                (maybe_prev_is_above, maybe_is_above, maybe_threshold_value)
+            {
+                if prev_is_above == is_above {
+                    continue;
+                }
+                log::info!(
+                    "Referendum #{} {:?} has crossed the threshold, is above: {}.",
+                    state.index,
+                    threshold,
+                    is_above,
+                );
+                let crossing = ReferendumThresholdCrossed {
+                    referendum_index: state.index,
+                    track_id: state.track_id,
+                    maybe_title: maybe_title.clone(),
+                    threshold,
+                    is_above,
+                    value_per_billion: value,
+                    threshold_per_billion: threshold_value,
+                    is_passing: state.is_passing(),
+                };
+                self.generate_notifications(
+                    app_postgres.clone(),
+                    &rules,
+                    state.block_number,
+                    &None,
+                    Some(&crossing),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn inspect_referendum_confirming_change(
+        &self,
+        app_postgres: &Arc<PostgreSQLAppStorage>,
+        rules: &ReferendumTallyRules,
+        prev_state: &ReferendumTallyState,
+        state: &ReferendumTallyState,
+        maybe_title: &Option<String>,
+    ) -> anyhow::Result<()> {
+        if prev_state.is_confirming == state.is_confirming {
+            return Ok(());
+        }
+        // a referendum that completes its confirmation is not ongoing anymore, so leaving the
+        // confirmation while still ongoing means the confirmation got aborted
+        let rules = if state.is_confirming {
+            get_track_rules(&rules.confirm_started, state.track_id)
+        } else {
+            get_track_rules(&rules.confirm_aborted, state.track_id)
+        };
+        if rules.is_empty() {
+            return Ok(());
+        }
+        log::info!(
+            "Referendum #{} confirming: {}.",
+            state.index,
+            state.is_confirming,
+        );
+        let change = ReferendumConfirmingChanged {
+            referendum_index: state.index,
+            track_id: state.track_id,
+            maybe_title: maybe_title.clone(),
+            is_confirming: state.is_confirming,
+            maybe_confirm_end_block_number: state
+                .maybe_confirm_end_block_number
+                .map(|block_number| block_number as u64),
+        };
+        self.generate_notifications(
+            app_postgres.clone(),
+            &rules,
+            state.block_number,
+            &None,
+            Some(&change),
+        )
+        .await
+    }
+
+    /// The tally of a referendum is compared to its tally at the last notification for each
+    /// rule. The tally at the first inspection of a referendum for a rule only sets the baseline.
+    async fn inspect_referendum_tally_change(
+        &self,
+        network_postgres: &Arc<PostgreSQLNetworkStorage>,
+        app_postgres: &Arc<PostgreSQLAppStorage>,
+        rules: &[UserNotificationRule],
+        state: &ReferendumTallyState,
+        maybe_title: &Option<String>,
+    ) -> anyhow::Result<()> {
+        for rule in get_track_rules(rules, state.track_id) {
+            let change_percent_threshold = match get_rule_param(&rule, CHANGE_PERCENT_PARAM_CODE) {
+                Some(value) => match value.parse::<f64>() {
+                    Ok(change_percent) => change_percent,
+                    Err(_) => {
+                        log::error!(
+                            "Invalid {} parameter for rule #{}.",
+                            CHANGE_PERCENT_PARAM_CODE,
+                            rule.id,
+                        );
+                        continue;
+                    }
+                },
+                None => DEFAULT_CHANGE_PERCENT,
+            };
+            let prev_tally = if let Some(prev_tally) = network_postgres
+                .get_notification_generator_referendum_tally_alert(rule.id, state.index)
+                .await?
+            {
+                prev_tally
+            } else {
+                network_postgres
+                    .save_notification_generator_referendum_tally_alert(rule.id, state)
+                    .await?;
+                continue;
+            };
+            let change_percent = state.get_max_change_percent(prev_tally);
+            if change_percent <= change_percent_threshold {
+                continue;
+            }
+            log::info!(
+                "Referendum #{} tally changed by {:.2}% for rule #{}.",
+                state.index,
+                change_percent,
+                rule.id,
+            );
+            let change = ReferendumTallyChanged {
+                referendum_index: state.index,
+                track_id: state.track_id,
+                maybe_title: maybe_title.clone(),
+                prev_ayes: prev_tally.0,
+                prev_nays: prev_tally.1,
+                prev_support: prev_tally.2,
+                ayes: state.ayes,
+                nays: state.nays,
+                support: state.support,
+                approval_per_billion: state.approval_per_billion,
+                support_per_billion: state.support_per_billion,
+                change_percent,
+            };
+            self.generate_notifications(
+                app_postgres.clone(),
+                &[rule.clone()],
+                state.block_number,
+                &None,
+                Some(&change),
+            )
+            .await?;
+            network_postgres
+                .save_notification_generator_referendum_tally_alert(rule.id, state)
+                .await?;
+        }
+        Ok(())
+    }
+}
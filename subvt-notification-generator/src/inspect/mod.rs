@@ -1,4 +1,13 @@
 //! Contains block and validator list processor modules.
+use subvt_types::app::notification::UserNotificationRule;
 
 pub mod block;
 pub mod validator_list;
+
+/// Value of the rule parameter with the given code, if it's set for the rule.
+pub(crate) fn get_rule_param<'a>(rule: &'a UserNotificationRule, code: &str) -> Option<&'a str> {
+    rule.parameters
+        .iter()
+        .find(|param| param.parameter_type_code == code)
+        .map(|param| param.value.as_str())
+}
@@ -7,7 +7,7 @@ use anyhow::Context;
 use futures_util::StreamExt as _;
 use redis::aio::Connection as RedisConnection;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicU32, AtomicU64};
 use std::sync::Arc;
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
@@ -16,12 +16,16 @@ use subvt_types::subvt::ValidatorDetails;
 mod add;
 mod init;
 mod onekv;
+mod referendum_vote_reminder;
 mod remove;
+mod telemetry_finality;
+mod telemetry_version;
 mod unclaimed_payout;
 mod update;
 
 impl NotificationGenerator {
     /// Called after each validator list update PUBLISH event.
+    #[allow(clippy::too_many_arguments)]
     async fn inspect_validator_list_update(
         &self,
         network_postgres: Arc<PostgreSQLNetworkStorage>,
@@ -30,6 +34,9 @@ impl NotificationGenerator {
         validator_map: &mut HashMap<String, ValidatorDetails>,
         finalized_block_number: u64,
         last_active_era_index: &AtomicU32,
+        last_referendum_vote_reminder_check_block_number: &AtomicU64,
+        last_telemetry_finality_check_block_number: &AtomicU64,
+        last_telemetry_version_check_block_number: &AtomicU64,
     ) -> anyhow::Result<()> {
         log::info!(
             "Process new update from validator list updater. Block #{}.",
@@ -115,8 +122,8 @@ impl NotificationGenerator {
         }
         // unclaimed payouts
         self.inspect_unclaimed_payouts(
-            network_postgres,
-            app_postgres,
+            network_postgres.clone(),
+            app_postgres.clone(),
             redis_connection,
             &redis_storage_prefix,
             last_active_era_index,
@@ -124,6 +131,33 @@ impl NotificationGenerator {
             validator_map,
         )
         .await?;
+        // referendum vote reminders
+        self.inspect_referendum_vote_reminders(
+            network_postgres.clone(),
+            app_postgres.clone(),
+            last_referendum_vote_reminder_check_block_number,
+            finalized_block_number,
+            validator_map,
+        )
+        .await?;
+        // finality participation of the validator nodes
+        self.inspect_telemetry_finality_participation(
+            network_postgres.clone(),
+            app_postgres.clone(),
+            last_telemetry_finality_check_block_number,
+            finalized_block_number,
+            validator_map,
+        )
+        .await?;
+        // client versions of the validator nodes
+        self.inspect_telemetry_version(
+            network_postgres,
+            app_postgres,
+            last_telemetry_version_check_block_number,
+            finalized_block_number,
+            validator_map,
+        )
+        .await?;
         Ok(())
     }
 
@@ -154,6 +188,9 @@ impl NotificationGenerator {
             // keep track of validators
             let mut validator_map: HashMap<String, ValidatorDetails> = HashMap::default();
             let last_active_era_index = AtomicU32::new(0);
+            let last_referendum_vote_reminder_check_block_number = AtomicU64::new(0);
+            let last_telemetry_finality_check_block_number = AtomicU64::new(0);
+            let last_telemetry_version_check_block_number = AtomicU64::new(0);
             let mut pubsub_stream = redis_pubsub_connection.on_message();
             let error: anyhow::Error = loop {
                 let maybe_message = pubsub_stream.next().await;
@@ -182,6 +219,9 @@ impl NotificationGenerator {
                         &mut validator_map,
                         finalized_block_number,
                         &last_active_era_index,
+                        &last_referendum_vote_reminder_check_block_number,
+                        &last_telemetry_finality_check_block_number,
+                        &last_telemetry_version_check_block_number,
                     )
                     .await
                 {
@@ -197,6 +237,7 @@ impl NotificationGenerator {
                 );
                 last_finalized_block_number = finalized_block_number;
             };
+            self.reset_substrate_client().await;
             let delay_seconds = CONFIG.common.recovery_retry_seconds;
             log::error!(
                 "Error while processing validator list: {:?}. Sleep for {} seconds, then retry.",
@@ -0,0 +1,180 @@
+//! Reminds validators that haven't voted on (or delegated their votes for) the open referenda
+//! whose decision deadlines are closer than the hours set in the notification rule.
+use crate::inspect::get_rule_param;
+use crate::{NotificationGenerator, CONFIG};
+use rustc_hash::FxHashMap as HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_substrate_client::SubstrateClient;
+use subvt_types::app::app_event::ReferendumVoteReminder;
+use subvt_types::app::notification::NotificationTypeCode;
+use subvt_types::crypto::AccountId;
+use subvt_types::substrate::metadata::get_metadata_expected_block_time_millis;
+use subvt_types::substrate::ConvictionVoting;
+use subvt_types::subvt::ValidatorDetails;
+
+const HOURS_BEFORE_DEADLINE_PARAM_CODE: &str = "hours_before_deadline";
+const DEFAULT_HOURS_BEFORE_DEADLINE: u64 = 24;
+const VOTING_ACCOUNT_PARAM_CODE: &str = "voting_account";
+
+/// Whether the account has either voted on the referendum, or delegated its votes on the track.
+async fn has_voted_or_delegated(
+    substrate_client: &SubstrateClient,
+    account_id: &AccountId,
+    track_id: u16,
+    referendum_index: u32,
+) -> anyhow::Result<bool> {
+    let has_voted_or_delegated = match substrate_client
+        .get_conviction_voting_for(account_id, track_id, None)
+        .await?
+    {
+        Some(ConvictionVoting::Casting(casting)) => {
+            casting.votes.iter().any(|vote| vote.0 == referendum_index)
+        }
+        Some(ConvictionVoting::Delegating(_)) => true,
+        None => false,
+    };
+    Ok(has_voted_or_delegated)
+}
+
+impl NotificationGenerator {
+    pub(crate) async fn inspect_referendum_vote_reminders(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        last_check_block_number: &AtomicU64,
+        finalized_block_number: u64,
+        validator_map: &HashMap<String, ValidatorDetails>,
+    ) -> anyhow::Result<()> {
+        if finalized_block_number
+            < last_check_block_number.load(Ordering::SeqCst)
+                + CONFIG
+                    .notification_generator
+                    .referendum_vote_reminder_check_period_blocks
+        {
+            return Ok(());
+        }
+        last_check_block_number.store(finalized_block_number, Ordering::SeqCst);
+        let deadlines = network_postgres
+            .get_open_referendum_decision_deadlines()
+            .await?;
+        if deadlines.is_empty() {
+            return Ok(());
+        }
+        let notification_type_code = NotificationTypeCode::ChainValidatorReferendumNotVoted;
+        if app_postgres
+            .get_notification_rules_by_type(
+                &notification_type_code.to_string(),
+                CONFIG.substrate.network_id,
+            )
+            .await?
+            .is_empty()
+        {
+            return Ok(());
+        }
+        log::debug!(
+            "Check referendum vote reminders for {} open referenda.",
+            deadlines.len()
+        );
+        let substrate_client = self.get_substrate_client().await?;
+        let block_time_millis =
+            get_metadata_expected_block_time_millis(&substrate_client.metadata)?;
+        for validator in validator_map.values() {
+            let rules = app_postgres
+                .get_notification_rules_for_validator(
+                    &notification_type_code.to_string(),
+                    CONFIG.substrate.network_id,
+                    &validator.account.id,
+                )
+                .await?;
+            for rule in rules {
+                let hours_before_deadline =
+                    match get_rule_param(&rule, HOURS_BEFORE_DEADLINE_PARAM_CODE) {
+                        Some(value) => match value.parse::<u64>() {
+                            Ok(hours) => hours,
+                            Err(_) => {
+                                log::error!(
+                                    "Invalid {} parameter for rule #{}.",
+                                    HOURS_BEFORE_DEADLINE_PARAM_CODE,
+                                    rule.id,
+                                );
+                                continue;
+                            }
+                        },
+                        None => DEFAULT_HOURS_BEFORE_DEADLINE,
+                    };
+                let voting_account_id = match get_rule_param(&rule, VOTING_ACCOUNT_PARAM_CODE) {
+                    Some(value) => match AccountId::from_str(value) {
+                        Ok(account_id) => account_id,
+                        Err(_) => {
+                            log::error!(
+                                "Invalid {} parameter for rule #{}.",
+                                VOTING_ACCOUNT_PARAM_CODE,
+                                rule.id,
+                            );
+                            continue;
+                        }
+                    },
+                    None => validator.account.id,
+                };
+                for deadline in &deadlines {
+                    let deadline_block_number = deadline.deadline_block_number as u64;
+                    if deadline_block_number <= finalized_block_number {
+                        continue;
+                    }
+                    let millis_left =
+                        (deadline_block_number - finalized_block_number) * block_time_millis;
+                    if millis_left > hours_before_deadline * 60 * 60 * 1000 {
+                        // deadlines are ordered, the rest are further away
+                        break;
+                    }
+                    if network_postgres
+                        .notification_generator_has_sent_referendum_vote_reminder(
+                            rule.id,
+                            &validator.account.id,
+                            deadline.index,
+                        )
+                        .await?
+                        || has_voted_or_delegated(
+                            &substrate_client,
+                            &voting_account_id,
+                            deadline.track_id,
+                            deadline.index,
+                        )
+                        .await?
+                    {
+                        continue;
+                    }
+                    let reminder = ReferendumVoteReminder {
+                        validator_account_id: validator.account.id,
+                        voting_account_id,
+                        referendum_index: deadline.index,
+                        track_id: deadline.track_id,
+                        maybe_title: deadline.maybe_title.clone(),
+                        deadline_block_number,
+                        hours_left: millis_left / (60 * 60 * 1000),
+                    };
+                    self.generate_notifications(
+                        app_postgres.clone(),
+                        &[rule.clone()],
+                        finalized_block_number,
+                        &Some(validator.account.id),
+                        Some(&reminder),
+                    )
+                    .await?;
+                    network_postgres
+                        .save_notification_generator_referendum_vote_reminder(
+                            rule.id,
+                            &validator.account.id,
+                            deadline.index,
+                        )
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! Notifies when a Telemetry node of an active validator keeps importing blocks, but hasn't cast
+//! any GRANDPA votes for the duration set in the notification rule. A node gets notified once
+//! per stop, i.e. it gets notified again only after it casts GRANDPA votes again.
+use crate::inspect::get_rule_param;
+use crate::{NotificationGenerator, CONFIG};
+use chrono::Utc;
+use rustc_hash::FxHashMap as HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::app_event::TelemetryFinalityParticipationStopped;
+use subvt_types::app::notification::NotificationTypeCode;
+use subvt_types::subvt::ValidatorDetails;
+
+const DURATION_SEC_PARAM_CODE: &str = "duration_sec";
+/// A node is considered to be importing blocks if its best block is at most this many blocks
+/// behind the network's best block.
+const MAX_IMPORTING_BEST_BLOCK_LAG: u64 = 10;
+
+impl NotificationGenerator {
+    pub(crate) async fn inspect_telemetry_finality_participation(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        last_check_block_number: &AtomicU64,
+        finalized_block_number: u64,
+        validator_map: &HashMap<String, ValidatorDetails>,
+    ) -> anyhow::Result<()> {
+        if finalized_block_number
+            < last_check_block_number.load(Ordering::SeqCst)
+                + CONFIG
+                    .notification_generator
+                    .telemetry_finality_check_period_blocks
+        {
+            return Ok(());
+        }
+        last_check_block_number.store(finalized_block_number, Ordering::SeqCst);
+        let notification_type_code =
+            NotificationTypeCode::TelemetryValidatorNotParticipatingInFinality;
+        if app_postgres
+            .get_notification_rules_by_type(
+                &notification_type_code.to_string(),
+                CONFIG.substrate.network_id,
+            )
+            .await?
+            .is_empty()
+        {
+            return Ok(());
+        }
+        log::debug!("Check the finality participation of the validator nodes.");
+        let now = Utc::now().timestamp_millis() as u64;
+        for validator in validator_map
+            .values()
+            .filter(|validator| validator.is_active)
+        {
+            let rules = app_postgres
+                .get_notification_rules_for_validator(
+                    &notification_type_code.to_string(),
+                    CONFIG.substrate.network_id,
+                    &validator.account.id,
+                )
+                .await?;
+            if rules.is_empty() {
+                continue;
+            }
+            let statuses = network_postgres
+                .get_validator_telemetry_node_finality_statuses(&validator.account.id)
+                .await?;
+            for status in statuses {
+                // nodes that don't import blocks are reported by the lagging notifications
+                if !matches!(status.best_block_lag, Some(lag) if lag <= MAX_IMPORTING_BEST_BLOCK_LAG)
+                {
+                    continue;
+                }
+                // no GRANDPA votes within the retention period, nothing has stopped
+                let last_finality_timestamp = match status.last_finality_timestamp {
+                    Some(timestamp) => timestamp,
+                    None => continue,
+                };
+                for rule in &rules {
+                    let duration_sec = match get_rule_param(rule, DURATION_SEC_PARAM_CODE)
+                        .and_then(|value| value.parse::<u64>().ok())
+                    {
+                        Some(duration_sec) => duration_sec,
+                        None => {
+                            log::error!(
+                                "Invalid or missing {} parameter for rule #{}.",
+                                DURATION_SEC_PARAM_CODE,
+                                rule.id,
+                            );
+                            continue;
+                        }
+                    };
+                    if now < last_finality_timestamp + duration_sec * 1000
+                        || network_postgres
+                            .notification_generator_has_sent_telemetry_finality_notification(
+                                rule.id,
+                                &validator.account.id,
+                                &status.validator_address,
+                                last_finality_timestamp,
+                            )
+                            .await?
+                    {
+                        continue;
+                    }
+                    log::info!(
+                        "Node {} of validator {} has not participated in finality since block #{:?}.",
+                        status.node_name,
+                        validator.account.id,
+                        status.last_finality_block_number,
+                    );
+                    let participation_stopped = TelemetryFinalityParticipationStopped {
+                        validator_account_id: validator.account.id,
+                        node_name: status.node_name.clone(),
+                        validator_address: status.validator_address.clone(),
+                        best_block_number: status.best_block_number,
+                        last_finality_block_number: status.last_finality_block_number,
+                        last_finality_timestamp,
+                    };
+                    self.generate_notifications(
+                        app_postgres.clone(),
+                        &[rule.clone()],
+                        finalized_block_number,
+                        &Some(validator.account.id),
+                        Some(&participation_stopped),
+                    )
+                    .await?;
+                    network_postgres
+                        .save_notification_generator_telemetry_finality_notification(
+                            rule.id,
+                            &validator.account.id,
+                            &status.validator_address,
+                            last_finality_timestamp,
+                        )
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! Notifies when a Telemetry node of a validator still runs an older client version than the
+//! latest release after the grace period set in the notification rule, counted from the release
+//! time. A node gets notified once per latest release.
+use crate::inspect::get_rule_param;
+use crate::{NotificationGenerator, CONFIG};
+use chrono::Utc;
+use rustc_hash::FxHashMap as HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::app_event::TelemetryBinaryOutOfDate;
+use subvt_types::app::notification::NotificationTypeCode;
+use subvt_types::subvt::ValidatorDetails;
+use subvt_types::telemetry::version::ClientVersion;
+
+const DURATION_SEC_PARAM_CODE: &str = "duration_sec";
+
+impl NotificationGenerator {
+    pub(crate) async fn inspect_telemetry_version(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        last_check_block_number: &AtomicU64,
+        finalized_block_number: u64,
+        validator_map: &HashMap<String, ValidatorDetails>,
+    ) -> anyhow::Result<()> {
+        if finalized_block_number
+            < last_check_block_number.load(Ordering::SeqCst)
+                + CONFIG
+                    .notification_generator
+                    .telemetry_version_check_period_blocks
+        {
+            return Ok(());
+        }
+        last_check_block_number.store(finalized_block_number, Ordering::SeqCst);
+        let notification_type_code = NotificationTypeCode::TelemetryValidatorBinaryOutOfDate;
+        if app_postgres
+            .get_notification_rules_by_type(
+                &notification_type_code.to_string(),
+                CONFIG.substrate.network_id,
+            )
+            .await?
+            .is_empty()
+        {
+            return Ok(());
+        }
+        let latest_version = match network_postgres.get_telemetry_latest_version().await? {
+            Some(latest_version) => latest_version,
+            None => {
+                log::warn!("Latest release is not known. Skip the validator node version check.");
+                return Ok(());
+            }
+        };
+        let latest_client_version = match ClientVersion::from_str(&latest_version.version) {
+            Ok(client_version) => client_version,
+            Err(error) => {
+                log::error!("{:?}", error);
+                return Ok(());
+            }
+        };
+        log::debug!(
+            "Check the validator node versions against the latest release {}.",
+            latest_version.version
+        );
+        let now = Utc::now().timestamp_millis() as u64;
+        for validator in validator_map.values() {
+            let rules = app_postgres
+                .get_notification_rules_for_validator(
+                    &notification_type_code.to_string(),
+                    CONFIG.substrate.network_id,
+                    &validator.account.id,
+                )
+                .await?;
+            if rules.is_empty() {
+                continue;
+            }
+            let nodes = network_postgres
+                .get_validator_telemetry_nodes(&validator.account.id)
+                .await?;
+            for node in nodes {
+                // nodes with unknown version formats cannot be compared
+                match ClientVersion::from_str(&node.client_version) {
+                    Ok(client_version) if client_version < latest_client_version => (),
+                    _ => continue,
+                }
+                for rule in &rules {
+                    let duration_sec = match get_rule_param(rule, DURATION_SEC_PARAM_CODE)
+                        .and_then(|value| value.parse::<u64>().ok())
+                    {
+                        Some(duration_sec) => duration_sec,
+                        None => {
+                            log::error!(
+                                "Invalid or missing {} parameter for rule #{}.",
+                                DURATION_SEC_PARAM_CODE,
+                                rule.id,
+                            );
+                            continue;
+                        }
+                    };
+                    if now < latest_version.released_timestamp + duration_sec * 1000
+                        || network_postgres
+                            .notification_generator_has_sent_telemetry_version_notification(
+                                rule.id,
+                                &validator.account.id,
+                                &node.name,
+                                &latest_version.version,
+                            )
+                            .await?
+                    {
+                        continue;
+                    }
+                    log::info!(
+                        "Node {} of validator {} runs {}, older than the latest release {}.",
+                        node.name,
+                        validator.account.id,
+                        node.client_version,
+                        latest_version.version,
+                    );
+                    let binary_out_of_date = TelemetryBinaryOutOfDate {
+                        validator_account_id: validator.account.id,
+                        node_name: node.name.clone(),
+                        client_version: node.client_version.clone(),
+                        latest_version: latest_version.version.clone(),
+                        latest_version_released_timestamp: latest_version.released_timestamp,
+                    };
+                    self.generate_notifications(
+                        app_postgres.clone(),
+                        &[rule.clone()],
+                        finalized_block_number,
+                        &Some(validator.account.id),
+                        Some(&binary_out_of_date),
+                    )
+                    .await?;
+                    network_postgres
+                        .save_notification_generator_telemetry_version_notification(
+                            rule.id,
+                            &validator.account.id,
+                            &node.name,
+                            &latest_version.version,
+                        )
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@
 //! notifications.
 //! 3. Regular Telemetry checks (this is work in progress still).
 #![warn(clippy::disallowed_types)]
+use async_lock::Mutex;
 use async_trait::async_trait;
 use lazy_static::lazy_static;
 use serde::Serialize;
@@ -25,9 +26,31 @@ lazy_static! {
 }
 
 #[derive(Default)]
-pub struct NotificationGenerator;
+pub struct NotificationGenerator {
+    /// Shared by the block and validator list inspections, see `get_substrate_client`.
+    substrate_client: Mutex<Option<Arc<SubstrateClient>>>,
+}
 
 impl NotificationGenerator {
+    /// Gets the Substrate client shared by the inspections, connecting it on the first use or
+    /// after a reset.
+    async fn get_substrate_client(&self) -> anyhow::Result<Arc<SubstrateClient>> {
+        let mut maybe_substrate_client = self.substrate_client.lock().await;
+        if let Some(substrate_client) = maybe_substrate_client.as_ref() {
+            return Ok(substrate_client.clone());
+        }
+        let substrate_client = Arc::new(SubstrateClient::new(&CONFIG).await?);
+        *maybe_substrate_client = Some(substrate_client.clone());
+        Ok(substrate_client)
+    }
+
+    /// Drops the shared Substrate client, so that the next use connects a new one with the
+    /// metadata of the current runtime. Called when an inspection fails, since the failure may
+    /// be due to a broken connection, and after runtime upgrades.
+    async fn reset_substrate_client(&self) {
+        *self.substrate_client.lock().await = None;
+    }
+
     /// Persist notifications for a validator, which will later be be processed by
     /// `subvt-notification-sender`.
     async fn generate_notifications<T: Clone + Serialize>(
@@ -41,7 +64,7 @@ impl NotificationGenerator {
         if rules.is_empty() {
             return Ok(());
         }
-        let substrate_client: Arc<SubstrateClient> = Arc::new(SubstrateClient::new(&CONFIG).await?);
+        let substrate_client = self.get_substrate_client().await?;
         let block_hash = substrate_client.get_block_hash(block_number).await?;
         // get account information for the validator stash address, which is used to display
         // identity information if exists
@@ -70,9 +93,10 @@ impl NotificationGenerator {
                 log::debug!("Generate {} notification.", rule.notification_type.code,);
             }
             for channel in &rule.notification_channels {
+                // the channels of team rules belong to the team members
                 let notification = Notification {
                     id: 0,
-                    user_id: rule.user_id,
+                    user_id: channel.user_id,
                     user_notification_rule_id: rule.id,
                     network_id: CONFIG.substrate.network_id,
                     period_type: rule.period_type,
@@ -0,0 +1,91 @@
+use subvt_types::app::event::conviction_voting::{
+    ConvictionVotingDelegatedEvent, ConvictionVotingUndelegatedEvent,
+};
+use subvt_types::app::{notification::Notification, Network};
+use subvt_types::substrate::democracy::get_conviction_votes;
+use subvt_utility::numeric::format_decimal;
+use subvt_utility::text::get_condensed_address;
+use tera::Context;
+
+pub(crate) fn set_conviction_voting_delegated_context(
+    network: &Network,
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(event) =
+            serde_json::from_str::<ConvictionVotingDelegatedEvent>(notification_data_json.as_str())
+        {
+            let delegator_address = event
+                .original_account_id
+                .to_ss58_check_with_version(network.ss58_prefix as u16);
+            context.insert("delegator_address", &delegator_address);
+            context.insert(
+                "delegator_display",
+                &get_condensed_address(&delegator_address, None),
+            );
+            if let Some(track_id) = event.track_id {
+                context.insert("track_id", &track_id);
+            }
+            if let (Some(conviction), Some(balance)) = (event.conviction, event.balance) {
+                context.insert("conviction", &conviction);
+                context.insert(
+                    "balance",
+                    &format_decimal(balance, network.token_decimal_count as usize, 4),
+                );
+                context.insert(
+                    "votes",
+                    &format_decimal(
+                        get_conviction_votes(conviction, balance),
+                        network.token_decimal_count as usize,
+                        4,
+                    ),
+                );
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize OpenGov delegated notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "OpenGov delegated event data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
+
+pub(crate) fn set_conviction_voting_undelegated_context(
+    network: &Network,
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(event) = serde_json::from_str::<ConvictionVotingUndelegatedEvent>(
+            notification_data_json.as_str(),
+        ) {
+            let delegator_address = event
+                .account_id
+                .to_ss58_check_with_version(network.ss58_prefix as u16);
+            context.insert("delegator_address", &delegator_address);
+            context.insert(
+                "delegator_display",
+                &get_condensed_address(&delegator_address, None),
+            );
+            if let Some(track_id) = event.track_id {
+                context.insert("track_id", &track_id);
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize OpenGov undelegated notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "OpenGov undelegated event data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
@@ -12,6 +12,9 @@ use crate::content::context::referenda::{
 use crate::content::context::{
     basic::set_basic_context,
     block_authorship::set_block_authorship_context,
+    delegation::{
+        set_conviction_voting_delegated_context, set_conviction_voting_undelegated_context,
+    },
     identity::set_identity_changed_context,
     lost_nomination::set_lost_nomination_context,
     new_nomination::set_new_nomination_context,
@@ -22,7 +25,14 @@ use crate::content::context::{
         rank::set_onekv_rank_changed_context, validity::set_onekv_validity_changed_context,
     },
     payout::set_payout_context,
+    referendum_tally::{
+        set_referendum_confirming_changed_context, set_referendum_tally_changed_context,
+        set_referendum_threshold_crossed_context,
+    },
+    referendum_vote_reminder::set_referendum_vote_reminder_context,
     session_keys::set_session_keys_changed_context,
+    telemetry_finality::set_telemetry_finality_participation_stopped_context,
+    telemetry_version::set_telemetry_binary_out_of_date_context,
     unclaimed_payout::set_unclaimed_payout_context,
     validate::set_validate_extrinsic_context,
     validator_active::set_validator_active_context,
@@ -36,6 +46,7 @@ use tera::Context;
 
 mod basic;
 mod block_authorship;
+mod delegation;
 mod identity;
 mod lost_nomination;
 mod new_nomination;
@@ -43,7 +54,11 @@ mod offline_offence;
 mod onekv;
 mod payout;
 mod referenda;
+mod referendum_tally;
+mod referendum_vote_reminder;
 mod session_keys;
+mod telemetry_finality;
+mod telemetry_version;
 mod unclaimed_payout;
 mod validate;
 mod validator_active;
@@ -117,8 +132,17 @@ pub(crate) fn get_renderer_context(
         NotificationTypeCode::ChainValidatorPayoutStakers => {
             set_payout_context(network, notification, &mut context);
         }
+        NotificationTypeCode::ChainValidatorReferendumNotVoted => {
+            set_referendum_vote_reminder_context(notification, &mut context);
+        }
         NotificationTypeCode::ChainValidatorStartedParaValidating => (),
         NotificationTypeCode::ChainValidatorStoppedParaValidating => (),
+        NotificationTypeCode::ConvictionVotingDelegated => {
+            set_conviction_voting_delegated_context(network, notification, &mut context);
+        }
+        NotificationTypeCode::ConvictionVotingUndelegated => {
+            set_conviction_voting_undelegated_context(network, notification, &mut context);
+        }
         NotificationTypeCode::OneKVValidatorRankChange => {
             set_onekv_rank_changed_context(notification, &mut context);
         }
@@ -137,6 +161,10 @@ pub(crate) fn get_renderer_context(
         NotificationTypeCode::ReferendumCancelled => {
             set_referendum_cancelled_context(notification, &mut context)
         }
+        NotificationTypeCode::ReferendumConfirmAborted
+        | NotificationTypeCode::ReferendumConfirmStarted => {
+            set_referendum_confirming_changed_context(notification, &mut context)
+        }
         NotificationTypeCode::ReferendumConfirmed => {
             set_referendum_confirmed_context(notification, &mut context)
         }
@@ -152,9 +180,21 @@ pub(crate) fn get_renderer_context(
         NotificationTypeCode::ReferendumSubmitted => {
             set_referendum_submitted_context(notification, &mut context)
         }
+        NotificationTypeCode::ReferendumTallyChanged => {
+            set_referendum_tally_changed_context(network, notification, &mut context)
+        }
+        NotificationTypeCode::ReferendumThresholdCrossed => {
+            set_referendum_threshold_crossed_context(notification, &mut context)
+        }
         NotificationTypeCode::ReferendumTimedOut => {
             set_referendum_timed_out_context(notification, &mut context)
         }
+        NotificationTypeCode::TelemetryValidatorNotParticipatingInFinality => {
+            set_telemetry_finality_participation_stopped_context(notification, &mut context)
+        }
+        NotificationTypeCode::TelemetryValidatorBinaryOutOfDate => {
+            set_telemetry_binary_out_of_date_context(notification, &mut context)
+        }
         _ => todo!(
             "Push notification content not yet ready for {}.",
             notification.notification_type_code
@@ -6,6 +6,9 @@ use subvt_types::app::event::referenda::{
 use subvt_types::app::notification::Notification;
 use tera::Context;
 
+/// Keeps the Telegram messages within the message length limit for large batch proposals.
+const MAX_PROPOSAL_CALL_LINES: usize = 30;
+
 pub(crate) fn set_referendum_approved_context(notification: &Notification, context: &mut Context) {
     if let Some(notification_data_json) = &notification.data_json {
         if let Ok(event) =
@@ -75,6 +78,12 @@ pub(crate) fn set_referendum_decision_started_context(
             serde_json::from_str::<ReferendumDecisionStartedEvent>(notification_data_json.as_str())
         {
             context.insert("referendum_index", &event.referendum_index);
+            if let Some(proposal_call) = &event.maybe_proposal_call {
+                context.insert(
+                    "proposal_call_lines",
+                    &proposal_call.get_truncated_summary_lines(MAX_PROPOSAL_CALL_LINES),
+                );
+            }
         } else {
             log::error!(
                 "Cannot deserialize referendum decision started notification data for notification #{}.",
@@ -135,6 +144,12 @@ pub(crate) fn set_referendum_submitted_context(notification: &Notification, cont
             serde_json::from_str::<ReferendumSubmittedEvent>(notification_data_json.as_str())
         {
             context.insert("referendum_index", &event.referendum_index);
+            if let Some(proposal_call) = &event.maybe_proposal_call {
+                context.insert(
+                    "proposal_call_lines",
+                    &proposal_call.get_truncated_summary_lines(MAX_PROPOSAL_CALL_LINES),
+                );
+            }
         } else {
             log::error!(
                 "Cannot deserialize referendum submitted notification data for notification #{}.",
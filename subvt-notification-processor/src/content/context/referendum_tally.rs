@@ -0,0 +1,124 @@
+use subvt_types::app::app_event::{
+    ReferendumConfirmingChanged, ReferendumTallyChanged, ReferendumThresholdCrossed,
+};
+use subvt_types::app::{notification::Notification, Network};
+use subvt_utility::numeric::format_decimal;
+use tera::Context;
+
+fn format_per_billion_percent(per_billion: u32) -> String {
+    format!("{:.2}", per_billion as f64 / 10_000_000.0)
+}
+
+pub(crate) fn set_referendum_threshold_crossed_context(
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(crossing) =
+            serde_json::from_str::<ReferendumThresholdCrossed>(notification_data_json.as_str())
+        {
+            context.insert("referendum_index", &crossing.referendum_index);
+            context.insert("track_id", &crossing.track_id);
+            if let Some(title) = &crossing.maybe_title {
+                context.insert("referendum_title", title);
+            }
+            context.insert("threshold", &crossing.threshold);
+            context.insert("is_above", &crossing.is_above);
+            context.insert(
+                "value_percent",
+                &format_per_billion_percent(crossing.value_per_billion),
+            );
+            context.insert(
+                "threshold_percent",
+                &format_per_billion_percent(crossing.threshold_per_billion),
+            );
+            context.insert("is_passing", &crossing.is_passing);
+        } else {
+            log::error!(
+                "Cannot deserialize referendum threshold crossed notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Referendum threshold crossed data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
+
+/// Used for both the confirmation start and abort notifications.
+pub(crate) fn set_referendum_confirming_changed_context(
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(change) =
+            serde_json::from_str::<ReferendumConfirmingChanged>(notification_data_json.as_str())
+        {
+            context.insert("referendum_index", &change.referendum_index);
+            context.insert("track_id", &change.track_id);
+            if let Some(title) = &change.maybe_title {
+                context.insert("referendum_title", title);
+            }
+            if let Some(confirm_end_block_number) = change.maybe_confirm_end_block_number {
+                context.insert("confirm_end_block_number", &confirm_end_block_number);
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize referendum confirming changed notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Referendum confirming changed data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
+
+pub(crate) fn set_referendum_tally_changed_context(
+    network: &Network,
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(change) =
+            serde_json::from_str::<ReferendumTallyChanged>(notification_data_json.as_str())
+        {
+            let format_balance =
+                |balance| format_decimal(balance, network.token_decimal_count as usize, 4);
+            context.insert("referendum_index", &change.referendum_index);
+            context.insert("track_id", &change.track_id);
+            if let Some(title) = &change.maybe_title {
+                context.insert("referendum_title", title);
+            }
+            context.insert("prev_ayes", &format_balance(change.prev_ayes));
+            context.insert("prev_nays", &format_balance(change.prev_nays));
+            context.insert("prev_support", &format_balance(change.prev_support));
+            context.insert("ayes", &format_balance(change.ayes));
+            context.insert("nays", &format_balance(change.nays));
+            context.insert("support", &format_balance(change.support));
+            context.insert(
+                "approval_percent",
+                &format_per_billion_percent(change.approval_per_billion),
+            );
+            context.insert(
+                "support_percent",
+                &format_per_billion_percent(change.support_per_billion),
+            );
+            context.insert("change_percent", &format!("{:.2}", change.change_percent));
+        } else {
+            log::error!(
+                "Cannot deserialize referendum tally changed notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Referendum tally changed data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
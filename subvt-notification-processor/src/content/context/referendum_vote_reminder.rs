@@ -0,0 +1,37 @@
+use subvt_types::app::app_event::ReferendumVoteReminder;
+use subvt_types::app::notification::Notification;
+use tera::Context;
+
+pub(crate) fn set_referendum_vote_reminder_context(
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(reminder) =
+            serde_json::from_str::<ReferendumVoteReminder>(notification_data_json.as_str())
+        {
+            context.insert("referendum_index", &reminder.referendum_index);
+            context.insert("track_id", &reminder.track_id);
+            if let Some(title) = &reminder.maybe_title {
+                context.insert("referendum_title", title);
+            }
+            context.insert("hours_left", &reminder.hours_left);
+            if reminder.voting_account_id != reminder.validator_account_id {
+                context.insert(
+                    "voting_address",
+                    &reminder.voting_account_id.to_ss58_check(),
+                );
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize referendum vote reminder notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Referendum vote reminder data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{LocalResult, TimeZone, Utc};
+use subvt_types::app::app_event::TelemetryFinalityParticipationStopped;
+use subvt_types::app::notification::Notification;
+use tera::Context;
+
+pub(crate) fn set_telemetry_finality_participation_stopped_context(
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(event) = serde_json::from_str::<TelemetryFinalityParticipationStopped>(
+            notification_data_json.as_str(),
+        ) {
+            context.insert("node_name", &event.node_name);
+            if let Some(best_block_number) = event.best_block_number {
+                context.insert("best_block_number", &best_block_number);
+            }
+            if let Some(last_finality_block_number) = event.last_finality_block_number {
+                context.insert("last_finality_block_number", &last_finality_block_number);
+            }
+            if let LocalResult::Single(last_finality_time) =
+                Utc::timestamp_opt(&Utc, event.last_finality_timestamp as i64 / 1000, 0)
+            {
+                context.insert(
+                    "last_finality_time",
+                    &last_finality_time.format("%b %d, %Y %H:%M UTC").to_string(),
+                );
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize finality participation stopped notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Finality participation stopped data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
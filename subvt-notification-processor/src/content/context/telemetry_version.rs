@@ -0,0 +1,39 @@
+use chrono::{LocalResult, TimeZone, Utc};
+use subvt_types::app::app_event::TelemetryBinaryOutOfDate;
+use subvt_types::app::notification::Notification;
+use tera::Context;
+
+pub(crate) fn set_telemetry_binary_out_of_date_context(
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(event) =
+            serde_json::from_str::<TelemetryBinaryOutOfDate>(notification_data_json.as_str())
+        {
+            context.insert("node_name", &event.node_name);
+            context.insert("client_version", &event.client_version);
+            context.insert("latest_version", &event.latest_version);
+            if let LocalResult::Single(latest_version_release_time) = Utc::timestamp_opt(
+                &Utc,
+                event.latest_version_released_timestamp as i64 / 1000,
+                0,
+            ) {
+                context.insert(
+                    "latest_version_release_date",
+                    &latest_version_release_time.format("%b %d, %Y").to_string(),
+                );
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize binary out-of-date notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Binary out-of-date data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
@@ -7,7 +7,9 @@ use subvt_config::Config;
 pub mod network;
 pub mod notification;
 pub mod notification_channel;
+pub mod notification_rule_template;
 pub mod notification_type;
+pub mod team;
 pub mod user;
 
 pub struct PostgreSQLAppStorage {
@@ -1,11 +1,54 @@
 //! Storage related to application notifications.
 use crate::postgres::app::PostgreSQLAppStorage;
+use chrono::NaiveDateTime;
 use subvt_types::app::db::{PostgresNotification, PostgresNotificationParamType};
 use subvt_types::app::notification::{
-    Notification, NotificationParamType, NotificationPeriodType, UserNotificationRule,
+    Notification, NotificationParamType, NotificationPeriodType, NotificationTypeCode,
+    UserNotificationRule,
 };
 use subvt_types::crypto::AccountId;
 
+type NotificationTimestamps = (
+    NaiveDateTime,
+    Option<NaiveDateTime>,
+    Option<NaiveDateTime>,
+    Option<NaiveDateTime>,
+);
+
+/// Notification row along with its timestamps.
+type PostgresNotificationRow = (
+    i32,
+    i32,
+    i32,
+    i32,
+    NotificationPeriodType,
+    i32,
+    Option<String>,
+    Option<String>,
+    String,
+    i32,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    NaiveDateTime,
+    Option<NaiveDateTime>,
+    Option<NaiveDateTime>,
+    Option<NaiveDateTime>,
+);
+
+fn split_db_notification_row(
+    row: PostgresNotificationRow,
+) -> (PostgresNotification, NotificationTimestamps) {
+    (
+        (
+            row.0, row.1, row.2, row.3, row.4, row.5, row.6, row.7, row.8, row.9, row.10, row.11,
+            row.12, row.13,
+        ),
+        (row.14, row.15, row.16, row.17),
+    )
+}
+
 impl PostgreSQLAppStorage {
     pub async fn get_notification_parameter_types(
         &self,
@@ -74,6 +117,10 @@ impl PostgreSQLAppStorage {
         network_id: u32,
         validator_account_id: &AccountId,
     ) -> anyhow::Result<Vec<UserNotificationRule>> {
+        // operator-only notifications are sent only for ownership-verified validators, whatever
+        // the rule, as the default rules are for all validators
+        let is_operator_only =
+            NotificationTypeCode::from(notification_type_code).is_operator_only();
         let rule_ids: Vec<(i32,)> = sqlx::query_as(
             r#"
             SELECT "id"
@@ -89,9 +136,13 @@ impl PostgreSQLAppStorage {
                         SELECT DISTINCT "id"
                         FROM app_user_validator UV1
                         WHERE UV1.network_id = $2
-                        AND UV1.user_id = UNR.user_id
+                        AND (
+                            (UNR.team_id IS NULL AND UV1.team_id IS NULL AND UV1.user_id = UNR.user_id)
+                            OR UV1.team_id = UNR.team_id
+                        )
                         AND UV1.validator_account_id = $3
                         AND UV1.deleted_at IS NULL
+                        AND ($4 = false OR UV1.ownership_verified_at IS NOT NULL)
                     )
                 )
                 OR
@@ -104,10 +155,14 @@ impl PostgreSQLAppStorage {
                             SELECT DISTINCT "id"
                             FROM app_user_validator UV2
                             WHERE UV2.network_id = $2
-                            AND UV2.user_id = UNR.user_id
+                            AND (
+                                (UNR.team_id IS NULL AND UV2.team_id IS NULL AND UV2.user_id = UNR.user_id)
+                                OR UV2.team_id = UNR.team_id
+                            )
                             AND UV2.validator_account_id = $3
                             AND UV2.id = UNRV.user_validator_id
                             AND UV2.deleted_at IS NULL
+                            AND ($4 = false OR UV2.ownership_verified_at IS NOT NULL)
                         )
                     )
                 )
@@ -117,6 +172,7 @@ impl PostgreSQLAppStorage {
         .bind(notification_type_code)
         .bind(network_id as i32)
         .bind(validator_account_id.to_string())
+        .bind(is_operator_only)
         .fetch_all(&self.connection_pool)
         .await?;
         let mut result = Vec::new();
@@ -309,4 +365,33 @@ impl PostgreSQLAppStorage {
         .await?;
         Ok(())
     }
+
+    /// Gets the notification history of the user, including the delivery timestamps.
+    pub async fn get_user_notifications(&self, user_id: u32) -> anyhow::Result<Vec<Notification>> {
+        let db_notifications: Vec<(PostgresNotification, NotificationTimestamps)> =
+            sqlx::query_as(
+                r#"
+                SELECT id, user_id, user_notification_rule_id, network_id, period_type, period, validator_account_id, validator_account_json, notification_type_code, user_notification_channel_id, notification_channel_code, notification_target, data_json, error_log, created_at, sent_at, delivered_at, read_at
+                FROM app_notification
+                WHERE user_id = $1
+                ORDER BY id ASC
+                "#,
+            )
+            .bind(user_id as i32)
+            .fetch_all(&self.connection_pool)
+            .await?
+            .into_iter()
+            .map(split_db_notification_row)
+            .collect();
+        let mut notifications = vec![];
+        for (db_notification, (created_at, sent_at, delivered_at, read_at)) in db_notifications {
+            let mut notification = Notification::from(db_notification)?;
+            notification.created_at = Some(created_at);
+            notification.sent_at = sent_at;
+            notification.delivered_at = delivered_at;
+            notification.read_at = read_at;
+            notifications.push(notification);
+        }
+        Ok(notifications)
+    }
 }
@@ -0,0 +1,99 @@
+//! Storage related to the notification rule templates of SubVT application users.
+use crate::postgres::app::PostgreSQLAppStorage;
+use subvt_types::app::notification::UserNotificationRuleTemplate;
+
+impl PostgreSQLAppStorage {
+    pub async fn save_user_notification_rule_template(
+        &self,
+        template: &UserNotificationRuleTemplate,
+    ) -> anyhow::Result<u32> {
+        let result: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO app_user_notification_rule_template (user_id, name, rules_json)
+            VALUES ($1, $2, $3)
+            RETURNING id
+            "#,
+        )
+        .bind(template.user_id as i32)
+        .bind(&template.name)
+        .bind(serde_json::to_string(&template.rules)?)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0 as u32)
+    }
+
+    pub async fn get_user_notification_rule_templates(
+        &self,
+        user_id: u32,
+    ) -> anyhow::Result<Vec<UserNotificationRuleTemplate>> {
+        let db_templates: Vec<(i32, i32, String, String)> = sqlx::query_as(
+            r#"
+            SELECT id, user_id, name, rules_json
+            FROM app_user_notification_rule_template
+            WHERE user_id = $1 AND deleted_at IS NULL
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(user_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut templates = Vec::new();
+        for (id, user_id, name, rules_json) in db_templates {
+            templates.push(UserNotificationRuleTemplate {
+                id: id as u32,
+                user_id: user_id as u32,
+                name,
+                rules: serde_json::from_str(&rules_json)?,
+            });
+        }
+        Ok(templates)
+    }
+
+    pub async fn get_user_notification_rule_template_by_id(
+        &self,
+        user_id: u32,
+        template_id: u32,
+    ) -> anyhow::Result<Option<UserNotificationRuleTemplate>> {
+        let maybe_db_template: Option<(i32, i32, String, String)> = sqlx::query_as(
+            r#"
+            SELECT id, user_id, name, rules_json
+            FROM app_user_notification_rule_template
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(template_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        if let Some((id, user_id, name, rules_json)) = maybe_db_template {
+            Ok(Some(UserNotificationRuleTemplate {
+                id: id as u32,
+                user_id: user_id as u32,
+                name,
+                rules: serde_json::from_str(&rules_json)?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn delete_user_notification_rule_template(
+        &self,
+        user_id: u32,
+        template_id: u32,
+    ) -> anyhow::Result<bool> {
+        let maybe_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE app_user_notification_rule_template
+            SET deleted_at = now(), updated_at = now()
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(template_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_id.is_some())
+    }
+}
@@ -0,0 +1,378 @@
+//! Storage related to the teams of SubVT application users.
+use crate::postgres::app::PostgreSQLAppStorage;
+use subvt_types::app::db::{PostgresUserNotificationChannel, PostgresUserValidator};
+use subvt_types::app::notification::{UserNotificationChannel, UserNotificationRule};
+use subvt_types::app::team::{Team, TeamInvitation, TeamMember, TeamRole};
+use subvt_types::app::UserValidator;
+
+impl PostgreSQLAppStorage {
+    /// Creates a new team with the user as its owner.
+    pub async fn save_team(&self, user_id: u32, name: &str) -> anyhow::Result<Team> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let result: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO app_team (name)
+            VALUES ($1)
+            RETURNING id
+            "#,
+        )
+        .bind(name)
+        .fetch_one(&mut transaction)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO app_team_member (team_id, user_id, role)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(result.0)
+        .bind(user_id as i32)
+        .bind(TeamRole::Owner)
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(Team {
+            id: result.0 as u32,
+            name: name.to_string(),
+            role: TeamRole::Owner,
+        })
+    }
+
+    /// Gets the teams of the user, along with the user's role in each team.
+    pub async fn get_user_teams(&self, user_id: u32) -> anyhow::Result<Vec<Team>> {
+        let db_teams: Vec<(i32, String, TeamRole)> = sqlx::query_as(
+            r#"
+            SELECT T.id, T.name, TM.role
+            FROM app_team T
+            INNER JOIN app_team_member TM ON TM.team_id = T.id
+            WHERE TM.user_id = $1
+            ORDER BY T.id ASC
+            "#,
+        )
+        .bind(user_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_teams
+            .into_iter()
+            .map(|(id, name, role)| Team {
+                id: id as u32,
+                name,
+                role,
+            })
+            .collect())
+    }
+
+    /// Gets the role of the user in the team, `None` if the user is not a member of the team.
+    pub async fn get_user_team_role(
+        &self,
+        user_id: u32,
+        team_id: u32,
+    ) -> anyhow::Result<Option<TeamRole>> {
+        let maybe_role: Option<(TeamRole,)> = sqlx::query_as(
+            r#"
+            SELECT role FROM app_team_member
+            WHERE team_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(team_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_role.map(|role| role.0))
+    }
+
+    pub async fn get_team_members(&self, team_id: u32) -> anyhow::Result<Vec<TeamMember>> {
+        let db_members: Vec<(i32, i32, TeamRole)> = sqlx::query_as(
+            r#"
+            SELECT team_id, user_id, role
+            FROM app_team_member
+            WHERE team_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(team_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_members
+            .into_iter()
+            .map(|(team_id, user_id, role)| TeamMember {
+                team_id: team_id as u32,
+                user_id: user_id as u32,
+                role,
+            })
+            .collect())
+    }
+
+    /// Updates the role of an existing team member. Returns `false` if the user is not a member
+    /// of the team, users join teams only by accepting invitations.
+    pub async fn save_team_member(&self, member: &TeamMember) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE app_team_member
+            SET role = $3, updated_at = now()
+            WHERE team_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(member.team_id as i32)
+        .bind(member.user_id as i32)
+        .bind(member.role)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Invites the user to the team with the given role, or updates the role and the inviting
+    /// owner of an existing invitation. Returns the invitation id.
+    pub async fn save_team_invitation(
+        &self,
+        member: &TeamMember,
+        invited_by_user_id: u32,
+    ) -> anyhow::Result<u32> {
+        let result: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO app_team_invitation (team_id, user_id, role, invited_by_user_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (team_id, user_id)
+            DO UPDATE SET role = EXCLUDED.role, invited_by_user_id = EXCLUDED.invited_by_user_id, updated_at = now()
+            RETURNING id
+            "#,
+        )
+        .bind(member.team_id as i32)
+        .bind(member.user_id as i32)
+        .bind(member.role)
+        .bind(invited_by_user_id as i32)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0 as u32)
+    }
+
+    /// Gets the pending team invitations of the user. An invitation with the given id is
+    /// returned only if it's for the user.
+    pub async fn get_user_team_invitations(
+        &self,
+        user_id: u32,
+        maybe_invitation_id: Option<u32>,
+    ) -> anyhow::Result<Vec<TeamInvitation>> {
+        let db_invitations: Vec<(i32, i32, String, i32, TeamRole, i32)> = sqlx::query_as(
+            r#"
+            SELECT TI.id, TI.team_id, T.name, TI.user_id, TI.role, TI.invited_by_user_id
+            FROM app_team_invitation TI
+            INNER JOIN app_team T ON T.id = TI.team_id
+            WHERE TI.user_id = $1
+            AND ($2::INTEGER IS NULL OR TI.id = $2)
+            ORDER BY TI.id ASC
+            "#,
+        )
+        .bind(user_id as i32)
+        .bind(maybe_invitation_id.map(|id| id as i32))
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_invitations
+            .into_iter()
+            .map(
+                |(id, team_id, team_name, user_id, role, invited_by_user_id)| TeamInvitation {
+                    id: id as u32,
+                    team_id: team_id as u32,
+                    team_name,
+                    user_id: user_id as u32,
+                    role,
+                    invited_by_user_id: invited_by_user_id as u32,
+                },
+            )
+            .collect())
+    }
+
+    /// Accepts the user's team invitation, adding the user to the team with the role of the
+    /// invitation. Returns `None` if the user has no such invitation.
+    pub async fn accept_team_invitation(
+        &self,
+        user_id: u32,
+        invitation_id: u32,
+    ) -> anyhow::Result<Option<TeamMember>> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let maybe_invitation: Option<(i32, TeamRole)> = sqlx::query_as(
+            r#"
+            DELETE FROM app_team_invitation
+            WHERE id = $1 AND user_id = $2
+            RETURNING team_id, role
+            "#,
+        )
+        .bind(invitation_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&mut transaction)
+        .await?;
+        let (team_id, role) = match maybe_invitation {
+            Some(invitation) => invitation,
+            None => return Ok(None),
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO app_team_member (team_id, user_id, role)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (team_id, user_id)
+            DO UPDATE SET role = EXCLUDED.role, updated_at = now()
+            "#,
+        )
+        .bind(team_id)
+        .bind(user_id as i32)
+        .bind(role)
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(Some(TeamMember {
+            team_id: team_id as u32,
+            user_id,
+            role,
+        }))
+    }
+
+    /// Declines (deletes) the user's team invitation.
+    pub async fn delete_team_invitation(
+        &self,
+        user_id: u32,
+        invitation_id: u32,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM app_team_invitation
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(invitation_id as i32)
+        .bind(user_id as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_team_member(&self, team_id: u32, user_id: u32) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM app_team_member
+            WHERE team_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(team_id as i32)
+        .bind(user_id as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_team_owner_count(&self, team_id: u32) -> anyhow::Result<u32> {
+        let record_count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(user_id) FROM app_team_member
+            WHERE team_id = $1 AND role = 'owner'
+            "#,
+        )
+        .bind(team_id as i32)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(record_count.0 as u32)
+    }
+
+    /// Gets the non-deleted validators of all the teams of the user.
+    pub async fn get_user_team_validators(
+        &self,
+        user_id: u32,
+    ) -> anyhow::Result<Vec<UserValidator>> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT id, user_id, network_id, validator_account_id, ownership_verified_at, team_id
+            FROM app_user_validator
+            WHERE team_id IN (SELECT team_id FROM app_team_member WHERE user_id = $1)
+            AND deleted_at IS NULL
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(user_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(PostgresUserValidator::into)
+        .collect())
+    }
+
+    /// Gets the non-deleted notification rules of all the teams of the user.
+    pub async fn get_user_team_notification_rules(
+        &self,
+        user_id: u32,
+    ) -> anyhow::Result<Vec<UserNotificationRule>> {
+        let rule_ids: Vec<(i32,)> = sqlx::query_as(
+            r#"
+            SELECT id
+            FROM app_user_notification_rule
+            WHERE team_id IN (SELECT team_id FROM app_team_member WHERE user_id = $1)
+            AND deleted_at IS NULL
+            "#,
+        )
+        .bind(user_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut rules = Vec::new();
+        for rule_id in rule_ids {
+            if let Some(rule) = self
+                .get_user_notification_rule_by_id(rule_id.0 as u32)
+                .await?
+            {
+                rules.push(rule);
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Gets the non-deleted notification rule with the given id if it belongs to the user,
+    /// or to one of the user's teams.
+    pub async fn get_user_accessible_notification_rule_by_id(
+        &self,
+        user_id: u32,
+        rule_id: u32,
+    ) -> anyhow::Result<Option<UserNotificationRule>> {
+        let maybe_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            SELECT id FROM app_user_notification_rule
+            WHERE id = $1
+            AND (
+                (user_id = $2 AND team_id IS NULL)
+                OR team_id IN (SELECT team_id FROM app_team_member WHERE user_id = $2)
+            )
+            AND deleted_at IS NULL
+            "#,
+        )
+        .bind(rule_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        if maybe_id.is_none() {
+            return Ok(None);
+        }
+        self.get_user_notification_rule_by_id(rule_id).await
+    }
+
+    /// Gets the non-deleted notification channels of all the non-deleted members of the team.
+    /// The notifications of team rules fan out to these channels.
+    pub async fn get_team_notification_channels(
+        &self,
+        team_id: u32,
+    ) -> anyhow::Result<Vec<UserNotificationChannel>> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT UNC.id, UNC.user_id, UNC.notification_channel_code, UNC.target
+            FROM app_user_notification_channel UNC
+            INNER JOIN app_team_member TM ON TM.user_id = UNC.user_id
+            INNER JOIN app_user U ON U.id = UNC.user_id
+            WHERE TM.team_id = $1
+            AND UNC.deleted_at IS NULL
+            AND U.deleted_at IS NULL
+            ORDER BY UNC.id ASC
+            "#,
+        )
+        .bind(team_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(PostgresUserNotificationChannel::into)
+        .collect())
+    }
+}
@@ -1,18 +1,22 @@
 //! Storage related to SubVT application users.
 use crate::postgres::app::PostgreSQLAppStorage;
+use chrono::NaiveDateTime;
 use rustc_hash::FxHashSet as HashSet;
+use sqlx::{Postgres, Transaction};
 use std::str::FromStr;
 use subvt_types::app::db::{
-    PostgresUserNotificationChannel, PostgresUserNotificationRule, PostgresUserValidator,
+    PostgresUserDevice, PostgresUserNotificationChannel, PostgresUserNotificationRule,
+    PostgresUserValidator,
 };
 use subvt_types::app::{
     notification::{
         NotificationPeriodType, NotificationTypeCode, UserNotificationChannel,
-        UserNotificationRule, UserNotificationRuleParameter,
+        UserNotificationRule, UserNotificationRuleParameter, UserNotificationRuleTemplate,
     },
-    User, UserValidator,
+    User, UserDevice, UserDeviceLink, UserValidator,
 };
 use subvt_types::crypto::AccountId;
+use subvt_types::telemetry::validator::TelemetryNodeOverride;
 
 impl PostgreSQLAppStorage {
     pub async fn get_user_registration_count_from_ip(
@@ -39,11 +43,14 @@ impl PostgreSQLAppStorage {
         Ok(result.0 as u64)
     }
 
+    /// Saves the user along with the device that registered it. Returns `None` and saves nothing
+    /// if the device key belongs to another user, including a soft-deleted one.
     pub async fn save_user(
         &self,
         user: &User,
         registration_ip: Option<&str>,
-    ) -> anyhow::Result<u32> {
+    ) -> anyhow::Result<Option<u32>> {
+        let mut transaction = self.connection_pool.begin().await?;
         let result: (i32,) = sqlx::query_as(
             r#"
             INSERT INTO app_user (public_key_hex, registration_ip)
@@ -55,9 +62,33 @@ impl PostgreSQLAppStorage {
         )
         .bind(&user.public_key_hex)
         .bind(registration_ip)
-        .fetch_one(&self.connection_pool)
+        .fetch_one(&mut transaction)
         .await?;
-        Ok(result.0 as u32)
+        // users created by the Telegram bot have no key, and no device
+        if let Some(public_key_hex) = &user.public_key_hex {
+            // the existing device of an undeleted user is kept, a device of another user is
+            // a conflict
+            let maybe_device_id: Option<(i32,)> = sqlx::query_as(
+                r#"
+                INSERT INTO app_user_device (user_id, public_key_hex)
+                VALUES ($1, $2)
+                ON CONFLICT(public_key_hex) DO UPDATE SET updated_at = now()
+                WHERE app_user_device.user_id = EXCLUDED.user_id
+                AND app_user_device.revoked_at IS NULL
+                RETURNING id
+                "#,
+            )
+            .bind(result.0)
+            .bind(public_key_hex)
+            .fetch_optional(&mut transaction)
+            .await?;
+            if maybe_device_id.is_none() {
+                transaction.rollback().await?;
+                return Ok(None);
+            }
+        }
+        transaction.commit().await?;
+        Ok(Some(result.0 as u32))
     }
 
     pub async fn delete_user(&self, user_id: u32) -> anyhow::Result<bool> {
@@ -90,12 +121,105 @@ impl PostgreSQLAppStorage {
         Ok(maybe_id.is_some() && maybe_id.unwrap().0 == user_id as i32)
     }
 
+    /// Gets the ids of the users that have been soft-deleted for longer than the retention
+    /// period, and that have no Telegram notification channel. Users with Telegram channels are
+    /// purged along with their chats by the Telegram bot, as the chats are in the network
+    /// databases.
+    pub async fn get_purgeable_non_telegram_user_ids(
+        &self,
+        retention_days: u16,
+    ) -> anyhow::Result<Vec<u32>> {
+        let user_ids: Vec<(i32,)> = sqlx::query_as(
+            format!(
+                "
+                SELECT U.id FROM app_user U
+                WHERE U.deleted_at < (now() - interval '{retention_days} days')
+                AND NOT EXISTS (
+                    SELECT C.id FROM app_user_notification_channel C
+                    WHERE C.user_id = U.id
+                    AND C.notification_channel_code = 'telegram'
+                )
+                ",
+            )
+            .as_str(),
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(user_ids.iter().map(|user_id| user_id.0 as u32).collect())
+    }
+
+    /// Permanently deletes a soft-deleted user along with all of the user's data. Returns `false`
+    /// if the user doesn't exist or is not soft-deleted.
+    pub async fn purge_user(&self, user_id: u32) -> anyhow::Result<bool> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let maybe_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            SELECT id FROM app_user
+            WHERE id = $1 AND deleted_at IS NOT NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id as i32)
+        .fetch_optional(&mut transaction)
+        .await?;
+        if maybe_id.is_none() {
+            return Ok(false);
+        }
+        // team validators and rules created by the user are handed over to another member
+        for table in ["app_user_validator", "app_user_notification_rule"] {
+            sqlx::query(
+                format!(
+                    "
+                    UPDATE {table} T
+                    SET user_id = (
+                        SELECT TM.user_id FROM app_team_member TM
+                        WHERE TM.team_id = T.team_id AND TM.user_id != $1
+                        ORDER BY TM.role DESC, TM.created_at ASC
+                        LIMIT 1
+                    )
+                    WHERE T.user_id = $1
+                    AND T.team_id IS NOT NULL
+                    AND EXISTS (
+                        SELECT TM.user_id FROM app_team_member TM
+                        WHERE TM.team_id = T.team_id AND TM.user_id != $1
+                    )
+                    "
+                )
+                .as_str(),
+            )
+            .bind(user_id as i32)
+            .execute(&mut transaction)
+            .await?;
+        }
+        // rules first, as rule validators and channels restrict the deletion of user
+        // validators and channels
+        for query in [
+            "DELETE FROM app_notification WHERE user_id = $1",
+            "DELETE FROM app_user_notification_rule WHERE user_id = $1",
+            "DELETE FROM app_user_notification_channel WHERE user_id = $1",
+            "DELETE FROM app_user_validator WHERE user_id = $1",
+            "DELETE FROM app_user_device WHERE user_id = $1",
+            "DELETE FROM app_user WHERE id = $1",
+        ] {
+            sqlx::query(query)
+                .bind(user_id as i32)
+                .execute(&mut transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    /// Checks whether a non-deleted user has an active (non-revoked) device with the given key.
     pub async fn user_exists_by_public_key(&self, public_key_hex: &str) -> anyhow::Result<bool> {
         let record_count: (i64,) = sqlx::query_as(
             r#"
-            SELECT COUNT(DISTINCT id) FROM app_user
-            WHERE public_key_hex = $1
-            AND deleted_at IS NULL
+            SELECT COUNT(DISTINCT U.id)
+            FROM app_user U
+            INNER JOIN app_user_device D ON D.user_id = U.id
+            WHERE D.public_key_hex = $1
+            AND D.revoked_at IS NULL
+            AND U.deleted_at IS NULL
             "#,
         )
         .bind(public_key_hex)
@@ -104,29 +228,328 @@ impl PostgreSQLAppStorage {
         Ok(record_count.0 > 0)
     }
 
+    /// Gets the user that has an active device with the given key. The returned user's public key
+    /// is the key of the device.
     pub async fn get_user_by_public_key(
         &self,
         public_key_hex: &str,
     ) -> anyhow::Result<Option<User>> {
-        let maybe_db_user: Option<(i32, Option<String>)> = sqlx::query_as(
+        Ok(self
+            .get_user_device_by_public_key(public_key_hex)
+            .await?
+            .map(|device| User {
+                id: device.user_id,
+                public_key_hex: Some(device.public_key_hex),
+            }))
+    }
+
+    /// Checks whether any device, active or revoked, has ever been registered with the given key.
+    /// Revoked and rotated keys cannot be registered again.
+    pub async fn user_device_exists_by_public_key(
+        &self,
+        public_key_hex: &str,
+    ) -> anyhow::Result<bool> {
+        let record_count: (i64,) = sqlx::query_as(
             r#"
-            SELECT id, public_key_hex
-            FROM app_user
+            SELECT COUNT(id) FROM app_user_device
             WHERE public_key_hex = $1
-            AND deleted_at IS NULL
+            "#,
+        )
+        .bind(public_key_hex)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(record_count.0 > 0)
+    }
+
+    pub async fn user_device_is_revoked_by_public_key(
+        &self,
+        public_key_hex: &str,
+    ) -> anyhow::Result<bool> {
+        let record_count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(id) FROM app_user_device
+            WHERE public_key_hex = $1
+            AND revoked_at IS NOT NULL
+            "#,
+        )
+        .bind(public_key_hex)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(record_count.0 > 0)
+    }
+
+    /// Gets the active device with the given key, if its user is not deleted.
+    pub async fn get_user_device_by_public_key(
+        &self,
+        public_key_hex: &str,
+    ) -> anyhow::Result<Option<UserDevice>> {
+        let maybe_db_device: Option<PostgresUserDevice> = sqlx::query_as(
+            r#"
+            SELECT D.id, D.user_id, D.public_key_hex, D.name, D.created_at
+            FROM app_user_device D
+            INNER JOIN app_user U ON U.id = D.user_id
+            WHERE D.public_key_hex = $1
+            AND D.revoked_at IS NULL
+            AND U.deleted_at IS NULL
             "#,
         )
         .bind(public_key_hex)
         .fetch_optional(&self.connection_pool)
         .await?;
-        if let Some(db_user) = maybe_db_user {
-            Ok(Some(User {
-                id: db_user.0 as u32,
-                public_key_hex: db_user.1,
-            }))
-        } else {
-            Ok(None)
+        Ok(maybe_db_device.map(UserDevice::from))
+    }
+
+    /// Saves a new device with the given key for the user.
+    pub async fn save_user_device(
+        &self,
+        user_id: u32,
+        public_key_hex: &str,
+        name: Option<&str>,
+    ) -> anyhow::Result<UserDevice> {
+        let db_device: PostgresUserDevice = sqlx::query_as(
+            r#"
+            INSERT INTO app_user_device (user_id, public_key_hex, name)
+            VALUES ($1, $2, $3)
+            RETURNING id, user_id, public_key_hex, name, created_at
+            "#,
+        )
+        .bind(user_id as i32)
+        .bind(public_key_hex)
+        .bind(name)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(UserDevice::from(db_device))
+    }
+
+    /// Gets the id of the user who has most recently verified the ownership of a validator of
+    /// their own (not of a team) with the given stash account, if any.
+    pub async fn get_user_id_by_verified_validator_account_id(
+        &self,
+        account_id: &AccountId,
+    ) -> anyhow::Result<Option<u32>> {
+        let maybe_user_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            SELECT UV.user_id
+            FROM app_user_validator UV
+            INNER JOIN app_user U ON U.id = UV.user_id
+            WHERE UV.validator_account_id = $1
+            AND UV.ownership_verified_at IS NOT NULL
+            AND UV.team_id IS NULL
+            AND UV.deleted_at IS NULL
+            AND U.deleted_at IS NULL
+            ORDER BY UV.ownership_verified_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(account_id.to_string())
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_user_id.map(|user_id| user_id.0 as u32))
+    }
+
+    /// Gets the active devices of the user.
+    pub async fn get_user_devices(&self, user_id: u32) -> anyhow::Result<Vec<UserDevice>> {
+        let db_devices: Vec<PostgresUserDevice> = sqlx::query_as(
+            r#"
+            SELECT id, user_id, public_key_hex, name, created_at
+            FROM app_user_device
+            WHERE user_id = $1
+            AND revoked_at IS NULL
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(user_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_devices.into_iter().map(UserDevice::from).collect())
+    }
+
+    pub async fn user_device_exists(&self, user_id: u32, device_id: u32) -> anyhow::Result<bool> {
+        let record_count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(id) FROM app_user_device
+            WHERE user_id = $1
+            AND id = $2
+            AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id as i32)
+        .bind(device_id as i32)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(record_count.0 > 0)
+    }
+
+    /// Revokes the device unless it is the only active device of the user. The device's key can
+    /// no longer authenticate, and cannot be registered again. The user row is locked so that
+    /// concurrent revocations cannot leave the user without a device.
+    pub async fn revoke_user_device(&self, user_id: u32, device_id: u32) -> anyhow::Result<bool> {
+        let mut transaction = self.connection_pool.begin().await?;
+        sqlx::query("SELECT id FROM app_user WHERE id = $1 FOR UPDATE")
+            .bind(user_id as i32)
+            .execute(&mut transaction)
+            .await?;
+        let maybe_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE app_user_device
+            SET revoked_at = now(), updated_at = now()
+            WHERE id = $1
+            AND user_id = $2
+            AND revoked_at IS NULL
+            AND (
+                SELECT COUNT(id) FROM app_user_device
+                WHERE user_id = $2
+                AND revoked_at IS NULL
+            ) > 1
+            RETURNING id
+            "#,
+        )
+        .bind(device_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(maybe_id.is_some())
+    }
+
+    /// Replaces the key of the device with a new one. The device with the old key is revoked and
+    /// a new device with the same name is created for the new key in a single transaction. The
+    /// user's own key is replaced too if it is the old key.
+    pub async fn rotate_user_device_key(
+        &self,
+        device: &UserDevice,
+        new_public_key_hex: &str,
+    ) -> anyhow::Result<Option<UserDevice>> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let maybe_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE app_user_device
+            SET revoked_at = now(), updated_at = now()
+            WHERE id = $1
+            AND revoked_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(device.id as i32)
+        .fetch_optional(&mut transaction)
+        .await?;
+        if maybe_id.is_none() {
+            return Ok(None);
         }
+        // the user's registration key follows the key of the device that registered the user
+        sqlx::query(
+            r#"
+            UPDATE app_user
+            SET public_key_hex = $1, updated_at = now()
+            WHERE id = $2
+            AND public_key_hex = $3
+            "#,
+        )
+        .bind(new_public_key_hex)
+        .bind(device.user_id as i32)
+        .bind(&device.public_key_hex)
+        .execute(&mut transaction)
+        .await?;
+        let db_device: PostgresUserDevice = sqlx::query_as(
+            r#"
+            INSERT INTO app_user_device (user_id, public_key_hex, name)
+            VALUES ($1, $2, $3)
+            RETURNING id, user_id, public_key_hex, name, created_at
+            "#,
+        )
+        .bind(device.user_id as i32)
+        .bind(new_public_key_hex)
+        .bind(&device.name)
+        .fetch_one(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(Some(UserDevice::from(db_device)))
+    }
+
+    /// Saves a one-time device link code created by an active device of the user.
+    pub async fn save_user_device_link(
+        &self,
+        device: &UserDevice,
+        code: &str,
+        expiry_mins: u16,
+    ) -> anyhow::Result<UserDeviceLink> {
+        let result: (NaiveDateTime,) = sqlx::query_as(
+            format!(
+                "
+                INSERT INTO app_user_device_link (user_id, device_id, code, expires_at)
+                VALUES ($1, $2, $3, now() + interval '{expiry_mins} minutes')
+                RETURNING expires_at
+                ",
+            )
+            .as_str(),
+        )
+        .bind(device.user_id as i32)
+        .bind(device.id as i32)
+        .bind(code)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(UserDeviceLink {
+            code: code.to_string(),
+            expires_at: result.0,
+        })
+    }
+
+    /// Links a new device with the given key to the user that created the link code. The code
+    /// can be used only once, before it expires, and while the approving device is still active.
+    /// Returns `None` if there is no such code.
+    pub async fn link_user_device(
+        &self,
+        code: &str,
+        public_key_hex: &str,
+        name: Option<&str>,
+    ) -> anyhow::Result<Option<UserDevice>> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let maybe_link: Option<(i32, i32)> = sqlx::query_as(
+            r#"
+            SELECT L.id, L.user_id
+            FROM app_user_device_link L
+            INNER JOIN app_user_device D ON D.id = L.device_id
+            INNER JOIN app_user U ON U.id = L.user_id
+            WHERE L.code = $1
+            AND L.expires_at > now()
+            AND L.linked_device_id IS NULL
+            AND D.revoked_at IS NULL
+            AND U.deleted_at IS NULL
+            FOR UPDATE OF L
+            "#,
+        )
+        .bind(code)
+        .fetch_optional(&mut transaction)
+        .await?;
+        let (link_id, user_id) = match maybe_link {
+            Some(link) => link,
+            None => return Ok(None),
+        };
+        let db_device: PostgresUserDevice = sqlx::query_as(
+            r#"
+            INSERT INTO app_user_device (user_id, public_key_hex, name)
+            VALUES ($1, $2, $3)
+            RETURNING id, user_id, public_key_hex, name, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(public_key_hex)
+        .bind(name)
+        .fetch_one(&mut transaction)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE app_user_device_link
+            SET linked_device_id = $1
+            WHERE id = $2
+            "#,
+        )
+        .bind(db_device.0)
+        .bind(link_id)
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(Some(UserDevice::from(db_device)))
     }
 
     pub async fn user_exists_by_id(&self, id: u32) -> anyhow::Result<bool> {
@@ -244,6 +667,32 @@ impl PostgreSQLAppStorage {
         Ok(result.0 as u32)
     }
 
+    /// Updates the target of the user's notification channels of the given type, e.g. when a
+    /// Telegram group gets migrated to a supergroup with a new chat id. Returns the number of
+    /// updated channels.
+    pub async fn update_user_notification_channel_target(
+        &self,
+        user_id: u32,
+        channel: &str,
+        target: &str,
+        new_target: &str,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE app_user_notification_channel
+            SET target = $4
+            WHERE user_id = $1 AND notification_channel_code = $2 AND target = $3 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(user_id as i32)
+        .bind(channel)
+        .bind(target)
+        .bind(new_target)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete_user_notification_channel(&self, id: u32) -> anyhow::Result<bool> {
         let maybe_id: Option<(i32,)> = sqlx::query_as(
             r#"
@@ -267,7 +716,7 @@ impl PostgreSQLAppStorage {
         let record_count: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(DISTINCT id) FROM app_user_validator
-            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+            WHERE id = $1 AND user_id = $2 AND team_id IS NULL AND deleted_at IS NULL
             "#,
         )
         .bind(user_validator_id as i32)
@@ -277,6 +726,8 @@ impl PostgreSQLAppStorage {
         Ok(record_count.0 > 0)
     }
 
+    /// Checks whether the validator exists in the user's own validators, or in the team's
+    /// validators if it's a team validator.
     pub async fn user_validator_exists(
         &self,
         user_validator: &UserValidator,
@@ -284,7 +735,10 @@ impl PostgreSQLAppStorage {
         let record_count: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(DISTINCT id) FROM app_user_validator
-            WHERE user_id = $1
+            WHERE (
+                ($4::INTEGER IS NULL AND user_id = $1 AND team_id IS NULL)
+                OR team_id = $4
+            )
             AND network_id = $2
             AND validator_account_id = $3
             AND deleted_at IS NULL
@@ -293,17 +747,18 @@ impl PostgreSQLAppStorage {
         .bind(user_validator.user_id as i32)
         .bind(user_validator.network_id as i32)
         .bind(user_validator.validator_account_id.to_string())
+        .bind(user_validator.team_id.map(|team_id| team_id as i32))
         .fetch_one(&self.connection_pool)
         .await?;
         Ok(record_count.0 > 0)
     }
 
     pub async fn get_user_validators(&self, user_id: u32) -> anyhow::Result<Vec<UserValidator>> {
-        let db_user_validators: Vec<(i32, i32, i32, String)> = sqlx::query_as(
+        let db_user_validators: Vec<PostgresUserValidator> = sqlx::query_as(
             r#"
-            SELECT id, user_id, network_id, validator_account_id
+            SELECT id, user_id, network_id, validator_account_id, ownership_verified_at, team_id
             FROM app_user_validator
-            WHERE user_id = $1 AND deleted_at IS NULL
+            WHERE user_id = $1 AND team_id IS NULL AND deleted_at IS NULL
             ORDER BY id ASC
             "#,
         )
@@ -317,29 +772,183 @@ impl PostgreSQLAppStorage {
                 user_id: db_user_validator.1 as u32,
                 network_id: db_user_validator.2 as u32,
                 validator_account_id: AccountId::from_str(&db_user_validator.3)?,
+                is_ownership_verified: db_user_validator.4.is_some(),
+                team_id: None,
             });
         }
         Ok(user_validators)
     }
 
-    pub async fn save_user_validator(&self, user_validator: &UserValidator) -> anyhow::Result<u32> {
-        let result: (i32,) = sqlx::query_as(
+    /// Gets a validator of the user, or of one of the user's teams.
+    pub async fn get_user_validator_by_id(
+        &self,
+        user_id: u32,
+        user_validator_id: u32,
+    ) -> anyhow::Result<Option<UserValidator>> {
+        let maybe_db_user_validator: Option<PostgresUserValidator> = sqlx::query_as(
             r#"
-            INSERT INTO app_user_validator (user_id, network_id, validator_account_id)
-            VALUES ($1, $2, $3)
-            ON CONFLICT (user_id, network_id, validator_account_id) WHERE deleted_at IS NULL
-            DO UPDATE SET deleted_at = NULL, updated_at = now()
+            SELECT id, user_id, network_id, validator_account_id, ownership_verified_at, team_id
+            FROM app_user_validator
+            WHERE id = $1
+            AND (
+                (user_id = $2 AND team_id IS NULL)
+                OR team_id IN (SELECT team_id FROM app_team_member WHERE user_id = $2)
+            )
+            AND deleted_at IS NULL
+            "#,
+        )
+        .bind(user_validator_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_db_user_validator.map(UserValidator::from))
+    }
+
+    /// Marks the user validator as ownership-verified, after the user has signed a challenge
+    /// with the validator stash account.
+    pub async fn set_user_validator_ownership_verified(
+        &self,
+        user_validator_id: u32,
+    ) -> anyhow::Result<bool> {
+        let maybe_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE app_user_validator
+            SET ownership_verified_at = now(), updated_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING id
             "#,
         )
-        .bind(user_validator.user_id as i32)
-        .bind(user_validator.network_id as i32)
-        .bind(user_validator.validator_account_id.to_string())
-        .fetch_one(&self.connection_pool)
+        .bind(user_validator_id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_id.is_some())
+    }
+
+    /// Sets the Telemetry node of the user validator, replaces the earlier one if any.
+    pub async fn save_user_validator_telemetry_override(
+        &self,
+        user_validator_id: u32,
+        maybe_node_network_id: Option<&str>,
+        maybe_node_name: Option<&str>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO app_user_validator_telemetry_override (user_validator_id, node_network_id, node_name)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_validator_id) DO UPDATE
+            SET node_network_id = EXCLUDED.node_network_id, node_name = EXCLUDED.node_name, updated_at = now()
+            "#,
+        )
+            .bind(user_validator_id as i32)
+            .bind(maybe_node_network_id)
+            .bind(maybe_node_name)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_user_validator_telemetry_override(
+        &self,
+        user_validator_id: u32,
+    ) -> anyhow::Result<bool> {
+        let maybe_id: Option<(i32,)> = sqlx::query_as(
+            r#"
+            DELETE FROM app_user_validator_telemetry_override
+            WHERE user_validator_id = $1
+            RETURNING user_validator_id
+            "#,
+        )
+        .bind(user_validator_id as i32)
+        .fetch_optional(&self.connection_pool)
         .await?;
+        Ok(maybe_id.is_some())
+    }
+
+    /// Telemetry node overrides of the ownership-verified validators on the network. Used by
+    /// the Telemetry processor in the node-validator matching, where the first matching override
+    /// wins, so the older overrides come first.
+    pub async fn get_telemetry_node_overrides(
+        &self,
+        network_id: u32,
+    ) -> anyhow::Result<Vec<TelemetryNodeOverride>> {
+        let db_overrides: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT UV.validator_account_id, T.node_network_id, T.node_name
+            FROM app_user_validator_telemetry_override T
+            INNER JOIN app_user_validator UV ON UV.id = T.user_validator_id
+            WHERE UV.network_id = $1
+            AND UV.ownership_verified_at IS NOT NULL
+            AND UV.deleted_at IS NULL
+            GROUP BY UV.validator_account_id, T.node_network_id, T.node_name
+            ORDER BY MIN(T.created_at) ASC, UV.validator_account_id ASC
+            "#,
+        )
+        .bind(network_id as i32)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut overrides = Vec::new();
+        for db_override in db_overrides {
+            overrides.push(TelemetryNodeOverride {
+                validator_account_id: AccountId::from_str(&db_override.0)?,
+                network_id: db_override.1,
+                node_name: db_override.2,
+            });
+        }
+        Ok(overrides)
+    }
+
+    fn get_save_user_validator_query(user_validator: &UserValidator) -> String {
+        // personal and team validators are unique in different partial indices
+        let conflict_target = if user_validator.team_id.is_some() {
+            "(team_id, network_id, validator_account_id) WHERE deleted_at IS NULL AND team_id IS NOT NULL"
+        } else {
+            "(user_id, network_id, validator_account_id) WHERE deleted_at IS NULL AND team_id IS NULL"
+        };
+        format!(
+            "
+            INSERT INTO app_user_validator (user_id, network_id, validator_account_id, team_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT {conflict_target}
+            DO UPDATE SET deleted_at = NULL, updated_at = now()
+            RETURNING id
+            ",
+        )
+    }
+
+    pub async fn save_user_validator(&self, user_validator: &UserValidator) -> anyhow::Result<u32> {
+        let result: (i32,) =
+            sqlx::query_as(Self::get_save_user_validator_query(user_validator).as_str())
+                .bind(user_validator.user_id as i32)
+                .bind(user_validator.network_id as i32)
+                .bind(user_validator.validator_account_id.to_string())
+                .bind(user_validator.team_id.map(|team_id| team_id as i32))
+                .fetch_one(&self.connection_pool)
+                .await?;
         Ok(result.0 as u32)
     }
 
+    /// Saves the validators in a single transaction, returns their ids in the same order.
+    pub async fn save_user_validators(
+        &self,
+        user_validators: &[UserValidator],
+    ) -> anyhow::Result<Vec<u32>> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let mut ids = Vec::new();
+        for user_validator in user_validators {
+            let result: (i32,) =
+                sqlx::query_as(Self::get_save_user_validator_query(user_validator).as_str())
+                    .bind(user_validator.user_id as i32)
+                    .bind(user_validator.network_id as i32)
+                    .bind(user_validator.validator_account_id.to_string())
+                    .bind(user_validator.team_id.map(|team_id| team_id as i32))
+                    .fetch_one(&mut transaction)
+                    .await?;
+            ids.push(result.0 as u32);
+        }
+        transaction.commit().await?;
+        Ok(ids)
+    }
+
     pub async fn delete_user_validator(&self, id: u32) -> anyhow::Result<bool> {
         let maybe_id: Option<(i32,)> = sqlx::query_as(
             r#"
@@ -383,7 +992,7 @@ impl PostgreSQLAppStorage {
     ) -> anyhow::Result<Vec<UserValidator>> {
         Ok(sqlx::query_as(
             r#"
-            SELECT id, user_id, network_id, validator_account_id
+            SELECT id, user_id, network_id, validator_account_id, ownership_verified_at, team_id
             FROM app_user_validator
             WHERE id IN (
                 SELECT user_validator_id
@@ -457,7 +1066,7 @@ impl PostgreSQLAppStorage {
     ) -> anyhow::Result<Option<UserNotificationRule>> {
         let maybe_db_notification_rule: Option<PostgresUserNotificationRule> = sqlx::query_as(
             r#"
-            SELECT id, user_id, notification_type_code, name, network_id, is_for_all_validators, period_type, period, notes
+            SELECT id, user_id, notification_type_code, name, network_id, is_for_all_validators, period_type, period, notes, team_id
             FROM app_user_notification_rule
             WHERE id = $1
             "#
@@ -485,9 +1094,17 @@ impl PostgreSQLAppStorage {
         } else {
             return Ok(None);
         };
+        // team rules are delivered to the channels of all team members
+        let notification_channels = if let Some(team_id) = db_notification_rule.9 {
+            self.get_team_notification_channels(team_id as u32).await?
+        } else {
+            self.get_user_notification_rule_channels(db_notification_rule.0 as u32)
+                .await?
+        };
         Ok(Some(UserNotificationRule {
             id: db_notification_rule.0 as u32,
             user_id: db_notification_rule.1 as u32,
+            team_id: db_notification_rule.9.map(|team_id| team_id as u32),
             notification_type,
             name: db_notification_rule.3,
             network: maybe_network,
@@ -497,9 +1114,7 @@ impl PostgreSQLAppStorage {
             validators: self
                 .get_user_notification_rule_validators(db_notification_rule.0 as u32)
                 .await?,
-            notification_channels: self
-                .get_user_notification_rule_channels(db_notification_rule.0 as u32)
-                .await?,
+            notification_channels,
             parameters: self
                 .get_user_notification_rule_parameters(db_notification_rule.0 as u32)
                 .await?,
@@ -515,7 +1130,7 @@ impl PostgreSQLAppStorage {
             r#"
             SELECT id
             FROM app_user_notification_rule
-            WHERE user_id = $1 AND deleted_at IS NULL
+            WHERE user_id = $1 AND team_id IS NULL AND deleted_at IS NULL
             "#,
         )
         .bind(user_id as i32)
@@ -541,7 +1156,7 @@ impl PostgreSQLAppStorage {
         let record_count: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(DISTINCT id) FROM app_user_notification_rule
-            WHERE id = $1 AND user_id = $2
+            WHERE id = $1 AND user_id = $2 AND team_id IS NULL
             AND deleted_at IS NULL
             "#,
         )
@@ -552,6 +1167,41 @@ impl PostgreSQLAppStorage {
         Ok(record_count.0 > 0)
     }
 
+    /// Gets the non-deleted rules of the notification type of the user, or of the team if a team
+    /// id is given.
+    pub async fn get_user_notification_rules_by_type(
+        &self,
+        (user_id, team_id): (u32, Option<u32>),
+        notification_type_code: &str,
+    ) -> anyhow::Result<Vec<UserNotificationRule>> {
+        let rule_ids: Vec<(i32,)> = sqlx::query_as(
+            r#"
+            SELECT id FROM app_user_notification_rule
+            WHERE (
+                ($3::INTEGER IS NULL AND user_id = $1 AND team_id IS NULL)
+                OR team_id = $3
+            )
+            AND notification_type_code = $2
+            AND deleted_at IS NULL
+            "#,
+        )
+        .bind(user_id as i32)
+        .bind(notification_type_code)
+        .bind(team_id.map(|team_id| team_id as i32))
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut rules = Vec::new();
+        for rule_id in rule_ids {
+            if let Some(rule) = self
+                .get_user_notification_rule_by_id(rule_id.0 as u32)
+                .await?
+            {
+                rules.push(rule);
+            }
+        }
+        Ok(rules)
+    }
+
     pub async fn delete_user_notification_rule(&self, id: u32) -> anyhow::Result<bool> {
         let maybe_id: Option<(i32,)> = sqlx::query_as(
             r#"
@@ -567,9 +1217,11 @@ impl PostgreSQLAppStorage {
         Ok(maybe_id.is_some() && maybe_id.unwrap().0 == id as i32)
     }
 
+    /// Saves a notification rule of the user, or of one of the user's teams if a team id is
+    /// given. Team rules have no channels of their own.
     pub async fn save_user_notification_rule(
         &self,
-        user_id: u32,
+        (user_id, team_id): (u32, Option<u32>),
         notification_type_code: &str,
         (name, notes): (Option<&str>, Option<&str>),
         (network_id, is_for_all_validators): (Option<u32>, bool),
@@ -581,11 +1233,75 @@ impl PostgreSQLAppStorage {
         ),
     ) -> anyhow::Result<u32> {
         let mut transaction = self.connection_pool.begin().await?;
+        let user_notification_rule_id = Self::save_user_notification_rule_in_transaction(
+            &mut transaction,
+            (user_id, team_id),
+            notification_type_code,
+            (name, notes),
+            (network_id, is_for_all_validators),
+            (period_type, period),
+            (
+                user_validator_ids,
+                user_notification_channel_ids,
+                parameters,
+            ),
+        )
+        .await?;
+        transaction.commit().await?;
+        Ok(user_notification_rule_id)
+    }
+
+    /// Saves the rules of a notification rule template for the given validators and channels,
+    /// in a single transaction. Returns the ids of the new rules.
+    pub async fn save_user_notification_rules_from_template(
+        &self,
+        (user_id, team_id): (u32, Option<u32>),
+        template: &UserNotificationRuleTemplate,
+        network_id: Option<u32>,
+        (user_validator_ids, user_notification_channel_ids): (&HashSet<u32>, &HashSet<u32>),
+    ) -> anyhow::Result<Vec<u32>> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let mut rule_ids = Vec::new();
+        for rule in &template.rules {
+            rule_ids.push(
+                Self::save_user_notification_rule_in_transaction(
+                    &mut transaction,
+                    (user_id, team_id),
+                    &rule.notification_type_code,
+                    (rule.name.as_deref(), rule.notes.as_deref()),
+                    (network_id, user_validator_ids.is_empty()),
+                    (&rule.period_type, rule.period),
+                    (
+                        user_validator_ids,
+                        user_notification_channel_ids,
+                        &rule.parameters,
+                    ),
+                )
+                .await?,
+            );
+        }
+        transaction.commit().await?;
+        Ok(rule_ids)
+    }
+
+    async fn save_user_notification_rule_in_transaction(
+        transaction: &mut Transaction<'_, Postgres>,
+        (user_id, team_id): (u32, Option<u32>),
+        notification_type_code: &str,
+        (name, notes): (Option<&str>, Option<&str>),
+        (network_id, is_for_all_validators): (Option<u32>, bool),
+        (period_type, period): (&NotificationPeriodType, u16),
+        (user_validator_ids, user_notification_channel_ids, parameters): (
+            &HashSet<u32>,
+            &HashSet<u32>,
+            &[UserNotificationRuleParameter],
+        ),
+    ) -> anyhow::Result<u32> {
         // insert notification rule
         let result: (i32,) = sqlx::query_as(
             r#"
-            INSERT INTO app_user_notification_rule (user_id, notification_type_code, name, network_id, is_for_all_validators, period_type, period, notes)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO app_user_notification_rule (user_id, notification_type_code, name, network_id, is_for_all_validators, period_type, period, notes, team_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             RETURNING id
             "#,
         )
@@ -597,7 +1313,8 @@ impl PostgreSQLAppStorage {
             .bind(period_type)
             .bind(period as i32)
             .bind(notes)
-            .fetch_one(&self.connection_pool)
+            .bind(team_id.map(|team_id| team_id as i32))
+            .fetch_one(&mut *transaction)
             .await?;
         let user_notification_rule_id = result.0;
         // insert validators
@@ -610,7 +1327,7 @@ impl PostgreSQLAppStorage {
             )
                 .bind(user_notification_rule_id)
                 .bind(*user_validator_id as i32)
-                .execute(&mut transaction)
+                .execute(&mut *transaction)
                 .await?;
         }
         // insert channel ids
@@ -623,7 +1340,7 @@ impl PostgreSQLAppStorage {
             )
                 .bind(user_notification_rule_id)
                 .bind(*user_notification_channel_id as i32)
-                .execute(&mut transaction)
+                .execute(&mut *transaction)
                 .await?;
         }
         // insert params
@@ -637,10 +1354,9 @@ impl PostgreSQLAppStorage {
                 .bind(user_notification_rule_id)
                 .bind(param.parameter_type_id as i32)
                 .bind(&param.value)
-                .execute(&mut transaction)
+                .execute(&mut *transaction)
                 .await?;
         }
-        transaction.commit().await?;
         Ok(user_notification_rule_id as u32)
     }
 
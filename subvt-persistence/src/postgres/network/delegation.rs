@@ -0,0 +1,178 @@
+//! Storage of the current OpenGov (conviction voting) delegations, maintained by the block
+//! processor from the `delegate` and `undelegate` calls.
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::conviction_voting::ConvictionVotingDelegation;
+use subvt_types::crypto::AccountId;
+use subvt_types::substrate::democracy::get_conviction_votes;
+use subvt_types::substrate::Balance;
+
+impl PostgreSQLNetworkStorage {
+    /// Older blocks don't overwrite the delegation set at a newer block, in case of the
+    /// reprocessing of past blocks.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn save_conviction_voting_delegation(
+        &self,
+        delegator_account_id: &AccountId,
+        delegate_account_id: &AccountId,
+        track_id: u16,
+        conviction: u8,
+        balance: Balance,
+        block_hash: &str,
+        block_number: u64,
+    ) -> anyhow::Result<()> {
+        self.save_account(delegator_account_id).await?;
+        self.save_account(delegate_account_id).await?;
+        sqlx::query(
+            r#"
+            INSERT INTO sub_conviction_voting_delegation (delegator_account_id, track_id, delegate_account_id, conviction, balance, block_hash, block_number)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (delegator_account_id, track_id) DO UPDATE
+            SET delegate_account_id = EXCLUDED.delegate_account_id, conviction = EXCLUDED.conviction, balance = EXCLUDED.balance, block_hash = EXCLUDED.block_hash, block_number = EXCLUDED.block_number, updated_at = now()
+            WHERE sub_conviction_voting_delegation.block_number <= EXCLUDED.block_number
+            "#,
+        )
+        .bind(delegator_account_id.to_string())
+        .bind(track_id as i16)
+        .bind(delegate_account_id.to_string())
+        .bind(conviction as i16)
+        .bind(balance.to_string())
+        .bind(block_hash)
+        .bind(block_number as i64)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Replaces the delegations up to the given block with the storage snapshot at that block.
+    /// Delegations saved from newer blocks are kept.
+    pub async fn save_conviction_voting_delegation_snapshot(
+        &self,
+        delegations: &[ConvictionVotingDelegation],
+        block_hash: &str,
+        block_number: u64,
+    ) -> anyhow::Result<()> {
+        let mut transaction = self.connection_pool.begin().await?;
+        sqlx::query(
+            r#"
+            DELETE FROM sub_conviction_voting_delegation
+            WHERE block_number <= $1
+            "#,
+        )
+        .bind(block_number as i64)
+        .execute(&mut transaction)
+        .await?;
+        for delegation in delegations {
+            for account_id in [
+                &delegation.delegator_account_id,
+                &delegation.delegate_account_id,
+            ] {
+                sqlx::query(
+                    r#"
+                    INSERT INTO sub_account (id)
+                    VALUES ($1)
+                    ON CONFLICT (id) DO NOTHING
+                    "#,
+                )
+                .bind(account_id.to_string())
+                .execute(&mut transaction)
+                .await?;
+            }
+            sqlx::query(
+                r#"
+                INSERT INTO sub_conviction_voting_delegation (delegator_account_id, track_id, delegate_account_id, conviction, balance, block_hash, block_number)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (delegator_account_id, track_id) DO NOTHING
+                "#,
+            )
+            .bind(delegation.delegator_account_id.to_string())
+            .bind(delegation.track_id as i16)
+            .bind(delegation.delegate_account_id.to_string())
+            .bind(delegation.conviction as i16)
+            .bind(delegation.balance.to_string())
+            .bind(block_hash)
+            .bind(block_number as i64)
+            .execute(&mut transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Deletes the delegation of the account on the track, and returns the delegate of the
+    /// deleted delegation if one existed.
+    pub async fn delete_conviction_voting_delegation(
+        &self,
+        delegator_account_id: &AccountId,
+        track_id: u16,
+        block_number: u64,
+    ) -> anyhow::Result<Option<AccountId>> {
+        let maybe_result: Option<(String,)> = sqlx::query_as(
+            r#"
+            DELETE FROM sub_conviction_voting_delegation
+            WHERE delegator_account_id = $1 AND track_id = $2 AND block_number <= $3
+            RETURNING delegate_account_id
+            "#,
+        )
+        .bind(delegator_account_id.to_string())
+        .bind(track_id as i16)
+        .bind(block_number as i64)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        if let Some(result) = maybe_result {
+            Ok(Some(AccountId::from_str(&result.0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn conviction_voting_delegate_exists(
+        &self,
+        delegate_account_id: &AccountId,
+    ) -> anyhow::Result<bool> {
+        let record_count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(DISTINCT delegator_account_id) FROM sub_conviction_voting_delegation
+            WHERE delegate_account_id = $1
+            "#,
+        )
+        .bind(delegate_account_id.to_string())
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(record_count.0 > 0)
+    }
+
+    /// Current delegations to the given account, ordered by track, then by votes descending.
+    pub async fn get_conviction_voting_delegations_to(
+        &self,
+        delegate_account_id: &AccountId,
+    ) -> anyhow::Result<Vec<ConvictionVotingDelegation>> {
+        let db_delegations: Vec<(String, i16, i16, String, i64)> = sqlx::query_as(
+            r#"
+            SELECT delegator_account_id, track_id, conviction, balance, block_number
+            FROM sub_conviction_voting_delegation
+            WHERE delegate_account_id = $1
+            ORDER BY track_id ASC
+            "#,
+        )
+        .bind(delegate_account_id.to_string())
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut delegations = Vec::with_capacity(db_delegations.len());
+        for db_delegation in db_delegations {
+            let conviction = db_delegation.2 as u8;
+            let balance = db_delegation.3.parse::<Balance>()?;
+            delegations.push(ConvictionVotingDelegation {
+                delegator_account_id: AccountId::from_str(&db_delegation.0)?,
+                delegate_account_id: *delegate_account_id,
+                track_id: db_delegation.1 as u16,
+                conviction,
+                balance,
+                votes: get_conviction_votes(conviction, balance),
+                block_number: db_delegation.4 as u64,
+            });
+        }
+        delegations.sort_by(|a, b| a.track_id.cmp(&b.track_id).then(b.votes.cmp(&a.votes)));
+        Ok(delegations)
+    }
+}
@@ -0,0 +1,5 @@
+//! Persistence of Substrate bounties events.
+pub(crate) mod bounty_awarded;
+pub(crate) mod bounty_canceled;
+pub(crate) mod bounty_claimed;
+pub(crate) mod bounty_proposed;
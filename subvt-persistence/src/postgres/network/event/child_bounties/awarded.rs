@@ -0,0 +1,54 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::crypto::AccountId;
+
+impl PostgreSQLNetworkStorage {
+    pub async fn save_child_bounty_awarded_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        parent_bounty_index: u32,
+        child_bounty_index: u32,
+        beneficiary_account_id: &AccountId,
+    ) -> anyhow::Result<Option<i32>> {
+        self.save_account(beneficiary_account_id).await?;
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_event_child_bounty_awarded (block_hash, extrinsic_index, event_index, parent_bounty_index, child_bounty_index, beneficiary_account_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(parent_bounty_index as i32)
+            .bind(child_bounty_index as i32)
+            .bind(beneficiary_account_id.to_string())
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        Ok(maybe_result.map(|result| result.0))
+    }
+
+    pub async fn update_child_bounty_awarded_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_child_bounty_awarded
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        )
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+//! Persistence of Substrate child bounties events.
+pub(crate) mod added;
+pub(crate) mod awarded;
+pub(crate) mod canceled;
+pub(crate) mod claimed;
@@ -0,0 +1,167 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::conviction_voting::ConvictionVotingDelegatedEvent;
+use subvt_types::crypto::AccountId;
+use subvt_types::substrate::Balance;
+
+impl PostgreSQLNetworkStorage {
+    pub async fn save_conviction_voting_delegated_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        original_account_id: &AccountId,
+        delegate_account_id: &AccountId,
+    ) -> anyhow::Result<Option<i32>> {
+        self.save_account(original_account_id).await?;
+        self.save_account(delegate_account_id).await?;
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_event_conviction_voting_delegated (block_hash, extrinsic_index, event_index, original_account_id, delegate_account_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(original_account_id.to_string())
+            .bind(delegate_account_id.to_string())
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        Ok(maybe_result.map(|result| result.0))
+    }
+
+    async fn get_conviction_voting_delegated_events(
+        &self,
+        condition: &str,
+        bind: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingDelegatedEvent>> {
+        #[allow(clippy::type_complexity)]
+        let db_events: Vec<(
+            i32,
+            String,
+            i64,
+            Option<i32>,
+            i32,
+            String,
+            String,
+            Option<i16>,
+            Option<i16>,
+            Option<String>,
+        )> = sqlx::query_as(&format!(
+            r#"
+            SELECT E."id", E.block_hash, B."number", E.extrinsic_index, E.event_index, E.original_account_id, E.delegate_account_id, E.track_id, E.conviction, E.balance
+            FROM sub_event_conviction_voting_delegated E
+            INNER JOIN sub_block B ON B.hash = E.block_hash
+            WHERE {condition}
+            ORDER BY B."number" DESC, E.event_index DESC
+            LIMIT $2
+            "#
+        ))
+        .bind(bind)
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut events = Vec::new();
+        for db_event in db_events {
+            events.push(ConvictionVotingDelegatedEvent {
+                id: db_event.0 as u32,
+                block_hash: db_event.1.clone(),
+                block_number: db_event.2 as u64,
+                extrinsic_index: db_event.3.map(|index| index as u32),
+                event_index: db_event.4 as u32,
+                original_account_id: AccountId::from_str(&db_event.5)?,
+                delegate_account_id: AccountId::from_str(&db_event.6)?,
+                track_id: db_event.7.map(|track_id| track_id as u16),
+                conviction: db_event.8.map(|conviction| conviction as u8),
+                balance: db_event.9.map(|balance| balance.parse()).transpose()?,
+            })
+        }
+        Ok(events)
+    }
+
+    /// Delegations both from and to the given account, most recent first.
+    pub async fn get_conviction_voting_delegated_events_for_account(
+        &self,
+        account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingDelegatedEvent>> {
+        self.get_conviction_voting_delegated_events(
+            "E.original_account_id = $1 OR E.delegate_account_id = $1",
+            &account_id.to_string(),
+            limit,
+        )
+        .await
+    }
+
+    /// Delegations to the given account, most recent first.
+    pub async fn get_conviction_voting_delegated_events_for_delegate(
+        &self,
+        delegate_account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingDelegatedEvent>> {
+        self.get_conviction_voting_delegated_events(
+            "E.delegate_account_id = $1",
+            &delegate_account_id.to_string(),
+            limit,
+        )
+        .await
+    }
+
+    pub async fn get_conviction_voting_delegated_events_in_block(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<ConvictionVotingDelegatedEvent>> {
+        self.get_conviction_voting_delegated_events("E.block_hash = $1", block_hash, u32::MAX)
+            .await
+    }
+
+    pub async fn set_conviction_voting_delegated_event_details(
+        &self,
+        block_hash: &str,
+        event_index: i32,
+        track_id: u16,
+        conviction: u8,
+        balance: Balance,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_conviction_voting_delegated
+            SET track_id = $1, conviction = $2, balance = $3
+            WHERE block_hash = $4 AND event_index = $5
+            "#,
+        )
+        .bind(track_id as i16)
+        .bind(conviction as i16)
+        .bind(balance.to_string())
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_conviction_voting_delegated_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_conviction_voting_delegated
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        )
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+}
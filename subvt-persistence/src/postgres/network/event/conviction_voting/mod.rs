@@ -0,0 +1,4 @@
+//! Persistence of Substrate conviction voting (OpenGov) events.
+pub(crate) mod delegated;
+pub(crate) mod undelegated;
+pub(crate) mod vote;
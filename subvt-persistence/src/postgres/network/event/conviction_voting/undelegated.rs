@@ -0,0 +1,156 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::conviction_voting::ConvictionVotingUndelegatedEvent;
+use subvt_types::crypto::AccountId;
+
+impl PostgreSQLNetworkStorage {
+    pub async fn save_conviction_voting_undelegated_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        account_id: &AccountId,
+    ) -> anyhow::Result<Option<i32>> {
+        self.save_account(account_id).await?;
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_event_conviction_voting_undelegated (block_hash, extrinsic_index, event_index, account_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(account_id.to_string())
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        Ok(maybe_result.map(|result| result.0))
+    }
+
+    async fn get_conviction_voting_undelegated_events(
+        &self,
+        condition: &str,
+        bind: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingUndelegatedEvent>> {
+        #[allow(clippy::type_complexity)]
+        let db_events: Vec<(
+            i32,
+            String,
+            i64,
+            Option<i32>,
+            i32,
+            String,
+            Option<i16>,
+            Option<String>,
+        )> = sqlx::query_as(&format!(
+            r#"
+            SELECT E."id", E.block_hash, B."number", E.extrinsic_index, E.event_index, E.account_id, E.track_id, E.delegate_account_id
+            FROM sub_event_conviction_voting_undelegated E
+            INNER JOIN sub_block B ON B.hash = E.block_hash
+            WHERE {condition}
+            ORDER BY B."number" DESC, E.event_index DESC
+            LIMIT $2
+            "#
+        ))
+        .bind(bind)
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut events = Vec::new();
+        for db_event in db_events {
+            events.push(ConvictionVotingUndelegatedEvent {
+                id: db_event.0 as u32,
+                block_hash: db_event.1.clone(),
+                block_number: db_event.2 as u64,
+                extrinsic_index: db_event.3.map(|index| index as u32),
+                event_index: db_event.4 as u32,
+                account_id: AccountId::from_str(&db_event.5)?,
+                track_id: db_event.6.map(|track_id| track_id as u16),
+                delegate_account_id: db_event.7.as_deref().map(AccountId::from_str).transpose()?,
+            })
+        }
+        Ok(events)
+    }
+
+    pub async fn get_conviction_voting_undelegated_events_for_account(
+        &self,
+        account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingUndelegatedEvent>> {
+        self.get_conviction_voting_undelegated_events(
+            "E.account_id = $1",
+            &account_id.to_string(),
+            limit,
+        )
+        .await
+    }
+
+    /// Undelegations from the given account, most recent first.
+    pub async fn get_conviction_voting_undelegated_events_for_delegate(
+        &self,
+        delegate_account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingUndelegatedEvent>> {
+        self.get_conviction_voting_undelegated_events(
+            "E.delegate_account_id = $1",
+            &delegate_account_id.to_string(),
+            limit,
+        )
+        .await
+    }
+
+    pub async fn get_conviction_voting_undelegated_events_in_block(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<ConvictionVotingUndelegatedEvent>> {
+        self.get_conviction_voting_undelegated_events("E.block_hash = $1", block_hash, u32::MAX)
+            .await
+    }
+
+    pub async fn set_conviction_voting_undelegated_event_details(
+        &self,
+        block_hash: &str,
+        event_index: i32,
+        track_id: u16,
+        maybe_delegate_account_id: Option<&AccountId>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_conviction_voting_undelegated
+            SET track_id = $1, delegate_account_id = $2
+            WHERE block_hash = $3 AND event_index = $4
+            "#,
+        )
+        .bind(track_id as i16)
+        .bind(maybe_delegate_account_id.map(|account_id| account_id.to_string()))
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_conviction_voting_undelegated_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_conviction_voting_undelegated
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        )
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+}
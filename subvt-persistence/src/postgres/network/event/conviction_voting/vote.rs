@@ -0,0 +1,270 @@
+//! `Voted` and `VoteRemoved` events share the same shape, and are persisted to separate tables
+//! of the same structure.
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::conviction_voting::ConvictionVotingVoteEvent;
+use subvt_types::crypto::AccountId;
+use subvt_types::substrate::democracy::DirectVote;
+
+const VOTED_TABLE: &str = "sub_event_conviction_voting_voted";
+const VOTE_REMOVED_TABLE: &str = "sub_event_conviction_voting_vote_removed";
+
+type PostgresConvictionVotingVoteEvent = (
+    i32,
+    String,
+    i64,
+    Option<i32>,
+    i32,
+    String,
+    Option<i64>,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i32>,
+);
+
+fn parse_vote_event(
+    db_event: PostgresConvictionVotingVoteEvent,
+) -> anyhow::Result<ConvictionVotingVoteEvent> {
+    Ok(ConvictionVotingVoteEvent {
+        id: db_event.0 as u32,
+        block_hash: db_event.1.clone(),
+        block_number: db_event.2 as u64,
+        extrinsic_index: db_event.3.map(|index| index as u32),
+        event_index: db_event.4 as u32,
+        account_id: AccountId::from_str(&db_event.5)?,
+        referendum_index: db_event.6.map(|index| index as u32),
+        vote_type: db_event.7,
+        aye_balance: db_event.8.map(|balance| balance.parse()).transpose()?,
+        nay_balance: db_event.9.map(|balance| balance.parse()).transpose()?,
+        abstain_balance: db_event.10.map(|balance| balance.parse()).transpose()?,
+        conviction: db_event.11.map(|c| c as u8),
+    })
+}
+
+impl PostgreSQLNetworkStorage {
+    #[allow(clippy::too_many_arguments)]
+    async fn save_conviction_voting_vote_event(
+        &self,
+        table: &str,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        account_id: &AccountId,
+        maybe_referendum_index: Option<u32>,
+        vote: &DirectVote,
+    ) -> anyhow::Result<Option<i32>> {
+        self.save_account(account_id).await?;
+        let maybe_result: Option<(i32,)> = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO {table} (block_hash, extrinsic_index, event_index, account_id, referendum_index, vote_type, aye_balance, nay_balance, abstain_balance, conviction)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        ))
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(account_id.to_string())
+            .bind(maybe_referendum_index.map(|index| index as i64))
+            .bind(vote.ty.to_string())
+            .bind(vote.aye.map(|balance| balance.to_string()))
+            .bind(vote.nay.map(|balance| balance.to_string()))
+            .bind(vote.abstain.map(|balance| balance.to_string()))
+            .bind(vote.conviction.map(|c| c as i32))
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        Ok(maybe_result.map(|result| result.0))
+    }
+
+    async fn get_conviction_voting_vote_events_for_account(
+        &self,
+        table: &str,
+        account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingVoteEvent>> {
+        let db_events: Vec<PostgresConvictionVotingVoteEvent> = sqlx::query_as(&format!(
+            r#"
+            SELECT E."id", E.block_hash, B."number", E.extrinsic_index, E.event_index, E.account_id, E.referendum_index, E.vote_type, E.aye_balance, E.nay_balance, E.abstain_balance, E.conviction
+            FROM {table} E
+            INNER JOIN sub_block B ON B.hash = E.block_hash
+            WHERE E.account_id = $1
+            ORDER BY B."number" DESC, E.event_index DESC
+            LIMIT $2
+            "#,
+        ))
+            .bind(account_id.to_string())
+            .bind(limit as i64)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        db_events.into_iter().map(parse_vote_event).collect()
+    }
+
+    /// Older runtimes don't emit the referendum index with the vote events, in which case
+    /// it gets set after the extrinsic is processed.
+    async fn set_conviction_voting_vote_event_referendum_index(
+        &self,
+        table: &str,
+        block_hash: &str,
+        event_index: i32,
+        referendum_index: u32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(&format!(
+            r#"
+            UPDATE {table}
+            SET referendum_index = $1
+            WHERE block_hash = $2 AND event_index = $3 AND referendum_index IS NULL
+            "#,
+        ))
+        .bind(referendum_index as i64)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn update_conviction_voting_vote_event_nesting_index(
+        &self,
+        table: &str,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(&format!(
+            r#"
+            UPDATE {table}
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        ))
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn save_conviction_voting_voted_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        account_id: &AccountId,
+        maybe_referendum_index: Option<u32>,
+        vote: &DirectVote,
+    ) -> anyhow::Result<Option<i32>> {
+        self.save_conviction_voting_vote_event(
+            VOTED_TABLE,
+            block_hash,
+            extrinsic_index,
+            event_index,
+            account_id,
+            maybe_referendum_index,
+            vote,
+        )
+        .await
+    }
+
+    pub async fn save_conviction_voting_vote_removed_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        account_id: &AccountId,
+        maybe_referendum_index: Option<u32>,
+        vote: &DirectVote,
+    ) -> anyhow::Result<Option<i32>> {
+        self.save_conviction_voting_vote_event(
+            VOTE_REMOVED_TABLE,
+            block_hash,
+            extrinsic_index,
+            event_index,
+            account_id,
+            maybe_referendum_index,
+            vote,
+        )
+        .await
+    }
+
+    pub async fn get_conviction_voting_voted_events_for_account(
+        &self,
+        account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingVoteEvent>> {
+        self.get_conviction_voting_vote_events_for_account(VOTED_TABLE, account_id, limit)
+            .await
+    }
+
+    pub async fn get_conviction_voting_vote_removed_events_for_account(
+        &self,
+        account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ConvictionVotingVoteEvent>> {
+        self.get_conviction_voting_vote_events_for_account(VOTE_REMOVED_TABLE, account_id, limit)
+            .await
+    }
+
+    pub async fn set_conviction_voting_voted_event_referendum_index(
+        &self,
+        block_hash: &str,
+        event_index: i32,
+        referendum_index: u32,
+    ) -> anyhow::Result<()> {
+        self.set_conviction_voting_vote_event_referendum_index(
+            VOTED_TABLE,
+            block_hash,
+            event_index,
+            referendum_index,
+        )
+        .await
+    }
+
+    pub async fn set_conviction_voting_vote_removed_event_referendum_index(
+        &self,
+        block_hash: &str,
+        event_index: i32,
+        referendum_index: u32,
+    ) -> anyhow::Result<()> {
+        self.set_conviction_voting_vote_event_referendum_index(
+            VOTE_REMOVED_TABLE,
+            block_hash,
+            event_index,
+            referendum_index,
+        )
+        .await
+    }
+
+    pub async fn update_conviction_voting_voted_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        self.update_conviction_voting_vote_event_nesting_index(
+            VOTED_TABLE,
+            block_hash,
+            maybe_nesting_index,
+            event_index,
+        )
+        .await
+    }
+
+    pub async fn update_conviction_voting_vote_removed_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        self.update_conviction_voting_vote_event_nesting_index(
+            VOTE_REMOVED_TABLE,
+            block_hash,
+            maybe_nesting_index,
+            event_index,
+        )
+        .await
+    }
+}
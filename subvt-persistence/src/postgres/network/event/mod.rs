@@ -1,4 +1,7 @@
+pub mod bounties;
+pub mod child_bounties;
 pub mod chilled;
+pub mod conviction_voting;
 pub mod democracy;
 pub mod era_paid;
 pub mod hearbeat;
@@ -10,3 +13,4 @@ pub mod payout_started;
 pub mod referenda;
 pub mod rewarded;
 pub mod slashed;
+pub mod treasury;
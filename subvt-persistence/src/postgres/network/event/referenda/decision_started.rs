@@ -45,12 +45,13 @@ impl PostgreSQLNetworkStorage {
         &self,
         block_hash: &str,
     ) -> anyhow::Result<Vec<ReferendumDecisionStartedEvent>> {
-        let db_events: Vec<(i32, String, Option<i32>, i32, i32, i32, i64, i64, i64)> = sqlx::query_as(
+        let db_events: Vec<(i32, String, Option<i32>, i32, i32, i32, i64, i64, i64, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT "id", block_hash, extrinsic_index, event_index, referendum_index, track_id, ayes::bigint, nays::bigint, support::bigint
-            FROM sub_event_referendum_decision_started
-            WHERE block_hash = $1
-            ORDER BY "id" ASC
+            SELECT E."id", E.block_hash, E.extrinsic_index, E.event_index, E.referendum_index, E.track_id, E.ayes::bigint, E.nays::bigint, E.support::bigint, R.proposal_call_json
+            FROM sub_event_referendum_decision_started E
+            LEFT JOIN sub_referendum R ON R.post_id = E.referendum_index
+            WHERE E.block_hash = $1
+            ORDER BY E."id" ASC
             "#,
         )
             .bind(block_hash)
@@ -68,6 +69,11 @@ impl PostgreSQLNetworkStorage {
                 ayes: db_event.6 as Balance,
                 nays: db_event.7 as Balance,
                 support: db_event.8 as Balance,
+                maybe_proposal_call: db_event
+                    .9
+                    .as_deref()
+                    .map(serde_json::from_str)
+                    .transpose()?,
             })
         }
         Ok(events)
@@ -1,5 +1,6 @@
 use crate::postgres::network::PostgreSQLNetworkStorage;
 use subvt_types::app::event::referenda::ReferendumSubmittedEvent;
+use subvt_types::governance::call::ReferendumCall;
 
 impl PostgreSQLNetworkStorage {
     #[allow(clippy::too_many_arguments)]
@@ -10,11 +11,12 @@ impl PostgreSQLNetworkStorage {
         event_index: i32,
         referendum_index: u32,
         track_id: u16,
+        maybe_proposal_call: Option<&ReferendumCall>,
     ) -> anyhow::Result<Option<i32>> {
         let maybe_result: Option<(i32,)> = sqlx::query_as(
             r#"
-            INSERT INTO sub_event_referendum_submitted (block_hash, extrinsic_index, event_index, referendum_index, track_id)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO sub_event_referendum_submitted (block_hash, extrinsic_index, event_index, referendum_index, track_id, proposal_call_json)
+            VALUES ($1, $2, $3, $4, $5, $6)
             ON CONFLICT(block_hash, event_index) DO NOTHING
             RETURNING id
             "#,
@@ -24,6 +26,7 @@ impl PostgreSQLNetworkStorage {
             .bind(event_index)
             .bind(referendum_index as i32)
             .bind(track_id as i32)
+            .bind(maybe_proposal_call.map(serde_json::to_string).transpose()?)
             .fetch_optional(&self.connection_pool)
             .await?;
         if let Some(result) = maybe_result {
@@ -38,12 +41,13 @@ impl PostgreSQLNetworkStorage {
         &self,
         block_hash: &str,
     ) -> anyhow::Result<Vec<ReferendumSubmittedEvent>> {
-        let db_events: Vec<(i32, String, Option<i32>, i32, i32, i32)> = sqlx::query_as(
+        let db_events: Vec<(i32, String, Option<i32>, i32, i32, i32, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT "id", block_hash, extrinsic_index, event_index, referendum_index, track_id
-            FROM sub_event_referendum_submitted
-            WHERE block_hash = $1
-            ORDER BY "id" ASC
+            SELECT E."id", E.block_hash, E.extrinsic_index, E.event_index, E.referendum_index, E.track_id, COALESCE(E.proposal_call_json, R.proposal_call_json)
+            FROM sub_event_referendum_submitted E
+            LEFT JOIN sub_referendum R ON R.post_id = E.referendum_index
+            WHERE E.block_hash = $1
+            ORDER BY E."id" ASC
             "#,
         )
         .bind(block_hash)
@@ -58,6 +62,11 @@ impl PostgreSQLNetworkStorage {
                 event_index: db_event.3 as u32,
                 referendum_index: db_event.4 as u32,
                 track_id: db_event.5 as u16,
+                maybe_proposal_call: db_event
+                    .6
+                    .as_deref()
+                    .map(serde_json::from_str)
+                    .transpose()?,
             })
         }
         Ok(events)
@@ -0,0 +1,66 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::crypto::AccountId;
+use subvt_types::substrate::{Balance, BlockNumber};
+
+impl PostgreSQLNetworkStorage {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn save_treasury_asset_spend_approved_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        spend_index: u32,
+        asset_kind: &serde_json::Value,
+        amount: Balance,
+        beneficiary: &serde_json::Value,
+        maybe_beneficiary_account_id: Option<&AccountId>,
+        valid_from: BlockNumber,
+        expire_at: BlockNumber,
+    ) -> anyhow::Result<Option<i32>> {
+        if let Some(beneficiary_account_id) = maybe_beneficiary_account_id {
+            self.save_account(beneficiary_account_id).await?;
+        }
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_event_treasury_asset_spend_approved (block_hash, extrinsic_index, event_index, spend_index, asset_kind_json, amount, beneficiary_json, beneficiary_account_id, valid_from, expire_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(spend_index as i32)
+            .bind(serde_json::to_string(asset_kind)?)
+            .bind(amount.to_string())
+            .bind(serde_json::to_string(beneficiary)?)
+            .bind(maybe_beneficiary_account_id.map(|account_id| account_id.to_string()))
+            .bind(valid_from as i64)
+            .bind(expire_at as i64)
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        Ok(maybe_result.map(|result| result.0))
+    }
+
+    pub async fn update_treasury_asset_spend_approved_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_treasury_asset_spend_approved
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        )
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+}
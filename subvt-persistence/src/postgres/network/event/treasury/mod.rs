@@ -0,0 +1,5 @@
+//! Persistence of Substrate treasury events.
+pub(crate) mod asset_spend_approved;
+pub(crate) mod awarded;
+pub(crate) mod paid;
+pub(crate) mod spend_approved;
@@ -7,6 +7,7 @@ use subvt_config::Config;
 pub mod account;
 pub mod app_event;
 pub mod block;
+pub mod delegation;
 pub mod epoch;
 pub mod era;
 pub mod error_log;
@@ -21,6 +22,8 @@ pub mod report;
 pub mod staking;
 pub mod telegram;
 pub mod telemetry;
+pub mod treasury;
+pub mod validator_snapshot;
 
 pub struct PostgreSQLNetworkStorage {
     uri: String,
@@ -1,9 +1,13 @@
 //! PostgreSQL notifications support, and SubVT notification generator state storage.
+use crate::postgres::network::validator_snapshot::get_date_time;
 use crate::postgres::network::PostgreSQLNetworkStorage;
 use serde::Serialize;
 use sqlx::postgres::PgListener;
 use std::future::Future;
+use subvt_types::crypto::AccountId;
+use subvt_types::governance::referendum::ReferendumTallyState;
 use subvt_types::rdb::BlockProcessedNotification;
+use subvt_types::substrate::Balance;
 
 enum Channel {
     BlockProcessed,
@@ -161,4 +165,302 @@ impl PostgreSQLNetworkStorage {
         .await?;
         Ok(())
     }
+
+    pub async fn notification_generator_has_sent_referendum_vote_reminder(
+        &self,
+        user_notification_rule_id: u32,
+        validator_account_id: &AccountId,
+        referendum_index: u32,
+    ) -> anyhow::Result<bool> {
+        let result: (bool,) = sqlx::query_as(
+            r#"
+                SELECT EXISTS(
+                    SELECT referendum_index
+                    FROM sub_notification_generator_referendum_vote_reminder
+                    WHERE user_notification_rule_id = $1 AND validator_account_id = $2 AND referendum_index = $3
+                )
+                "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(validator_account_id.to_string())
+        .bind(referendum_index as i64)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0)
+    }
+
+    pub async fn save_notification_generator_referendum_vote_reminder(
+        &self,
+        user_notification_rule_id: u32,
+        validator_account_id: &AccountId,
+        referendum_index: u32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_notification_generator_referendum_vote_reminder(user_notification_rule_id, validator_account_id, referendum_index)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(user_notification_rule_id, validator_account_id, referendum_index) DO NOTHING
+            "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(validator_account_id.to_string())
+        .bind(referendum_index as i64)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_notification_generator_referendum_tally_states(
+        &self,
+    ) -> anyhow::Result<Vec<ReferendumTallyState>> {
+        #[allow(clippy::type_complexity)]
+        let db_states: Vec<(
+            i64,
+            i16,
+            i64,
+            String,
+            String,
+            String,
+            i64,
+            i64,
+            Option<i64>,
+            Option<i64>,
+            Option<i64>,
+        )> = sqlx::query_as(
+            r#"
+            SELECT referendum_index, track_id, block_number, ayes, nays, support, approval_per_billion, support_per_billion, min_approval_per_billion, min_support_per_billion, confirm_end_block_number
+            FROM sub_notification_generator_referendum_tally_state
+            ORDER BY referendum_index ASC
+            "#,
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut states = Vec::with_capacity(db_states.len());
+        for db_state in db_states {
+            states.push(ReferendumTallyState {
+                index: db_state.0 as u32,
+                track_id: db_state.1 as u16,
+                block_number: db_state.2 as u64,
+                ayes: db_state.3.parse::<Balance>()?,
+                nays: db_state.4.parse::<Balance>()?,
+                support: db_state.5.parse::<Balance>()?,
+                approval_per_billion: db_state.6 as u32,
+                support_per_billion: db_state.7 as u32,
+                maybe_min_approval_per_billion: db_state.8.map(|value| value as u32),
+                maybe_min_support_per_billion: db_state.9.map(|value| value as u32),
+                is_confirming: db_state.10.is_some(),
+                maybe_confirm_end_block_number: db_state.10.map(|value| value as u32),
+            });
+        }
+        Ok(states)
+    }
+
+    pub async fn save_notification_generator_referendum_tally_state(
+        &self,
+        state: &ReferendumTallyState,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_notification_generator_referendum_tally_state(referendum_index, track_id, block_number, ayes, nays, support, approval_per_billion, support_per_billion, min_approval_per_billion, min_support_per_billion, confirm_end_block_number)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT(referendum_index) DO UPDATE
+            SET track_id = EXCLUDED.track_id, block_number = EXCLUDED.block_number, ayes = EXCLUDED.ayes, nays = EXCLUDED.nays, support = EXCLUDED.support, approval_per_billion = EXCLUDED.approval_per_billion, support_per_billion = EXCLUDED.support_per_billion, min_approval_per_billion = EXCLUDED.min_approval_per_billion, min_support_per_billion = EXCLUDED.min_support_per_billion, confirm_end_block_number = EXCLUDED.confirm_end_block_number, updated_at = now()
+            "#,
+        )
+        .bind(state.index as i64)
+        .bind(state.track_id as i16)
+        .bind(state.block_number as i64)
+        .bind(state.ayes.to_string())
+        .bind(state.nays.to_string())
+        .bind(state.support.to_string())
+        .bind(state.approval_per_billion as i64)
+        .bind(state.support_per_billion as i64)
+        .bind(state.maybe_min_approval_per_billion.map(|value| value as i64))
+        .bind(state.maybe_min_support_per_billion.map(|value| value as i64))
+        .bind(state.maybe_confirm_end_block_number.map(|value| value as i64))
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Deletes the tracked tally states and the tally alert baselines of the referenda that are
+    /// not in the given list of ongoing referenda.
+    pub async fn delete_notification_generator_finished_referendum_tally_states(
+        &self,
+        ongoing_referendum_indices: &[u32],
+    ) -> anyhow::Result<()> {
+        let indices: Vec<i64> = ongoing_referendum_indices
+            .iter()
+            .map(|index| *index as i64)
+            .collect();
+        let mut transaction = self.connection_pool.begin().await?;
+        sqlx::query(
+            r#"
+            DELETE FROM sub_notification_generator_referendum_tally_state
+            WHERE referendum_index <> ALL($1)
+            "#,
+        )
+        .bind(&indices)
+        .execute(&mut transaction)
+        .await?;
+        sqlx::query(
+            r#"
+            DELETE FROM sub_notification_generator_referendum_tally_alert
+            WHERE referendum_index <> ALL($1)
+            "#,
+        )
+        .bind(&indices)
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Ayes, nays and support of the referendum at the last tally change notification for the
+    /// rule, or at the time the referendum was first seen by the rule.
+    pub async fn get_notification_generator_referendum_tally_alert(
+        &self,
+        user_notification_rule_id: u32,
+        referendum_index: u32,
+    ) -> anyhow::Result<Option<(Balance, Balance, Balance)>> {
+        let maybe_db_alert: Option<(String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT ayes, nays, support
+            FROM sub_notification_generator_referendum_tally_alert
+            WHERE user_notification_rule_id = $1 AND referendum_index = $2
+            "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(referendum_index as i64)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        if let Some(db_alert) = maybe_db_alert {
+            Ok(Some((
+                db_alert.0.parse::<Balance>()?,
+                db_alert.1.parse::<Balance>()?,
+                db_alert.2.parse::<Balance>()?,
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn save_notification_generator_referendum_tally_alert(
+        &self,
+        user_notification_rule_id: u32,
+        state: &ReferendumTallyState,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_notification_generator_referendum_tally_alert(user_notification_rule_id, referendum_index, ayes, nays, support)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(user_notification_rule_id, referendum_index) DO UPDATE
+            SET ayes = EXCLUDED.ayes, nays = EXCLUDED.nays, support = EXCLUDED.support, updated_at = now()
+            "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(state.index as i64)
+        .bind(state.ayes.to_string())
+        .bind(state.nays.to_string())
+        .bind(state.support.to_string())
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn notification_generator_has_sent_telemetry_finality_notification(
+        &self,
+        user_notification_rule_id: u32,
+        validator_account_id: &AccountId,
+        validator_address: &str,
+        last_finality_timestamp: u64,
+    ) -> anyhow::Result<bool> {
+        let result: (bool,) = sqlx::query_as(
+            r#"
+                SELECT EXISTS(
+                    SELECT last_finality_time
+                    FROM sub_notification_generator_telemetry_finality
+                    WHERE user_notification_rule_id = $1 AND validator_account_id = $2 AND validator_address = $3 AND last_finality_time = $4
+                )
+                "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(validator_account_id.to_string())
+        .bind(validator_address)
+        .bind(get_date_time(last_finality_timestamp)?)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0)
+    }
+
+    pub async fn save_notification_generator_telemetry_finality_notification(
+        &self,
+        user_notification_rule_id: u32,
+        validator_account_id: &AccountId,
+        validator_address: &str,
+        last_finality_timestamp: u64,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_notification_generator_telemetry_finality(user_notification_rule_id, validator_account_id, validator_address, last_finality_time)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(user_notification_rule_id, validator_account_id, validator_address, last_finality_time) DO NOTHING
+            "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(validator_account_id.to_string())
+        .bind(validator_address)
+        .bind(get_date_time(last_finality_timestamp)?)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn notification_generator_has_sent_telemetry_version_notification(
+        &self,
+        user_notification_rule_id: u32,
+        validator_account_id: &AccountId,
+        node_name: &str,
+        latest_version: &str,
+    ) -> anyhow::Result<bool> {
+        let result: (bool,) = sqlx::query_as(
+            r#"
+                SELECT EXISTS(
+                    SELECT latest_version
+                    FROM sub_notification_generator_telemetry_version
+                    WHERE user_notification_rule_id = $1 AND validator_account_id = $2 AND node_name = $3 AND latest_version = $4
+                )
+                "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(validator_account_id.to_string())
+        .bind(node_name)
+        .bind(latest_version)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0)
+    }
+
+    pub async fn save_notification_generator_telemetry_version_notification(
+        &self,
+        user_notification_rule_id: u32,
+        validator_account_id: &AccountId,
+        node_name: &str,
+        latest_version: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_notification_generator_telemetry_version(user_notification_rule_id, validator_account_id, node_name, latest_version)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(user_notification_rule_id, validator_account_id, node_name, latest_version) DO NOTHING
+            "#,
+        )
+        .bind(user_notification_rule_id as i64)
+        .bind(validator_account_id.to_string())
+        .bind(node_name)
+        .bind(latest_version)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
 }
@@ -2,19 +2,32 @@ use crate::postgres::network::PostgreSQLNetworkStorage;
 use chrono::{NaiveDateTime, TimeZone};
 use std::str::FromStr;
 use subvt_types::crypto::AccountId;
-use subvt_types::governance::polkassembly::{ReferendumPost, ReferendumStatus};
+use subvt_types::governance::call::ReferendumCall;
+use subvt_types::governance::polkassembly::{
+    ReferendumPost, ReferendumPostDetails, ReferendumStatus,
+};
+use subvt_types::governance::referendum::{Referendum, ReferendumDecisionDeadline};
 
 type PostgresReferenda = (
     i32,
+    Option<String>,
     String,
-    String,
-    i16,
+    Option<i16>,
     Option<String>,
     Option<String>,
     String,
     NaiveDateTime,
 );
 
+/// Polkassembly post type of the OpenGov referenda.
+const REFERENDUM_TYPE: &str = "ReferendumV2";
+/// Polkassembly post type of the Fellowship referenda.
+const FELLOWSHIP_REFERENDUM_TYPE: &str = "FellowshipReferendum";
+/// Upper limit of the exponent of the Polkassembly fetch backoff, to keep it in range.
+const MAX_FETCH_BACKOFF_EXPONENT: u32 = 30;
+const OPEN_REFERENDUM_STATUS_CONDITION: &str =
+    "status IN ('Submitted', 'DecisionDepositPlaced', 'Deciding', 'ConfirmStarted')";
+
 impl PostgreSQLNetworkStorage {
     pub async fn save_or_update_referendum(
         &self,
@@ -42,41 +55,246 @@ impl PostgreSQLNetworkStorage {
         Ok(referendum_save_result.0)
     }
 
+    /// Saves the on-chain state of the referendum. Polkassembly details of the referendum, and
+    /// the track and the proposer that aren't kept on chain after the referendum is finished,
+    /// are left as is.
+    pub async fn save_or_update_onchain_referendum(
+        &self,
+        referendum: &Referendum,
+    ) -> anyhow::Result<i32> {
+        self.save_or_update_onchain_referendum_in("sub_referendum", REFERENDUM_TYPE, referendum)
+            .await
+    }
+
+    /// Saves the on-chain state of the `FellowshipReferenda` pallet referendum, see
+    /// `save_or_update_onchain_referendum`.
+    pub async fn save_or_update_onchain_fellowship_referendum(
+        &self,
+        referendum: &Referendum,
+    ) -> anyhow::Result<i32> {
+        self.save_or_update_onchain_referendum_in(
+            "sub_fellowship_referendum",
+            FELLOWSHIP_REFERENDUM_TYPE,
+            referendum,
+        )
+        .await
+    }
+
+    async fn save_or_update_onchain_referendum_in(
+        &self,
+        table_name: &str,
+        ty: &str,
+        referendum: &Referendum,
+    ) -> anyhow::Result<i32> {
+        let tally = referendum.maybe_tally.as_ref();
+        let decision = referendum.maybe_decision.as_ref();
+        let referendum_save_result: (i32,) = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO {table_name} (post_id, proposer_account_id, type, track_id, status, submitted_block_number, prepare_end_block_number, end_block_number, queue_position, ayes, nays, support, approval_per_billion, support_per_billion, deciding_since_block_number, decision_deadline_block_number, confirm_end_block_number, min_approval_per_billion, min_support_per_billion, is_passing)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+            ON CONFLICT(post_id) DO UPDATE
+            SET proposer_account_id = COALESCE(EXCLUDED.proposer_account_id, {table_name}.proposer_account_id), track_id = COALESCE(EXCLUDED.track_id, {table_name}.track_id), status = EXCLUDED.status, submitted_block_number = COALESCE(EXCLUDED.submitted_block_number, {table_name}.submitted_block_number), prepare_end_block_number = COALESCE(EXCLUDED.prepare_end_block_number, {table_name}.prepare_end_block_number), end_block_number = EXCLUDED.end_block_number, queue_position = EXCLUDED.queue_position, ayes = COALESCE(EXCLUDED.ayes, {table_name}.ayes), nays = COALESCE(EXCLUDED.nays, {table_name}.nays), support = COALESCE(EXCLUDED.support, {table_name}.support), approval_per_billion = COALESCE(EXCLUDED.approval_per_billion, {table_name}.approval_per_billion), support_per_billion = COALESCE(EXCLUDED.support_per_billion, {table_name}.support_per_billion), deciding_since_block_number = COALESCE(EXCLUDED.deciding_since_block_number, {table_name}.deciding_since_block_number), decision_deadline_block_number = COALESCE(EXCLUDED.decision_deadline_block_number, {table_name}.decision_deadline_block_number), confirm_end_block_number = EXCLUDED.confirm_end_block_number, min_approval_per_billion = COALESCE(EXCLUDED.min_approval_per_billion, {table_name}.min_approval_per_billion), min_support_per_billion = COALESCE(EXCLUDED.min_support_per_billion, {table_name}.min_support_per_billion), is_passing = EXCLUDED.is_passing, updated_at = now()
+            RETURNING post_id
+            "#,
+        ))
+            .bind(referendum.index as i32)
+            .bind(referendum.maybe_proposer_account_id.map(|account_id| account_id.to_string()))
+            .bind(ty)
+            .bind(referendum.maybe_track_id.map(|track_id| track_id as i16))
+            .bind(referendum.status.to_string())
+            .bind(referendum.maybe_submitted_block_number.map(|number| number as i64))
+            .bind(referendum.maybe_prepare_end_block_number.map(|number| number as i64))
+            .bind(referendum.maybe_end_block_number.map(|number| number as i64))
+            .bind(referendum.maybe_queue_position.map(|position| position as i32))
+            .bind(tally.map(|tally| tally.ayes.to_string()))
+            .bind(tally.map(|tally| tally.nays.to_string()))
+            .bind(tally.map(|tally| tally.support.to_string()))
+            .bind(tally.map(|tally| tally.approval.deconstruct() as i64))
+            .bind(tally.map(|tally| tally.support_ratio.deconstruct() as i64))
+            .bind(decision.map(|decision| decision.since_block_number as i64))
+            .bind(decision.map(|decision| decision.deadline_block_number as i64))
+            .bind(decision.and_then(|decision| decision.confirm_end_block_number.map(|number| number as i64)))
+            .bind(decision.map(|decision| decision.min_approval.deconstruct() as i64))
+            .bind(decision.map(|decision| decision.min_support.deconstruct() as i64))
+            .bind(decision.map(|decision| decision.is_passing))
+            .fetch_one(&self.connection_pool)
+            .await?;
+        Ok(referendum_save_result.0)
+    }
+
+    /// Saves the title, the content and the creation time of the referendum from Polkassembly.
+    pub async fn update_referendum_polkassembly_details(
+        &self,
+        details: &ReferendumPostDetails,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_referendum
+            SET title = $1, content = $2, pa_created_at = $3, track_id = COALESCE(track_id, $4), updated_at = now()
+            WHERE post_id = $5
+            "#,
+        )
+        .bind(&details.maybe_title)
+        .bind(&details.maybe_content)
+        .bind(details.created_at)
+        .bind(details.track_id as i16)
+        .bind(details.post_id as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Saves the decoded proposal call of the referendum.
+    pub async fn update_referendum_proposal_call(
+        &self,
+        referendum_index: u32,
+        call: &ReferendumCall,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_referendum
+            SET proposal_call_json = $1, updated_at = now()
+            WHERE post_id = $2
+            "#,
+        )
+        .bind(serde_json::to_string(call)?)
+        .bind(referendum_index as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_referendum_proposal_call(
+        &self,
+        referendum_index: u32,
+    ) -> anyhow::Result<Option<ReferendumCall>> {
+        let maybe_db_call: Option<(Option<String>,)> = sqlx::query_as(
+            r#"
+            SELECT proposal_call_json
+            FROM sub_referendum
+            WHERE post_id = $1
+            "#,
+        )
+        .bind(referendum_index as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        match maybe_db_call.and_then(|db_call| db_call.0) {
+            Some(call_json) => Ok(Some(serde_json::from_str(&call_json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Ids of the referenda that are either ongoing, or have no Polkassembly details yet. After
+    /// a failed fetch, or a fetch without a title, the referendum is skipped for
+    /// `base_backoff_seconds`, doubled for each consecutive failure up to `max_backoff_seconds`.
+    pub async fn get_referendum_ids_to_enrich(
+        &self,
+        base_backoff_seconds: u64,
+        max_backoff_seconds: u64,
+    ) -> anyhow::Result<Vec<u32>> {
+        let db_ids: Vec<(i32,)> = sqlx::query_as(&format!(
+            r#"
+            SELECT post_id
+            FROM sub_referendum
+            WHERE (title IS NULL OR {OPEN_REFERENDUM_STATUS_CONDITION})
+            AND (
+                pa_fetch_failure_count = 0
+                OR pa_last_fetch_at IS NULL
+                OR pa_last_fetch_at + make_interval(secs => LEAST($1 * POWER(2, LEAST(pa_fetch_failure_count - 1, $3)), $2)) <= now()
+            )
+            ORDER BY post_id ASC
+            "#
+        ))
+        .bind(base_backoff_seconds as i64)
+        .bind(max_backoff_seconds as i64)
+        .bind(MAX_FETCH_BACKOFF_EXPONENT as i32)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_ids.iter().map(|db_id| db_id.0 as u32).collect())
+    }
+
+    /// Records a Polkassembly fetch of the referendum for the backoff. A failed fetch, or a fetch
+    /// without a title, increments the consecutive failure count, and a fetch with a title
+    /// resets it.
+    pub async fn save_referendum_polkassembly_fetch(
+        &self,
+        referendum_id: u32,
+        is_successful: bool,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_referendum
+            SET pa_fetch_failure_count = CASE WHEN $1 THEN 0 ELSE pa_fetch_failure_count + 1 END, pa_last_fetch_at = now()
+            WHERE post_id = $2
+            "#,
+        )
+        .bind(is_successful)
+        .bind(referendum_id as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn get_open_referenda(
         &self,
         track_id: Option<u16>,
     ) -> anyhow::Result<Vec<ReferendumPost>> {
         let db_referenda: Vec<PostgresReferenda> = if let Some(track_id) = track_id {
-            sqlx::query_as(
+            sqlx::query_as(&format!(
                 r#"
-            SELECT post_id, proposer_account_id, type, track_id, title, method, status, pa_created_at
+            SELECT post_id, proposer_account_id, type, track_id, title, method, status, COALESCE(pa_created_at, created_at)
             FROM sub_referendum
             WHERE track_id = $1
-            AND (status = 'Deciding' OR status = 'Submitted' OR status = 'DecisionDepositPlaced')
-            ORDER BY track_id ASC
+            AND {OPEN_REFERENDUM_STATUS_CONDITION}
+            ORDER BY track_id ASC, post_id ASC
             "#,
-            )
+            ))
                 .bind(track_id as i16)
                 .fetch_all(&self.connection_pool)
                 .await?
         } else {
-            sqlx::query_as(
+            sqlx::query_as(&format!(
                 r#"
-            SELECT post_id, proposer_account_id, type, track_id, title, method, status, pa_created_at
+            SELECT post_id, proposer_account_id, type, track_id, title, method, status, COALESCE(pa_created_at, created_at)
             FROM sub_referendum
-            WHERE (status = 'Deciding' OR status = 'Submitted' OR status = 'DecisionDepositPlaced')
-            ORDER BY track_id ASC
+            WHERE {OPEN_REFERENDUM_STATUS_CONDITION}
+            ORDER BY track_id ASC, post_id ASC
             "#,
-            )
+            ))
                 .fetch_all(&self.connection_pool)
                 .await?
         };
         let mut referenda = vec![];
         for db_referendum in &db_referenda {
+            let track_id = match db_referendum.3 {
+                Some(track_id) => track_id,
+                None => {
+                    log::warn!("Skip open referendum #{} without a track.", db_referendum.0);
+                    continue;
+                }
+            };
+            let proposer = match db_referendum.1.as_deref().map(AccountId::from_str) {
+                Some(Ok(proposer)) => proposer,
+                Some(Err(error)) => {
+                    log::warn!(
+                        "Skip open referendum #{} with an invalid proposer: {:?}",
+                        db_referendum.0,
+                        error
+                    );
+                    continue;
+                }
+                None => {
+                    log::warn!(
+                        "Skip open referendum #{} without a proposer.",
+                        db_referendum.0
+                    );
+                    continue;
+                }
+            };
             referenda.push(ReferendumPost {
                 post_id: db_referendum.0 as u32,
-                track_id: db_referendum.3 as u16,
-                proposer: AccountId::from_str(&db_referendum.1)?,
+                track_id: track_id as u16,
+                proposer,
                 maybe_title: db_referendum.4.clone(),
                 maybe_method: db_referendum.5.clone(),
                 status: ReferendumStatus::from_str(&db_referendum.6)?,
@@ -86,4 +304,31 @@ impl PostgreSQLNetworkStorage {
         }
         Ok(referenda)
     }
+
+    /// Open referenda that are in their decision periods, earliest deadline first.
+    pub async fn get_open_referendum_decision_deadlines(
+        &self,
+    ) -> anyhow::Result<Vec<ReferendumDecisionDeadline>> {
+        let db_deadlines: Vec<(i32, i16, Option<String>, i64)> = sqlx::query_as(&format!(
+            r#"
+            SELECT post_id, track_id, title, decision_deadline_block_number
+            FROM sub_referendum
+            WHERE {OPEN_REFERENDUM_STATUS_CONDITION}
+            AND track_id IS NOT NULL
+            AND decision_deadline_block_number IS NOT NULL
+            ORDER BY decision_deadline_block_number ASC, post_id ASC
+            "#
+        ))
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_deadlines
+            .into_iter()
+            .map(|db_deadline| ReferendumDecisionDeadline {
+                index: db_deadline.0 as u32,
+                track_id: db_deadline.1 as u16,
+                maybe_title: db_deadline.2,
+                deadline_block_number: db_deadline.3 as u32,
+            })
+            .collect())
+    }
 }
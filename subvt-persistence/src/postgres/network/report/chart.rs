@@ -0,0 +1,105 @@
+use crate::postgres::network::report::parse_maybe_string;
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{
+    ParaVotesSummary, ValidatorEraChartData, ValidatorSessionParaVotesChartData,
+};
+
+type PostgresValidatorEraChartData = (
+    i64,
+    bool,
+    Option<String>,
+    Option<String>,
+    Option<i32>,
+    i64,
+    Option<i64>,
+);
+
+impl PostgreSQLNetworkStorage {
+    /// Validator's per-era stakes, nominator count and reward points along with the median
+    /// reward points of the active set, for the last `era_count` eras the validator is in.
+    pub async fn get_validator_era_chart_data(
+        &self,
+        validator_account_id: &AccountId,
+        era_count: u32,
+    ) -> anyhow::Result<Vec<ValidatorEraChartData>> {
+        let db_chart_data: Vec<PostgresValidatorEraChartData> = sqlx::query_as(
+            r#"
+            SELECT * FROM (
+                SELECT EV.era_index, EV.is_active, EV.self_stake, EV.total_stake, EV.active_nominator_count, EV.reward_points, M.median_reward_points
+                FROM sub_era_validator EV
+                LEFT JOIN (
+                    SELECT era_index, (percentile_cont(0.5) WITHIN GROUP (ORDER BY reward_points))::bigint AS median_reward_points
+                    FROM sub_era_validator
+                    WHERE is_active = true
+                    GROUP BY era_index
+                ) M ON M.era_index = EV.era_index
+                WHERE EV.validator_account_id = $1
+                ORDER BY EV.era_index DESC
+                LIMIT $2
+            ) AS chart_data
+            ORDER BY era_index ASC
+            "#,
+        )
+        .bind(validator_account_id.to_string())
+        .bind(era_count as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut result = Vec::new();
+        for db_datum in db_chart_data {
+            result.push(ValidatorEraChartData {
+                era_index: db_datum.0 as u32,
+                is_active: db_datum.1,
+                self_stake: parse_maybe_string(&db_datum.2)?,
+                total_stake: parse_maybe_string(&db_datum.3)?,
+                active_nominator_count: db_datum.4.map(|count| count as u32),
+                reward_points: db_datum.5 as u64,
+                active_set_median_reward_points: db_datum.6.map(|points| points as u64),
+            });
+        }
+        Ok(result)
+    }
+
+    /// Explicit, implicit and missed para vote counts of the validator in each of the last
+    /// `session_count` sessions it was a para validator in.
+    pub async fn get_validator_session_para_votes_chart_data(
+        &self,
+        validator_account_id: &AccountId,
+        session_count: u32,
+    ) -> anyhow::Result<Vec<ValidatorSessionParaVotesChartData>> {
+        let db_chart_data: Vec<(i64, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT * FROM (
+                SELECT SPV.session_index,
+                    COUNT(PV.id) FILTER (WHERE PV.is_explicit = true),
+                    COUNT(PV.id) FILTER (WHERE PV.is_explicit = false),
+                    COUNT(PV.id) FILTER (WHERE PV.is_explicit IS NULL)
+                FROM sub_session_para_validator SPV
+                INNER JOIN sub_para_vote PV
+                    ON PV.session_index = SPV.session_index
+                    AND PV.para_validator_index = SPV.para_validator_index
+                WHERE SPV.validator_account_id = $1
+                GROUP BY SPV.session_index
+                ORDER BY SPV.session_index DESC
+                LIMIT $2
+            ) AS chart_data
+            ORDER BY session_index ASC
+            "#,
+        )
+        .bind(validator_account_id.to_string())
+        .bind(session_count as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_chart_data
+            .iter()
+            .map(|db_datum| ValidatorSessionParaVotesChartData {
+                session_index: db_datum.0 as u64,
+                para_votes_summary: ParaVotesSummary {
+                    explicit: db_datum.1 as u32,
+                    implicit: db_datum.2 as u32,
+                    missed: db_datum.3 as u32,
+                },
+            })
+            .collect())
+    }
+}
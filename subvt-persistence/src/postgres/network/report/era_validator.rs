@@ -112,4 +112,36 @@ impl PostgreSQLNetworkStorage {
         };
         Ok(era_reports)
     }
+
+    /// Active era count and average era reward points in active eras for every validator
+    /// that was active at least once in the given era range (inclusive).
+    pub async fn get_validator_era_performances(
+        &self,
+        start_era_index: u32,
+        end_era_index: u32,
+    ) -> anyhow::Result<Vec<(AccountId, u32, u64)>> {
+        let performances: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT validator_account_id, COUNT(id)::bigint, AVG(reward_points)::bigint
+            FROM sub_era_validator
+            WHERE era_index >= $1
+            AND era_index <= $2
+            AND is_active = true
+            GROUP BY validator_account_id
+            "#,
+        )
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut result = Vec::new();
+        for performance in performances {
+            result.push((
+                AccountId::from_str(&performance.0)?,
+                performance.1 as u32,
+                performance.2 as u64,
+            ));
+        }
+        Ok(result)
+    }
 }
@@ -8,6 +8,17 @@ impl PostgreSQLNetworkStorage {
     pub async fn get_validator_all_era_rewards(
         &self,
         validator_account_id: &AccountId,
+    ) -> anyhow::Result<Vec<(Era, Balance)>> {
+        self.get_validator_era_rewards(validator_account_id, 0, u32::MAX)
+            .await
+    }
+
+    /// Rewards of the validator in the eras of the given inclusive era index range.
+    pub async fn get_validator_era_rewards(
+        &self,
+        validator_account_id: &AccountId,
+        start_era_index: u32,
+        end_era_index: u32,
     ) -> anyhow::Result<Vec<(Era, Balance)>> {
         let era_rewards: Vec<(i64, i64, i64, i64)> = sqlx::query_as(
             r#"
@@ -21,11 +32,15 @@ impl PostgreSQLNetworkStorage {
             INNER JOIN sub_era E
                 ON E.index = EX.era_index
             WHERE EV.rewardee_account_id = $1
+            AND EX.era_index >= $2
+            AND EX.era_index <= $3
             GROUP BY E.index
             ORDER BY E.index ASC;
             "#,
         )
         .bind(validator_account_id.to_string())
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
         .fetch_all(&self.connection_pool)
         .await?;
         let mut result = vec![];
@@ -204,6 +204,44 @@ impl PostgreSQLNetworkStorage {
         Ok(())
     }
 
+    /// Gets the chat ids and app user ids of the chats that have been deleted for longer than
+    /// the retention period.
+    pub async fn get_purgeable_chats(
+        &self,
+        retention_days: u16,
+    ) -> anyhow::Result<Vec<(i64, u32)>> {
+        let chats: Vec<(i64, i32)> = sqlx::query_as(
+            format!(
+                "
+                SELECT telegram_chat_id, app_user_id FROM sub_telegram_chat
+                WHERE deleted_at < (now() - interval '{retention_days} days')
+                ",
+            )
+            .as_str(),
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(chats
+            .iter()
+            .map(|(chat_id, app_user_id)| (*chat_id, *app_user_id as u32))
+            .collect())
+    }
+
+    /// Permanently deletes a deleted chat along with its validators, activity log, bug reports
+    /// and feature requests.
+    pub async fn purge_chat(&self, telegram_chat_id: i64) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM sub_telegram_chat
+            WHERE telegram_chat_id = $1 AND deleted_at IS NOT NULL
+            "#,
+        )
+        .bind(telegram_chat_id)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn undelete_chat(&self, telegram_chat_id: i64) -> anyhow::Result<()> {
         sqlx::query(
             r#"
@@ -259,6 +297,64 @@ impl PostgreSQLNetworkStorage {
         Ok(())
     }
 
+    /// Group chats have per-group validators and notification rules, managed only by the group
+    /// administrators.
+    pub async fn set_chat_is_group(
+        &self,
+        telegram_chat_id: i64,
+        is_group: bool,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_telegram_chat
+            SET is_group = $1
+            WHERE telegram_chat_id = $2 AND is_group != $1
+            "#,
+        )
+        .bind(is_group)
+        .bind(telegram_chat_id)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn chat_is_group(&self, telegram_chat_id: i64) -> anyhow::Result<bool> {
+        let maybe_is_group: Option<(bool,)> = sqlx::query_as(
+            r#"
+            SELECT is_group FROM sub_telegram_chat
+            WHERE telegram_chat_id = $1
+            "#,
+        )
+        .bind(telegram_chat_id)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_is_group.map(|is_group| is_group.0).unwrap_or(false))
+    }
+
+    /// Moves the chat, along with its validators and logs, to the new chat id when a Telegram
+    /// group gets upgraded to a supergroup. Returns `false` if there is no chat with the old id, or
+    /// there is already a chat with the new id.
+    pub async fn migrate_chat(
+        &self,
+        telegram_chat_id: i64,
+        new_telegram_chat_id: i64,
+    ) -> anyhow::Result<bool> {
+        let maybe_id: Option<(i64,)> = sqlx::query_as(
+            r#"
+            UPDATE sub_telegram_chat
+            SET telegram_chat_id = $2, is_group = true
+            WHERE telegram_chat_id = $1
+            AND NOT EXISTS (SELECT 1 FROM sub_telegram_chat WHERE telegram_chat_id = $2)
+            RETURNING telegram_chat_id
+            "#,
+        )
+        .bind(telegram_chat_id)
+        .bind(new_telegram_chat_id)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_id.is_some())
+    }
+
     pub async fn get_chat_app_user_id(&self, telegram_chat_id: i64) -> anyhow::Result<u32> {
         let app_user_id: (i32,) = sqlx::query_as(
             r#"
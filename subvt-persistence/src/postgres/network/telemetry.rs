@@ -1,10 +1,84 @@
 //! Telemetry-related storage. Used by the `subvt-telemetry-processor` crate, and other crates
 //! that query the telemetry data (validator details, notification generator, etc.).
+use crate::postgres::network::validator_snapshot::get_date_time;
 use crate::postgres::network::PostgreSQLNetworkStorage;
+use rustc_hash::FxHashMap as HashMap;
+use sqlx::{Postgres, QueryBuilder};
 use std::str::FromStr;
 use subvt_types::crypto::AccountId;
+use subvt_types::report::{
+    TelemetryNodeVersion, TelemetryVersionCount, TimeSeriesResolution,
+    ValidatorTelemetryFinalitySession, ValidatorTelemetryNode, ValidatorTelemetryNodeStats,
+    ValidatorTelemetryNodeVersion,
+};
+use subvt_types::telemetry::finality::{TelemetryFinalityMessage, TelemetryNodeFinalityStatus};
+use subvt_types::telemetry::validator::{
+    TelemetryNode, TelemetryNodeMatchMethod, TelemetryNodeValidator,
+};
+use subvt_types::telemetry::version::{TelemetryLatestVersion, TelemetryRelease};
 use subvt_types::telemetry::{NodeDetails, NodeHardware, NodeLocation, NodeStats};
 
+/// Finality messages are inserted in multi-row statements of at most this many rows, to stay
+/// well within the bind parameter limit of PostgreSQL.
+const FINALITY_MESSAGE_INSERT_CHUNK_SIZE: usize = 1000;
+
+type PostgresValidatorTelemetryNode = (
+    i64,
+    String,
+    Option<String>,
+    String,
+    String,
+    Option<i64>,
+    Option<i64>,
+    Option<String>,
+    String,
+    i16,
+);
+
+type PostgresValidatorTelemetryNodeStats = (
+    i64,
+    String,
+    Option<String>,
+    Option<i64>,
+    Option<i64>,
+    Option<f64>,
+    Option<f64>,
+    String,
+);
+
+type PostgresValidatorTelemetryFinalitySession = (
+    i64,
+    String,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    Option<i64>,
+);
+
+type PostgresTelemetryNodeFinalityStatus = (
+    i64,
+    String,
+    String,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+);
+
+fn get_rate(count: u64, total: u64) -> Option<f64> {
+    if total == 0 {
+        None
+    } else {
+        Some(count as f64 / total as f64)
+    }
+}
+
 impl PostgreSQLNetworkStorage {
     pub async fn update_node_best_block(
         &self,
@@ -99,10 +173,10 @@ impl PostgreSQLNetworkStorage {
         };
         sqlx::query(
             r#"
-            INSERT INTO sub_telemetry_node (id, controller_account_id, name, client_implementation, client_version, startup_time, location, latitude, longitude)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO sub_telemetry_node (id, controller_account_id, name, client_implementation, client_version, startup_time, location, latitude, longitude, network_id, validator_address)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             ON CONFLICT(id) DO UPDATE
-            SET controller_account_id = EXCLUDED.controller_account_id, name = EXCLUDED.name, client_implementation = EXCLUDED.client_implementation, client_version = EXCLUDED.client_version, startup_time = EXCLUDED.startup_time,  location = EXCLUDED.location, latitude = EXCLUDED.latitude, longitude = EXCLUDED.longitude
+            SET controller_account_id = EXCLUDED.controller_account_id, name = EXCLUDED.name, client_implementation = EXCLUDED.client_implementation, client_version = EXCLUDED.client_version, startup_time = EXCLUDED.startup_time,  location = EXCLUDED.location, latitude = EXCLUDED.latitude, longitude = EXCLUDED.longitude, network_id = EXCLUDED.network_id, validator_address = EXCLUDED.validator_address
             "#,
         )
             .bind(node_id as i64)
@@ -114,11 +188,54 @@ impl PostgreSQLNetworkStorage {
             .bind(location.as_ref().map(|location| location.2.clone()))
             .bind(location.as_ref().map(|location| location.0 as f64))
             .bind(location.as_ref().map(|location| location.1 as f64))
+            .bind(&node_details.network_id)
+            .bind(&node_details.validator)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Records the feed that reports the node, along with the id of the node on the feed.
+    pub async fn save_node_feed(
+        &self,
+        node_id: u64,
+        feed_url: &str,
+        feed_node_id: u64,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_telemetry_node_feed (node_id, feed_url, feed_node_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (node_id, feed_url) DO UPDATE
+            SET feed_node_id = EXCLUDED.feed_node_id
+            "#,
+        )
+        .bind(node_id as i64)
+        .bind(feed_url)
+        .bind(feed_node_id as i64)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_node_feed(&self, node_id: u64, feed_url: &str) -> anyhow::Result<()> {
+        sqlx::query("DELETE FROM sub_telemetry_node_feed WHERE node_id = $1 AND feed_url = $2")
+            .bind(node_id as i64)
+            .bind(feed_url)
             .execute(&self.connection_pool)
             .await?;
         Ok(())
     }
 
+    /// Removes all nodes along with their stats. Called by the Telemetry processor on startup,
+    /// as the node ids are assigned by the processor.
+    pub async fn remove_all_nodes(&self) -> anyhow::Result<u64> {
+        let result = sqlx::query("DELETE FROM sub_telemetry_node")
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn save_node_stats(&self, node_id: u64, stats: &NodeStats) -> anyhow::Result<()> {
         sqlx::query(
             r#"
@@ -222,4 +339,597 @@ impl PostgreSQLNetworkStorage {
         .await?;
         Ok(())
     }
+
+    /// Nodes with the fields used in the node-validator matching.
+    pub async fn get_telemetry_nodes(&self) -> anyhow::Result<Vec<TelemetryNode>> {
+        let db_nodes: Vec<(i64, String, Option<String>, Option<String>, Option<String>)> =
+            sqlx::query_as(
+                r#"
+                SELECT id, name, network_id, validator_address, controller_account_id
+                FROM sub_telemetry_node
+                ORDER BY id ASC
+                "#,
+            )
+            .fetch_all(&self.connection_pool)
+            .await?;
+        Ok(db_nodes
+            .into_iter()
+            .map(|db_node| TelemetryNode {
+                id: db_node.0 as u64,
+                name: db_node.1,
+                network_id: db_node.2,
+                validator_address: db_node.3,
+                controller_account_id: db_node
+                    .4
+                    .and_then(|account_id| AccountId::from_str(&account_id).ok()),
+            })
+            .collect())
+    }
+
+    /// Network ids matched to validators earlier with a reliable method.
+    pub async fn get_telemetry_validator_network_ids(
+        &self,
+    ) -> anyhow::Result<HashMap<String, AccountId>> {
+        let db_network_ids: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT network_id, validator_account_id
+            FROM sub_telemetry_validator_network_id
+            "#,
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut network_ids = HashMap::default();
+        for db_network_id in db_network_ids {
+            network_ids.insert(db_network_id.0, AccountId::from_str(&db_network_id.1)?);
+        }
+        Ok(network_ids)
+    }
+
+    /// Replaces the node-validator matches, and remembers the network ids and the validator
+    /// addresses of the nodes matched with a reliable method.
+    pub async fn save_telemetry_node_validators(
+        &self,
+        node_validators: &[TelemetryNodeValidator],
+    ) -> anyhow::Result<()> {
+        let mut transaction = self.connection_pool.begin().await?;
+        let node_ids: Vec<i64> = node_validators
+            .iter()
+            .map(|node_validator| node_validator.node_id as i64)
+            .collect();
+        sqlx::query("DELETE FROM sub_telemetry_node_validator WHERE node_id <> ALL($1)")
+            .bind(&node_ids)
+            .execute(&mut transaction)
+            .await?;
+        for node_validator in node_validators {
+            sqlx::query(
+                r#"
+                INSERT INTO sub_telemetry_node_validator (node_id, validator_account_id, match_method, confidence)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (node_id) DO UPDATE
+                SET validator_account_id = EXCLUDED.validator_account_id, match_method = EXCLUDED.match_method, confidence = EXCLUDED.confidence, updated_at = now()
+                "#,
+            )
+                .bind(node_validator.node_id as i64)
+                .bind(node_validator.validator_account_id.to_string())
+                .bind(node_validator.match_method.to_string())
+                .bind(node_validator.confidence as i16)
+                .execute(&mut transaction)
+                .await?;
+            if node_validator.match_method.is_reliable() {
+                sqlx::query(
+                    r#"
+                    INSERT INTO sub_telemetry_validator_network_id (network_id, validator_account_id)
+                    SELECT network_id, $2 FROM sub_telemetry_node
+                    WHERE id = $1 AND network_id IS NOT NULL
+                    ON CONFLICT (network_id) DO UPDATE
+                    SET validator_account_id = EXCLUDED.validator_account_id, updated_at = now()
+                    "#,
+                )
+                    .bind(node_validator.node_id as i64)
+                    .bind(node_validator.validator_account_id.to_string())
+                    .execute(&mut transaction)
+                    .await?;
+                sqlx::query(
+                    r#"
+                    INSERT INTO sub_telemetry_validator_address (validator_address, validator_account_id)
+                    SELECT validator_address, $2 FROM sub_telemetry_node
+                    WHERE id = $1 AND validator_address IS NOT NULL
+                    ON CONFLICT (validator_address) DO UPDATE
+                    SET validator_account_id = EXCLUDED.validator_account_id, updated_at = now()
+                    "#,
+                )
+                    .bind(node_validator.node_id as i64)
+                    .bind(node_validator.validator_account_id.to_string())
+                    .execute(&mut transaction)
+                    .await?;
+            }
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Snapshots the latest stats of the nodes matched to validators, for the validator node
+    /// history reports. Returns the number of snapshots.
+    pub async fn save_telemetry_validator_node_stats(&self) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO sub_telemetry_validator_node_stats (validator_account_id, node_name, network_id, peer_count, best_block_lag, download_bandwidth, upload_bandwidth, client_version)
+            SELECT NV.validator_account_id, N.name, N.network_id,
+                (SELECT S.peer_count FROM sub_telemetry_node_stats S WHERE S.node_id = N.id ORDER BY S.time DESC LIMIT 1),
+                CASE WHEN N.best_block_number IS NULL THEN NULL ELSE GREATEST(NS.best_block_number - N.best_block_number, 0) END,
+                (SELECT NNS.download_bandwidth FROM sub_telemetry_node_network_stats NNS WHERE NNS.node_id = N.id ORDER BY NNS.time DESC LIMIT 1),
+                (SELECT NNS.upload_bandwidth FROM sub_telemetry_node_network_stats NNS WHERE NNS.node_id = N.id ORDER BY NNS.time DESC LIMIT 1),
+                N.client_version
+            FROM sub_telemetry_node_validator NV
+            INNER JOIN sub_telemetry_node N ON N.id = NV.node_id
+            CROSS JOIN sub_telemetry_network_status NS
+            WHERE NS.id = 1
+            "#,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Nodes currently matched to the validator, most confident first.
+    pub async fn get_validator_telemetry_nodes(
+        &self,
+        validator_account_id: &AccountId,
+    ) -> anyhow::Result<Vec<ValidatorTelemetryNode>> {
+        let db_nodes: Vec<PostgresValidatorTelemetryNode> = sqlx::query_as(
+            r#"
+            SELECT N.id, N.name, N.network_id, N.client_implementation, N.client_version, N.best_block_number, N.finalized_block_number, N.location, NV.match_method, NV.confidence
+            FROM sub_telemetry_node_validator NV
+            INNER JOIN sub_telemetry_node N ON N.id = NV.node_id
+            WHERE NV.validator_account_id = $1
+            ORDER BY NV.confidence DESC, N.id ASC
+            "#,
+        )
+            .bind(validator_account_id.to_string())
+            .fetch_all(&self.connection_pool)
+            .await?;
+        let mut nodes = Vec::new();
+        for db_node in db_nodes {
+            let match_method = TelemetryNodeMatchMethod::from_str(&db_node.8)
+                .map_err(|error| anyhow::anyhow!(error.description))?;
+            nodes.push(ValidatorTelemetryNode {
+                id: db_node.0 as u64,
+                name: db_node.1,
+                network_id: db_node.2,
+                client_implementation: db_node.3,
+                client_version: db_node.4,
+                best_block_number: db_node.5.map(|number| number as u64),
+                finalized_block_number: db_node.6.map(|number| number as u64),
+                location: db_node.7,
+                match_method,
+                confidence: db_node.9 as u8,
+            });
+        }
+        Ok(nodes)
+    }
+
+    /// Stats of the validator's nodes per node and time bucket of the given resolution
+    /// in the `[start_timestamp, end_timestamp)` range.
+    pub async fn get_validator_telemetry_node_stats(
+        &self,
+        validator_account_id: &AccountId,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        resolution: TimeSeriesResolution,
+    ) -> anyhow::Result<Vec<ValidatorTelemetryNodeStats>> {
+        let db_stats: Vec<PostgresValidatorTelemetryNodeStats> = sqlx::query_as(
+            r#"
+            SELECT (EXTRACT(EPOCH FROM time_bucket($1::interval, time)) * 1000)::bigint AS bucket, node_name, last(network_id, time), ROUND(AVG(peer_count))::bigint, MAX(best_block_lag), AVG(download_bandwidth), AVG(upload_bandwidth), last(client_version, time)
+            FROM sub_telemetry_validator_node_stats
+            WHERE validator_account_id = $2
+            AND time >= $3
+            AND time < $4
+            GROUP BY bucket, node_name
+            ORDER BY bucket ASC, node_name ASC
+            "#,
+        )
+            .bind(resolution.get_interval())
+            .bind(validator_account_id.to_string())
+            .bind(get_date_time(start_timestamp)?)
+            .bind(get_date_time(end_timestamp)?)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        Ok(db_stats
+            .into_iter()
+            .map(|db_stats| ValidatorTelemetryNodeStats {
+                timestamp: db_stats.0 as u64,
+                node_name: db_stats.1,
+                network_id: db_stats.2,
+                peer_count: db_stats.3.map(|peer_count| peer_count as u32),
+                max_best_block_lag: db_stats.4.map(|lag| lag as u64),
+                download_bandwidth: db_stats.5,
+                upload_bandwidth: db_stats.6,
+                client_version: db_stats.7,
+            })
+            .collect())
+    }
+
+    /// Client versions run by the validator's nodes, most recently started first.
+    pub async fn get_validator_telemetry_node_versions(
+        &self,
+        validator_account_id: &AccountId,
+    ) -> anyhow::Result<Vec<ValidatorTelemetryNodeVersion>> {
+        let db_versions: Vec<(String, Option<String>, String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT node_name, network_id, client_version, (EXTRACT(EPOCH FROM MIN(time)) * 1000)::bigint AS first_seen, (EXTRACT(EPOCH FROM MAX(time)) * 1000)::bigint AS last_seen
+            FROM sub_telemetry_validator_node_stats
+            WHERE validator_account_id = $1
+            GROUP BY node_name, network_id, client_version
+            ORDER BY first_seen DESC
+            "#,
+        )
+            .bind(validator_account_id.to_string())
+            .fetch_all(&self.connection_pool)
+            .await?;
+        Ok(db_versions
+            .into_iter()
+            .map(|db_version| ValidatorTelemetryNodeVersion {
+                node_name: db_version.0,
+                network_id: db_version.1,
+                client_version: db_version.2,
+                first_seen_timestamp: db_version.3 as u64,
+                last_seen_timestamp: db_version.4 as u64,
+            })
+            .collect())
+    }
+
+    pub async fn save_node_finality_messages(
+        &self,
+        messages: &[TelemetryFinalityMessage],
+    ) -> anyhow::Result<()> {
+        for chunk in messages.chunks(FINALITY_MESSAGE_INSERT_CHUNK_SIZE) {
+            let date_times = chunk
+                .iter()
+                .map(|message| get_date_time(message.timestamp))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO sub_telemetry_node_finality (time, validator_address, kind, block_number, block_hash, voter, is_late) ",
+            );
+            query_builder.push_values(
+                chunk.iter().zip(date_times),
+                |mut row, (message, date_time)| {
+                    row.push_bind(date_time)
+                        .push_bind(&message.validator_address)
+                        .push_bind(message.kind.to_string())
+                        .push_bind(message.block_number as i64)
+                        .push_bind(&message.block_hash)
+                        .push_bind(&message.voter)
+                        .push_bind(message.is_late);
+                },
+            );
+            query_builder.build().execute(&self.connection_pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Updates the GRANDPA participation of the validators for the current and the previous
+    /// sessions from the finality messages. Votes are attributed to the validators by their
+    /// voter address, from the messages of all the reporting nodes, and a vote received by more
+    /// than one node is counted once. Returns the number of updated rows.
+    pub async fn save_telemetry_validator_finality_sessions(&self) -> anyhow::Result<u64> {
+        // voters are reported debug-formatted, i.e. possibly in quotes
+        let result = sqlx::query(
+            r#"
+            WITH session AS (
+                SELECT E.index,
+                    to_timestamp(E.start_timestamp / 1000.0) AT TIME ZONE 'UTC' AS start_time,
+                    COALESCE(
+                        (SELECT to_timestamp(NE.start_timestamp / 1000.0) AT TIME ZONE 'UTC' FROM sub_epoch NE WHERE NE.index = E.index + 1 AND NE.start_timestamp > 0),
+                        now() AT TIME ZONE 'UTC'
+                    ) AS end_time
+                FROM sub_epoch E
+                WHERE E.start_timestamp > 0
+                ORDER BY E.index DESC
+                LIMIT 2
+            ), vote AS (
+                SELECT S.index, btrim(F.voter, '"') AS voter, F.kind, F.block_number, F.block_hash, bool_and(F.is_late) AS is_late
+                FROM session S
+                INNER JOIN sub_telemetry_node_finality F ON F.time >= S.start_time AND F.time < S.end_time
+                WHERE F.kind IN ('prevote', 'precommit') AND F.voter IS NOT NULL
+                GROUP BY S.index, btrim(F.voter, '"'), F.kind, F.block_number, F.block_hash
+            ), network AS (
+                SELECT V.index,
+                    COUNT(DISTINCT V.block_number) FILTER (WHERE V.kind = 'prevote') AS prevote_target_count,
+                    COUNT(DISTINCT V.block_number) FILTER (WHERE V.kind = 'precommit') AS precommit_target_count
+                FROM vote V
+                GROUP BY V.index
+            ), validator_vote AS (
+                SELECT V.index, V.voter,
+                    COUNT(*) FILTER (WHERE V.kind = 'prevote') AS prevote_count,
+                    COUNT(*) FILTER (WHERE V.kind = 'precommit') AS precommit_count,
+                    COUNT(*) FILTER (WHERE V.kind = 'prevote' AND V.is_late) AS late_prevote_count,
+                    COUNT(*) FILTER (WHERE V.kind = 'precommit' AND V.is_late) AS late_precommit_count,
+                    COUNT(DISTINCT V.block_number) FILTER (WHERE V.kind = 'prevote') AS prevote_target_count,
+                    COUNT(DISTINCT V.block_number) FILTER (WHERE V.kind = 'precommit') AS precommit_target_count
+                FROM vote V
+                GROUP BY V.index, V.voter
+            ), finalized AS (
+                SELECT S.index, F.validator_address, COUNT(*) AS finalized_count, MAX(F.block_number) AS last_finalized_block_number
+                FROM session S
+                INNER JOIN sub_telemetry_node_finality F ON F.time >= S.start_time AND F.time < S.end_time
+                WHERE F.kind = 'finalized'
+                GROUP BY S.index, F.validator_address
+            )
+            INSERT INTO sub_telemetry_validator_finality_session (validator_account_id, validator_address, session_index, finalized_count, prevote_count, precommit_count, late_prevote_count, late_precommit_count, prevote_target_count, precommit_target_count, network_prevote_target_count, network_precommit_target_count, last_finalized_block_number)
+            SELECT VA.validator_account_id, VA.validator_address, N.index,
+                COALESCE(FN.finalized_count, 0),
+                COALESCE(VV.prevote_count, 0),
+                COALESCE(VV.precommit_count, 0),
+                COALESCE(VV.late_prevote_count, 0),
+                COALESCE(VV.late_precommit_count, 0),
+                COALESCE(VV.prevote_target_count, 0),
+                COALESCE(VV.precommit_target_count, 0),
+                N.prevote_target_count,
+                N.precommit_target_count,
+                FN.last_finalized_block_number
+            FROM sub_telemetry_validator_address VA
+            CROSS JOIN network N
+            LEFT JOIN validator_vote VV ON VV.index = N.index AND VV.voter = VA.validator_address
+            LEFT JOIN finalized FN ON FN.index = N.index AND FN.validator_address = VA.validator_address
+            WHERE VV.voter IS NOT NULL OR FN.validator_address IS NOT NULL
+            ON CONFLICT (validator_account_id, validator_address, session_index) DO UPDATE
+            SET finalized_count = EXCLUDED.finalized_count, prevote_count = EXCLUDED.prevote_count, precommit_count = EXCLUDED.precommit_count, late_prevote_count = EXCLUDED.late_prevote_count, late_precommit_count = EXCLUDED.late_precommit_count, prevote_target_count = EXCLUDED.prevote_target_count, precommit_target_count = EXCLUDED.precommit_target_count, network_prevote_target_count = EXCLUDED.network_prevote_target_count, network_precommit_target_count = EXCLUDED.network_precommit_target_count, last_finalized_block_number = EXCLUDED.last_finalized_block_number, updated_at = now()
+            "#,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// GRANDPA participation of the validator's nodes in the given inclusive session range,
+    /// most recent session first.
+    pub async fn get_validator_telemetry_finality_sessions(
+        &self,
+        validator_account_id: &AccountId,
+        maybe_start_session_index: Option<u64>,
+        maybe_end_session_index: Option<u64>,
+    ) -> anyhow::Result<Vec<ValidatorTelemetryFinalitySession>> {
+        let db_sessions: Vec<PostgresValidatorTelemetryFinalitySession> = sqlx::query_as(
+            r#"
+            SELECT session_index, validator_address, finalized_count, prevote_count, precommit_count, late_prevote_count, late_precommit_count, prevote_target_count, precommit_target_count, network_prevote_target_count, network_precommit_target_count, last_finalized_block_number
+            FROM sub_telemetry_validator_finality_session
+            WHERE validator_account_id = $1
+            AND ($2::bigint IS NULL OR session_index >= $2)
+            AND ($3::bigint IS NULL OR session_index <= $3)
+            ORDER BY session_index DESC, validator_address ASC
+            "#,
+        )
+            .bind(validator_account_id.to_string())
+            .bind(maybe_start_session_index.map(|index| index as i64))
+            .bind(maybe_end_session_index.map(|index| index as i64))
+            .fetch_all(&self.connection_pool)
+            .await?;
+        Ok(db_sessions
+            .into_iter()
+            .map(|db_session| {
+                let prevote_target_count = db_session.7 as u64;
+                let precommit_target_count = db_session.8 as u64;
+                let network_prevote_target_count = db_session.9 as u64;
+                let network_precommit_target_count = db_session.10 as u64;
+                ValidatorTelemetryFinalitySession {
+                    session_index: db_session.0 as u64,
+                    validator_address: db_session.1,
+                    finalized_count: db_session.2 as u64,
+                    prevote_count: db_session.3 as u64,
+                    precommit_count: db_session.4 as u64,
+                    late_prevote_count: db_session.5 as u64,
+                    late_precommit_count: db_session.6 as u64,
+                    prevote_target_count,
+                    precommit_target_count,
+                    network_prevote_target_count,
+                    network_precommit_target_count,
+                    prevote_rate: get_rate(prevote_target_count, network_prevote_target_count),
+                    precommit_rate: get_rate(
+                        precommit_target_count,
+                        network_precommit_target_count,
+                    ),
+                    last_finalized_block_number: db_session.11.map(|number| number as u64),
+                }
+            })
+            .collect())
+    }
+
+    /// Finality status of the validator's current nodes that report a validator address. The
+    /// last finality message is the last vote cast by the node, as received by any node. The
+    /// GRANDPA messages are reported by the nodes that receive them, so the `validator_address`
+    /// of the message is the receiver, and the voter is reported debug-formatted, i.e. possibly
+    /// in quotes.
+    pub async fn get_validator_telemetry_node_finality_statuses(
+        &self,
+        validator_account_id: &AccountId,
+    ) -> anyhow::Result<Vec<TelemetryNodeFinalityStatus>> {
+        let db_statuses: Vec<PostgresTelemetryNodeFinalityStatus> = sqlx::query_as(
+            r#"
+            SELECT N.id, N.name, N.validator_address, N.best_block_number,
+                CASE WHEN N.best_block_number IS NULL THEN NULL ELSE GREATEST(NS.best_block_number - N.best_block_number, 0) END,
+                (EXTRACT(EPOCH FROM LF.time) * 1000)::bigint,
+                LF.block_number
+            FROM sub_telemetry_node_validator NV
+            INNER JOIN sub_telemetry_node N ON N.id = NV.node_id
+            CROSS JOIN sub_telemetry_network_status NS
+            LEFT JOIN LATERAL (
+                SELECT F.time, F.block_number
+                FROM sub_telemetry_node_finality F
+                WHERE btrim(F.voter, '"') = N.validator_address
+                AND F.kind IN ('prevote', 'precommit')
+                ORDER BY F.time DESC
+                LIMIT 1
+            ) LF ON TRUE
+            WHERE NV.validator_account_id = $1
+            AND N.validator_address IS NOT NULL
+            AND NS.id = 1
+            ORDER BY N.id ASC
+            "#,
+        )
+            .bind(validator_account_id.to_string())
+            .fetch_all(&self.connection_pool)
+            .await?;
+        Ok(db_statuses
+            .into_iter()
+            .map(|db_status| TelemetryNodeFinalityStatus {
+                node_id: db_status.0 as u64,
+                node_name: db_status.1,
+                validator_address: db_status.2,
+                best_block_number: db_status.3.map(|number| number as u64),
+                best_block_lag: db_status.4.map(|lag| lag as u64),
+                last_finality_timestamp: db_status.5.map(|timestamp| timestamp as u64),
+                last_finality_block_number: db_status.6.map(|number| number as u64),
+            })
+            .collect())
+    }
+
+    /// Updates the client version history of the nodes that have a network id. Returns the
+    /// number of updated rows.
+    pub async fn save_telemetry_node_versions(&self) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO sub_telemetry_node_version (network_id, node_name, client_implementation, client_version)
+            SELECT DISTINCT ON (network_id, client_version) network_id, name, client_implementation, client_version
+            FROM sub_telemetry_node
+            WHERE network_id IS NOT NULL
+            ORDER BY network_id, client_version, id DESC
+            ON CONFLICT (network_id, client_version) DO UPDATE
+            SET node_name = EXCLUDED.node_name, client_implementation = EXCLUDED.client_implementation, last_seen_at = now()
+            "#,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Snapshots the number of nodes per client version. Returns the number of versions.
+    pub async fn save_telemetry_version_distribution(&self) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO sub_telemetry_version_distribution (client_version, node_count, validator_node_count)
+            SELECT N.client_version, COUNT(N.id), COUNT(NV.node_id)
+            FROM sub_telemetry_node N
+            LEFT JOIN sub_telemetry_node_validator NV ON NV.node_id = N.id
+            GROUP BY N.client_version
+            "#,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Saves the release as the latest release. Release time is kept as the first save time if
+    /// the release doesn't have one.
+    pub async fn save_telemetry_latest_release(
+        &self,
+        release: &TelemetryRelease,
+    ) -> anyhow::Result<()> {
+        let released_at = release
+            .released_at
+            .map(|released_at| released_at.naive_utc());
+        sqlx::query(
+            r#"
+            INSERT INTO sub_telemetry_release (version, released_at)
+            VALUES ($1, COALESCE($2, now()))
+            ON CONFLICT (version) DO UPDATE
+            SET released_at = COALESCE($2, sub_telemetry_release.released_at), updated_at = now()
+            "#,
+        )
+        .bind(&release.version)
+        .bind(released_at)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_telemetry_latest_version(
+        &self,
+    ) -> anyhow::Result<Option<TelemetryLatestVersion>> {
+        let maybe_db_version: Option<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT version, (EXTRACT(EPOCH FROM released_at) * 1000)::bigint
+            FROM sub_telemetry_release
+            ORDER BY updated_at DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_db_version.map(|db_version| TelemetryLatestVersion {
+            version: db_version.0,
+            released_timestamp: db_version.1 as u64,
+        }))
+    }
+
+    /// Number of nodes per client version and time bucket of the given resolution in the
+    /// `[start_timestamp, end_timestamp)` range. Counts are averaged over all the snapshots in
+    /// a bucket, so that a version missing in some of the snapshots counts as zero in those.
+    pub async fn get_telemetry_version_distribution(
+        &self,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        resolution: TimeSeriesResolution,
+    ) -> anyhow::Result<Vec<TelemetryVersionCount>> {
+        let db_counts: Vec<(i64, String, i64, i64)> = sqlx::query_as(
+            r#"
+            WITH distribution AS (
+                SELECT time_bucket($1::interval, time) AS bucket, time, client_version, node_count, validator_node_count
+                FROM sub_telemetry_version_distribution
+                WHERE time >= $2
+                AND time < $3
+            ), bucket_snapshot AS (
+                SELECT bucket, COUNT(DISTINCT time) AS snapshot_count
+                FROM distribution
+                GROUP BY bucket
+            )
+            SELECT (EXTRACT(EPOCH FROM D.bucket) * 1000)::bigint, D.client_version, ROUND(SUM(D.node_count)::numeric / BS.snapshot_count)::bigint, ROUND(SUM(D.validator_node_count)::numeric / BS.snapshot_count)::bigint
+            FROM distribution D
+            INNER JOIN bucket_snapshot BS ON BS.bucket = D.bucket
+            GROUP BY D.bucket, D.client_version, BS.snapshot_count
+            ORDER BY D.bucket ASC, D.client_version ASC
+            "#,
+        )
+            .bind(resolution.get_interval())
+            .bind(get_date_time(start_timestamp)?)
+            .bind(get_date_time(end_timestamp)?)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        Ok(db_counts
+            .into_iter()
+            .map(|db_count| TelemetryVersionCount {
+                timestamp: db_count.0 as u64,
+                client_version: db_count.1,
+                node_count: db_count.2 as u32,
+                validator_node_count: db_count.3 as u32,
+            })
+            .collect())
+    }
+
+    /// Client versions that the node with the given network id has run, most recent first.
+    pub async fn get_telemetry_node_versions(
+        &self,
+        network_id: &str,
+    ) -> anyhow::Result<Vec<TelemetryNodeVersion>> {
+        let db_versions: Vec<(String, String, String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT node_name, client_implementation, client_version, (EXTRACT(EPOCH FROM first_seen_at) * 1000)::bigint, (EXTRACT(EPOCH FROM last_seen_at) * 1000)::bigint
+            FROM sub_telemetry_node_version
+            WHERE network_id = $1
+            ORDER BY first_seen_at DESC
+            "#,
+        )
+            .bind(network_id)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        Ok(db_versions
+            .into_iter()
+            .map(|db_version| TelemetryNodeVersion {
+                node_name: db_version.0,
+                client_implementation: db_version.1,
+                client_version: db_version.2,
+                first_seen_timestamp: db_version.3 as u64,
+                last_seen_timestamp: db_version.4 as u64,
+            })
+            .collect())
+    }
 }
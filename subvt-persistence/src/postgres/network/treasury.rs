@@ -0,0 +1,272 @@
+//! Treasury spends requested by the referendum proposals, and the treasury payment history
+//! merged from the treasury and bounty events.
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::treasury::{TreasurySpendEvent, TreasurySpendSource};
+use subvt_types::crypto::AccountId;
+use subvt_types::governance::polkassembly::ReferendumStatus;
+use subvt_types::governance::treasury::ReferendumTreasurySpend;
+use subvt_types::report::ReferendumTreasurySpendReport;
+
+type PostgresReferendumTreasurySpend = (
+    i32,
+    i32,
+    String,
+    Option<String>,
+    String,
+    String,
+    Option<String>,
+    Option<i64>,
+);
+
+fn parse_treasury_spend_source(source: &str) -> anyhow::Result<TreasurySpendSource> {
+    let source = match source {
+        "spend_approved" => TreasurySpendSource::SpendApproved,
+        "asset_spend_approved" => TreasurySpendSource::AssetSpendApproved,
+        "awarded" => TreasurySpendSource::Awarded,
+        "bounty_claimed" => TreasurySpendSource::BountyClaimed,
+        "child_bounty_claimed" => TreasurySpendSource::ChildBountyClaimed,
+        _ => return Err(anyhow::anyhow!("Unknown treasury spend source: {}", source)),
+    };
+    Ok(source)
+}
+
+fn parse_json(maybe_json: &Option<String>) -> anyhow::Result<Option<serde_json::Value>> {
+    Ok(maybe_json
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()?)
+}
+
+impl PostgreSQLNetworkStorage {
+    /// Replaces the treasury spends of the referendum. The proposal of a referendum doesn't
+    /// change, but the spends get re-saved on every update while the referendum is ongoing.
+    pub async fn save_referendum_treasury_spends(
+        &self,
+        referendum_index: u32,
+        spends: &[ReferendumTreasurySpend],
+    ) -> anyhow::Result<()> {
+        let mut transaction = self.connection_pool.begin().await?;
+        sqlx::query(
+            r#"
+            DELETE FROM sub_referendum_treasury_spend
+            WHERE referendum_index = $1
+            "#,
+        )
+        .bind(referendum_index as i32)
+        .execute(&mut transaction)
+        .await?;
+        for spend in spends {
+            sqlx::query(
+                r#"
+                INSERT INTO sub_referendum_treasury_spend (referendum_index, call_index, call_name, asset_kind_json, amount, beneficiary_json, beneficiary_account_id, valid_from)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+                .bind(spend.referendum_index as i32)
+                .bind(spend.call_index as i32)
+                .bind(&spend.call_name)
+                .bind(spend.asset_kind.as_ref().map(serde_json::to_string).transpose()?)
+                .bind(spend.amount.to_string())
+                .bind(serde_json::to_string(&spend.beneficiary)?)
+                .bind(spend.beneficiary_account_id.map(|account_id| account_id.to_string()))
+                .bind(spend.valid_from.map(|block_number| block_number as i64))
+                .execute(&mut transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn get_referendum_treasury_spends(
+        &self,
+        referendum_indices: &[i32],
+        maybe_beneficiary_account_id: Option<&AccountId>,
+    ) -> anyhow::Result<Vec<ReferendumTreasurySpend>> {
+        let db_spends: Vec<PostgresReferendumTreasurySpend> = sqlx::query_as(
+            r#"
+            SELECT referendum_index, call_index, call_name, asset_kind_json, amount, beneficiary_json, beneficiary_account_id, valid_from
+            FROM sub_referendum_treasury_spend
+            WHERE referendum_index = ANY($1)
+            AND ($2::VARCHAR IS NULL OR beneficiary_account_id = $2)
+            ORDER BY referendum_index DESC, call_index ASC
+            "#,
+        )
+        .bind(referendum_indices)
+        .bind(maybe_beneficiary_account_id.map(|account_id| account_id.to_string()))
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut spends = Vec::with_capacity(db_spends.len());
+        for db_spend in db_spends {
+            spends.push(ReferendumTreasurySpend {
+                referendum_index: db_spend.0 as u32,
+                call_index: db_spend.1 as u32,
+                call_name: db_spend.2.clone(),
+                asset_kind: parse_json(&db_spend.3)?,
+                amount: db_spend.4.parse()?,
+                beneficiary: serde_json::from_str(&db_spend.5)?,
+                beneficiary_account_id: db_spend
+                    .6
+                    .as_deref()
+                    .map(AccountId::from_str)
+                    .transpose()?,
+                valid_from: db_spend.7.map(|block_number| block_number as u32),
+            });
+        }
+        Ok(spends)
+    }
+
+    async fn get_referendum_treasury_spend_reports(
+        &self,
+        db_referenda: Vec<(i32, Option<i16>, String, Option<String>)>,
+        maybe_beneficiary_account_id: Option<&AccountId>,
+    ) -> anyhow::Result<Vec<ReferendumTreasurySpendReport>> {
+        let indices: Vec<i32> = db_referenda.iter().map(|referendum| referendum.0).collect();
+        let spends = self
+            .get_referendum_treasury_spends(&indices, maybe_beneficiary_account_id)
+            .await?;
+        let mut reports = Vec::with_capacity(db_referenda.len());
+        for db_referendum in db_referenda {
+            let referendum_index = db_referendum.0 as u32;
+            reports.push(ReferendumTreasurySpendReport {
+                referendum_index,
+                track_id: db_referendum.1.map(|track_id| track_id as u16),
+                status: ReferendumStatus::from_str(&db_referendum.2)?,
+                title: db_referendum.3,
+                spends: spends
+                    .iter()
+                    .filter(|spend| spend.referendum_index == referendum_index)
+                    .cloned()
+                    .collect(),
+            });
+        }
+        Ok(reports)
+    }
+
+    /// Referenda of the track that request treasury spends, most recent first.
+    pub async fn get_referendum_treasury_spend_reports_for_track(
+        &self,
+        track_id: u16,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ReferendumTreasurySpendReport>> {
+        let db_referenda: Vec<(i32, Option<i16>, String, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT R.post_id, R.track_id, R.status, R.title
+            FROM sub_referendum R
+            WHERE R.track_id = $1
+            AND EXISTS (SELECT 1 FROM sub_referendum_treasury_spend S WHERE S.referendum_index = R.post_id)
+            ORDER BY R.post_id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(track_id as i16)
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        self.get_referendum_treasury_spend_reports(db_referenda, None)
+            .await
+    }
+
+    /// Referenda that request treasury spends for the account, most recent first. Only the
+    /// spends for the account are included.
+    pub async fn get_referendum_treasury_spend_reports_for_beneficiary(
+        &self,
+        beneficiary_account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ReferendumTreasurySpendReport>> {
+        let db_referenda: Vec<(i32, Option<i16>, String, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT R.post_id, R.track_id, R.status, R.title
+            FROM sub_referendum R
+            WHERE EXISTS (SELECT 1 FROM sub_referendum_treasury_spend S WHERE S.referendum_index = R.post_id AND S.beneficiary_account_id = $1)
+            ORDER BY R.post_id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(beneficiary_account_id.to_string())
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        self.get_referendum_treasury_spend_reports(db_referenda, Some(beneficiary_account_id))
+            .await
+    }
+
+    /// Treasury payments granted to the account through the spend approvals, the legacy
+    /// proposal awards and the bounty and child bounty claims, most recent first.
+    pub async fn get_treasury_spend_events_for_beneficiary(
+        &self,
+        beneficiary_account_id: &AccountId,
+        limit: u32,
+    ) -> anyhow::Result<Vec<TreasurySpendEvent>> {
+        #[allow(clippy::type_complexity)]
+        let db_events: Vec<(
+            String,
+            String,
+            i64,
+            Option<i32>,
+            i32,
+            i32,
+            Option<i32>,
+            String,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+        )> = sqlx::query_as(
+            r#"
+            SELECT * FROM (
+                SELECT 'spend_approved' AS source, E.block_hash, B."number", E.extrinsic_index, E.event_index, E.proposal_index AS "index", NULL::INTEGER AS child_index, E.amount, NULL::TEXT AS asset_kind_json, E.beneficiary_account_id, NULL::TEXT AS beneficiary_json
+                FROM sub_event_treasury_spend_approved E
+                INNER JOIN sub_block B ON B.hash = E.block_hash
+                WHERE E.beneficiary_account_id = $1
+                UNION ALL
+                SELECT 'asset_spend_approved', E.block_hash, B."number", E.extrinsic_index, E.event_index, E.spend_index, NULL, E.amount, E.asset_kind_json, E.beneficiary_account_id, E.beneficiary_json
+                FROM sub_event_treasury_asset_spend_approved E
+                INNER JOIN sub_block B ON B.hash = E.block_hash
+                WHERE E.beneficiary_account_id = $1
+                UNION ALL
+                SELECT 'awarded', E.block_hash, B."number", E.extrinsic_index, E.event_index, E.proposal_index, NULL, E.amount, NULL, E.beneficiary_account_id, NULL
+                FROM sub_event_treasury_awarded E
+                INNER JOIN sub_block B ON B.hash = E.block_hash
+                WHERE E.beneficiary_account_id = $1
+                UNION ALL
+                SELECT 'bounty_claimed', E.block_hash, B."number", E.extrinsic_index, E.event_index, E.bounty_index, NULL, E.payout, NULL, E.beneficiary_account_id, NULL
+                FROM sub_event_bounty_claimed E
+                INNER JOIN sub_block B ON B.hash = E.block_hash
+                WHERE E.beneficiary_account_id = $1
+                UNION ALL
+                SELECT 'child_bounty_claimed', E.block_hash, B."number", E.extrinsic_index, E.event_index, E.parent_bounty_index, E.child_bounty_index, E.payout, NULL, E.beneficiary_account_id, NULL
+                FROM sub_event_child_bounty_claimed E
+                INNER JOIN sub_block B ON B.hash = E.block_hash
+                WHERE E.beneficiary_account_id = $1
+            ) AS spend
+            ORDER BY "number" DESC, event_index DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(beneficiary_account_id.to_string())
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut events = Vec::with_capacity(db_events.len());
+        for db_event in db_events {
+            events.push(TreasurySpendEvent {
+                source: parse_treasury_spend_source(&db_event.0)?,
+                block_hash: db_event.1.clone(),
+                block_number: db_event.2 as u64,
+                extrinsic_index: db_event.3.map(|index| index as u32),
+                event_index: db_event.4 as u32,
+                index: db_event.5 as u32,
+                child_bounty_index: db_event.6.map(|index| index as u32),
+                amount: db_event.7.parse()?,
+                asset_kind: parse_json(&db_event.8)?,
+                beneficiary_account_id: db_event
+                    .9
+                    .as_deref()
+                    .map(AccountId::from_str)
+                    .transpose()?,
+                beneficiary: parse_json(&db_event.10)?,
+            });
+        }
+        Ok(events)
+    }
+}
@@ -0,0 +1,131 @@
+//! Downsampled historical validator snapshots. Written by `subvt-validator-list-updater`,
+//! read by the report service for the validator time series reports.
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use chrono::NaiveDateTime;
+use sqlx::{Postgres, QueryBuilder};
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{TimeSeriesResolution, ValidatorSnapshot};
+use subvt_types::substrate::Balance;
+use subvt_types::subvt::ValidatorDetails;
+
+/// Snapshots are inserted in multi-row statements of at most this many rows, to stay well
+/// within the bind parameter limit of PostgreSQL.
+const SNAPSHOT_INSERT_CHUNK_SIZE: usize = 1000;
+
+type PostgresValidatorSnapshot = (
+    i64,
+    i64,
+    bool,
+    i64,
+    String,
+    Option<String>,
+    i32,
+    Option<i64>,
+    Option<i64>,
+);
+
+pub(crate) fn get_date_time(timestamp_ms: u64) -> anyhow::Result<NaiveDateTime> {
+    match NaiveDateTime::from_timestamp_opt(
+        timestamp_ms as i64 / 1000,
+        (timestamp_ms as i64 % 1000) as u32 * 1_000_000,
+    ) {
+        Some(date_time) => Ok(date_time),
+        None => Err(anyhow::anyhow!("Invalid timestamp: {timestamp_ms}")),
+    }
+}
+
+impl PostgreSQLNetworkStorage {
+    pub async fn save_validator_snapshots(
+        &self,
+        block_number: u64,
+        block_timestamp: u64,
+        validators: &[ValidatorDetails],
+    ) -> anyhow::Result<()> {
+        let date_time = get_date_time(block_timestamp)?;
+        let mut transaction = self.connection_pool.begin().await?;
+        for chunk in validators.chunks(SNAPSHOT_INSERT_CHUNK_SIZE) {
+            let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO sub_validator_snapshot (time, block_number, validator_account_id, is_active, commission_per_billion, self_stake, total_stake, nominator_count, return_rate_per_billion, onekv_rank) ",
+            );
+            query_builder.push_values(chunk, |mut row, validator| {
+                let snapshot = ValidatorSnapshot::from((block_timestamp, block_number, validator));
+                row.push_bind(date_time)
+                    .push_bind(block_number as i64)
+                    .push_bind(validator.account.id.to_string())
+                    .push_bind(snapshot.is_active)
+                    .push_bind(snapshot.commission_per_billion as i64)
+                    .push_bind(snapshot.self_stake.to_string())
+                    .push_bind(
+                        snapshot
+                            .total_stake
+                            .map(|total_stake| total_stake.to_string()),
+                    )
+                    .push_bind(snapshot.nominator_count as i32)
+                    .push_bind(snapshot.return_rate_per_billion.map(|rate| rate as i64))
+                    .push_bind(snapshot.onekv_rank.map(|rank| rank as i64));
+            });
+            query_builder.push(" ON CONFLICT (time, validator_account_id) DO NOTHING");
+            query_builder.build().execute(&mut transaction).await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Block number of the most recent snapshot, if there is any.
+    pub async fn get_last_validator_snapshot_block_number(&self) -> anyhow::Result<Option<u64>> {
+        let maybe_block_number: Option<(i64,)> = sqlx::query_as(
+            r#"
+            SELECT block_number
+            FROM sub_validator_snapshot
+            ORDER BY time DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_block_number.map(|block_number| block_number.0 as u64))
+    }
+
+    /// Returns the last snapshot in each time bucket of the given resolution
+    /// in the `[start_timestamp, end_timestamp)` range.
+    pub async fn get_validator_snapshots(
+        &self,
+        validator_account_id: &AccountId,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        resolution: TimeSeriesResolution,
+    ) -> anyhow::Result<Vec<ValidatorSnapshot>> {
+        let db_snapshots: Vec<PostgresValidatorSnapshot> = sqlx::query_as(
+            r#"
+            SELECT (EXTRACT(EPOCH FROM time_bucket($1::interval, time)) * 1000)::bigint AS bucket, last(block_number, time), last(is_active, time), last(commission_per_billion, time), last(self_stake, time), last(total_stake, time), last(nominator_count, time), last(return_rate_per_billion, time), last(onekv_rank, time)
+            FROM sub_validator_snapshot
+            WHERE validator_account_id = $2
+            AND time >= $3
+            AND time < $4
+            GROUP BY bucket
+            ORDER BY bucket ASC
+            "#,
+        )
+            .bind(resolution.get_interval())
+            .bind(validator_account_id.to_string())
+            .bind(get_date_time(start_timestamp)?)
+            .bind(get_date_time(end_timestamp)?)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        let mut result = Vec::new();
+        for db_snapshot in db_snapshots {
+            result.push(ValidatorSnapshot {
+                timestamp: db_snapshot.0 as u64,
+                block_number: db_snapshot.1 as u64,
+                is_active: db_snapshot.2,
+                commission_per_billion: db_snapshot.3 as u32,
+                self_stake: db_snapshot.4.parse::<Balance>()?,
+                total_stake: super::report::parse_maybe_string(&db_snapshot.5)?,
+                nominator_count: db_snapshot.6 as u32,
+                return_rate_per_billion: db_snapshot.7.map(|rate| rate as u32),
+                onekv_rank: db_snapshot.8.map(|rank| rank as u64),
+            });
+        }
+        Ok(result)
+    }
+}
@@ -3,6 +3,7 @@ use lazy_static::lazy_static;
 use redis::{Client, RedisResult};
 use rustc_hash::FxHashSet as HashSet;
 use subvt_config::Config;
+use subvt_types::app::UserSignInChallenge;
 use subvt_types::crypto::AccountId;
 use subvt_types::report::BlockSummary;
 use subvt_types::subvt::{NetworkStatus, ValidatorDetails, ValidatorSummary};
@@ -197,6 +198,19 @@ impl Redis {
         finalized_block_number: u64,
         is_active: bool,
     ) -> anyhow::Result<Vec<ValidatorSummary>> {
+        Ok(self
+            .get_validator_details_list(finalized_block_number, is_active)
+            .await?
+            .iter()
+            .map(ValidatorSummary::from)
+            .collect())
+    }
+
+    pub async fn get_validator_details_list(
+        &self,
+        finalized_block_number: u64,
+        is_active: bool,
+    ) -> anyhow::Result<Vec<ValidatorDetails>> {
         let mut connection = self.client.get_async_connection().await?;
         let prefix = format!(
             "subvt:{}:validators:{}:{}",
@@ -221,6 +235,10 @@ impl Redis {
                 )
             })
             .collect();
+        // MGET fails with no keys
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
         let validator_json_strings: Vec<String> = redis::cmd("MGET")
             .arg(&keys)
             .query_async(&mut connection)
@@ -228,9 +246,111 @@ impl Redis {
             .context("Can't read validator JSON string from Redis.")?;
         let mut result = Vec::new();
         for validator_json_string in &validator_json_strings {
-            let validator_details: ValidatorDetails = serde_json::from_str(validator_json_string)?;
-            result.push(ValidatorSummary::from(&validator_details))
+            result.push(serde_json::from_str(validator_json_string)?);
         }
         Ok(result)
     }
+
+    /// Records the nonce of a signed app service request for the given public key, expiring
+    /// after the given number of seconds. Returns `false` if the nonce has already been seen
+    /// for the key, i.e. the request is a replay.
+    pub async fn add_app_service_auth_nonce(
+        &self,
+        public_key_hex: &str,
+        nonce: u64,
+        expiry_secs: u64,
+    ) -> anyhow::Result<bool> {
+        let mut connection = self.client.get_async_connection().await?;
+        let result: Option<String> = redis::cmd("SET")
+            .arg(format!(
+                "subvt:app_service:auth:nonce:{public_key_hex}:{nonce}"
+            ))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(expiry_secs)
+            .query_async(&mut connection)
+            .await?;
+        Ok(result.is_some())
+    }
+
+    /// Saves the sign-in challenge of the device with the given public key, replacing any
+    /// earlier challenge of the device.
+    pub async fn set_app_service_sign_in_challenge(
+        &self,
+        public_key_hex: &str,
+        challenge: &UserSignInChallenge,
+        expiry_secs: u64,
+    ) -> anyhow::Result<()> {
+        let challenge_json = serde_json::to_string(challenge)?;
+        let mut connection = self.client.get_async_connection().await?;
+        redis::cmd("SET")
+            .arg(format!(
+                "subvt:app_service:sign_in_challenge:{public_key_hex}"
+            ))
+            .arg(&challenge_json)
+            .arg("EX")
+            .arg(expiry_secs)
+            .query_async(&mut connection)
+            .await?;
+        Ok(())
+    }
+
+    /// Gets and deletes the sign-in challenge of the device with the given public key, so that
+    /// a challenge can be used only once.
+    pub async fn take_app_service_sign_in_challenge(
+        &self,
+        public_key_hex: &str,
+    ) -> anyhow::Result<Option<UserSignInChallenge>> {
+        let mut connection = self.client.get_async_connection().await?;
+        let key = format!("subvt:app_service:sign_in_challenge:{public_key_hex}");
+        let (challenge_json, _): (Option<String>, i32) = redis::pipe()
+            .atomic()
+            .get(&key)
+            .del(&key)
+            .query_async(&mut connection)
+            .await?;
+        match challenge_json {
+            Some(challenge_json) => Ok(Some(serde_json::from_str(&challenge_json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Saves the ownership verification challenge of the user validator, replacing any earlier
+    /// challenge.
+    pub async fn set_app_service_validator_ownership_challenge(
+        &self,
+        user_validator_id: u32,
+        challenge: &str,
+        expiry_secs: u64,
+    ) -> anyhow::Result<()> {
+        let mut connection = self.client.get_async_connection().await?;
+        redis::cmd("SET")
+            .arg(format!(
+                "subvt:app_service:validator_ownership_challenge:{user_validator_id}"
+            ))
+            .arg(challenge)
+            .arg("EX")
+            .arg(expiry_secs)
+            .query_async(&mut connection)
+            .await?;
+        Ok(())
+    }
+
+    /// Gets and deletes the ownership verification challenge of the user validator, so that
+    /// a challenge can be used only once.
+    pub async fn take_app_service_validator_ownership_challenge(
+        &self,
+        user_validator_id: u32,
+    ) -> anyhow::Result<Option<String>> {
+        let mut connection = self.client.get_async_connection().await?;
+        let key = format!("subvt:app_service:validator_ownership_challenge:{user_validator_id}");
+        let (challenge, _): (Option<String>, i32) = redis::pipe()
+            .atomic()
+            .get(&key)
+            .del(&key)
+            .query_async(&mut connection)
+            .await?;
+        Ok(challenge)
+    }
 }
@@ -0,0 +1,80 @@
+//! Era points of a validator against the median era points of the active set.
+use crate::plotlib::{AxisPosition, Chart, Color, LineSeriesView, ScaleBand, ScaleLinear};
+use crate::{save_chart, PlotterError};
+use std::path::PathBuf;
+use subvt_types::report::{ChartImageFormat, ValidatorEraChartData};
+
+const VALIDATOR_KEY: &str = "Validator";
+const MEDIAN_KEY: &str = "Active Set Median";
+
+/// Plots the validator's era points and the active set median as two lines. Eras in which
+/// the validator was inactive are left out of the validator line, and there's no data to plot
+/// if the validator wasn't active in any of the eras.
+pub(crate) fn with_era_points_chart<R>(
+    title: &str,
+    data: &[ValidatorEraChartData],
+    f: impl FnOnce(Chart) -> anyhow::Result<R>,
+) -> anyhow::Result<R> {
+    if !data.iter().any(|datum| datum.is_active) {
+        return Err(PlotterError::EmptyData.into());
+    }
+    let mut domain = vec![];
+    let mut points_data = vec![];
+    let mut max_points = 0u64;
+    for datum in data {
+        let era = datum.era_index.to_string();
+        domain.push(era.clone());
+        if datum.is_active {
+            points_data.push((
+                era.clone(),
+                datum.reward_points as f32,
+                VALIDATOR_KEY.to_string(),
+            ));
+            max_points = max_points.max(datum.reward_points);
+        }
+        if let Some(median) = datum.active_set_median_reward_points {
+            points_data.push((era, median as f32, MEDIAN_KEY.to_string()));
+            max_points = max_points.max(median);
+        }
+    }
+    let y_max = (max_points as f32 * 1.2).ceil().max(1.0);
+
+    let width = 1200;
+    let height = 600;
+    let (top, right, bottom, left) = (40, 30, 90, 60);
+    let x = ScaleBand::new()
+        .set_domain(domain)
+        .set_range(vec![0, width - left - right]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, y_max])
+        .set_range(vec![height - top - bottom, 0]);
+    let view = LineSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_keys(vec![VALIDATOR_KEY.to_string(), MEDIAN_KEY.to_string()])
+        .set_colors(Color::from_vec_of_hex_strings(vec!["#1f77b4", "#ff7f0e"]))
+        .set_label_visibility(false)
+        .load_data(&points_data)
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title.to_string())
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Era Points")
+        .set_bottom_axis_tick_label_rotation(-45)
+        .add_legend_at(AxisPosition::Bottom);
+    f(chart)
+}
+
+/// Plots the era points chart and saves it in the given format to the temporary directory.
+pub fn plot_era_points(
+    title: &str,
+    data: &[ValidatorEraChartData],
+    format: ChartImageFormat,
+) -> anyhow::Result<PathBuf> {
+    with_era_points_chart(title, data, |chart| save_chart(chart, format))
+}
@@ -1,9 +1,23 @@
 #![warn(clippy::disallowed_types)]
+use crate::plotlib::{Chart, Page};
 use lazy_static::lazy_static;
+use pdf_writer::{Content, Finish, Name, PdfWriter, Rect, Ref};
+use rand::Rng;
+use resvg::usvg::fontdb;
+use std::path::PathBuf;
 use subvt_config::Config;
+use subvt_types::report::ChartImageFormat;
+use usvg::{TreeParsing, TreeTextToPath};
 
+pub mod era_points;
+pub mod monthly_report;
+pub mod para_votes;
 mod plotlib;
 pub mod rewards;
+pub mod stake;
+#[cfg(test)]
+mod test;
+pub mod validator_chart;
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
@@ -14,3 +28,143 @@ pub enum PlotterError {
     #[error("Provided data set is empty.")]
     EmptyData,
 }
+
+fn get_token_amount(amount: u128) -> f32 {
+    amount as f32 / 10u128.pow(CONFIG.substrate.token_decimals as u32) as f32
+}
+
+fn get_tmp_file_path(name: &str, extension: &str) -> String {
+    format!(
+        "{}{}{}.{}",
+        CONFIG.plotter.tmp_dir_path,
+        std::path::MAIN_SEPARATOR,
+        name,
+        extension,
+    )
+}
+
+fn get_unique_file_name() -> String {
+    let millis = chrono::Utc::now().timestamp_millis();
+    let random: u16 = rand::thread_rng().gen();
+    format!("{millis}_{random}")
+}
+
+/// Parses the SVG data and converts its text to paths using the configured fonts, so that the
+/// tree can be rendered to PNG or PDF.
+fn get_svg_tree(svg_data: &[u8]) -> anyhow::Result<usvg::Tree> {
+    let mut fontdb = fontdb::Database::new();
+    fontdb.load_fonts_dir(&CONFIG.plotter.font_dir_path);
+    fontdb.set_sans_serif_family(&CONFIG.plotter.font_sans_serif_family);
+    let mut rtree = usvg::Tree::from_data(svg_data, &usvg::Options::default())?;
+    rtree.convert_text(&fontdb);
+    Ok(rtree)
+}
+
+fn save_svg_as_png(svg_data: &[u8], png_path: &str) -> anyhow::Result<()> {
+    let rtree = get_svg_tree(svg_data)?;
+    let pixmap_size = rtree.size.to_screen_size();
+    let mut pixmap = tiny_skia::Pixmap::new(pixmap_size.width(), pixmap_size.height())
+        .ok_or_else(|| anyhow::anyhow!("Cannot create pixmap."))?;
+    resvg::render(
+        &rtree,
+        resvg::FitTo::Original,
+        tiny_skia::Transform::default(),
+        pixmap.as_mut(),
+    )
+    .ok_or_else(|| anyhow::anyhow!("Cannot render SVG."))?;
+    pixmap.save_png(png_path)?;
+    Ok(())
+}
+
+fn save_svg_document(document: svg::Document, format: ChartImageFormat) -> anyhow::Result<PathBuf> {
+    let path = get_tmp_file_path(&get_unique_file_name(), format.get_extension());
+    match format {
+        ChartImageFormat::Svg => svg::save(&path, &document)?,
+        ChartImageFormat::Png => save_svg_as_png(document.to_string().as_bytes(), &path)?,
+    }
+    Ok(PathBuf::from(&path))
+}
+
+/// Saves the chart to a file with a unique name in the temporary directory and returns the
+/// file path.
+fn save_chart(chart: Chart, format: ChartImageFormat) -> anyhow::Result<PathBuf> {
+    let document = chart
+        .to_svg_document()
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    save_svg_document(document, format)
+}
+
+/// Saves the page as an image to a file with a unique name in the temporary directory and
+/// returns the file path.
+fn save_page(page: &Page, format: ChartImageFormat) -> anyhow::Result<PathBuf> {
+    let document = page
+        .to_svg_document()
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    save_svg_document(document, format)
+}
+
+/// Renders the pages as a multi-page PDF document. Each page is converted to a PDF form XObject
+/// that fills the page.
+fn get_pages_pdf(pages: &[Page]) -> anyhow::Result<Vec<u8>> {
+    if pages.is_empty() {
+        return Err(PlotterError::EmptyData.into());
+    }
+    let mut trees = Vec::new();
+    for page in pages {
+        let document = page
+            .to_svg_document()
+            .map_err(|error| anyhow::anyhow!("{}", error))?;
+        trees.push(get_svg_tree(document.to_string().as_bytes())?);
+    }
+    // indirect object ids: catalog, page tree, pages, page contents, then the XObjects
+    let mut next_ref_id = 1;
+    let mut next_ref = || {
+        let id = Ref::new(next_ref_id);
+        next_ref_id += 1;
+        id
+    };
+    let catalog_id = next_ref();
+    let page_tree_id = next_ref();
+    let page_ids: Vec<Ref> = trees.iter().map(|_| next_ref()).collect();
+    let content_ids: Vec<Ref> = trees.iter().map(|_| next_ref()).collect();
+    let mut next_id = next_ref();
+    let mut writer = PdfWriter::new();
+    writer.catalog(catalog_id).pages(page_tree_id);
+    writer
+        .pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_ids.len() as i32);
+    let x_object_name = Name(b"P1");
+    for (i, tree) in trees.iter().enumerate() {
+        // CSS pixels to PDF points
+        let width = tree.size.width() as f32 * 0.75;
+        let height = tree.size.height() as f32 * 0.75;
+        let x_object_id = next_id;
+        let mut page = writer.page(page_ids[i]);
+        page.media_box(Rect::new(0.0, 0.0, width, height));
+        page.parent(page_tree_id);
+        page.contents(content_ids[i]);
+        page.resources()
+            .x_objects()
+            .pair(x_object_name, x_object_id);
+        page.finish();
+        let mut content = Content::new();
+        content.save_state();
+        content.transform([width, 0.0, 0.0, height, 0.0, 0.0]);
+        content.x_object(x_object_name);
+        content.restore_state();
+        writer.stream(content_ids[i], &content.finish());
+        next_id =
+            svg2pdf::convert_tree_into(tree, svg2pdf::Options::default(), &mut writer, x_object_id);
+    }
+    Ok(writer.finish())
+}
+
+/// Saves the pages as a multi-page PDF file with a unique name in the temporary directory and
+/// returns the file path.
+fn save_pages_as_pdf(pages: &[Page]) -> anyhow::Result<PathBuf> {
+    let pdf = get_pages_pdf(pages)?;
+    let path = get_tmp_file_path(&get_unique_file_name(), "pdf");
+    std::fs::write(&path, pdf)?;
+    Ok(PathBuf::from(&path))
+}
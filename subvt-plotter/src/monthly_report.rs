@@ -0,0 +1,146 @@
+//! Monthly validator performance report. A multi-page PDF of the validator charts along with a
+//! summary of the month, to be attached to email digests.
+use crate::plotlib::Page;
+use crate::{era_points, para_votes, save_page, save_pages_as_pdf, stake, PlotterError, CONFIG};
+use std::path::PathBuf;
+use subvt_types::report::{
+    ChartImageFormat, ValidatorEraChartData, ValidatorSessionParaVotesChartData,
+};
+use subvt_types::substrate::Balance;
+use subvt_utility::numeric::format_decimal;
+
+fn get_summary(
+    era_data: &[ValidatorEraChartData],
+    para_votes_data: &[ValidatorSessionParaVotesChartData],
+    total_reward: Balance,
+) -> String {
+    let active_eras: Vec<&ValidatorEraChartData> =
+        era_data.iter().filter(|datum| datum.is_active).collect();
+    let mut lines = vec![format!(
+        "Active in {} of {} eras.",
+        active_eras.len(),
+        era_data.len()
+    )];
+    if !active_eras.is_empty() {
+        let average_points = active_eras
+            .iter()
+            .map(|datum| datum.reward_points)
+            .sum::<u64>()
+            / active_eras.len() as u64;
+        let median_points: Vec<u64> = active_eras
+            .iter()
+            .filter_map(|datum| datum.active_set_median_reward_points)
+            .collect();
+        if median_points.is_empty() {
+            lines.push(format!("Average era points: {average_points}."));
+        } else {
+            lines.push(format!(
+                "Average era points: {average_points}, active set median average: {}.",
+                median_points.iter().sum::<u64>() / median_points.len() as u64
+            ));
+        }
+    }
+    lines.push(format!(
+        "Total rewards: {} {}.",
+        format_decimal(
+            total_reward,
+            CONFIG.substrate.token_decimals,
+            CONFIG.substrate.token_format_decimal_points,
+        ),
+        CONFIG.substrate.token_ticker,
+    ));
+    if !para_votes_data.is_empty() {
+        let (explicit, implicit, missed) =
+            para_votes_data
+                .iter()
+                .fold((0, 0, 0), |(explicit, implicit, missed), datum| {
+                    (
+                        explicit + datum.para_votes_summary.explicit,
+                        implicit + datum.para_votes_summary.implicit,
+                        missed + datum.para_votes_summary.missed,
+                    )
+                });
+        lines.push(format!(
+            "Para votes in {} sessions: {explicit} explicit, {implicit} implicit, {missed} missed.",
+            para_votes_data.len(),
+        ));
+    }
+    lines.join("\n")
+}
+
+/// First page of the report: the summary, the stake composition and the era points charts.
+fn get_summary_page(
+    title: &str,
+    era_data: &[ValidatorEraChartData],
+    para_votes_data: &[ValidatorSessionParaVotesChartData],
+    total_reward: Balance,
+) -> anyhow::Result<Page> {
+    let page = Page::new()
+        .add_title(title.to_string())
+        .add_text(get_summary(era_data, para_votes_data, total_reward), 2);
+    let page = stake::with_stake_composition_chart("Stake Composition", era_data, |chart| {
+        Ok(page.add_chart(&chart, 2))
+    })?;
+    if era_data.iter().any(|datum| datum.is_active) {
+        era_points::with_era_points_chart("Era Points", era_data, |chart| {
+            Ok(page.add_chart(&chart, 2))
+        })
+    } else {
+        Ok(page)
+    }
+}
+
+/// Second page of the report: the para votes and the nominator count charts.
+fn get_detail_page(
+    era_data: &[ValidatorEraChartData],
+    para_votes_data: &[ValidatorSessionParaVotesChartData],
+) -> anyhow::Result<Page> {
+    let page = Page::new();
+    let page = if para_votes_data.is_empty() {
+        page
+    } else {
+        para_votes::with_para_votes_chart("Para Votes", para_votes_data, |chart| {
+            Ok(page.add_chart(&chart, 2))
+        })?
+    };
+    stake::with_nominator_count_chart("Active Nominators", era_data, |chart| {
+        Ok(page.add_chart(&chart, 2))
+    })
+}
+
+/// Plots the monthly report of the validator as a two-page PDF. The first page has the summary,
+/// the stake composition and era points charts, and the second page has the para votes and the
+/// nominator count charts. The era data and the para votes data should cover the month. Only
+/// the report service serves the report for now, it's not attached to email notifications.
+pub fn plot_validator_monthly_report(
+    title: &str,
+    era_data: &[ValidatorEraChartData],
+    para_votes_data: &[ValidatorSessionParaVotesChartData],
+    total_reward: Balance,
+) -> anyhow::Result<PathBuf> {
+    if era_data.is_empty() {
+        return Err(PlotterError::EmptyData.into());
+    }
+    save_pages_as_pdf(&[
+        get_summary_page(title, era_data, para_votes_data, total_reward)?,
+        get_detail_page(era_data, para_votes_data)?,
+    ])
+}
+
+/// Plots only the summary page of the monthly report and saves it in the given image format,
+/// for channels that display images inline rather than attachments.
+pub fn plot_validator_monthly_report_summary(
+    title: &str,
+    era_data: &[ValidatorEraChartData],
+    para_votes_data: &[ValidatorSessionParaVotesChartData],
+    total_reward: Balance,
+    format: ChartImageFormat,
+) -> anyhow::Result<PathBuf> {
+    if era_data.is_empty() {
+        return Err(PlotterError::EmptyData.into());
+    }
+    save_page(
+        &get_summary_page(title, era_data, para_votes_data, total_reward)?,
+        format,
+    )
+}
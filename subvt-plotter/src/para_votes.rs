@@ -0,0 +1,88 @@
+//! Explicit, implicit and missed para votes of a validator per session.
+use crate::plotlib::{AxisPosition, Chart, Color, ScaleBand, ScaleLinear, VerticalBarView};
+use crate::{save_chart, PlotterError};
+use std::path::PathBuf;
+use subvt_types::report::{ChartImageFormat, ValidatorSessionParaVotesChartData};
+
+const EXPLICIT_KEY: &str = "Explicit";
+const IMPLICIT_KEY: &str = "Implicit";
+const MISSED_KEY: &str = "Missed";
+
+/// Plots the para votes of the validator as stacked bars, one bar per session.
+pub(crate) fn with_para_votes_chart<R>(
+    title: &str,
+    data: &[ValidatorSessionParaVotesChartData],
+    f: impl FnOnce(Chart) -> anyhow::Result<R>,
+) -> anyhow::Result<R> {
+    if data.is_empty() {
+        return Err(PlotterError::EmptyData.into());
+    }
+    let mut domain = vec![];
+    let mut bar_data = vec![];
+    let mut max_vote_count = 0;
+    for datum in data {
+        let session = datum.session_index.to_string();
+        let summary = &datum.para_votes_summary;
+        domain.push(session.clone());
+        bar_data.push((
+            session.clone(),
+            summary.explicit as f32,
+            EXPLICIT_KEY.to_string(),
+        ));
+        bar_data.push((
+            session.clone(),
+            summary.implicit as f32,
+            IMPLICIT_KEY.to_string(),
+        ));
+        bar_data.push((session, summary.missed as f32, MISSED_KEY.to_string()));
+        max_vote_count = max_vote_count.max(summary.explicit + summary.implicit + summary.missed);
+    }
+    let y_max = (max_vote_count as f32 * 1.2).ceil().max(1.0);
+
+    let width = 1200;
+    let height = 600;
+    let (top, right, bottom, left) = (40, 30, 90, 60);
+    let x = ScaleBand::new()
+        .set_domain(domain)
+        .set_range(vec![0, width - left - right])
+        .set_inner_padding(0.1)
+        .set_outer_padding(0.1);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, y_max])
+        .set_range(vec![height - top - bottom, 0]);
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_keys(vec![
+            EXPLICIT_KEY.to_string(),
+            IMPLICIT_KEY.to_string(),
+            MISSED_KEY.to_string(),
+        ])
+        .set_colors(Color::from_vec_of_hex_strings(vec![
+            "#2ca02c", "#1f77b4", "#d62728",
+        ]))
+        .set_label_visibility(false)
+        .load_data(&bar_data)
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title.to_string())
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Para Votes")
+        .set_bottom_axis_tick_label_rotation(-45)
+        .add_legend_at(AxisPosition::Bottom);
+    f(chart)
+}
+
+/// Plots the para votes chart and saves it in the given format to the temporary directory.
+pub fn plot_para_votes(
+    title: &str,
+    data: &[ValidatorSessionParaVotesChartData],
+    format: ChartImageFormat,
+) -> anyhow::Result<PathBuf> {
+    with_para_votes_chart(title, data, |chart| save_chart(chart, format))
+}
@@ -19,7 +19,7 @@ pub enum Orientation {
 }
 
 /// The Chart struct definition.
-/// A Chart is the smallest entity that can be saved (the bigger one is a Page (TBD)).
+/// A Chart is the smallest entity that can be saved (the bigger one is a Page).
 pub struct Chart<'a> {
     margin_top: isize,
     margin_bottom: isize,
@@ -33,6 +33,7 @@ pub struct Chart<'a> {
     y_axis_right: Option<Axis>,
     legend_position: Option<AxisPosition>,
     views: Vec<&'a dyn View<'a>>,
+    right_axis_views: Vec<&'a dyn View<'a>>,
     title: String,
     subtitle: String,
     summary: Option<String>,
@@ -54,6 +55,7 @@ impl<'a> Chart<'a> {
             y_axis_right: None,
             legend_position: None,
             views: Vec::new(),
+            right_axis_views: Vec::new(),
             title: String::new(),
             subtitle: String::new(),
             summary: None,
@@ -105,6 +107,13 @@ impl<'a> Chart<'a> {
         self
     }
 
+    /// Add a dataset that is plotted against the right (secondary) Y axis. The view should use
+    /// the same scale as the right axis, and its legend entries are marked as right axis series.
+    pub fn add_right_axis_view(mut self, view: &'a dyn View<'a>) -> Self {
+        self.right_axis_views.push(view);
+        self
+    }
+
     /// Add an axis at the bottom of the chart.
     pub fn add_axis_bottom<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
         self.x_axis_bottom = Some(Axis::new_bottom_axis(scale, &self));
@@ -403,7 +412,10 @@ impl<'a> Chart<'a> {
             format!("translate({},{})", self.margin_left, self.margin_top),
         );
 
-        for view in self.views.iter() {
+        if !self.right_axis_views.is_empty() && self.y_axis_right.is_none() {
+            return Err("Please add a right axis before adding right axis views.".to_string());
+        }
+        for view in self.views.iter().chain(self.right_axis_views.iter()) {
             view_group.append(view.to_svg()?);
         }
         group.append(view_group);
@@ -481,11 +493,17 @@ impl<'a> Chart<'a> {
                 }
             };
 
-            let legend_entries = self
+            let mut legend_entries = self
                 .views
                 .iter()
                 .flat_map(|view| view.get_legend_entries())
                 .collect::<Vec<LegendEntry>>();
+            legend_entries.extend(self.right_axis_views.iter().flat_map(|view| {
+                view.get_legend_entries().into_iter().map(|mut entry| {
+                    entry.append_to_label(" (right axis)");
+                    entry
+                })
+            }));
             let legend = Legend::new(legend_entries, width as usize);
             let mut legend_group = legend.to_svg()?;
             legend_group.assign("transform", format!("translate({x_offset},{y_offset})"));
@@ -498,28 +516,28 @@ impl<'a> Chart<'a> {
         Ok(group)
     }
 
+    /// Generate the SVG document of the chart.
+    pub fn to_svg_document(&self) -> Result<svg::Document, String> {
+        match self.to_svg() {
+            Ok(svg_content) => Ok(svg::Document::new()
+                .set("width", self.width)
+                .set("height", self.height)
+                .set("viewBox", (0, 0, self.width, self.height))
+                .add(svg_content)),
+            Err(e) => Err(format!(
+                "Encountered an error while saving the chart: {e:?}",
+            )),
+        }
+    }
+
     /// Save the chart to a file
     pub fn save<P>(self, path: P) -> Result<(), String>
     where
         P: AsRef<Path>,
     {
         if let Some("svg") = path.as_ref().extension().and_then(OsStr::to_str) {
-            match self.to_svg() {
-                Ok(svg_content) => {
-                    let document = svg::Document::new()
-                        .set("width", self.width)
-                        .set("height", self.height)
-                        .set("viewBox", (0, 0, self.width, self.height))
-                        .add(svg_content);
-
-                    svg::save(path, &document).unwrap()
-                }
-                Err(e) => {
-                    return Err(format!(
-                        "Encountered an error while saving the chart: {e:?}",
-                    ))
-                }
-            }
+            let document = self.to_svg_document()?;
+            svg::save(path, &document).unwrap()
         }
         Ok(())
     }
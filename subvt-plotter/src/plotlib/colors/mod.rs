@@ -5,6 +5,13 @@ pub struct Color {
 }
 
 impl Color {
+    /// Create a color from a hex string, e.g. `#1f77b4`.
+    pub fn from_hex(hex: &str) -> Self {
+        Color {
+            hex: String::from(hex),
+        }
+    }
+
     /// Generate a color scheme from a string.
     /// Useful when displaying a single dataset that requires one color.
     pub fn from_vec_of_hex_strings(color_strings: Vec<&str>) -> Vec<Self> {
@@ -16,6 +16,15 @@ pub enum BarLabelPosition {
     EndOutside,
 }
 
+/// Set how the bars of multiple keys in the same category are laid out.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BarMode {
+    /// Blocks of all keys are stacked on top of each other in a single bar.
+    Stacked,
+    /// Each key gets its own bar, placed side by side within the category band.
+    Grouped,
+}
+
 /// Represents a block within a bar.
 /// The first tuple element represents the starting position, the second
 /// one is the size of that block and the third one is the color.
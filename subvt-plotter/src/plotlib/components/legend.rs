@@ -50,6 +50,11 @@ impl LegendEntry {
         }
     }
 
+    /// Append text to the label, e.g. to mark the axis the series is plotted against.
+    pub fn append_to_label(&mut self, suffix: &str) {
+        self.label.push_str(suffix);
+    }
+
     /// Return legend entry width to compute the placement of legend entries on the chart.
     pub fn get_width(&self) -> usize {
         // TODO ideally, compute the length of the given `label` in the given font and size
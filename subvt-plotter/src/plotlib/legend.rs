@@ -13,19 +13,19 @@ impl Legend {
         Self { entries, width }
     }
 
+    /// Lay out the entries in rows, wrapping to a new row when the next entry doesn't fit in
+    /// the legend width. Each entry takes as much space as its own label needs, so legends of
+    /// many series with labels of different lengths stay compact.
     pub fn to_svg(&self) -> Result<Group, String> {
         let mut group = Group::new().set("class", "g-legend");
-        let max_entry_length = match self.entries.iter().map(|entry| entry.get_width()).max() {
-            None => return Ok(group),
-            Some(len) => len,
-        };
         let gap_between_legend_entries = 10;
         let legend_row_height = 20;
         let mut current_row_offset = 0;
         let mut acc_row_width = 0;
 
         for entry in self.entries.iter() {
-            if acc_row_width + max_entry_length > self.width && acc_row_width > 0 {
+            let entry_width = entry.get_width();
+            if acc_row_width + entry_width > self.width && acc_row_width > 0 {
                 acc_row_width = 0;
                 current_row_offset += 1;
             }
@@ -41,7 +41,7 @@ impl Legend {
             );
             group.append(entry_group);
 
-            acc_row_width += max_entry_length + gap_between_legend_entries;
+            acc_row_width += entry_width + gap_between_legend_entries;
         }
 
         Ok(group)
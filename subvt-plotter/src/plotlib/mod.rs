@@ -8,13 +8,17 @@
 //!
 //! 1. Bar Chart (horizontal and vertical)
 //! 2. Stacked Bar Chart (horizontal and vertical)
+//! 3. Grouped Bar Chart (horizontal and vertical)
+//! 4. Line, Area and Scatter Charts
+//!
+//! Views can be plotted against the left or the right (secondary) Y axis of a chart.
 //!
 //! ## Abstraction Layers
 //!
 //! There are several abstractions at the foundation of this visualization library:
 //!
 //!   Page
-//!   └- Grid
+//!   └- Grid (charts, titles and text blocks)
 //!      └- Chart
 //!         ├- Axes
 //!         └- View
@@ -25,9 +29,10 @@
 pub(crate) use crate::plotlib::axis::{Axis, AxisPosition};
 pub(crate) use crate::plotlib::chart::Chart;
 pub(crate) use crate::plotlib::colors::Color;
-pub(crate) use crate::plotlib::components::bar::BarLabelPosition;
+pub(crate) use crate::plotlib::components::bar::{BarLabelPosition, BarMode};
 pub(crate) use crate::plotlib::components::line::LineSeries;
 pub(crate) use crate::plotlib::components::scatter::{MarkerType, PointLabelPosition};
+pub(crate) use crate::plotlib::page::Page;
 pub(crate) use crate::plotlib::scales::band::ScaleBand;
 pub(crate) use crate::plotlib::scales::linear::ScaleLinear;
 pub(crate) use crate::plotlib::scales::Scale;
@@ -43,6 +48,7 @@ mod chart;
 mod colors;
 mod components;
 mod legend;
+mod page;
 mod scales;
 // mod view;
 mod views;
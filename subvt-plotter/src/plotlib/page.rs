@@ -0,0 +1,304 @@
+use crate::plotlib::Chart;
+use svg::node::element::Group;
+use svg::node::element::Rectangle;
+use svg::node::element::Text;
+use svg::node::Text as TextNode;
+use svg::Node;
+
+/// An item in the page grid. Charts are rendered when they get added to the page, so that
+/// the page doesn't have to outlive the scales and views of its charts.
+enum PageItem {
+    Chart {
+        document: Result<svg::Document, String>,
+        width: isize,
+        height: isize,
+    },
+    Title(String),
+    Text(String),
+}
+
+/// An item placed in the grid, along with the number of columns it spans.
+struct PageCell {
+    item: PageItem,
+    column_span: usize,
+}
+
+/// The Page struct definition.
+/// A Page is a grid of charts, titles and text blocks. Items are laid out in rows in the order
+/// they are added, and an item that doesn't fit in the remaining columns of the current row
+/// starts a new row. The height of a row is the height of its tallest item.
+pub struct Page {
+    margin_top: isize,
+    margin_bottom: isize,
+    margin_right: isize,
+    margin_left: isize,
+    width: isize,
+    height: isize,
+    column_count: usize,
+    gap: isize,
+    title: String,
+    cells: Vec<PageCell>,
+}
+
+impl Page {
+    /// Create a new instance of a page with default sizes (A4 at 150 DPI).
+    pub fn new() -> Self {
+        Self {
+            margin_top: 40,
+            margin_bottom: 40,
+            margin_right: 40,
+            margin_left: 40,
+            width: 1240,
+            height: 1754,
+            column_count: 2,
+            gap: 20,
+            title: String::new(),
+            cells: Vec::new(),
+        }
+    }
+
+    /// Set page width.
+    pub fn set_width(mut self, width: isize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set page height.
+    pub fn set_height(mut self, height: isize) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set the margins of the page to provided values.
+    pub fn set_margins(mut self, top: isize, right: isize, bottom: isize, left: isize) -> Self {
+        self.margin_top = top;
+        self.margin_right = right;
+        self.margin_bottom = bottom;
+        self.margin_left = left;
+        self
+    }
+
+    /// Set the number of columns in the grid.
+    pub fn set_column_count(mut self, column_count: usize) -> Self {
+        self.column_count = column_count.max(1);
+        self
+    }
+
+    /// Set the horizontal and vertical gap between the grid cells.
+    pub fn set_gap(mut self, gap: isize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Add page title, displayed at the top of the page above the grid.
+    pub fn add_title(mut self, title: String) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Add a chart spanning the given number of columns. The chart is scaled to the cell width,
+    /// keeping its aspect ratio.
+    pub fn add_chart(mut self, chart: &Chart, column_span: usize) -> Self {
+        self.add_cell(
+            PageItem::Chart {
+                document: chart.to_svg_document(),
+                width: chart.get_chart_width(),
+                height: chart.get_chart_height(),
+            },
+            column_span,
+        );
+        self
+    }
+
+    /// Add a section title spanning the given number of columns.
+    pub fn add_section_title(mut self, title: String, column_span: usize) -> Self {
+        self.add_cell(PageItem::Title(title), column_span);
+        self
+    }
+
+    /// Add a text block spanning the given number of columns. Lines are wrapped to fit the
+    /// cell width, and new line characters start new paragraphs.
+    pub fn add_text(mut self, text: String, column_span: usize) -> Self {
+        self.add_cell(PageItem::Text(text), column_span);
+        self
+    }
+
+    fn add_cell(&mut self, item: PageItem, column_span: usize) {
+        self.cells.push(PageCell {
+            item,
+            column_span: column_span.clamp(1, self.column_count),
+        });
+    }
+
+    /// Return the width of a single grid column.
+    fn get_column_width(&self) -> f32 {
+        let grid_width = self.width - self.margin_left - self.margin_right;
+        (grid_width - self.gap * (self.column_count as isize - 1)) as f32 / self.column_count as f32
+    }
+
+    /// Return the width of a cell that spans the given number of columns.
+    fn get_cell_width(&self, column_span: usize) -> f32 {
+        self.get_column_width() * column_span as f32
+            + (self.gap * (column_span as isize - 1)) as f32
+    }
+
+    /// Split the text into lines that fit the given width.
+    fn wrap_text(text: &str, width: f32) -> Vec<String> {
+        // TODO ideally, compute the length of the text in the given font and size
+        let avg_letter_width = 7_f32; // this is for the default sans-serif 13px font
+        let max_line_length = ((width / avg_letter_width) as usize).max(1);
+        let mut lines = Vec::new();
+        for paragraph in text.lines() {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                if !line.is_empty() && line.len() + 1 + word.len() > max_line_length {
+                    lines.push(line);
+                    line = String::new();
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Return the height of an item when laid out in a cell of the given width.
+    fn get_item_height(item: &PageItem, width: f32) -> f32 {
+        match item {
+            PageItem::Chart {
+                width: chart_width,
+                height: chart_height,
+                ..
+            } => width * *chart_height as f32 / *chart_width as f32,
+            PageItem::Title(_) => 30_f32,
+            PageItem::Text(text) => Self::wrap_text(text, width).len() as f32 * 18_f32,
+        }
+    }
+
+    fn item_to_svg(item: &PageItem, width: f32, height: f32) -> Result<Group, String> {
+        let mut group = Group::new();
+        match item {
+            PageItem::Chart { document, .. } => {
+                let document = document
+                    .clone()?
+                    .set("x", 0)
+                    .set("y", 0)
+                    .set("width", width)
+                    .set("height", height);
+                group.assign("class", "g-page-chart");
+                group.append(document);
+            }
+            PageItem::Title(title) => {
+                group.assign("class", "g-page-section-title");
+                group.append(
+                    Text::new()
+                        .set("x", 0)
+                        .set("y", height / 2_f32)
+                        .set("dy", ".35em")
+                        .set("fill", "#222")
+                        .set("font-size", "16px")
+                        .set("font-family", "sans-serif")
+                        .set("font-weight", "bold")
+                        .add(TextNode::new(title)),
+                );
+            }
+            PageItem::Text(text) => {
+                group.assign("class", "g-page-text");
+                for (i, line) in Self::wrap_text(text, width).iter().enumerate() {
+                    group.append(
+                        Text::new()
+                            .set("x", 0)
+                            .set("y", i as f32 * 18_f32 + 9_f32)
+                            .set("dy", ".35em")
+                            .set("fill", "#333")
+                            .set("font-size", "13px")
+                            .set("font-family", "sans-serif")
+                            .add(TextNode::new(line)),
+                    );
+                }
+            }
+        }
+        Ok(group)
+    }
+
+    /// Generate the SVG for the page and its items.
+    fn to_svg(&self) -> Result<Group, String> {
+        let mut group = Group::new().set("class", "g-page");
+        let mut y_offset = self.margin_top as f32;
+
+        if !self.title.is_empty() {
+            group.append(
+                Group::new()
+                    .set("class", "g-title")
+                    .set(
+                        "transform",
+                        format!("translate({},{})", self.width / 2, y_offset + 12_f32),
+                    )
+                    .add(
+                        Text::new()
+                            .set("x", 0)
+                            .set("y", 0)
+                            .set("dy", ".35em")
+                            .set("fill", "#222")
+                            .set("text-anchor", "middle")
+                            .set("font-size", "22px")
+                            .set("font-family", "sans-serif")
+                            .set("font-weight", "bold")
+                            .add(TextNode::new(&self.title)),
+                    ),
+            );
+            y_offset += 24_f32 + self.gap as f32;
+        }
+
+        // group the cells into rows
+        let mut rows: Vec<Vec<&PageCell>> = Vec::new();
+        let mut row_column_count = 0;
+        for cell in self.cells.iter() {
+            if rows.is_empty() || row_column_count + cell.column_span > self.column_count {
+                rows.push(Vec::new());
+                row_column_count = 0;
+            }
+            rows.last_mut().unwrap().push(cell);
+            row_column_count += cell.column_span;
+        }
+
+        for row in rows.iter() {
+            let mut x_offset = self.margin_left as f32;
+            let mut row_height = 0_f32;
+            for cell in row.iter() {
+                let cell_width = self.get_cell_width(cell.column_span);
+                let cell_height = Self::get_item_height(&cell.item, cell_width);
+                if y_offset + cell_height > (self.height - self.margin_bottom) as f32 {
+                    return Err("Page content does not fit in the page height.".to_string());
+                }
+                let mut cell_group = Self::item_to_svg(&cell.item, cell_width, cell_height)?;
+                cell_group.assign("transform", format!("translate({x_offset},{y_offset})"));
+                group.append(cell_group);
+                x_offset += cell_width + self.gap as f32;
+                row_height = row_height.max(cell_height);
+            }
+            y_offset += row_height + self.gap as f32;
+        }
+
+        Ok(group)
+    }
+
+    /// Generate the SVG document of the page.
+    pub fn to_svg_document(&self) -> Result<svg::Document, String> {
+        let content = self.to_svg()?;
+        Ok(svg::Document::new()
+            .set("width", self.width)
+            .set("height", self.height)
+            .set("viewBox", (0, 0, self.width, self.height))
+            .add(
+                Rectangle::new()
+                    .set("width", self.width)
+                    .set("height", self.height)
+                    .set("fill", "#fff"),
+            )
+            .add(content))
+    }
+}
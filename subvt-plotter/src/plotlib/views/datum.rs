@@ -279,3 +279,17 @@ impl PointDatum<String, f32> for (String, isize) {
         String::new()
     }
 }
+
+impl PointDatum<String, f32> for (String, f32, String) {
+    fn get_x(&self) -> String {
+        self.0.clone()
+    }
+
+    fn get_y(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        self.2.clone()
+    }
+}
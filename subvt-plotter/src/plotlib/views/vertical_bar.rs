@@ -1,6 +1,6 @@
 use crate::plotlib::chart::Orientation;
 use crate::plotlib::colors::Color;
-use crate::plotlib::components::bar::{Bar, BarBlock, BarLabelPosition};
+use crate::plotlib::components::bar::{Bar, BarBlock, BarLabelPosition, BarMode};
 use crate::plotlib::components::legend::{LegendEntry, LegendMarkerType};
 use crate::plotlib::components::DatumRepresentation;
 use crate::plotlib::scales::ScaleType;
@@ -13,11 +13,13 @@ use svg::node::Node;
 /// A View that represents data as vertical bars.
 pub struct VerticalBarView<'a> {
     label_position: BarLabelPosition,
+    bar_mode: BarMode,
     labels_visible: bool,
     rounding_precision: Option<usize>,
     entries: Vec<Bar>,
     keys: Vec<String>,
     colors: Vec<Color>,
+    key_colors: HashMap<String, String>,
     color_map: HashMap<String, String>,
     x_scale: Option<&'a dyn Scale<String>>,
     y_scale: Option<&'a dyn Scale<f32>>,
@@ -29,11 +31,13 @@ impl<'a> VerticalBarView<'a> {
     pub fn new() -> Self {
         Self {
             label_position: BarLabelPosition::EndOutside,
+            bar_mode: BarMode::Stacked,
             labels_visible: true,
             rounding_precision: None,
             entries: Vec::new(),
             keys: Vec::new(),
             colors: Color::color_scheme_10(),
+            key_colors: HashMap::default(),
             color_map: HashMap::default(),
             x_scale: None,
             y_scale: None,
@@ -65,12 +69,24 @@ impl<'a> VerticalBarView<'a> {
         self
     }
 
+    /// Set whether the bars of multiple keys are stacked or grouped side by side.
+    pub fn set_bar_mode(mut self, bar_mode: BarMode) -> Self {
+        self.bar_mode = bar_mode;
+        self
+    }
+
     /// Set the color palette of the view.
     pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
         self.colors = colors;
         self
     }
 
+    /// Set the color of a single key (series), overriding the palette color for that key.
+    pub fn set_key_color(mut self, key: &str, color: Color) -> Self {
+        self.key_colors.insert(key.to_string(), color.as_hex());
+        self
+    }
+
     /// Set labels visibility.
     pub fn set_label_visibility(mut self, label_visibility: bool) -> Self {
         self.labels_visible = label_visibility;
@@ -110,14 +126,17 @@ impl<'a> VerticalBarView<'a> {
 
         // HashMap to group all data related to a category. This is needed when there
         // are many data entries under a single category as in a stacked bar chart.
-        let mut categories: HashMap<String, Vec<(&String, f32)>> = HashMap::default();
+        let mut categories: HashMap<String, Vec<(usize, &String, f32)>> = HashMap::default();
 
         // Organize entries based on the order of the keys first, since displayed data
         // should keep the order defined in the `keys` attribute.
-        for (i, key) in self.keys.iter_mut().enumerate() {
+        for (i, key) in self.keys.iter().enumerate() {
             // Map the key to the corresponding color.
-            self.color_map
-                .insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
+            let color = match self.key_colors.get(key) {
+                Some(color) => color.clone(),
+                None => self.colors[i % self.colors.len()].as_hex(),
+            };
+            self.color_map.insert(key.clone(), color);
 
             for entry in data.iter() {
                 if entry.get_key() == *key {
@@ -127,7 +146,7 @@ impl<'a> VerticalBarView<'a> {
                         categories.insert(entry.get_category(), Vec::new());
                     }
                     if let Some(category_entries) = categories.get_mut(&entry_category) {
-                        category_entries.push((key, entry.get_value()));
+                        category_entries.push((i, key, entry.get_value()));
                     }
                 }
             }
@@ -135,44 +154,60 @@ impl<'a> VerticalBarView<'a> {
 
         // Create a Bar entry for each category data that was grouped in the previous step.
         let mut bars = Vec::new();
-        let y_range_is_reversed = self.y_scale.unwrap().is_range_reversed();
-
-        for (category, key_value_pairs) in categories.iter_mut() {
-            let mut value_acc = 0_f32;
-            let mut bar_blocks = Vec::new();
-            let mut stacked_start = self.y_scale.unwrap().scale(&value_acc);
-            let mut stacked_end = stacked_start;
-
-            for (key, value) in key_value_pairs.iter() {
-                value_acc += *value;
-                // If Y axis' scale has the range in reversed order, then adjust the computation of
-                // the start and end positions to account for SVG coordinate system origin.
-                if y_range_is_reversed {
-                    stacked_end = stacked_start;
-                    stacked_start = self.y_scale.unwrap().scale(&value_acc);
-                } else {
-                    stacked_start = stacked_end;
-                    stacked_end = self.y_scale.unwrap().scale(&value_acc);
+
+        let key_count = self.keys.len().max(1);
+        let bandwidth = self.x_scale.unwrap().bandwidth().unwrap();
+
+        for (category, key_value_pairs) in categories.iter() {
+            let category_offset = self.x_scale.unwrap().scale(category);
+            match self.bar_mode {
+                BarMode::Stacked => {
+                    let mut value_acc = 0_f32;
+                    let mut bar_blocks = Vec::new();
+                    for (_, key, value) in key_value_pairs.iter() {
+                        let (start, end) = self.get_block_range(value_acc, value_acc + *value);
+                        value_acc += *value;
+                        bar_blocks.push(BarBlock::new(
+                            start,
+                            end,
+                            *value,
+                            self.color_map.get(*key).unwrap().clone(),
+                        ));
+                    }
+                    bars.push(Bar::new(
+                        bar_blocks,
+                        Orientation::Vertical,
+                        category.to_string(),
+                        self.label_position,
+                        self.labels_visible,
+                        self.rounding_precision,
+                        bandwidth,
+                        category_offset,
+                    ));
+                }
+                BarMode::Grouped => {
+                    // Each key gets an equal slice of the category band, in the order of the keys.
+                    let bar_width = bandwidth / key_count as f32;
+                    for (key_index, key, value) in key_value_pairs.iter() {
+                        let (start, end) = self.get_block_range(0_f32, *value);
+                        bars.push(Bar::new(
+                            vec![BarBlock::new(
+                                start,
+                                end,
+                                *value,
+                                self.color_map.get(*key).unwrap().clone(),
+                            )],
+                            Orientation::Vertical,
+                            category.to_string(),
+                            self.label_position,
+                            self.labels_visible,
+                            self.rounding_precision,
+                            bar_width,
+                            category_offset + *key_index as f32 * bar_width,
+                        ));
+                    }
                 }
-                bar_blocks.push(BarBlock::new(
-                    stacked_start,
-                    stacked_end,
-                    *value,
-                    self.color_map.get(*key).unwrap().clone(),
-                ));
             }
-
-            let bar = Bar::new(
-                bar_blocks,
-                Orientation::Vertical,
-                category.to_string(),
-                self.label_position,
-                self.labels_visible,
-                self.rounding_precision,
-                self.x_scale.unwrap().bandwidth().unwrap(),
-                self.x_scale.unwrap().scale(category),
-            );
-            bars.push(bar);
         }
 
         for bar in bars {
@@ -182,6 +217,18 @@ impl<'a> VerticalBarView<'a> {
         Ok(self)
     }
 
+    /// Get the start and end positions of a block that spans the given values on the value axis.
+    /// If the value axis' scale has the range in reversed order, swap the start and end positions
+    /// to account for the SVG coordinate system origin.
+    fn get_block_range(&self, start_value: f32, end_value: f32) -> (f32, f32) {
+        let scale = self.y_scale.unwrap();
+        if scale.is_range_reversed() {
+            (scale.scale(&end_value), scale.scale(&start_value))
+        } else {
+            (scale.scale(&start_value), scale.scale(&end_value))
+        }
+    }
+
     /// Extract the list of keys to use when stacking and coloring the bars.
     fn extract_keys(data: &[impl BarDatum]) -> Vec<String> {
         let mut keys = Vec::new();
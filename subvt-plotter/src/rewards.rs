@@ -1,14 +1,12 @@
 use crate::plotlib::{Chart, ScaleBand, ScaleLinear, VerticalBarView};
-use crate::{PlotterError, CONFIG};
+use crate::{get_token_amount, save_chart, PlotterError, CONFIG};
 use chrono::Datelike;
 use itertools::Itertools;
-use rand::Rng;
-use resvg::usvg::fontdb;
 use rustc_hash::FxHashMap as HashMap;
 use std::path::PathBuf;
+use subvt_types::report::ChartImageFormat;
 use subvt_types::substrate::{Balance, Era};
 use subvt_utility::numeric::format_decimal;
-use usvg::{TreeParsing, TreeTextToPath};
 
 fn get_monthly_rewards(rewards: &[(Era, Balance)]) -> anyhow::Result<HashMap<u32, Balance>> {
     if rewards.is_empty() {
@@ -47,7 +45,7 @@ pub fn plot_era_rewards(title: &str, rewards: &[(Era, Balance)]) -> anyhow::Resu
         let reward = *monthly_rewards.get(month_index).unwrap();
         let tick = format!("{} {}", months[month as usize], year % 100);
         domain.push(tick.clone());
-        let amount = reward as f32 / 10u128.pow(CONFIG.substrate.token_decimals as u32) as f32;
+        let amount = get_token_amount(reward);
         data.push((tick, amount));
         total += reward;
     }
@@ -56,8 +54,7 @@ pub fn plot_era_rewards(title: &str, rewards: &[(Era, Balance)]) -> anyhow::Resu
         CONFIG.substrate.token_decimals,
         CONFIG.substrate.token_format_decimal_points,
     );
-    let max_reward = *monthly_rewards.values().max().unwrap() as f32
-        / 10u128.pow(CONFIG.substrate.token_decimals as u32) as f32;
+    let max_reward = get_token_amount(*monthly_rewards.values().max().unwrap());
     let y_max = (max_reward * 1.2).ceil();
 
     let width = 1200;
@@ -77,17 +74,7 @@ pub fn plot_era_rewards(title: &str, rewards: &[(Era, Balance)]) -> anyhow::Resu
         .set_label_rounding_precision(4)
         .load_data(&data)
         .unwrap();
-    let millis = chrono::Utc::now().timestamp_millis();
-    let random: u16 = rand::thread_rng().gen();
-    // save svg
-    let svg_path = format!(
-        "{}{}{}_{}.svg",
-        CONFIG.plotter.tmp_dir_path,
-        std::path::MAIN_SEPARATOR,
-        millis,
-        random,
-    );
-    if let Err(error) = Chart::new()
+    let chart = Chart::new()
         .set_width(width)
         .set_height(height)
         .set_margins(top, right, bottom, left)
@@ -101,44 +88,6 @@ pub fn plot_era_rewards(title: &str, rewards: &[(Era, Balance)]) -> anyhow::Resu
         .add_axis_bottom(&x)
         .add_axis_left(&y)
         .add_left_axis_label(format!("Reward ({})", CONFIG.substrate.token_ticker))
-        .set_bottom_axis_tick_label_rotation(-45)
-        .save(&svg_path)
-    {
-        return Err(anyhow::anyhow!("{}", error));
-    }
-
-    // save png
-    let png_path = format!(
-        "{}{}{}_{}.png",
-        CONFIG.plotter.tmp_dir_path,
-        std::path::MAIN_SEPARATOR,
-        millis,
-        random,
-    );
-    let opt = usvg::Options {
-        resources_dir: std::fs::canonicalize(&svg_path)
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf())),
-        ..Default::default()
-    };
-    let mut fontdb = fontdb::Database::new();
-    fontdb.load_fonts_dir(&CONFIG.plotter.font_dir_path);
-    fontdb.set_sans_serif_family(&CONFIG.plotter.font_sans_serif_family);
-    let svg_data = std::fs::read(&svg_path).unwrap();
-    let mut rtree = usvg::Tree::from_data(&svg_data, &opt).unwrap();
-    rtree.convert_text(&fontdb);
-    //let pixmap_size = rtree.svg_node().size.to_screen_size();
-    let pixmap_size = rtree.size.to_screen_size();
-    let mut pixmap = tiny_skia::Pixmap::new(pixmap_size.width(), pixmap_size.height()).unwrap();
-    resvg::render(
-        &rtree,
-        resvg::FitTo::Original,
-        tiny_skia::Transform::default(),
-        pixmap.as_mut(),
-    )
-    .unwrap();
-    pixmap.save_png(&png_path)?;
-    // delete the svg file
-    std::fs::remove_file(svg_path)?;
-    Ok(PathBuf::from(&png_path))
+        .set_bottom_axis_tick_label_rotation(-45);
+    save_chart(chart, ChartImageFormat::Png)
 }
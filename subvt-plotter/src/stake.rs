@@ -0,0 +1,158 @@
+//! Stake composition and nominator count charts over eras.
+use crate::plotlib::{
+    AreaSeriesView, AxisPosition, Chart, Color, LineSeriesView, ScaleBand, ScaleLinear,
+};
+use crate::{get_token_amount, save_chart, PlotterError, CONFIG};
+use std::path::PathBuf;
+use subvt_types::report::{ChartImageFormat, ValidatorEraChartData};
+
+const SELF_STAKE_COLOR: &str = "#1f77b4";
+const NOMINATOR_STAKE_COLOR: &str = "#aec7e8";
+const NOMINATOR_COUNT_COLOR: &str = "#2ca02c";
+
+fn get_era_domain(data: &[ValidatorEraChartData]) -> Vec<String> {
+    data.iter()
+        .map(|datum| datum.era_index.to_string())
+        .collect()
+}
+
+/// Self stake and total stake of the validator as a stacked area over the eras. Total stake is
+/// the self stake in the eras the validator was not active in.
+pub(crate) fn with_stake_composition_chart<R>(
+    title: &str,
+    data: &[ValidatorEraChartData],
+    f: impl FnOnce(Chart) -> anyhow::Result<R>,
+) -> anyhow::Result<R> {
+    if data.is_empty() {
+        return Err(PlotterError::EmptyData.into());
+    }
+    let mut self_stake_data = vec![];
+    let mut total_stake_data = vec![];
+    let mut max_total_stake = 0.0_f32;
+    for datum in data {
+        let self_stake = get_token_amount(datum.self_stake.unwrap_or(0));
+        let total_stake = get_token_amount(
+            datum
+                .total_stake
+                .unwrap_or(0)
+                .max(datum.self_stake.unwrap_or(0)),
+        );
+        self_stake_data.push((datum.era_index.to_string(), self_stake));
+        total_stake_data.push((datum.era_index.to_string(), total_stake));
+        max_total_stake = max_total_stake.max(total_stake);
+    }
+    let y_max = (max_total_stake * 1.2).ceil().max(1.0);
+
+    let width = 1200;
+    let height = 600;
+    let (top, right, bottom, left) = (40, 30, 90, 80);
+    let x = ScaleBand::new()
+        .set_domain(get_era_domain(data))
+        .set_range(vec![0, width - left - right]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, y_max])
+        .set_range(vec![height - top - bottom, 0]);
+    // the total stake area is drawn first and the self stake area on top of it,
+    // which results in a stacked area chart
+    let total_stake_view = AreaSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_label_visibility(false)
+        .set_colors(Color::from_vec_of_hex_strings(vec![NOMINATOR_STAKE_COLOR]))
+        .set_custom_data_label("Total Stake".to_string())
+        .load_data(&total_stake_data)
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    let self_stake_view = AreaSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_label_visibility(false)
+        .set_colors(Color::from_vec_of_hex_strings(vec![SELF_STAKE_COLOR]))
+        .set_custom_data_label("Self Stake".to_string())
+        .load_data(&self_stake_data)
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title.to_string())
+        .add_view(&total_stake_view)
+        .add_view(&self_stake_view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label(format!("Stake ({})", CONFIG.substrate.token_ticker))
+        .set_bottom_axis_tick_label_rotation(-45)
+        .add_legend_at(AxisPosition::Bottom);
+    f(chart)
+}
+
+/// Plots the stake composition chart and saves it in the given format to the temporary directory.
+pub fn plot_stake_composition(
+    title: &str,
+    data: &[ValidatorEraChartData],
+    format: ChartImageFormat,
+) -> anyhow::Result<PathBuf> {
+    with_stake_composition_chart(title, data, |chart| save_chart(chart, format))
+}
+
+/// Active nominator count of the validator over the eras.
+pub(crate) fn with_nominator_count_chart<R>(
+    title: &str,
+    data: &[ValidatorEraChartData],
+    f: impl FnOnce(Chart) -> anyhow::Result<R>,
+) -> anyhow::Result<R> {
+    if data.is_empty() {
+        return Err(PlotterError::EmptyData.into());
+    }
+    let nominator_count_data: Vec<(String, isize)> = data
+        .iter()
+        .map(|datum| {
+            (
+                datum.era_index.to_string(),
+                datum.active_nominator_count.unwrap_or(0) as isize,
+            )
+        })
+        .collect();
+    let max_nominator_count = nominator_count_data
+        .iter()
+        .map(|datum| datum.1)
+        .max()
+        .unwrap_or(0);
+    let y_max = (max_nominator_count as f32 * 1.2).ceil().max(1.0);
+
+    let width = 1200;
+    let height = 600;
+    let (top, right, bottom, left) = (40, 30, 50, 60);
+    let x = ScaleBand::new()
+        .set_domain(get_era_domain(data))
+        .set_range(vec![0, width - left - right]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, y_max])
+        .set_range(vec![height - top - bottom, 0]);
+    let view = LineSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_label_visibility(false)
+        .set_colors(Color::from_vec_of_hex_strings(vec![NOMINATOR_COUNT_COLOR]))
+        .load_data(&nominator_count_data)
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title.to_string())
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Active Nominators")
+        .set_bottom_axis_tick_label_rotation(-45);
+    f(chart)
+}
+
+/// Plots the nominator count chart and saves it in the given format to the temporary directory.
+pub fn plot_nominator_count(
+    title: &str,
+    data: &[ValidatorEraChartData],
+    format: ChartImageFormat,
+) -> anyhow::Result<PathBuf> {
+    with_nominator_count_chart(title, data, |chart| save_chart(chart, format))
+}
@@ -0,0 +1,172 @@
+use super::assert_golden;
+use crate::plotlib::{
+    AxisPosition, BarMode, Chart, Color, HorizontalBarView, ScaleBand, ScaleLinear, VerticalBarView,
+};
+
+fn get_data() -> Vec<(&'static str, f32, &'static str)> {
+    vec![
+        ("A", 40.0, "Explicit"),
+        ("A", 20.0, "Implicit"),
+        ("A", 10.0, "Missed"),
+        ("B", 30.0, "Explicit"),
+        ("B", 30.0, "Implicit"),
+        ("B", 5.0, "Missed"),
+    ]
+}
+
+fn get_keys() -> Vec<String> {
+    vec![
+        "Explicit".to_string(),
+        "Implicit".to_string(),
+        "Missed".to_string(),
+    ]
+}
+
+#[test]
+fn test_vertical_stacked_bar_golden() {
+    let x = ScaleBand::new()
+        .set_domain(vec!["A".to_string(), "B".to_string()])
+        .set_range(vec![0, 300]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, 100.0])
+        .set_range(vec![200, 0]);
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_keys(get_keys())
+        .set_bar_mode(BarMode::Stacked)
+        .load_data(&get_data())
+        .unwrap();
+    let chart = Chart::new()
+        .set_width(400)
+        .set_height(300)
+        .set_margins(20, 40, 60, 60)
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_legend_at(AxisPosition::Bottom);
+    assert_golden("vertical_stacked_bar", &chart.to_svg_document().unwrap());
+}
+
+#[test]
+fn test_vertical_grouped_bar_golden() {
+    let x = ScaleBand::new()
+        .set_domain(vec!["A".to_string(), "B".to_string()])
+        .set_range(vec![0, 300]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, 50.0])
+        .set_range(vec![200, 0]);
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_keys(get_keys())
+        .set_bar_mode(BarMode::Grouped)
+        .set_key_color("Missed", Color::from_hex("#d62728"))
+        .load_data(&get_data())
+        .unwrap();
+    let chart = Chart::new()
+        .set_width(400)
+        .set_height(300)
+        .set_margins(20, 40, 60, 60)
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_legend_at(AxisPosition::Bottom);
+    assert_golden("vertical_grouped_bar", &chart.to_svg_document().unwrap());
+}
+
+#[test]
+fn test_horizontal_grouped_bar_golden() {
+    let x = ScaleLinear::new()
+        .set_domain(vec![0.0, 50.0])
+        .set_range(vec![0, 300]);
+    let y = ScaleBand::new()
+        .set_domain(vec!["A".to_string(), "B".to_string()])
+        .set_range(vec![0, 200]);
+    let view = HorizontalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_keys(get_keys())
+        .set_bar_mode(BarMode::Grouped)
+        .load_data(&get_data())
+        .unwrap();
+    let chart = Chart::new()
+        .set_width(400)
+        .set_height(300)
+        .set_margins(20, 40, 60, 60)
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_legend_at(AxisPosition::Bottom);
+    assert_golden("horizontal_grouped_bar", &chart.to_svg_document().unwrap());
+}
+
+/// Grouped bars split the category band evenly between the keys, in the order of the keys.
+#[test]
+fn test_vertical_grouped_bar_layout() {
+    let x = ScaleBand::new()
+        .set_domain(vec!["A".to_string(), "B".to_string()])
+        .set_range(vec![0, 300])
+        .set_inner_padding(0.0)
+        .set_outer_padding(0.0);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, 128.0])
+        .set_range(vec![128, 0]);
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_keys(get_keys())
+        .set_bar_mode(BarMode::Grouped)
+        .set_label_visibility(false)
+        .load_data(&get_data())
+        .unwrap();
+    let svg = Chart::new()
+        .set_width(300)
+        .set_height(128)
+        .set_margins(0, 0, 0, 0)
+        .add_view(&view)
+        .to_svg_document()
+        .unwrap()
+        .to_string();
+    // bandwidth is 150, each of the three keys gets a 50 wide slot
+    for offset in ["0", "50", "100", "150", "200", "250"] {
+        assert!(svg.contains(&format!("translate({offset},0)")));
+    }
+    assert_eq!(svg.matches("width=\"50\"").count(), 6);
+    // 40 on a [0, 128] -> [128, 0] scale starts at 88 and is 40 high
+    assert!(svg.contains("height=\"40\""));
+    assert!(svg.contains("y=\"88\""));
+}
+
+/// Stacked bars keep a single bar per category, with blocks following each other.
+#[test]
+fn test_vertical_stacked_bar_layout() {
+    let x = ScaleBand::new()
+        .set_domain(vec!["A".to_string(), "B".to_string()])
+        .set_range(vec![0, 300])
+        .set_inner_padding(0.0)
+        .set_outer_padding(0.0);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, 128.0])
+        .set_range(vec![128, 0]);
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_keys(get_keys())
+        .set_label_visibility(false)
+        .load_data(&get_data())
+        .unwrap();
+    let svg = Chart::new()
+        .set_width(300)
+        .set_height(128)
+        .set_margins(0, 0, 0, 0)
+        .add_view(&view)
+        .to_svg_document()
+        .unwrap()
+        .to_string();
+    assert_eq!(svg.matches("width=\"150\"").count(), 6);
+    // A: explicit [88, 128], implicit [68, 88], missed [58, 68]
+    assert!(svg.contains("y=\"88\""));
+    assert!(svg.contains("y=\"68\""));
+    assert!(svg.contains("y=\"58\""));
+}
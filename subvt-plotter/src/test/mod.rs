@@ -0,0 +1,35 @@
+//! Golden-file tests for the chart layout. Rendered SVG documents are compared against the
+//! files in `src/test/golden`. A missing golden file fails the test, running the tests with
+//! `SUBVT_PLOTTER_UPDATE_GOLDEN=1` records all of them after an intentional layout change.
+use std::path::PathBuf;
+
+mod bar;
+mod page;
+mod right_axis;
+mod validator_chart;
+
+pub(crate) fn assert_golden(name: &str, document: &svg::Document) {
+    let golden_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("test")
+        .join("golden");
+    let path = golden_dir.join(format!("{name}.svg"));
+    let actual = document.to_string();
+    if std::env::var("SUBVT_PLOTTER_UPDATE_GOLDEN").is_ok() {
+        std::fs::create_dir_all(&golden_dir).unwrap();
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Golden file {} is missing. Run the tests with SUBVT_PLOTTER_UPDATE_GOLDEN=1 to record it.",
+            path.display()
+        )
+    });
+    assert_eq!(
+        expected,
+        actual,
+        "Chart {name} does not match the golden file {}.",
+        path.display()
+    );
+}
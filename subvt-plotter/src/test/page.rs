@@ -0,0 +1,73 @@
+use super::assert_golden;
+use crate::get_pages_pdf;
+use crate::plotlib::{Chart, LineSeriesView, Page, ScaleLinear};
+
+fn get_chart_page(chart_count: usize) -> Page {
+    let x = ScaleLinear::new()
+        .set_domain(vec![0.0, 10.0])
+        .set_range(vec![0, 300]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, 10.0])
+        .set_range(vec![100, 0]);
+    let view = LineSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .load_data(&[(0.0, 1.0), (5.0, 7.0), (10.0, 4.0)])
+        .unwrap();
+    let chart = Chart::new()
+        .set_width(400)
+        .set_height(200)
+        .set_margins(20, 20, 60, 60)
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y);
+    let mut page = Page::new()
+        .set_width(600)
+        .set_height(800)
+        .set_margins(20, 20, 20, 20)
+        .set_gap(20)
+        .add_title("Page".to_string())
+        .add_text("First paragraph.\nSecond paragraph.".to_string(), 2);
+    for _ in 0..chart_count {
+        page = page.add_chart(&chart, 1);
+    }
+    page
+}
+
+#[test]
+fn test_page_golden() {
+    assert_golden(
+        "page_grid",
+        &get_chart_page(4)
+            .add_section_title("Section".to_string(), 2)
+            .to_svg_document()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn test_page_charts_keep_aspect_ratio() {
+    // 600 - 2 * 20 margin - 20 gap = 540, so each of the 2 columns is 270 wide
+    let document = get_chart_page(2).to_svg_document().unwrap().to_string();
+    assert!(document.contains("width=\"270\""));
+    assert!(document.contains("height=\"135\""));
+}
+
+#[test]
+fn test_page_overflow_errors() {
+    assert!(get_chart_page(20).to_svg_document().is_err());
+}
+
+#[test]
+fn test_pages_pdf() {
+    let pdf = get_pages_pdf(&[get_chart_page(2), get_chart_page(4)]).unwrap();
+    assert!(pdf.starts_with(b"%PDF-"));
+    let pdf = String::from_utf8_lossy(&pdf);
+    assert!(pdf.contains("/Count 2"));
+    assert_eq!(pdf.matches("/Type /Page\n").count(), 2);
+}
+
+#[test]
+fn test_pages_pdf_empty_errors() {
+    assert!(get_pages_pdf(&[]).is_err());
+}
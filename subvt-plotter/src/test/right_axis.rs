@@ -0,0 +1,67 @@
+use super::assert_golden;
+use crate::plotlib::{
+    AxisPosition, Chart, Color, LineSeriesView, ScaleBand, ScaleLinear, VerticalBarView,
+};
+
+#[test]
+fn test_right_axis_view_golden() {
+    let x = ScaleBand::new()
+        .set_domain(vec!["1".to_string(), "2".to_string(), "3".to_string()])
+        .set_range(vec![0, 300]);
+    let y_left = ScaleLinear::new()
+        .set_domain(vec![0.0, 100.0])
+        .set_range(vec![200, 0]);
+    let y_right = ScaleLinear::new()
+        .set_domain(vec![0.0, 10.0])
+        .set_range(vec![200, 0]);
+    let bar_view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y_left)
+        .set_custom_data_label("Stake".to_string())
+        .load_data(&[("1", 50.0), ("2", 70.0), ("3", 60.0)])
+        .unwrap();
+    let line_view = LineSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y_right)
+        .set_colors(vec![Color::from_hex("#ff7f0e")])
+        .set_custom_data_label("Nominators".to_string())
+        .load_data(&[
+            ("1".to_string(), 3.0),
+            ("2".to_string(), 5.0),
+            ("3".to_string(), 8.0),
+        ])
+        .unwrap();
+    let chart = Chart::new()
+        .set_width(460)
+        .set_height(300)
+        .set_margins(20, 100, 60, 60)
+        .add_view(&bar_view)
+        .add_right_axis_view(&line_view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y_left)
+        .add_axis_right(&y_right)
+        .add_legend_at(AxisPosition::Bottom);
+    let document = chart.to_svg_document().unwrap();
+    assert!(document.to_string().contains("Nominators (right axis)"));
+    assert_golden("right_axis_view", &document);
+}
+
+#[test]
+fn test_right_axis_view_without_right_axis() {
+    let x = ScaleBand::new()
+        .set_domain(vec!["1".to_string()])
+        .set_range(vec![0, 100]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0.0, 10.0])
+        .set_range(vec![100, 0]);
+    let line_view = LineSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .load_data(&[("1".to_string(), 3.0)])
+        .unwrap();
+    let result = Chart::new()
+        .add_right_axis_view(&line_view)
+        .add_axis_bottom(&x)
+        .to_svg_document();
+    assert!(result.is_err());
+}
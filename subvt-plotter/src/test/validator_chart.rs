@@ -0,0 +1,46 @@
+//! Empty data handling of the validator charts.
+use crate::validator_chart::plot_validator_chart;
+use crate::PlotterError;
+use std::path::PathBuf;
+use subvt_types::report::{ChartImageFormat, ValidatorChartType, ValidatorEraChartData};
+
+fn is_empty_data_error(result: anyhow::Result<PathBuf>) -> bool {
+    match result {
+        Ok(_) => false,
+        Err(error) => matches!(
+            error.downcast_ref::<PlotterError>(),
+            Some(PlotterError::EmptyData)
+        ),
+    }
+}
+
+#[test]
+fn test_validator_chart_empty_data() {
+    for chart_type in [
+        ValidatorChartType::StakeComposition,
+        ValidatorChartType::EraPoints,
+        ValidatorChartType::ParaVotes,
+        ValidatorChartType::NominatorCount,
+    ] {
+        assert!(is_empty_data_error(plot_validator_chart(
+            chart_type,
+            "address",
+            &[],
+            &[],
+            ChartImageFormat::Svg,
+        )));
+    }
+    // the era points chart needs an active era, the active set medians aren't enough
+    let inactive_era_data = vec![ValidatorEraChartData {
+        era_index: 1,
+        active_set_median_reward_points: Some(1_000),
+        ..Default::default()
+    }];
+    assert!(is_empty_data_error(plot_validator_chart(
+        ValidatorChartType::EraPoints,
+        "address",
+        &inactive_era_data,
+        &[],
+        ChartImageFormat::Svg,
+    )));
+}
@@ -0,0 +1,41 @@
+//! Validator charts by chart type, shared by the report service and the Telegram bot.
+use crate::{era_points, para_votes, stake};
+use std::path::PathBuf;
+use subvt_types::report::{
+    ChartImageFormat, ValidatorChartType, ValidatorEraChartData, ValidatorSessionParaVotesChartData,
+};
+
+/// Plots the chart of the given type for the validator with the given display address, and saves
+/// it in the given format to the temporary directory. The para votes chart is plotted from the
+/// session data and the other charts from the era data. Fails with `PlotterError::EmptyData` if
+/// there's nothing to plot.
+pub fn plot_validator_chart(
+    chart_type: ValidatorChartType,
+    address: &str,
+    era_chart_data: &[ValidatorEraChartData],
+    para_votes_chart_data: &[ValidatorSessionParaVotesChartData],
+    format: ChartImageFormat,
+) -> anyhow::Result<PathBuf> {
+    match chart_type {
+        ValidatorChartType::StakeComposition => stake::plot_stake_composition(
+            &format!("Stake Composition for {address}"),
+            era_chart_data,
+            format,
+        ),
+        ValidatorChartType::EraPoints => era_points::plot_era_points(
+            &format!("Era Points for {address}"),
+            era_chart_data,
+            format,
+        ),
+        ValidatorChartType::ParaVotes => para_votes::plot_para_votes(
+            &format!("Para Votes for {address}"),
+            para_votes_chart_data,
+            format,
+        ),
+        ValidatorChartType::NominatorCount => stake::plot_nominator_count(
+            &format!("Active Nominators for {address}"),
+            era_chart_data,
+            format,
+        ),
+    }
+}
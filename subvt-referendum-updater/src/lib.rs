@@ -1,12 +1,25 @@
+//! Reads the OpenGov referenda from the `Referenda` pallet storage at the last finalized block,
+//! evaluates their tallies against the approval and support curves of their tracks, and persists
+//! them to the network PostgreSQL database. Polkassembly is used only to fetch the titles and the
+//! descriptions of the referenda, when enabled in the configuration. The proposal calls of the
+//! ongoing referenda are fetched from the `Preimage` pallet, and persisted as decoded call trees
+//! along with the treasury spends in them. Referenda of the `FellowshipReferenda` pallet are
+//! persisted too on the chains that have it.
 #![warn(clippy::disallowed_types)]
 use async_trait::async_trait;
-use enum_iterator::all;
 use lazy_static::lazy_static;
+use rustc_hash::FxHashMap as HashMap;
 use subvt_config::Config;
-use subvt_governance::polkassembly::fetch_track_referenda;
+use subvt_governance::polkassembly::fetch_referendum_details;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
-use subvt_types::governance::track::Track;
+use subvt_substrate_client::SubstrateClient;
+use subvt_types::governance::referendum::{
+    OngoingReferendum, Referendum, ReferendumInfo, TrackInfo,
+};
+use subvt_types::governance::track::FellowshipTrack;
+use subvt_types::governance::treasury::get_referendum_treasury_spends;
+use subvt_types::substrate::Balance;
 
 mod metrics;
 
@@ -17,6 +30,277 @@ lazy_static! {
 #[derive(Default)]
 pub struct ReferendumUpdater {}
 
+impl ReferendumUpdater {
+    async fn update_referenda(
+        substrate_client: &SubstrateClient,
+        postgres: &PostgreSQLNetworkStorage,
+    ) -> anyhow::Result<()> {
+        let block_hash = substrate_client.get_finalized_block_hash().await?;
+        let block_number = substrate_client
+            .get_block_header(&block_hash)
+            .await?
+            .get_number()? as u32;
+        log::info!("Fetch referenda at block #{}.", block_number);
+        let tracks: HashMap<u16, TrackInfo> = substrate_client
+            .get_referendum_tracks()?
+            .into_iter()
+            .collect();
+        let mut track_queues: HashMap<u16, Vec<u32>> = HashMap::default();
+        for track_id in tracks.keys() {
+            track_queues.insert(
+                *track_id,
+                substrate_client
+                    .get_referendum_track_queue(*track_id, &block_hash)
+                    .await?,
+            );
+        }
+        let active_issuance = substrate_client.get_active_issuance(&block_hash).await?;
+        let referenda = substrate_client.get_referenda(&block_hash).await?;
+        log::info!(
+            "Fetched {} referenda in {} tracks.",
+            referenda.len(),
+            tracks.len()
+        );
+        let mut ongoing_referendum_count = 0;
+        for (index, info) in &referenda {
+            let maybe_track_id = match info {
+                ReferendumInfo::Ongoing(referendum) => Some(referendum.track_id),
+                _ => None,
+            };
+            let referendum = Referendum::new(
+                *index,
+                info,
+                maybe_track_id.and_then(|track_id| tracks.get(&track_id)),
+                maybe_track_id
+                    .and_then(|track_id| track_queues.get(&track_id))
+                    .map(|queue| queue.as_slice())
+                    .unwrap_or_default(),
+                active_issuance,
+                block_number,
+            );
+            if referendum.is_ongoing() {
+                ongoing_referendum_count += 1;
+            }
+            postgres
+                .save_or_update_onchain_referendum(&referendum)
+                .await?;
+            if let ReferendumInfo::Ongoing(ongoing_referendum) = info {
+                Self::update_referendum_proposal(
+                    substrate_client,
+                    postgres,
+                    *index,
+                    ongoing_referendum,
+                    &block_hash,
+                )
+                .await?;
+            }
+        }
+        metrics::ongoing_referendum_count().set(ongoing_referendum_count);
+        log::info!(
+            "Persisted {} referenda, {} ongoing.",
+            referenda.len(),
+            ongoing_referendum_count
+        );
+        if substrate_client.has_fellowship_referenda() {
+            Self::update_fellowship_referenda(
+                substrate_client,
+                postgres,
+                &block_hash,
+                block_number,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Support of the Fellowship referenda is evaluated against the number of the Fellowship
+    /// members at or above the minimum rank of the track.
+    async fn update_fellowship_referenda(
+        substrate_client: &SubstrateClient,
+        postgres: &PostgreSQLNetworkStorage,
+        block_hash: &str,
+        block_number: u32,
+    ) -> anyhow::Result<()> {
+        let tracks: HashMap<u16, TrackInfo> = substrate_client
+            .get_fellowship_referendum_tracks()?
+            .into_iter()
+            .collect();
+        let mut track_queues: HashMap<u16, Vec<u32>> = HashMap::default();
+        let mut track_member_counts: HashMap<u16, u32> = HashMap::default();
+        for track_id in tracks.keys() {
+            track_queues.insert(
+                *track_id,
+                substrate_client
+                    .get_fellowship_referendum_track_queue(*track_id, block_hash)
+                    .await?,
+            );
+            match FellowshipTrack::from_id(*track_id) {
+                Some(track) => {
+                    track_member_counts.insert(
+                        *track_id,
+                        substrate_client
+                            .get_fellowship_member_count(track.min_rank(), block_hash)
+                            .await?,
+                    );
+                }
+                None => log::warn!("Unknown Fellowship track #{}.", track_id),
+            }
+        }
+        let referenda = substrate_client
+            .get_fellowship_referenda(block_hash)
+            .await?;
+        log::info!(
+            "Fetched {} Fellowship referenda in {} tracks.",
+            referenda.len(),
+            tracks.len()
+        );
+        let mut ongoing_referendum_count = 0;
+        for (index, info) in &referenda {
+            let maybe_track_id = match info {
+                ReferendumInfo::Ongoing(referendum) => Some(referendum.track_id),
+                _ => None,
+            };
+            let referendum = Referendum::new(
+                *index,
+                info,
+                maybe_track_id.and_then(|track_id| tracks.get(&track_id)),
+                maybe_track_id
+                    .and_then(|track_id| track_queues.get(&track_id))
+                    .map(|queue| queue.as_slice())
+                    .unwrap_or_default(),
+                maybe_track_id
+                    .and_then(|track_id| track_member_counts.get(&track_id))
+                    .map(|member_count| *member_count as Balance)
+                    .unwrap_or_default(),
+                block_number,
+            );
+            if referendum.is_ongoing() {
+                ongoing_referendum_count += 1;
+            }
+            postgres
+                .save_or_update_onchain_fellowship_referendum(&referendum)
+                .await?;
+        }
+        metrics::ongoing_fellowship_referendum_count().set(ongoing_referendum_count);
+        log::info!(
+            "Persisted {} Fellowship referenda, {} ongoing.",
+            referenda.len(),
+            ongoing_referendum_count
+        );
+        Ok(())
+    }
+
+    /// Fetches the inline call or the preimage of the ongoing referendum's proposal, and saves
+    /// its decoded call tree and the treasury spends in it. Preimages aren't kept on chain after
+    /// the referendum is finished, so the call and the spends saved while the referendum was
+    /// ongoing are kept. Unavailable or undecodable proposal calls are logged and skipped.
+    async fn update_referendum_proposal(
+        substrate_client: &SubstrateClient,
+        postgres: &PostgreSQLNetworkStorage,
+        index: u32,
+        referendum: &OngoingReferendum,
+        block_hash: &str,
+    ) -> anyhow::Result<()> {
+        let bytes = match substrate_client
+            .get_referendum_proposal_call_bytes(referendum, block_hash)
+            .await
+        {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => {
+                log::debug!("Proposal call of referendum #{} is not available.", index);
+                return Ok(());
+            }
+            Err(error) => {
+                log::warn!(
+                    "Cannot fetch the proposal call of referendum #{}: {:?}",
+                    index,
+                    error
+                );
+                return Ok(());
+            }
+        };
+        match substrate_client.decode_call_tree(&bytes) {
+            Ok(call_tree) => {
+                postgres
+                    .update_referendum_proposal_call(index, &call_tree)
+                    .await?;
+            }
+            Err(error) => {
+                log::warn!(
+                    "Cannot decode the proposal call tree of referendum #{}: {:?}",
+                    index,
+                    error
+                );
+            }
+        }
+        let call = match substrate_client.decode_call(&bytes) {
+            Ok(call) => call,
+            Err(error) => {
+                log::warn!(
+                    "Cannot decode the proposal call of referendum #{}: {:?}",
+                    index,
+                    error
+                );
+                return Ok(());
+            }
+        };
+        let spends = get_referendum_treasury_spends(index, &call);
+        if !spends.is_empty() {
+            log::debug!(
+                "Referendum #{} proposes {} treasury spends.",
+                index,
+                spends.len()
+            );
+        }
+        postgres
+            .save_referendum_treasury_spends(index, &spends)
+            .await
+    }
+
+    /// Polkassembly is optional, errors are logged and skipped. Failed fetches and fetches
+    /// without a title are retried with an exponential backoff.
+    async fn enrich_referenda(postgres: &PostgreSQLNetworkStorage) -> anyhow::Result<()> {
+        let referendum_ids = postgres
+            .get_referendum_ids_to_enrich(
+                CONFIG.referendum_updater.refresh_seconds,
+                CONFIG.referendum_updater.enrich_max_backoff_seconds,
+            )
+            .await?;
+        log::info!(
+            "Fetch Polkassembly details of {} referenda.",
+            referendum_ids.len()
+        );
+        for referendum_id in referendum_ids {
+            let is_successful = match fetch_referendum_details(referendum_id).await {
+                Ok(details) => {
+                    postgres
+                        .update_referendum_polkassembly_details(&details)
+                        .await?;
+                    if details.maybe_title.is_none() {
+                        log::debug!(
+                            "Polkassembly details of referendum #{} have no title.",
+                            referendum_id
+                        );
+                    }
+                    details.maybe_title.is_some()
+                }
+                Err(error) => {
+                    log::warn!(
+                        "Cannot fetch Polkassembly details of referendum #{}: {:?}",
+                        referendum_id,
+                        error
+                    );
+                    false
+                }
+            };
+            postgres
+                .save_referendum_polkassembly_fetch(referendum_id, is_successful)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
 #[async_trait(?Send)]
 impl Service for ReferendumUpdater {
     fn get_metrics_server_addr() -> (&'static str, u16) {
@@ -33,34 +317,13 @@ impl Service for ReferendumUpdater {
         );
         let postgres =
             PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?;
-        let tracks = all::<Track>().collect::<Vec<_>>();
-
-        let _ = postgres.get_open_referenda(None).await?;
         loop {
             metrics::last_run_timestamp_ms().set(chrono::Utc::now().timestamp_millis());
-            for track in &tracks {
-                log::info!("Fetch {} referenda.", track.name());
-                let mut page = 1;
-                let limit = 100;
-                loop {
-                    match fetch_track_referenda(track.id(), page, limit).await {
-                        Ok(referenda) => {
-                            log::info!("{} referenda in {}.", referenda.len(), track.name());
-                            for referendum in &referenda {
-                                postgres.save_or_update_referendum(referendum).await?;
-                                log::info!("Persisted #{}", referendum.post_id);
-                            }
-                            if referenda.is_empty() {
-                                break;
-                            }
-                            page += 1;
-                        }
-                        Err(error) => {
-                            log::error!("Error while fetching referenda: {:?}", error);
-                            break;
-                        }
-                    }
-                }
+            // new client every run to get the latest metadata, i.e. track definitions
+            let substrate_client = SubstrateClient::new(&CONFIG).await?;
+            Self::update_referenda(&substrate_client, &postgres).await?;
+            if CONFIG.referendum_updater.enrich_from_polkassembly {
+                Self::enrich_referenda(&postgres).await?;
             }
             log::info!(
                 "Sleep for {} seconds.",
@@ -14,3 +14,27 @@ pub fn last_run_timestamp_ms() -> IntGauge {
     });
     METER.clone()
 }
+
+pub fn ongoing_referendum_count() -> IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge(
+            METRIC_PREFIX,
+            "ongoing_referendum_count",
+            "Number of ongoing referenda at the last run",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn ongoing_fellowship_referendum_count() -> IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge(
+            METRIC_PREFIX,
+            "ongoing_fellowship_referendum_count",
+            "Number of ongoing Fellowship referenda at the last run",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
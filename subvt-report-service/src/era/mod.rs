@@ -4,9 +4,12 @@ use serde::Deserialize;
 use std::str::FromStr;
 use subvt_types::crypto::AccountId;
 use subvt_types::err::ServiceError;
-use subvt_types::report::{EraValidatorListReport, EraValidatorReport};
+use subvt_types::report::{EraReport, EraValidatorListReport, EraValidatorReport};
+use subvt_types::substrate::Era;
+use utoipa::IntoParams;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub(crate) struct EraValidatorListReportPathParameters {
     era_index: u32,
 }
@@ -48,6 +51,16 @@ async fn get_era_validator_list_report(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/report/era/{era_index}/validator/active",
+    tag = "era",
+    params(EraValidatorListReportPathParameters),
+    responses(
+        (status = 200, description = "Reports of the active validators of the era.", body = EraValidatorListReport),
+        (status = 404, description = "Era not found.", body = ServiceError),
+    ),
+)]
 #[get("/report/era/{era_index}/validator/active")]
 pub(crate) async fn era_active_validator_list_report_service(
     path: web::Path<EraValidatorListReportPathParameters>,
@@ -56,6 +69,16 @@ pub(crate) async fn era_active_validator_list_report_service(
     get_era_validator_list_report(data, path.era_index, true).await
 }
 
+#[utoipa::path(
+    get,
+    path = "/report/era/{era_index}/validator/inactive",
+    tag = "era",
+    params(EraValidatorListReportPathParameters),
+    responses(
+        (status = 200, description = "Reports of the inactive validators of the era.", body = EraValidatorListReport),
+        (status = 404, description = "Era not found.", body = ServiceError),
+    ),
+)]
 #[get("/report/era/{era_index}/validator/inactive")]
 pub(crate) async fn era_inactive_validator_list_report_service(
     path: web::Path<EraValidatorListReportPathParameters>,
@@ -64,21 +87,33 @@ pub(crate) async fn era_inactive_validator_list_report_service(
     get_era_validator_list_report(data, path.era_index, false).await
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub(crate) struct ValidatorReportPathParameters {
     account_id_hex_string: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct EraReportQueryParameters {
     start_era_index: u32,
     /// Report will be generated for a single era when this parameter is omitted.
-    #[serde(rename(deserialize = "end_era_index"))]
+    #[serde(rename = "end_era_index")]
     maybe_end_era_index: Option<u32>,
 }
 
 /// Gets the report for a certain validator in a range of eras, or a single era.
 /// See `EraValidatorReport` struct in the `subvt-types` for details.
+#[utoipa::path(
+    get,
+    path = "/report/validator/{account_id_hex_string}",
+    tag = "era",
+    params(ValidatorReportPathParameters, EraReportQueryParameters),
+    responses(
+        (status = 200, description = "Era reports of the validator.", body = [EraValidatorReport]),
+        (status = 400, description = "Invalid account id or era range.", body = ServiceError),
+    ),
+)]
 #[get("/report/validator/{account_id_hex_string}")]
 pub(crate) async fn era_validator_report_service(
     path: web::Path<ValidatorReportPathParameters>,
@@ -118,6 +153,15 @@ pub(crate) async fn era_validator_report_service(
 }
 
 /// Gets the current era.
+#[utoipa::path(
+    get,
+    path = "/era/current",
+    tag = "era",
+    responses(
+        (status = 200, description = "Current era.", body = Era),
+        (status = 404, description = "Current era not found.", body = ServiceError),
+    ),
+)]
 #[get("/era/current")]
 pub(crate) async fn current_era_service(data: web::Data<ServiceState>) -> ResultResponse {
     if let Some(era) = data.postgres.get_current_era().await? {
@@ -130,6 +174,12 @@ pub(crate) async fn current_era_service(data: web::Data<ServiceState>) -> Result
 }
 
 /// Gets all eras.
+#[utoipa::path(
+    get,
+    path = "/era",
+    tag = "era",
+    responses((status = 200, description = "All eras.", body = [Era])),
+)]
 #[get("/era")]
 pub(crate) async fn all_eras_service(data: web::Data<ServiceState>) -> ResultResponse {
     Ok(HttpResponse::Ok().json(data.postgres.get_all_eras().await?))
@@ -137,6 +187,16 @@ pub(crate) async fn all_eras_service(data: web::Data<ServiceState>) -> ResultRes
 
 /// Gets the report for a range of eras, or a single era.
 /// See `EraReport` struct in the `subvt-types` definition for details.
+#[utoipa::path(
+    get,
+    path = "/report/era",
+    tag = "era",
+    params(EraReportQueryParameters),
+    responses(
+        (status = 200, description = "Era reports.", body = [EraReport]),
+        (status = 400, description = "Invalid era range.", body = ServiceError),
+    ),
+)]
 #[get("/report/era")]
 pub(crate) async fn era_report_service(
     query: web::Query<EraReportQueryParameters>,
@@ -0,0 +1,283 @@
+use crate::util::validate_account_id_param;
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use subvt_types::app::event::conviction_voting::ConvictionVotingDelegation;
+use subvt_types::err::ServiceError;
+use subvt_types::report::{
+    ConvictionVotingReport, DelegateReport, DelegateTrackReport, TrackTreasurySpendReport,
+    TreasuryBeneficiaryReport,
+};
+use utoipa::IntoParams;
+
+const DEFAULT_EVENT_LIMIT: u32 = 100;
+const MAX_EVENT_LIMIT: u32 = 1000;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub(crate) struct AccountIdPathParameter {
+    ss58_address_or_account_id: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct EventLimitQueryParameter {
+    /// Maximum number of events of each type. Defaults to 100, cannot be more than 1000.
+    limit: Option<u32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub(crate) struct TrackIdPathParameter {
+    track_id: u16,
+}
+
+fn validate_limit_param(query: &EventLimitQueryParameter) -> Result<u32, HttpResponse> {
+    let limit = query.limit.unwrap_or(DEFAULT_EVENT_LIMIT);
+    if limit == 0 || limit > MAX_EVENT_LIMIT {
+        return Err(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Limit should be between 1 and {MAX_EVENT_LIMIT}."
+        ))));
+    }
+    Ok(limit)
+}
+
+/// Groups the delegations, which are ordered by track, into per-track totals.
+fn get_delegate_track_reports(
+    delegations: Vec<ConvictionVotingDelegation>,
+) -> Vec<DelegateTrackReport> {
+    let mut tracks: Vec<DelegateTrackReport> = Vec::new();
+    for delegation in delegations {
+        if tracks.last().map(|track| track.track_id) != Some(delegation.track_id) {
+            tracks.push(DelegateTrackReport {
+                track_id: delegation.track_id,
+                delegator_count: 0,
+                capital: 0,
+                votes: 0,
+                delegations: Vec::new(),
+            });
+        }
+        let track = tracks.last_mut().unwrap();
+        track.delegator_count += 1;
+        track.capital = track.capital.saturating_add(delegation.balance);
+        track.votes = track.votes.saturating_add(delegation.votes);
+        track.delegations.push(delegation);
+    }
+    tracks
+}
+
+#[utoipa::path(
+    get,
+    path = "/governance/{ss58_address_or_account_id}/conviction-voting",
+    tag = "governance",
+    params(AccountIdPathParameter, EventLimitQueryParameter),
+    responses(
+        (status = 200, description = "OpenGov votes and delegations of the account.", body = ConvictionVotingReport),
+        (status = 400, description = "Invalid address, account id or limit.", body = ServiceError),
+    ),
+)]
+#[get("/governance/{ss58_address_or_account_id}/conviction-voting")]
+pub(crate) async fn conviction_voting_service(
+    path: web::Path<AccountIdPathParameter>,
+    query: web::Query<EventLimitQueryParameter>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let limit = match validate_limit_param(&query) {
+        Ok(limit) => limit,
+        Err(response) => return Ok(response),
+    };
+    Ok(HttpResponse::Ok().json(ConvictionVotingReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        votes: data
+            .postgres
+            .get_conviction_voting_voted_events_for_account(&account_id, limit)
+            .await?,
+        removed_votes: data
+            .postgres
+            .get_conviction_voting_vote_removed_events_for_account(&account_id, limit)
+            .await?,
+        delegations: data
+            .postgres
+            .get_conviction_voting_delegated_events_for_account(&account_id, limit)
+            .await?,
+        undelegations: data
+            .postgres
+            .get_conviction_voting_undelegated_events_for_account(&account_id, limit)
+            .await?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/governance/{ss58_address_or_account_id}/delegate",
+    tag = "governance",
+    params(AccountIdPathParameter, EventLimitQueryParameter),
+    responses(
+        (status = 200, description = "Current delegations to the account per track, and the history of its delegations and undelegations.", body = DelegateReport),
+        (status = 400, description = "Invalid address, account id or limit.", body = ServiceError),
+    ),
+)]
+#[get("/governance/{ss58_address_or_account_id}/delegate")]
+pub(crate) async fn delegate_service(
+    path: web::Path<AccountIdPathParameter>,
+    query: web::Query<EventLimitQueryParameter>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let limit = match validate_limit_param(&query) {
+        Ok(limit) => limit,
+        Err(response) => return Ok(response),
+    };
+    Ok(HttpResponse::Ok().json(DelegateReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        tracks: get_delegate_track_reports(
+            data.postgres
+                .get_conviction_voting_delegations_to(&account_id)
+                .await?,
+        ),
+        delegated: data
+            .postgres
+            .get_conviction_voting_delegated_events_for_delegate(&account_id, limit)
+            .await?,
+        undelegated: data
+            .postgres
+            .get_conviction_voting_undelegated_events_for_delegate(&account_id, limit)
+            .await?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/governance/{ss58_address_or_account_id}/treasury-spend",
+    tag = "governance",
+    params(AccountIdPathParameter, EventLimitQueryParameter),
+    responses(
+        (status = 200, description = "Treasury payments granted to the account, and the referenda that request treasury spends for the account.", body = TreasuryBeneficiaryReport),
+        (status = 400, description = "Invalid address, account id or limit.", body = ServiceError),
+    ),
+)]
+#[get("/governance/{ss58_address_or_account_id}/treasury-spend")]
+pub(crate) async fn beneficiary_treasury_spend_service(
+    path: web::Path<AccountIdPathParameter>,
+    query: web::Query<EventLimitQueryParameter>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let limit = match validate_limit_param(&query) {
+        Ok(limit) => limit,
+        Err(response) => return Ok(response),
+    };
+    Ok(HttpResponse::Ok().json(TreasuryBeneficiaryReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        spends: data
+            .postgres
+            .get_treasury_spend_events_for_beneficiary(&account_id, limit)
+            .await?,
+        referenda: data
+            .postgres
+            .get_referendum_treasury_spend_reports_for_beneficiary(&account_id, limit)
+            .await?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/governance/track/{track_id}/treasury-spend",
+    tag = "governance",
+    params(TrackIdPathParameter, EventLimitQueryParameter),
+    responses(
+        (status = 200, description = "Referenda of the track that request treasury spends, along with the decoded spends.", body = TrackTreasurySpendReport),
+        (status = 400, description = "Invalid track id or limit.", body = ServiceError),
+    ),
+)]
+#[get("/governance/track/{track_id}/treasury-spend")]
+pub(crate) async fn track_treasury_spend_service(
+    path: web::Path<TrackIdPathParameter>,
+    query: web::Query<EventLimitQueryParameter>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let track_id = path.into_inner().track_id;
+    let limit = match validate_limit_param(&query) {
+        Ok(limit) => limit,
+        Err(response) => return Ok(response),
+    };
+    Ok(HttpResponse::Ok().json(TrackTreasurySpendReport {
+        track_id,
+        referenda: data
+            .postgres
+            .get_referendum_treasury_spend_reports_for_track(track_id, limit)
+            .await?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_delegate_track_reports;
+    use subvt_types::app::event::conviction_voting::ConvictionVotingDelegation;
+    use subvt_types::crypto::AccountId;
+    use subvt_types::substrate::Balance;
+
+    fn get_delegation(
+        delegator_index: u8,
+        track_id: u16,
+        balance: Balance,
+        votes: Balance,
+    ) -> ConvictionVotingDelegation {
+        ConvictionVotingDelegation {
+            delegator_account_id: AccountId::new([delegator_index; 32]),
+            delegate_account_id: AccountId::new([0; 32]),
+            track_id,
+            conviction: 1,
+            balance,
+            votes,
+            block_number: 1,
+        }
+    }
+
+    #[test]
+    fn test_delegate_track_reports() {
+        let tracks = get_delegate_track_reports(vec![
+            get_delegation(1, 0, 100, 100),
+            get_delegation(2, 0, 50, 300),
+            get_delegation(1, 2, 10, 1),
+            get_delegation(3, 11, Balance::MAX, Balance::MAX),
+            get_delegation(4, 11, 1, 1),
+        ]);
+        assert_eq!(tracks.len(), 3);
+        assert_eq!(tracks[0].track_id, 0);
+        assert_eq!(tracks[0].delegator_count, 2);
+        assert_eq!(tracks[0].capital, 150);
+        assert_eq!(tracks[0].votes, 400);
+        assert_eq!(tracks[0].delegations.len(), 2);
+        assert_eq!(tracks[1].track_id, 2);
+        assert_eq!(tracks[1].delegator_count, 1);
+        assert_eq!(tracks[1].capital, 10);
+        assert_eq!(tracks[1].votes, 1);
+        // totals saturate instead of overflowing
+        assert_eq!(tracks[2].track_id, 11);
+        assert_eq!(tracks[2].delegator_count, 2);
+        assert_eq!(tracks[2].capital, Balance::MAX);
+        assert_eq!(tracks[2].votes, Balance::MAX);
+    }
+
+    #[test]
+    fn test_delegate_track_reports_empty() {
+        assert!(get_delegate_track_reports(Vec::new()).is_empty());
+    }
+}
@@ -1,4 +1,5 @@
-//!  Public reporting REST services.
+//!  Public reporting REST services. The OpenAPI specification of the services is served at
+//! `/openapi.json`.
 #![warn(clippy::disallowed_types)]
 use actix_web::dev::Service as _;
 use actix_web::{web, App, HttpResponse, HttpServer};
@@ -12,6 +13,7 @@ use std::sync::{Arc, RwLock};
 use subvt_config::Config;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_persistence::redis::Redis;
+use subvt_service_common::rate_limit::{http::RateLimitFactory, RateLimiter};
 use subvt_service_common::{err::InternalServerError, Service};
 use subvt_substrate_client::SubstrateClient;
 use subvt_types::crypto::AccountId;
@@ -20,10 +22,14 @@ use subvt_types::substrate::Account;
 use subvt_types::subvt::ValidatorSummary;
 
 mod era;
+mod governance;
 mod metrics;
+mod nomination;
 mod onekv;
+pub mod openapi;
 mod session;
 mod staking;
+mod telemetry;
 pub(crate) mod util;
 mod validator;
 
@@ -33,6 +39,25 @@ lazy_static! {
 
 pub(crate) type ResultResponse = Result<HttpResponse, InternalServerError>;
 
+/// Rate limiting costs of the expensive routes. Other routes cost 1.
+const ROUTE_COSTS: &[(&str, u32)] = &[
+    ("/report/era/{era_index}/validator/active", 5),
+    ("/report/era/{era_index}/validator/inactive", 5),
+    ("/report/validator/{account_id_hex_string}", 5),
+    ("/report/session/paras", 5),
+    ("/validator/list", 5),
+    ("/validator/list/active", 5),
+    ("/validator/list/inactive", 5),
+    ("/validator/search", 2),
+    ("/validator/reward/chart", 10),
+    (
+        "/validator/{ss58_address_or_account_id}/chart/{chart_type}",
+        10,
+    ),
+    ("/validator/{ss58_address_or_account_id}/report/monthly", 20),
+    ("/nomination/optimize", 20),
+];
+
 #[derive(Clone)]
 pub(crate) struct ServiceState {
     postgres: Arc<PostgreSQLNetworkStorage>,
@@ -65,6 +90,7 @@ impl Service for ReportService {
             PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?,
         );
         let redis = Arc::new(Redis::new()?);
+        let rate_limiter = RateLimiter::new(&CONFIG, "subvt_report_service").await?;
         let account_map = Arc::new(RwLock::new(HashMap::default()));
         let finalized_block_summary = Arc::new(RwLock::new(BlockSummary::default()));
         let active_validator_list = Arc::new(RwLock::new(Vec::new()));
@@ -184,6 +210,7 @@ impl Service for ReportService {
                     active_validator_list: active_validator_list.clone(),
                     inactive_validator_list: inactive_validator_list.clone(),
                 }))
+                .wrap(RateLimitFactory::new(rate_limiter.clone(), ROUTE_COSTS))
                 .wrap_fn(|request, service| {
                     metrics::request_counter().inc();
                     metrics::connection_count().inc();
@@ -227,8 +254,23 @@ impl Service for ReportService {
                 .service(validator::validator_era_rewards_service)
                 .service(validator::validator_era_payouts_service)
                 .service(validator::validator_reward_chart_service)
+                .service(validator::validator_history_service)
+                .service(validator::validator_chart_service)
+                .service(validator::validator_monthly_report_service)
+                .service(validator::telemetry::validator_telemetry_node_service)
+                .service(validator::telemetry::validator_telemetry_history_service)
+                .service(validator::telemetry::validator_telemetry_version_service)
+                .service(validator::telemetry::validator_telemetry_finality_service)
+                .service(telemetry::telemetry_version_distribution_service)
+                .service(telemetry::telemetry_node_version_service)
                 .service(staking::controller_service)
                 .service(staking::bond_service)
+                .service(nomination::nomination_optimization_service)
+                .service(governance::conviction_voting_service)
+                .service(governance::delegate_service)
+                .service(governance::beneficiary_treasury_spend_service)
+                .service(governance::track_treasury_spend_service)
+                .service(openapi::get_openapi_spec)
         })
         .workers(10)
         .disable_signals()
@@ -0,0 +1,445 @@
+//! Nomination optimizer. Selects a set of validators for a nominator's stake that maximizes
+//! the expected return, subject to the nominator's constraints.
+use crate::{ResultResponse, ServiceState, CONFIG};
+use actix_web::{post, web, HttpResponse};
+use rustc_hash::FxHashMap as HashMap;
+use subvt_types::crypto::AccountId;
+use subvt_types::err::ServiceError;
+use subvt_types::report::{
+    NominationOptimizationReport, NominationOptimizationRequest, NominationOptimizationValidator,
+};
+use subvt_types::substrate::metadata::get_metadata_constant;
+use subvt_types::substrate::Balance;
+use subvt_types::subvt::{ValidatorDetails, ValidatorSummary};
+
+/// Performance factor is clamped to this range (per billion) so that a single outlier era
+/// cannot dominate the expected return.
+const MIN_PERFORMANCE_FACTOR_PER_BILLION: u128 = 500_000_000;
+const MAX_PERFORMANCE_FACTOR_PER_BILLION: u128 = 1_500_000_000;
+
+fn satisfies_constraints(
+    validator: &ValidatorDetails,
+    request: &NominationOptimizationRequest,
+) -> bool {
+    if validator.preferences.blocks_nominations || validator.return_rate_per_billion.is_none() {
+        return false;
+    }
+    if let Some(max_commission_per_billion) = request.max_commission_per_billion {
+        if validator.preferences.commission_per_billion > max_commission_per_billion {
+            return false;
+        }
+    }
+    if let Some(min_self_stake) = request.min_self_stake {
+        if validator.self_stake.active_amount < min_self_stake {
+            return false;
+        }
+    }
+    if request.exclude_oversubscribed && validator.oversubscribed {
+        return false;
+    }
+    if request.identity_required && validator.account.get_full_display().is_none() {
+        return false;
+    }
+    if request.onekv_only && validator.onekv_candidate_record_id.is_none() {
+        return false;
+    }
+    true
+}
+
+/// Validators with a sub-identity are grouped under their parent account.
+fn get_identity_key(validator: &ValidatorDetails) -> Option<AccountId> {
+    if let Some(parent_account_id) = validator.account.parent_account_id {
+        Some(parent_account_id)
+    } else if validator.account.identity.is_some() {
+        Some(validator.account.id)
+    } else {
+        None
+    }
+}
+
+/// Expected annual return rate of the nominator's stake share on the validator. Starts from
+/// the validator's current return rate, scales it with the validator's era points performance
+/// against the network average and accounts for the dilution caused by the new stake.
+fn get_expected_return_rate_per_billion(
+    validator: &ValidatorDetails,
+    average_reward_points: Option<u64>,
+    network_average_reward_points: u64,
+    stake_share: Balance,
+) -> u32 {
+    let return_rate_per_billion = validator.return_rate_per_billion.unwrap_or(0) as u128;
+    let performance_factor_per_billion = match average_reward_points {
+        Some(average_reward_points) if network_average_reward_points > 0 => {
+            (average_reward_points as u128 * 1_000_000_000 / network_average_reward_points as u128)
+                .clamp(
+                    MIN_PERFORMANCE_FACTOR_PER_BILLION,
+                    MAX_PERFORMANCE_FACTOR_PER_BILLION,
+                )
+        }
+        _ => 1_000_000_000,
+    };
+    let total_stake = validator
+        .validator_stake
+        .as_ref()
+        .map(|validator_stake| validator_stake.total_stake)
+        .unwrap_or(0);
+    let return_rate_per_billion =
+        return_rate_per_billion * performance_factor_per_billion / 1_000_000_000;
+    let return_rate_per_billion = if total_stake > 0 {
+        return_rate_per_billion * total_stake / (total_stake + stake_share)
+    } else {
+        return_rate_per_billion
+    };
+    return_rate_per_billion as u32
+}
+
+/// Greedy selection by expected return, skipping validators that would break the identity or
+/// location diversity limits.
+fn select_validators(
+    candidates: Vec<NominationOptimizationValidator>,
+    identity_keys: &HashMap<AccountId, Option<AccountId>>,
+    request: &NominationOptimizationRequest,
+    max_nominations: usize,
+) -> Vec<NominationOptimizationValidator> {
+    let mut candidates = candidates;
+    candidates.sort_by(|a, b| {
+        b.expected_return_rate_per_billion
+            .cmp(&a.expected_return_rate_per_billion)
+            .then(b.active_era_count.cmp(&a.active_era_count))
+    });
+    let mut identity_counts: HashMap<AccountId, u32> = HashMap::default();
+    let mut location_counts: HashMap<String, u32> = HashMap::default();
+    let mut selected = Vec::new();
+    for candidate in candidates {
+        if selected.len() >= max_nominations {
+            break;
+        }
+        let maybe_identity_key = identity_keys
+            .get(&candidate.validator_summary.account_id)
+            .cloned()
+            .flatten();
+        if let (Some(max_per_identity), Some(identity_key)) =
+            (request.max_per_identity, &maybe_identity_key)
+        {
+            if *identity_counts.get(identity_key).unwrap_or(&0) >= max_per_identity {
+                continue;
+            }
+        }
+        if let (Some(max_per_location), Some(location)) =
+            (request.max_per_location, &candidate.location)
+        {
+            if *location_counts.get(location).unwrap_or(&0) >= max_per_location {
+                continue;
+            }
+        }
+        if let Some(identity_key) = maybe_identity_key {
+            *identity_counts.entry(identity_key).or_insert(0) += 1;
+        }
+        if let Some(location) = &candidate.location {
+            *location_counts.entry(location.clone()).or_insert(0) += 1;
+        }
+        selected.push(candidate);
+    }
+    selected
+}
+
+/// Sets the expected return rates of the selected validators for the stake split evenly between
+/// them, since the constraints may leave fewer validators than the maximum number of nominations,
+/// then orders them by the updated rates.
+fn set_selected_stake_share(
+    selected: &mut [NominationOptimizationValidator],
+    validator_map: &HashMap<AccountId, &ValidatorDetails>,
+    performance_map: &HashMap<AccountId, (u32, u64)>,
+    network_average_reward_points: u64,
+    stake: Balance,
+) {
+    if selected.is_empty() {
+        return;
+    }
+    let stake_share = stake / selected.len() as Balance;
+    for candidate in selected.iter_mut() {
+        let account_id = candidate.validator_summary.account_id;
+        if let Some(validator) = validator_map.get(&account_id) {
+            candidate.expected_return_rate_per_billion = get_expected_return_rate_per_billion(
+                validator,
+                performance_map
+                    .get(&account_id)
+                    .map(|(_, average_reward_points)| *average_reward_points),
+                network_average_reward_points,
+                stake_share,
+            );
+        }
+    }
+    selected.sort_by(|a, b| {
+        b.expected_return_rate_per_billion
+            .cmp(&a.expected_return_rate_per_billion)
+            .then(b.active_era_count.cmp(&a.active_era_count))
+    });
+}
+
+/// Recommends up to the maximum number of nominations for the posted stake and constraints.
+/// Only active validators are evaluated, since waiting validators have no current return rate.
+#[utoipa::path(
+    post,
+    path = "/nomination/optimize",
+    tag = "nomination",
+    request_body = NominationOptimizationRequest,
+    responses(
+        (status = 200, description = "Recommended nominations.", body = NominationOptimizationReport),
+        (status = 400, description = "Invalid stake or nomination count.", body = ServiceError),
+    ),
+)]
+#[post("/nomination/optimize")]
+pub(crate) async fn nomination_optimization_service(
+    input: web::Json<NominationOptimizationRequest>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let request = input.into_inner();
+    if request.stake == 0 {
+        return Ok(
+            HttpResponse::BadRequest().json(ServiceError::from("Stake should be greater than 0."))
+        );
+    }
+    let runtime_max_nominations: u32 =
+        get_metadata_constant(&data.substrate_client.metadata, "Staking", "MaxNominations")?;
+    let max_nominations = match request.max_nominations {
+        Some(0) => {
+            return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+                "Maximum number of nominations should be greater than 0.",
+            )));
+        }
+        Some(max_nominations) if max_nominations > runtime_max_nominations => {
+            return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+                format!("Maximum number of nominations cannot exceed {runtime_max_nominations}.")
+                    .as_ref(),
+            )));
+        }
+        Some(max_nominations) => max_nominations,
+        None => runtime_max_nominations,
+    };
+    let finalized_block = data.redis.get_finalized_block_summary().await?;
+    let validators = data
+        .redis
+        .get_validator_details_list(finalized_block.number, true)
+        .await?;
+    // era performance history
+    let history_era_count = CONFIG.report.nomination_optimizer_history_era_count;
+    let performance_map: HashMap<AccountId, (u32, u64)> =
+        match data.postgres.get_current_era().await? {
+            Some(current_era) if current_era.index > 0 => {
+                let end_era_index = current_era.index - 1;
+                let start_era_index =
+                    end_era_index.saturating_sub(history_era_count.saturating_sub(1));
+                data.postgres
+                    .get_validator_era_performances(start_era_index, end_era_index)
+                    .await?
+                    .into_iter()
+                    .map(|(account_id, active_era_count, average_reward_points)| {
+                        (account_id, (active_era_count, average_reward_points))
+                    })
+                    .collect()
+            }
+            _ => HashMap::default(),
+        };
+    let network_average_reward_points = if performance_map.is_empty() {
+        0
+    } else {
+        performance_map
+            .values()
+            .map(|(_, average_reward_points)| *average_reward_points)
+            .sum::<u64>()
+            / performance_map.len() as u64
+    };
+    // initial estimate for the ranking, corrected for the actual number of selected validators
+    let stake_share = request.stake / max_nominations as Balance;
+    let mut identity_keys: HashMap<AccountId, Option<AccountId>> = HashMap::default();
+    let candidates: Vec<NominationOptimizationValidator> = validators
+        .iter()
+        .filter(|validator| satisfies_constraints(validator, &request))
+        .map(|validator| {
+            identity_keys.insert(validator.account.id, get_identity_key(validator));
+            let performance = performance_map.get(&validator.account.id);
+            NominationOptimizationValidator {
+                validator_summary: ValidatorSummary::from(validator),
+                location: validator.onekv_location.clone(),
+                active_era_count: performance
+                    .map(|(active_era_count, _)| *active_era_count)
+                    .unwrap_or(0),
+                average_reward_points: performance
+                    .map(|(_, average_reward_points)| *average_reward_points)
+                    .unwrap_or(0),
+                expected_return_rate_per_billion: get_expected_return_rate_per_billion(
+                    validator,
+                    performance.map(|(_, average_reward_points)| *average_reward_points),
+                    network_average_reward_points,
+                    stake_share,
+                ),
+            }
+        })
+        .collect();
+    let mut selected = select_validators(
+        candidates,
+        &identity_keys,
+        &request,
+        max_nominations as usize,
+    );
+    let validator_map: HashMap<AccountId, &ValidatorDetails> = validators
+        .iter()
+        .map(|validator| (validator.account.id, validator))
+        .collect();
+    set_selected_stake_share(
+        &mut selected,
+        &validator_map,
+        &performance_map,
+        network_average_reward_points,
+        request.stake,
+    );
+    // stake gets split evenly between the selected validators
+    let expected_return_rate_per_billion = if selected.is_empty() {
+        0
+    } else {
+        selected
+            .iter()
+            .map(|validator| validator.expected_return_rate_per_billion as u128)
+            .sum::<u128>()
+            / selected.len() as u128
+    };
+    Ok(HttpResponse::Ok().json(NominationOptimizationReport {
+        finalized_block,
+        history_era_count,
+        validators: selected,
+        expected_return_rate_per_billion: expected_return_rate_per_billion as u32,
+        expected_annual_return: request.stake * expected_return_rate_per_billion / 1_000_000_000,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_validators, set_selected_stake_share};
+    use rustc_hash::FxHashMap as HashMap;
+    use subvt_types::crypto::AccountId;
+    use subvt_types::report::{NominationOptimizationRequest, NominationOptimizationValidator};
+    use subvt_types::substrate::{Balance, ValidatorStake};
+    use subvt_types::subvt::{ValidatorDetails, ValidatorSummary};
+
+    fn get_account_id(index: u8) -> AccountId {
+        AccountId::new([index; 32])
+    }
+
+    fn get_candidate(
+        index: u8,
+        expected_return_rate_per_billion: u32,
+        active_era_count: u32,
+        location: Option<&str>,
+    ) -> NominationOptimizationValidator {
+        NominationOptimizationValidator {
+            validator_summary: ValidatorSummary {
+                account_id: get_account_id(index),
+                ..Default::default()
+            },
+            location: location.map(|location| location.to_string()),
+            active_era_count,
+            expected_return_rate_per_billion,
+            ..Default::default()
+        }
+    }
+
+    fn get_selected_account_ids(selected: &[NominationOptimizationValidator]) -> Vec<AccountId> {
+        selected
+            .iter()
+            .map(|validator| validator.validator_summary.account_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_select_validators_max_nominations() {
+        let candidates = (1..=5)
+            .map(|index| get_candidate(index, index as u32 * 1000, 1, None))
+            .collect();
+        let selected = select_validators(
+            candidates,
+            &HashMap::default(),
+            &NominationOptimizationRequest::default(),
+            3,
+        );
+        assert_eq!(
+            get_selected_account_ids(&selected),
+            vec![get_account_id(5), get_account_id(4), get_account_id(3)],
+        );
+    }
+
+    #[test]
+    fn test_select_validators_identity_and_location_limits() {
+        let candidates = vec![
+            get_candidate(1, 5000, 1, Some("Berlin")),
+            get_candidate(2, 4000, 1, Some("Berlin")),
+            get_candidate(3, 3000, 1, Some("Paris")),
+            get_candidate(4, 2000, 1, Some("Rome")),
+        ];
+        let mut identity_keys = HashMap::default();
+        // 1 and 3 share the same parent identity
+        identity_keys.insert(get_account_id(1), Some(get_account_id(100)));
+        identity_keys.insert(get_account_id(3), Some(get_account_id(100)));
+        let request = NominationOptimizationRequest {
+            max_per_identity: Some(1),
+            max_per_location: Some(1),
+            ..Default::default()
+        };
+        let selected = select_validators(candidates, &identity_keys, &request, 16);
+        assert_eq!(
+            get_selected_account_ids(&selected),
+            vec![get_account_id(1), get_account_id(4)],
+        );
+    }
+
+    #[test]
+    fn test_select_validators_ties() {
+        // equal expected returns are ordered by the number of active eras
+        let candidates = vec![
+            get_candidate(1, 1000, 2, None),
+            get_candidate(2, 1000, 8, None),
+            get_candidate(3, 1000, 4, None),
+        ];
+        let selected = select_validators(
+            candidates,
+            &HashMap::default(),
+            &NominationOptimizationRequest::default(),
+            2,
+        );
+        assert_eq!(
+            get_selected_account_ids(&selected),
+            vec![get_account_id(2), get_account_id(3)],
+        );
+    }
+
+    #[test]
+    fn test_selected_stake_share() {
+        let stake: Balance = 1000;
+        let validators: Vec<ValidatorDetails> = (1..=2)
+            .map(|index| {
+                let mut validator = ValidatorDetails {
+                    return_rate_per_billion: Some(100_000_000),
+                    validator_stake: Some(ValidatorStake {
+                        total_stake: 1000,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                };
+                validator.account.id = get_account_id(index);
+                validator
+            })
+            .collect();
+        let validator_map: HashMap<AccountId, &ValidatorDetails> = validators
+            .iter()
+            .map(|validator| (validator.account.id, validator))
+            .collect();
+        let mut selected = vec![get_candidate(1, 0, 1, None), get_candidate(2, 0, 1, None)];
+        set_selected_stake_share(&mut selected, &validator_map, &HashMap::default(), 0, stake);
+        // the stake is split between the 2 selected validators, not the maximum nominations
+        for validator in &selected {
+            assert_eq!(
+                validator.expected_return_rate_per_billion,
+                (100_000_000u128 * 1000 / (1000 + 500)) as u32,
+            );
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use crate::{ResultResponse, ServiceState};
 use actix_web::{get, web, HttpResponse};
+use subvt_types::onekv::OneKVNominatorSummary;
 
+#[utoipa::path(
+    get,
+    path = "/onekv/nominator",
+    tag = "onekv",
+    responses((status = 200, description = "1KV nominators.", body = [OneKVNominatorSummary])),
+)]
 #[get("/onekv/nominator")]
 pub(crate) async fn get_onekv_nominator_summaries(data: web::Data<ServiceState>) -> ResultResponse {
     Ok(HttpResponse::Ok().json(data.postgres.get_onekv_nominator_summaries().await?))
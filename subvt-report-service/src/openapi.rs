@@ -0,0 +1,189 @@
+//! OpenAPI 3 specification of the reporting REST interface, generated from the handler
+//! annotations and served at `/openapi.json`.
+use crate::{era, governance, nomination, onekv, session, staking, telemetry, validator};
+use actix_web::{get, HttpResponse};
+use subvt_service_common::openapi::SubstrateSchemaAddon;
+use subvt_types::app::event::conviction_voting::{
+    ConvictionVotingDelegatedEvent, ConvictionVotingDelegation, ConvictionVotingUndelegatedEvent,
+    ConvictionVotingVoteEvent,
+};
+use subvt_types::app::event::treasury::{TreasurySpendEvent, TreasurySpendSource};
+use subvt_types::crypto::AccountId;
+use subvt_types::err::ServiceError;
+use subvt_types::governance::polkassembly::ReferendumStatus;
+use subvt_types::governance::treasury::ReferendumTreasurySpend;
+use subvt_types::onekv::OneKVNominatorSummary;
+use subvt_types::report::{
+    BlockSummary, Bond, ChartImageFormat, Controller, ConvictionVotingReport, DelegateReport,
+    DelegateTrackReport, EraReport, EraValidatorListReport, EraValidatorPayoutReport,
+    EraValidatorReport, EraValidatorRewardReport, HeartbeatEvent, NominationOptimizationReport,
+    NominationOptimizationRequest, NominationOptimizationValidator, ParaVote, ParaVoteType,
+    ParaVotesSummary, ReferendumTreasurySpendReport, SessionParaVoteReport, SessionParasVoteReport,
+    SessionValidatorParaVoteReport, SessionValidatorReport, TelemetryNodeVersion,
+    TelemetryNodeVersionReport, TelemetryVersionCount, TelemetryVersionDistributionReport,
+    TimeSeriesResolution, TrackTreasurySpendReport, TreasuryBeneficiaryReport, ValidatorChartType,
+    ValidatorDetailsReport, ValidatorHistoryReport, ValidatorListReport, ValidatorSnapshot,
+    ValidatorSummaryReport, ValidatorTelemetryFinalityReport, ValidatorTelemetryFinalitySession,
+    ValidatorTelemetryHistoryReport, ValidatorTelemetryNode, ValidatorTelemetryNodeReport,
+    ValidatorTelemetryNodeStats, ValidatorTelemetryNodeVersion, ValidatorTelemetryVersionReport,
+    ValidatorTotalReward, ValidatorTotalRewardChartData,
+};
+use subvt_types::substrate::{
+    Account, Epoch, Era, IdentityRegistration, InactiveNominationsSummary, NominationSummary,
+    NominatorStake, Stake, StakeSummary, ValidatorPreferences, ValidatorStake,
+};
+use subvt_types::subvt::{
+    ValidatorDetails, ValidatorSearchSummary, ValidatorStakeSummary, ValidatorSummary,
+};
+use subvt_types::telemetry::validator::TelemetryNodeMatchMethod;
+use subvt_types::telemetry::version::TelemetryLatestVersion;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "SubVT Report Service",
+        description = "Era, session, validator, staking and nomination reports of the network.",
+    ),
+    paths(
+        get_openapi_spec,
+        era::era_validator_report_service,
+        era::era_active_validator_list_report_service,
+        era::era_inactive_validator_list_report_service,
+        era::era_report_service,
+        era::current_era_service,
+        era::all_eras_service,
+        session::current_session_service,
+        session::validator::session_validator_report_service,
+        session::validator::session_validator_para_vote_service,
+        session::para::session_paras_vote_summaries_service,
+        onekv::get_onekv_nominator_summaries,
+        validator::validator_summary_service,
+        validator::validator_details_service,
+        validator::validator_list_service,
+        validator::active_validator_list_service,
+        validator::inactive_validator_list_service,
+        validator::validator_search_service,
+        validator::validator_era_rewards_service,
+        validator::validator_era_payouts_service,
+        validator::validator_reward_chart_service,
+        validator::validator_history_service,
+        validator::validator_chart_service,
+        validator::validator_monthly_report_service,
+        validator::telemetry::validator_telemetry_node_service,
+        validator::telemetry::validator_telemetry_history_service,
+        validator::telemetry::validator_telemetry_version_service,
+        validator::telemetry::validator_telemetry_finality_service,
+        telemetry::telemetry_version_distribution_service,
+        telemetry::telemetry_node_version_service,
+        staking::controller_service,
+        staking::bond_service,
+        nomination::nomination_optimization_service,
+        governance::conviction_voting_service,
+        governance::delegate_service,
+        governance::beneficiary_treasury_spend_service,
+        governance::track_treasury_spend_service,
+    ),
+    components(schemas(
+        Account,
+        AccountId,
+        BlockSummary,
+        Bond,
+        ChartImageFormat,
+        Controller,
+        ConvictionVotingDelegatedEvent,
+        ConvictionVotingDelegation,
+        ConvictionVotingReport,
+        ConvictionVotingUndelegatedEvent,
+        ConvictionVotingVoteEvent,
+        DelegateReport,
+        DelegateTrackReport,
+        Epoch,
+        Era,
+        EraReport,
+        EraValidatorListReport,
+        EraValidatorPayoutReport,
+        EraValidatorReport,
+        EraValidatorRewardReport,
+        HeartbeatEvent,
+        IdentityRegistration,
+        InactiveNominationsSummary,
+        NominationOptimizationReport,
+        NominationOptimizationRequest,
+        NominationOptimizationValidator,
+        NominationSummary,
+        NominatorStake,
+        OneKVNominatorSummary,
+        ParaVote,
+        ParaVoteType,
+        ParaVotesSummary,
+        ReferendumStatus,
+        ReferendumTreasurySpend,
+        ReferendumTreasurySpendReport,
+        ServiceError,
+        SessionParaVoteReport,
+        SessionParasVoteReport,
+        SessionValidatorParaVoteReport,
+        SessionValidatorReport,
+        Stake,
+        StakeSummary,
+        TelemetryLatestVersion,
+        TelemetryNodeMatchMethod,
+        TelemetryNodeVersion,
+        TelemetryNodeVersionReport,
+        TelemetryVersionCount,
+        TelemetryVersionDistributionReport,
+        TimeSeriesResolution,
+        TrackTreasurySpendReport,
+        TreasuryBeneficiaryReport,
+        TreasurySpendEvent,
+        TreasurySpendSource,
+        ValidatorChartType,
+        ValidatorDetails,
+        ValidatorDetailsReport,
+        ValidatorHistoryReport,
+        ValidatorListReport,
+        ValidatorPreferences,
+        ValidatorSearchSummary,
+        ValidatorSnapshot,
+        ValidatorStake,
+        ValidatorStakeSummary,
+        ValidatorSummary,
+        ValidatorSummaryReport,
+        ValidatorTelemetryFinalityReport,
+        ValidatorTelemetryFinalitySession,
+        ValidatorTelemetryHistoryReport,
+        ValidatorTelemetryNode,
+        ValidatorTelemetryNodeReport,
+        ValidatorTelemetryNodeStats,
+        ValidatorTelemetryNodeVersion,
+        ValidatorTelemetryVersionReport,
+        ValidatorTotalReward,
+        ValidatorTotalRewardChartData,
+    )),
+    tags(
+        (name = "era", description = "Eras and era reports."),
+        (name = "session", description = "Sessions and session reports."),
+        (name = "validator", description = "Validator summaries, details, history, charts and Telemetry nodes."),
+        (name = "telemetry", description = "Client version distribution and node version history of the network's Telemetry nodes."),
+        (name = "staking", description = "Controller and bond of stash accounts."),
+        (name = "onekv", description = "1KV (Thousand Validators Programme) nominators."),
+        (name = "nomination", description = "Nomination optimizer."),
+        (name = "governance", description = "OpenGov votes and delegations of accounts, the delegations received by delegates, and treasury spends by beneficiary and track."),
+        (name = "openapi", description = "This specification."),
+    ),
+    modifiers(&SubstrateSchemaAddon),
+)]
+pub struct ApiDoc;
+
+/// `GET`s the OpenAPI 3 specification of the service.
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "openapi",
+    responses((status = 200, description = "OpenAPI specification.", content_type = "application/json")),
+)]
+#[get("/openapi.json")]
+pub(crate) async fn get_openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
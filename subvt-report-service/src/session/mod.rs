@@ -1,11 +1,21 @@
 use crate::{ResultResponse, ServiceState};
 use actix_web::{get, web, HttpResponse};
 use subvt_types::err::ServiceError;
+use subvt_types::substrate::Epoch;
 
 pub(crate) mod para;
 pub(crate) mod validator;
 
 /// Gets the current era.
+#[utoipa::path(
+    get,
+    path = "/session/current",
+    tag = "session",
+    responses(
+        (status = 200, description = "Current session.", body = Epoch),
+        (status = 404, description = "Current session not found.", body = ServiceError),
+    ),
+)]
 #[get("/session/current")]
 pub(crate) async fn current_session_service(data: web::Data<ServiceState>) -> ResultResponse {
     if let Some(epoch) = data.postgres.get_current_epoch().await? {
@@ -3,6 +3,7 @@ use actix_web::{get, web, HttpResponse};
 use serde::Deserialize;
 use subvt_types::report::SessionParasVoteReport;
 use subvt_types::{err::ServiceError, substrate::Epoch};
+use utoipa::IntoParams;
 
 async fn validate_params(
     maybe_start_session_index: Option<i64>,
@@ -64,14 +65,25 @@ async fn validate_params(
     Ok((start_session_index, end_session_index))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct SessionParasReportQueryParameters {
-    #[serde(rename(deserialize = "start_session_index"))]
+    #[serde(rename = "start_session_index")]
     maybe_start_session_index: Option<i64>,
-    #[serde(rename(deserialize = "end_session_index"))]
+    #[serde(rename = "end_session_index")]
     maybe_end_session_index: Option<i64>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/report/session/paras",
+    tag = "session",
+    params(SessionParasReportQueryParameters),
+    responses(
+        (status = 200, description = "Para vote summaries of the sessions.", body = [SessionParasVoteReport]),
+        (status = 400, description = "Invalid session range.", body = ServiceError),
+    ),
+)]
 #[get("/report/session/paras")]
 pub(crate) async fn session_paras_vote_summaries_service(
     query: web::Query<SessionParasReportQueryParameters>,
@@ -9,6 +9,7 @@ use subvt_types::report::{
 };
 use subvt_types::substrate::Epoch;
 use subvt_types::{crypto::AccountId, err::ServiceError};
+use utoipa::IntoParams;
 
 async fn validate_params(
     ss58_address: &str,
@@ -145,19 +146,31 @@ async fn get_session_validator_report(
     Ok(Some(report))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub(crate) struct SessionValidatorReportPathParameters {
     ss58_address: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct SessionValidatorReportQueryParameters {
-    #[serde(rename(deserialize = "start_session_index"))]
+    #[serde(rename = "start_session_index")]
     maybe_start_session_index: Option<i64>,
-    #[serde(rename(deserialize = "end_session_index"))]
+    #[serde(rename = "end_session_index")]
     maybe_end_session_index: Option<i64>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/report/session/validator/{ss58_address}",
+    tag = "session",
+    params(SessionValidatorReportPathParameters, SessionValidatorReportQueryParameters),
+    responses(
+        (status = 200, description = "Session reports of the validator.", body = [SessionValidatorReport]),
+        (status = 400, description = "Invalid address or session range.", body = ServiceError),
+    ),
+)]
 #[get("/report/session/validator/{ss58_address}")]
 pub(crate) async fn session_validator_report_service(
     path: web::Path<SessionValidatorReportPathParameters>,
@@ -240,6 +253,16 @@ async fn get_session_validator_para_vote_report(
     Ok(Some(report))
 }
 
+#[utoipa::path(
+    get,
+    path = "/report/session/validator/{ss58_address}/paravote",
+    tag = "session",
+    params(SessionValidatorReportPathParameters, SessionValidatorReportQueryParameters),
+    responses(
+        (status = 200, description = "Session para vote reports of the validator.", body = [SessionValidatorParaVoteReport]),
+        (status = 400, description = "Invalid address or session range.", body = ServiceError),
+    ),
+)]
 #[get("/report/session/validator/{ss58_address}/paravote")]
 pub(crate) async fn session_validator_para_vote_service(
     path: web::Path<SessionValidatorReportPathParameters>,
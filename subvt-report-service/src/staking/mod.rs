@@ -4,17 +4,31 @@ use actix_web::{get, web, HttpResponse};
 use serde::Deserialize;
 use subvt_types::err::ServiceError;
 use subvt_types::report::{Bond, Controller};
+use utoipa::IntoParams;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub(crate) struct AccountIdPathParameter {
     ss58_address_or_account_id: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct BlockHashQueryParameter {
     block_hash: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/staking/{ss58_address_or_account_id}/controller",
+    tag = "staking",
+    params(AccountIdPathParameter, BlockHashQueryParameter),
+    responses(
+        (status = 200, description = "Controller of the stash account.", body = Controller),
+        (status = 400, description = "Invalid address, account id or block hash.", body = ServiceError),
+        (status = 404, description = "Controller not found.", body = ServiceError),
+    ),
+)]
 #[get("/staking/{ss58_address_or_account_id}/controller")]
 pub(crate) async fn controller_service(
     path: web::Path<AccountIdPathParameter>,
@@ -49,6 +63,17 @@ pub(crate) async fn controller_service(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/staking/{ss58_address_or_account_id}/bond",
+    tag = "staking",
+    params(AccountIdPathParameter, BlockHashQueryParameter),
+    responses(
+        (status = 200, description = "Bond of the stash account.", body = Bond),
+        (status = 400, description = "Invalid address, account id or block hash.", body = ServiceError),
+        (status = 404, description = "Bond not found.", body = ServiceError),
+    ),
+)]
 #[get("/staking/{ss58_address_or_account_id}/bond")]
 pub(crate) async fn bond_service(
     path: web::Path<AccountIdPathParameter>,
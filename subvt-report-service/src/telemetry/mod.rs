@@ -0,0 +1,90 @@
+//! Telemetry reports of the network, served from the node data persisted by the Telemetry
+//! processor.
+use crate::validator::validate_time_series_range;
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use subvt_types::err::ServiceError;
+use subvt_types::report::{
+    TelemetryNodeVersionReport, TelemetryVersionDistributionReport, TimeSeriesResolution,
+};
+use utoipa::IntoParams;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct TelemetryVersionDistributionQueryParameters {
+    start_timestamp: u64,
+    end_timestamp: u64,
+    resolution: TimeSeriesResolution,
+}
+
+/// Number of the network's Telemetry nodes per client version in the given time range, per
+/// time bucket, along with the latest client release.
+#[utoipa::path(
+    get,
+    path = "/telemetry/version/distribution",
+    tag = "telemetry",
+    params(TelemetryVersionDistributionQueryParameters),
+    responses(
+        (status = 200, description = "Client version distribution of the network's nodes.", body = TelemetryVersionDistributionReport),
+        (status = 400, description = "Invalid time range.", body = ServiceError),
+    ),
+)]
+#[get("/telemetry/version/distribution")]
+pub(crate) async fn telemetry_version_distribution_service(
+    query: web::Query<TelemetryVersionDistributionQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    if let Err(response) = validate_time_series_range(query.start_timestamp, query.end_timestamp) {
+        return Ok(response);
+    }
+    let latest_version = data.postgres.get_telemetry_latest_version().await?;
+    let versions = data
+        .postgres
+        .get_telemetry_version_distribution(
+            query.start_timestamp,
+            query.end_timestamp,
+            query.resolution,
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(TelemetryVersionDistributionReport {
+        latest_version,
+        resolution: query.resolution,
+        start_timestamp: query.start_timestamp,
+        end_timestamp: query.end_timestamp,
+        versions,
+    }))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub(crate) struct TelemetryNodePathParameter {
+    /// libp2p network id (peer id) of the node.
+    network_id: String,
+}
+
+/// Client versions that the node has run, with the first and last time each version was seen.
+#[utoipa::path(
+    get,
+    path = "/telemetry/node/{network_id}/version",
+    tag = "telemetry",
+    params(TelemetryNodePathParameter),
+    responses(
+        (status = 200, description = "Client version history of the node.", body = TelemetryNodeVersionReport),
+    ),
+)]
+#[get("/telemetry/node/{network_id}/version")]
+pub(crate) async fn telemetry_node_version_service(
+    path: web::Path<TelemetryNodePathParameter>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let network_id = path.into_inner().network_id;
+    let versions = data
+        .postgres
+        .get_telemetry_node_versions(&network_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(TelemetryNodeVersionReport {
+        network_id,
+        versions,
+    }))
+}
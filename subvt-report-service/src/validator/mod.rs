@@ -1,14 +1,21 @@
-use crate::{ResultResponse, ServiceState};
+use crate::{ResultResponse, ServiceState, CONFIG};
 use actix_web::{get, web, HttpResponse};
 use serde::Deserialize;
 use std::str::FromStr;
+use subvt_plotter::PlotterError;
 use subvt_types::crypto::AccountId;
 use subvt_types::err::ServiceError;
 use subvt_types::report::{
-    BlockSummary, EraValidatorPayoutReport, EraValidatorRewardReport, ValidatorDetailsReport,
+    BlockSummary, ChartImageFormat, EraValidatorPayoutReport, EraValidatorRewardReport,
+    TimeSeriesResolution, ValidatorChartType, ValidatorDetailsReport, ValidatorHistoryReport,
     ValidatorListReport, ValidatorSummaryReport, ValidatorTotalRewardChartData,
 };
+use subvt_types::substrate::metadata::get_metadata_era_duration_millis;
+use subvt_types::substrate::Balance;
 use subvt_types::subvt::{ValidatorSearchSummary, ValidatorSummary};
+use utoipa::IntoParams;
+
+pub(crate) mod telemetry;
 
 fn validate_path_param(ss58_address_or_account_id: &str) -> Result<AccountId, HttpResponse> {
     let account_id = match AccountId::from_str(ss58_address_or_account_id) {
@@ -52,11 +59,46 @@ fn get_validator_list(
     }
 }
 
-#[derive(Deserialize)]
+pub(crate) fn validate_time_series_range(
+    start_timestamp: u64,
+    end_timestamp: u64,
+) -> Result<(), HttpResponse> {
+    if end_timestamp <= start_timestamp {
+        return Err(HttpResponse::BadRequest().json(ServiceError::from(
+            "End timestamp should be greater than start timestamp.",
+        )));
+    }
+    let max_range_millis = CONFIG.report.max_time_series_range_days as u64
+        * TimeSeriesResolution::Day.get_duration_millis();
+    if end_timestamp - start_timestamp > max_range_millis {
+        return Err(HttpResponse::BadRequest().json(ServiceError::from(
+            format!(
+                "Time series cannot span more than {} days.",
+                CONFIG.report.max_time_series_range_days
+            )
+            .as_ref(),
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub(crate) struct ValidatorPathParameter {
     ss58_address_or_account_id: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/summary",
+    tag = "validator",
+    params(ValidatorPathParameter),
+    responses(
+        (status = 200, description = "Validator summary at the last finalized block.", body = ValidatorSummaryReport),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+        (status = 404, description = "Validator not found.", body = ServiceError),
+    ),
+)]
 #[get("/validator/{ss58_address_or_account_id}/summary")]
 pub(crate) async fn validator_summary_service(
     path: web::Path<ValidatorPathParameter>,
@@ -81,6 +123,17 @@ pub(crate) async fn validator_summary_service(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/details",
+    tag = "validator",
+    params(ValidatorPathParameter),
+    responses(
+        (status = 200, description = "Validator details at the last finalized block.", body = ValidatorDetailsReport),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+        (status = 404, description = "Validator not found.", body = ServiceError),
+    ),
+)]
 #[get("/validator/{ss58_address_or_account_id}/details")]
 pub(crate) async fn validator_details_service(
     path: web::Path<ValidatorPathParameter>,
@@ -105,6 +158,12 @@ pub(crate) async fn validator_details_service(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/list",
+    tag = "validator",
+    responses((status = 200, description = "Active and inactive validators.", body = ValidatorListReport)),
+)]
 #[get("/validator/list")]
 pub(crate) async fn validator_list_service(data: web::Data<ServiceState>) -> ResultResponse {
     let finalized_block = match get_finalized_block_summary(&data) {
@@ -130,6 +189,12 @@ pub(crate) async fn validator_list_service(data: web::Data<ServiceState>) -> Res
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/list/active",
+    tag = "validator",
+    responses((status = 200, description = "Active validators.", body = ValidatorListReport)),
+)]
 #[get("/validator/list/active")]
 pub(crate) async fn active_validator_list_service(data: web::Data<ServiceState>) -> ResultResponse {
     let finalized_block = match get_finalized_block_summary(&data) {
@@ -148,6 +213,12 @@ pub(crate) async fn active_validator_list_service(data: web::Data<ServiceState>)
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/list/inactive",
+    tag = "validator",
+    responses((status = 200, description = "Inactive validators.", body = ValidatorListReport)),
+)]
 #[get("/validator/list/inactive")]
 pub(crate) async fn inactive_validator_list_service(
     data: web::Data<ServiceState>,
@@ -168,11 +239,19 @@ pub(crate) async fn inactive_validator_list_service(
     }))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct ValidatorSearchQueryParameters {
     query: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/search",
+    tag = "validator",
+    params(ValidatorSearchQueryParameters),
+    responses((status = 200, description = "Matching validators, best matches first.", body = [ValidatorSearchSummary])),
+)]
 #[get("/validator/search")]
 pub(crate) async fn validator_search_service(
     query: web::Query<ValidatorSearchQueryParameters>,
@@ -191,19 +270,33 @@ pub(crate) async fn validator_search_service(
         }
     };
     active_validator_list.append(&mut inactive_validator_list);
-    let list: Vec<ValidatorSearchSummary> = active_validator_list
+    let mut matches: Vec<(u32, &ValidatorSummary)> = active_validator_list
         .iter()
         .filter_map(|validator_summary| {
-            if validator_summary.filter(&query.query) {
-                Some(ValidatorSearchSummary::from(validator_summary))
-            } else {
-                None
-            }
+            validator_summary
+                .get_search_score(&query.query)
+                .map(|score| (score, validator_summary))
         })
         .collect();
+    // best matches first
+    matches.sort_by(|a, b| b.0.cmp(&a.0));
+    let list: Vec<ValidatorSearchSummary> = matches
+        .into_iter()
+        .map(|(_, validator_summary)| ValidatorSearchSummary::from(validator_summary))
+        .collect();
     Ok(HttpResponse::Ok().json(list))
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/era/reward",
+    tag = "validator",
+    params(ValidatorPathParameter),
+    responses(
+        (status = 200, description = "Era rewards of the validator.", body = [EraValidatorRewardReport]),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+    ),
+)]
 #[get("/validator/{ss58_address_or_account_id}/era/reward")]
 pub(crate) async fn validator_era_rewards_service(
     path: web::Path<ValidatorPathParameter>,
@@ -223,6 +316,16 @@ pub(crate) async fn validator_era_rewards_service(
     Ok(HttpResponse::Ok().json(era_rewards))
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/era/payout",
+    tag = "validator",
+    params(ValidatorPathParameter),
+    responses(
+        (status = 200, description = "Era payouts of the validator.", body = [EraValidatorPayoutReport]),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+    ),
+)]
 #[get("/validator/{ss58_address_or_account_id}/era/payout")]
 pub(crate) async fn validator_era_payouts_service(
     path: web::Path<ValidatorPathParameter>,
@@ -242,12 +345,20 @@ pub(crate) async fn validator_era_payouts_service(
     Ok(HttpResponse::Ok().json(era_payouts))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct ValidatorRewardChartQueryParameters {
     start_timestamp: u64,
     end_timestamp: u64,
 }
 
+#[utoipa::path(
+    get,
+    path = "/validator/reward/chart",
+    tag = "validator",
+    params(ValidatorRewardChartQueryParameters),
+    responses((status = 200, description = "Total rewards of the validators in the time range.", body = ValidatorTotalRewardChartData)),
+)]
 #[get("/validator/reward/chart")]
 pub(crate) async fn validator_reward_chart_service(
     query: web::Query<ValidatorRewardChartQueryParameters>,
@@ -310,3 +421,274 @@ pub(crate) async fn validator_reward_chart_service(
         end_timestamp: query.end_timestamp,
     }))
 }
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ValidatorHistoryQueryParameters {
+    start_timestamp: u64,
+    end_timestamp: u64,
+    resolution: TimeSeriesResolution,
+}
+
+/// Downsampled historical snapshots of the validator's stake, nominator count, commission,
+/// active status, return rate and 1KV rank in the given time range.
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/history",
+    tag = "validator",
+    params(ValidatorPathParameter, ValidatorHistoryQueryParameters),
+    responses(
+        (status = 200, description = "Historical snapshots of the validator.", body = ValidatorHistoryReport),
+        (status = 400, description = "Invalid address, account id or time range.", body = ServiceError),
+    ),
+)]
+#[get("/validator/{ss58_address_or_account_id}/history")]
+pub(crate) async fn validator_history_service(
+    path: web::Path<ValidatorPathParameter>,
+    query: web::Query<ValidatorHistoryQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    if let Err(response) = validate_time_series_range(query.start_timestamp, query.end_timestamp) {
+        return Ok(response);
+    }
+    let snapshots = data
+        .postgres
+        .get_validator_snapshots(
+            &account_id,
+            query.start_timestamp,
+            query.end_timestamp,
+            query.resolution,
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorHistoryReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        resolution: query.resolution,
+        start_timestamp: query.start_timestamp,
+        end_timestamp: query.end_timestamp,
+        snapshots,
+    }))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub(crate) struct ValidatorChartPathParameter {
+    ss58_address_or_account_id: String,
+    chart_type: ValidatorChartType,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ValidatorChartQueryParameters {
+    #[serde(default)]
+    format: ChartImageFormat,
+}
+
+/// Stake composition, era points, para votes or nominator count chart of the validator,
+/// as an SVG or PNG image.
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/chart/{chart_type}",
+    tag = "validator",
+    params(ValidatorChartPathParameter, ValidatorChartQueryParameters),
+    responses(
+        (status = 200, description = "Chart image.", content_type = ["image/png", "image/svg+xml"]),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+        (status = 404, description = "Chart data not found.", body = ServiceError),
+    ),
+)]
+#[get("/validator/{ss58_address_or_account_id}/chart/{chart_type}")]
+pub(crate) async fn validator_chart_service(
+    path: web::Path<ValidatorChartPathParameter>,
+    query: web::Query<ValidatorChartQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let path = path.into_inner();
+    let account_id = match validate_path_param(&path.ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let chart_type = path.chart_type;
+    let (era_chart_data, para_votes_chart_data) = match chart_type {
+        ValidatorChartType::ParaVotes => (
+            Vec::new(),
+            data.postgres
+                .get_validator_session_para_votes_chart_data(
+                    &account_id,
+                    CONFIG.plotter.chart_session_count,
+                )
+                .await?,
+        ),
+        _ => (
+            data.postgres
+                .get_validator_era_chart_data(&account_id, CONFIG.plotter.chart_era_count)
+                .await?,
+            Vec::new(),
+        ),
+    };
+    let format = query.format;
+    // rendering is CPU-bound, kept off the async workers
+    let plot_result = tokio::task::spawn_blocking(move || {
+        subvt_plotter::validator_chart::plot_validator_chart(
+            chart_type,
+            &account_id.to_ss58_check(),
+            &era_chart_data,
+            &para_votes_chart_data,
+            format,
+        )
+    })
+    .await
+    .map_err(anyhow::Error::from)?;
+    let image_path = match plot_result {
+        Ok(image_path) => image_path,
+        Err(error) => match error.downcast_ref::<PlotterError>() {
+            Some(PlotterError::EmptyData) => {
+                return Ok(
+                    HttpResponse::NotFound().json(ServiceError::from("Chart data not found."))
+                );
+            }
+            None => return Err(error.into()),
+        },
+    };
+    let image = std::fs::read(&image_path).map_err(anyhow::Error::from);
+    if let Err(error) = std::fs::remove_file(&image_path) {
+        log::error!("Error while removing chart image file: {:?}", error);
+    }
+    Ok(HttpResponse::Ok()
+        .content_type(format.get_content_type())
+        .body(image?))
+}
+
+/// Period covered by the monthly validator report.
+const MONTHLY_REPORT_PERIOD_MILLIS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ValidatorMonthlyReportQueryParameters {
+    /// Image format of the summary page. The full report is returned as a PDF document if not
+    /// given.
+    #[serde(rename = "format")]
+    maybe_format: Option<ChartImageFormat>,
+}
+
+/// Performance report of the validator for the last 30 days: a summary, and the stake
+/// composition, era points, para votes and nominator count charts. Returned as a two-page PDF
+/// document, or as an SVG or PNG image of the summary page.
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/report/monthly",
+    tag = "validator",
+    params(ValidatorPathParameter, ValidatorMonthlyReportQueryParameters),
+    responses(
+        (status = 200, description = "Monthly report document.", content_type = ["application/pdf", "image/png", "image/svg+xml"]),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+        (status = 404, description = "Report data not found.", body = ServiceError),
+    ),
+)]
+#[get("/validator/{ss58_address_or_account_id}/report/monthly")]
+pub(crate) async fn validator_monthly_report_service(
+    path: web::Path<ValidatorPathParameter>,
+    query: web::Query<ValidatorMonthlyReportQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let era_duration_millis = get_metadata_era_duration_millis(&data.substrate_client.metadata)?;
+    if era_duration_millis == 0 {
+        return Err(anyhow::anyhow!("Invalid era duration.").into());
+    }
+    let era_count = (MONTHLY_REPORT_PERIOD_MILLIS + era_duration_millis - 1) / era_duration_millis;
+    let era_chart_data = data
+        .postgres
+        .get_validator_era_chart_data(&account_id, era_count as u32)
+        .await?;
+    let para_votes_chart_data = data
+        .postgres
+        .get_validator_session_para_votes_chart_data(
+            &account_id,
+            era_count as u32 * CONFIG.substrate.epochs_per_era as u32,
+        )
+        .await?;
+    let era_indices = era_chart_data
+        .iter()
+        .map(|era_chart_datum| era_chart_datum.era_index);
+    let total_reward: Balance = match (era_indices.clone().min(), era_indices.max()) {
+        (Some(start_era_index), Some(end_era_index)) => data
+            .postgres
+            .get_validator_era_rewards(&account_id, start_era_index, end_era_index)
+            .await?
+            .iter()
+            .map(|(_, reward)| *reward)
+            .sum(),
+        _ => 0,
+    };
+    let title = format!("Monthly Report for {}", account_id.to_ss58_check());
+    let maybe_format = query.maybe_format;
+    let content_type = match maybe_format {
+        Some(format) => format.get_content_type(),
+        None => "application/pdf",
+    };
+    // rendering is CPU-bound, kept off the async workers
+    let plot_result = tokio::task::spawn_blocking(move || match maybe_format {
+        Some(format) => subvt_plotter::monthly_report::plot_validator_monthly_report_summary(
+            &title,
+            &era_chart_data,
+            &para_votes_chart_data,
+            total_reward,
+            format,
+        ),
+        None => subvt_plotter::monthly_report::plot_validator_monthly_report(
+            &title,
+            &era_chart_data,
+            &para_votes_chart_data,
+            total_reward,
+        ),
+    })
+    .await
+    .map_err(anyhow::Error::from)?;
+    let report_path = match plot_result {
+        Ok(report_path) => report_path,
+        Err(error) => match error.downcast_ref::<PlotterError>() {
+            Some(PlotterError::EmptyData) => {
+                return Ok(
+                    HttpResponse::NotFound().json(ServiceError::from("Report data not found."))
+                );
+            }
+            None => return Err(error.into()),
+        },
+    };
+    let report = std::fs::read(&report_path).map_err(anyhow::Error::from);
+    if let Err(error) = std::fs::remove_file(&report_path) {
+        log::error!("Error while removing monthly report file: {:?}", error);
+    }
+    Ok(HttpResponse::Ok().content_type(content_type).body(report?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_time_series_range;
+    use crate::CONFIG;
+    use actix_web::http::StatusCode;
+    use subvt_types::report::TimeSeriesResolution;
+
+    #[test]
+    fn test_validate_time_series_range() {
+        let day = TimeSeriesResolution::Day.get_duration_millis();
+        let max_range = CONFIG.report.max_time_series_range_days as u64 * day;
+        let start = 1_700_000_000_000;
+        assert!(validate_time_series_range(start, start + day).is_ok());
+        assert!(validate_time_series_range(start, start + max_range).is_ok());
+        for end in [start, start - 1, start + max_range + 1] {
+            assert_eq!(
+                validate_time_series_range(start, end).unwrap_err().status(),
+                StatusCode::BAD_REQUEST,
+            );
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Telemetry reports of the validators, served from the nodes matched to the validators by the
+//! Telemetry processor.
+use super::{validate_path_param, validate_time_series_range, ValidatorPathParameter};
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use subvt_types::err::ServiceError;
+use subvt_types::report::{
+    TimeSeriesResolution, ValidatorTelemetryFinalityReport, ValidatorTelemetryHistoryReport,
+    ValidatorTelemetryNodeReport, ValidatorTelemetryVersionReport,
+};
+use utoipa::IntoParams;
+
+/// Telemetry nodes currently matched to the validator, along with the match method and
+/// confidence.
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/telemetry/node",
+    tag = "validator",
+    params(ValidatorPathParameter),
+    responses(
+        (status = 200, description = "Telemetry nodes of the validator.", body = ValidatorTelemetryNodeReport),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+    ),
+)]
+#[get("/validator/{ss58_address_or_account_id}/telemetry/node")]
+pub(crate) async fn validator_telemetry_node_service(
+    path: web::Path<ValidatorPathParameter>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let nodes = data
+        .postgres
+        .get_validator_telemetry_nodes(&account_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorTelemetryNodeReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        nodes,
+    }))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ValidatorTelemetryHistoryQueryParameters {
+    start_timestamp: u64,
+    end_timestamp: u64,
+    resolution: TimeSeriesResolution,
+}
+
+/// Peer count, best block lag, bandwidth and client version history of the validator's nodes
+/// in the given time range, per node and time bucket.
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/telemetry/history",
+    tag = "validator",
+    params(ValidatorPathParameter, ValidatorTelemetryHistoryQueryParameters),
+    responses(
+        (status = 200, description = "Telemetry node stats history of the validator.", body = ValidatorTelemetryHistoryReport),
+        (status = 400, description = "Invalid address, account id or time range.", body = ServiceError),
+    ),
+)]
+#[get("/validator/{ss58_address_or_account_id}/telemetry/history")]
+pub(crate) async fn validator_telemetry_history_service(
+    path: web::Path<ValidatorPathParameter>,
+    query: web::Query<ValidatorTelemetryHistoryQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    if let Err(response) = validate_time_series_range(query.start_timestamp, query.end_timestamp) {
+        return Ok(response);
+    }
+    let stats = data
+        .postgres
+        .get_validator_telemetry_node_stats(
+            &account_id,
+            query.start_timestamp,
+            query.end_timestamp,
+            query.resolution,
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorTelemetryHistoryReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        resolution: query.resolution,
+        start_timestamp: query.start_timestamp,
+        end_timestamp: query.end_timestamp,
+        stats,
+    }))
+}
+
+/// Client versions that the validator's nodes have run, with the first and last time each
+/// version was seen.
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/telemetry/version",
+    tag = "validator",
+    params(ValidatorPathParameter),
+    responses(
+        (status = 200, description = "Client version history of the validator's nodes.", body = ValidatorTelemetryVersionReport),
+        (status = 400, description = "Invalid address or account id.", body = ServiceError),
+    ),
+)]
+#[get("/validator/{ss58_address_or_account_id}/telemetry/version")]
+pub(crate) async fn validator_telemetry_version_service(
+    path: web::Path<ValidatorPathParameter>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let versions = data
+        .postgres
+        .get_validator_telemetry_node_versions(&account_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorTelemetryVersionReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        versions,
+    }))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ValidatorTelemetryFinalityQueryParameters {
+    #[serde(rename = "start_session_index")]
+    maybe_start_session_index: Option<u64>,
+    #[serde(rename = "end_session_index")]
+    maybe_end_session_index: Option<u64>,
+}
+
+/// GRANDPA participation of the validator's nodes per session: finalized block, prevote and
+/// precommit counts, late votes, and the vote rates relative to the network, within the
+/// finality message retention period.
+#[utoipa::path(
+    get,
+    path = "/validator/{ss58_address_or_account_id}/telemetry/finality",
+    tag = "validator",
+    params(ValidatorPathParameter, ValidatorTelemetryFinalityQueryParameters),
+    responses(
+        (status = 200, description = "Finality participation of the validator's nodes.", body = ValidatorTelemetryFinalityReport),
+        (status = 400, description = "Invalid address, account id or session range.", body = ServiceError),
+    ),
+)]
+#[get("/validator/{ss58_address_or_account_id}/telemetry/finality")]
+pub(crate) async fn validator_telemetry_finality_service(
+    path: web::Path<ValidatorPathParameter>,
+    query: web::Query<ValidatorTelemetryFinalityQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    if let (Some(start_session_index), Some(end_session_index)) = (
+        query.maybe_start_session_index,
+        query.maybe_end_session_index,
+    ) {
+        if start_session_index > end_session_index {
+            return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+                "End session index cannot be less than start session index.",
+            )));
+        }
+    }
+    let sessions = data
+        .postgres
+        .get_validator_telemetry_finality_sessions(
+            &account_id,
+            query.maybe_start_session_index,
+            query.maybe_end_session_index,
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorTelemetryFinalityReport {
+        account_id,
+        address: account_id.to_ss58_check(),
+        sessions,
+    }))
+}
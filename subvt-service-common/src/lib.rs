@@ -7,6 +7,8 @@ use subvt_config::Config;
 use subvt_types::substrate::Chain;
 
 pub mod err;
+pub mod openapi;
+pub mod rate_limit;
 
 #[async_trait(?Send)]
 pub trait Service {
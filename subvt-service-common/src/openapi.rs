@@ -0,0 +1,279 @@
+//! OpenAPI helpers shared by the REST services. The services generate their OpenAPI 3
+//! specifications from the handler annotations and the `subvt-types` schemas, and serve them at
+//! `/openapi.json`. `get_route_diff` is used in the service tests to check that the specification
+//! documents exactly the routes that the handlers serve, and `get_spec_errors` to check the
+//! parameters and the request and response bodies of the documented operations.
+use std::collections::BTreeSet;
+use std::path::Path;
+use utoipa::openapi::{ObjectBuilder, OpenApi, SchemaType};
+use utoipa::Modify;
+
+const ROUTE_MACROS: [&str; 4] = ["get", "post", "put", "delete"];
+
+/// Adds the schemas of the type aliases used in the `subvt-types` schemas, which cannot implement
+/// `ToSchema` themselves.
+pub struct SubstrateSchemaAddon;
+
+impl Modify for SubstrateSchemaAddon {
+    fn modify(&self, openapi: &mut OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.schemas.insert(
+                "Balance".to_string(),
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Integer)
+                    .description(Some(
+                        "Token amount in the smallest unit of the native token (e.g. Planck).",
+                    ))
+                    .minimum(Some(0.0))
+                    .into(),
+            );
+        }
+    }
+}
+
+/// `(method, path)` pair of an HTTP route, e.g. `("get", "/network")`.
+pub type Route = (String, String);
+
+/// Parses the route of an `actix-web` route macro line, e.g. `#[get("/network")]`.
+fn parse_route_macro(line: &str) -> Option<Route> {
+    let line = line.trim();
+    let inner = line.strip_prefix("#[")?.strip_suffix(")]")?;
+    let (method, rest) = inner.split_once('(')?;
+    if !ROUTE_MACROS.contains(&method) {
+        return None;
+    }
+    let path = rest.strip_prefix('"')?.split('"').next()?;
+    Some((method.to_string(), path.to_string()))
+}
+
+/// Scans the Rust source files under `dir` for the `actix-web` route macros.
+pub fn get_handler_routes(dir: &Path) -> anyhow::Result<BTreeSet<Route>> {
+    let mut routes = BTreeSet::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            routes.extend(get_handler_routes(&path)?);
+        } else if path.extension().map(|ext| ext == "rs").unwrap_or(false) {
+            for line in std::fs::read_to_string(&path)?.lines() {
+                if let Some(route) = parse_route_macro(line) {
+                    routes.insert(route);
+                }
+            }
+        }
+    }
+    Ok(routes)
+}
+
+/// Routes documented in the OpenAPI specification.
+pub fn get_documented_routes(openapi: &OpenApi) -> anyhow::Result<BTreeSet<Route>> {
+    let mut routes = BTreeSet::new();
+    let spec = serde_json::to_value(openapi)?;
+    if let Some(paths) = spec["paths"].as_object() {
+        for (path, path_item) in paths {
+            if let Some(operations) = path_item.as_object() {
+                for method in operations.keys() {
+                    if ROUTE_MACROS.contains(&method.as_str()) {
+                        routes.insert((method.clone(), path.clone()));
+                    }
+                }
+            }
+        }
+    }
+    Ok(routes)
+}
+
+/// Returns the routes served by the handlers in the source files under `src_dir` but missing in
+/// the specification, and the routes in the specification that aren't served by any handler.
+pub fn get_route_diff(
+    src_dir: &Path,
+    openapi: &OpenApi,
+) -> anyhow::Result<(BTreeSet<Route>, BTreeSet<Route>)> {
+    let handler_routes = get_handler_routes(src_dir)?;
+    let documented_routes = get_documented_routes(openapi)?;
+    Ok((
+        handler_routes
+            .difference(&documented_routes)
+            .cloned()
+            .collect(),
+        documented_routes
+            .difference(&handler_routes)
+            .cloned()
+            .collect(),
+    ))
+}
+
+/// Parameter names in the curly braces of a path, e.g. `era_index` of `/era/{era_index}/report`.
+fn get_path_parameter_names(path: &str) -> BTreeSet<String> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Collects the `$ref` values in the JSON value.
+fn collect_refs(value: &serde_json::Value, refs: &mut BTreeSet<String>) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                match (key.as_str(), value) {
+                    ("$ref", serde_json::Value::String(reference)) => {
+                        refs.insert(reference.clone());
+                    }
+                    _ => collect_refs(value, refs),
+                }
+            }
+        }
+        serde_json::Value::Array(array) => {
+            for value in array {
+                collect_refs(value, refs);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Checks the operations in the specification. Every operation should document exactly the
+/// path parameters of its path, a schema for each parameter, and a successful response. Every
+/// schema reference in the parameters, request and response bodies and component schemas
+/// should resolve to a component schema. Returns the descriptions of the problems.
+pub fn get_spec_errors(openapi: &OpenApi) -> anyhow::Result<Vec<String>> {
+    let mut errors = Vec::new();
+    let spec = serde_json::to_value(openapi)?;
+    if let Some(paths) = spec["paths"].as_object() {
+        for (path, path_item) in paths {
+            let path_parameter_names = get_path_parameter_names(path);
+            let operations = match path_item.as_object() {
+                Some(operations) => operations,
+                None => continue,
+            };
+            for (method, operation) in operations {
+                if !ROUTE_MACROS.contains(&method.as_str()) {
+                    continue;
+                }
+                let route = format!("{} {path}", method.to_uppercase());
+                let parameters = operation["parameters"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default();
+                let documented_path_parameter_names: BTreeSet<String> = parameters
+                    .iter()
+                    .filter(|parameter| parameter["in"] == "path")
+                    .filter_map(|parameter| parameter["name"].as_str())
+                    .map(str::to_string)
+                    .collect();
+                for name in path_parameter_names.difference(&documented_path_parameter_names) {
+                    errors.push(format!(
+                        "{route}: path parameter `{name}` is not documented."
+                    ));
+                }
+                for name in documented_path_parameter_names.difference(&path_parameter_names) {
+                    errors.push(format!(
+                        "{route}: path parameter `{name}` is not in the path."
+                    ));
+                }
+                for parameter in &parameters {
+                    if parameter["schema"].is_null() {
+                        errors.push(format!(
+                            "{route}: parameter `{}` has no schema.",
+                            parameter["name"].as_str().unwrap_or_default(),
+                        ));
+                    }
+                }
+                let has_success_response = operation["responses"]
+                    .as_object()
+                    .map(|responses| responses.keys().any(|status| status.starts_with('2')))
+                    .unwrap_or(false);
+                if !has_success_response {
+                    errors.push(format!("{route}: no successful response is documented."));
+                }
+            }
+        }
+    }
+    let mut refs = BTreeSet::new();
+    collect_refs(&spec, &mut refs);
+    for reference in refs {
+        let is_resolved = reference
+            .strip_prefix("#/components/schemas/")
+            .map(|name| !spec["components"]["schemas"][name].is_null())
+            .unwrap_or(false);
+        if !is_resolved {
+            errors.push(format!(
+                "Schema reference `{reference}` cannot be resolved."
+            ));
+        }
+    }
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_spec_errors, parse_route_macro};
+
+    #[test]
+    fn test_parse_route_macro() {
+        assert_eq!(
+            parse_route_macro(r#"#[get("/network")]"#),
+            Some(("get".to_string(), "/network".to_string()))
+        );
+        assert_eq!(
+            parse_route_macro(r#"    #[delete("/secure/user/validator/{id}")]"#),
+            Some((
+                "delete".to_string(),
+                "/secure/user/validator/{id}".to_string()
+            ))
+        );
+        assert_eq!(parse_route_macro(r#"#[serde(rename = "get")]"#), None);
+        assert_eq!(parse_route_macro("#[derive(Clone)]"), None);
+    }
+
+    #[test]
+    fn test_get_spec_errors() {
+        let openapi: utoipa::openapi::OpenApi = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Test", "version": "1.0.0" },
+            "paths": {
+                "/era/{era_index}/report": {
+                    "get": {
+                        "parameters": [
+                            { "name": "era_index", "in": "path", "required": true, "schema": { "type": "integer" } },
+                            { "name": "format", "in": "query", "required": false, "schema": { "$ref": "#/components/schemas/Format" } }
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "Report.",
+                                "content": { "application/json": { "schema": { "$ref": "#/components/schemas/EraReport" } } }
+                            }
+                        }
+                    }
+                },
+                "/validator/{account_id}": {
+                    "get": {
+                        "parameters": [
+                            { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+                        ],
+                        "responses": {
+                            "404": { "description": "Not found." }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "EraReport": { "type": "object", "properties": { "era": { "$ref": "#/components/schemas/Era" } } },
+                    "Format": { "type": "string" }
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            get_spec_errors(&openapi).unwrap(),
+            vec![
+                "GET /validator/{account_id}: path parameter `account_id` is not documented."
+                    .to_string(),
+                "GET /validator/{account_id}: path parameter `id` is not in the path.".to_string(),
+                "GET /validator/{account_id}: no successful response is documented.".to_string(),
+                "Schema reference `#/components/schemas/Era` cannot be resolved.".to_string(),
+            ]
+        );
+    }
+}
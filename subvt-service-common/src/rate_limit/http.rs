@@ -0,0 +1,303 @@
+//! Rate limiting middleware (`Transform`) for the `actix-web` REST services. Rejects requests
+//! with `429 Too Many Requests` and a `Retry-After` header when the IP address or the public key
+//! of the request has exceeded its budget, or with `503 Service Unavailable` when the budgets
+//! cannot be checked and the limiter is configured to reject requests then. The IP address is
+//! the peer address of the connection, or the forwarded client address if the peer is a trusted
+//! reverse proxy.
+//!
+//! `RateLimitFactory::new` checks the IP address and global budgets, and should be the outermost
+//! middleware, so that floods are rejected before any other work. The public key budget only
+//! applies to the requests of authenticated users, so services with signed requests should also
+//! wrap a `RateLimitFactory::per_key` inside their authentication middleware:
+//!
+//! ```ignore
+//! App::new()
+//!     .wrap(RateLimitFactory::per_key(rate_limiter.clone(), ROUTE_COSTS))
+//!     .wrap(AuthServiceFactory {})
+//!     .wrap(RateLimitFactory::new(rate_limiter.clone(), ROUTE_COSTS))
+//! ```
+use crate::rate_limit::{get_request_cost, RateLimitExceeded, RateLimiter, PUBLIC_KEY_HEADER};
+use actix_web::http::{header, StatusCode};
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpMessage, HttpResponse, ResponseError,
+};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use futures_util::FutureExt;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use subvt_types::app::User;
+use subvt_types::err::ServiceError;
+
+#[derive(Debug)]
+pub struct RateLimitError {
+    exceeded: RateLimitExceeded,
+    retry_after_secs: u64,
+}
+
+impl Display for RateLimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self.exceeded {
+            RateLimitExceeded::KeyBudget => "Too many requests with the same public key.",
+            RateLimitExceeded::GlobalBudget => "Too many requests.",
+            RateLimitExceeded::Unavailable => "Service unavailable.",
+            _ => "Too many requests from the same IP address.",
+        };
+        write!(f, "{description}")
+    }
+}
+
+impl ResponseError for RateLimitError {
+    fn status_code(&self) -> StatusCode {
+        match self.exceeded {
+            RateLimitExceeded::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header((header::RETRY_AFTER, self.retry_after_secs.to_string()))
+            .json(ServiceError::from(self.to_string().as_ref()))
+    }
+}
+
+pub struct RateLimitService<S> {
+    service: Rc<S>,
+    limiter: RateLimiter,
+    route_costs: &'static [(&'static str, u32)],
+    is_per_key: bool,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let limiter = self.limiter.clone();
+        let cost = get_request_cost(self.route_costs, request.path());
+        let is_per_key = self.is_per_key;
+        async move {
+            let maybe_exceeded = if is_per_key {
+                // the header is verified only if the authentication middleware has found the user
+                let maybe_key = if request.extensions().contains::<User>() {
+                    request
+                        .headers()
+                        .get(PUBLIC_KEY_HEADER)
+                        .and_then(|value| value.to_str().ok())
+                        .map(|value| value.trim_start_matches("0x").to_lowercase())
+                } else {
+                    None
+                };
+                match maybe_key {
+                    Some(key) => limiter.check_key(&key, cost).await,
+                    None => None,
+                }
+            } else {
+                let maybe_ip = request.peer_addr().map(|peer_addr| {
+                    limiter.get_client_ip(&peer_addr, |name| {
+                        request
+                            .headers()
+                            .get(name)
+                            .and_then(|value| value.to_str().ok())
+                    })
+                });
+                limiter.check(maybe_ip.as_deref(), cost).await
+            };
+            if let Some(exceeded) = maybe_exceeded {
+                limiter.on_rejected(exceeded);
+                return Err(RateLimitError {
+                    exceeded,
+                    retry_after_secs: limiter.get_retry_after_secs(),
+                }
+                .into());
+            }
+            service.call(request).await
+        }
+        .boxed_local()
+    }
+}
+
+/// `route_costs` are `(path pattern, cost)` pairs, e.g. `("/validator/{account_id}/details", 2)`.
+/// Requests to other routes cost `DEFAULT_REQUEST_COST`.
+pub struct RateLimitFactory {
+    limiter: RateLimiter,
+    route_costs: &'static [(&'static str, u32)],
+    is_per_key: bool,
+}
+
+impl RateLimitFactory {
+    /// Checks the IP address and global budgets.
+    pub fn new(limiter: RateLimiter, route_costs: &'static [(&'static str, u32)]) -> Self {
+        Self {
+            limiter,
+            route_costs,
+            is_per_key: false,
+        }
+    }
+
+    /// Checks the public key budget of the requests of authenticated users.
+    pub fn per_key(limiter: RateLimiter, route_costs: &'static [(&'static str, u32)]) -> Self {
+        Self {
+            limiter,
+            route_costs,
+            is_per_key: true,
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimitFactory
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RateLimitService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitService {
+            service: Rc::new(service),
+            limiter: self.limiter.clone(),
+            route_costs: self.route_costs,
+            is_per_key: self.is_per_key,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RateLimitFactory;
+    use crate::rate_limit::tests::get_unavailable_limiter;
+    use crate::rate_limit::RateLimiter;
+    use actix_web::dev::Service;
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{test, web, App, HttpMessage, HttpResponse};
+    use std::net::SocketAddr;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use subvt_config::Config;
+    use subvt_types::app::User;
+
+    /// A limiter on the test Redis with tiny budgets.
+    async fn get_limiter(per_ip_budget: u32, per_key_budget: u32) -> RateLimiter {
+        let mut config = Config::test().unwrap();
+        config.rate_limit.is_enabled = true;
+        config.rate_limit.per_ip_budget = per_ip_budget;
+        config.rate_limit.per_key_budget = per_key_budget;
+        RateLimiter::new(&config, "subvt_test_service")
+            .await
+            .unwrap()
+    }
+
+    /// Budgets are kept in Redis between the test runs, so every run uses new subjects.
+    fn get_unique_suffix() -> u32 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .subsec_nanos()
+    }
+
+    #[actix_web::test]
+    async fn test_ip_budget() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RateLimitFactory::new(get_limiter(2, 100).await, &[]))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let peer_addr = SocketAddr::from((get_unique_suffix().to_be_bytes(), 4711));
+        for _ in 0..2 {
+            let request = test::TestRequest::get().peer_addr(peer_addr).to_request();
+            assert_eq!(app.call(request).await.unwrap().status(), StatusCode::OK);
+        }
+        let request = test::TestRequest::get().peer_addr(peer_addr).to_request();
+        let response = app.call(request).await.unwrap_err().error_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key(header::RETRY_AFTER));
+        // other addresses have their own budgets
+        let request = test::TestRequest::get()
+            .peer_addr(SocketAddr::from((
+                (get_unique_suffix() ^ 1).to_be_bytes(),
+                4711,
+            )))
+            .to_request();
+        assert_eq!(app.call(request).await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_key_budget_applies_only_to_authenticated_requests() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RateLimitFactory::per_key(get_limiter(100, 1).await, &[]))
+                // stands in for the authentication middleware
+                .wrap_fn(|request, service| {
+                    if request.headers().contains_key("Authenticated") {
+                        request.extensions_mut().insert(User::default());
+                    }
+                    service.call(request)
+                })
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let key = format!("0x{:08x}", get_unique_suffix());
+        for _ in 0..2 {
+            let request = test::TestRequest::get()
+                .insert_header(("SubVT-Public-Key", key.as_str()))
+                .to_request();
+            assert_eq!(app.call(request).await.unwrap().status(), StatusCode::OK);
+        }
+        let get_authenticated_request = || {
+            test::TestRequest::get()
+                .insert_header(("SubVT-Public-Key", key.as_str()))
+                .insert_header(("Authenticated", "true"))
+                .to_request()
+        };
+        assert_eq!(
+            app.call(get_authenticated_request())
+                .await
+                .unwrap()
+                .status(),
+            StatusCode::OK
+        );
+        let response = app
+            .call(get_authenticated_request())
+            .await
+            .unwrap_err()
+            .error_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[actix_web::test]
+    async fn test_redis_error_policy() {
+        for (allow_on_redis_error, expected_status) in [
+            (true, StatusCode::OK),
+            (false, StatusCode::SERVICE_UNAVAILABLE),
+        ] {
+            let app = test::init_service(
+                App::new()
+                    .wrap(RateLimitFactory::new(
+                        get_unavailable_limiter(allow_on_redis_error).await,
+                        &[],
+                    ))
+                    .route("/", web::get().to(HttpResponse::Ok)),
+            )
+            .await;
+            let request = test::TestRequest::get()
+                .peer_addr(SocketAddr::from(([10, 0, 0, 1], 4711)))
+                .to_request();
+            let status = match app.call(request).await {
+                Ok(response) => response.status(),
+                Err(error) => error.error_response().status(),
+            };
+            assert_eq!(status, expected_status);
+        }
+    }
+}
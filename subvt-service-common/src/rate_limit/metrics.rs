@@ -0,0 +1,48 @@
+use once_cell::sync::OnceCell;
+use subvt_metrics::registry::{IntCounter, IntCounterVec};
+
+static REQUEST_COST_COUNTER: OnceCell<IntCounter> = OnceCell::new();
+static REJECTED_REQUEST_COUNTER: OnceCell<IntCounterVec> = OnceCell::new();
+static BUDGET_ERROR_COUNTER: OnceCell<IntCounter> = OnceCell::new();
+
+/// Each process runs a single service, so the metrics get registered with the prefix of the
+/// first caller.
+pub(crate) fn request_cost_counter(prefix: &str) -> IntCounter {
+    REQUEST_COST_COUNTER
+        .get_or_init(|| {
+            subvt_metrics::registry::register_int_counter(
+                prefix,
+                "rate_limit_request_cost",
+                "Total cost of the requests checked by the rate limiter",
+            )
+            .unwrap()
+        })
+        .clone()
+}
+
+pub(crate) fn rejected_request_counter(prefix: &str, limit: &str) -> IntCounter {
+    REJECTED_REQUEST_COUNTER
+        .get_or_init(|| {
+            subvt_metrics::registry::register_int_counter_vec(
+                prefix,
+                "rate_limit_rejected_request_count",
+                "Number of requests rejected by the rate limiter",
+                &["limit"],
+            )
+            .unwrap()
+        })
+        .with_label_values(&[limit])
+}
+
+pub(crate) fn budget_error_counter(prefix: &str) -> IntCounter {
+    BUDGET_ERROR_COUNTER
+        .get_or_init(|| {
+            subvt_metrics::registry::register_int_counter(
+                prefix,
+                "rate_limit_budget_error_count",
+                "Number of budget checks that have failed, e.g. when Redis is not available",
+            )
+            .unwrap()
+        })
+        .clone()
+}
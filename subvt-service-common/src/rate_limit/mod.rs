@@ -0,0 +1,449 @@
+//! Rate limiting for the public HTTP and WebSocket RPC services. Every request has a cost, and
+//! the total cost of the requests from an IP address, of the requests signed with a public key,
+//! and of all the requests to a service is limited per fixed time window. Budgets are kept in
+//! Redis, so they're shared by all the instances of a service. When the budgets cannot be
+//! checked, e.g. when Redis is down, the requests are either all allowed or all rejected,
+//! depending on the `allow_on_redis_error` configuration.
+//!
+//! The IP address of a client is the peer address of its connection. Forwarded headers are
+//! only trusted for the connections from the configured reverse proxy addresses, since they can
+//! be set by any client.
+//!
+//! `http` has the `actix-web` middleware for the REST services, and `rpc` has the `jsonrpsee`
+//! middleware and connection tracking for the WebSocket RPC servers.
+use anyhow::Context;
+use redis::aio::MultiplexedConnection;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use subvt_config::{Config, RateLimitConfig};
+use tokio::sync::OnceCell;
+
+pub mod http;
+mod metrics;
+pub mod rpc;
+
+/// Requests signed by app users carry the user's public key in this header.
+pub const PUBLIC_KEY_HEADER: &str = "SubVT-Public-Key";
+/// Cost of the requests to routes without a configured cost.
+pub const DEFAULT_REQUEST_COST: u32 = 1;
+
+/// The limit that caused a request to get rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateLimitExceeded {
+    IpBudget,
+    KeyBudget,
+    GlobalBudget,
+    Connections,
+    Subscriptions,
+    /// The budgets cannot be checked and the requests are configured to be rejected then.
+    Unavailable,
+}
+
+impl RateLimitExceeded {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::IpBudget => "ip_budget",
+            Self::KeyBudget => "key_budget",
+            Self::GlobalBudget => "global_budget",
+            Self::Connections => "connections",
+            Self::Subscriptions => "subscriptions",
+            Self::Unavailable => "unavailable",
+        }
+    }
+}
+
+/// Redis-backed fixed window request budgets, for a single service.
+#[derive(Clone)]
+pub struct RateLimiter {
+    service_name: &'static str,
+    chain: String,
+    config: RateLimitConfig,
+    trusted_proxy_ips: Arc<[IpAddr]>,
+    client: redis::Client,
+    /// Connected on the first successful attempt, so that the service can start, and the budgets
+    /// get checked once Redis is available.
+    connection: Arc<OnceCell<MultiplexedConnection>>,
+}
+
+impl RateLimiter {
+    /// `service_name` is used as the Redis key and metric prefix, e.g. `subvt_report_service`.
+    pub async fn new(config: &Config, service_name: &'static str) -> anyhow::Result<Self> {
+        let client = redis::Client::open(config.redis.url.as_str()).context(format!(
+            "Cannot connect to Redis at URL {}.",
+            config.redis.url
+        ))?;
+        let limiter = Self {
+            service_name,
+            chain: config.substrate.chain.clone(),
+            config: config.rate_limit.clone(),
+            trusted_proxy_ips: config.rate_limit.get_trusted_proxy_ips().into(),
+            client,
+            connection: Default::default(),
+        };
+        if let Err(error) = limiter.get_connection().await {
+            log::error!(
+                "Cannot connect to Redis for rate limiting, requests will be {} until connected: {:?}",
+                if limiter.config.allow_on_redis_error {
+                    "allowed"
+                } else {
+                    "rejected"
+                },
+                error,
+            );
+        }
+        Ok(limiter)
+    }
+
+    async fn get_connection(&self) -> anyhow::Result<MultiplexedConnection> {
+        Ok(self
+            .connection
+            .get_or_try_init(|| self.client.get_multiplexed_tokio_connection())
+            .await?
+            .clone())
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Gets the client IP address of a connection from a reverse proxy from the forwarded
+    /// headers, and uses the peer address for the other connections.
+    pub fn get_client_ip<'a>(
+        &self,
+        peer_addr: &SocketAddr,
+        get_header: impl Fn(&str) -> Option<&'a str>,
+    ) -> String {
+        get_client_ip(peer_addr.ip(), &self.trusted_proxy_ips, get_header).to_string()
+    }
+
+    pub(crate) fn on_rejected(&self, exceeded: RateLimitExceeded) {
+        log::debug!("Request rejected: {} limit exceeded.", exceeded.as_str());
+        metrics::rejected_request_counter(self.service_name, exceeded.as_str()).inc();
+    }
+
+    /// Adds `cost` to the spent budget of the subject within the current time window, and
+    /// returns whether the budget is exceeded.
+    async fn consume_budget(
+        &self,
+        subject_type: &str,
+        subject: &str,
+        cost: u32,
+        budget: u32,
+    ) -> anyhow::Result<bool> {
+        let window_secs = self.config.time_window_secs.max(1) as u64;
+        let window_index = self.get_window_index();
+        let key = format!(
+            "subvt:{}:rate_limit:{}:{subject_type}:{subject}:{window_index}",
+            self.chain, self.service_name,
+        );
+        let mut connection = self.get_connection().await?;
+        let (spent,): (u64,) = redis::pipe()
+            .atomic()
+            .cmd("INCRBY")
+            .arg(&key)
+            .arg(cost)
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(window_secs)
+            .ignore()
+            .query_async(&mut connection)
+            .await?;
+        Ok(spent > budget as u64)
+    }
+
+    /// Called when a budget cannot be checked. Returns `Unavailable` if the requests should be
+    /// rejected then.
+    fn on_budget_error(
+        &self,
+        budget_name: &str,
+        error: anyhow::Error,
+    ) -> Option<RateLimitExceeded> {
+        log::error!("Cannot check {budget_name} request budget: {:?}", error);
+        metrics::budget_error_counter(self.service_name).inc();
+        if self.config.allow_on_redis_error {
+            None
+        } else {
+            Some(RateLimitExceeded::Unavailable)
+        }
+    }
+
+    /// Spends `cost` from the budget of the IP address, if any, and from the global budget of
+    /// the service. Returns the exceeded limit if the request should be rejected.
+    pub async fn check(&self, maybe_ip: Option<&str>, cost: u32) -> Option<RateLimitExceeded> {
+        if !self.config.is_enabled {
+            return None;
+        }
+        metrics::request_cost_counter(self.service_name).inc_by(cost as u64);
+        if let Some(ip) = maybe_ip {
+            match self
+                .consume_budget("ip", ip, cost, self.config.per_ip_budget)
+                .await
+            {
+                Ok(true) => return Some(RateLimitExceeded::IpBudget),
+                Ok(false) => (),
+                Err(error) => return self.on_budget_error("IP", error),
+            }
+        }
+        match self
+            .consume_budget("global", "all", cost, self.config.global_budget)
+            .await
+        {
+            Ok(true) => Some(RateLimitExceeded::GlobalBudget),
+            Ok(false) => None,
+            Err(error) => self.on_budget_error("global", error),
+        }
+    }
+
+    /// Spends `cost` from the budget of the public key of an authenticated request. Returns the
+    /// exceeded limit if the request should be rejected.
+    pub async fn check_key(&self, key: &str, cost: u32) -> Option<RateLimitExceeded> {
+        if !self.config.is_enabled {
+            return None;
+        }
+        match self
+            .consume_budget("key", key, cost, self.config.per_key_budget)
+            .await
+        {
+            Ok(true) => Some(RateLimitExceeded::KeyBudget),
+            Ok(false) => None,
+            Err(error) => self.on_budget_error("public key", error),
+        }
+    }
+
+    fn get_now_secs() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Index of the current time window since the Unix epoch.
+    pub(crate) fn get_window_index(&self) -> u64 {
+        Self::get_now_secs() / self.config.time_window_secs.max(1) as u64
+    }
+
+    /// Number of seconds until the budgets are reset.
+    pub fn get_retry_after_secs(&self) -> u64 {
+        let window_secs = self.config.time_window_secs.max(1) as u64;
+        window_secs - Self::get_now_secs() % window_secs
+    }
+}
+
+/// Gets the cost of a request from the `(path pattern, cost)` pairs, where path segments in
+/// curly braces match any segment, e.g. `/validator/{account_id}/details`.
+pub fn get_request_cost(route_costs: &[(&str, u32)], path: &str) -> u32 {
+    route_costs
+        .iter()
+        .find(|(pattern, _)| path_matches(pattern, path))
+        .map(|(_, cost)| *cost)
+        .unwrap_or(DEFAULT_REQUEST_COST)
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern_segments = pattern.trim_end_matches('/').split('/');
+    let mut path_segments = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return true,
+            (Some(pattern_segment), Some(path_segment)) => {
+                let is_parameter =
+                    pattern_segment.starts_with('{') && pattern_segment.ends_with('}');
+                if !is_parameter && pattern_segment != path_segment {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Parses an address in a forwarded header, with or without the port, e.g. `10.0.0.1`,
+/// `10.0.0.1:4711`, `"[2001:db8::1]:4711"` or `2001:db8::1`.
+fn parse_forwarded_ip(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"');
+    value
+        .parse::<IpAddr>()
+        .ok()
+        .or_else(|| value.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .or_else(|| {
+            value
+                .strip_prefix('[')
+                .and_then(|value| value.strip_suffix(']'))
+                .and_then(|value| value.parse::<IpAddr>().ok())
+        })
+}
+
+/// Addresses in the `Forwarded`, `X-Forwarded-For` or `X-Real-IP` header, in the order they were
+/// added by the proxies, i.e. the client first.
+fn get_forwarded_addresses<'a>(get_header: impl Fn(&str) -> Option<&'a str>) -> Vec<&'a str> {
+    if let Some(forwarded) = get_header("forwarded") {
+        return forwarded
+            .split(',')
+            .filter_map(|element| {
+                element
+                    .split(';')
+                    .map(str::trim)
+                    .find_map(|pair| pair.strip_prefix("for="))
+            })
+            .collect();
+    }
+    if let Some(forwarded_for) = get_header("x-forwarded-for") {
+        return forwarded_for.split(',').map(str::trim).collect();
+    }
+    get_header("x-real-ip").into_iter().collect()
+}
+
+/// The client IP address is the peer address, unless the peer is a trusted proxy. Then the
+/// forwarded addresses are walked from the last one added, and the first address that is not a
+/// trusted proxy is the client address. A client can prepend any address to the forwarded
+/// headers, but cannot change the ones appended by the trusted proxies.
+pub fn get_client_ip<'a>(
+    peer_ip: IpAddr,
+    trusted_proxy_ips: &[IpAddr],
+    get_header: impl Fn(&str) -> Option<&'a str>,
+) -> IpAddr {
+    let mut client_ip = peer_ip;
+    if !trusted_proxy_ips.contains(&client_ip) {
+        return client_ip;
+    }
+    for address in get_forwarded_addresses(get_header).into_iter().rev() {
+        match parse_forwarded_ip(address) {
+            Some(ip) => {
+                client_ip = ip;
+                if !trusted_proxy_ips.contains(&ip) {
+                    break;
+                }
+            }
+            None => break,
+        }
+    }
+    client_ip
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{
+        get_client_ip, get_request_cost, RateLimitExceeded, RateLimiter, DEFAULT_REQUEST_COST,
+    };
+    use std::net::IpAddr;
+    use subvt_config::Config;
+
+    /// Nothing listens on port 1, so the connections are refused.
+    const UNAVAILABLE_REDIS_URL: &str = "redis://127.0.0.1:1/";
+
+    /// A limiter whose budgets cannot be checked.
+    pub(crate) async fn get_unavailable_limiter(allow_on_redis_error: bool) -> RateLimiter {
+        let mut config = Config::test().unwrap();
+        config.redis.url = UNAVAILABLE_REDIS_URL.to_string();
+        config.rate_limit.is_enabled = true;
+        config.rate_limit.allow_on_redis_error = allow_on_redis_error;
+        RateLimiter::new(&config, "subvt_test_service")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_redis_error_policy() {
+        let limiter = get_unavailable_limiter(true).await;
+        assert_eq!(limiter.check(Some("10.0.0.1"), 1).await, None);
+        assert_eq!(limiter.check(None, 1).await, None);
+        assert_eq!(limiter.check_key("0x01", 1).await, None);
+        let limiter = get_unavailable_limiter(false).await;
+        assert_eq!(
+            limiter.check(Some("10.0.0.1"), 1).await,
+            Some(RateLimitExceeded::Unavailable)
+        );
+        assert_eq!(
+            limiter.check(None, 1).await,
+            Some(RateLimitExceeded::Unavailable)
+        );
+        assert_eq!(
+            limiter.check_key("0x01", 1).await,
+            Some(RateLimitExceeded::Unavailable)
+        );
+    }
+
+    const ROUTE_COSTS: &[(&str, u32)] = &[
+        ("/validator/list", 5),
+        ("/validator/{account_id}/chart/{chart_type}", 10),
+    ];
+
+    #[test]
+    fn test_get_request_cost() {
+        assert_eq!(get_request_cost(ROUTE_COSTS, "/validator/list"), 5);
+        assert_eq!(get_request_cost(ROUTE_COSTS, "/validator/list/"), 5);
+        assert_eq!(
+            get_request_cost(ROUTE_COSTS, "/validator/list/active"),
+            DEFAULT_REQUEST_COST
+        );
+        assert_eq!(
+            get_request_cost(ROUTE_COSTS, "/validator/abc/chart/reward"),
+            10
+        );
+        assert_eq!(
+            get_request_cost(ROUTE_COSTS, "/validator/abc/chart"),
+            DEFAULT_REQUEST_COST
+        );
+    }
+
+    fn get_ip(
+        peer_ip: &str,
+        trusted_proxy_ips: &[&str],
+        headers: &[(&'static str, &'static str)],
+    ) -> String {
+        let trusted_proxy_ips: Vec<IpAddr> = trusted_proxy_ips
+            .iter()
+            .map(|ip| ip.parse().unwrap())
+            .collect();
+        get_client_ip(peer_ip.parse().unwrap(), &trusted_proxy_ips, |name| {
+            headers
+                .iter()
+                .find(|(header_name, _)| *header_name == name)
+                .map(|(_, value)| *value)
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_get_client_ip() {
+        let proxies = ["127.0.0.1", "10.0.0.100"];
+        // direct connections cannot spoof their address
+        assert_eq!(
+            get_ip("10.0.0.9", &proxies, &[("x-forwarded-for", "10.0.0.1")]),
+            "10.0.0.9"
+        );
+        assert_eq!(get_ip("127.0.0.1", &proxies, &[]), "127.0.0.1");
+        assert_eq!(
+            get_ip("127.0.0.1", &proxies, &[("x-real-ip", "10.0.0.2")]),
+            "10.0.0.2"
+        );
+        // the address prepended by the client is ignored
+        assert_eq!(
+            get_ip(
+                "127.0.0.1",
+                &proxies,
+                &[("x-forwarded-for", "10.0.0.1, 10.0.0.3, 10.0.0.100")]
+            ),
+            "10.0.0.3"
+        );
+        assert_eq!(
+            get_ip(
+                "127.0.0.1",
+                &proxies,
+                &[
+                    (
+                        "forwarded",
+                        "for=10.0.0.1, for=\"[2001:db8::1]:4711\";proto=https"
+                    ),
+                    ("x-forwarded-for", "10.0.0.5"),
+                ]
+            ),
+            "2001:db8::1"
+        );
+        assert_eq!(
+            get_ip("127.0.0.1", &proxies, &[("x-forwarded-for", "invalid")]),
+            "127.0.0.1"
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! Rate limiting for the `jsonrpsee` WebSocket RPC servers. `RpcRateLimitLayer` is a `tower`
+//! middleware that spends the IP budget for every HTTP request, including the WebSocket upgrade
+//! requests, and rejects upgrades from IP addresses that have reached the max number of
+//! concurrent connections. `RpcConnectionLogger` tracks the open connections and subscriptions
+//! per IP address, and spends the budgets for the calls on the WebSocket connections with the
+//! cost of the method. Both should be set on the same server, along with the max subscriptions
+//! per connection, and the subscription callbacks should start with `reject_if_rate_limited`:
+//!
+//! ```ignore
+//! let (middleware, logger) = rpc::build(&rate_limiter, METHOD_COSTS);
+//! let server = ServerBuilder::default()
+//!     .set_middleware(middleware)
+//!     .set_logger(logger)
+//!     .max_subscriptions_per_connection(rate_limiter.config().max_subscriptions_per_connection)
+//!     .build(address)
+//!     .await?;
+//! ```
+//!
+//! The `jsonrpsee` middleware doesn't have access to the peer address, but the server calls the
+//! logger with the peer address while the middleware is handling the request. The middleware
+//! passes a `PeerAddrSlot` in the request extensions, which the logger fills in.
+use crate::rate_limit::{get_request_cost, RateLimitExceeded, RateLimiter, DEFAULT_REQUEST_COST};
+use futures_util::future::BoxFuture;
+use hyper::{header, Body, Request, Response, StatusCode};
+use jsonrpsee::server::logger::{HttpRequest, Logger, MethodKind, Params, TransportProtocol};
+use jsonrpsee::types::ErrorObject;
+use jsonrpsee::SubscriptionSink;
+use rustc_hash::FxHashMap as HashMap;
+use std::cell::Cell;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// JSON-RPC error code of the responses to rate-limited requests.
+pub const RATE_LIMIT_ERROR_CODE: i32 = -32029;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+thread_local! {
+    /// Limit exceeded by the call that is being dispatched on this thread, if any. The server
+    /// calls `Logger::on_call` and then the subscription callback synchronously on the same
+    /// thread, so the callback gets the verdict of the logger through this.
+    static CALL_VERDICT: Cell<Option<RateLimitExceeded>> = const { Cell::new(None) };
+}
+
+/// Rejects the subscription with a rate limiting error if the subscription call has exceeded a
+/// limit, in which case the subscription callback should return without doing anything else.
+pub fn reject_if_rate_limited(sink: &mut SubscriptionSink) -> bool {
+    match CALL_VERDICT.with(|verdict| verdict.take()) {
+        Some(exceeded) => {
+            let _ = sink.reject(ErrorObject::owned(
+                RATE_LIMIT_ERROR_CODE,
+                get_message(exceeded),
+                None::<()>,
+            ));
+            true
+        }
+        None => false,
+    }
+}
+
+fn get_message(exceeded: RateLimitExceeded) -> &'static str {
+    match exceeded {
+        RateLimitExceeded::Connections => "Too many connections from the same IP address.",
+        RateLimitExceeded::Subscriptions => "Too many subscriptions from the same IP address.",
+        RateLimitExceeded::GlobalBudget => "Too many requests.",
+        RateLimitExceeded::Unavailable => "Service unavailable.",
+        _ => "Too many requests from the same IP address.",
+    }
+}
+
+/// Peer address of the request, set by `RpcConnectionLogger::on_connect`.
+#[derive(Clone, Default)]
+struct PeerAddrSlot(Arc<Mutex<Option<SocketAddr>>>);
+
+#[derive(Default)]
+struct Connections {
+    ip_by_remote_addr: HashMap<SocketAddr, String>,
+    count_by_ip: HashMap<String, u32>,
+    subscription_count_by_remote_addr: HashMap<SocketAddr, u32>,
+    subscription_count_by_ip: HashMap<String, u32>,
+    /// Window index until the end of which the IP address budget is known to be exceeded.
+    exceeded_ip_budget_windows: HashMap<String, u64>,
+    exceeded_global_budget_window: Option<u64>,
+    /// Window index in which the budgets could not be checked, and the calls are rejected.
+    unavailable_window: Option<u64>,
+}
+
+/// Open WebSocket connections and subscriptions per client IP address, shared by the middleware
+/// and the logger, along with the budgets known to be exceeded for the WebSocket calls.
+#[derive(Clone, Default)]
+pub struct ConnectionCounter {
+    connections: Arc<Mutex<Connections>>,
+}
+
+impl ConnectionCounter {
+    fn get_count(&self, ip: &str) -> u32 {
+        let connections = self.connections.lock().unwrap();
+        connections.count_by_ip.get(ip).copied().unwrap_or(0)
+    }
+
+    fn on_connect(&self, remote_addr: SocketAddr, ip: String) {
+        let mut connections = self.connections.lock().unwrap();
+        *connections.count_by_ip.entry(ip.clone()).or_insert(0) += 1;
+        connections.ip_by_remote_addr.insert(remote_addr, ip);
+    }
+
+    fn on_disconnect(&self, remote_addr: &SocketAddr) {
+        let mut connections = self.connections.lock().unwrap();
+        let subscription_count = connections
+            .subscription_count_by_remote_addr
+            .remove(remote_addr)
+            .unwrap_or(0);
+        if let Some(ip) = connections.ip_by_remote_addr.remove(remote_addr) {
+            if let Some(count) = connections.count_by_ip.get_mut(&ip) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    connections.count_by_ip.remove(&ip);
+                }
+            }
+            if let Some(count) = connections.subscription_count_by_ip.get_mut(&ip) {
+                *count = count.saturating_sub(subscription_count);
+                if *count == 0 {
+                    connections.subscription_count_by_ip.remove(&ip);
+                }
+            }
+        }
+    }
+
+    /// Adds a subscription for the connection, unless the IP address is at the limit.
+    /// Subscriptions rejected by the server or by the subscription callback are counted until
+    /// the connection is closed.
+    fn try_subscribe(&self, remote_addr: SocketAddr, ip: &str, max_subscriptions: u32) -> bool {
+        let mut connections = self.connections.lock().unwrap();
+        let ip_count = connections
+            .subscription_count_by_ip
+            .entry(ip.to_string())
+            .or_insert(0);
+        if *ip_count >= max_subscriptions {
+            return false;
+        }
+        *ip_count += 1;
+        *connections
+            .subscription_count_by_remote_addr
+            .entry(remote_addr)
+            .or_insert(0) += 1;
+        true
+    }
+
+    /// Unsubscriptions of unknown subscription ids are counted too, but the count of a
+    /// connection cannot go below zero, and the subscriptions of a connection are still limited
+    /// by the server.
+    fn unsubscribe(&self, remote_addr: &SocketAddr, ip: &str) {
+        let mut connections = self.connections.lock().unwrap();
+        match connections
+            .subscription_count_by_remote_addr
+            .get_mut(remote_addr)
+        {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => return,
+        }
+        if let Some(count) = connections.subscription_count_by_ip.get_mut(ip) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    fn get_exceeded_budget(&self, ip: &str, window_index: u64) -> Option<RateLimitExceeded> {
+        let mut connections = self.connections.lock().unwrap();
+        connections
+            .exceeded_ip_budget_windows
+            .retain(|_, exceeded_window_index| *exceeded_window_index >= window_index);
+        if connections.unavailable_window == Some(window_index) {
+            Some(RateLimitExceeded::Unavailable)
+        } else if connections.exceeded_global_budget_window == Some(window_index) {
+            Some(RateLimitExceeded::GlobalBudget)
+        } else if connections.exceeded_ip_budget_windows.contains_key(ip) {
+            Some(RateLimitExceeded::IpBudget)
+        } else {
+            None
+        }
+    }
+
+    fn on_budget_exceeded(&self, ip: &str, exceeded: RateLimitExceeded, window_index: u64) {
+        let mut connections = self.connections.lock().unwrap();
+        match exceeded {
+            RateLimitExceeded::GlobalBudget => {
+                connections.exceeded_global_budget_window = Some(window_index)
+            }
+            RateLimitExceeded::Unavailable => connections.unavailable_window = Some(window_index),
+            _ => {
+                connections
+                    .exceeded_ip_budget_windows
+                    .insert(ip.to_string(), window_index);
+            }
+        }
+    }
+}
+
+/// Builds the middleware and the logger for a server, sharing the same connection counter.
+/// `method_costs` are the `(method name, cost)` pairs of the WebSocket calls, other methods
+/// cost `DEFAULT_REQUEST_COST`.
+pub fn build(
+    limiter: &RateLimiter,
+    method_costs: &'static [(&'static str, u32)],
+) -> (
+    tower::ServiceBuilder<
+        tower::layer::util::Stack<RpcRateLimitLayer, tower::layer::util::Identity>,
+    >,
+    RpcConnectionLogger,
+) {
+    let connection_counter = ConnectionCounter::default();
+    (
+        tower::ServiceBuilder::new().layer(RpcRateLimitLayer {
+            limiter: limiter.clone(),
+            connection_counter: connection_counter.clone(),
+        }),
+        RpcConnectionLogger {
+            limiter: limiter.clone(),
+            method_costs,
+            connection_counter,
+            client: Arc::new(Mutex::new(None)),
+        },
+    )
+}
+
+#[derive(Clone)]
+pub struct RpcRateLimitLayer {
+    limiter: RateLimiter,
+    connection_counter: ConnectionCounter,
+}
+
+impl<S> Layer<S> for RpcRateLimitLayer {
+    type Service = RpcRateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+            connection_counter: self.connection_counter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RpcRateLimit<S> {
+    inner: S,
+    limiter: RateLimiter,
+    connection_counter: ConnectionCounter,
+}
+
+fn is_websocket_upgrade(request: &Request<Body>) -> bool {
+    request
+        .headers()
+        .get(header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false)
+}
+
+fn get_rate_limited_response(exceeded: RateLimitExceeded, retry_after_secs: u64) -> Response<Body> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": RATE_LIMIT_ERROR_CODE,
+            "message": get_message(exceeded),
+        },
+        "id": null,
+    });
+    let status = match exceeded {
+        RateLimitExceeded::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::TOO_MANY_REQUESTS,
+    };
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::RETRY_AFTER, retry_after_secs.to_string())
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+impl<S> Service<Request<Body>> for RpcRateLimit<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        // the ready inner service is taken, leaving the clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+        let connection_counter = self.connection_counter.clone();
+        let is_websocket_upgrade = is_websocket_upgrade(&request);
+        let peer_addr_slot = PeerAddrSlot::default();
+        request.extensions_mut().insert(peer_addr_slot.clone());
+        let headers = request.headers().clone();
+        // the server calls the logger with the peer address when the request is accepted, and
+        // handles the request only when the returned future is polled
+        let future = inner.call(request);
+        let maybe_remote_addr = *peer_addr_slot.0.lock().unwrap();
+        let maybe_ip_and_remote_addr = maybe_remote_addr.map(|remote_addr| {
+            let ip = limiter.get_client_ip(&remote_addr, |name| {
+                headers.get(name).and_then(|value| value.to_str().ok())
+            });
+            (ip, remote_addr)
+        });
+        Box::pin(async move {
+            // requests rejected by the server, e.g. for a disallowed host, don't get here
+            let (ip, remote_addr) = match maybe_ip_and_remote_addr {
+                Some(ip_and_remote_addr) => ip_and_remote_addr,
+                None => return future.await,
+            };
+            let mut maybe_exceeded = None;
+            if is_websocket_upgrade
+                && limiter.config().is_enabled
+                && connection_counter.get_count(&ip) >= limiter.config().max_connections_per_ip
+            {
+                maybe_exceeded = Some(RateLimitExceeded::Connections);
+            }
+            if maybe_exceeded.is_none() {
+                maybe_exceeded = limiter.check(Some(&ip), DEFAULT_REQUEST_COST).await;
+            }
+            if let Some(exceeded) = maybe_exceeded {
+                limiter.on_rejected(exceeded);
+                // the upgrade doesn't complete without the switching protocols response
+                return Ok(get_rate_limited_response(
+                    exceeded,
+                    limiter.get_retry_after_secs(),
+                ));
+            }
+            let response = future.await?;
+            if is_websocket_upgrade && response.status() == StatusCode::SWITCHING_PROTOCOLS {
+                connection_counter.on_connect(remote_addr, ip);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Tracks the open WebSocket connections and subscriptions per client IP address for
+/// `RpcRateLimit`, and spends the budgets for the calls on the WebSocket connections.
+///
+/// The server clones the logger for every connection, and the clone gets the client of the
+/// connection in `on_connect`. Clones of that clone, such as the one that handles the calls on
+/// the WebSocket connection, share the client.
+pub struct RpcConnectionLogger {
+    limiter: RateLimiter,
+    method_costs: &'static [(&'static str, u32)],
+    connection_counter: ConnectionCounter,
+    /// Peer address and client IP address of the connection.
+    client: Arc<Mutex<Option<(SocketAddr, String)>>>,
+}
+
+impl Clone for RpcConnectionLogger {
+    fn clone(&self) -> Self {
+        let client = if self.client.lock().unwrap().is_some() {
+            self.client.clone()
+        } else {
+            Arc::new(Mutex::new(None))
+        };
+        Self {
+            limiter: self.limiter.clone(),
+            method_costs: self.method_costs,
+            connection_counter: self.connection_counter.clone(),
+            client,
+        }
+    }
+}
+
+impl RpcConnectionLogger {
+    /// Returns the limit exceeded by the call, if any. The budgets are spent asynchronously, so
+    /// a call is rejected when the budget has been exceeded by an earlier call in the same time
+    /// window.
+    fn check_call(
+        &self,
+        remote_addr: SocketAddr,
+        ip: &str,
+        method_name: &str,
+        kind: &MethodKind,
+    ) -> Option<RateLimitExceeded> {
+        let config = self.limiter.config();
+        if !config.is_enabled {
+            return None;
+        }
+        let window_index = self.limiter.get_window_index();
+        if let Some(exceeded) = self
+            .connection_counter
+            .get_exceeded_budget(ip, window_index)
+        {
+            return Some(exceeded);
+        }
+        if let MethodKind::Subscription = kind {
+            if !self.connection_counter.try_subscribe(
+                remote_addr,
+                ip,
+                config.max_subscriptions_per_ip,
+            ) {
+                return Some(RateLimitExceeded::Subscriptions);
+            }
+        }
+        let cost = get_request_cost(self.method_costs, method_name);
+        let limiter = self.limiter.clone();
+        let connection_counter = self.connection_counter.clone();
+        let ip = ip.to_string();
+        tokio::spawn(async move {
+            if let Some(exceeded) = limiter.check(Some(&ip), cost).await {
+                connection_counter.on_budget_exceeded(&ip, exceeded, window_index);
+            }
+        });
+        None
+    }
+}
+
+impl Logger for RpcConnectionLogger {
+    type Instant = ();
+
+    fn on_connect(&self, remote_addr: SocketAddr, request: &HttpRequest, _t: TransportProtocol) {
+        if let Some(slot) = request.extensions().get::<PeerAddrSlot>() {
+            *slot.0.lock().unwrap() = Some(remote_addr);
+        }
+        let ip = self.limiter.get_client_ip(&remote_addr, |name| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        });
+        *self.client.lock().unwrap() = Some((remote_addr, ip));
+    }
+
+    fn on_request(&self, _t: TransportProtocol) -> Self::Instant {}
+
+    fn on_call(&self, method_name: &str, _params: Params, kind: MethodKind, t: TransportProtocol) {
+        CALL_VERDICT.with(|verdict| verdict.set(None));
+        // HTTP calls are limited by the middleware
+        if !matches!(t, TransportProtocol::WebSocket) {
+            return;
+        }
+        let (remote_addr, ip) = match self.client.lock().unwrap().clone() {
+            Some(client) => client,
+            None => return,
+        };
+        let maybe_exceeded = match kind {
+            // always allowed, so that the clients can release their subscriptions
+            MethodKind::Unsubscription => {
+                self.connection_counter.unsubscribe(&remote_addr, &ip);
+                None
+            }
+            kind => self.check_call(remote_addr, &ip, method_name, &kind),
+        };
+        if let Some(exceeded) = maybe_exceeded {
+            self.limiter.on_rejected(exceeded);
+        }
+        CALL_VERDICT.with(|verdict| verdict.set(maybe_exceeded));
+    }
+
+    fn on_result(
+        &self,
+        _method_name: &str,
+        _success: bool,
+        _started_at: Self::Instant,
+        _t: TransportProtocol,
+    ) {
+        // clear the verdict of a call whose callback didn't read it
+        CALL_VERDICT.with(|verdict| verdict.set(None));
+    }
+
+    fn on_response(&self, _result: &str, _started_at: Self::Instant, _t: TransportProtocol) {}
+
+    fn on_disconnect(&self, remote_addr: SocketAddr, t: TransportProtocol) {
+        if let TransportProtocol::WebSocket = t {
+            self.connection_counter.on_disconnect(&remote_addr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, ConnectionCounter};
+    use crate::rate_limit::tests::get_unavailable_limiter;
+    use crate::rate_limit::{RateLimitExceeded, RateLimiter};
+    use jsonrpsee::server::ServerBuilder;
+    use jsonrpsee::RpcModule;
+    use std::net::SocketAddr;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    /// Makes a JSON-RPC call over HTTP to a server with the limiter, and returns the status line
+    /// of the response.
+    async fn call(limiter: &RateLimiter) -> String {
+        let (middleware, logger) = build(limiter, &[]);
+        let server = ServerBuilder::default()
+            .set_middleware(middleware)
+            .set_logger(logger)
+            .build("127.0.0.1:0")
+            .await
+            .unwrap();
+        let address = server.local_addr().unwrap();
+        let mut module = RpcModule::new(());
+        module.register_method("ping", |_, _| Ok("pong")).unwrap();
+        let handle = server.start(module).unwrap();
+        let body = r#"{"jsonrpc":"2.0","method":"ping","params":[],"id":1}"#;
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: {address}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        handle.stop().unwrap();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_redis_error_policy() {
+        let status_line = call(&get_unavailable_limiter(true).await).await;
+        assert!(status_line.contains("200"), "{status_line}");
+        let status_line = call(&get_unavailable_limiter(false).await).await;
+        assert!(status_line.contains("503"), "{status_line}");
+    }
+
+    #[test]
+    fn test_subscription_count() {
+        let counter = ConnectionCounter::default();
+        let first = SocketAddr::from(([10, 0, 0, 1], 1));
+        let second = SocketAddr::from(([10, 0, 0, 1], 2));
+        counter.on_connect(first, "10.0.0.1".to_string());
+        counter.on_connect(second, "10.0.0.1".to_string());
+        assert_eq!(counter.get_count("10.0.0.1"), 2);
+        assert!(counter.try_subscribe(first, "10.0.0.1", 2));
+        assert!(counter.try_subscribe(second, "10.0.0.1", 2));
+        assert!(!counter.try_subscribe(second, "10.0.0.1", 2));
+        // unknown subscriptions of the connection don't release the others' subscriptions
+        counter.unsubscribe(&second, "10.0.0.1");
+        counter.unsubscribe(&second, "10.0.0.1");
+        assert!(counter.try_subscribe(first, "10.0.0.1", 2));
+        assert!(!counter.try_subscribe(second, "10.0.0.1", 2));
+        counter.on_disconnect(&first);
+        assert_eq!(counter.get_count("10.0.0.1"), 1);
+        assert!(counter.try_subscribe(second, "10.0.0.1", 2));
+    }
+
+    #[test]
+    fn test_exceeded_budget_window() {
+        let counter = ConnectionCounter::default();
+        counter.on_budget_exceeded("10.0.0.1", RateLimitExceeded::IpBudget, 5);
+        assert_eq!(
+            counter.get_exceeded_budget("10.0.0.1", 5),
+            Some(RateLimitExceeded::IpBudget)
+        );
+        assert_eq!(counter.get_exceeded_budget("10.0.0.2", 5), None);
+        assert_eq!(counter.get_exceeded_budget("10.0.0.1", 6), None);
+        counter.on_budget_exceeded("10.0.0.1", RateLimitExceeded::Unavailable, 6);
+        assert_eq!(
+            counter.get_exceeded_budget("10.0.0.2", 6),
+            Some(RateLimitExceeded::Unavailable)
+        );
+        assert_eq!(counter.get_exceeded_budget("10.0.0.2", 7), None);
+    }
+}
@@ -22,10 +22,16 @@ use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use subvt_config::Config;
+use subvt_types::app::event::conviction_voting::ConvictionVotingDelegation;
 use subvt_types::app::event::democracy::{AccountVote, ConvictionVote};
 use subvt_types::crypto::AccountId;
+use subvt_types::governance::call::ReferendumCall;
+use subvt_types::governance::referendum::{
+    FellowshipTally, OngoingReferendum, ProposalCall, ReferendumInfo, TrackInfo,
+};
 use subvt_types::substrate::democracy::{
-    get_democracy_conviction_u8, DelegatedVote, DirectVote, ReferendumVote, VoteType,
+    get_conviction_votes, get_democracy_conviction_u8, DelegatedVote, DirectVote, ReferendumVote,
+    VoteType,
 };
 use subvt_types::substrate::error::DecodeError;
 use subvt_types::substrate::metadata::{
@@ -48,6 +54,9 @@ use tokio::time::timeout;
 mod storage_utility;
 
 const KEY_QUERY_PAGE_SIZE: usize = 1000;
+const REFERENDA_PALLET: &str = "Referenda";
+const FELLOWSHIP_REFERENDA_PALLET: &str = "FellowshipReferenda";
+const CONVICTION_VOTING_PALLET: &str = "ConvictionVoting";
 
 /// The client.
 pub struct SubstrateClient {
@@ -1352,6 +1361,76 @@ impl SubstrateClient {
         Ok(None)
     }
 
+    /// Get all OpenGov delegations from the `ConvictionVoting.VotingFor` storage at the given
+    /// block. Returns an empty list if the runtime doesn't have the pallet.
+    pub async fn get_conviction_voting_delegations(
+        &self,
+        block_number: u64,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<ConvictionVotingDelegation>> {
+        if !self
+            .metadata
+            .pallets
+            .iter()
+            .any(|pallet| pallet.name == CONVICTION_VOTING_PALLET)
+        {
+            return Ok(Vec::new());
+        }
+        let keys = self
+            .get_all_keys_for_storage(CONVICTION_VOTING_PALLET, "VotingFor", block_hash)
+            .await?;
+        let mut delegations = Vec::new();
+        for chunk in keys.chunks(KEY_QUERY_PAGE_SIZE) {
+            let chunk_values: Vec<StorageChangeSet<String>> = self
+                .ws_client
+                .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
+                .await?;
+            let change_set = chunk_values.first().ok_or_else(|| {
+                DecodeError::Error(format!(
+                    "Empty conviction voting storage query result at block {block_hash}."
+                ))
+            })?;
+            for (storage_key, data) in &change_set.changes {
+                if let Some(data) = data {
+                    // Twox64Concat hashed key ends with the encoded account id, then the hash
+                    // and the encoded track id
+                    let key = &storage_key.0;
+                    if key.len() < 32 + 8 + 2 {
+                        return Err(DecodeError::Error(format!(
+                            "Invalid conviction voting storage key 0x{}.",
+                            hex::encode(key),
+                        ))
+                        .into());
+                    }
+                    let track_id: u16 = Decode::decode(&mut &key[key.len() - 2..])?;
+                    let delegator_account_id: AccountId =
+                        Decode::decode(&mut &key[key.len() - 2 - 8 - 32..key.len() - 2 - 8])?;
+                    let mut bytes: &[u8] = &data.0;
+                    let voting: ConvictionVoting<
+                        Balance,
+                        AccountId,
+                        BlockNumber,
+                        u32,
+                        ConstU32<{ u32::MAX }>,
+                    > = Decode::decode(&mut bytes)?;
+                    if let ConvictionVoting::Delegating(delegating) = voting {
+                        let conviction = get_democracy_conviction_u8(&delegating.conviction);
+                        delegations.push(ConvictionVotingDelegation {
+                            delegator_account_id,
+                            delegate_account_id: delegating.target,
+                            track_id,
+                            conviction,
+                            balance: delegating.balance,
+                            votes: get_conviction_votes(conviction, delegating.balance),
+                            block_number,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(delegations)
+    }
+
     pub async fn get_democracy_voting_of(
         &self,
         account_id: &AccountId,
@@ -1547,6 +1626,280 @@ impl SubstrateClient {
         Ok(None)
     }
 
+    /// Get the OpenGov track definitions from the runtime metadata.
+    pub fn get_referendum_tracks(&self) -> anyhow::Result<Vec<(u16, TrackInfo)>> {
+        get_metadata_constant(&self.metadata, REFERENDA_PALLET, "Tracks")
+    }
+
+    /// Whether the runtime has the `FellowshipReferenda` pallet, i.e. Kusama.
+    pub fn has_fellowship_referenda(&self) -> bool {
+        self.metadata
+            .pallets
+            .iter()
+            .any(|pallet| pallet.name == FELLOWSHIP_REFERENDA_PALLET)
+    }
+
+    /// Get the Fellowship track definitions from the runtime metadata.
+    pub fn get_fellowship_referendum_tracks(&self) -> anyhow::Result<Vec<(u16, TrackInfo)>> {
+        get_metadata_constant(&self.metadata, FELLOWSHIP_REFERENDA_PALLET, "Tracks")
+    }
+
+    /// Get all OpenGov referenda, ongoing and finished, at the given block.
+    pub async fn get_referenda(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<(u32, ReferendumInfo)>> {
+        let keys = self
+            .get_all_keys_for_storage(REFERENDA_PALLET, "ReferendumInfoFor", block_hash)
+            .await?;
+        self.get_referenda_by_storage_keys(REFERENDA_PALLET, &keys, block_hash)
+            .await
+    }
+
+    /// Get all Fellowship referenda, ongoing and finished, at the given block.
+    pub async fn get_fellowship_referenda(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<(u32, ReferendumInfo<FellowshipTally>)>> {
+        let keys = self
+            .get_all_keys_for_storage(FELLOWSHIP_REFERENDA_PALLET, "ReferendumInfoFor", block_hash)
+            .await?;
+        self.get_referenda_by_storage_keys(FELLOWSHIP_REFERENDA_PALLET, &keys, block_hash)
+            .await
+    }
+
+    /// Get the OpenGov referenda with the given indices at the given block. Non-existent
+    /// referenda are skipped.
+    pub async fn get_referenda_by_index(
+        &self,
+        indices: &[u32],
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<(u32, ReferendumInfo)>> {
+        let keys: Vec<String> = indices
+            .iter()
+            .map(|index| {
+                get_storage_map_key(&self.metadata, REFERENDA_PALLET, "ReferendumInfoFor", index)
+            })
+            .collect();
+        self.get_referenda_by_storage_keys(REFERENDA_PALLET, &keys, block_hash)
+            .await
+    }
+
+    async fn get_referenda_by_storage_keys<T: Decode>(
+        &self,
+        pallet_name: &str,
+        keys: &[String],
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<(u32, ReferendumInfo<T>)>> {
+        let mut referenda = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(KEY_QUERY_PAGE_SIZE) {
+            let chunk_values: Vec<StorageChangeSet<String>> = self
+                .ws_client
+                .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
+                .await?;
+            let change_set = chunk_values.first().ok_or_else(|| {
+                DecodeError::Error(format!(
+                    "Empty {pallet_name} referenda storage query result at block {block_hash}."
+                ))
+            })?;
+            for (storage_key, data) in &change_set.changes {
+                if let Some(data) = data {
+                    // Blake2_128Concat hashed key ends with the encoded referendum index
+                    let index: u32 =
+                        Decode::decode(&mut &storage_key.0[storage_key.0.len() - 4..])?;
+                    let mut bytes: &[u8] = &data.0;
+                    match ReferendumInfo::decode_with_metadata(
+                        &self.metadata,
+                        pallet_name,
+                        &mut bytes,
+                    ) {
+                        Ok(info) => referenda.push((index, info)),
+                        Err(error) => {
+                            log::error!("Cannot decode referendum #{}: {:?}", index, error)
+                        }
+                    }
+                }
+            }
+        }
+        referenda.sort_by_key(|(index, _)| *index);
+        Ok(referenda)
+    }
+
+    /// Get the indices of the referenda waiting for a decision slot in the given track, in the
+    /// order of their support.
+    pub async fn get_referendum_track_queue(
+        &self,
+        track_id: u16,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<u32>> {
+        self.get_track_queue::<Balance>(REFERENDA_PALLET, track_id, block_hash)
+            .await
+    }
+
+    /// Get the indices of the Fellowship referenda waiting for a decision slot in the given
+    /// track, in the order of their support.
+    pub async fn get_fellowship_referendum_track_queue(
+        &self,
+        track_id: u16,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<u32>> {
+        self.get_track_queue::<u32>(FELLOWSHIP_REFERENDA_PALLET, track_id, block_hash)
+            .await
+    }
+
+    /// `V` is the type of the votes of the referenda pallet instance.
+    async fn get_track_queue<V: Decode>(
+        &self,
+        pallet_name: &str,
+        track_id: u16,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<u32>> {
+        let params = get_rpc_storage_map_params(
+            &self.metadata,
+            pallet_name,
+            "TrackQueue",
+            &track_id,
+            Some(block_hash),
+        );
+        let maybe_hex_string: Option<String> =
+            self.ws_client.request("state_getStorage", params).await?;
+        if let Some(hex_string) = maybe_hex_string {
+            let queue: Vec<(u32, V)> = decode_hex_string(hex_string.as_str())?;
+            // the queue is kept in ascending order of support
+            Ok(queue.iter().rev().map(|(index, _)| *index).collect())
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Get the number of the Fellowship members at or above the given rank, i.e. the electorate
+    /// for the support calculations of the Fellowship track with the rank as its minimum rank.
+    pub async fn get_fellowship_member_count(
+        &self,
+        rank: u16,
+        block_hash: &str,
+    ) -> anyhow::Result<u32> {
+        let params = get_rpc_storage_map_params(
+            &self.metadata,
+            "FellowshipCollective",
+            "MemberCount",
+            &rank,
+            Some(block_hash),
+        );
+        let maybe_hex_string: Option<String> =
+            self.ws_client.request("state_getStorage", params).await?;
+        if let Some(hex_string) = maybe_hex_string {
+            Ok(decode_hex_string(hex_string.as_str())?)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Get the total issuance minus the inactive issuance, i.e. the electorate for the OpenGov
+    /// support calculations, at the given block.
+    pub async fn get_active_issuance(&self, block_hash: &str) -> anyhow::Result<Balance> {
+        let mut issuance: [Balance; 2] = [0, 0];
+        for (i, storage_name) in ["TotalIssuance", "InactiveIssuance"].iter().enumerate() {
+            let maybe_hex_string: Option<String> = self
+                .ws_client
+                .request(
+                    "state_getStorage",
+                    get_rpc_storage_plain_params("Balances", storage_name, Some(block_hash)),
+                )
+                .await?;
+            if let Some(hex_string) = maybe_hex_string {
+                issuance[i] = decode_hex_string(hex_string.as_str())?;
+            }
+        }
+        Ok(issuance[0].saturating_sub(issuance[1]))
+    }
+
+    /// Gets the encoded call with the given hash and length from the `Preimage` pallet storage.
+    /// Preimages get removed after the enactment of the proposals, or when unrequested.
+    pub async fn get_preimage(
+        &self,
+        hash: &sp_core::H256,
+        len: u32,
+        block_hash: &str,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let storage_key =
+            get_storage_map_key(&self.metadata, "Preimage", "PreimageFor", &(hash, len));
+        let chunk_values: Vec<StorageChangeSet<String>> = self
+            .ws_client
+            .request(
+                "state_queryStorageAt",
+                rpc_params!(vec![storage_key], block_hash),
+            )
+            .await?;
+        if let Some(value) = chunk_values.first() {
+            if let Some((_, Some(data))) = value.changes.first() {
+                let mut bytes: &[u8] = &data.0;
+                let preimage: Vec<u8> = Decode::decode(&mut bytes)?;
+                return Ok(Some(preimage));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Gets the encoded call of an ongoing referendum's proposal, if it's available.
+    pub async fn get_referendum_proposal_call_bytes(
+        &self,
+        referendum: &OngoingReferendum,
+        block_hash: &str,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        self.get_proposal_call_bytes(referendum.get_proposal_call(), block_hash)
+            .await
+    }
+
+    /// Gets the encoded call of a proposal, either inline or from the preimage storage, if it's
+    /// available.
+    pub async fn get_proposal_call_bytes(
+        &self,
+        proposal_call: ProposalCall,
+        block_hash: &str,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        match proposal_call {
+            ProposalCall::Inline(bytes) => Ok(Some(bytes)),
+            ProposalCall::Preimage { hash, len } => self.get_preimage(&hash, len, block_hash).await,
+            ProposalCall::Unavailable => Ok(None),
+        }
+    }
+
+    /// Gets and decodes the call of an ongoing referendum's proposal, if it's available.
+    pub async fn get_referendum_proposal_call(
+        &self,
+        referendum: &OngoingReferendum,
+        block_hash: &str,
+    ) -> anyhow::Result<Option<SubstrateExtrinsic>> {
+        let bytes = if let Some(bytes) = self
+            .get_referendum_proposal_call_bytes(referendum, block_hash)
+            .await?
+        {
+            bytes
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(self.decode_call(&bytes)?))
+    }
+
+    /// Decodes an encoded call, such as a preimage, with the current metadata.
+    pub fn decode_call(&self, bytes: &[u8]) -> anyhow::Result<SubstrateExtrinsic> {
+        let mut bytes = bytes;
+        Ok(SubstrateExtrinsic::decode_call(
+            &self.chain,
+            self.last_runtime_upgrade_info.spec_version,
+            &self.metadata,
+            &None,
+            &mut bytes,
+        )?)
+    }
+
+    /// Decodes an encoded call, such as a preimage, into a call tree with the current metadata.
+    pub fn decode_call_tree(&self, bytes: &[u8]) -> anyhow::Result<ReferendumCall> {
+        let mut bytes = bytes;
+        Ok(ReferendumCall::decode(&self.metadata, &mut bytes)?)
+    }
+
     async fn subscribe_to_blocks<F>(
         &self,
         subscribe_method_name: &str,
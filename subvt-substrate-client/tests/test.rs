@@ -124,3 +124,28 @@ async fn test_get_conviction_referendum_voting_direct() {
     assert!(vote.direct_vote.unwrap().conviction.is_some());
     assert!(vote.direct_vote.unwrap().nay.is_some());
 }
+
+#[tokio::test]
+async fn test_get_referenda() {
+    let config = Config::test().expect("Cannot get test config.");
+    let substrate_client = SubstrateClient::new(&config)
+        .await
+        .expect("Cannot initialize client.");
+    let tracks = substrate_client
+        .get_referendum_tracks()
+        .expect("Cannot get referendum tracks.");
+    assert!(tracks.iter().any(|(id, _)| *id == Track::Root.id()));
+    assert!(tracks
+        .iter()
+        .any(|(id, _)| *id == Track::MediumSpender.id()));
+    let block_number = 18_497_450;
+    let block_hash = substrate_client
+        .get_block_hash(block_number)
+        .await
+        .unwrap_or_else(|_| panic!("Cannot get block hash for block #{block_number}."));
+    let referenda = substrate_client
+        .get_referenda(&block_hash)
+        .await
+        .expect("Cannot get referenda.");
+    assert!(!referenda.is_empty());
+}
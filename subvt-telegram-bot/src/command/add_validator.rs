@@ -8,9 +8,11 @@ use std::str::FromStr;
 use subvt_types::app::UserValidator;
 use subvt_types::crypto::AccountId;
 use subvt_types::telegram::TelegramChatState;
+use subvt_utility::text::get_condensed_address;
 
 impl<M: Messenger + Send + Sync> TelegramBot<M> {
-    //! Processes the `/add` command that is used to add a validator to a chat.
+    //! Processes the `/add` command that is used to add a validator or an OpenGov delegate to a
+    //! chat.
     pub(crate) async fn process_add_validator_command(
         &self,
         chat_id: i64,
@@ -21,15 +23,21 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
             .get_chat_validator_count(chat_id)
             .await?;
         // check validator count against the max permitted per chat
-        if validator_count >= CONFIG.telegram_bot.max_validators_per_chat {
+        let max_validator_count = if self.network_postgres.chat_is_group(chat_id).await? {
+            CONFIG.telegram_bot.max_validators_per_group_chat
+        } else {
+            CONFIG.telegram_bot.max_validators_per_chat
+        };
+        if validator_count >= max_validator_count {
             self.messenger
                 .send_message(
                     &self.app_postgres,
                     &self.network_postgres,
                     chat_id,
-                    Box::new(MessageType::TooManyValidatorsOnChat),
+                    Box::new(MessageType::TooManyValidatorsOnChat(max_validator_count)),
                 )
                 .await?;
+            return Ok(());
         }
         // check address exists
         if args.is_empty() {
@@ -52,11 +60,18 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 // get validator details from Redis
                 Ok(account_id) => {
                     let block = self.redis.get_finalized_block_summary().await?;
-                    if let Some(validator) = self
+                    let maybe_validator = self
                         .redis
                         .fetch_validator_details(block.number, &account_id)
-                        .await?
-                    {
+                        .await?;
+                    // OpenGov delegates can be followed for their delegation notifications
+                    // even if they are not validators
+                    let is_delegate = maybe_validator.is_none()
+                        && self
+                            .network_postgres
+                            .conviction_voting_delegate_exists(&account_id)
+                            .await?;
+                    if maybe_validator.is_some() || is_delegate {
                         if self
                             .network_postgres
                             .chat_has_validator(chat_id, &account_id)
@@ -68,7 +83,12 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                                     &self.network_postgres,
                                     chat_id,
                                     Box::new(MessageType::ValidatorExistsOnChat(
-                                        validator.account.get_display_or_condensed_address(None),
+                                        match &maybe_validator {
+                                            Some(validator) => validator
+                                                .account
+                                                .get_display_or_condensed_address(None),
+                                            None => get_condensed_address(address, None),
+                                        },
                                     )),
                                 )
                                 .await?;
@@ -80,7 +100,9 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                                     chat_id,
                                     &account_id,
                                     &account_id.to_ss58_check(),
-                                    &validator.account.get_full_display(),
+                                    &maybe_validator
+                                        .as_ref()
+                                        .and_then(|validator| validator.account.get_full_display()),
                                 )
                                 .await?;
                             self.update_metrics_validator_count().await?;
@@ -101,6 +123,8 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                                     user_id: app_user_id,
                                     network_id: CONFIG.substrate.network_id,
                                     validator_account_id: account_id,
+                                    is_ownership_verified: false,
+                                    team_id: None,
                                 })
                                 .await?;
                             let query = Query {
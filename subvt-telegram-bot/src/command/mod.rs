@@ -2,6 +2,7 @@
 //! the `process_command` function below and the corresponding command modules.
 use crate::{query::QueryType, MessageType, Messenger, TelegramBot};
 use async_recursion::async_recursion;
+use subvt_types::report::ValidatorChartType;
 
 mod add_validator;
 mod broadcast;
@@ -14,6 +15,7 @@ mod remove_validator;
 mod rewards;
 mod settings;
 mod summary;
+mod validator_chart;
 mod validators;
 
 impl<M: Messenger + Send + Sync> TelegramBot<M> {
@@ -64,6 +66,10 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 self.network_postgres
                     .save_chat_command_log(chat_id, command)
                     .await?;
+                // deep link from an inline query result, the argument is the validator address
+                if !args.is_empty() {
+                    return self.process_add_validator_command(chat_id, args).await;
+                }
                 self.messenger
                     .send_message(
                         &self.app_postgres,
@@ -168,6 +174,46 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                     )
                     .await?;
             }
+            "/erapoints" => {
+                crate::metrics::command_call_counter(command).inc();
+                self.network_postgres
+                    .save_chat_command_log(chat_id, command)
+                    .await?;
+                self.process_validator_chart_command(chat_id, args, ValidatorChartType::EraPoints)
+                    .await?;
+            }
+            "/nominatorchart" => {
+                crate::metrics::command_call_counter(command).inc();
+                self.network_postgres
+                    .save_chat_command_log(chat_id, command)
+                    .await?;
+                self.process_validator_chart_command(
+                    chat_id,
+                    args,
+                    ValidatorChartType::NominatorCount,
+                )
+                .await?;
+            }
+            "/paravotes" => {
+                crate::metrics::command_call_counter(command).inc();
+                self.network_postgres
+                    .save_chat_command_log(chat_id, command)
+                    .await?;
+                self.process_validator_chart_command(chat_id, args, ValidatorChartType::ParaVotes)
+                    .await?;
+            }
+            "/stakechart" => {
+                crate::metrics::command_call_counter(command).inc();
+                self.network_postgres
+                    .save_chat_command_log(chat_id, command)
+                    .await?;
+                self.process_validator_chart_command(
+                    chat_id,
+                    args,
+                    ValidatorChartType::StakeComposition,
+                )
+                .await?;
+            }
             "/rewards" => {
                 crate::metrics::command_call_counter(command).inc();
                 self.network_postgres
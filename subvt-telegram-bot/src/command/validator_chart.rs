@@ -0,0 +1,39 @@
+//! `/stakechart`, `/erapoints`, `/paravotes` and `/nominatorchart` command processor.
+use crate::query::QueryType;
+use crate::{Messenger, Query, TelegramBot};
+use subvt_types::report::ValidatorChartType;
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    //! Sends the user the selected chart for a selected validator, such as the stake composition
+    //! over the eras or the para votes per session.
+    pub(crate) async fn process_validator_chart_command(
+        &self,
+        chat_id: i64,
+        args: &[String],
+        chart_type: ValidatorChartType,
+    ) -> anyhow::Result<()> {
+        let query_type = QueryType::ValidatorChart(chart_type);
+        if let Some(validator_address) = args.first() {
+            if let Some(chat_validator) = self
+                .network_postgres
+                .get_chat_validator_by_address(chat_id, validator_address)
+                .await?
+            {
+                self.process_query(
+                    chat_id,
+                    None,
+                    &Query {
+                        query_type,
+                        parameter: Some(chat_validator.id.to_string()),
+                    },
+                )
+                .await?;
+            } else {
+                self.process_validators_command(chat_id, query_type).await?;
+            }
+        } else {
+            self.process_validators_command(chat_id, query_type).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! Group chat support. A group chat has a single set of validators and notification rules, shared
+//! by all the members of the group, and only the group administrators can manage them.
+use crate::query::QueryType;
+use crate::{Messenger, TelegramBot};
+use frankenstein::{Chat, ChatType, Message};
+use subvt_types::app::notification::NotificationChannel;
+
+/// Commands that change the validators or the notification settings of the chat.
+const GROUP_ADMIN_COMMANDS: [&str; 4] = ["/add", "/remove", "/removeall", "/settings"];
+
+pub(crate) fn is_group_chat(chat: &Chat) -> bool {
+    matches!(chat.type_field, ChatType::Group | ChatType::Supergroup)
+}
+
+/// Id of the user who has sent the message, or the id of the chat on behalf of which it was sent.
+fn get_message_sender_id(message: &Message) -> Option<i64> {
+    match (&message.sender_chat, &message.from) {
+        (Some(sender_chat), _) => Some(sender_chat.id),
+        (None, Some(user)) => Some(user.id as i64),
+        (None, None) => None,
+    }
+}
+
+/// Whether the command can only be called by the administrators in a group chat.
+pub(crate) fn command_requires_group_admin(command: &str, args: &[String]) -> bool {
+    // `/start` with an address argument adds the validator
+    GROUP_ADMIN_COMMANDS.contains(&command) || (command == "/start" && !args.is_empty())
+}
+
+/// Whether the query can only be made by the administrators in a group chat.
+pub(crate) fn query_requires_group_admin(query_type: &QueryType) -> bool {
+    matches!(
+        query_type,
+        QueryType::RemoveAllValidators | QueryType::RemoveValidator | QueryType::SettingsEdit(_)
+    )
+}
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    /// Whether the sender of the message is the creator or an administrator of the group chat.
+    /// The sender is remembered as the last administrator of the chat if so.
+    pub(crate) async fn message_sender_is_chat_admin(
+        &self,
+        message: &Message,
+    ) -> anyhow::Result<bool> {
+        let is_chat_admin = match (&message.sender_chat, &message.from) {
+            // anonymous administrators send messages on behalf of the group
+            (Some(sender_chat), _) => sender_chat.id == message.chat.id,
+            (None, Some(user)) => {
+                self.messenger
+                    .is_chat_admin(message.chat.id, user.id)
+                    .await?
+            }
+            (None, None) => false,
+        };
+        if is_chat_admin {
+            if let Some(sender_id) = get_message_sender_id(message) {
+                self.last_group_admin_sender_ids
+                    .lock()
+                    .unwrap()
+                    .insert(message.chat.id, sender_id);
+            }
+        }
+        Ok(is_chat_admin)
+    }
+
+    /// Whether the message is from the administrator who has sent the last admin-only command to
+    /// the group chat, e.g. the `/add` command that the message may be a response to. Doesn't
+    /// call the Telegram API, so that the messages of the other members can be ignored cheaply.
+    pub(crate) fn message_sender_is_last_chat_admin(&self, message: &Message) -> bool {
+        let maybe_sender_id = get_message_sender_id(message);
+        maybe_sender_id.is_some()
+            && self
+                .last_group_admin_sender_ids
+                .lock()
+                .unwrap()
+                .get(&message.chat.id)
+                == maybe_sender_id.as_ref()
+    }
+
+    /// Saves whether the chat is a group chat, if it has changed since the last save.
+    pub(crate) async fn save_chat_is_group(&self, chat: &Chat) -> anyhow::Result<()> {
+        let is_group = is_group_chat(chat);
+        if self.chat_is_group_flags.lock().unwrap().get(&chat.id) == Some(&is_group) {
+            return Ok(());
+        }
+        self.network_postgres
+            .set_chat_is_group(chat.id, is_group)
+            .await?;
+        self.chat_is_group_flags
+            .lock()
+            .unwrap()
+            .insert(chat.id, is_group);
+        Ok(())
+    }
+
+    /// Telegram assigns a new chat id to a group when it gets upgraded to a supergroup. Moves the
+    /// validators and the notification rules of the group to the new chat id.
+    pub(crate) async fn migrate_chat(&self, chat_id: i64, new_chat_id: i64) -> anyhow::Result<()> {
+        if !self
+            .network_postgres
+            .migrate_chat(chat_id, new_chat_id)
+            .await?
+        {
+            return Ok(());
+        }
+        let app_user_id = self
+            .network_postgres
+            .get_chat_app_user_id(new_chat_id)
+            .await?;
+        self.app_postgres
+            .update_user_notification_channel_target(
+                app_user_id,
+                &NotificationChannel::Telegram.to_string(),
+                &chat_id.to_string(),
+                &new_chat_id.to_string(),
+            )
+            .await?;
+        log::info!("Chat {} migrated to {}.", chat_id, new_chat_id);
+        Ok(())
+    }
+}
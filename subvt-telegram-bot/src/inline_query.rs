@@ -0,0 +1,150 @@
+//! Inline mode: `@bot_username <query>` typed in any chat searches the active and inactive
+//! validators in Redis by identity and address, and answers with shareable validator info cards.
+//!
+//! Telegram sends a query for every keystroke, so a query is answered only if the user hasn't
+//! typed on in the debounce period, and the validator list and the 1KV candidate summaries are
+//! cached for a while instead of being loaded for every query.
+use crate::{Messenger, TelegramBot, CONFIG};
+use frankenstein::InlineQuery;
+use rustc_hash::FxHashMap as HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use subvt_types::crypto::AccountId;
+use subvt_types::onekv::OneKVCandidateSummary;
+use subvt_types::subvt::{ValidatorDetails, ValidatorSummary};
+
+/// Queries shorter than this are answered with no results.
+const MIN_QUERY_LENGTH: usize = 2;
+/// Telegram permits at most 50 results per inline query answer.
+const MAX_RESULT_COUNT: usize = 20;
+
+/// Data cached for the inline queries, reset when the validator list gets reloaded.
+#[derive(Default)]
+pub(crate) struct InlineQueryCache {
+    /// Active and inactive validators at the finalized block at the time of loading.
+    validators: Option<(Instant, Arc<Vec<ValidatorDetails>>)>,
+    onekv_candidate_summaries: HashMap<AccountId, Option<OneKVCandidateSummary>>,
+}
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    /// Returns the cached validator list, or loads the validators at the last finalized block
+    /// if the cache has expired.
+    async fn get_inline_query_validators(&self) -> anyhow::Result<Arc<Vec<ValidatorDetails>>> {
+        // the lock is held while loading, so that concurrent queries don't load the list again
+        let mut cache = self.inline_query_cache.lock().await;
+        if let Some((loaded_at, validators)) = &cache.validators {
+            if loaded_at.elapsed().as_secs() < CONFIG.telegram_bot.inline_query_cache_seconds {
+                return Ok(validators.clone());
+            }
+        }
+        let block_number = self.redis.get_finalized_block_summary().await?.number;
+        let mut validators = self
+            .redis
+            .get_validator_details_list(block_number, true)
+            .await?;
+        validators.append(
+            &mut self
+                .redis
+                .get_validator_details_list(block_number, false)
+                .await?,
+        );
+        let validators = Arc::new(validators);
+        cache.validators = Some((Instant::now(), validators.clone()));
+        cache.onekv_candidate_summaries.clear();
+        Ok(validators)
+    }
+
+    async fn get_inline_query_onekv_candidate_summary(
+        &self,
+        account_id: &AccountId,
+    ) -> anyhow::Result<Option<OneKVCandidateSummary>> {
+        if let Some(maybe_summary) = self
+            .inline_query_cache
+            .lock()
+            .await
+            .onekv_candidate_summaries
+            .get(account_id)
+        {
+            return Ok(maybe_summary.clone());
+        }
+        let maybe_summary = self
+            .network_postgres
+            .get_onekv_candidate_summary_by_account_id(account_id)
+            .await?;
+        self.inline_query_cache
+            .lock()
+            .await
+            .onekv_candidate_summaries
+            .insert(*account_id, maybe_summary.clone());
+        Ok(maybe_summary)
+    }
+
+    /// Waits for the debounce period, and returns whether the query is still the last query of
+    /// the user.
+    async fn debounce_inline_query(&self, inline_query: &InlineQuery) -> bool {
+        let user_id = inline_query.from.id;
+        self.last_inline_query_ids
+            .lock()
+            .unwrap()
+            .insert(user_id, inline_query.id.clone());
+        tokio::time::sleep(Duration::from_millis(
+            CONFIG.telegram_bot.inline_query_debounce_millis,
+        ))
+        .await;
+        let mut last_inline_query_ids = self.last_inline_query_ids.lock().unwrap();
+        if last_inline_query_ids.get(&user_id) != Some(&inline_query.id) {
+            return false;
+        }
+        last_inline_query_ids.remove(&user_id);
+        true
+    }
+
+    /// Searches the cached validators, best matches first.
+    async fn search_validators(&self, query: &str) -> anyhow::Result<Vec<ValidatorDetails>> {
+        let validators = self.get_inline_query_validators().await?;
+        let mut matches: Vec<(u32, &ValidatorDetails)> = validators
+            .iter()
+            .filter_map(|validator_details| {
+                ValidatorSummary::from(validator_details)
+                    .get_search_score(query)
+                    .map(|score| (score, validator_details))
+            })
+            .collect();
+        // best matches first, active validators first among the equal matches
+        matches.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then_with(|| b.1.is_active.cmp(&a.1.is_active))
+        });
+        Ok(matches
+            .into_iter()
+            .take(MAX_RESULT_COUNT)
+            .map(|(_, validator_details)| validator_details.clone())
+            .collect())
+    }
+
+    pub(crate) async fn process_inline_query(
+        &self,
+        inline_query: &InlineQuery,
+    ) -> anyhow::Result<()> {
+        crate::metrics::inline_query_counter().inc();
+        if !self.debounce_inline_query(inline_query).await {
+            // Telegram only shows the answer to the last query
+            return Ok(());
+        }
+        let query = inline_query.query.trim();
+        let mut results = vec![];
+        if query.chars().count() >= MIN_QUERY_LENGTH {
+            log::info!("New inline query: {}", query);
+            for validator_details in self.search_validators(query).await? {
+                let maybe_onekv_candidate_summary = self
+                    .get_inline_query_onekv_candidate_summary(&validator_details.account.id)
+                    .await?;
+                results.push((validator_details, maybe_onekv_candidate_summary));
+            }
+        }
+        self.messenger
+            .answer_inline_query(&inline_query.id, results)
+            .await?;
+        Ok(())
+    }
+}
@@ -2,10 +2,12 @@
 //! migrated to the SubVT backend (https://github.com/helikon-labs/subvt-backend/tree/development).
 #![warn(clippy::disallowed_types)]
 
+use crate::group::{command_requires_group_admin, is_group_chat, query_requires_group_admin};
+use crate::inline_query::InlineQueryCache;
 use crate::messenger::Messenger;
 use crate::{
     api::AsyncApi,
-    messenger::{message::MessageType, MessengerImpl},
+    messenger::{get_user_mention, message::MessageType, MessengerImpl, USER_MENTION},
     query::Query,
 };
 use async_trait::async_trait;
@@ -13,7 +15,7 @@ pub use frankenstein::{AsyncTelegramApi, ChatId, ParseMode, SendMessageParams};
 use frankenstein::{ChatType, GetUpdatesParams, Message};
 use lazy_static::lazy_static;
 use regex::Regex;
-use rustc_hash::FxHashSet as HashSet;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::str::FromStr;
 use subvt_config::Config;
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
@@ -31,8 +33,11 @@ use subvt_types::telegram::TelegramChatState;
 
 pub mod api;
 mod command;
+mod group;
+mod inline_query;
 pub mod messenger;
 mod metrics;
+mod purge;
 mod query;
 #[cfg(test)]
 mod test;
@@ -150,6 +155,16 @@ lazy_static! {
             0,
         ),
         // referenda/opengov
+        (
+            NotificationTypeCode::ConvictionVotingDelegated,
+            NotificationPeriodType::Immediate,
+            0,
+        ),
+        (
+            NotificationTypeCode::ConvictionVotingUndelegated,
+            NotificationPeriodType::Immediate,
+            0,
+        ),
         (
             NotificationTypeCode::ReferendumConfirmed,
             NotificationPeriodType::Immediate,
@@ -195,6 +210,14 @@ pub struct TelegramBot<M: Messenger + Send + Sync> {
     api: AsyncApi,
     /// Telegram messenger struct.
     messenger: M,
+    /// Validator list and 1KV data cached for the inline queries.
+    inline_query_cache: tokio::sync::Mutex<InlineQueryCache>,
+    /// Id of the last inline query of each Telegram user, for debouncing.
+    last_inline_query_ids: std::sync::Mutex<HashMap<u64, String>>,
+    /// Saved group flag of each chat, so that it's written only when it changes.
+    chat_is_group_flags: std::sync::Mutex<HashMap<i64, bool>>,
+    /// Sender of the last admin-only command in each group chat.
+    last_group_admin_sender_ids: std::sync::Mutex<HashMap<i64, i64>>,
 }
 
 impl<M: Messenger + Send + Sync> TelegramBot<M> {
@@ -211,6 +234,10 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
             redis,
             api,
             messenger: MessengerImpl::new()?,
+            inline_query_cache: Default::default(),
+            last_inline_query_ids: Default::default(),
+            chat_is_group_flags: Default::default(),
+            last_group_admin_sender_ids: Default::default(),
         })
     }
 }
@@ -234,7 +261,7 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
         for rule in DEFAULT_RULES.iter() {
             self.app_postgres
                 .save_user_notification_rule(
-                    app_user_id,
+                    (app_user_id, None),
                     &rule.0.to_string(),
                     (None, None),
                     (Some(CONFIG.substrate.network_id), true),
@@ -253,7 +280,11 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
             chat_id
         );
         // save app user
-        let app_user_id = self.app_postgres.save_user(&User::default(), None).await?;
+        let app_user_id = self
+            .app_postgres
+            .save_user(&User::default(), None)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Cannot save the app user for chat {chat_id}."))?;
         // save notification channel
         let channel_id = self
             .app_postgres
@@ -308,12 +339,29 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 return Ok(());
             }
         }
+        // bot added to an existing group - send intro
+        if let Some(new_chat_members) = &message.new_chat_members {
+            let bot_username = CONFIG.telegram_bot.username.trim_start_matches('@');
+            if new_chat_members
+                .iter()
+                .any(|member| member.is_bot && member.username.as_deref() == Some(bot_username))
+            {
+                self.messenger
+                    .send_message(
+                        &self.app_postgres,
+                        &self.network_postgres,
+                        message.chat.id,
+                        Box::new(MessageType::Intro),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        }
         // text message
         if let Some(text) = message.text.clone() {
             let text = text.trim();
             if CMD_REGEX.is_match(text) {
                 log::info!("New command: {}", text);
-                self.reset_chat_state(message.chat.id).await?;
                 let (command, arguments): (String, Vec<String>) = {
                     let parts: Vec<String> = SPLITTER_REGEX.split(text).map(String::from).collect();
                     (
@@ -326,6 +374,21 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                     )
                 };
                 let command = command.replace(&CONFIG.telegram_bot.username, "");
+                if is_group_chat(&message.chat)
+                    && command_requires_group_admin(&command, &arguments)
+                    && !self.message_sender_is_chat_admin(message).await?
+                {
+                    self.messenger
+                        .send_message(
+                            &self.app_postgres,
+                            &self.network_postgres,
+                            message.chat.id,
+                            Box::new(MessageType::GroupAdminOnly),
+                        )
+                        .await?;
+                    return Ok(());
+                }
+                self.reset_chat_state(message.chat.id).await?;
                 self.process_command(message.chat.id, &command, &arguments)
                     .await?;
             } else {
@@ -337,7 +400,12 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 if let Some(state) = maybe_state {
                     match state {
                         TelegramChatState::AddValidator => {
-                            if AccountId::from_str(text).is_ok() {
+                            // the other members of the group keep chatting, ignore them
+                            if is_group_chat(&message.chat)
+                                && !self.message_sender_is_last_chat_admin(message)
+                            {
+                                return Ok(());
+                            } else if AccountId::from_str(text).is_ok() {
                                 self.reset_chat_state(message.chat.id).await?;
                                 self.process_command(message.chat.id, "/add", &[text.to_string()])
                                     .await?;
@@ -458,7 +526,7 @@ impl<M: Messenger + Send + Sync> Service for TelegramBot<M> {
 
     async fn run(&'static self) -> anyhow::Result<()> {
         log::info!("Telegram bot has started.");
-        // only receive message and callback query updates
+        // only receive message, callback query and inline query updates
         let mut update_params = GetUpdatesParams {
             offset: None,
             limit: None,
@@ -466,11 +534,14 @@ impl<M: Messenger + Send + Sync> Service for TelegramBot<M> {
             allowed_updates: Some(vec![
                 frankenstein::AllowedUpdate::Message,
                 frankenstein::AllowedUpdate::CallbackQuery,
+                frankenstein::AllowedUpdate::InlineQuery,
             ]),
         };
         // update metrics
         self.update_metrics_chat_count().await?;
         self.update_metrics_validator_count().await?;
+        // permanently delete the chats that have blocked the bot past the retention period
+        tokio::spawn(self.run_purge_job());
         // process Telegram updates in a loop
         loop {
             let result = self.api.get_updates(&update_params).await;
@@ -481,8 +552,24 @@ impl<M: Messenger + Send + Sync> Service for TelegramBot<M> {
                         match update.content {
                             // process message
                             frankenstein::UpdateContent::Message(message) => {
+                                // group upgraded to a supergroup
+                                if let Some(new_chat_id) = message.migrate_to_chat_id {
+                                    self.migrate_chat(message.chat.id, new_chat_id).await?;
+                                    continue;
+                                }
+                                if let Some(chat_id) = message.migrate_from_chat_id {
+                                    self.migrate_chat(chat_id, message.chat.id).await?;
+                                    continue;
+                                }
                                 self.save_or_restore_chat(message.chat.id).await?;
-                                tokio::spawn(async move {
+                                self.save_chat_is_group(&message.chat).await?;
+                                // mention the sender in the responses to a group
+                                let mention = if is_group_chat(&message.chat) {
+                                    message.from.as_deref().map(get_user_mention)
+                                } else {
+                                    None
+                                };
+                                tokio::spawn(USER_MENTION.scope(mention, async move {
                                     if let Err(error) = self.process_message(&message).await {
                                         log::error!(
                                             "Error while processing message #{}: {:?}",
@@ -499,14 +586,21 @@ impl<M: Messenger + Send + Sync> Service for TelegramBot<M> {
                                             )
                                             .await;
                                     }
-                                });
+                                }));
                             }
                             // process callback query
                             frankenstein::UpdateContent::CallbackQuery(callback_query) => {
                                 if let Some(callback_data) = callback_query.data {
                                     if let Some(message) = callback_query.message {
                                         self.save_or_restore_chat(message.chat.id).await?;
-                                        tokio::spawn(async move {
+                                        let is_group = is_group_chat(&message.chat);
+                                        let user_id = callback_query.from.id;
+                                        let mention = if is_group {
+                                            Some(get_user_mention(&callback_query.from))
+                                        } else {
+                                            None
+                                        };
+                                        tokio::spawn(USER_MENTION.scope(mention, async move {
                                             let query: Query = if let Ok(query) =
                                                 serde_json::from_str(&callback_data)
                                             {
@@ -518,6 +612,28 @@ impl<M: Messenger + Send + Sync> Service for TelegramBot<M> {
                                                     callback_data
                                                 );
                                             };
+                                            if is_group
+                                                && query_requires_group_admin(&query.query_type)
+                                                && !self
+                                                    .messenger
+                                                    .is_chat_admin(message.chat.id, user_id)
+                                                    .await
+                                                    .unwrap_or(false)
+                                            {
+                                                let _ = tokio::try_join!(
+                                                    self.messenger.answer_callback_query(
+                                                        &callback_query.id,
+                                                        None
+                                                    ),
+                                                    self.messenger.send_message(
+                                                        &self.app_postgres,
+                                                        &self.network_postgres,
+                                                        message.chat.id,
+                                                        Box::new(MessageType::GroupAdminOnly),
+                                                    ),
+                                                );
+                                                return;
+                                            }
                                             if let Err(error) = tokio::try_join!(
                                                 self.reset_chat_state(message.chat.id),
                                                 self.process_query(
@@ -545,10 +661,24 @@ impl<M: Messenger + Send + Sync> Service for TelegramBot<M> {
                                                     )
                                                     .await;
                                             }
-                                        });
+                                        }));
                                     }
                                 }
                             }
+                            // process inline query, i.e. validator search
+                            frankenstein::UpdateContent::InlineQuery(inline_query) => {
+                                tokio::spawn(async move {
+                                    if let Err(error) =
+                                        self.process_inline_query(&inline_query).await
+                                    {
+                                        log::error!(
+                                            "Error while processing inline query {}: {:?}",
+                                            inline_query.id,
+                                            error
+                                        );
+                                    }
+                                });
+                            }
                             _ => (),
                         }
                     }
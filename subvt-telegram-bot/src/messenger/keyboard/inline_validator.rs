@@ -0,0 +1,31 @@
+//! Keyboard attached to the validator info cards shared through the inline query results. The
+//! message is posted by the user who made the query, possibly in a chat the bot isn't a member
+//! of, so the button deep-links to the private chat with the bot, which adds the validator with
+//! the `/start <address>` command.
+use crate::CONFIG;
+use frankenstein::{InlineKeyboardButton, InlineKeyboardMarkup};
+use tera::{Context, Tera};
+
+pub fn get_inline_validator_keyboard(
+    renderer: &Tera,
+    address: &str,
+) -> anyhow::Result<InlineKeyboardMarkup> {
+    Ok(InlineKeyboardMarkup {
+        inline_keyboard: vec![vec![InlineKeyboardButton {
+            text: renderer.render("add_validator_from_inline_query.html", &Context::new())?,
+            url: Some(format!(
+                "https://t.me/{}?start={}",
+                CONFIG.telegram_bot.username.trim_start_matches('@'),
+                address,
+            )),
+            login_url: None,
+            callback_data: None,
+            web_app: None,
+            switch_inline_query: None,
+            switch_inline_query_current_chat: None,
+            switch_inline_query_chosen_chat: None,
+            callback_game: None,
+            pay: None,
+        }]],
+    })
+}
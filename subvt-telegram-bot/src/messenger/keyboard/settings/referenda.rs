@@ -13,6 +13,20 @@ pub(crate) fn get_referenda_settings_keyboard(
         "settings_referenda_title.html",
         QueryType::NoOp,
     )?];
+    rows.push(get_notification_on_off_button(
+        renderer,
+        NotificationTypeCode::ConvictionVotingDelegated,
+        "settings_item_conviction_voting_delegated.html",
+        SettingsEditQueryType::ConvictionVotingDelegated,
+        notification_rules,
+    )?);
+    rows.push(get_notification_on_off_button(
+        renderer,
+        NotificationTypeCode::ConvictionVotingUndelegated,
+        "settings_item_conviction_voting_undelegated.html",
+        SettingsEditQueryType::ConvictionVotingUndelegated,
+        notification_rules,
+    )?);
     rows.push(get_notification_on_off_button(
         renderer,
         NotificationTypeCode::ReferendumApproved,
@@ -34,6 +48,20 @@ pub(crate) fn get_referenda_settings_keyboard(
         SettingsEditQueryType::ReferendumConfirmed,
         notification_rules,
     )?);
+    rows.push(get_notification_on_off_button(
+        renderer,
+        NotificationTypeCode::ReferendumConfirmStarted,
+        "settings_item_referendum_confirm_started.html",
+        SettingsEditQueryType::ReferendumConfirmStarted,
+        notification_rules,
+    )?);
+    rows.push(get_notification_on_off_button(
+        renderer,
+        NotificationTypeCode::ReferendumConfirmAborted,
+        "settings_item_referendum_confirm_aborted.html",
+        SettingsEditQueryType::ReferendumConfirmAborted,
+        notification_rules,
+    )?);
     rows.push(get_notification_on_off_button(
         renderer,
         NotificationTypeCode::ReferendumDecisionStarted,
@@ -48,6 +76,13 @@ pub(crate) fn get_referenda_settings_keyboard(
         SettingsEditQueryType::ReferendumKilled,
         notification_rules,
     )?);
+    rows.push(get_notification_on_off_button(
+        renderer,
+        NotificationTypeCode::ChainValidatorReferendumNotVoted,
+        "settings_item_referendum_not_voted.html",
+        SettingsEditQueryType::ReferendumNotVoted,
+        notification_rules,
+    )?);
     rows.push(get_notification_on_off_button(
         renderer,
         NotificationTypeCode::ReferendumRejected,
@@ -62,6 +97,20 @@ pub(crate) fn get_referenda_settings_keyboard(
         SettingsEditQueryType::ReferendumSubmitted,
         notification_rules,
     )?);
+    rows.push(get_notification_on_off_button(
+        renderer,
+        NotificationTypeCode::ReferendumTallyChanged,
+        "settings_item_referendum_tally_changed.html",
+        SettingsEditQueryType::ReferendumTallyChanged,
+        notification_rules,
+    )?);
+    rows.push(get_notification_on_off_button(
+        renderer,
+        NotificationTypeCode::ReferendumThresholdCrossed,
+        "settings_item_referendum_threshold_crossed.html",
+        SettingsEditQueryType::ReferendumThresholdCrossed,
+        notification_rules,
+    )?);
     rows.push(get_notification_on_off_button(
         renderer,
         NotificationTypeCode::ReferendumTimedOut,
@@ -23,7 +23,10 @@ impl MessageType {
                 }
                 "about.html"
             }
-            Self::Help => "help.html",
+            Self::Help => {
+                context.insert("bot_username", &CONFIG.telegram_bot.username);
+                "help.html"
+            }
             Self::Intro => {
                 context.insert("chain", &CONFIG.substrate.chain);
                 "introduction.html"
@@ -59,13 +62,11 @@ impl MessageType {
                 context.insert("validator_display", validator_display);
                 "validator_exists_on_chat.html"
             }
-            Self::TooManyValidatorsOnChat => {
-                context.insert(
-                    "max_validators_per_chat",
-                    &CONFIG.telegram_bot.max_validators_per_chat,
-                );
+            Self::TooManyValidatorsOnChat(max_validators_per_chat) => {
+                context.insert("max_validators_per_chat", max_validators_per_chat);
                 "too_many_validators_on_chat.html"
             }
+            Self::GroupAdminOnly => "group_admin_only.html",
             Self::NoValidatorsOnChat => "no_validators_on_chat.html",
             Self::ValidatorAdded => "validator_added.html",
             Self::AddValidator => "add_validator.html",
@@ -119,6 +120,7 @@ impl MessageType {
             }
             Self::NoPayoutsFound => "no_payouts_found.html",
             Self::NoRewardsFound => "no_rewards_found.html",
+            Self::NoChartDataFound => "no_chart_data_found.html",
             Self::NoOpenReferendaFound(track) => {
                 context.insert("track", track.name());
                 context.insert("chain", &CONFIG.substrate.chain);
@@ -133,9 +135,15 @@ impl MessageType {
             }
             Self::ReferendumDetails {
                 post,
+                maybe_proposal_call,
                 chat_validator_votes,
             } => {
-                self.fill_referendum_details_context(&mut context, post, chat_validator_votes);
+                self.fill_referendum_details_context(
+                    &mut context,
+                    post,
+                    maybe_proposal_call,
+                    chat_validator_votes,
+                );
                 "referendum_details.html"
             }
             Self::SelectContactType => "select_contact_type.html",
@@ -1,6 +1,7 @@
 //! Content for a selected open referendum.
 use crate::MessageType;
 use crate::CONFIG;
+use subvt_types::governance::call::ReferendumCall;
 use subvt_types::governance::polkassembly::ReferendumPostDetails;
 use subvt_types::governance::track::Track;
 use subvt_types::substrate::democracy::ReferendumVote;
@@ -9,11 +10,15 @@ use subvt_utility::numeric::format_decimal;
 use subvt_utility::text::get_condensed_address;
 use tera::Context;
 
+/// Keeps the message within the Telegram message length limit for large batch proposals.
+const MAX_PROPOSAL_CALL_LINES: usize = 30;
+
 impl MessageType {
     pub(in crate::messenger::message) fn fill_referendum_details_context(
         &self,
         context: &mut Context,
         post: &ReferendumPostDetails,
+        maybe_proposal_call: &Option<ReferendumCall>,
         chat_validator_votes: &[(TelegramChatValidator, Option<ReferendumVote>)],
     ) {
         context.insert("chain", &CONFIG.substrate.chain);
@@ -34,6 +39,12 @@ impl MessageType {
         if let Some(content) = &post.maybe_content {
             context.insert("content", &content);
         }
+        if let Some(proposal_call) = maybe_proposal_call {
+            context.insert(
+                "proposal_call_lines",
+                &proposal_call.get_truncated_summary_lines(MAX_PROPOSAL_CALL_LINES),
+            );
+        }
 
         // validator votes
         let mut validators_without_vote = vec![];
@@ -15,7 +15,7 @@ impl MessageType {
         address: &str,
         maybe_validator_details: &Option<ValidatorDetails>,
         maybe_onekv_candidate_summary: &Option<OneKVCandidateSummary>,
-        missing_referendum_votes: &Vec<u32>,
+        missing_referendum_votes: &Option<Vec<u32>>,
     ) {
         context.insert("chain", &CONFIG.substrate.chain);
         context.insert("condensed_address", &get_condensed_address(address, None));
@@ -65,7 +65,10 @@ impl MessageType {
             }
             context.insert("slash_count", &validator_details.slash_count);
         }
-        context.insert("missing_referendum_votes", missing_referendum_votes);
+        context.insert("show_referendum_votes", &missing_referendum_votes.is_some());
+        if let Some(missing_referendum_votes) = missing_referendum_votes {
+            context.insert("missing_referendum_votes", missing_referendum_votes);
+        }
         context.insert("is_onekv", &maybe_onekv_candidate_summary.is_some());
         if let Some(onekv_summary) = maybe_onekv_candidate_summary {
             context.insert("onekv_name", &onekv_summary.name);
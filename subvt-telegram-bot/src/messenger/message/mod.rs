@@ -1,6 +1,7 @@
 //! This module manages the creation of the content for every message type.
 use crate::query::QueryType;
 use subvt_types::crypto::AccountId;
+use subvt_types::governance::call::ReferendumCall;
 use subvt_types::governance::polkassembly::{ReferendumPost, ReferendumPostDetails};
 use subvt_types::governance::track::Track;
 use subvt_types::onekv::OneKVCandidateSummary;
@@ -29,7 +30,8 @@ pub enum MessageType {
     },
     AddValidatorNotFound(String),
     ValidatorExistsOnChat(String),
-    TooManyValidatorsOnChat,
+    TooManyValidatorsOnChat(u16),
+    GroupAdminOnly,
     NoValidatorsOnChat,
     ValidatorAdded,
     AddValidator,
@@ -41,7 +43,8 @@ pub enum MessageType {
         address: String,
         maybe_validator_details: Box<Option<ValidatorDetails>>,
         maybe_onekv_candidate_summary: Box<Option<OneKVCandidateSummary>>,
-        missing_referendum_votes: Vec<u32>,
+        /// `None` when the votes aren't checked, e.g. for the inline query results.
+        missing_referendum_votes: Option<Vec<u32>>,
     },
     NominationSummary {
         chat_validator_id: u64,
@@ -58,11 +61,13 @@ pub enum MessageType {
     NetworkStatus(NetworkStatus),
     NoPayoutsFound,
     NoRewardsFound,
+    NoChartDataFound,
     NoOpenReferendaFound(Track),
     ReferendumList(u16, Vec<ReferendumPost>),
     ReferendumNotFound(u32),
     ReferendumDetails {
         post: ReferendumPostDetails,
+        maybe_proposal_call: Option<ReferendumCall>,
         chat_validator_votes: Vec<(TelegramChatValidator, Option<ReferendumVote>)>,
     },
     ReferendumTracks(Vec<(Track, usize)>),
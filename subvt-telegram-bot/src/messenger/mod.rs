@@ -1,5 +1,6 @@
 //! This module handles the sending of all the messages to a Telegram chat.
 use crate::api::{AsyncApi, Error};
+use crate::messenger::keyboard::inline_validator::get_inline_validator_keyboard;
 use crate::messenger::keyboard::referendum_tracks::get_referendum_tracks_keyboard;
 use crate::messenger::keyboard::settings::referenda::get_referenda_settings_keyboard;
 use crate::messenger::keyboard::{
@@ -21,9 +22,11 @@ use crate::query::{QueryType, SettingsEditQueryType, SettingsSubSection};
 use crate::{TelegramBotError, CONFIG};
 use async_trait::async_trait;
 use frankenstein::{
-    AnswerCallbackQueryParams, AsyncTelegramApi, ChatId, DeleteMessageParams, EditMessageResponse,
-    EditMessageTextParams, Message as TelegramMessage, MethodResponse, ParseMode, ReplyMarkup,
-    SendMessageParams, SendPhotoParams,
+    AnswerCallbackQueryParams, AnswerInlineQueryParams, AsyncTelegramApi, ChatId, ChatMember,
+    DeleteMessageParams, EditMessageResponse, EditMessageTextParams, GetChatMemberParams,
+    InlineQueryResult, InlineQueryResultArticle, InputMessageContent, InputTextMessageContent,
+    Message as TelegramMessage, MethodResponse, ParseMode, ReplyMarkup, SendMessageParams,
+    SendPhotoParams, User as TelegramUser,
 };
 use message::MessageType;
 #[cfg(test)]
@@ -32,7 +35,11 @@ use std::path::Path;
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_types::app::notification::{NotificationTypeCode, UserNotificationRule};
+use subvt_types::onekv::OneKVCandidateSummary;
 use subvt_types::sub_id::NFTCollection;
+use subvt_types::subvt::ValidatorDetails;
+use subvt_utility::numeric::format_decimal;
+use subvt_utility::text::get_condensed_address;
 use tera::{Context, Tera};
 
 pub mod button;
@@ -40,6 +47,24 @@ pub mod keyboard;
 pub mod message;
 
 const FORBIDDEN_ERROR_CODE: u64 = 403;
+/// Telegram caches the inline query results on its servers for this long.
+const INLINE_QUERY_CACHE_TIME_SECONDS: u32 = 30;
+
+tokio::task_local! {
+    /// Mention of the user whose command or query is being processed in a group chat. Set for
+    /// the processing task of each update, and prepended to the messages sent to the group.
+    pub static USER_MENTION: Option<String>;
+}
+
+/// HTML mention of a Telegram user, which notifies the user even if they don't have a username.
+pub fn get_user_mention(user: &TelegramUser) -> String {
+    let name = user
+        .first_name
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!("<a href=\"tg://user?id={}\">{}</a>", user.id, name)
+}
 
 #[cfg_attr(test, automock)]
 #[async_trait]
@@ -50,12 +75,20 @@ pub trait Messenger {
         text: Option<String>,
     ) -> anyhow::Result<MethodResponse<bool>>;
 
+    async fn answer_inline_query(
+        &self,
+        inline_query_id: &str,
+        validators: Vec<(ValidatorDetails, Option<OneKVCandidateSummary>)>,
+    ) -> anyhow::Result<MethodResponse<bool>>;
+
     async fn delete_message(
         &self,
         chat_id: i64,
         message_id: i32,
     ) -> anyhow::Result<MethodResponse<bool>>;
 
+    async fn is_chat_admin(&self, chat_id: i64, user_id: u64) -> anyhow::Result<bool>;
+
     async fn send_image(
         &self,
         app_postgres: &PostgreSQLAppStorage,
@@ -138,6 +171,69 @@ impl Messenger for MessengerImpl {
         }
     }
 
+    /// Answers an inline query with a shareable validator info card for each validator in the
+    /// search results.
+    async fn answer_inline_query(
+        &self,
+        inline_query_id: &str,
+        validators: Vec<(ValidatorDetails, Option<OneKVCandidateSummary>)>,
+    ) -> anyhow::Result<MethodResponse<bool>> {
+        let mut results = vec![];
+        for (validator_details, maybe_onekv_candidate_summary) in validators {
+            let address = validator_details.account.address.clone();
+            let title = validator_details
+                .account
+                .get_display_or_condensed_address(None);
+            let description = {
+                let mut context = Context::new();
+                context.insert("condensed_address", &get_condensed_address(&address, None));
+                context.insert("is_active", &validator_details.is_active);
+                context.insert(
+                    "commission",
+                    &format_decimal(
+                        validator_details.preferences.commission_per_billion as u128,
+                        7,
+                        2,
+                    ),
+                );
+                context.insert("is_onekv", &maybe_onekv_candidate_summary.is_some());
+                self.renderer
+                    .render("inline_validator_description.html", &context)?
+            };
+            let content = MessageType::ValidatorInfo {
+                address: address.clone(),
+                maybe_validator_details: Box::new(Some(validator_details)),
+                maybe_onekv_candidate_summary: Box::new(maybe_onekv_candidate_summary),
+                missing_referendum_votes: None,
+            }
+            .get_content(&self.renderer);
+            results.push(InlineQueryResult::Article(
+                InlineQueryResultArticle::builder()
+                    .id(address.clone())
+                    .title(title)
+                    .description(description)
+                    .input_message_content(InputMessageContent::Text(
+                        InputTextMessageContent::builder()
+                            .message_text(content)
+                            .parse_mode(ParseMode::Html)
+                            .disable_web_page_preview(true)
+                            .build(),
+                    ))
+                    .reply_markup(get_inline_validator_keyboard(&self.renderer, &address)?)
+                    .build(),
+            ));
+        }
+        let params = AnswerInlineQueryParams::builder()
+            .inline_query_id(inline_query_id)
+            .results(results)
+            .cache_time(INLINE_QUERY_CACHE_TIME_SECONDS)
+            .build();
+        match self.api.answer_inline_query(&params).await {
+            Ok(response) => Ok(response),
+            Err(error) => Err(TelegramBotError::Error(format!("{error:?}")).into()),
+        }
+    }
+
     async fn delete_message(
         &self,
         chat_id: i64,
@@ -153,6 +249,21 @@ impl Messenger for MessengerImpl {
         }
     }
 
+    /// Whether the user is the creator or an administrator of the group chat.
+    async fn is_chat_admin(&self, chat_id: i64, user_id: u64) -> anyhow::Result<bool> {
+        let params = GetChatMemberParams::builder()
+            .chat_id(chat_id)
+            .user_id(user_id)
+            .build();
+        match self.api.get_chat_member(&params).await {
+            Ok(response) => Ok(matches!(
+                response.result,
+                ChatMember::Creator(_) | ChatMember::Administrator(_)
+            )),
+            Err(error) => Err(TelegramBotError::Error(format!("{error:?}")).into()),
+        }
+    }
+
     async fn send_image(
         &self,
         app_postgres: &PostgreSQLAppStorage,
@@ -252,7 +363,13 @@ impl Messenger for MessengerImpl {
         };
         let params = SendMessageParams {
             chat_id: ChatId::Integer(chat_id),
-            text: message_type.get_content(&self.renderer),
+            text: {
+                let content = message_type.get_content(&self.renderer);
+                match USER_MENTION.try_with(|mention| mention.clone()) {
+                    Ok(Some(mention)) => format!("{mention} {content}"),
+                    _ => content,
+                }
+            },
             parse_mode: Some(ParseMode::Html),
             entities: None,
             disable_web_page_preview: Some(true),
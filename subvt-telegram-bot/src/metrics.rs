@@ -43,6 +43,18 @@ pub fn command_call_counter(command: &str) -> IntCounter {
     METER.with_label_values(&[command])
 }
 
+pub fn inline_query_counter() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter(
+            METRIC_PREFIX,
+            "inline_query_count",
+            "The number of inline validator search queries",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
 pub fn query_call_counter(query: &QueryType) -> IntCounter {
     static METER: Lazy<IntCounterVec> = Lazy::new(|| {
         subvt_metrics::registry::register_int_counter_vec(
@@ -71,6 +83,7 @@ pub fn query_call_counter(query: &QueryType) -> IntCounter {
         QueryType::Rewards => "Rewards",
         QueryType::SettingsEdit(_) => "SettingsEdit",
         QueryType::SettingsNavigate(_) => "SettingsNavigate",
+        QueryType::ValidatorChart(_) => "ValidatorChart",
         QueryType::ValidatorInfo => "ValidatorInfo",
     };
     METER.with_label_values(&[label])
@@ -0,0 +1,44 @@
+//! Permanent deletion of the chats that have blocked the bot, along with their SubVT app users.
+//! Chats and users are soft-deleted when a chat blocks the bot, and restored if the chat
+//! interacts with the bot again within the retention period. Other deleted app users are purged
+//! by the app service.
+use crate::{Messenger, TelegramBot, CONFIG};
+
+const PURGE_PERIOD_SECONDS: u64 = 60 * 60 * 24;
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    /// Purges the chat and its app user. The chat is purged after the user, so that a failed
+    /// purge gets retried with the next run.
+    pub(crate) async fn purge_chat_and_user(
+        &self,
+        chat_id: i64,
+        app_user_id: u32,
+    ) -> anyhow::Result<()> {
+        self.app_postgres.purge_user(app_user_id).await?;
+        self.network_postgres.purge_chat(chat_id).await?;
+        Ok(())
+    }
+
+    async fn purge_deleted_chats_and_users(&self) -> anyhow::Result<()> {
+        let retention_days = CONFIG.telegram_bot.deleted_chat_retention_days;
+        let chats = self
+            .network_postgres
+            .get_purgeable_chats(retention_days)
+            .await?;
+        for (chat_id, app_user_id) in &chats {
+            self.purge_chat_and_user(*chat_id, *app_user_id).await?;
+        }
+        log::info!("Purged {} deleted chats.", chats.len());
+        Ok(())
+    }
+
+    /// Runs the purge once a day.
+    pub(crate) async fn run_purge_job(&'static self) {
+        loop {
+            if let Err(error) = self.purge_deleted_chats_and_users().await {
+                log::error!("Error while purging deleted chats and users: {:?}", error);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(PURGE_PERIOD_SECONDS)).await;
+        }
+    }
+}
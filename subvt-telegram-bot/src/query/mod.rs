@@ -1,6 +1,7 @@
 //! Queries happen as a result of user interaction with inline keyboards, usually a call-to-action
 //! or item selection (validator, referendum, NFT, etc.).
 use serde::{Deserialize, Serialize};
+use subvt_types::report::ValidatorChartType;
 
 pub mod process;
 
@@ -50,6 +51,8 @@ pub enum QueryType {
     SettingsEdit(SettingsEditQueryType),
     #[serde(rename = "SN")]
     SettingsNavigate(SettingsSubSection),
+    #[serde(rename = "VC")]
+    ValidatorChart(ValidatorChartType),
     #[serde(rename = "VI")]
     ValidatorInfo,
 }
@@ -122,20 +125,34 @@ pub enum SettingsEditQueryType {
     OneKVLocationChange,
     #[serde(rename = "OKVO")]
     OneKVOnlineStatusChange,
+    #[serde(rename = "CVD")]
+    ConvictionVotingDelegated,
+    #[serde(rename = "CVU")]
+    ConvictionVotingUndelegated,
     #[serde(rename = "RA")]
     ReferendumApproved,
     #[serde(rename = "RCA")]
     ReferendumCancelled,
     #[serde(rename = "RCO")]
     ReferendumConfirmed,
+    #[serde(rename = "RCS")]
+    ReferendumConfirmStarted,
+    #[serde(rename = "RCAB")]
+    ReferendumConfirmAborted,
     #[serde(rename = "RDS")]
     ReferendumDecisionStarted,
     #[serde(rename = "RK")]
     ReferendumKilled,
+    #[serde(rename = "RNV")]
+    ReferendumNotVoted,
     #[serde(rename = "RR")]
     ReferendumRejected,
     #[serde(rename = "RS")]
     ReferendumSubmitted,
+    #[serde(rename = "RTCH")]
+    ReferendumTallyChanged,
+    #[serde(rename = "RTC")]
+    ReferendumThresholdCrossed,
     #[serde(rename = "RTO")]
     ReferendumTimedOut,
 }
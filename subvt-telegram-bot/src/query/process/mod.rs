@@ -15,6 +15,7 @@ mod report_feature_request;
 mod rewards;
 mod settings;
 mod settings_navigate;
+mod validator_chart;
 mod validator_info;
 
 impl<M: Messenger + Send + Sync> TelegramBot<M> {
@@ -98,6 +99,15 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 self.process_settings_navigate_query(chat_id, *sub_section)
                     .await?;
             }
+            QueryType::ValidatorChart(chart_type) => {
+                self.process_validator_chart_query(
+                    chat_id,
+                    original_message_id,
+                    query,
+                    *chart_type,
+                )
+                .await?;
+            }
             QueryType::ValidatorInfo => {
                 self.process_validator_info_query(chat_id, original_message_id, query)
                     .await?;
@@ -19,6 +19,10 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
             let (track_id, referendum_id_str) = serde_json::from_str::<(u16, String)>(params_str)?;
             let referendum_id: u32 = referendum_id_str.parse()?;
             let post = polkassembly::fetch_referendum_details(referendum_id).await?;
+            let maybe_proposal_call = self
+                .network_postgres
+                .get_referendum_proposal_call(referendum_id)
+                .await?;
             let chat_validators = self.network_postgres.get_chat_validators(chat_id).await?;
             let mut chat_validator_votes: Vec<(TelegramChatValidator, Option<ReferendumVote>)> =
                 vec![];
@@ -41,6 +45,7 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                     chat_id,
                     Box::new(MessageType::ReferendumDetails {
                         post,
+                        maybe_proposal_call,
                         chat_validator_votes,
                     }),
                 )
@@ -45,7 +45,7 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
         channel_id_set.insert(telegram_channel_id);
         self.app_postgres
             .save_user_notification_rule(
-                user_id,
+                (user_id, None),
                 &type_code.to_string(),
                 (None, None),
                 (Some(CONFIG.substrate.network_id), true),
@@ -307,6 +307,24 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 .await?;
                 SettingsSubSection::OneKV
             }
+            SettingsEditQueryType::ConvictionVotingDelegated => {
+                self.process_notification_on_off_setting_query(
+                    user_id,
+                    query,
+                    NotificationTypeCode::ConvictionVotingDelegated,
+                )
+                .await?;
+                SettingsSubSection::Referenda
+            }
+            SettingsEditQueryType::ConvictionVotingUndelegated => {
+                self.process_notification_on_off_setting_query(
+                    user_id,
+                    query,
+                    NotificationTypeCode::ConvictionVotingUndelegated,
+                )
+                .await?;
+                SettingsSubSection::Referenda
+            }
             SettingsEditQueryType::ReferendumApproved => {
                 self.process_notification_on_off_setting_query(
                     user_id,
@@ -334,6 +352,24 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 .await?;
                 SettingsSubSection::Referenda
             }
+            SettingsEditQueryType::ReferendumConfirmStarted => {
+                self.process_notification_on_off_setting_query(
+                    user_id,
+                    query,
+                    NotificationTypeCode::ReferendumConfirmStarted,
+                )
+                .await?;
+                SettingsSubSection::Referenda
+            }
+            SettingsEditQueryType::ReferendumConfirmAborted => {
+                self.process_notification_on_off_setting_query(
+                    user_id,
+                    query,
+                    NotificationTypeCode::ReferendumConfirmAborted,
+                )
+                .await?;
+                SettingsSubSection::Referenda
+            }
             SettingsEditQueryType::ReferendumDecisionStarted => {
                 self.process_notification_on_off_setting_query(
                     user_id,
@@ -352,6 +388,15 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 .await?;
                 SettingsSubSection::Referenda
             }
+            SettingsEditQueryType::ReferendumNotVoted => {
+                self.process_notification_on_off_setting_query(
+                    user_id,
+                    query,
+                    NotificationTypeCode::ChainValidatorReferendumNotVoted,
+                )
+                .await?;
+                SettingsSubSection::Referenda
+            }
             SettingsEditQueryType::ReferendumRejected => {
                 self.process_notification_on_off_setting_query(
                     user_id,
@@ -370,6 +415,24 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 .await?;
                 SettingsSubSection::Referenda
             }
+            SettingsEditQueryType::ReferendumTallyChanged => {
+                self.process_notification_on_off_setting_query(
+                    user_id,
+                    query,
+                    NotificationTypeCode::ReferendumTallyChanged,
+                )
+                .await?;
+                SettingsSubSection::Referenda
+            }
+            SettingsEditQueryType::ReferendumThresholdCrossed => {
+                self.process_notification_on_off_setting_query(
+                    user_id,
+                    query,
+                    NotificationTypeCode::ReferendumThresholdCrossed,
+                )
+                .await?;
+                SettingsSubSection::Referenda
+            }
             SettingsEditQueryType::ReferendumTimedOut => {
                 self.process_notification_on_off_setting_query(
                     user_id,
@@ -0,0 +1,112 @@
+use crate::query::Query;
+use crate::{messenger::message::MessageType, Messenger, TelegramBot, CONFIG};
+use std::path::PathBuf;
+use subvt_plotter::PlotterError;
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{ChartImageFormat, ValidatorChartType};
+use subvt_utility::text::get_condensed_address;
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    /// Fetches the chart data and plots the chart. Returns `None` if there is no data to plot.
+    async fn plot_validator_chart(
+        &self,
+        account_id: &AccountId,
+        condensed_address: &str,
+        chart_type: ValidatorChartType,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let (era_chart_data, para_votes_chart_data) = match chart_type {
+            ValidatorChartType::ParaVotes => (
+                Vec::new(),
+                self.network_postgres
+                    .get_validator_session_para_votes_chart_data(
+                        account_id,
+                        CONFIG.plotter.chart_session_count,
+                    )
+                    .await?,
+            ),
+            _ => (
+                self.network_postgres
+                    .get_validator_era_chart_data(account_id, CONFIG.plotter.chart_era_count)
+                    .await?,
+                Vec::new(),
+            ),
+        };
+        let condensed_address = condensed_address.to_string();
+        // rendering is CPU-bound, kept off the async workers
+        let plot_result = tokio::task::spawn_blocking(move || {
+            subvt_plotter::validator_chart::plot_validator_chart(
+                chart_type,
+                &condensed_address,
+                &era_chart_data,
+                &para_votes_chart_data,
+                ChartImageFormat::Png,
+            )
+        })
+        .await?;
+        match plot_result {
+            Ok(path) => Ok(Some(path)),
+            Err(error) => match error.downcast_ref::<PlotterError>() {
+                Some(PlotterError::EmptyData) => Ok(None),
+                None => Err(error),
+            },
+        }
+    }
+
+    pub(crate) async fn process_validator_chart_query(
+        &self,
+        chat_id: i64,
+        original_message_id: Option<i32>,
+        query: &Query,
+        chart_type: ValidatorChartType,
+    ) -> anyhow::Result<()> {
+        if let Some(message_id) = original_message_id {
+            self.messenger.delete_message(chat_id, message_id).await?;
+        }
+        if let Some(id_str) = &query.parameter {
+            log::info!(
+                "Validator selected for {:?} chart in chat {}.",
+                chart_type,
+                chat_id,
+            );
+            if let Some(validator) = self
+                .network_postgres
+                .get_chat_validator_by_id(chat_id, id_str.parse()?)
+                .await?
+            {
+                let condensed_address = get_condensed_address(&validator.address, Some(3));
+                if let Some(path) = self
+                    .plot_validator_chart(&validator.account_id, &condensed_address, chart_type)
+                    .await?
+                {
+                    self.messenger
+                        .send_image(&self.app_postgres, &self.network_postgres, chat_id, &path)
+                        .await?;
+                    if let Err(error) = std::fs::remove_file(&path) {
+                        log::error!("Error while removing chart PNG file: {:?}", error);
+                    }
+                } else {
+                    self.messenger
+                        .send_message(
+                            &self.app_postgres,
+                            &self.network_postgres,
+                            chat_id,
+                            Box::new(MessageType::NoChartDataFound),
+                        )
+                        .await?;
+                }
+            } else {
+                self.messenger
+                    .send_message(
+                        &self.app_postgres,
+                        &self.network_postgres,
+                        chat_id,
+                        Box::new(MessageType::ValidatorNotFound {
+                            maybe_address: None,
+                        }),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
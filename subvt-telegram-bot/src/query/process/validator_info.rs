@@ -66,7 +66,7 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                                     )
                                     .await?,
                             ),
-                            missing_referendum_votes,
+                            missing_referendum_votes: Some(missing_referendum_votes),
                         }),
                     )
                     .await?;
@@ -68,3 +68,42 @@ async fn test_restore_chat_and_user() {
             .len()
     );
 }
+
+/// Tests the permanent deletion of a deleted chat along with the respective SubVT application
+/// user.
+#[tokio::test]
+async fn test_purge_chat_and_user() {
+    let chat_id = get_random_chat_id();
+    let bot = new_test_bot(MockMessenger::new()).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    let user_id = bot
+        .network_postgres
+        .get_chat_app_user_id(chat_id)
+        .await
+        .unwrap();
+    bot.network_postgres.delete_chat(chat_id).await.unwrap();
+    bot.app_postgres.delete_user(user_id).await.unwrap();
+    bot.purge_chat_and_user(chat_id, user_id).await.unwrap();
+    assert!(!bot
+        .network_postgres
+        .chat_exists_by_id(chat_id)
+        .await
+        .unwrap());
+    assert!(!bot.network_postgres.chat_is_deleted(chat_id).await.unwrap());
+    assert!(!bot.app_postgres.user_exists_by_id(user_id).await.unwrap());
+    assert!(bot
+        .app_postgres
+        .get_user_notification_channels(user_id)
+        .await
+        .unwrap()
+        .is_empty());
+    // a purged chat starts over with a new user
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    assert_ne!(
+        user_id,
+        bot.network_postgres
+            .get_chat_app_user_id(chat_id)
+            .await
+            .unwrap()
+    );
+}
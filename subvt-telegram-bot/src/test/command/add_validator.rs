@@ -3,7 +3,7 @@ use crate::test::util::data::{
     add_validator_to_redis, get_telegram_message_response, set_redis_finalized_block,
 };
 use crate::test::util::{get_random_account_id, get_random_chat_id, new_test_bot};
-use crate::MessageType;
+use crate::{MessageType, CONFIG};
 
 /// Tests the case when the user enters the /add command without following it
 /// by the stash address.
@@ -104,6 +104,48 @@ async fn test_add_validator_duplicate() {
         .unwrap();
 }
 
+/// Tests that the validator doesn't get added when the chat already has the maximum number of
+/// validators, and that the limit reply is the only message sent.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_add_validator_too_many_validators() {
+    let chat_id = get_random_chat_id();
+    let account_id = get_random_account_id();
+    let command_args = [account_id.to_ss58_check()];
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(
+                **message_type,
+                MessageType::TooManyValidatorsOnChat(max_validator_count)
+                    if max_validator_count == CONFIG.telegram_bot.max_validators_per_chat
+            )
+        })
+        .times(1)
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    for _ in 0..CONFIG.telegram_bot.max_validators_per_chat {
+        let account_id = get_random_account_id();
+        bot.network_postgres
+            .add_validator_to_chat(chat_id, &account_id, &account_id.to_ss58_check(), &None)
+            .await
+            .unwrap();
+    }
+    add_validator_to_redis(&bot.redis, &account_id)
+        .await
+        .unwrap();
+    bot.process_command(chat_id, "/add", &command_args)
+        .await
+        .unwrap();
+    assert!(!bot
+        .network_postgres
+        .chat_has_validator(chat_id, &account_id)
+        .await
+        .unwrap());
+}
+
 /// Test the successful addition of a validator to a chat.
 #[tokio::test]
 #[allow(clippy::borrowed_box)]
@@ -140,3 +182,56 @@ async fn test_add_validator_successful() {
         .await
         .unwrap();
 }
+
+/// Tests that an OpenGov delegate that is not a validator can be added to a chat.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_add_delegate_successful() {
+    let chat_id = get_random_chat_id();
+    let account_id = get_random_account_id();
+    let address = account_id.to_ss58_check();
+    let command_args = [address.clone()];
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_send_message()
+        .withf(
+            move |_, _, _, message_type: &Box<MessageType>| match &**message_type {
+                MessageType::ValidatorInfo {
+                    address: added_address,
+                    maybe_validator_details,
+                    ..
+                } => added_address == address.as_str() && maybe_validator_details.is_none(),
+                _ => false,
+            },
+        )
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(**message_type, MessageType::ValidatorAdded)
+        })
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    set_redis_finalized_block(&bot.redis).await.unwrap();
+    bot.network_postgres
+        .save_conviction_voting_delegation(
+            &get_random_account_id(),
+            &account_id,
+            0,
+            1,
+            1_000,
+            "0x00",
+            1,
+        )
+        .await
+        .unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_command(chat_id, "/add", &command_args)
+        .await
+        .unwrap();
+    assert!(bot
+        .network_postgres
+        .chat_has_validator(chat_id, &account_id)
+        .await
+        .unwrap());
+}
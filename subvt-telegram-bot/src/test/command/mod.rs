@@ -15,4 +15,5 @@ mod remove_validator;
 mod rewards;
 mod settings;
 mod start;
+mod validator_chart;
 mod validator_info;
@@ -1,7 +1,7 @@
 use crate::messenger::message::MessageType;
 use crate::messenger::MockMessenger;
-use crate::test::util::data::get_telegram_message_response;
-use crate::test::util::{get_random_chat_id, new_test_bot};
+use crate::test::util::data::{add_validator_to_redis, get_telegram_message_response};
+use crate::test::util::{get_random_account_id, get_random_chat_id, new_test_bot};
 
 /// /start command gets called by Telegram automatically at the beginning of a new chat.
 #[tokio::test]
@@ -19,3 +19,46 @@ async fn test_start() {
     bot.save_or_restore_chat(chat_id).await.unwrap();
     bot.process_command(chat_id, "/start", &[]).await.unwrap();
 }
+
+/// /start command with a validator address argument, i.e. the deep link of the inline query
+/// results, adds the validator to the chat.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_start_add_validator() {
+    let chat_id = get_random_chat_id();
+    let account_id = get_random_account_id();
+    let address = account_id.to_ss58_check();
+    let command_args = [address.clone()];
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_send_message()
+        .withf(
+            move |_, _, _, message_type: &Box<MessageType>| match &**message_type {
+                MessageType::ValidatorInfo {
+                    address: added_validator_address,
+                    ..
+                } => added_validator_address == address.as_str(),
+                _ => false,
+            },
+        )
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(**message_type, MessageType::ValidatorAdded)
+        })
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    add_validator_to_redis(&bot.redis, &account_id)
+        .await
+        .unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_command(chat_id, "/start", &command_args)
+        .await
+        .unwrap();
+    assert!(bot
+        .network_postgres
+        .chat_has_validator(chat_id, &account_id)
+        .await
+        .unwrap());
+}
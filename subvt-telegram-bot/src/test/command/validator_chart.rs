@@ -0,0 +1,96 @@
+use crate::messenger::MockMessenger;
+use crate::query::QueryType;
+use crate::test::util::data::{add_validator_to_redis, get_telegram_message_response};
+use crate::test::util::{get_random_account_id, get_random_chat_id, new_test_bot};
+use crate::MessageType;
+use rand::Rng;
+use subvt_types::report::ValidatorChartType;
+
+/// Tests the case when the user calls the /stakechart command before adding
+/// any validators to the chat.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_validator_chart_no_validator() {
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(**message_type, MessageType::NoValidatorsOnChat)
+        })
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_command(chat_id, "/stakechart", &[])
+        .await
+        .unwrap();
+}
+
+/// Tests calling the /paravotes command with a single validator on the chat that has no
+/// para votes yet.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_validator_chart_single_validator_no_data() {
+    let chat_id = get_random_chat_id();
+    let account_id = get_random_account_id();
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(&**message_type, MessageType::NoChartDataFound)
+        })
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    add_validator_to_redis(&bot.redis, &account_id)
+        .await
+        .unwrap();
+    bot.network_postgres
+        .add_validator_to_chat(chat_id, &account_id, &account_id.to_ss58_check(), &None)
+        .await
+        .unwrap();
+    bot.process_command(chat_id, "/paravotes", &[])
+        .await
+        .unwrap();
+}
+
+/// Tests calling the /erapoints command with multiple validators on the chat - the
+/// user should receive the list of validators to pick one from.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_validator_chart_multiple_validators() {
+    let mut rng = rand::thread_rng();
+    let validator_count = rng.gen_range(3..15);
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_send_message()
+        .withf(
+            move |_, _, _, message_type: &Box<MessageType>| match &**message_type {
+                MessageType::ValidatorList {
+                    validators,
+                    query_type,
+                } => {
+                    validators.len() == validator_count
+                        && matches!(
+                            query_type,
+                            QueryType::ValidatorChart(ValidatorChartType::EraPoints)
+                        )
+                }
+                _ => false,
+            },
+        )
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    for _ in 0..validator_count {
+        let account_id = get_random_account_id();
+        bot.network_postgres
+            .add_validator_to_chat(chat_id, &account_id, &account_id.to_ss58_check(), &None)
+            .await
+            .unwrap();
+    }
+    bot.process_command(chat_id, "/erapoints", &[])
+        .await
+        .unwrap();
+}
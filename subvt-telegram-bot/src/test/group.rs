@@ -0,0 +1,192 @@
+use crate::messenger::message::MessageType;
+use crate::messenger::MockMessenger;
+use crate::test::util::data::{get_group_message, get_telegram_message_response};
+use crate::test::util::{get_random_account_id, get_random_chat_id, new_test_bot};
+use subvt_types::telegram::TelegramChatState;
+
+/// Group members that aren't administrators cannot manage the validators of the group.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_group_member_cannot_remove_validator() {
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_is_chat_admin()
+        .withf(move |admin_chat_id, user_id| *admin_chat_id == chat_id && *user_id == 7)
+        .returning(|_, _| Ok(false));
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(**message_type, MessageType::GroupAdminOnly)
+        })
+        .times(1)
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_message(&get_group_message(chat_id, Some(7), "/remove"))
+        .await
+        .unwrap();
+}
+
+/// Group administrators can manage the validators of the group.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_group_admin_can_add_validator() {
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger.expect_is_chat_admin().returning(|_, _| Ok(true));
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(**message_type, MessageType::AddValidator)
+        })
+        .times(1)
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_message(&get_group_message(chat_id, Some(7), "/add"))
+        .await
+        .unwrap();
+}
+
+/// Anonymous group administrators post on behalf of the group, and don't need to be checked.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_group_anonymous_admin_can_add_validator() {
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger.expect_is_chat_admin().never();
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(**message_type, MessageType::AddValidator)
+        })
+        .times(1)
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_message(&get_group_message(chat_id, None, "/add"))
+        .await
+        .unwrap();
+}
+
+/// Commands that don't change the validators or the settings are open to all group members.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_group_member_can_view_help() {
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger.expect_is_chat_admin().never();
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(**message_type, MessageType::Help)
+        })
+        .times(1)
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_message(&get_group_message(chat_id, Some(7), "/help"))
+        .await
+        .unwrap();
+}
+
+/// Validators and the group flag move to the new chat id when a group becomes a supergroup.
+#[tokio::test]
+async fn test_group_migration() {
+    let chat_id = get_random_chat_id();
+    let new_chat_id = get_random_chat_id();
+    let account_id = get_random_account_id();
+    let bot = new_test_bot(MockMessenger::new()).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.network_postgres
+        .add_validator_to_chat(chat_id, &account_id, &account_id.to_ss58_check(), &None)
+        .await
+        .unwrap();
+    bot.migrate_chat(chat_id, new_chat_id).await.unwrap();
+    assert!(!bot
+        .network_postgres
+        .chat_exists_by_id(chat_id)
+        .await
+        .unwrap());
+    assert!(bot
+        .network_postgres
+        .chat_is_group(new_chat_id)
+        .await
+        .unwrap());
+    assert!(bot
+        .network_postgres
+        .chat_has_validator(new_chat_id, &account_id)
+        .await
+        .unwrap());
+    // no-op the second time, e.g. for the migration message in the new supergroup
+    bot.migrate_chat(chat_id, new_chat_id).await.unwrap();
+}
+
+/// An admin-only command by a member doesn't cancel the command that an administrator has
+/// started, and the other members' messages are ignored while the bot waits for the address.
+#[tokio::test]
+#[allow(clippy::borrowed_box)]
+async fn test_group_member_messages_are_ignored_while_adding_validator() {
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_is_chat_admin()
+        .withf(move |_, user_id| *user_id == 7)
+        .times(1)
+        .returning(|_, _| Ok(true));
+    messenger
+        .expect_is_chat_admin()
+        .withf(move |_, user_id| *user_id == 8)
+        .times(1)
+        .returning(|_, _| Ok(false));
+    messenger
+        .expect_send_message()
+        .withf(|_, _, _, message_type: &Box<MessageType>| {
+            matches!(
+                **message_type,
+                MessageType::AddValidator | MessageType::GroupAdminOnly
+            )
+        })
+        .times(2)
+        .returning(|_, _, _, _| Ok(get_telegram_message_response()));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    bot.process_message(&get_group_message(chat_id, Some(7), "/add"))
+        .await
+        .unwrap();
+    bot.process_message(&get_group_message(chat_id, Some(8), "/remove"))
+        .await
+        .unwrap();
+    assert_eq!(
+        bot.network_postgres.get_chat_state(chat_id).await.unwrap(),
+        Some(TelegramChatState::AddValidator),
+    );
+    let address = get_random_account_id().to_ss58_check();
+    for text in ["hello", address.as_str()] {
+        bot.process_message(&get_group_message(chat_id, Some(8), text))
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        bot.network_postgres.get_chat_state(chat_id).await.unwrap(),
+        Some(TelegramChatState::AddValidator),
+    );
+}
+
+/// The group flag is written only when it changes.
+#[tokio::test]
+async fn test_group_flag_is_saved_on_change() {
+    let chat_id = get_random_chat_id();
+    let bot = new_test_bot(MockMessenger::new()).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    let message = get_group_message(chat_id, Some(7), "hello");
+    bot.save_chat_is_group(&message.chat).await.unwrap();
+    assert!(bot.network_postgres.chat_is_group(chat_id).await.unwrap());
+    bot.network_postgres
+        .set_chat_is_group(chat_id, false)
+        .await
+        .unwrap();
+    bot.save_chat_is_group(&message.chat).await.unwrap();
+    assert!(!bot.network_postgres.chat_is_group(chat_id).await.unwrap());
+}
@@ -0,0 +1,125 @@
+use crate::messenger::MockMessenger;
+use crate::test::util::data::{
+    add_validator_with_display_to_redis, get_inline_query, get_inline_query_with_id,
+    get_telegram_bool_response,
+};
+use crate::test::util::{get_random_account_id, new_test_bot};
+use rand::Rng;
+use subvt_types::onekv::OneKVCandidateSummary;
+use subvt_types::subvt::ValidatorDetails;
+
+/// Queries that are too short get answered with no results.
+#[tokio::test]
+async fn test_inline_query_too_short() {
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_answer_inline_query()
+        .withf(
+            |_, validators: &Vec<(ValidatorDetails, Option<OneKVCandidateSummary>)>| {
+                validators.is_empty()
+            },
+        )
+        .returning(|_, _| Ok(get_telegram_bool_response(true)));
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.process_inline_query(&get_inline_query(" k "))
+        .await
+        .unwrap();
+}
+
+/// Test the search by the exact display of the validator's identity.
+#[tokio::test]
+async fn test_inline_query_display() {
+    let account_id = get_random_account_id();
+    let display = format!("Kaan Validator {}", rand::thread_rng().gen::<u32>());
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_answer_inline_query()
+        .withf(
+            move |_, validators: &Vec<(ValidatorDetails, Option<OneKVCandidateSummary>)>| {
+                validators
+                    .first()
+                    .map(|(validator_details, _)| validator_details.account.id == account_id)
+                    .unwrap_or(false)
+            },
+        )
+        .returning(|_, _| Ok(get_telegram_bool_response(true)));
+    let bot = new_test_bot(messenger).await.unwrap();
+    add_validator_with_display_to_redis(&bot.redis, &account_id, Some(display.clone()))
+        .await
+        .unwrap();
+    bot.process_inline_query(&get_inline_query(&display.to_uppercase()))
+        .await
+        .unwrap();
+}
+
+/// Test the fuzzy search, i.e. the characters of the query appear in the display in order.
+#[tokio::test]
+async fn test_inline_query_fuzzy_display() {
+    let account_id = get_random_account_id();
+    let suffix = rand::thread_rng().gen::<u32>();
+    let display = format!("Xylophone Node {suffix}");
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_answer_inline_query()
+        .withf(
+            move |_, validators: &Vec<(ValidatorDetails, Option<OneKVCandidateSummary>)>| {
+                validators
+                    .iter()
+                    .any(|(validator_details, _)| validator_details.account.id == account_id)
+            },
+        )
+        .returning(|_, _| Ok(get_telegram_bool_response(true)));
+    let bot = new_test_bot(messenger).await.unwrap();
+    add_validator_with_display_to_redis(&bot.redis, &account_id, Some(display))
+        .await
+        .unwrap();
+    bot.process_inline_query(&get_inline_query(&format!("xylo nd {suffix}")))
+        .await
+        .unwrap();
+}
+
+/// Test the search by the address prefix.
+#[tokio::test]
+async fn test_inline_query_address_prefix() {
+    let account_id = get_random_account_id();
+    let address = account_id.to_ss58_check();
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_answer_inline_query()
+        .withf(
+            move |_, validators: &Vec<(ValidatorDetails, Option<OneKVCandidateSummary>)>| {
+                validators
+                    .first()
+                    .map(|(validator_details, _)| validator_details.account.id == account_id)
+                    .unwrap_or(false)
+            },
+        )
+        .returning(|_, _| Ok(get_telegram_bool_response(true)));
+    let bot = new_test_bot(messenger).await.unwrap();
+    add_validator_with_display_to_redis(&bot.redis, &account_id, None)
+        .await
+        .unwrap();
+    bot.process_inline_query(&get_inline_query(&address[..16]))
+        .await
+        .unwrap();
+}
+
+/// Only the last one of the queries that the user sends while typing gets answered.
+#[tokio::test]
+async fn test_inline_query_debounce() {
+    let mut messenger = MockMessenger::new();
+    messenger
+        .expect_answer_inline_query()
+        .withf(|inline_query_id, _| inline_query_id == "3")
+        .times(1)
+        .returning(|_, _| Ok(get_telegram_bool_response(true)));
+    let bot = new_test_bot(messenger).await.unwrap();
+    let (first, second, third) = tokio::join!(
+        bot.process_inline_query(&get_inline_query_with_id("1", "ka")),
+        bot.process_inline_query(&get_inline_query_with_id("2", "kaa")),
+        bot.process_inline_query(&get_inline_query_with_id("3", "kaan")),
+    );
+    first.unwrap();
+    second.unwrap();
+    third.unwrap();
+}
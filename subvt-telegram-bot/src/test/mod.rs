@@ -1,3 +1,5 @@
 mod basic;
 mod command;
+mod group;
+mod inline_query;
 pub mod util;
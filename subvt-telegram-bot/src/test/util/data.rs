@@ -1,7 +1,8 @@
-use frankenstein::{Chat, ChatType, Message, MethodResponse};
+use frankenstein::{Chat, ChatType, InlineQuery, Message, MethodResponse};
 use subvt_persistence::redis::Redis;
 use subvt_types::crypto::AccountId;
 use subvt_types::report::BlockSummary;
+use subvt_types::substrate::IdentityRegistration;
 use subvt_types::subvt::ValidatorDetails;
 
 pub fn get_telegram_response_message() -> Message {
@@ -25,6 +26,50 @@ pub fn get_telegram_bool_response(result: bool) -> MethodResponse<bool> {
     }
 }
 
+/// Text message sent to a group chat by the user with the given id, or anonymously by an
+/// administrator on behalf of the group if there's no user id.
+pub fn get_group_message(chat_id: i64, maybe_user_id: Option<u64>, text: &str) -> Message {
+    let chat = serde_json::json!({
+        "id": chat_id,
+        "type": "group",
+        "title": "Test Group",
+    });
+    let mut message = serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": chat.clone(),
+        "text": text,
+    });
+    if let Some(user_id) = maybe_user_id {
+        message["from"] = serde_json::json!({
+            "id": user_id,
+            "is_bot": false,
+            "first_name": "Test",
+        });
+    } else {
+        message["sender_chat"] = chat;
+    }
+    serde_json::from_value(message).unwrap()
+}
+
+pub fn get_inline_query(query: &str) -> InlineQuery {
+    get_inline_query_with_id("1", query)
+}
+
+pub fn get_inline_query_with_id(id: &str, query: &str) -> InlineQuery {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "from": {
+            "id": 1,
+            "is_bot": false,
+            "first_name": "Test",
+        },
+        "query": query,
+        "offset": "",
+    }))
+    .unwrap()
+}
+
 pub async fn set_redis_finalized_block(redis: &Redis) -> anyhow::Result<BlockSummary> {
     let block_summary = BlockSummary {
         number: 13928858,
@@ -36,8 +81,20 @@ pub async fn set_redis_finalized_block(redis: &Redis) -> anyhow::Result<BlockSum
 }
 
 pub async fn add_validator_to_redis(redis: &Redis, account_id: &AccountId) -> anyhow::Result<()> {
+    add_validator_with_display_to_redis(redis, account_id, None).await
+}
+
+pub async fn add_validator_with_display_to_redis(
+    redis: &Redis,
+    account_id: &AccountId,
+    display: Option<String>,
+) -> anyhow::Result<()> {
     let mut validator_details = ValidatorDetails::default();
     validator_details.account.id = *account_id;
+    validator_details.account.identity = display.map(|display| IdentityRegistration {
+        display: Some(display),
+        ..Default::default()
+    });
     validator_details.account.address = account_id.to_ss58_check();
     let block = set_redis_finalized_block(redis).await?;
     redis
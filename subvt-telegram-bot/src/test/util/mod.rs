@@ -21,6 +21,10 @@ pub async fn new_test_bot(messenger: MockMessenger) -> anyhow::Result<TelegramBo
         redis,
         api,
         messenger,
+        inline_query_cache: Default::default(),
+        last_inline_query_ids: Default::default(),
+        chat_is_group_flags: Default::default(),
+        last_group_admin_sender_ids: Default::default(),
     })
 }
 
@@ -0,0 +1,130 @@
+//! Connection to a single Telemetry feed. Telemetry servers announce the chains they have with
+//! `AddedChain` messages, so the feed subscribes to the configured chain when the chain gets
+//! added to the server, and unsubscribes from any other chain it finds itself subscribed to.
+//! GRANDPA messages of the nodes are sent by the servers only to the feeds that request them
+//! after subscribing.
+use anyhow::Context;
+use async_tungstenite::{tokio::connect_async, tungstenite::Message};
+use futures::{SinkExt, StreamExt};
+use subvt_types::telemetry::FeedMessage;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Debug, PartialEq)]
+pub enum FeedEvent {
+    Messages(Vec<FeedMessage>),
+    /// The chain has been removed from the server, or the feed connection is lost. Node ids
+    /// of the feed are not valid anymore.
+    Unsubscribed,
+}
+
+#[derive(Clone, Debug)]
+pub struct Feed {
+    pub index: usize,
+    pub websocket_url: String,
+    pub genesis_hash: String,
+    pub receive_finality: bool,
+}
+
+fn normalize_hash(hash: &str) -> String {
+    hash.trim_start_matches("0x").to_lowercase()
+}
+
+impl Feed {
+    pub fn new(
+        index: usize,
+        websocket_url: &str,
+        genesis_hash: &str,
+        receive_finality: bool,
+    ) -> Self {
+        Self {
+            index,
+            websocket_url: websocket_url.to_string(),
+            genesis_hash: genesis_hash.to_string(),
+            receive_finality,
+        }
+    }
+
+    fn is_own_chain(&self, genesis_hash: &str) -> bool {
+        normalize_hash(genesis_hash) == normalize_hash(&self.genesis_hash)
+    }
+
+    /// Connects to the feed and sends the received messages along with the feed index, until
+    /// the connection is lost. The caller should send `FeedEvent::Unsubscribed` for the feed
+    /// when this function returns.
+    pub async fn receive_messages(
+        &self,
+        tx: &UnboundedSender<(usize, FeedEvent)>,
+    ) -> anyhow::Result<()> {
+        let (ws_stream, _) = connect_async(&self.websocket_url)
+            .await
+            .with_context(|| format!("Failed to connect to {}.", self.websocket_url))?;
+        log::info!(
+            "Connected to the Telemetry feed #{} at {}.",
+            self.index,
+            self.websocket_url
+        );
+        let (mut sink, mut stream) = ws_stream.split();
+        let mut is_subscribed = false;
+        while let Some(message_result) = stream.next().await {
+            let message = message_result?;
+            if !message.is_text() && !message.is_binary() {
+                continue;
+            }
+            let feed_messages = FeedMessage::from_bytes(&message.into_data())
+                .context("Error while decoding Telemetry feed message.")?;
+            let mut is_unsubscribed = false;
+            for feed_message in &feed_messages {
+                match feed_message {
+                    FeedMessage::AddedChain { genesis_hash, .. }
+                        if !is_subscribed && self.is_own_chain(genesis_hash) =>
+                    {
+                        log::info!(
+                            "Subscribe to chain {} on feed #{}.",
+                            genesis_hash,
+                            self.index
+                        );
+                        sink.send(Message::text(format!("subscribe:{genesis_hash}")))
+                            .await?;
+                        if self.receive_finality {
+                            sink.send(Message::text(format!("send-finality:{genesis_hash}")))
+                                .await?;
+                        }
+                        is_subscribed = true;
+                    }
+                    FeedMessage::SubscribedTo { genesis_hash }
+                        if !self.is_own_chain(genesis_hash) =>
+                    {
+                        log::warn!(
+                            "Subscribed to chain {} on feed #{}, unsubscribe.",
+                            genesis_hash,
+                            self.index,
+                        );
+                        sink.send(Message::text(format!("unsubscribe:{genesis_hash}")))
+                            .await?;
+                    }
+                    FeedMessage::RemovedChain { genesis_hash }
+                    | FeedMessage::UnsubscribedFrom { genesis_hash }
+                        if self.is_own_chain(genesis_hash) =>
+                    {
+                        log::warn!(
+                            "Chain {} is not available on feed #{} anymore.",
+                            genesis_hash,
+                            self.index,
+                        );
+                        is_subscribed = false;
+                        is_unsubscribed = true;
+                    }
+                    _ => (),
+                }
+            }
+            tx.send((self.index, FeedEvent::Messages(feed_messages)))?;
+            if is_unsubscribed {
+                tx.send((self.index, FeedEvent::Unsubscribed))?;
+            }
+        }
+        Err(anyhow::anyhow!(
+            "Telemetry feed #{} connection closed.",
+            self.index
+        ))
+    }
+}
@@ -0,0 +1,78 @@
+//! Last finalized blocks of the validator nodes, used to detect the late GRANDPA votes, i.e. the
+//! votes received by a node after it has already finalized the voted block. GRANDPA messages are
+//! saved in batches by a writer task, off the feed message loop.
+use rustc_hash::FxHashMap as HashMap;
+use std::sync::Arc;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::telemetry::finality::TelemetryFinalityMessage;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// At most this many queued messages are saved at once.
+const MAX_FINALITY_MESSAGE_BATCH_SIZE: usize = 1000;
+
+#[derive(Default)]
+pub(crate) struct FinalityTracker {
+    /// Validator address to the last finalized block number reported by the node. Kept after
+    /// the nodes disconnect, bounded by the number of validator addresses.
+    finalized_block_numbers: HashMap<String, u64>,
+}
+
+impl FinalityTracker {
+    pub fn set_finalized(&mut self, validator_address: &str, block_number: u64) {
+        let finalized_block_number = self
+            .finalized_block_numbers
+            .entry(validator_address.to_string())
+            .or_default();
+        *finalized_block_number = block_number.max(*finalized_block_number);
+    }
+
+    pub fn is_late_vote(&self, validator_address: &str, block_number: u64) -> bool {
+        matches!(
+            self.finalized_block_numbers.get(validator_address),
+            Some(finalized_block_number) if block_number <= *finalized_block_number
+        )
+    }
+}
+
+/// Saves the messages queued by the feed message loop. Messages queued while a batch is being
+/// saved are saved together in the next batch. Returns when the queue's sender is dropped.
+pub(crate) async fn save_finality_messages(
+    postgres: Arc<PostgreSQLNetworkStorage>,
+    mut rx: UnboundedReceiver<TelemetryFinalityMessage>,
+) {
+    while let Some(message) = rx.recv().await {
+        let mut messages = vec![message];
+        while messages.len() < MAX_FINALITY_MESSAGE_BATCH_SIZE {
+            match rx.try_recv() {
+                Ok(message) => messages.push(message),
+                Err(_) => break,
+            }
+        }
+        if let Err(error) = postgres.save_node_finality_messages(&messages).await {
+            log::error!(
+                "Error while saving {} node finality messages: {:?}",
+                messages.len(),
+                error
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FinalityTracker;
+
+    #[test]
+    fn test_late_vote() {
+        let mut tracker = FinalityTracker::default();
+        let address = "5GNJqTPyNqANBkUVMN1LPPrxXnFouWXoe2wNSmmEoLctxiZY";
+        // nothing finalized yet
+        assert!(!tracker.is_late_vote(address, 100));
+        tracker.set_finalized(address, 100);
+        // finalized block numbers don't go back
+        tracker.set_finalized(address, 98);
+        assert!(tracker.is_late_vote(address, 99));
+        assert!(tracker.is_late_vote(address, 100));
+        assert!(!tracker.is_late_vote(address, 101));
+    }
+}
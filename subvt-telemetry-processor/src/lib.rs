@@ -1,21 +1,43 @@
-//! Connects to the WebSocket feed stream of the given Telemetry and stores the feed data in
-//! the time series database (TimeScaleDB on PostgreSQL). Can be configured to connect to the
-//! W3F or Polkadot Telemetry servers.
+//! Connects to the WebSocket feed streams of the given Telemetry servers and stores the feed data
+//! in the time series database (TimeScaleDB on PostgreSQL). Can be configured to connect to the
+//! W3F and Polkadot Telemetry servers, and to private Telemetry servers. Nodes connected to more
+//! than one of the servers are merged by their network ids. Periodically matches the nodes to
+//! the validators, and snapshots the stats of the validator nodes for the validator node history
+//! reports. GRANDPA messages of the validator nodes are persisted for the finality participation
+//! reports. Client version history of the nodes and the network's client version distribution
+//! are updated along with the latest release, determined from the configured source.
 #![warn(clippy::disallowed_types)]
-use anyhow::Context;
-use async_lock::Mutex;
+use crate::feed::{Feed, FeedEvent};
+use crate::finality::FinalityTracker;
+use crate::node::{NodeRegistry, RemovedNode};
 use async_trait::async_trait;
-use async_tungstenite::{tokio::connect_async, tungstenite::Message};
-use futures::{SinkExt, StreamExt};
 use lazy_static::lazy_static;
-use rustc_hash::FxHashMap as HashMap;
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use subvt_config::Config;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_persistence::redis::Redis;
 use subvt_service_common::Service;
-use subvt_types::telemetry::{FeedMessage, NodeDetails, NodeLocation};
+use subvt_types::telemetry::finality::{TelemetryFinalityMessage, TelemetryFinalityMessageKind};
+use subvt_types::telemetry::validator::{match_nodes_to_validators, TelemetryNodeMatchCandidate};
+use subvt_types::telemetry::{FeedMessage, NodeLocation};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
+pub mod feed;
+mod finality;
 mod metrics;
+mod node;
+mod version;
+
+const FEED_RECONNECT_DELAY_SECONDS: u64 = 5;
+
+fn get_current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
@@ -25,14 +47,61 @@ lazy_static! {
 pub struct TelemetryProcessor;
 
 impl TelemetryProcessor {
+    async fn remove_feed_node(
+        postgres: &PostgreSQLNetworkStorage,
+        feed: &Feed,
+        removed_node: &RemovedNode,
+    ) -> anyhow::Result<()> {
+        if removed_node.is_last {
+            postgres.remove_node(removed_node.node_id).await?;
+        } else {
+            postgres
+                .remove_node_feed(removed_node.node_id, &feed.websocket_url)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Queues the GRANDPA message to be saved if the feed is the primary feed of the node with
+    /// the validator address.
+    fn queue_finality_message(
+        finality_tx: &UnboundedSender<TelemetryFinalityMessage>,
+        node_registry: &NodeRegistry,
+        finality_tracker: &mut FinalityTracker,
+        feed: &Feed,
+        mut message: TelemetryFinalityMessage,
+    ) {
+        if node_registry
+            .get_primary_node_id_by_validator_address(feed.index, &message.validator_address)
+            .is_none()
+        {
+            return;
+        }
+        match message.kind {
+            TelemetryFinalityMessageKind::Finalized => {
+                finality_tracker.set_finalized(&message.validator_address, message.block_number)
+            }
+            TelemetryFinalityMessageKind::Prevote | TelemetryFinalityMessageKind::Precommit => {
+                message.is_late =
+                    finality_tracker.is_late_vote(&message.validator_address, message.block_number);
+            }
+        }
+        if finality_tx.send(message).is_err() {
+            log::error!("Finality message writer has stopped.");
+        }
+    }
+
     async fn process_feed_message(
         postgres: &PostgreSQLNetworkStorage,
-        node_map: &Mutex<HashMap<u64, NodeDetails>>,
+        finality_tx: &UnboundedSender<TelemetryFinalityMessage>,
+        node_registry: &mut NodeRegistry,
+        finality_tracker: &mut FinalityTracker,
+        feed: &Feed,
         feed_message: &FeedMessage,
     ) -> anyhow::Result<()> {
         match feed_message {
             FeedMessage::Version(version) => {
-                log::debug!("Version: {}.", version);
+                log::debug!("Feed #{} version: {}.", feed.index, version);
             }
             FeedMessage::BestBlock {
                 block_number,
@@ -45,23 +114,28 @@ impl TelemetryProcessor {
                     timestamp,
                     avg_block_time
                 );
-                metrics::best_block_number().set(*block_number as i64);
-                postgres
-                    .update_best_block_number(*block_number, *timestamp, *avg_block_time)
-                    .await?;
+                // the first feed is the reference for the network status
+                if feed.index == 0 {
+                    metrics::best_block_number().set(*block_number as i64);
+                    postgres
+                        .update_best_block_number(*block_number, *timestamp, *avg_block_time)
+                        .await?;
+                }
             }
             FeedMessage::BestFinalized {
                 block_number,
                 block_hash,
             } => {
                 log::debug!("Finalized block: {} {}.", block_number, block_hash);
-                metrics::finalized_block_number().set(*block_number as i64);
-                postgres
-                    .update_finalized_block_number(
-                        *block_number,
-                        &format!("0x{}", block_hash.trim_start_matches("0x").to_uppercase()),
-                    )
-                    .await?;
+                if feed.index == 0 {
+                    metrics::finalized_block_number().set(*block_number as i64);
+                    postgres
+                        .update_finalized_block_number(
+                            *block_number,
+                            &format!("0x{}", block_hash.trim_start_matches("0x").to_uppercase()),
+                        )
+                        .await?;
+                }
             }
             FeedMessage::AddedNode {
                 node_id,
@@ -73,22 +147,41 @@ impl TelemetryProcessor {
                 location,
                 startup_time,
             } => {
-                log::debug!("Add node #{} :: {:?}.", node_id, node_details);
-                let mut map = node_map.lock().await;
-                if map.insert(*node_id, *node_details.clone()).is_none() {
-                    metrics::node_count().inc();
+                log::debug!(
+                    "Add node #{} of feed #{} :: {:?}.",
+                    node_id,
+                    feed.index,
+                    node_details
+                );
+                let added_node = node_registry.add_node(
+                    feed.index,
+                    *node_id,
+                    node_details.network_id.as_deref(),
+                );
+                if added_node.is_new
+                    || node_registry
+                        .get_primary_node_id(feed.index, *node_id)
+                        .is_some()
+                {
+                    node_registry.set_validator_address(
+                        added_node.node_id,
+                        node_details.validator.as_deref(),
+                    );
+                    postgres
+                        .save_node(added_node.node_id, node_details, *startup_time, location)
+                        .await?;
                 }
                 postgres
-                    .save_node(*node_id, node_details, *startup_time, location)
+                    .save_node_feed(added_node.node_id, &feed.websocket_url, *node_id)
                     .await?;
+                metrics::node_count().set(node_registry.node_count() as i64);
             }
             FeedMessage::RemovedNode { node_id } => {
-                log::debug!("Removed node #{}.", node_id);
-                let mut map = node_map.lock().await;
-                if map.remove(node_id).is_some() {
-                    metrics::node_count().dec();
+                log::debug!("Removed node #{} of feed #{}.", node_id, feed.index);
+                if let Some(removed_node) = node_registry.remove_node(feed.index, *node_id) {
+                    TelemetryProcessor::remove_feed_node(postgres, feed, &removed_node).await?;
                 }
-                postgres.remove_node(*node_id).await?;
+                metrics::node_count().set(node_registry.node_count() as i64);
             }
             FeedMessage::LocatedNode {
                 node_id,
@@ -96,44 +189,72 @@ impl TelemetryProcessor {
                 longitude,
                 city,
             } => {
-                let location = NodeLocation(*latitude, *longitude, city.clone());
-                postgres.update_node_location(*node_id, &location).await?;
+                if let Some(node_id) = node_registry.get_primary_node_id(feed.index, *node_id) {
+                    let location = NodeLocation(*latitude, *longitude, city.clone());
+                    postgres.update_node_location(node_id, &location).await?;
+                }
             }
             FeedMessage::NodeImportedBlock {
                 node_id,
                 block_details,
             } => {
                 log::trace!(
-                    "Node #{} imported block #{}.",
+                    "Node #{} of feed #{} imported block #{}.",
                     node_id,
+                    feed.index,
                     block_details.block_number
                 );
-                postgres
-                    .update_node_best_block(
-                        *node_id,
-                        block_details.block_number,
-                        &block_details.block_hash,
-                    )
-                    .await?;
+                if let Some(node_id) = node_registry.get_primary_node_id(feed.index, *node_id) {
+                    postgres
+                        .update_node_best_block(
+                            node_id,
+                            block_details.block_number,
+                            &block_details.block_hash,
+                        )
+                        .await?;
+                }
             }
             FeedMessage::NodeFinalizedBlock {
                 node_id,
                 block_number,
                 block_hash,
             } => {
-                log::trace!("Node #{} finalized block #{}.", node_id, block_number);
-                postgres
-                    .update_node_finalized_block(*node_id, *block_number, block_hash)
-                    .await?;
+                log::trace!(
+                    "Node #{} of feed #{} finalized block #{}.",
+                    node_id,
+                    feed.index,
+                    block_number
+                );
+                if let Some(node_id) = node_registry.get_primary_node_id(feed.index, *node_id) {
+                    postgres
+                        .update_node_finalized_block(node_id, *block_number, block_hash)
+                        .await?;
+                }
             }
             FeedMessage::NodeStatsUpdate { node_id, stats } => {
-                log::trace!("Node #{} status {:?}.", node_id, stats);
-                if let Err(error) = postgres.save_node_stats(*node_id, stats).await {
-                    log::error!("Error while saving node stats: {:?}", error);
+                log::trace!(
+                    "Node #{} of feed #{} status {:?}.",
+                    node_id,
+                    feed.index,
+                    stats
+                );
+                if let Some(node_id) = node_registry.get_primary_node_id(feed.index, *node_id) {
+                    if let Err(error) = postgres.save_node_stats(node_id, stats).await {
+                        log::error!("Error while saving node stats: {:?}", error);
+                    }
                 }
             }
             FeedMessage::NodeHardware { node_id, hardware } => {
-                log::trace!("Node #{} hardware {:?}.", node_id, hardware);
+                log::trace!(
+                    "Node #{} of feed #{} hardware {:?}.",
+                    node_id,
+                    feed.index,
+                    hardware
+                );
+                let node_id = match node_registry.get_primary_node_id(feed.index, *node_id) {
+                    Some(node_id) => node_id,
+                    None => return Ok(()),
+                };
                 if hardware.0.len() != hardware.1.len() || hardware.1.len() != hardware.2.len() {
                     log::warn!(
                         "Invalid node network stats data. Timestamp [{}], download bandwidth [{}] and upload bandwidth [{}] vectors are not of equal lengths.",
@@ -141,8 +262,7 @@ impl TelemetryProcessor {
                         hardware.1.len(),
                         hardware.0.len(),
                     );
-                } else if let Err(error) =
-                    postgres.save_node_network_stats(*node_id, hardware).await
+                } else if let Err(error) = postgres.save_node_network_stats(node_id, hardware).await
                 {
                     log::error!("Error while saving node network stats: {:?}", error);
                 }
@@ -155,22 +275,106 @@ impl TelemetryProcessor {
                 genesis_hash,
                 node_count,
             } => {
-                log::debug!("Added chain {} {} {}", name, genesis_hash, node_count);
+                log::debug!(
+                    "Added chain {} {} {} on feed #{}.",
+                    name,
+                    genesis_hash,
+                    node_count,
+                    feed.index
+                );
             }
             FeedMessage::RemovedChain { genesis_hash } => {
-                log::debug!("Removed chain {}", genesis_hash);
+                log::debug!("Removed chain {} on feed #{}.", genesis_hash, feed.index);
             }
             FeedMessage::SubscribedTo { genesis_hash } => {
-                log::debug!("Subscribed to chain {}", genesis_hash);
+                log::info!(
+                    "Subscribed to chain {} on feed #{}.",
+                    genesis_hash,
+                    feed.index
+                );
             }
             FeedMessage::UnsubscribedFrom { genesis_hash } => {
-                log::debug!("Unsubscribed from chain {}", genesis_hash);
+                log::info!(
+                    "Unsubscribed from chain {} on feed #{}.",
+                    genesis_hash,
+                    feed.index
+                );
+            }
+            FeedMessage::AfgFinalized {
+                address,
+                block_number,
+                block_hash,
+            } => {
+                log::trace!(
+                    "Node {} of feed #{} finalized block #{}.",
+                    address,
+                    feed.index,
+                    block_number
+                );
+                let message = TelemetryFinalityMessage {
+                    validator_address: address.clone(),
+                    kind: TelemetryFinalityMessageKind::Finalized,
+                    block_number: *block_number,
+                    block_hash: block_hash.clone(),
+                    voter: None,
+                    is_late: false,
+                    timestamp: get_current_timestamp(),
+                };
+                TelemetryProcessor::queue_finality_message(
+                    finality_tx,
+                    node_registry,
+                    finality_tracker,
+                    feed,
+                    message,
+                );
+            }
+            FeedMessage::AfgReceivedPrevote {
+                address,
+                block_number,
+                block_hash,
+                voter,
+            }
+            | FeedMessage::AfgReceivedPrecommit {
+                address,
+                block_number,
+                block_hash,
+                voter,
+            } => {
+                let kind = if matches!(feed_message, FeedMessage::AfgReceivedPrevote { .. }) {
+                    TelemetryFinalityMessageKind::Prevote
+                } else {
+                    TelemetryFinalityMessageKind::Precommit
+                };
+                log::trace!(
+                    "Node {} of feed #{} received {} for block #{} from {:?}.",
+                    address,
+                    feed.index,
+                    kind,
+                    block_number,
+                    voter,
+                );
+                let message = TelemetryFinalityMessage {
+                    validator_address: address.clone(),
+                    kind,
+                    block_number: *block_number,
+                    block_hash: block_hash.clone(),
+                    voter: voter.clone(),
+                    is_late: false,
+                    timestamp: get_current_timestamp(),
+                };
+                TelemetryProcessor::queue_finality_message(
+                    finality_tx,
+                    node_registry,
+                    finality_tracker,
+                    feed,
+                    message,
+                );
             }
             FeedMessage::Pong { message } => {
                 log::trace!("Pong :: {}", message);
             }
             FeedMessage::StaleNode { node_id } => {
-                log::trace!("Stale node #{}.", node_id);
+                log::trace!("Stale node #{} of feed #{}.", node_id, feed.index);
             }
             FeedMessage::NodeIOUpdate { node_id, io } => {
                 log::trace!("IO update #{} :: {:?}", node_id, io);
@@ -180,59 +384,178 @@ impl TelemetryProcessor {
         Ok(())
     }
 
-    async fn receive_messages(tx: Sender<Vec<FeedMessage>>) -> anyhow::Result<()> {
-        // connect to Telemetry feed
-        let (mut ws_stream, _) = connect_async(&CONFIG.telemetry.websocket_url)
-            .await
-            .context("Failed to connect")?;
-        log::debug!("Telemetry server websocket handshake has been successfully completed.");
-        ws_stream
-            .send(Message::text(format!(
-                "subscribe:{}",
-                CONFIG.substrate.chain_genesis_hash
-            )))
-            .await?;
-        log::debug!("Subscribed to the chain.");
-        // receiver thread
-        let error = loop {
-            let message_result = match ws_stream.next().await {
-                Some(message_result) => message_result,
-                None => {
-                    log::warn!("None message received. Try to receive next message.");
-                    continue;
-                }
-            };
-            let message = match message_result {
-                Ok(message) => message,
-                Err(error) => {
-                    log::error!("Error while receiving Telemetry message: {:?}", error);
-                    break error.into();
+    async fn process_feed_event(
+        postgres: &PostgreSQLNetworkStorage,
+        finality_tx: &UnboundedSender<TelemetryFinalityMessage>,
+        node_registry: &mut NodeRegistry,
+        finality_tracker: &mut FinalityTracker,
+        feed: &Feed,
+        event: FeedEvent,
+    ) -> anyhow::Result<()> {
+        match event {
+            FeedEvent::Messages(messages) => {
+                for message in messages {
+                    TelemetryProcessor::process_feed_message(
+                        postgres,
+                        finality_tx,
+                        node_registry,
+                        finality_tracker,
+                        feed,
+                        &message,
+                    )
+                    .await?;
                 }
-            };
-            let bytes = &message.into_data();
-            let feed_messages = match FeedMessage::from_bytes(bytes) {
-                Ok(feed_messages) => feed_messages,
-                Err(error) => {
-                    log::error!("Error while decoding Telemetry feed message: {:?}", error);
-                    break error;
+            }
+            FeedEvent::Unsubscribed => {
+                let removed_nodes = node_registry.remove_feed_nodes(feed.index);
+                log::info!(
+                    "Remove {} nodes of feed #{}.",
+                    removed_nodes.len(),
+                    feed.index
+                );
+                for removed_node in removed_nodes {
+                    TelemetryProcessor::remove_feed_node(postgres, feed, &removed_node).await?;
                 }
-            };
-            tx.send(feed_messages)?;
-        };
-        Err(error)
+                metrics::node_count().set(node_registry.node_count() as i64);
+            }
+        }
+        Ok(())
     }
 
-    async fn process_messages(
-        node_map: Mutex<HashMap<u64, NodeDetails>>,
-        rx: Receiver<Vec<FeedMessage>>,
+    /// Receives the messages of a feed, reconnects when the connection is lost.
+    async fn receive_feed_messages(feed: Feed, tx: UnboundedSender<(usize, FeedEvent)>) {
+        loop {
+            if let Err(error) = feed.receive_messages(&tx).await {
+                log::error!(
+                    "Error while receiving messages from feed #{}: {:?}",
+                    feed.index,
+                    error
+                );
+            }
+            metrics::connected_feed_count().dec();
+            if tx.send((feed.index, FeedEvent::Unsubscribed)).is_err() {
+                log::error!("Feed event channel is closed.");
+                return;
+            }
+            tokio::time::sleep(Duration::from_secs(FEED_RECONNECT_DELAY_SECONDS)).await;
+            metrics::connected_feed_count().inc();
+        }
+    }
+
+    /// Matches the Telemetry nodes to the active and inactive validators, then snapshots the
+    /// stats of the matched nodes and updates their finality participation in the recent
+    /// sessions.
+    async fn map_validator_nodes(
+        postgres: &PostgreSQLNetworkStorage,
+        app_postgres: &PostgreSQLAppStorage,
+        redis: &Redis,
     ) -> anyhow::Result<()> {
+        let finalized_block = redis.get_finalized_block_summary().await?;
+        let mut candidates = Vec::new();
+        for is_active in [true, false] {
+            for validator in redis
+                .get_validator_details_list(finalized_block.number, is_active)
+                .await?
+            {
+                candidates.push(TelemetryNodeMatchCandidate::from(&validator));
+            }
+        }
+        let nodes = postgres.get_telemetry_nodes().await?;
+        let overrides = app_postgres
+            .get_telemetry_node_overrides(CONFIG.substrate.network_id)
+            .await?;
+        let known_network_ids = postgres.get_telemetry_validator_network_ids().await?;
+        let node_validators =
+            match_nodes_to_validators(&nodes, &candidates, &overrides, &known_network_ids);
+        postgres
+            .save_telemetry_node_validators(&node_validators)
+            .await?;
+        metrics::validator_node_count().set(node_validators.len() as i64);
+        let snapshot_count = postgres.save_telemetry_validator_node_stats().await?;
+        let finality_session_count = postgres
+            .save_telemetry_validator_finality_sessions()
+            .await?;
+        log::info!(
+            "Matched {} of {} nodes to validators, saved {} validator node stats snapshots and {} validator finality sessions.",
+            node_validators.len(),
+            nodes.len(),
+            snapshot_count,
+            finality_session_count,
+        );
+        Ok(())
+    }
+
+    /// Updates the client version history of the nodes, snapshots the network's client version
+    /// distribution, and saves the latest release from the configured source.
+    async fn update_versions(postgres: &PostgreSQLNetworkStorage) -> anyhow::Result<()> {
+        let node_version_count = postgres.save_telemetry_node_versions().await?;
+        let version_count = postgres.save_telemetry_version_distribution().await?;
+        log::info!(
+            "Updated {} node versions, saved the distribution of {} client versions.",
+            node_version_count,
+            version_count,
+        );
+        if let Some(latest_release) = version::get_latest_release()? {
+            postgres
+                .save_telemetry_latest_release(&latest_release)
+                .await?;
+            log::info!("Latest release is {}.", latest_release.version);
+        } else {
+            log::warn!("Latest release is not known.");
+        }
+        Ok(())
+    }
+
+    async fn run_validator_node_mapping() -> anyhow::Result<()> {
         let postgres =
             PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?;
-        for messages in rx {
-            for message in messages {
-                TelemetryProcessor::process_feed_message(&postgres, &node_map, &message).await?;
+        let app_postgres =
+            PostgreSQLAppStorage::new(&CONFIG, CONFIG.get_app_postgres_url()).await?;
+        let redis = Redis::new()?;
+        loop {
+            tokio::time::sleep(Duration::from_secs(
+                CONFIG.telemetry.validator_node_mapping_period_seconds,
+            ))
+            .await;
+            if let Err(error) =
+                TelemetryProcessor::map_validator_nodes(&postgres, &app_postgres, &redis).await
+            {
+                log::error!("Error while mapping nodes to validators: {:?}", error);
+            }
+            if let Err(error) = TelemetryProcessor::update_versions(&postgres).await {
+                log::error!("Error while updating node versions: {:?}", error);
             }
         }
+    }
+
+    async fn process_messages(
+        feeds: &[Feed],
+        mut rx: UnboundedReceiver<(usize, FeedEvent)>,
+    ) -> anyhow::Result<()> {
+        let postgres = Arc::new(
+            PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?,
+        );
+        // feed node ids are valid only within a feed connection, nodes get re-added on connection
+        postgres.remove_all_nodes().await?;
+        // the writer saves the queued messages and stops when the sender is dropped on return
+        let (finality_tx, finality_rx) = mpsc::unbounded_channel();
+        tokio::spawn(finality::save_finality_messages(
+            postgres.clone(),
+            finality_rx,
+        ));
+        let mut node_registry = NodeRegistry::default();
+        let mut finality_tracker = FinalityTracker::default();
+        while let Some((feed_index, event)) = rx.recv().await {
+            TelemetryProcessor::process_feed_event(
+                &postgres,
+                &finality_tx,
+                &mut node_registry,
+                &mut finality_tracker,
+                &feeds[feed_index],
+                event,
+            )
+            .await?;
+        }
         Ok(())
     }
 }
@@ -250,18 +573,45 @@ impl Service for TelemetryProcessor {
         log::info!("Running the Telemetry processor.");
         metrics::init();
         metrics::node_count().set(0);
-        let (tx, rx) = mpsc::channel();
-        tokio::spawn(async move {
-            loop {
-                let tx = tx.clone();
-                if let Err(error) = TelemetryProcessor::receive_messages(tx).await {
-                    log::error!("Error while receiving feed messages: {:?}", error);
-                }
+        let feeds: Vec<Feed> = CONFIG
+            .telemetry
+            .get_websocket_urls()
+            .iter()
+            .enumerate()
+            .map(|(index, websocket_url)| {
+                Feed::new(
+                    index,
+                    websocket_url,
+                    &CONFIG.substrate.chain_genesis_hash,
+                    CONFIG.telemetry.receive_finality,
+                )
+            })
+            .collect();
+        metrics::connected_feed_count().set(feeds.len() as i64);
+        let (tx, rx) = mpsc::unbounded_channel();
+        let feed_receivers: Vec<_> = feeds
+            .iter()
+            .cloned()
+            .map(|feed| tokio::spawn(TelemetryProcessor::receive_feed_messages(feed, tx.clone())))
+            .collect();
+        let validator_node_mapping = tokio::spawn(async move {
+            if let Err(error) = TelemetryProcessor::run_validator_node_mapping().await {
+                log::error!(
+                    "Error while running the validator node mapping: {:?}",
+                    error
+                );
             }
         });
-        let node_map: Mutex<HashMap<u64, NodeDetails>> = Default::default();
-        if let Err(error) = TelemetryProcessor::process_messages(node_map, rx).await {
+        let result = TelemetryProcessor::process_messages(&feeds, rx).await;
+        // the service restarts the run, which would start other feed receivers and another
+        // mapping task
+        for feed_receiver in &feed_receivers {
+            feed_receiver.abort();
+        }
+        validator_node_mapping.abort();
+        if let Err(error) = result {
             log::error!("Error while processing feed messages: {:?}", error);
+            return Err(error);
         }
         Ok(())
     }
@@ -6,6 +6,8 @@ const METRIC_PREFIX: &str = "subvt_telemetry_processor";
 static BEST_BLOCK_NUMBER: OnceCell<IntGauge> = OnceCell::new();
 static FINALIZED_BLOCK_NUMBER: OnceCell<IntGauge> = OnceCell::new();
 static NODE_COUNT: OnceCell<IntGauge> = OnceCell::new();
+static VALIDATOR_NODE_COUNT: OnceCell<IntGauge> = OnceCell::new();
+static CONNECTED_FEED_COUNT: OnceCell<IntGauge> = OnceCell::new();
 
 pub(crate) fn init() {
     if BEST_BLOCK_NUMBER.get().is_none() {
@@ -33,7 +35,27 @@ pub(crate) fn init() {
             subvt_metrics::registry::register_int_gauge(
                 METRIC_PREFIX,
                 "node_count",
-                "Number of nodes connected to this Telemetry instance",
+                "Number of nodes connected to the Telemetry servers, merged by network id",
+            )
+            .unwrap(),
+        );
+    }
+    if VALIDATOR_NODE_COUNT.get().is_none() {
+        let _ = VALIDATOR_NODE_COUNT.set(
+            subvt_metrics::registry::register_int_gauge(
+                METRIC_PREFIX,
+                "validator_node_count",
+                "Number of nodes matched to validators",
+            )
+            .unwrap(),
+        );
+    }
+    if CONNECTED_FEED_COUNT.get().is_none() {
+        let _ = CONNECTED_FEED_COUNT.set(
+            subvt_metrics::registry::register_int_gauge(
+                METRIC_PREFIX,
+                "connected_feed_count",
+                "Number of Telemetry feeds connected or connecting",
             )
             .unwrap(),
         );
@@ -51,3 +73,11 @@ pub fn finalized_block_number() -> IntGauge {
 pub fn node_count() -> IntGauge {
     NODE_COUNT.get().unwrap().clone()
 }
+
+pub fn validator_node_count() -> IntGauge {
+    VALIDATOR_NODE_COUNT.get().unwrap().clone()
+}
+
+pub fn connected_feed_count() -> IntGauge {
+    CONNECTED_FEED_COUNT.get().unwrap().clone()
+}
@@ -0,0 +1,260 @@
+//! Nodes of all the Telemetry feeds. Each feed has its own node ids, and a node connected to more
+//! than one Telemetry server is reported by each of them, so the feed nodes are merged by their
+//! network ids into nodes with processor-assigned ids.
+use rustc_hash::FxHashMap as HashMap;
+
+/// Result of the addition of a feed node.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct AddedNode {
+    pub node_id: u64,
+    /// `false` if the node was already reported by another feed.
+    pub is_new: bool,
+}
+
+/// Result of the removal of a feed node.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct RemovedNode {
+    pub node_id: u64,
+    /// `true` if no other feed reports the node.
+    pub is_last: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct NodeRegistry {
+    next_node_id: u64,
+    /// (feed index, feed node id) to node id.
+    feed_nodes: HashMap<(usize, u64), u64>,
+    network_id_nodes: HashMap<String, u64>,
+    /// Feeds of each node in the order they reported the node, the first one is the primary
+    /// feed of the node.
+    node_feeds: HashMap<u64, Vec<usize>>,
+    node_network_ids: HashMap<u64, String>,
+    /// Validator (authority) addresses reported by the nodes, GRANDPA messages of the nodes are
+    /// identified by these addresses.
+    validator_address_nodes: HashMap<String, u64>,
+    node_validator_addresses: HashMap<u64, String>,
+}
+
+impl NodeRegistry {
+    pub fn node_count(&self) -> usize {
+        self.node_feeds.len()
+    }
+
+    pub fn add_node(
+        &mut self,
+        feed_index: usize,
+        feed_node_id: u64,
+        maybe_network_id: Option<&str>,
+    ) -> AddedNode {
+        // re-added feed node, i.e. network id has changed
+        if let Some(node_id) = self.feed_nodes.get(&(feed_index, feed_node_id)).cloned() {
+            let network_id_node_id = maybe_network_id
+                .and_then(|network_id| self.network_id_nodes.get(network_id))
+                .cloned();
+            if network_id_node_id.is_none() || network_id_node_id == Some(node_id) {
+                if let Some(network_id) = maybe_network_id {
+                    self.set_network_id(node_id, network_id);
+                }
+                return AddedNode {
+                    node_id,
+                    is_new: false,
+                };
+            }
+            self.remove_node(feed_index, feed_node_id);
+        }
+        let existing_node_id = maybe_network_id
+            .and_then(|network_id| self.network_id_nodes.get(network_id))
+            .cloned();
+        let (node_id, is_new) = match existing_node_id {
+            Some(node_id) => (node_id, false),
+            None => {
+                self.next_node_id += 1;
+                (self.next_node_id, true)
+            }
+        };
+        self.feed_nodes.insert((feed_index, feed_node_id), node_id);
+        let feeds = self.node_feeds.entry(node_id).or_default();
+        if !feeds.contains(&feed_index) {
+            feeds.push(feed_index);
+        }
+        if let Some(network_id) = maybe_network_id {
+            self.set_network_id(node_id, network_id);
+        }
+        AddedNode { node_id, is_new }
+    }
+
+    fn set_network_id(&mut self, node_id: u64, network_id: &str) {
+        if let Some(old_network_id) = self
+            .node_network_ids
+            .insert(node_id, network_id.to_string())
+        {
+            self.network_id_nodes.remove(&old_network_id);
+        }
+        self.network_id_nodes
+            .insert(network_id.to_string(), node_id);
+    }
+
+    pub fn set_validator_address(&mut self, node_id: u64, maybe_validator_address: Option<&str>) {
+        if let Some(old_validator_address) = self.node_validator_addresses.remove(&node_id) {
+            self.validator_address_nodes.remove(&old_validator_address);
+        }
+        if let Some(validator_address) = maybe_validator_address {
+            self.node_validator_addresses
+                .insert(node_id, validator_address.to_string());
+            self.validator_address_nodes
+                .insert(validator_address.to_string(), node_id);
+        }
+    }
+
+    pub fn remove_node(&mut self, feed_index: usize, feed_node_id: u64) -> Option<RemovedNode> {
+        let node_id = self.feed_nodes.remove(&(feed_index, feed_node_id))?;
+        let is_last = match self.node_feeds.get_mut(&node_id) {
+            Some(feeds) => {
+                feeds.retain(|index| *index != feed_index);
+                feeds.is_empty()
+            }
+            None => true,
+        };
+        if is_last {
+            self.node_feeds.remove(&node_id);
+            if let Some(network_id) = self.node_network_ids.remove(&node_id) {
+                self.network_id_nodes.remove(&network_id);
+            }
+            self.set_validator_address(node_id, None);
+        }
+        Some(RemovedNode { node_id, is_last })
+    }
+
+    /// Removes all nodes of a feed, when the feed disconnects or the chain gets removed from it.
+    pub fn remove_feed_nodes(&mut self, feed_index: usize) -> Vec<RemovedNode> {
+        let mut feed_node_ids: Vec<u64> = self
+            .feed_nodes
+            .keys()
+            .filter(|(index, _)| *index == feed_index)
+            .map(|(_, feed_node_id)| *feed_node_id)
+            .collect();
+        feed_node_ids.sort_unstable();
+        feed_node_ids
+            .into_iter()
+            .filter_map(|feed_node_id| self.remove_node(feed_index, feed_node_id))
+            .collect()
+    }
+
+    pub fn get_node_id(&self, feed_index: usize, feed_node_id: u64) -> Option<u64> {
+        self.feed_nodes.get(&(feed_index, feed_node_id)).cloned()
+    }
+
+    /// Node id, if the feed is the primary feed of the node. Time series data, such as the node
+    /// stats, are saved only from the primary feed to avoid duplicates.
+    pub fn get_primary_node_id(&self, feed_index: usize, feed_node_id: u64) -> Option<u64> {
+        let node_id = self.get_node_id(feed_index, feed_node_id)?;
+        match self.node_feeds.get(&node_id) {
+            Some(feeds) if feeds.first() == Some(&feed_index) => Some(node_id),
+            _ => None,
+        }
+    }
+
+    /// Node id of the validator address, if the feed is the primary feed of the node. Feeds
+    /// report the GRANDPA messages of the nodes by their validator addresses.
+    pub fn get_primary_node_id_by_validator_address(
+        &self,
+        feed_index: usize,
+        validator_address: &str,
+    ) -> Option<u64> {
+        let node_id = self.validator_address_nodes.get(validator_address)?;
+        match self.node_feeds.get(node_id) {
+            Some(feeds) if feeds.first() == Some(&feed_index) => Some(*node_id),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddedNode, NodeRegistry, RemovedNode};
+
+    #[test]
+    fn test_merge_nodes_by_network_id() {
+        let mut registry = NodeRegistry::default();
+        assert_eq!(
+            registry.add_node(0, 10, Some("12D3KooWA")),
+            AddedNode {
+                node_id: 1,
+                is_new: true
+            }
+        );
+        assert_eq!(
+            registry.add_node(1, 3, Some("12D3KooWA")),
+            AddedNode {
+                node_id: 1,
+                is_new: false
+            }
+        );
+        assert_eq!(
+            registry.add_node(1, 4, None),
+            AddedNode {
+                node_id: 2,
+                is_new: true
+            }
+        );
+        assert_eq!(registry.node_count(), 2);
+        assert_eq!(registry.get_node_id(1, 3), Some(1));
+        assert_eq!(registry.get_primary_node_id(0, 10), Some(1));
+        assert_eq!(registry.get_primary_node_id(1, 3), None);
+        registry.set_validator_address(1, Some("5GNJqTPyNqANBkUVMN1LPPrxXnFouWXoe2wNSmmEoLctxiZY"));
+        assert_eq!(
+            registry.get_primary_node_id_by_validator_address(
+                0,
+                "5GNJqTPyNqANBkUVMN1LPPrxXnFouWXoe2wNSmmEoLctxiZY"
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            registry.get_primary_node_id_by_validator_address(
+                1,
+                "5GNJqTPyNqANBkUVMN1LPPrxXnFouWXoe2wNSmmEoLctxiZY"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remove_nodes() {
+        let mut registry = NodeRegistry::default();
+        registry.add_node(0, 10, Some("12D3KooWA"));
+        registry.add_node(1, 3, Some("12D3KooWA"));
+        registry.add_node(1, 4, Some("12D3KooWB"));
+        assert_eq!(
+            registry.remove_node(0, 10),
+            Some(RemovedNode {
+                node_id: 1,
+                is_last: false
+            })
+        );
+        // the other feed becomes the primary feed
+        assert_eq!(registry.get_primary_node_id(1, 3), Some(1));
+        assert_eq!(registry.remove_node(0, 10), None);
+        assert_eq!(
+            registry.remove_feed_nodes(1),
+            vec![
+                RemovedNode {
+                    node_id: 1,
+                    is_last: true
+                },
+                RemovedNode {
+                    node_id: 2,
+                    is_last: true
+                },
+            ]
+        );
+        assert_eq!(registry.node_count(), 0);
+        // network id is released with the node
+        assert_eq!(
+            registry.add_node(0, 11, Some("12D3KooWA")),
+            AddedNode {
+                node_id: 3,
+                is_new: true
+            }
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Determines the latest client release from the configured source, for the comparison of the
+//! node versions to the latest release.
+use crate::CONFIG;
+use anyhow::Context;
+use subvt_config::TelemetryLatestVersionSource;
+use subvt_types::telemetry::version::{TelemetryRelease, TelemetryReleaseManifest};
+
+/// Latest release from the static configuration or the local release manifest. `None` if the
+/// source doesn't have a release.
+pub(crate) fn get_latest_release() -> anyhow::Result<Option<TelemetryRelease>> {
+    match CONFIG.telemetry.latest_version_source {
+        TelemetryLatestVersionSource::Config => {
+            let version = CONFIG.telemetry.latest_version.trim();
+            if version.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(TelemetryRelease {
+                version: version.to_string(),
+                released_at: None,
+            }))
+        }
+        TelemetryLatestVersionSource::Manifest => {
+            let path = &CONFIG.telemetry.release_manifest_path;
+            let manifest_json = std::fs::read_to_string(path)
+                .context(format!("Cannot read the release manifest at {path}."))?;
+            let manifest: TelemetryReleaseManifest = serde_json::from_str(&manifest_json)
+                .context(format!("Cannot parse the release manifest at {path}."))?;
+            Ok(manifest.get_latest_release().cloned())
+        }
+    }
+}
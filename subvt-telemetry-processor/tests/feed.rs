@@ -0,0 +1,100 @@
+//! Tests the feed subscription management against a local mock Telemetry feed server that
+//! replays captured feed frames.
+use async_tungstenite::tungstenite::Message;
+use futures::{SinkExt, StreamExt};
+use std::time::Duration;
+use subvt_telemetry_processor::feed::{Feed, FeedEvent};
+use subvt_types::telemetry::FeedMessage;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+const POLKADOT_GENESIS_HASH: &str =
+    "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
+const KUSAMA_GENESIS_HASH: &str =
+    "0xb0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe";
+/// One frame per line, in the order they get sent.
+const FEED_FRAMES: &str = include_str!("data/feed_frames.txt");
+
+/// Replays the frames to the first client and closes the connection. Returns the text messages
+/// received from the client.
+async fn run_mock_feed_server(listener: TcpListener) -> Vec<String> {
+    let (tcp_stream, _) = listener.accept().await.unwrap();
+    let ws_stream = async_tungstenite::tokio::accept_async(tcp_stream)
+        .await
+        .unwrap();
+    let (mut sink, mut stream) = ws_stream.split();
+    let receiver = tokio::spawn(async move {
+        let mut client_messages = Vec::new();
+        while let Some(Ok(message)) = stream.next().await {
+            if let Message::Text(text) = message {
+                client_messages.push(text);
+            }
+        }
+        client_messages
+    });
+    for frame in FEED_FRAMES.lines().filter(|line| !line.trim().is_empty()) {
+        sink.send(Message::text(frame)).await.unwrap();
+        // give the client time to respond before the next frame
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    sink.close().await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), receiver)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_feed_subscription() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(run_mock_feed_server(listener));
+    let feed = Feed::new(1, &format!("ws://{address}"), POLKADOT_GENESIS_HASH, true);
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    // returns an error when the server closes the connection
+    assert!(feed.receive_messages(&tx).await.is_err());
+    drop(tx);
+    // subscribes when the chain gets added, unsubscribes from other chains
+    assert_eq!(
+        server.await.unwrap(),
+        vec![
+            format!("subscribe:{POLKADOT_GENESIS_HASH}"),
+            format!("send-finality:{POLKADOT_GENESIS_HASH}"),
+            format!("unsubscribe:{KUSAMA_GENESIS_HASH}"),
+        ]
+    );
+    let mut events = Vec::new();
+    while let Some((feed_index, event)) = rx.recv().await {
+        assert_eq!(feed_index, 1);
+        events.push(event);
+    }
+    // one event per frame, and the removal of the chain
+    assert_eq!(events.len(), 5);
+    match &events[1] {
+        FeedEvent::Messages(messages) => {
+            assert_eq!(messages.len(), 4);
+            match &messages[3] {
+                FeedMessage::AddedNode {
+                    node_id,
+                    node_details,
+                    location,
+                    ..
+                } => {
+                    assert_eq!(*node_id, 42);
+                    assert_eq!(node_details.name, "helikon-polkadot-1");
+                    assert_eq!(
+                        node_details.network_id.as_deref(),
+                        Some("12D3KooWDjwLQxJ2XGVK5Q5nPJ6mEuX1P6e2q1dnV9ELz3W9v7GN")
+                    );
+                    assert_eq!(
+                        location.as_ref().map(|location| location.2.as_str()),
+                        Some("Berlin")
+                    );
+                }
+                message => panic!("Unexpected feed message: {message:?}"),
+            }
+        }
+        event => panic!("Unexpected feed event: {event:?}"),
+    }
+    assert_eq!(events.last(), Some(&FeedEvent::Unsubscribed));
+}
@@ -66,3 +66,87 @@ pub struct OneKVOnlineStatusChange {
     pub validator_account_id: AccountId,
     pub offline_since: u64,
 }
+
+/// Reminds that the validator (or its voting account) has neither voted on nor delegated its
+/// votes for an open referendum that is close to its decision deadline.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReferendumVoteReminder {
+    pub validator_account_id: AccountId,
+    pub voting_account_id: AccountId,
+    pub referendum_index: u32,
+    pub track_id: u16,
+    pub maybe_title: Option<String>,
+    pub deadline_block_number: u64,
+    pub hours_left: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferendumThreshold {
+    Approval,
+    Support,
+}
+
+/// The approval or the support of a deciding referendum has crossed the current threshold of
+/// its track curve, in either direction.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReferendumThresholdCrossed {
+    pub referendum_index: u32,
+    pub track_id: u16,
+    pub maybe_title: Option<String>,
+    pub threshold: ReferendumThreshold,
+    pub is_above: bool,
+    pub value_per_billion: u32,
+    pub threshold_per_billion: u32,
+    /// Whether both the approval and the support are above their thresholds.
+    pub is_passing: bool,
+}
+
+/// A referendum has entered or left its confirmation period.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReferendumConfirmingChanged {
+    pub referendum_index: u32,
+    pub track_id: u16,
+    pub maybe_title: Option<String>,
+    pub is_confirming: bool,
+    pub maybe_confirm_end_block_number: Option<u64>,
+}
+
+/// The tally of a referendum has changed by more than the rule threshold since the last alert.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReferendumTallyChanged {
+    pub referendum_index: u32,
+    pub track_id: u16,
+    pub maybe_title: Option<String>,
+    pub prev_ayes: Balance,
+    pub prev_nays: Balance,
+    pub prev_support: Balance,
+    pub ayes: Balance,
+    pub nays: Balance,
+    pub support: Balance,
+    pub approval_per_billion: u32,
+    pub support_per_billion: u32,
+    pub change_percent: f64,
+}
+
+/// A node of the validator keeps importing blocks but has stopped casting GRANDPA votes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TelemetryFinalityParticipationStopped {
+    pub validator_account_id: AccountId,
+    pub node_name: String,
+    pub validator_address: String,
+    pub best_block_number: Option<u64>,
+    pub last_finality_block_number: Option<u64>,
+    pub last_finality_timestamp: u64,
+}
+
+/// A node of the validator still runs an older client version than the latest release after the
+/// grace period following the release.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TelemetryBinaryOutOfDate {
+    pub validator_account_id: AccountId,
+    pub node_name: String,
+    pub client_version: String,
+    pub latest_version: String,
+    pub latest_version_released_timestamp: u64,
+}
@@ -5,9 +5,10 @@ use crate::app::{
     notification::{
         Notification, NotificationParamDataType, NotificationPeriodType, UserNotificationChannel,
     },
-    Block, Network, UserValidator,
+    Block, Network, UserDevice, UserValidator,
 };
 use crate::crypto::AccountId;
+use chrono::NaiveDateTime;
 use std::str::FromStr;
 
 pub type PostgresNetwork = (
@@ -54,7 +55,21 @@ impl From<PostgresNetwork> for Network {
     }
 }
 
-pub type PostgresUserValidator = (i32, i32, i32, String);
+pub type PostgresUserDevice = (i32, i32, String, Option<String>, NaiveDateTime);
+
+impl From<PostgresUserDevice> for UserDevice {
+    fn from(db_user_device: PostgresUserDevice) -> Self {
+        UserDevice {
+            id: db_user_device.0 as u32,
+            user_id: db_user_device.1 as u32,
+            public_key_hex: db_user_device.2,
+            name: db_user_device.3,
+            created_at: Some(db_user_device.4),
+        }
+    }
+}
+
+pub type PostgresUserValidator = (i32, i32, i32, String, Option<NaiveDateTime>, Option<i32>);
 
 impl From<PostgresUserValidator> for UserValidator {
     fn from(db_user_validator: PostgresUserValidator) -> Self {
@@ -63,6 +78,8 @@ impl From<PostgresUserValidator> for UserValidator {
             user_id: db_user_validator.1 as u32,
             network_id: db_user_validator.2 as u32,
             validator_account_id: AccountId::from_str(&db_user_validator.3).unwrap(),
+            is_ownership_verified: db_user_validator.4.is_some(),
+            team_id: db_user_validator.5.map(|team_id| team_id as u32),
         }
     }
 }
@@ -90,6 +107,7 @@ pub type PostgresUserNotificationRule = (
     NotificationPeriodType,
     i32,
     Option<String>,
+    Option<i32>,
 );
 
 pub type PostgresNotificationParamType = (
@@ -0,0 +1,67 @@
+use crate::crypto::AccountId;
+use crate::substrate::Balance;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The track, conviction and balance are not part of the event, and get resolved from the
+/// `delegate` call.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConvictionVotingDelegatedEvent {
+    pub id: u32,
+    pub block_hash: String,
+    pub block_number: u64,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: u32,
+    pub original_account_id: AccountId,
+    pub delegate_account_id: AccountId,
+    pub track_id: Option<u16>,
+    pub conviction: Option<u8>,
+    pub balance: Option<Balance>,
+}
+
+/// The track is resolved from the `undelegate` call, and the delegate from the delegation
+/// state at the time of the call.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConvictionVotingUndelegatedEvent {
+    pub id: u32,
+    pub block_hash: String,
+    pub block_number: u64,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: u32,
+    pub account_id: AccountId,
+    pub track_id: Option<u16>,
+    pub delegate_account_id: Option<AccountId>,
+}
+
+/// Shared by the `Voted` and `VoteRemoved` events. The referendum index is missing only when
+/// it could be resolved from neither the event nor the extrinsic.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConvictionVotingVoteEvent {
+    pub id: u32,
+    pub block_hash: String,
+    pub block_number: u64,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: u32,
+    pub account_id: AccountId,
+    pub referendum_index: Option<u32>,
+    /// One of `standard`, `split` or `split_abstain`.
+    pub vote_type: String,
+    pub aye_balance: Option<Balance>,
+    pub nay_balance: Option<Balance>,
+    pub abstain_balance: Option<Balance>,
+    pub conviction: Option<u8>,
+}
+
+/// Current delegation of an account on a track.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConvictionVotingDelegation {
+    pub delegator_account_id: AccountId,
+    pub delegate_account_id: AccountId,
+    pub track_id: u16,
+    pub conviction: u8,
+    /// Delegated capital.
+    pub balance: Balance,
+    /// Conviction-weighted votes of the delegated capital.
+    pub votes: Balance,
+    pub block_number: u64,
+}
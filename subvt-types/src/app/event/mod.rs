@@ -2,8 +2,10 @@
 use crate::crypto::AccountId;
 use serde::{Deserialize, Serialize};
 
+pub mod conviction_voting;
 pub mod democracy;
 pub mod referenda;
+pub mod treasury;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ValidatorOfflineEvent {
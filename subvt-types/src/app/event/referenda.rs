@@ -1,3 +1,4 @@
+use crate::governance::call::ReferendumCall;
 use crate::substrate::Balance;
 use serde::{Deserialize, Serialize};
 
@@ -45,6 +46,9 @@ pub struct ReferendumDecisionStartedEvent {
     pub ayes: Balance,
     pub nays: Balance,
     pub support: Balance,
+    /// Decoded proposal call of the referendum, if it's been saved by the referendum updater.
+    #[serde(default)]
+    pub maybe_proposal_call: Option<ReferendumCall>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -79,6 +83,9 @@ pub struct ReferendumSubmittedEvent {
     pub event_index: u32,
     pub referendum_index: u32,
     pub track_id: u16,
+    /// Decoded proposal call of the referendum, if it's been saved by the referendum updater.
+    #[serde(default)]
+    pub maybe_proposal_call: Option<ReferendumCall>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -0,0 +1,41 @@
+use crate::crypto::AccountId;
+use crate::substrate::Balance;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The event through which a payment was granted from the treasury.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TreasurySpendSource {
+    /// `Treasury.SpendApproved`, native token spend approved by a referendum.
+    SpendApproved,
+    /// `Treasury.AssetSpendApproved`, any-asset spend approved by a referendum.
+    AssetSpendApproved,
+    /// `Treasury.Awarded`, legacy treasury proposal award.
+    Awarded,
+    /// `Bounties.BountyClaimed`.
+    BountyClaimed,
+    /// `ChildBounties.Claimed`.
+    ChildBountyClaimed,
+}
+
+/// A treasury payment granted to a beneficiary, merged from the treasury and bounty events.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TreasurySpendEvent {
+    pub source: TreasurySpendSource,
+    pub block_hash: String,
+    pub block_number: u64,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: u32,
+    /// Proposal or spend index for the treasury events, bounty index for the bounty events,
+    /// and the parent bounty index for the child bounty events.
+    pub index: u32,
+    pub child_bounty_index: Option<u32>,
+    pub amount: Balance,
+    /// Missing for the native token spends.
+    pub asset_kind: Option<serde_json::Value>,
+    /// Missing when an asset spend beneficiary doesn't resolve to an account on the network.
+    pub beneficiary_account_id: Option<AccountId>,
+    /// Only for the asset spends, whose beneficiaries are locations.
+    pub beneficiary: Option<serde_json::Value>,
+}
@@ -1,12 +1,16 @@
 //! Types used in the application logic of SubVT.
+use crate::app::notification::{Notification, UserNotificationChannel, UserNotificationRule};
 use crate::crypto::AccountId;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 pub mod app_event;
 pub mod db;
 pub mod event;
 pub mod extrinsic;
 pub mod notification;
+pub mod team;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Block {
@@ -21,7 +25,7 @@ pub struct Block {
     pub runtime_version: u16,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
 pub struct Network {
     pub id: u32,
     pub hash: String,
@@ -46,14 +50,34 @@ fn default_id() -> u32 {
     0
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct User {
     #[serde(default = "default_id")]
     pub id: u32,
     pub public_key_hex: Option<String>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+/// A device registered to a user. Each device authenticates with its own secp256k1 key, so
+/// a user can access the same validators and rules from multiple devices.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct UserDevice {
+    #[serde(default = "default_id")]
+    pub id: u32,
+    #[serde(default = "default_id")]
+    pub user_id: u32,
+    pub public_key_hex: String,
+    pub name: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// One-time code created by a registered device to approve the linking of a new device.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct UserDeviceLink {
+    pub code: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct UserValidator {
     #[serde(default = "default_id")]
     pub id: u32,
@@ -61,4 +85,41 @@ pub struct UserValidator {
     pub user_id: u32,
     pub network_id: u32,
     pub validator_account_id: AccountId,
+    /// Whether the user has proven the control of the validator stash account by signing a
+    /// challenge with it. Operator-only notification types require verified validators.
+    #[serde(default)]
+    pub is_ownership_verified: bool,
+    /// Id of the team that owns the validator, `None` for the user's own validators. The user id
+    /// of a team validator is the id of the member who has added it.
+    #[serde(default)]
+    pub team_id: Option<u32>,
+}
+
+/// All the data of a user, exported on the user's request.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct UserDataExport {
+    pub user: User,
+    pub devices: Vec<UserDevice>,
+    pub notification_channels: Vec<UserNotificationChannel>,
+    pub validators: Vec<UserValidator>,
+    pub notification_rules: Vec<UserNotificationRule>,
+    pub notifications: Vec<Notification>,
+    pub exported_at: NaiveDateTime,
+}
+
+/// Challenge to be signed by the validator stash account to verify the user's ownership
+/// of the validator.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct UserValidatorOwnershipChallenge {
+    pub challenge: String,
+    pub expires_at: NaiveDateTime,
+}
+
+/// Challenge to be signed by a Substrate account to sign a new device in to the user who has
+/// verified the ownership of a validator with the account.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct UserSignInChallenge {
+    pub account_id: AccountId,
+    pub challenge: String,
+    pub expires_at: NaiveDateTime,
 }
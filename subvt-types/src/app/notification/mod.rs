@@ -4,10 +4,11 @@ use crate::substrate::Account;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use utoipa::ToSchema;
 
 pub mod rules;
 
-#[derive(Clone, Copy, Debug, Deserialize, Hash, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Hash, Eq, PartialEq, Serialize, ToSchema)]
 pub enum NotificationChannel {
     #[serde(rename = "apns")]
     APNS,
@@ -66,6 +67,7 @@ pub enum NotificationTypeCode {
     ChainValidatorNominationAmountChange,
     ChainValidatorOfflineOffence,
     ChainValidatorPayoutStakers,
+    ChainValidatorReferendumNotVoted,
     ChainValidatorSessionKeysChanged,
     ChainValidatorSetController,
     ChainValidatorUnclaimedPayout,
@@ -79,10 +81,13 @@ pub enum NotificationTypeCode {
     TelemetryValidatorDownloadBwLow,
     TelemetryValidatorFinalityLagging,
     TelemetryValidatorLagging,
+    TelemetryValidatorNotParticipatingInFinality,
     TelemetryValidatorOffline,
     TelemetryValidatorPeerCountLow,
     TelemetryValidatorTooManyTxsInQueue,
     TelemetryValidatorUploadBwLow,
+    ConvictionVotingDelegated,
+    ConvictionVotingUndelegated,
     DemocracyCancelled,
     DemocracyDelegated,
     DemocracyNotPassed,
@@ -94,14 +99,32 @@ pub enum NotificationTypeCode {
     DemocracyVoted,
     ReferendumApproved,
     ReferendumCancelled,
+    ReferendumConfirmAborted,
+    ReferendumConfirmStarted,
     ReferendumConfirmed,
     ReferendumDecisionStarted,
     ReferendumKilled,
     ReferendumRejected,
     ReferendumSubmitted,
+    ReferendumTallyChanged,
+    ReferendumThresholdCrossed,
     ReferendumTimedOut,
 }
 
+impl NotificationTypeCode {
+    /// Operator-only notification types are about the operations of the validator, and can be
+    /// set up only for validators whose ownership is verified by the user. Notifications of these
+    /// types are generated only for verified validators, including for the rules that are for
+    /// all validators.
+    pub fn is_operator_only(&self) -> bool {
+        matches!(
+            self,
+            NotificationTypeCode::ChainValidatorSetController
+                | NotificationTypeCode::ChainValidatorSessionKeysChanged
+        )
+    }
+}
+
 impl Display for NotificationTypeCode {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let code = match self {
@@ -135,6 +158,9 @@ impl Display for NotificationTypeCode {
                 "chain_validator_identity_changed"
             }
             NotificationTypeCode::ChainValidatorPayoutStakers => "chain_validator_payout_stakers",
+            NotificationTypeCode::ChainValidatorReferendumNotVoted => {
+                "chain_validator_referendum_not_voted"
+            }
             NotificationTypeCode::ChainValidatorStartedParaValidating => {
                 "chain_validator_started_para_validating"
             }
@@ -155,6 +181,9 @@ impl Display for NotificationTypeCode {
             NotificationTypeCode::TelemetryValidatorFinalityLagging => {
                 "telemetry_validator_finality_lagging"
             }
+            NotificationTypeCode::TelemetryValidatorNotParticipatingInFinality => {
+                "telemetry_validator_not_participating_in_finality"
+            }
             NotificationTypeCode::TelemetryValidatorDownloadBwLow => {
                 "telemetry_validator_download_bw_low"
             }
@@ -168,6 +197,8 @@ impl Display for NotificationTypeCode {
                 "onekv_validator_online_status_change"
             }
             // democracy
+            NotificationTypeCode::ConvictionVotingDelegated => "conviction_voting_delegated",
+            NotificationTypeCode::ConvictionVotingUndelegated => "conviction_voting_undelegated",
             NotificationTypeCode::DemocracyCancelled => "democracy_cancelled",
             NotificationTypeCode::DemocracyDelegated => "democracy_delegated",
             NotificationTypeCode::DemocracyNotPassed => "democracy_not_passed",
@@ -180,11 +211,15 @@ impl Display for NotificationTypeCode {
             // referenda
             NotificationTypeCode::ReferendumApproved => "referendum_approved",
             NotificationTypeCode::ReferendumCancelled => "referendum_cancelled",
+            NotificationTypeCode::ReferendumConfirmAborted => "referendum_confirm_aborted",
+            NotificationTypeCode::ReferendumConfirmStarted => "referendum_confirm_started",
             NotificationTypeCode::ReferendumConfirmed => "referendum_confirmed",
             NotificationTypeCode::ReferendumDecisionStarted => "referendum_decision_started",
             NotificationTypeCode::ReferendumKilled => "referendum_killed",
             NotificationTypeCode::ReferendumRejected => "referendum_rejected",
             NotificationTypeCode::ReferendumSubmitted => "referendum_submitted",
+            NotificationTypeCode::ReferendumTallyChanged => "referendum_tally_changed",
+            NotificationTypeCode::ReferendumThresholdCrossed => "referendum_threshold_crossed",
             NotificationTypeCode::ReferendumTimedOut => "referendum_timed_out",
         };
         write!(f, "{code}")
@@ -224,6 +259,9 @@ impl From<&str> for NotificationTypeCode {
                 NotificationTypeCode::ChainValidatorIdentityChanged
             }
             "chain_validator_payout_stakers" => NotificationTypeCode::ChainValidatorPayoutStakers,
+            "chain_validator_referendum_not_voted" => {
+                NotificationTypeCode::ChainValidatorReferendumNotVoted
+            }
             "chain_validator_started_para_validating" => {
                 NotificationTypeCode::ChainValidatorStartedParaValidating
             }
@@ -244,6 +282,9 @@ impl From<&str> for NotificationTypeCode {
             "telemetry_validator_finality_lagging" => {
                 NotificationTypeCode::TelemetryValidatorFinalityLagging
             }
+            "telemetry_validator_not_participating_in_finality" => {
+                NotificationTypeCode::TelemetryValidatorNotParticipatingInFinality
+            }
             "telemetry_validator_download_bw_low" => {
                 NotificationTypeCode::TelemetryValidatorDownloadBwLow
             }
@@ -256,6 +297,8 @@ impl From<&str> for NotificationTypeCode {
             "onekv_validator_online_status_change" => {
                 NotificationTypeCode::OneKVValidatorOnlineStatusChange
             }
+            "conviction_voting_delegated" => NotificationTypeCode::ConvictionVotingDelegated,
+            "conviction_voting_undelegated" => NotificationTypeCode::ConvictionVotingUndelegated,
             "democracy_cancelled" => NotificationTypeCode::DemocracyCancelled,
             "democracy_delegated" => NotificationTypeCode::DemocracyDelegated,
             "democracy_not_passed" => NotificationTypeCode::DemocracyNotPassed,
@@ -267,25 +310,29 @@ impl From<&str> for NotificationTypeCode {
             "democracy_voted" => NotificationTypeCode::DemocracyVoted,
             "referendum_approved" => NotificationTypeCode::ReferendumApproved,
             "referendum_cancelled" => NotificationTypeCode::ReferendumCancelled,
+            "referendum_confirm_aborted" => NotificationTypeCode::ReferendumConfirmAborted,
+            "referendum_confirm_started" => NotificationTypeCode::ReferendumConfirmStarted,
             "referendum_confirmed" => NotificationTypeCode::ReferendumConfirmed,
             "referendum_decision_started" => NotificationTypeCode::ReferendumDecisionStarted,
             "referendum_killed" => NotificationTypeCode::ReferendumKilled,
             "referendum_rejected" => NotificationTypeCode::ReferendumRejected,
             "referendum_submitted" => NotificationTypeCode::ReferendumSubmitted,
+            "referendum_tally_changed" => NotificationTypeCode::ReferendumTallyChanged,
+            "referendum_threshold_crossed" => NotificationTypeCode::ReferendumThresholdCrossed,
             "referendum_timed_out" => NotificationTypeCode::ReferendumTimedOut,
             _ => panic!("Unknown notification type code: {code}"),
         }
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct NotificationType {
     pub code: String,
     pub is_enabled: bool,
     pub param_types: Vec<NotificationParamType>,
 }
 
-#[derive(Clone, Debug, sqlx::Type, Serialize, Deserialize)]
+#[derive(Clone, Debug, sqlx::Type, Serialize, Deserialize, ToSchema)]
 #[sqlx(
     type_name = "app_notification_type_param_data_type",
     rename_all = "lowercase"
@@ -315,20 +362,20 @@ impl Display for NotificationParamDataType {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub struct NotificationParamType {
     pub id: u32,
     pub notification_type_code: String,
     pub order: u8,
     pub code: String,
-    #[serde(rename(serialize = "type", deserialize = "type"))]
+    #[serde(rename = "type")]
     pub type_: NotificationParamDataType,
     pub min: Option<String>,
     pub max: Option<String>,
     pub is_optional: bool,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub struct UserNotificationChannel {
     #[serde(default = "default_id")]
     pub id: u32,
@@ -338,7 +385,7 @@ pub struct UserNotificationChannel {
     pub target: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub struct UserNotificationRuleParameter {
     #[serde(default = "default_id")]
     pub user_notification_rule_id: u32,
@@ -453,7 +500,7 @@ impl UserNotificationRuleParameter {
     }
 }
 
-#[derive(Clone, Copy, Debug, sqlx::Type, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, sqlx::Type, Serialize, Deserialize, Eq, PartialEq, ToSchema)]
 #[sqlx(type_name = "app_notification_period_type", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum NotificationPeriodType {
@@ -482,10 +529,38 @@ impl Display for NotificationPeriodType {
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+/// A rule in a notification rule template. Has no validators or channels, they get selected when
+/// the template is applied.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct UserNotificationRuleTemplateItem {
+    pub notification_type_code: String,
+    pub name: Option<String>,
+    pub period_type: NotificationPeriodType,
+    pub period: u16,
+    #[serde(default)]
+    pub parameters: Vec<UserNotificationRuleParameter>,
+    pub notes: Option<String>,
+}
+
+/// A named set of notification rules created by the user, to be applied to a set of validators
+/// in one go.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct UserNotificationRuleTemplate {
+    #[serde(default = "default_id")]
+    pub id: u32,
+    #[serde(default = "default_id")]
+    pub user_id: u32,
+    pub name: String,
+    pub rules: Vec<UserNotificationRuleTemplateItem>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
 pub struct UserNotificationRule {
     pub id: u32,
     pub user_id: u32,
+    /// Id of the team that owns the rule, if any. The notifications of a team rule are delivered
+    /// to the notification channels of all the team members.
+    pub team_id: Option<u32>,
     pub notification_type: NotificationType,
     pub name: Option<String>,
     pub network: Option<Network>,
@@ -498,7 +573,7 @@ pub struct UserNotificationRule {
     pub notes: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, ToSchema)]
 pub struct Notification {
     pub id: u32,
     pub user_id: u32,
@@ -99,6 +99,17 @@ lazy_static! {
             NotificationPeriodType::Immediate,
             0,
         ),
+        // opengov delegations
+        (
+            NotificationTypeCode::ConvictionVotingDelegated,
+            NotificationPeriodType::Immediate,
+            0,
+        ),
+        (
+            NotificationTypeCode::ConvictionVotingUndelegated,
+            NotificationPeriodType::Immediate,
+            0,
+        ),
         // democracy
         (
             NotificationTypeCode::DemocracyCancelled,
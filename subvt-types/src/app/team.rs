@@ -0,0 +1,58 @@
+//! Types for the teams (organizations) of SubVT application users. A team shares its validators
+//! and notification rules among its members, and the notifications of its rules are delivered to
+//! the notification channels of every member.
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Role of a user in a team. Ordered by privilege, so that `role >= TeamRole::Editor` checks
+/// whether the user can edit the team's validators and rules.
+#[derive(
+    Clone, Copy, Debug, sqlx::Type, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, ToSchema,
+)]
+#[sqlx(type_name = "app_team_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TeamRole {
+    /// Can view the team's validators, rules and members, and receives the team notifications.
+    Viewer,
+    /// Can also add and delete the team's validators and rules.
+    Editor,
+    /// Can also add, update and remove team members.
+    Owner,
+}
+
+impl TeamRole {
+    pub fn can_edit(&self) -> bool {
+        *self >= TeamRole::Editor
+    }
+
+    pub fn can_manage_members(&self) -> bool {
+        *self == TeamRole::Owner
+    }
+}
+
+/// A team as seen by one of its members, along with the role of the member.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct Team {
+    pub id: u32,
+    pub name: String,
+    pub role: TeamRole,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TeamMember {
+    pub team_id: u32,
+    pub user_id: u32,
+    pub role: TeamRole,
+}
+
+/// Invitation of a user to a team by a team owner. The user becomes a member with the role of
+/// the invitation only after accepting it.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TeamInvitation {
+    pub id: u32,
+    pub team_id: u32,
+    pub team_name: String,
+    pub user_id: u32,
+    pub role: TeamRole,
+    pub invited_by_user_id: u32,
+}
@@ -3,6 +3,7 @@ use crate::substrate::error::DecodeError;
 use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
+use sp_core::Pair;
 use std::convert::{From, TryFrom, TryInto};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -58,6 +59,36 @@ impl AccountId {
     }
 }
 
+impl AccountId {
+    /// Verifies an sr25519 or ed25519 signature of the message by this account. The signature is
+    /// either the raw 64 bytes, or 65 bytes prefixed with the `MultiSignature` scheme index.
+    /// Wallets wrap raw payloads in `<Bytes>` tags before signing, so the wrapped message is
+    /// also accepted.
+    pub fn verify_signature(&self, message: &[u8], signature: &[u8]) -> bool {
+        let (is_ed25519, is_sr25519, signature) = match signature.len() {
+            64 => (true, true, signature),
+            65 => (signature[0] == 0, signature[0] == 1, &signature[1..]),
+            _ => return false,
+        };
+        let signature: [u8; 64] = signature.try_into().unwrap();
+        let wrapped_message = [b"<Bytes>", message, b"</Bytes>"].concat();
+        [message, wrapped_message.as_slice()].iter().any(|message| {
+            (is_sr25519
+                && sp_core::sr25519::Pair::verify(
+                    &sp_core::sr25519::Signature::from_raw(signature),
+                    message,
+                    &sp_core::sr25519::Public::from_raw(self.0),
+                ))
+                || (is_ed25519
+                    && sp_core::ed25519::Pair::verify(
+                        &sp_core::ed25519::Signature::from_raw(signature),
+                        message,
+                        &sp_core::ed25519::Public::from_raw(self.0),
+                    ))
+        })
+    }
+}
+
 impl AccountId {
     fn from_hex(hex: &str) -> Result<Self, DecodeError> {
         let error = DecodeError::Error("Invalid account id hex.".to_string());
@@ -132,3 +163,24 @@ impl<'de> Deserialize<'de> for AccountId {
             .map_err(|e| serde::de::Error::custom(format!("{e:?}")))
     }
 }
+
+/// Serialized as a string, so it's described as such in the OpenAPI specifications.
+impl<'s> utoipa::ToSchema<'s> for AccountId {
+    fn schema() -> (
+        &'s str,
+        utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+    ) {
+        (
+            "AccountId",
+            utoipa::openapi::ObjectBuilder::new()
+                .schema_type(utoipa::openapi::SchemaType::String)
+                .description(Some(
+                    "0x-prefixed hex account id. Input also accepts SS58 addresses.",
+                ))
+                .example(Some(serde_json::json!(
+                    "0xA00505EB2A4607F27837F57232F0C456602E39540582685B4F58CDE293F1A116"
+                )))
+                .into(),
+        )
+    }
+}
@@ -1,7 +1,8 @@
 //! Error types.
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct ServiceError {
     pub description: String,
 }
@@ -13,3 +14,20 @@ impl ServiceError {
         }
     }
 }
+
+/// Error of a single item of a bulk request, such as an address of a bulk validator import.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct BulkItemError {
+    /// Index of the item in the request.
+    pub index: usize,
+    pub item: String,
+    pub description: String,
+}
+
+/// Returned when one or more items of a bulk request are invalid, in which case none of the
+/// items get saved.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct BulkServiceError {
+    pub description: String,
+    pub item_errors: Vec<BulkItemError>,
+}
@@ -0,0 +1,397 @@
+//! Referendum proposal calls decoded with the runtime metadata into a nested call tree, for
+//! display purposes.
+use crate::substrate::error::DecodeError;
+use crate::substrate::metadata::{
+    decode_sequence_length, decode_value_at_depth, get_metadata_type,
+};
+use frame_metadata::RuntimeMetadataV14;
+use parity_scale_codec::Decode;
+use scale_info::form::PortableForm;
+use scale_info::Type;
+use serde::{Deserialize, Serialize};
+
+/// Arguments longer than this are truncated in the summary lines.
+const MAX_SUMMARY_ARG_LENGTH: usize = 120;
+/// Calls nested deeper than this, i.e. batches in batches, are rejected to keep the recursion
+/// bounded, since the decoded preimages are untrusted input.
+const MAX_CALL_DEPTH: usize = 16;
+
+/// A call, such as the proposal call of a referendum. Calls nested in other calls, i.e. the
+/// calls in utility batches or the call of a `Whitelist.dispatch_whitelisted_call_with_preimage`,
+/// are decoded into call trees too.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ReferendumCall {
+    pub pallet: String,
+    pub name: String,
+    pub args: Vec<ReferendumCallArg>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ReferendumCallArg {
+    pub name: String,
+    pub maybe_type_name: Option<String>,
+    pub value: ReferendumCallArgValue,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ReferendumCallArgValue {
+    /// Decoded JSON value of a non-call argument.
+    Value(serde_json::Value),
+    Call(Box<ReferendumCall>),
+    Calls(Vec<ReferendumCall>),
+}
+
+/// The outer call type of the runtime, i.e. `polkadot_runtime::RuntimeCall`. Pallet call types
+/// have longer paths, such as `pallet_utility::pallet::Call`.
+fn is_runtime_call(value_type: &Type<PortableForm>) -> bool {
+    matches!(
+        value_type.path.segments.last().map(String::as_str),
+        Some("RuntimeCall") | Some("Call")
+    ) && value_type.path.segments.len() == 2
+}
+
+/// `depth` is the depth of the call that has the argument, calls in the argument are decoded
+/// one level deeper. Other values count the depth of the call towards their depth limit.
+fn decode_arg_value(
+    metadata: &RuntimeMetadataV14,
+    arg_type: &Type<PortableForm>,
+    bytes: &mut &[u8],
+    depth: usize,
+) -> Result<ReferendumCallArgValue, DecodeError> {
+    if is_runtime_call(arg_type) {
+        return Ok(ReferendumCallArgValue::Call(Box::new(
+            ReferendumCall::decode_at_depth(metadata, bytes, depth + 1)?,
+        )));
+    }
+    if let scale_info::TypeDef::Sequence(sequence_type_def) = &arg_type.type_def {
+        let item_type = get_metadata_type(metadata, sequence_type_def.type_param.id);
+        if is_runtime_call(item_type) {
+            let length = decode_sequence_length(bytes)?;
+            let mut calls = Vec::with_capacity(length);
+            for _ in 0..length {
+                calls.push(ReferendumCall::decode_at_depth(metadata, bytes, depth + 1)?);
+            }
+            return Ok(ReferendumCallArgValue::Calls(calls));
+        }
+    }
+    let value = decode_value_at_depth(metadata, arg_type, bytes, false, depth)
+        .map_err(|error| DecodeError::Error(error.to_string()))?;
+    Ok(ReferendumCallArgValue::Value(value))
+}
+
+impl ReferendumCall {
+    /// Decodes an encoded call, i.e. a preimage, starting with the pallet and call indices.
+    pub fn decode(metadata: &RuntimeMetadataV14, bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Self::decode_at_depth(metadata, bytes, 1)
+    }
+
+    fn decode_at_depth(
+        metadata: &RuntimeMetadataV14,
+        bytes: &mut &[u8],
+        depth: usize,
+    ) -> Result<Self, DecodeError> {
+        if depth > MAX_CALL_DEPTH {
+            return Err(DecodeError::Error(format!(
+                "Calls are nested deeper than {MAX_CALL_DEPTH} levels."
+            )));
+        }
+        let pallet_index: u8 = Decode::decode(bytes)?;
+        let call_index: u8 = Decode::decode(bytes)?;
+        let pallet = metadata
+            .pallets
+            .iter()
+            .find(|metadata_pallet| metadata_pallet.index == pallet_index)
+            .ok_or_else(|| {
+                DecodeError::Error(format!("Pallet with index {pallet_index} not found."))
+            })?;
+        let calls_type_id = pallet
+            .calls
+            .as_ref()
+            .ok_or_else(|| DecodeError::Error(format!("Pallet {} has no calls.", pallet.name)))?
+            .ty
+            .id;
+        let call_variant = match &get_metadata_type(metadata, calls_type_id).type_def {
+            scale_info::TypeDef::Variant(variant) => variant
+                .variants
+                .iter()
+                .find(|variant| variant.index == call_index)
+                .ok_or_else(|| {
+                    DecodeError::Error(format!(
+                        "Call with index {} not found in pallet {}.",
+                        call_index, pallet.name,
+                    ))
+                })?,
+            type_def => {
+                return Err(DecodeError::Error(format!(
+                    "Unexpected non-variant call type: {type_def:?}",
+                )))
+            }
+        };
+        let mut args = Vec::with_capacity(call_variant.fields.len());
+        for (field_index, field) in call_variant.fields.iter().enumerate() {
+            let field_type = get_metadata_type(metadata, field.ty.id);
+            args.push(ReferendumCallArg {
+                name: field
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| field_index.to_string()),
+                maybe_type_name: field.type_name.clone(),
+                value: decode_arg_value(metadata, field_type, bytes, depth)?,
+            });
+        }
+        Ok(ReferendumCall {
+            pallet: pallet.name.clone(),
+            name: call_variant.name.clone(),
+            args,
+        })
+    }
+
+    /// Call tree as indented text lines, one line for each call and each non-call argument.
+    pub fn get_summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        self.collect_summary_lines(0, &mut lines);
+        lines
+    }
+
+    /// Summary lines cut at the given line count, with an ellipsis line at the end when cut,
+    /// for the messages with length limits.
+    pub fn get_truncated_summary_lines(&self, max_line_count: usize) -> Vec<String> {
+        let mut lines = self.get_summary_lines();
+        if lines.len() > max_line_count {
+            lines.truncate(max_line_count);
+            lines.push("...".to_string());
+        }
+        lines
+    }
+
+    fn collect_summary_lines(&self, depth: usize, lines: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        lines.push(format!("{indent}{}.{}", self.pallet, self.name));
+        for arg in &self.args {
+            match &arg.value {
+                ReferendumCallArgValue::Value(value) => {
+                    let mut value = value.to_string();
+                    if value.chars().count() > MAX_SUMMARY_ARG_LENGTH {
+                        value = format!(
+                            "{}...",
+                            value
+                                .chars()
+                                .take(MAX_SUMMARY_ARG_LENGTH)
+                                .collect::<String>()
+                        );
+                    }
+                    lines.push(format!("{indent}  {}: {value}", arg.name));
+                }
+                ReferendumCallArgValue::Call(call) => {
+                    lines.push(format!("{indent}  {}:", arg.name));
+                    call.collect_summary_lines(depth + 2, lines);
+                }
+                ReferendumCallArgValue::Calls(calls) => {
+                    lines.push(format!("{indent}  {} ({}):", arg.name, calls.len()));
+                    for call in calls {
+                        call.collect_summary_lines(depth + 2, lines);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReferendumCall, ReferendumCallArg, ReferendumCallArgValue, MAX_CALL_DEPTH};
+    use frame_metadata::v14::{ExtrinsicMetadata, PalletCallMetadata, PalletMetadata};
+    use frame_metadata::RuntimeMetadataV14;
+    use parity_scale_codec::{Compact, Encode};
+    use scale_info::build::{Fields, Variants};
+    use scale_info::{meta_type, Path, Type, TypeInfo};
+
+    #[allow(non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum SystemCall {
+        #[codec(index = 0)]
+        remark { remark: Vec<u8> },
+    }
+
+    #[allow(non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum UtilityCall {
+        #[codec(index = 0)]
+        batch { calls: Vec<RuntimeCall> },
+        #[codec(index = 1)]
+        dispatch_as { call: Box<RuntimeCall> },
+    }
+
+    #[derive(Encode)]
+    enum RuntimeCall {
+        #[codec(index = 0)]
+        System(SystemCall),
+        #[codec(index = 1)]
+        Utility(UtilityCall),
+    }
+
+    /// The runtime call type is recognized by its two-segment path, which the derived type info
+    /// of a test type doesn't have.
+    impl TypeInfo for RuntimeCall {
+        type Identity = Self;
+
+        fn type_info() -> Type {
+            Type::builder()
+                .path(Path::new("RuntimeCall", "test_runtime"))
+                .variant(
+                    Variants::new()
+                        .variant("System", |v| {
+                            v.index(0)
+                                .fields(Fields::unnamed().field(|f| f.ty::<SystemCall>()))
+                        })
+                        .variant("Utility", |v| {
+                            v.index(1)
+                                .fields(Fields::unnamed().field(|f| f.ty::<UtilityCall>()))
+                        }),
+                )
+        }
+    }
+
+    fn get_metadata() -> RuntimeMetadataV14 {
+        let get_pallet = |name, index, calls| PalletMetadata {
+            name,
+            storage: None,
+            calls: Some(PalletCallMetadata { ty: calls }),
+            event: None,
+            constants: vec![],
+            error: None,
+            index,
+        };
+        RuntimeMetadataV14::new(
+            vec![
+                get_pallet("System", 0, meta_type::<SystemCall>()),
+                get_pallet("Utility", 1, meta_type::<UtilityCall>()),
+            ],
+            ExtrinsicMetadata {
+                ty: meta_type::<()>(),
+                version: 4,
+                signed_extensions: vec![],
+            },
+            meta_type::<RuntimeCall>(),
+        )
+    }
+
+    fn remark() -> RuntimeCall {
+        RuntimeCall::System(SystemCall::remark { remark: vec![1, 2] })
+    }
+
+    /// Wraps a remark in `depth - 1` alternating batches and dispatches.
+    fn get_nested_call(depth: usize) -> RuntimeCall {
+        (1..depth).fold(remark(), |call, level| {
+            RuntimeCall::Utility(if level % 2 == 0 {
+                UtilityCall::batch { calls: vec![call] }
+            } else {
+                UtilityCall::dispatch_as {
+                    call: Box::new(call),
+                }
+            })
+        })
+    }
+
+    #[test]
+    fn test_decode_nested_calls() {
+        let metadata = get_metadata();
+        let encoded = RuntimeCall::Utility(UtilityCall::batch {
+            calls: vec![remark(), get_nested_call(2)],
+        })
+        .encode();
+        let mut bytes = encoded.as_slice();
+        let call = ReferendumCall::decode(&metadata, &mut bytes).unwrap();
+        assert!(bytes.is_empty());
+        assert_eq!(
+            call.get_summary_lines(),
+            vec![
+                "Utility.batch",
+                "  calls (2):",
+                "    System.remark",
+                "      remark: \"0x0102\"",
+                "    Utility.dispatch_as",
+                "      call:",
+                "        System.remark",
+                "          remark: \"0x0102\"",
+            ]
+        );
+        let encoded = get_nested_call(MAX_CALL_DEPTH).encode();
+        assert!(ReferendumCall::decode(&metadata, &mut encoded.as_slice()).is_ok());
+    }
+
+    #[test]
+    fn test_decode_excessive_nesting() {
+        let metadata = get_metadata();
+        let encoded = get_nested_call(MAX_CALL_DEPTH + 1).encode();
+        let error = ReferendumCall::decode(&metadata, &mut encoded.as_slice()).unwrap_err();
+        assert!(error.to_string().contains("nested deeper"), "{error}");
+    }
+
+    #[test]
+    fn test_decode_oversized_call_sequence_length() {
+        let metadata = get_metadata();
+        // Utility.batch claiming u32::MAX calls, followed by a single remark.
+        let mut encoded = vec![1u8, 0];
+        encoded.extend(Compact(u32::MAX).encode());
+        encoded.extend(remark().encode());
+        let error = ReferendumCall::decode(&metadata, &mut encoded.as_slice()).unwrap_err();
+        assert!(error.to_string().contains("exceeds"), "{error}");
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let remark = ReferendumCall {
+            pallet: "System".to_string(),
+            name: "remark".to_string(),
+            args: vec![ReferendumCallArg {
+                name: "remark".to_string(),
+                maybe_type_name: Some("Vec<u8>".to_string()),
+                value: ReferendumCallArgValue::Value(serde_json::json!("0x0102")),
+            }],
+        };
+        let batch = ReferendumCall {
+            pallet: "Utility".to_string(),
+            name: "batch_all".to_string(),
+            args: vec![ReferendumCallArg {
+                name: "calls".to_string(),
+                maybe_type_name: Some("Vec<<T as Config>::RuntimeCall>".to_string()),
+                value: ReferendumCallArgValue::Calls(vec![remark.clone(), remark]),
+            }],
+        };
+        assert_eq!(
+            batch.get_summary_lines(),
+            vec![
+                "Utility.batch_all",
+                "  calls (2):",
+                "    System.remark",
+                "      remark: \"0x0102\"",
+                "    System.remark",
+                "      remark: \"0x0102\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_arg_truncation() {
+        let call = ReferendumCall {
+            pallet: "System".to_string(),
+            name: "remark".to_string(),
+            args: vec![ReferendumCallArg {
+                name: "remark".to_string(),
+                maybe_type_name: None,
+                value: ReferendumCallArgValue::Value(serde_json::json!("a".repeat(200))),
+            }],
+        };
+        let lines = call.get_summary_lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with("..."));
+        assert_eq!(lines[1].chars().count(), "  remark: ".len() + 120 + 3);
+        assert_eq!(
+            call.get_truncated_summary_lines(1),
+            vec!["System.remark", "..."]
+        );
+        assert_eq!(call.get_truncated_summary_lines(2).len(), 2);
+    }
+}
@@ -1,2 +1,5 @@
+pub mod call;
 pub mod polkassembly;
+pub mod referendum;
 pub mod track;
+pub mod treasury;
@@ -2,8 +2,9 @@ use crate::crypto::AccountId;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub enum ReferendumStatus {
     Submitted,
     DecisionDepositPlaced,
@@ -16,6 +17,8 @@ pub enum ReferendumStatus {
     ConfirmStarted,
     Confirmed,
     Killed,
+    /// On-chain status of a confirmed referendum, whose execution outcome isn't known.
+    Approved,
 }
 
 impl Display for ReferendumStatus {
@@ -32,6 +35,7 @@ impl Display for ReferendumStatus {
             ReferendumStatus::ConfirmStarted => "ConfirmStarted",
             ReferendumStatus::Confirmed => "Confirmed",
             ReferendumStatus::Killed => "Killed",
+            ReferendumStatus::Approved => "Approved",
         };
         write!(f, "{display}")
     }
@@ -50,7 +54,10 @@ impl FromStr for ReferendumStatus {
             "TimedOut" => Ok(Self::TimedOut),
             "Cancelled" => Ok(Self::Cancelled),
             "Rejected" => Ok(Self::Rejected),
+            "ConfirmStarted" => Ok(Self::ConfirmStarted),
             "Confirmed" => Ok(Self::Confirmed),
+            "Killed" => Ok(Self::Killed),
+            "Approved" => Ok(Self::Approved),
             _ => panic!("Unknown referendum status: {s}"),
         }
     }
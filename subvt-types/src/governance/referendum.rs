@@ -0,0 +1,846 @@
+//! On-chain OpenGov types, decoded from the `Referenda` and `FellowshipReferenda` pallet storage
+//! and metadata constants, and the evaluation of referendum tallies against the approval and
+//! support curves of the tracks.
+use crate::crypto::AccountId;
+use crate::governance::polkassembly::ReferendumStatus;
+use crate::substrate::error::DecodeError;
+use crate::substrate::metadata::{decode_field, get_metadata_type};
+use crate::substrate::{Balance, BlockNumber};
+use frame_metadata::{v14::StorageEntryType, RuntimeMetadataV14};
+use frame_support::traits::Bounded;
+use pallet_conviction_voting::Tally;
+pub use pallet_referenda::Curve;
+use parity_scale_codec::Decode;
+use scale_info::form::PortableForm;
+use scale_info::{Type, TypeDef};
+use sp_core::H256;
+use sp_runtime::traits::BlakeTwo256;
+use sp_runtime::Perbill;
+
+const REFERENDUM_INFO_STORAGE_NAME: &str = "ReferendumInfoFor";
+
+/// Track definition, an element of the `Referenda.Tracks` and `FellowshipReferenda.Tracks`
+/// metadata constants.
+#[derive(Clone, Debug, Decode)]
+pub struct TrackInfo {
+    pub name: String,
+    pub max_deciding: u32,
+    pub decision_deposit: Balance,
+    pub prepare_period: BlockNumber,
+    pub decision_period: BlockNumber,
+    pub confirm_period: BlockNumber,
+    pub min_enactment_period: BlockNumber,
+    pub min_approval: Curve,
+    pub min_support: Curve,
+}
+
+/// The runtime origin the proposal gets dispatched with, i.e. the variant of the runtime
+/// `OriginCaller` enum and the variant of the pallet origin enum in it. The variants differ
+/// between runtimes and may have fields, e.g. the account of a signed origin or the id of a
+/// parachain origin, so the origin is decoded with its type in the runtime metadata.
+#[derive(Clone, Debug)]
+pub struct ProposalOrigin {
+    pub caller_index: u8,
+    pub origin_index: u8,
+}
+
+impl ProposalOrigin {
+    fn decode_with_metadata(
+        metadata: &RuntimeMetadataV14,
+        origin_type: &Type<PortableForm>,
+        bytes: &mut &[u8],
+    ) -> anyhow::Result<Self> {
+        let (caller_index, origin_index) = match bytes {
+            [caller_index, origin_index, ..] => (*caller_index, *origin_index),
+            _ => {
+                return Err(DecodeError::Error(
+                    "Not enough data to decode the proposal origin.".to_string(),
+                )
+                .into())
+            }
+        };
+        // moves past the fields of the origin
+        decode_field(metadata, origin_type, bytes, false)?;
+        Ok(Self {
+            caller_index,
+            origin_index,
+        })
+    }
+}
+
+/// Type of the origin of an ongoing referendum in the `ReferendumInfoFor` storage of the
+/// referenda pallet instance, i.e. the runtime `OriginCaller` type.
+fn get_origin_type<'a>(
+    metadata: &'a RuntimeMetadataV14,
+    pallet_name: &str,
+) -> anyhow::Result<&'a Type<PortableForm>> {
+    let get_error = |description: &str| {
+        DecodeError::Error(format!(
+            "Cannot get the referendum origin type of {pallet_name}: {description}"
+        ))
+    };
+    let storage_entry = metadata
+        .pallets
+        .iter()
+        .find(|pallet| pallet.name == pallet_name)
+        .and_then(|pallet| pallet.storage.as_ref())
+        .and_then(|storage| {
+            storage
+                .entries
+                .iter()
+                .find(|entry| entry.name == REFERENDUM_INFO_STORAGE_NAME)
+        })
+        .ok_or_else(|| get_error("storage not found."))?;
+    let info_type = match &storage_entry.ty {
+        StorageEntryType::Map { value, .. } => get_metadata_type(metadata, value.id),
+        StorageEntryType::Plain(_) => return Err(get_error("storage is not a map.").into()),
+    };
+    let status_type_id = match &info_type.type_def {
+        TypeDef::Variant(variant_type_def) => variant_type_def
+            .variants
+            .iter()
+            .find(|variant| variant.name == "Ongoing")
+            .and_then(|variant| variant.fields.first())
+            .map(|field| field.ty.id),
+        _ => None,
+    }
+    .ok_or_else(|| get_error("ongoing variant not found."))?;
+    let origin_type_id = match &get_metadata_type(metadata, status_type_id).type_def {
+        TypeDef::Composite(composite_type_def) => composite_type_def
+            .fields
+            .iter()
+            .find(|field| field.name.as_deref() == Some("origin"))
+            .map(|field| field.ty.id),
+        _ => None,
+    }
+    .ok_or_else(|| get_error("origin field not found."))?;
+    Ok(get_metadata_type(metadata, origin_type_id))
+}
+
+#[derive(Clone, Debug, Decode)]
+pub struct ReferendumDeposit {
+    pub who: AccountId,
+    pub amount: Balance,
+}
+
+#[derive(Clone, Debug, Decode)]
+pub enum EnactmentTime {
+    At(BlockNumber),
+    After(BlockNumber),
+}
+
+#[derive(Clone, Debug, Decode)]
+pub struct DecidingStatus {
+    pub since: BlockNumber,
+    /// The block at which the confirmation period ends, if the referendum is confirming.
+    pub confirming: Option<BlockNumber>,
+}
+
+/// Tally of the `FellowshipReferenda` pallet, `pallet_ranked_collective::Tally`. `ayes` and
+/// `nays` are weighted by the ranks of the voters, and `bare_ayes` is the number of aye voters.
+#[derive(Clone, Debug, Decode)]
+pub struct FellowshipTally {
+    pub bare_ayes: u32,
+    pub ayes: u32,
+    pub nays: u32,
+}
+
+/// Tallies of the referenda pallet instances, to be evaluated against the approval and support
+/// curves of the tracks.
+pub trait ReferendumVotes {
+    /// Ayes, nays and the support.
+    fn get_votes(&self) -> (Balance, Balance, Balance);
+}
+
+impl ReferendumVotes for Tally<Balance, Balance> {
+    fn get_votes(&self) -> (Balance, Balance, Balance) {
+        (self.ayes, self.nays, self.support)
+    }
+}
+
+impl ReferendumVotes for FellowshipTally {
+    fn get_votes(&self) -> (Balance, Balance, Balance) {
+        (
+            self.ayes as Balance,
+            self.nays as Balance,
+            self.bare_ayes as Balance,
+        )
+    }
+}
+
+/// Ongoing referendum with the tally type of the referenda pallet instance, the conviction
+/// voting tally for the `Referenda` pallet and `FellowshipTally` for `FellowshipReferenda`.
+#[derive(Clone, Debug)]
+pub struct OngoingReferendum<T = Tally<Balance, Balance>> {
+    pub track_id: u16,
+    pub origin: ProposalOrigin,
+    // type parameter is dummy
+    pub proposal: Bounded<u8, BlakeTwo256>,
+    pub enactment: EnactmentTime,
+    pub submitted: BlockNumber,
+    pub submission_deposit: ReferendumDeposit,
+    pub decision_deposit: Option<ReferendumDeposit>,
+    pub deciding: Option<DecidingStatus>,
+    pub tally: T,
+    pub in_queue: bool,
+    pub alarm: Option<(BlockNumber, (BlockNumber, u32))>,
+}
+
+/// Source of the encoded call of a referendum proposal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalCall {
+    Inline(Vec<u8>),
+    /// The call is in the `Preimage.PreimageFor` storage, with the hash and length key.
+    Preimage {
+        hash: H256,
+        len: u32,
+    },
+    /// Legacy proposals reference the preimage without its length.
+    Unavailable,
+}
+
+impl From<&Bounded<u8, BlakeTwo256>> for ProposalCall {
+    fn from(proposal: &Bounded<u8, BlakeTwo256>) -> Self {
+        match proposal {
+            Bounded::Inline(bytes) => ProposalCall::Inline(bytes.to_vec()),
+            Bounded::Lookup { hash, len } => ProposalCall::Preimage {
+                hash: *hash,
+                len: *len,
+            },
+            Bounded::Legacy { .. } => ProposalCall::Unavailable,
+        }
+    }
+}
+
+impl<T> OngoingReferendum<T> {
+    pub fn get_proposal_call(&self) -> ProposalCall {
+        ProposalCall::from(&self.proposal)
+    }
+}
+
+/// `Referenda.ReferendumInfoFor` and `FellowshipReferenda.ReferendumInfoFor` storage value.
+/// Finished referenda keep only the end block and the deposits that haven't been refunded yet.
+#[derive(Clone, Debug)]
+pub enum ReferendumInfo<T = Tally<Balance, Balance>> {
+    Ongoing(OngoingReferendum<T>),
+    Approved(
+        BlockNumber,
+        Option<ReferendumDeposit>,
+        Option<ReferendumDeposit>,
+    ),
+    Rejected(
+        BlockNumber,
+        Option<ReferendumDeposit>,
+        Option<ReferendumDeposit>,
+    ),
+    Cancelled(
+        BlockNumber,
+        Option<ReferendumDeposit>,
+        Option<ReferendumDeposit>,
+    ),
+    TimedOut(
+        BlockNumber,
+        Option<ReferendumDeposit>,
+        Option<ReferendumDeposit>,
+    ),
+    Killed(BlockNumber),
+}
+
+impl<T: Decode> ReferendumInfo<T> {
+    /// Decodes a `ReferendumInfoFor` storage value of the referenda pallet instance with the
+    /// given name, with the origin type in the runtime metadata.
+    pub fn decode_with_metadata(
+        metadata: &RuntimeMetadataV14,
+        pallet_name: &str,
+        bytes: &mut &[u8],
+    ) -> anyhow::Result<Self> {
+        let info = match u8::decode(bytes)? {
+            0 => {
+                let origin_type = get_origin_type(metadata, pallet_name)?;
+                // fields are decoded in the order they are written
+                Self::Ongoing(OngoingReferendum {
+                    track_id: Decode::decode(bytes)?,
+                    origin: ProposalOrigin::decode_with_metadata(metadata, origin_type, bytes)?,
+                    proposal: Decode::decode(bytes)?,
+                    enactment: Decode::decode(bytes)?,
+                    submitted: Decode::decode(bytes)?,
+                    submission_deposit: Decode::decode(bytes)?,
+                    decision_deposit: Decode::decode(bytes)?,
+                    deciding: Decode::decode(bytes)?,
+                    tally: Decode::decode(bytes)?,
+                    in_queue: Decode::decode(bytes)?,
+                    alarm: Decode::decode(bytes)?,
+                })
+            }
+            1 => Self::Approved(
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+            ),
+            2 => Self::Rejected(
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+            ),
+            3 => Self::Cancelled(
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+            ),
+            4 => Self::TimedOut(
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+                Decode::decode(bytes)?,
+            ),
+            5 => Self::Killed(Decode::decode(bytes)?),
+            index => {
+                return Err(DecodeError::Error(format!(
+                    "Unknown referendum info variant index {index}."
+                ))
+                .into())
+            }
+        };
+        Ok(info)
+    }
+}
+
+/// Tally of an ongoing referendum, along with the approval and support ratios.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReferendumTally {
+    pub ayes: Balance,
+    pub nays: Balance,
+    pub support: Balance,
+    pub approval: Perbill,
+    /// Support as a ratio of the electorate.
+    pub support_ratio: Perbill,
+}
+
+impl ReferendumTally {
+    /// `electorate` is the active issuance for the `Referenda` pallet, and the number of the
+    /// Fellowship members at or above the minimum rank of the track for `FellowshipReferenda`.
+    pub fn new<T: ReferendumVotes>(tally: &T, electorate: Balance) -> Self {
+        let (ayes, nays, support) = tally.get_votes();
+        let total = ayes.saturating_add(nays);
+        Self {
+            ayes,
+            nays,
+            support,
+            approval: if total == 0 {
+                Perbill::zero()
+            } else {
+                Perbill::from_rational(ayes, total)
+            },
+            support_ratio: if electorate == 0 {
+                Perbill::zero()
+            } else {
+                Perbill::from_rational(support.min(electorate), electorate)
+            },
+        }
+    }
+}
+
+/// Deadlines and the current thresholds of a referendum in its decision period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferendumDecision {
+    pub since_block_number: BlockNumber,
+    /// The referendum times out and gets rejected at this block unless it's confirming.
+    pub deadline_block_number: BlockNumber,
+    /// The block at which the confirmation ends, if the referendum is confirming.
+    pub confirm_end_block_number: Option<BlockNumber>,
+    /// Minimum approval required at the current block, as per the approval curve of the track.
+    pub min_approval: Perbill,
+    /// Minimum support required at the current block, as per the support curve of the track.
+    pub min_support: Perbill,
+    pub is_passing: bool,
+}
+
+impl ReferendumDecision {
+    pub fn new(
+        deciding: &DecidingStatus,
+        track: &TrackInfo,
+        tally: &ReferendumTally,
+        current_block_number: BlockNumber,
+    ) -> Self {
+        let elapsed = current_block_number
+            .saturating_sub(deciding.since)
+            .min(track.decision_period);
+        let x = Perbill::from_rational(elapsed, track.decision_period.max(1));
+        let min_approval = track.min_approval.threshold(x);
+        let min_support = track.min_support.threshold(x);
+        Self {
+            since_block_number: deciding.since,
+            deadline_block_number: deciding.since.saturating_add(track.decision_period),
+            confirm_end_block_number: deciding.confirming,
+            min_approval,
+            min_support,
+            is_passing: tally.approval >= min_approval && tally.support_ratio >= min_support,
+        }
+    }
+}
+
+/// Decision deadline of an open referendum in its decision period.
+#[derive(Clone, Debug)]
+pub struct ReferendumDecisionDeadline {
+    pub index: u32,
+    pub track_id: u16,
+    pub maybe_title: Option<String>,
+    pub deadline_block_number: BlockNumber,
+}
+
+/// Current on-chain state of a referendum, evaluated at a block.
+#[derive(Clone, Debug)]
+pub struct Referendum {
+    pub index: u32,
+    /// Not kept on chain for finished referenda.
+    pub maybe_track_id: Option<u16>,
+    pub status: ReferendumStatus,
+    /// Not known for finished referenda with refunded submission deposits.
+    pub maybe_proposer_account_id: Option<AccountId>,
+    pub maybe_submitted_block_number: Option<BlockNumber>,
+    /// Block at which the preparation period ends and the referendum can start deciding.
+    pub maybe_prepare_end_block_number: Option<BlockNumber>,
+    pub maybe_end_block_number: Option<BlockNumber>,
+    pub maybe_tally: Option<ReferendumTally>,
+    pub maybe_decision: Option<ReferendumDecision>,
+    /// Position in the track queue, for referenda waiting for a free decision slot.
+    pub maybe_queue_position: Option<u32>,
+}
+
+impl Referendum {
+    pub fn is_ongoing(&self) -> bool {
+        self.maybe_end_block_number.is_none()
+    }
+
+    /// Evaluates the referendum at the given block. `track` is the track of the referendum for
+    /// ongoing referenda, and `track_queue` is the ordered list of the referendum indices
+    /// waiting in the queue of the track. See `ReferendumTally::new` for the `electorate`.
+    pub fn new<T: ReferendumVotes>(
+        index: u32,
+        info: &ReferendumInfo<T>,
+        track: Option<&TrackInfo>,
+        track_queue: &[u32],
+        electorate: Balance,
+        current_block_number: BlockNumber,
+    ) -> Self {
+        let finished =
+            |status: ReferendumStatus,
+             end: BlockNumber,
+             submission_deposit: &Option<ReferendumDeposit>| Self {
+                index,
+                maybe_track_id: None,
+                status,
+                maybe_proposer_account_id: submission_deposit.as_ref().map(|deposit| deposit.who),
+                maybe_submitted_block_number: None,
+                maybe_prepare_end_block_number: None,
+                maybe_end_block_number: Some(end),
+                maybe_tally: None,
+                maybe_decision: None,
+                maybe_queue_position: None,
+            };
+        match info {
+            ReferendumInfo::Ongoing(referendum) => {
+                let tally = ReferendumTally::new(&referendum.tally, electorate);
+                let maybe_decision = match (&referendum.deciding, track) {
+                    (Some(deciding), Some(track)) => Some(ReferendumDecision::new(
+                        deciding,
+                        track,
+                        &tally,
+                        current_block_number,
+                    )),
+                    _ => None,
+                };
+                let status = match &referendum.deciding {
+                    Some(DecidingStatus {
+                        confirming: Some(_),
+                        ..
+                    }) => ReferendumStatus::ConfirmStarted,
+                    Some(_) => ReferendumStatus::Deciding,
+                    None if referendum.decision_deposit.is_some() => {
+                        ReferendumStatus::DecisionDepositPlaced
+                    }
+                    None => ReferendumStatus::Submitted,
+                };
+                Self {
+                    index,
+                    maybe_track_id: Some(referendum.track_id),
+                    status,
+                    maybe_proposer_account_id: Some(referendum.submission_deposit.who),
+                    maybe_submitted_block_number: Some(referendum.submitted),
+                    maybe_prepare_end_block_number: track
+                        .map(|track| referendum.submitted.saturating_add(track.prepare_period)),
+                    maybe_end_block_number: None,
+                    maybe_tally: Some(tally),
+                    maybe_decision,
+                    maybe_queue_position: track_queue
+                        .iter()
+                        .position(|queued_index| *queued_index == index)
+                        .map(|position| position as u32),
+                }
+            }
+            ReferendumInfo::Approved(end, submission_deposit, _) => {
+                finished(ReferendumStatus::Approved, *end, submission_deposit)
+            }
+            ReferendumInfo::Rejected(end, submission_deposit, _) => {
+                finished(ReferendumStatus::Rejected, *end, submission_deposit)
+            }
+            ReferendumInfo::Cancelled(end, submission_deposit, _) => {
+                finished(ReferendumStatus::Cancelled, *end, submission_deposit)
+            }
+            ReferendumInfo::TimedOut(end, submission_deposit, _) => {
+                finished(ReferendumStatus::TimedOut, *end, submission_deposit)
+            }
+            ReferendumInfo::Killed(end) => finished(ReferendumStatus::Killed, *end, &None),
+        }
+    }
+}
+
+/// Tally and decision state of an ongoing referendum at a block, tracked block by block to
+/// detect threshold crossings, confirmation changes and tally changes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReferendumTallyState {
+    pub index: u32,
+    pub track_id: u16,
+    pub block_number: u64,
+    pub ayes: Balance,
+    pub nays: Balance,
+    pub support: Balance,
+    pub approval_per_billion: u32,
+    pub support_per_billion: u32,
+    /// Current approval threshold, set only while the referendum is deciding.
+    pub maybe_min_approval_per_billion: Option<u32>,
+    /// Current support threshold, set only while the referendum is deciding.
+    pub maybe_min_support_per_billion: Option<u32>,
+    pub is_confirming: bool,
+    pub maybe_confirm_end_block_number: Option<BlockNumber>,
+}
+
+impl ReferendumTallyState {
+    /// `None` for finished referenda.
+    pub fn new(referendum: &Referendum, block_number: u64) -> Option<Self> {
+        let (track_id, tally) = match (referendum.maybe_track_id, &referendum.maybe_tally) {
+            (Some(track_id), Some(tally)) => (track_id, tally),
+            _ => return None,
+        };
+        let decision = referendum.maybe_decision.as_ref();
+        let maybe_confirm_end_block_number =
+            decision.and_then(|decision| decision.confirm_end_block_number);
+        Some(Self {
+            index: referendum.index,
+            track_id,
+            block_number,
+            ayes: tally.ayes,
+            nays: tally.nays,
+            support: tally.support,
+            approval_per_billion: tally.approval.deconstruct(),
+            support_per_billion: tally.support_ratio.deconstruct(),
+            maybe_min_approval_per_billion: decision
+                .map(|decision| decision.min_approval.deconstruct()),
+            maybe_min_support_per_billion: decision
+                .map(|decision| decision.min_support.deconstruct()),
+            is_confirming: maybe_confirm_end_block_number.is_some(),
+            maybe_confirm_end_block_number,
+        })
+    }
+
+    pub fn is_approval_passing(&self) -> Option<bool> {
+        self.maybe_min_approval_per_billion
+            .map(|min_approval| self.approval_per_billion >= min_approval)
+    }
+
+    pub fn is_support_passing(&self) -> Option<bool> {
+        self.maybe_min_support_per_billion
+            .map(|min_support| self.support_per_billion >= min_support)
+    }
+
+    pub fn is_passing(&self) -> bool {
+        self.is_approval_passing().unwrap_or(false) && self.is_support_passing().unwrap_or(false)
+    }
+
+    /// Largest relative change of the ayes, nays and support from the given previous values,
+    /// in percent. Any change from zero counts as a 100% change.
+    pub fn get_max_change_percent(&self, prev: (Balance, Balance, Balance)) -> f64 {
+        let change_percent = |prev: Balance, current: Balance| -> f64 {
+            if prev == current {
+                0.0
+            } else if prev == 0 {
+                100.0
+            } else {
+                (current.abs_diff(prev) as f64 / prev as f64) * 100.0
+            }
+        };
+        change_percent(prev.0, self.ayes)
+            .max(change_percent(prev.1, self.nays))
+            .max(change_percent(prev.2, self.support))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Curve, DecidingStatus, FellowshipTally, ProposalCall, ReferendumDecision, ReferendumInfo,
+        ReferendumTally, ReferendumTallyState, TrackInfo,
+    };
+    use frame_metadata::v14::{
+        ExtrinsicMetadata, PalletMetadata, PalletStorageMetadata, StorageEntryMetadata,
+        StorageEntryModifier, StorageEntryType, StorageHasher,
+    };
+    use frame_metadata::RuntimeMetadataV14;
+    use pallet_conviction_voting::Tally;
+    use parity_scale_codec::{Decode, Encode};
+    use scale_info::{meta_type, TypeInfo};
+    use sp_core::H256;
+    use sp_runtime::Perbill;
+
+    const DECISION_PERIOD: u32 = 28 * 14_400;
+
+    fn get_track() -> TrackInfo {
+        TrackInfo {
+            name: "test_track".to_string(),
+            max_deciding: 10,
+            decision_deposit: 1_000,
+            prepare_period: 2 * 14_400,
+            decision_period: DECISION_PERIOD,
+            confirm_period: 14_400,
+            min_enactment_period: 14_400,
+            // 100% to 50% in the whole decision period
+            min_approval: Curve::LinearDecreasing {
+                length: Perbill::from_percent(100),
+                floor: Perbill::from_percent(50),
+                ceil: Perbill::from_percent(100),
+            },
+            // 10% to 0% in the first half of the decision period
+            min_support: Curve::LinearDecreasing {
+                length: Perbill::from_percent(50),
+                floor: Perbill::from_percent(0),
+                ceil: Perbill::from_percent(10),
+            },
+        }
+    }
+
+    fn get_tally(ayes: u128, nays: u128, support: u128) -> Tally<u128, u128> {
+        Tally::decode(&mut &(ayes, nays, support).encode()[..]).unwrap()
+    }
+
+    #[derive(Encode, TypeInfo)]
+    enum RawOrigin {
+        #[codec(index = 0)]
+        Root,
+        #[codec(index = 1)]
+        Signed([u8; 32]),
+    }
+
+    #[derive(Encode, TypeInfo)]
+    enum ParachainOrigin {
+        #[codec(index = 0)]
+        Parachain(u32),
+    }
+
+    #[derive(Encode, TypeInfo)]
+    enum OriginCaller {
+        #[codec(index = 0)]
+        System(RawOrigin),
+        #[codec(index = 50)]
+        ParachainsOrigin(ParachainOrigin),
+    }
+
+    /// Only the origin type is read from the metadata, the other fields are decoded statically.
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    struct TestReferendumStatus {
+        track: u16,
+        origin: OriginCaller,
+    }
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    enum TestReferendumInfo {
+        Ongoing(TestReferendumStatus),
+    }
+
+    fn get_metadata() -> RuntimeMetadataV14 {
+        RuntimeMetadataV14::new(
+            vec![PalletMetadata {
+                name: "Referenda",
+                storage: Some(PalletStorageMetadata {
+                    prefix: "Referenda",
+                    entries: vec![StorageEntryMetadata {
+                        name: "ReferendumInfoFor",
+                        modifier: StorageEntryModifier::Optional,
+                        ty: StorageEntryType::Map {
+                            hashers: vec![StorageHasher::Blake2_128Concat],
+                            key: meta_type::<u32>(),
+                            value: meta_type::<TestReferendumInfo>(),
+                        },
+                        default: vec![],
+                        docs: vec![],
+                    }],
+                }),
+                calls: None,
+                event: None,
+                constants: vec![],
+                error: None,
+                index: 0,
+            }],
+            ExtrinsicMetadata {
+                ty: meta_type::<()>(),
+                version: 4,
+                signed_extensions: vec![],
+            },
+            meta_type::<()>(),
+        )
+    }
+
+    fn get_encoded_ongoing_referendum(origin: OriginCaller) -> Vec<u8> {
+        let mut encoded = vec![0u8];
+        encoded.extend(3u16.encode());
+        encoded.extend(origin.encode());
+        // lookup proposal
+        encoded.extend((2u8, H256::repeat_byte(1), 10u32).encode());
+        // enactment after 100 blocks, submitted at block 1_000
+        encoded.extend((1u8, 100u32, 1_000u32).encode());
+        // submission deposit, no decision deposit, deciding since block 1_100
+        encoded.extend(([2u8; 32], 10u128, None::<([u8; 32], u128)>).encode());
+        encoded.extend(Some((1_100u32, None::<u32>)).encode());
+        encoded.extend((75u128, 25u128, 10u128, false).encode());
+        encoded.extend(None::<(u32, (u32, u32))>.encode());
+        encoded
+    }
+
+    #[test]
+    fn test_decode_referendum_origins() {
+        let metadata = get_metadata();
+        for (origin, caller_index, origin_index) in [
+            (OriginCaller::System(RawOrigin::Root), 0, 0),
+            (OriginCaller::System(RawOrigin::Signed([1; 32])), 0, 1),
+            (
+                OriginCaller::ParachainsOrigin(ParachainOrigin::Parachain(2_000)),
+                50,
+                0,
+            ),
+        ] {
+            let encoded = get_encoded_ongoing_referendum(origin);
+            let mut bytes = &encoded[..];
+            let info: ReferendumInfo =
+                ReferendumInfo::decode_with_metadata(&metadata, "Referenda", &mut bytes).unwrap();
+            assert!(bytes.is_empty());
+            let referendum = match info {
+                ReferendumInfo::Ongoing(referendum) => referendum,
+                _ => panic!("Expected an ongoing referendum."),
+            };
+            assert_eq!(referendum.track_id, 3);
+            assert_eq!(referendum.origin.caller_index, caller_index);
+            assert_eq!(referendum.origin.origin_index, origin_index);
+            assert_eq!(
+                referendum.get_proposal_call(),
+                ProposalCall::Preimage {
+                    hash: H256::repeat_byte(1),
+                    len: 10
+                }
+            );
+            assert_eq!(referendum.submitted, 1_000);
+            assert_eq!(
+                referendum.deciding.map(|deciding| deciding.since),
+                Some(1_100)
+            );
+            assert_eq!(referendum.tally.ayes, 75);
+            assert!(referendum.alarm.is_none());
+        }
+    }
+
+    #[test]
+    fn test_decode_finished_referendum() {
+        let metadata = get_metadata();
+        let encoded = (5u8, 2_000u32).encode();
+        let info: ReferendumInfo =
+            ReferendumInfo::decode_with_metadata(&metadata, "Referenda", &mut &encoded[..])
+                .unwrap();
+        assert!(matches!(info, ReferendumInfo::Killed(2_000)));
+        // unknown pallet
+        let encoded = get_encoded_ongoing_referendum(OriginCaller::System(RawOrigin::Root));
+        assert!(ReferendumInfo::<Tally<u128, u128>>::decode_with_metadata(
+            &metadata,
+            "FellowshipReferenda",
+            &mut &encoded[..],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_tally() {
+        let tally = ReferendumTally::new(&get_tally(75, 25, 10), 1_000);
+        assert_eq!(tally.approval, Perbill::from_percent(75));
+        assert_eq!(tally.support_ratio, Perbill::from_percent(1));
+        let tally = ReferendumTally::new(&get_tally(0, 0, 0), 1_000);
+        assert_eq!(tally.approval, Perbill::zero());
+        assert_eq!(tally.support_ratio, Perbill::zero());
+    }
+
+    #[test]
+    fn test_fellowship_tally() {
+        // 3 aye voters with rank-weighted 6 ayes against 2 nays, 10 members in the track rank
+        let tally = FellowshipTally::decode(&mut &(3u32, 6u32, 2u32).encode()[..]).unwrap();
+        let tally = ReferendumTally::new(&tally, 10);
+        assert_eq!(tally.ayes, 6);
+        assert_eq!(tally.support, 3);
+        assert_eq!(tally.approval, Perbill::from_percent(75));
+        assert_eq!(tally.support_ratio, Perbill::from_percent(30));
+    }
+
+    #[test]
+    fn test_decision_thresholds() {
+        let track = get_track();
+        let deciding = DecidingStatus {
+            since: 1_000,
+            confirming: None,
+        };
+        let tally = ReferendumTally::new(&get_tally(75, 25, 40), 1_000);
+        // at the start of the decision period
+        let decision = ReferendumDecision::new(&deciding, &track, &tally, 1_000);
+        assert_eq!(decision.deadline_block_number, 1_000 + DECISION_PERIOD);
+        assert_eq!(decision.min_approval, Perbill::from_percent(100));
+        assert_eq!(decision.min_support, Perbill::from_percent(10));
+        assert!(!decision.is_passing);
+        // half way through the decision period
+        let decision =
+            ReferendumDecision::new(&deciding, &track, &tally, 1_000 + DECISION_PERIOD / 2);
+        assert_eq!(decision.min_approval, Perbill::from_percent(75));
+        assert_eq!(decision.min_support, Perbill::zero());
+        assert!(decision.is_passing);
+        // thresholds don't go below the floor after the decision period
+        let decision =
+            ReferendumDecision::new(&deciding, &track, &tally, 1_000 + 2 * DECISION_PERIOD);
+        assert_eq!(decision.min_approval, Perbill::from_percent(50));
+        assert_eq!(decision.min_support, Perbill::zero());
+    }
+
+    #[test]
+    fn test_tally_state_change_percent() {
+        let state = ReferendumTallyState {
+            ayes: 150,
+            nays: 90,
+            support: 10,
+            ..Default::default()
+        };
+        assert_eq!(state.get_max_change_percent((150, 90, 10)), 0.0);
+        assert_eq!(state.get_max_change_percent((100, 100, 10)), 50.0);
+        assert_eq!(state.get_max_change_percent((150, 90, 0)), 100.0);
+    }
+
+    #[test]
+    fn test_tally_state_passing() {
+        let mut state = ReferendumTallyState {
+            approval_per_billion: 600_000_000,
+            support_per_billion: 10_000_000,
+            ..Default::default()
+        };
+        assert_eq!(state.is_approval_passing(), None);
+        assert!(!state.is_passing());
+        state.maybe_min_approval_per_billion = Some(500_000_000);
+        state.maybe_min_support_per_billion = Some(20_000_000);
+        assert_eq!(state.is_approval_passing(), Some(true));
+        assert_eq!(state.is_support_passing(), Some(false));
+        assert!(!state.is_passing());
+        state.maybe_min_support_per_billion = Some(10_000_000);
+        assert!(state.is_passing());
+    }
+}
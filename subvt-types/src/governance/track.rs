@@ -1,8 +1,14 @@
 use enum_iterator::Sequence;
 
+/// Tracks of the `Referenda` pallet of Polkadot and Kusama. Track definitions, including the names,
+/// are also available in the runtime metadata, see `SubstrateClient::get_referendum_tracks`.
+/// Fellowship referenda live in a separate pallet with track ids of their own, see
+/// `FellowshipTrack`.
 #[derive(Copy, Clone, Debug, PartialEq, Sequence)]
 pub enum Track {
+    Root,
     WhitelistedCaller,
+    WishForChange,
     // general admin
     StakingAdmin,
     Treasurer,
@@ -24,7 +30,9 @@ pub enum Track {
 impl Track {
     pub fn id(&self) -> u16 {
         match self {
+            Track::Root => 0,
             Track::WhitelistedCaller => 1,
+            Track::WishForChange => 2,
             // general admin
             Track::StakingAdmin => 10,
             Track::Treasurer => 11,
@@ -46,7 +54,9 @@ impl Track {
 
     pub fn name(&self) -> &str {
         match self {
+            Track::Root => "Root",
             Track::WhitelistedCaller => "Whitelisted Caller",
+            Track::WishForChange => "Wish For Change",
             // general admin
             Track::StakingAdmin => "Staking Admin",
             Track::Treasurer => "Treasurer",
@@ -68,7 +78,9 @@ impl Track {
 
     pub fn from_id(id: u16) -> Option<Track> {
         match id {
+            0 => Some(Track::Root),
             1 => Some(Track::WhitelistedCaller),
+            2 => Some(Track::WishForChange),
             10 => Some(Track::StakingAdmin),
             11 => Some(Track::Treasurer),
             12 => Some(Track::LeaseAdmin),
@@ -86,3 +98,72 @@ impl Track {
         }
     }
 }
+
+/// Tracks of the `FellowshipReferenda` pallet of Kusama. Each track is open to the members of
+/// the Fellowship ranked collective at or above the rank equal to the track id.
+#[derive(Copy, Clone, Debug, PartialEq, Sequence)]
+pub enum FellowshipTrack {
+    Candidates,
+    Members,
+    Proficients,
+    Fellows,
+    Architects,
+    ArchitectsAdept,
+    GrandArchitects,
+    Masters,
+    MastersConstant,
+    GrandMasters,
+}
+
+impl FellowshipTrack {
+    pub fn id(&self) -> u16 {
+        match self {
+            FellowshipTrack::Candidates => 0,
+            FellowshipTrack::Members => 1,
+            FellowshipTrack::Proficients => 2,
+            FellowshipTrack::Fellows => 3,
+            FellowshipTrack::Architects => 4,
+            FellowshipTrack::ArchitectsAdept => 5,
+            FellowshipTrack::GrandArchitects => 6,
+            FellowshipTrack::Masters => 7,
+            FellowshipTrack::MastersConstant => 8,
+            FellowshipTrack::GrandMasters => 9,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            FellowshipTrack::Candidates => "Candidates",
+            FellowshipTrack::Members => "Members",
+            FellowshipTrack::Proficients => "Proficients",
+            FellowshipTrack::Fellows => "Fellows",
+            FellowshipTrack::Architects => "Architects",
+            FellowshipTrack::ArchitectsAdept => "Architects Adept",
+            FellowshipTrack::GrandArchitects => "Grand Architects",
+            FellowshipTrack::Masters => "Masters",
+            FellowshipTrack::MastersConstant => "Masters Constant",
+            FellowshipTrack::GrandMasters => "Grand Masters",
+        }
+    }
+
+    pub fn from_id(id: u16) -> Option<FellowshipTrack> {
+        match id {
+            0 => Some(FellowshipTrack::Candidates),
+            1 => Some(FellowshipTrack::Members),
+            2 => Some(FellowshipTrack::Proficients),
+            3 => Some(FellowshipTrack::Fellows),
+            4 => Some(FellowshipTrack::Architects),
+            5 => Some(FellowshipTrack::ArchitectsAdept),
+            6 => Some(FellowshipTrack::GrandArchitects),
+            7 => Some(FellowshipTrack::Masters),
+            8 => Some(FellowshipTrack::MastersConstant),
+            9 => Some(FellowshipTrack::GrandMasters),
+            _ => None,
+        }
+    }
+
+    /// Minimum rank of the Fellowship members that can vote on the referenda of the track.
+    pub fn min_rank(&self) -> u16 {
+        self.id()
+    }
+}
@@ -0,0 +1,90 @@
+//! Treasury spends requested by OpenGov referendum proposals.
+use crate::crypto::AccountId;
+use crate::substrate::extrinsic::treasury::{get_beneficiary_account_id, TreasuryExtrinsic};
+use crate::substrate::extrinsic::utility::UtilityExtrinsic;
+use crate::substrate::extrinsic::SubstrateExtrinsic;
+use crate::substrate::{Balance, MultiAddress};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A `Treasury.spend` or `Treasury.spend_local` call in a referendum proposal.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ReferendumTreasurySpend {
+    pub referendum_index: u32,
+    /// Order of the spend among the spend calls of the proposal.
+    pub call_index: u32,
+    pub call_name: String,
+    /// Missing for native token spends.
+    pub asset_kind: Option<serde_json::Value>,
+    pub amount: Balance,
+    pub beneficiary: serde_json::Value,
+    /// Missing when the beneficiary location doesn't resolve to an account on the network.
+    pub beneficiary_account_id: Option<AccountId>,
+    /// Block number after which the spend can be paid out, for the asset spends.
+    pub valid_from: Option<u32>,
+}
+
+fn get_multi_address_json(address: &MultiAddress) -> serde_json::Value {
+    match address.get_account_id() {
+        Some(account_id) => serde_json::json!({ "Id": account_id.to_string() }),
+        None => serde_json::Value::String(format!("{address:?}")),
+    }
+}
+
+fn collect_treasury_spends(
+    referendum_index: u32,
+    call: &SubstrateExtrinsic,
+    spends: &mut Vec<ReferendumTreasurySpend>,
+) {
+    match call {
+        SubstrateExtrinsic::Treasury(TreasuryExtrinsic::Spend {
+            asset_kind,
+            amount,
+            beneficiary,
+            maybe_valid_from,
+            ..
+        }) => spends.push(ReferendumTreasurySpend {
+            referendum_index,
+            call_index: spends.len() as u32,
+            call_name: "spend".to_string(),
+            asset_kind: Some(asset_kind.clone()),
+            amount: *amount,
+            beneficiary: beneficiary.clone(),
+            beneficiary_account_id: get_beneficiary_account_id(beneficiary),
+            valid_from: *maybe_valid_from,
+        }),
+        SubstrateExtrinsic::Treasury(TreasuryExtrinsic::SpendLocal {
+            amount,
+            beneficiary,
+            ..
+        }) => spends.push(ReferendumTreasurySpend {
+            referendum_index,
+            call_index: spends.len() as u32,
+            call_name: "spend_local".to_string(),
+            asset_kind: None,
+            amount: *amount,
+            beneficiary: get_multi_address_json(beneficiary),
+            beneficiary_account_id: beneficiary.get_account_id(),
+            valid_from: None,
+        }),
+        SubstrateExtrinsic::Utility(UtilityExtrinsic::Batch { calls, .. })
+        | SubstrateExtrinsic::Utility(UtilityExtrinsic::BatchAll { calls, .. })
+        | SubstrateExtrinsic::Utility(UtilityExtrinsic::ForceBatch { calls, .. }) => {
+            for call in calls {
+                collect_treasury_spends(referendum_index, call, spends);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Gets the treasury spends in the decoded proposal call of a referendum, including the spends
+/// in utility batches.
+pub fn get_referendum_treasury_spends(
+    referendum_index: u32,
+    call: &SubstrateExtrinsic,
+) -> Vec<ReferendumTreasurySpend> {
+    let mut spends = Vec::new();
+    collect_treasury_spends(referendum_index, call, &mut spends);
+    spends
+}
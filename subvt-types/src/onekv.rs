@@ -4,6 +4,7 @@ use crate::crypto::AccountId;
 use crate::substrate::Balance;
 use serde::{Deserialize, Serialize};
 use subvt_proc_macro::Diff;
+use utoipa::ToSchema;
 
 #[derive(Clone, Debug, Deserialize, Diff, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -146,7 +147,7 @@ pub struct OneKVNominator {
     pub reward_destination: String,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OneKVNominatorSummary {
     pub id: u64,
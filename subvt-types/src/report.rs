@@ -1,17 +1,27 @@
 //! Report presentation types. Utilized by the `subvt-report-service` crate to server era and
 //! validator reports.
+use crate::app::event::conviction_voting::{
+    ConvictionVotingDelegatedEvent, ConvictionVotingDelegation, ConvictionVotingUndelegatedEvent,
+    ConvictionVotingVoteEvent,
+};
+use crate::app::event::treasury::TreasurySpendEvent;
 use crate::crypto::AccountId;
+use crate::governance::polkassembly::ReferendumStatus;
+use crate::governance::treasury::ReferendumTreasurySpend;
 use crate::substrate::{Account, Balance, Epoch, Era, Stake};
 use crate::subvt::{ValidatorDetails, ValidatorSummary};
+use crate::telemetry::validator::TelemetryNodeMatchMethod;
+use crate::telemetry::version::TelemetryLatestVersion;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct EraValidatorListReport {
     pub era: Era,
     pub validators: Vec<EraValidatorReport>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
 pub struct EraValidatorReport {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub account_id: Option<AccountId>,
@@ -35,7 +45,7 @@ pub struct EraValidatorReport {
     pub chilling_count: u16,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
 pub struct EraReport {
     pub era: Era,
     pub minimum_stake: Option<u128>,
@@ -70,21 +80,21 @@ pub struct SessionParaValidator {
     pub para_validator_index: u64,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct BlockSummary {
     pub number: u64,
     pub hash: String,
     pub timestamp: u64,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct ParaVotesSummary {
     pub explicit: u32,
     pub implicit: u32,
     pub missed: u32,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub enum ParaVoteType {
     #[serde(rename = "explicit")]
     EXPLICIT,
@@ -94,7 +104,7 @@ pub enum ParaVoteType {
     MISSED,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub struct ParaVote {
     pub block_number: u64,
     pub block_hash: String,
@@ -106,14 +116,14 @@ pub struct ParaVote {
     pub vote: ParaVoteType,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub struct HeartbeatEvent {
     pub block: BlockSummary,
     pub event_index: u32,
     pub im_online_key: String,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct SessionValidatorReport {
     pub session: Epoch,
     pub is_active: bool,
@@ -125,7 +135,7 @@ pub struct SessionValidatorReport {
     pub para_votes_summary: Option<ParaVotesSummary>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct SessionValidatorParaVoteReport {
     pub session: Epoch,
     pub para_validator_group_index: Option<u64>,
@@ -134,37 +144,37 @@ pub struct SessionValidatorParaVoteReport {
     pub para_votes: Option<Vec<ParaVote>>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct SessionParaVoteReport {
     pub para_id: u64,
     pub para_votes_summary: ParaVotesSummary,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct SessionParasVoteReport {
     pub session: Epoch,
     pub paras: Vec<SessionParaVoteReport>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct ValidatorSummaryReport {
     pub finalized_block: BlockSummary,
     pub validator_summary: ValidatorSummary,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct ValidatorDetailsReport {
     pub finalized_block: BlockSummary,
     pub validator_details: ValidatorDetails,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct ValidatorListReport {
     pub finalized_block: BlockSummary,
     pub validators: Vec<ValidatorSummary>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct EraValidatorRewardReport {
     pub era: Era,
     pub reward: Balance,
@@ -179,7 +189,7 @@ impl From<&(Era, Balance)> for EraValidatorRewardReport {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct EraValidatorPayoutReport {
     pub era: Era,
     pub payout: Balance,
@@ -194,13 +204,13 @@ impl From<&(Era, Balance)> for EraValidatorPayoutReport {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct ValidatorTotalReward {
     pub validator_account_id: AccountId,
     pub total_reward: Balance,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct ValidatorTotalRewardChartData {
     pub accounts: Vec<Account>,
     pub rewards: Vec<ValidatorTotalReward>,
@@ -208,15 +218,451 @@ pub struct ValidatorTotalRewardChartData {
     pub end_timestamp: u64,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct Controller {
     pub controller_account_id: AccountId,
     pub controller_address: String,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct Bond {
     pub controller_account_id: AccountId,
     pub controller_address: String,
     pub bond: Stake,
 }
+
+/// Nominator input to the nomination optimizer. All constraints are optional, the optimizer
+/// considers every active validator when none is given.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct NominationOptimizationRequest {
+    /// Amount the nominator intends to bond, in the smallest unit of the native token.
+    pub stake: Balance,
+    /// Number of validators to select. Defaults to (and cannot exceed) the runtime maximum.
+    pub max_nominations: Option<u32>,
+    pub max_commission_per_billion: Option<u32>,
+    pub min_self_stake: Option<Balance>,
+    #[serde(default)]
+    pub exclude_oversubscribed: bool,
+    #[serde(default)]
+    pub identity_required: bool,
+    #[serde(default)]
+    pub onekv_only: bool,
+    /// Maximum number of selected validators that share the same identity (parent identity
+    /// if the validator has a sub-identity).
+    pub max_per_identity: Option<u32>,
+    /// Maximum number of selected validators that share the same 1KV location.
+    pub max_per_location: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct NominationOptimizationValidator {
+    pub validator_summary: ValidatorSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Number of eras the validator was active in the evaluated era history.
+    pub active_era_count: u32,
+    /// Average era reward points of the validator in its active eras in the evaluated history.
+    pub average_reward_points: u64,
+    /// Expected annual return rate of the nominator's stake share on this validator.
+    pub expected_return_rate_per_billion: u32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct NominationOptimizationReport {
+    pub finalized_block: BlockSummary,
+    /// Number of past eras that were used to evaluate validator performance.
+    pub history_era_count: u32,
+    pub validators: Vec<NominationOptimizationValidator>,
+    /// Expected annual return rate of the whole stake, split evenly between the validators.
+    pub expected_return_rate_per_billion: u32,
+    /// Expected annual return amount for the whole stake.
+    pub expected_annual_return: Balance,
+}
+
+/// Resolution of the historical time series reports.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub enum TimeSeriesResolution {
+    #[serde(rename = "hour")]
+    Hour,
+    #[serde(rename = "day")]
+    Day,
+    #[serde(rename = "week")]
+    Week,
+}
+
+impl TimeSeriesResolution {
+    /// PostgreSQL interval string for the TimescaleDB `time_bucket` function.
+    pub fn get_interval(&self) -> &'static str {
+        match self {
+            Self::Hour => "1 hour",
+            Self::Day => "1 day",
+            Self::Week => "1 week",
+        }
+    }
+
+    pub fn get_duration_millis(&self) -> u64 {
+        match self {
+            Self::Hour => 60 * 60 * 1000,
+            Self::Day => 24 * 60 * 60 * 1000,
+            Self::Week => 7 * 24 * 60 * 60 * 1000,
+        }
+    }
+}
+
+/// Downsampled snapshot of a validator's key fields, persisted by the validator list updater.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub struct ValidatorSnapshot {
+    pub timestamp: u64,
+    pub block_number: u64,
+    pub is_active: bool,
+    pub commission_per_billion: u32,
+    pub self_stake: Balance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_stake: Option<Balance>,
+    pub nominator_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_rate_per_billion: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onekv_rank: Option<u64>,
+}
+
+impl From<(u64, u64, &ValidatorDetails)> for ValidatorSnapshot {
+    fn from(input: (u64, u64, &ValidatorDetails)) -> Self {
+        let (timestamp, block_number, validator) = input;
+        Self {
+            timestamp,
+            block_number,
+            is_active: validator.is_active,
+            commission_per_billion: validator.preferences.commission_per_billion,
+            self_stake: validator.self_stake.active_amount,
+            total_stake: validator
+                .validator_stake
+                .as_ref()
+                .map(|validator_stake| validator_stake.total_stake),
+            nominator_count: validator.nominations.len() as u32,
+            return_rate_per_billion: validator.return_rate_per_billion,
+            onekv_rank: validator.onekv_rank,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ValidatorHistoryReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub resolution: TimeSeriesResolution,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    /// One snapshot per time bucket, the last snapshot in each bucket.
+    pub snapshots: Vec<ValidatorSnapshot>,
+}
+
+/// Per-era validator data used in the stake composition, era points and nominator count charts.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ValidatorEraChartData {
+    pub era_index: u32,
+    pub is_active: bool,
+    pub self_stake: Option<Balance>,
+    pub total_stake: Option<Balance>,
+    pub active_nominator_count: Option<u32>,
+    pub reward_points: u64,
+    /// Median of the reward points of the active validators in the era.
+    pub active_set_median_reward_points: Option<u64>,
+}
+
+/// Para votes summary of a para validator in a session, used in the para votes chart.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ValidatorSessionParaVotesChartData {
+    pub session_index: u64,
+    pub para_votes_summary: ParaVotesSummary,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub enum ValidatorChartType {
+    #[serde(rename = "stake_composition")]
+    StakeComposition,
+    #[serde(rename = "era_points")]
+    EraPoints,
+    #[serde(rename = "para_votes")]
+    ParaVotes,
+    #[serde(rename = "nominator_count")]
+    NominatorCount,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub enum ChartImageFormat {
+    #[serde(rename = "svg")]
+    Svg,
+    #[default]
+    #[serde(rename = "png")]
+    Png,
+}
+
+impl ChartImageFormat {
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+
+    pub fn get_content_type(&self) -> &'static str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Png => "image/png",
+        }
+    }
+}
+
+/// OpenGov (conviction voting) activity of an account, most recent first.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConvictionVotingReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub votes: Vec<ConvictionVotingVoteEvent>,
+    pub removed_votes: Vec<ConvictionVotingVoteEvent>,
+    /// Delegations both from and to the account.
+    pub delegations: Vec<ConvictionVotingDelegatedEvent>,
+    pub undelegations: Vec<ConvictionVotingUndelegatedEvent>,
+}
+
+/// Delegations received by an OpenGov delegate on a track.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct DelegateTrackReport {
+    pub track_id: u16,
+    pub delegator_count: u32,
+    /// Total delegated capital.
+    pub capital: Balance,
+    /// Total conviction-weighted votes of the delegated capital.
+    pub votes: Balance,
+    /// Ordered by votes, descending.
+    pub delegations: Vec<ConvictionVotingDelegation>,
+}
+
+/// Current delegations to an OpenGov delegate account per track, and the history of the
+/// delegations to and undelegations from the account, most recent first.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct DelegateReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub tracks: Vec<DelegateTrackReport>,
+    pub delegated: Vec<ConvictionVotingDelegatedEvent>,
+    pub undelegated: Vec<ConvictionVotingUndelegatedEvent>,
+}
+
+/// A referendum along with the treasury spends requested by its proposal.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ReferendumTreasurySpendReport {
+    pub referendum_index: u32,
+    pub track_id: Option<u16>,
+    pub status: ReferendumStatus,
+    pub title: Option<String>,
+    pub spends: Vec<ReferendumTreasurySpend>,
+}
+
+/// Treasury payments granted to an account, and the referenda that request treasury spends
+/// for the account, most recent first.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TreasuryBeneficiaryReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub spends: Vec<TreasurySpendEvent>,
+    /// Only the spends for the account are included for each referendum.
+    pub referenda: Vec<ReferendumTreasurySpendReport>,
+}
+
+/// Referenda of a track that request treasury spends, most recent first.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TrackTreasurySpendReport {
+    pub track_id: u16,
+    pub referenda: Vec<ReferendumTreasurySpendReport>,
+}
+
+/// A Telemetry node currently matched to a validator.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ValidatorTelemetryNode {
+    pub id: u64,
+    pub name: String,
+    /// libp2p network id (peer id) of the node.
+    pub network_id: Option<String>,
+    pub client_implementation: String,
+    pub client_version: String,
+    pub best_block_number: Option<u64>,
+    pub finalized_block_number: Option<u64>,
+    pub location: Option<String>,
+    pub match_method: TelemetryNodeMatchMethod,
+    /// Confidence percentage of the match.
+    pub confidence: u8,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ValidatorTelemetryNodeReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub nodes: Vec<ValidatorTelemetryNode>,
+}
+
+/// Stats of a validator's node in a time bucket.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema)]
+pub struct ValidatorTelemetryNodeStats {
+    pub timestamp: u64,
+    pub node_name: String,
+    pub network_id: Option<String>,
+    /// Average peer count in the bucket.
+    pub peer_count: Option<u32>,
+    /// Maximum number of blocks the node's best block was behind the network's best block.
+    pub max_best_block_lag: Option<u64>,
+    /// Average download bandwidth in bytes per second.
+    pub download_bandwidth: Option<f64>,
+    /// Average upload bandwidth in bytes per second.
+    pub upload_bandwidth: Option<f64>,
+    pub client_version: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ValidatorTelemetryHistoryReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub resolution: TimeSeriesResolution,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    /// Stats of each node of the validator per time bucket, ordered by time.
+    pub stats: Vec<ValidatorTelemetryNodeStats>,
+}
+
+/// A client version that a validator's node has run.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub struct ValidatorTelemetryNodeVersion {
+    pub node_name: String,
+    pub network_id: Option<String>,
+    pub client_version: String,
+    pub first_seen_timestamp: u64,
+    pub last_seen_timestamp: u64,
+}
+
+/// Client versions of a validator's nodes within the stats retention period, most recent first.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ValidatorTelemetryVersionReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub versions: Vec<ValidatorTelemetryNodeVersion>,
+}
+
+/// GRANDPA participation of a validator in a session. Votes are the ones cast by the validator's
+/// address as received by any of the reporting nodes, each counted once. Target counts are the
+/// numbers of distinct blocks voted for, and the rates are the validator's target counts over
+/// the network's.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+pub struct ValidatorTelemetryFinalitySession {
+    pub session_index: u64,
+    /// Authority address reported by the node.
+    pub validator_address: String,
+    pub finalized_count: u64,
+    pub prevote_count: u64,
+    pub precommit_count: u64,
+    /// Number of prevotes received by all the receiving nodes after they have finalized the
+    /// voted block.
+    pub late_prevote_count: u64,
+    /// Number of precommits received by all the receiving nodes after they have finalized the
+    /// voted block.
+    pub late_precommit_count: u64,
+    pub prevote_target_count: u64,
+    pub precommit_target_count: u64,
+    pub network_prevote_target_count: u64,
+    pub network_precommit_target_count: u64,
+    pub prevote_rate: Option<f64>,
+    pub precommit_rate: Option<f64>,
+    pub last_finalized_block_number: Option<u64>,
+}
+
+/// GRANDPA participation of a validator's nodes per session, most recent session first.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ValidatorTelemetryFinalityReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub sessions: Vec<ValidatorTelemetryFinalitySession>,
+}
+
+/// A client version that a Telemetry node has run.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub struct TelemetryNodeVersion {
+    pub node_name: String,
+    pub client_implementation: String,
+    pub client_version: String,
+    pub first_seen_timestamp: u64,
+    pub last_seen_timestamp: u64,
+}
+
+/// Client versions that a Telemetry node, identified by its network id, has run, most recent
+/// first.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TelemetryNodeVersionReport {
+    pub network_id: String,
+    pub versions: Vec<TelemetryNodeVersion>,
+}
+
+/// Number of the network's Telemetry nodes that run a client version in a time bucket,
+/// averaged over the snapshots in the bucket.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub struct TelemetryVersionCount {
+    pub timestamp: u64,
+    pub client_version: String,
+    pub node_count: u32,
+    /// Number of the nodes matched to validators.
+    pub validator_node_count: u32,
+}
+
+/// Client version distribution of the network's Telemetry nodes over time, along with the
+/// latest client release.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TelemetryVersionDistributionReport {
+    pub latest_version: Option<TelemetryLatestVersion>,
+    pub resolution: TimeSeriesResolution,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub versions: Vec<TelemetryVersionCount>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidatorSnapshot;
+    use crate::substrate::{NominationSummary, ValidatorStake};
+    use crate::subvt::ValidatorDetails;
+
+    #[test]
+    fn test_validator_snapshot_from_details() {
+        let mut validator = ValidatorDetails {
+            is_active: true,
+            nominations: vec![NominationSummary::default(); 3],
+            return_rate_per_billion: Some(140_000_000),
+            onekv_rank: Some(12),
+            ..Default::default()
+        };
+        validator.preferences.commission_per_billion = 50_000_000;
+        validator.self_stake.active_amount = 1_000;
+        validator.validator_stake = Some(ValidatorStake {
+            total_stake: 5_000,
+            ..Default::default()
+        });
+        assert_eq!(
+            ValidatorSnapshot::from((1_700_000_000_000, 100, &validator)),
+            ValidatorSnapshot {
+                timestamp: 1_700_000_000_000,
+                block_number: 100,
+                is_active: true,
+                commission_per_billion: 50_000_000,
+                self_stake: 1_000,
+                total_stake: Some(5_000),
+                nominator_count: 3,
+                return_rate_per_billion: Some(140_000_000),
+                onekv_rank: Some(12),
+            }
+        );
+        // inactive validators don't have a validator stake
+        validator.validator_stake = None;
+        assert_eq!(
+            ValidatorSnapshot::from((1_700_000_000_000, 100, &validator)).total_stake,
+            None
+        );
+    }
+}
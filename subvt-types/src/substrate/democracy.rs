@@ -1,5 +1,6 @@
 use crate::crypto::AccountId;
 use crate::substrate::{Balance, DemocracyConviction};
+use pallet_conviction_voting::AccountVote as ConvictionVote;
 use std::fmt::{Display, Formatter};
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -30,6 +31,34 @@ pub struct DirectVote {
     pub conviction: Option<u8>,
 }
 
+impl From<&ConvictionVote<Balance>> for DirectVote {
+    fn from(vote: &ConvictionVote<Balance>) -> Self {
+        match vote {
+            ConvictionVote::Standard { vote, balance } => DirectVote {
+                ty: VoteType::Standard,
+                aye: if vote.aye { Some(*balance) } else { None },
+                nay: if !vote.aye { Some(*balance) } else { None },
+                abstain: None,
+                conviction: Some(get_democracy_conviction_u8(&vote.conviction)),
+            },
+            ConvictionVote::Split { aye, nay } => DirectVote {
+                ty: VoteType::Split,
+                aye: Some(*aye),
+                nay: Some(*nay),
+                abstain: None,
+                conviction: None,
+            },
+            ConvictionVote::SplitAbstain { aye, nay, abstain } => DirectVote {
+                ty: VoteType::SplitAbstain,
+                aye: Some(*aye),
+                nay: Some(*nay),
+                abstain: Some(*abstain),
+                conviction: None,
+            },
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct DelegatedVote {
     pub target_account_id: AccountId,
@@ -58,3 +87,13 @@ pub fn get_democracy_conviction_u8(conviction: &DemocracyConviction) -> u8 {
         DemocracyConviction::Locked6x => 6,
     }
 }
+
+/// Conviction-weighted votes of the given balance, for the conviction as returned by
+/// `get_democracy_conviction_u8`. No conviction counts as a tenth of the balance.
+pub fn get_conviction_votes(conviction: u8, balance: Balance) -> Balance {
+    if conviction == 0 {
+        balance / 10
+    } else {
+        balance.saturating_mul(conviction as Balance)
+    }
+}
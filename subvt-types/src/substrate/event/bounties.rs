@@ -0,0 +1,85 @@
+use crate::crypto::AccountId;
+use crate::substrate::error::DecodeError;
+use crate::substrate::event::SubstrateEvent;
+use crate::substrate::Balance;
+use parity_scale_codec::Decode;
+
+const BOUNTY_AWARDED: &str = "BountyAwarded";
+const BOUNTY_CANCELED: &str = "BountyCanceled";
+const BOUNTY_CLAIMED: &str = "BountyClaimed";
+const BOUNTY_PROPOSED: &str = "BountyProposed";
+
+#[derive(Clone, Debug)]
+pub enum BountiesEvent {
+    BountyAwarded {
+        extrinsic_index: Option<u32>,
+        bounty_index: u32,
+        beneficiary_account_id: AccountId,
+    },
+    BountyCanceled {
+        extrinsic_index: Option<u32>,
+        bounty_index: u32,
+    },
+    BountyClaimed {
+        extrinsic_index: Option<u32>,
+        bounty_index: u32,
+        payout: Balance,
+        beneficiary_account_id: AccountId,
+    },
+    BountyProposed {
+        extrinsic_index: Option<u32>,
+        bounty_index: u32,
+    },
+}
+
+impl BountiesEvent {
+    pub fn get_extrinsic_index(&self) -> Option<u32> {
+        match self {
+            Self::BountyAwarded {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::BountyCanceled {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::BountyClaimed {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::BountyProposed {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+        }
+    }
+}
+
+impl BountiesEvent {
+    pub fn decode(
+        _runtime_version: u32,
+        name: &str,
+        extrinsic_index: Option<u32>,
+        bytes: &mut &[u8],
+    ) -> Result<Option<SubstrateEvent>, DecodeError> {
+        let maybe_event = match name {
+            BOUNTY_AWARDED => Some(SubstrateEvent::Bounties(BountiesEvent::BountyAwarded {
+                extrinsic_index,
+                bounty_index: Decode::decode(bytes)?,
+                beneficiary_account_id: Decode::decode(bytes)?,
+            })),
+            BOUNTY_CANCELED => Some(SubstrateEvent::Bounties(BountiesEvent::BountyCanceled {
+                extrinsic_index,
+                bounty_index: Decode::decode(bytes)?,
+            })),
+            BOUNTY_CLAIMED => Some(SubstrateEvent::Bounties(BountiesEvent::BountyClaimed {
+                extrinsic_index,
+                bounty_index: Decode::decode(bytes)?,
+                payout: Decode::decode(bytes)?,
+                beneficiary_account_id: Decode::decode(bytes)?,
+            })),
+            BOUNTY_PROPOSED => Some(SubstrateEvent::Bounties(BountiesEvent::BountyProposed {
+                extrinsic_index,
+                bounty_index: Decode::decode(bytes)?,
+            })),
+            _ => None,
+        };
+        Ok(maybe_event)
+    }
+}
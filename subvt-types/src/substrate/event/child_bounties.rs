@@ -0,0 +1,95 @@
+use crate::crypto::AccountId;
+use crate::substrate::error::DecodeError;
+use crate::substrate::event::SubstrateEvent;
+use crate::substrate::Balance;
+use parity_scale_codec::Decode;
+
+const ADDED: &str = "Added";
+const AWARDED: &str = "Awarded";
+const CANCELED: &str = "Canceled";
+const CLAIMED: &str = "Claimed";
+
+#[derive(Clone, Debug)]
+pub enum ChildBountiesEvent {
+    Added {
+        extrinsic_index: Option<u32>,
+        parent_bounty_index: u32,
+        child_bounty_index: u32,
+    },
+    Awarded {
+        extrinsic_index: Option<u32>,
+        parent_bounty_index: u32,
+        child_bounty_index: u32,
+        beneficiary_account_id: AccountId,
+    },
+    Canceled {
+        extrinsic_index: Option<u32>,
+        parent_bounty_index: u32,
+        child_bounty_index: u32,
+    },
+    Claimed {
+        extrinsic_index: Option<u32>,
+        parent_bounty_index: u32,
+        child_bounty_index: u32,
+        payout: Balance,
+        beneficiary_account_id: AccountId,
+    },
+}
+
+impl ChildBountiesEvent {
+    pub fn get_extrinsic_index(&self) -> Option<u32> {
+        match self {
+            Self::Added {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Awarded {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Canceled {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Claimed {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+        }
+    }
+}
+
+impl ChildBountiesEvent {
+    pub fn decode(
+        _runtime_version: u32,
+        name: &str,
+        extrinsic_index: Option<u32>,
+        bytes: &mut &[u8],
+    ) -> Result<Option<SubstrateEvent>, DecodeError> {
+        let maybe_event = match name {
+            ADDED => Some(SubstrateEvent::ChildBounties(ChildBountiesEvent::Added {
+                extrinsic_index,
+                parent_bounty_index: Decode::decode(bytes)?,
+                child_bounty_index: Decode::decode(bytes)?,
+            })),
+            AWARDED => Some(SubstrateEvent::ChildBounties(ChildBountiesEvent::Awarded {
+                extrinsic_index,
+                parent_bounty_index: Decode::decode(bytes)?,
+                child_bounty_index: Decode::decode(bytes)?,
+                beneficiary_account_id: Decode::decode(bytes)?,
+            })),
+            CANCELED => Some(SubstrateEvent::ChildBounties(
+                ChildBountiesEvent::Canceled {
+                    extrinsic_index,
+                    parent_bounty_index: Decode::decode(bytes)?,
+                    child_bounty_index: Decode::decode(bytes)?,
+                },
+            )),
+            CLAIMED => Some(SubstrateEvent::ChildBounties(ChildBountiesEvent::Claimed {
+                extrinsic_index,
+                parent_bounty_index: Decode::decode(bytes)?,
+                child_bounty_index: Decode::decode(bytes)?,
+                payout: Decode::decode(bytes)?,
+                beneficiary_account_id: Decode::decode(bytes)?,
+            })),
+            _ => None,
+        };
+        Ok(maybe_event)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::crypto::AccountId;
+use crate::substrate::error::DecodeError;
+use crate::substrate::event::SubstrateEvent;
+use crate::substrate::Balance;
+use pallet_conviction_voting::AccountVote;
+use parity_scale_codec::Decode;
+
+const DELEGATED: &str = "Delegated";
+const UNDELEGATED: &str = "Undelegated";
+const VOTED: &str = "Voted";
+const VOTE_REMOVED: &str = "VoteRemoved";
+
+#[derive(Clone, Debug)]
+pub enum ConvictionVotingEvent {
+    Delegated {
+        extrinsic_index: Option<u32>,
+        original_account_id: AccountId,
+        delegate_account_id: AccountId,
+    },
+    Undelegated {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+    },
+    Voted {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+        vote: AccountVote<Balance>,
+        /// Only emitted by newer runtimes - older ones don't include the poll index,
+        /// in which case it's resolved from the extrinsic.
+        maybe_referendum_index: Option<u32>,
+    },
+    VoteRemoved {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+        vote: AccountVote<Balance>,
+        maybe_referendum_index: Option<u32>,
+    },
+}
+
+impl ConvictionVotingEvent {
+    pub fn get_extrinsic_index(&self) -> Option<u32> {
+        match self {
+            Self::Delegated {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Undelegated {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Voted {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::VoteRemoved {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+        }
+    }
+}
+
+/// Decodes the poll index appended to the vote events by newer runtimes, if present.
+fn decode_maybe_referendum_index(bytes: &mut &[u8]) -> Result<Option<u32>, DecodeError> {
+    if bytes.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(Decode::decode(bytes)?))
+    }
+}
+
+impl ConvictionVotingEvent {
+    pub fn decode(
+        _runtime_version: u32,
+        name: &str,
+        extrinsic_index: Option<u32>,
+        bytes: &mut &[u8],
+    ) -> Result<Option<SubstrateEvent>, DecodeError> {
+        let maybe_event = match name {
+            DELEGATED => Some(SubstrateEvent::ConvictionVoting(
+                ConvictionVotingEvent::Delegated {
+                    extrinsic_index,
+                    original_account_id: Decode::decode(bytes)?,
+                    delegate_account_id: Decode::decode(bytes)?,
+                },
+            )),
+            UNDELEGATED => Some(SubstrateEvent::ConvictionVoting(
+                ConvictionVotingEvent::Undelegated {
+                    extrinsic_index,
+                    account_id: Decode::decode(bytes)?,
+                },
+            )),
+            VOTED => Some(SubstrateEvent::ConvictionVoting(
+                ConvictionVotingEvent::Voted {
+                    extrinsic_index,
+                    account_id: Decode::decode(bytes)?,
+                    vote: Decode::decode(bytes)?,
+                    maybe_referendum_index: decode_maybe_referendum_index(bytes)?,
+                },
+            )),
+            VOTE_REMOVED => Some(SubstrateEvent::ConvictionVoting(
+                ConvictionVotingEvent::VoteRemoved {
+                    extrinsic_index,
+                    account_id: Decode::decode(bytes)?,
+                    vote: Decode::decode(bytes)?,
+                    maybe_referendum_index: decode_maybe_referendum_index(bytes)?,
+                },
+            )),
+            _ => None,
+        };
+        Ok(maybe_event)
+    }
+}
@@ -8,6 +8,9 @@ use crate::substrate::{
 use frame_metadata::RuntimeMetadataV14;
 use parity_scale_codec::{Compact, Decode};
 
+pub mod bounties;
+pub mod child_bounties;
+pub mod conviction_voting;
 pub mod democracy;
 pub mod identity;
 pub mod im_online;
@@ -17,10 +20,14 @@ pub mod proxy;
 pub mod referenda;
 pub mod staking;
 pub mod system;
+pub mod treasury;
 pub mod utility;
 
 #[derive(Clone, Debug)]
 pub enum SubstrateEvent {
+    Bounties(bounties::BountiesEvent),
+    ChildBounties(child_bounties::ChildBountiesEvent),
+    ConvictionVoting(conviction_voting::ConvictionVotingEvent),
     Democracy(democracy::DemocracyEvent),
     Identity(identity::IdentityEvent),
     ImOnline(im_online::ImOnlineEvent),
@@ -30,6 +37,7 @@ pub enum SubstrateEvent {
     Referenda(referenda::ReferendaEvent),
     Staking(staking::StakingEvent),
     System(system::SystemEvent),
+    Treasury(treasury::TreasuryEvent),
     Utility(utility::UtilityEvent),
     Other {
         module_name: String,
@@ -41,6 +49,9 @@ pub enum SubstrateEvent {
 impl SubstrateEvent {
     pub fn get_extrinsic_index(&self) -> Option<u32> {
         match self {
+            Self::Bounties(event) => event.get_extrinsic_index(),
+            Self::ChildBounties(event) => event.get_extrinsic_index(),
+            Self::ConvictionVoting(event) => event.get_extrinsic_index(),
             Self::Democracy(event) => event.get_extrinsic_index(),
             Self::Identity(event) => event.get_extrinsic_index(),
             Self::ImOnline(event) => event.get_extrinsic_index(),
@@ -50,6 +61,7 @@ impl SubstrateEvent {
             Self::Referenda(event) => event.get_extrinsic_index(),
             Self::Staking(event) => event.get_extrinsic_index(),
             Self::System(event) => event.get_extrinsic_index(),
+            Self::Treasury(event) => event.get_extrinsic_index(),
             Self::Utility(event) => event.get_extrinsic_index(),
             Self::Other {
                 extrinsic_index, ..
@@ -109,6 +121,24 @@ impl SubstrateEvent {
         let _topics = Vec::<sp_core::H256>::decode(bytes)?;
         // decode events
         let maybe_event = match pallet.name.as_str() {
+            "Bounties" => bounties::BountiesEvent::decode(
+                runtime_version,
+                &event_variant.name,
+                extrinsic_index,
+                event_bytes,
+            )?,
+            "ChildBounties" => child_bounties::ChildBountiesEvent::decode(
+                runtime_version,
+                &event_variant.name,
+                extrinsic_index,
+                event_bytes,
+            )?,
+            "ConvictionVoting" => conviction_voting::ConvictionVotingEvent::decode(
+                runtime_version,
+                &event_variant.name,
+                extrinsic_index,
+                event_bytes,
+            )?,
             "Democracy" => democracy::DemocracyEvent::decode(
                 runtime_version,
                 &event_variant.name,
@@ -163,6 +193,12 @@ impl SubstrateEvent {
                 extrinsic_index,
                 event_bytes,
             )?,
+            "Treasury" => treasury::TreasuryEvent::decode(
+                metadata,
+                event_variant,
+                extrinsic_index,
+                event_bytes,
+            )?,
             "Utility" => utility::UtilityEvent::decode(
                 runtime_version,
                 &event_variant.name,
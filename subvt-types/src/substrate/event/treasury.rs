@@ -0,0 +1,139 @@
+use crate::crypto::AccountId;
+use crate::substrate::error::DecodeError;
+use crate::substrate::event::SubstrateEvent;
+use crate::substrate::extrinsic::treasury::get_beneficiary_account_id;
+use crate::substrate::metadata::{decode_value, get_metadata_type};
+use crate::substrate::{Balance, BlockNumber};
+use frame_metadata::RuntimeMetadataV14;
+use parity_scale_codec::Decode;
+use scale_info::form::PortableForm;
+use scale_info::Variant;
+
+const ASSET_SPEND_APPROVED: &str = "AssetSpendApproved";
+const AWARDED: &str = "Awarded";
+const PAID: &str = "Paid";
+const SPEND_APPROVED: &str = "SpendApproved";
+
+#[derive(Clone, Debug)]
+pub enum TreasuryEvent {
+    /// Approval of a spend of any asset, paid out by a later `payout` call.
+    /// The asset kind and the beneficiary are runtime-specific (XCM locations on the relay
+    /// chains), so they're kept as decoded JSON values.
+    AssetSpendApproved {
+        extrinsic_index: Option<u32>,
+        spend_index: u32,
+        asset_kind: serde_json::Value,
+        amount: Balance,
+        beneficiary: serde_json::Value,
+        maybe_beneficiary_account_id: Option<AccountId>,
+        valid_from: BlockNumber,
+        expire_at: BlockNumber,
+    },
+    Awarded {
+        extrinsic_index: Option<u32>,
+        proposal_index: u32,
+        amount: Balance,
+        beneficiary_account_id: AccountId,
+    },
+    /// Payment of an asset spend. The runtime-specific payment id is not decoded.
+    Paid {
+        extrinsic_index: Option<u32>,
+        spend_index: u32,
+    },
+    /// Approval of a spend of the native token, paid out at the next spend period.
+    SpendApproved {
+        extrinsic_index: Option<u32>,
+        proposal_index: u32,
+        amount: Balance,
+        beneficiary_account_id: AccountId,
+    },
+}
+
+impl TreasuryEvent {
+    pub fn get_extrinsic_index(&self) -> Option<u32> {
+        match self {
+            Self::AssetSpendApproved {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Awarded {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Paid {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::SpendApproved {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+        }
+    }
+}
+
+/// Decodes the field at the given position of the event as JSON.
+fn decode_field_value(
+    metadata: &RuntimeMetadataV14,
+    event_variant: &Variant<PortableForm>,
+    field_index: usize,
+    bytes: &mut &[u8],
+) -> Result<serde_json::Value, DecodeError> {
+    let field = event_variant.fields.get(field_index).ok_or_else(|| {
+        DecodeError::Error(format!(
+            "Field #{} not found in event {}.",
+            field_index, event_variant.name,
+        ))
+    })?;
+    decode_value(
+        metadata,
+        get_metadata_type(metadata, field.ty.id),
+        bytes,
+        false,
+    )
+    .map_err(|error| DecodeError::Error(error.to_string()))
+}
+
+impl TreasuryEvent {
+    pub fn decode(
+        metadata: &RuntimeMetadataV14,
+        event_variant: &Variant<PortableForm>,
+        extrinsic_index: Option<u32>,
+        bytes: &mut &[u8],
+    ) -> Result<Option<SubstrateEvent>, DecodeError> {
+        let maybe_event = match event_variant.name.as_str() {
+            ASSET_SPEND_APPROVED => {
+                let spend_index = Decode::decode(bytes)?;
+                let asset_kind = decode_field_value(metadata, event_variant, 1, bytes)?;
+                let amount = Decode::decode(bytes)?;
+                let beneficiary = decode_field_value(metadata, event_variant, 3, bytes)?;
+                Some(SubstrateEvent::Treasury(
+                    TreasuryEvent::AssetSpendApproved {
+                        extrinsic_index,
+                        spend_index,
+                        asset_kind,
+                        amount,
+                        maybe_beneficiary_account_id: get_beneficiary_account_id(&beneficiary),
+                        beneficiary,
+                        valid_from: Decode::decode(bytes)?,
+                        expire_at: Decode::decode(bytes)?,
+                    },
+                ))
+            }
+            AWARDED => Some(SubstrateEvent::Treasury(TreasuryEvent::Awarded {
+                extrinsic_index,
+                proposal_index: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+                beneficiary_account_id: Decode::decode(bytes)?,
+            })),
+            PAID => Some(SubstrateEvent::Treasury(TreasuryEvent::Paid {
+                extrinsic_index,
+                spend_index: Decode::decode(bytes)?,
+            })),
+            SPEND_APPROVED => Some(SubstrateEvent::Treasury(TreasuryEvent::SpendApproved {
+                extrinsic_index,
+                proposal_index: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+                beneficiary_account_id: Decode::decode(bytes)?,
+            })),
+            _ => None,
+        };
+        Ok(maybe_event)
+    }
+}
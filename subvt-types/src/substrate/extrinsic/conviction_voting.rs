@@ -1,20 +1,33 @@
 use crate::substrate::error::DecodeError;
 use crate::substrate::extrinsic::{Signature, SubstrateExtrinsic};
-use crate::substrate::Balance;
+use crate::substrate::{Balance, DemocracyConviction, MultiAddress};
 use pallet_conviction_voting::AccountVote;
 use pallet_democracy::ReferendumIndex;
 use parity_scale_codec::{Compact, Decode};
 
+const DELEGATE: &str = "delegate";
 const REMOVE_VOTE: &str = "remove_vote";
+const UNDELEGATE: &str = "undelegate";
 const VOTE: &str = "vote";
 
 #[derive(Clone, Debug)]
 pub enum ConvictionVotingExtrinsic {
+    Delegate {
+        maybe_signature: Option<Signature>,
+        class: u16,
+        to: MultiAddress,
+        conviction: DemocracyConviction,
+        balance: Balance,
+    },
     RemoveVote {
         maybe_signature: Option<Signature>,
         class: Option<u16>,
         index: ReferendumIndex,
     },
+    Undelegate {
+        maybe_signature: Option<Signature>,
+        class: u16,
+    },
     Vote {
         maybe_signature: Option<Signature>,
         poll_index: Compact<ReferendumIndex>,
@@ -29,6 +42,15 @@ impl ConvictionVotingExtrinsic {
         bytes: &mut &[u8],
     ) -> Result<Option<SubstrateExtrinsic>, DecodeError> {
         let maybe_event = match name {
+            DELEGATE => Some(SubstrateExtrinsic::ConvictionVoting(
+                ConvictionVotingExtrinsic::Delegate {
+                    maybe_signature: maybe_signature.clone(),
+                    class: Decode::decode(bytes)?,
+                    to: Decode::decode(bytes)?,
+                    conviction: Decode::decode(bytes)?,
+                    balance: Decode::decode(bytes)?,
+                },
+            )),
             REMOVE_VOTE => Some(SubstrateExtrinsic::ConvictionVoting(
                 ConvictionVotingExtrinsic::RemoveVote {
                     maybe_signature: maybe_signature.clone(),
@@ -36,6 +58,12 @@ impl ConvictionVotingExtrinsic {
                     index: Decode::decode(bytes)?,
                 },
             )),
+            UNDELEGATE => Some(SubstrateExtrinsic::ConvictionVoting(
+                ConvictionVotingExtrinsic::Undelegate {
+                    maybe_signature: maybe_signature.clone(),
+                    class: Decode::decode(bytes)?,
+                },
+            )),
             VOTE => Some(SubstrateExtrinsic::ConvictionVoting(
                 ConvictionVotingExtrinsic::Vote {
                     maybe_signature: maybe_signature.clone(),
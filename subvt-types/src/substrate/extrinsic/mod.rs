@@ -16,6 +16,7 @@ pub mod proxy;
 pub mod session;
 pub mod staking;
 pub mod timestamp;
+pub mod treasury;
 pub mod utility;
 
 #[derive(Clone, Debug)]
@@ -42,6 +43,7 @@ pub enum SubstrateExtrinsic {
     Session(session::SessionExtrinsic),
     Staking(staking::StakingExtrinsic),
     Timestamp(timestamp::TimestampExtrinsic),
+    Treasury(treasury::TreasuryExtrinsic),
     Utility(utility::UtilityExtrinsic),
     Other {
         module_name: String,
@@ -83,13 +85,28 @@ impl SubstrateExtrinsic {
                 None
             };
         }
+        SubstrateExtrinsic::decode_call(chain, runtime_version, metadata, &maybe_signature, bytes)
+    }
+
+    /// Decodes a call without the extrinsic version and signature prefix, such as a call nested
+    /// in a batch or the call of a referendum proposal.
+    pub fn decode_call(
+        chain: &Chain,
+        runtime_version: u32,
+        metadata: &RuntimeMetadataV14,
+        maybe_signature: &Option<Signature>,
+        bytes: &mut &[u8],
+    ) -> Result<Self, DecodeError> {
+        let maybe_signature = maybe_signature.clone();
         let module_index: u8 = Decode::decode(&mut *bytes)?;
         let call_index: u8 = Decode::decode(&mut *bytes)?;
         let pallet = metadata
             .pallets
             .iter()
             .find(|metadata_pallet| metadata_pallet.index == module_index)
-            .unwrap();
+            .ok_or_else(|| {
+                DecodeError::Error(format!("Pallet with index {module_index} not found."))
+            })?;
         let calls_type = metadata
             .types
             .types
@@ -101,7 +118,12 @@ impl SubstrateExtrinsic {
                 .variants
                 .iter()
                 .find(|variant| variant.index == call_index)
-                .unwrap(),
+                .ok_or_else(|| {
+                    DecodeError::Error(format!(
+                        "Call with index {} not found in pallet {}.",
+                        call_index, pallet.name,
+                    ))
+                })?,
             _ => {
                 return Err(DecodeError::Error(format!(
                     "Unexpected non-variant call type: {:?}",
@@ -143,6 +165,12 @@ impl SubstrateExtrinsic {
             "Timestamp" => {
                 timestamp::TimestampExtrinsic::decode(&call_variant.name, &maybe_signature, bytes)?
             }
+            "Treasury" => treasury::TreasuryExtrinsic::decode(
+                metadata,
+                call_variant,
+                &maybe_signature,
+                bytes,
+            )?,
             "Utility" => utility::UtilityExtrinsic::decode(
                 chain,
                 runtime_version,
@@ -159,7 +187,8 @@ impl SubstrateExtrinsic {
         } else {
             for call_field in &call_variant.fields {
                 let call_field_type = get_metadata_type(metadata, call_field.ty.id);
-                decode_field(metadata, call_field_type, bytes, false).unwrap();
+                decode_field(metadata, call_field_type, bytes, false)
+                    .map_err(|error| DecodeError::Error(error.to_string()))?;
             }
             log::debug!(
                 "Decoded non-specified extrinsic {}.{}.",
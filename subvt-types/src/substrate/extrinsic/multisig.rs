@@ -46,7 +46,7 @@ impl MultisigExtrinsic {
                 threshold: Decode::decode(bytes)?,
                 other_signatories: Decode::decode(bytes)?,
                 maybe_timepoint: Decode::decode(bytes)?,
-                call: Box::new(SubstrateExtrinsic::decode_extrinsic(
+                call: Box::new(SubstrateExtrinsic::decode_call(
                     chain,
                     runtime_version,
                     metadata,
@@ -60,7 +60,7 @@ impl MultisigExtrinsic {
                 MultisigExtrinsic::AsMultiThreshold1 {
                     maybe_signature: maybe_signature.clone(),
                     other_signatories: Decode::decode(bytes)?,
-                    call: Box::new(SubstrateExtrinsic::decode_extrinsic(
+                    call: Box::new(SubstrateExtrinsic::decode_call(
                         chain,
                         runtime_version,
                         metadata,
@@ -38,7 +38,7 @@ impl ProxyExtrinsic {
                 maybe_signature: maybe_signature.clone(),
                 real: Decode::decode(bytes)?,
                 force_proxy_type: Decode::decode(bytes)?,
-                call: Box::new(SubstrateExtrinsic::decode_extrinsic(
+                call: Box::new(SubstrateExtrinsic::decode_call(
                     chain,
                     runtime_version,
                     metadata,
@@ -51,7 +51,7 @@ impl ProxyExtrinsic {
                 delegate: Decode::decode(bytes)?,
                 real: Decode::decode(bytes)?,
                 force_proxy_type: Decode::decode(bytes)?,
-                call: Box::new(SubstrateExtrinsic::decode_extrinsic(
+                call: Box::new(SubstrateExtrinsic::decode_call(
                     chain,
                     runtime_version,
                     metadata,
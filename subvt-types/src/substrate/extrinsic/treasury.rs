@@ -0,0 +1,159 @@
+use crate::crypto::AccountId;
+use crate::substrate::error::DecodeError;
+use crate::substrate::extrinsic::{Signature, SubstrateExtrinsic};
+use crate::substrate::metadata::{decode_value, get_metadata_type};
+use crate::substrate::{Balance, BlockNumber, MultiAddress};
+use frame_metadata::RuntimeMetadataV14;
+use parity_scale_codec::{Compact, Decode};
+use scale_info::form::PortableForm;
+use scale_info::Variant;
+use std::str::FromStr;
+
+const SPEND: &str = "spend";
+const SPEND_LOCAL: &str = "spend_local";
+
+#[derive(Clone, Debug)]
+pub enum TreasuryExtrinsic {
+    /// Spend of any asset. The asset kind and the beneficiary are runtime-specific (XCM
+    /// locations on the relay chains), so they're kept as decoded JSON values.
+    Spend {
+        maybe_signature: Option<Signature>,
+        asset_kind: serde_json::Value,
+        amount: Balance,
+        beneficiary: serde_json::Value,
+        maybe_valid_from: Option<BlockNumber>,
+    },
+    /// Spend of the native token. Older runtimes named this call `spend`, which gets decoded
+    /// into this variant too.
+    SpendLocal {
+        maybe_signature: Option<Signature>,
+        amount: Balance,
+        beneficiary: MultiAddress,
+    },
+}
+
+/// Finds the 32-byte account id in a decoded beneficiary location, if it has one.
+/// Both `MultiAddress::Id` and XCM `AccountId32` junctions are recognized.
+pub fn get_beneficiary_account_id(beneficiary: &serde_json::Value) -> Option<AccountId> {
+    match beneficiary {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                let maybe_account_id = match (key.as_str(), value) {
+                    ("Id", serde_json::Value::String(hex)) => AccountId::from_str(hex).ok(),
+                    ("AccountId32", serde_json::Value::Object(junction)) => {
+                        if let Some(serde_json::Value::String(hex)) = junction.get("id") {
+                            AccountId::from_str(hex).ok()
+                        } else {
+                            None
+                        }
+                    }
+                    _ => get_beneficiary_account_id(value),
+                };
+                if maybe_account_id.is_some() {
+                    return maybe_account_id;
+                }
+            }
+            None
+        }
+        serde_json::Value::Array(values) => values.iter().find_map(get_beneficiary_account_id),
+        _ => None,
+    }
+}
+
+fn decode_spend(
+    metadata: &RuntimeMetadataV14,
+    call_variant: &Variant<PortableForm>,
+    maybe_signature: &Option<Signature>,
+    bytes: &mut &[u8],
+) -> Result<SubstrateExtrinsic, DecodeError> {
+    let decode_error = |error: anyhow::Error| DecodeError::Error(error.to_string());
+    let fields = &call_variant.fields;
+    if fields.len() < 4 {
+        let amount: Compact<Balance> = Decode::decode(bytes)?;
+        return Ok(SubstrateExtrinsic::Treasury(
+            TreasuryExtrinsic::SpendLocal {
+                maybe_signature: maybe_signature.clone(),
+                amount: amount.0,
+                beneficiary: Decode::decode(bytes)?,
+            },
+        ));
+    }
+    let asset_kind = decode_value(
+        metadata,
+        get_metadata_type(metadata, fields[0].ty.id),
+        bytes,
+        false,
+    )
+    .map_err(decode_error)?;
+    let amount: Compact<Balance> = Decode::decode(bytes)?;
+    let beneficiary = decode_value(
+        metadata,
+        get_metadata_type(metadata, fields[2].ty.id),
+        bytes,
+        false,
+    )
+    .map_err(decode_error)?;
+    Ok(SubstrateExtrinsic::Treasury(TreasuryExtrinsic::Spend {
+        maybe_signature: maybe_signature.clone(),
+        asset_kind,
+        amount: amount.0,
+        beneficiary,
+        maybe_valid_from: Decode::decode(bytes)?,
+    }))
+}
+
+impl TreasuryExtrinsic {
+    pub fn decode(
+        metadata: &RuntimeMetadataV14,
+        call_variant: &Variant<PortableForm>,
+        maybe_signature: &Option<Signature>,
+        bytes: &mut &[u8],
+    ) -> Result<Option<SubstrateExtrinsic>, DecodeError> {
+        let maybe_extrinsic = match call_variant.name.as_str() {
+            SPEND => Some(decode_spend(
+                metadata,
+                call_variant,
+                maybe_signature,
+                bytes,
+            )?),
+            SPEND_LOCAL => {
+                let amount: Compact<Balance> = Decode::decode(bytes)?;
+                Some(SubstrateExtrinsic::Treasury(
+                    TreasuryExtrinsic::SpendLocal {
+                        maybe_signature: maybe_signature.clone(),
+                        amount: amount.0,
+                        beneficiary: Decode::decode(bytes)?,
+                    },
+                ))
+            }
+            _ => None,
+        };
+        Ok(maybe_extrinsic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_beneficiary_account_id;
+    use crate::crypto::AccountId;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_get_beneficiary_account_id() {
+        let hex = "0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+        let account_id = AccountId::from_str(hex).unwrap();
+        let location = serde_json::json!({
+            "V3": {
+                "parents": 0,
+                "interior": { "X1": { "AccountId32": { "network": null, "id": hex } } }
+            }
+        });
+        assert_eq!(get_beneficiary_account_id(&location), Some(account_id));
+        let address = serde_json::json!({ "Id": hex });
+        assert_eq!(get_beneficiary_account_id(&address), Some(account_id));
+        let parachain = serde_json::json!({
+            "V3": { "parents": 0, "interior": { "X1": { "Parachain": 1000 } } }
+        });
+        assert_eq!(get_beneficiary_account_id(&parachain), None);
+    }
+}
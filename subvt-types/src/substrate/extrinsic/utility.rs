@@ -18,7 +18,7 @@ fn get_extrinsic_calls(
     let call_count: Compact<u64> = Decode::decode(bytes)?;
     let mut calls = Vec::new();
     for _ in 0..call_count.0 {
-        calls.push(SubstrateExtrinsic::decode_extrinsic(
+        calls.push(SubstrateExtrinsic::decode_call(
             chain,
             runtime_version,
             metadata,
@@ -10,6 +10,9 @@ use scale_info::{Type, TypeDefPrimitive, Variant};
 use sp_core::U256;
 use sp_runtime::DispatchError;
 
+/// Maximum nesting depth of a value decoded by `decode_value`.
+pub(crate) const MAX_VALUE_DEPTH: usize = 128;
+
 pub fn hash(hasher: &StorageHasher, bytes: &[u8]) -> Vec<u8> {
     match hasher {
         StorageHasher::Identity => bytes.to_vec(),
@@ -146,20 +149,23 @@ pub(crate) fn decode_field(
         }
         scale_info::TypeDef::Variant(variant_type_def) => {
             let index: u8 = Decode::decode(bytes)?;
-            let variant = &variant_type_def
+            let variant = variant_type_def
                 .variants
                 .iter()
                 .find(|v| v.index == index)
-                .unwrap();
+                .ok_or_else(|| {
+                    DecodeError::Error(format!(
+                        "Variant with index {} not found in type {:?}.",
+                        index, field_type.path,
+                    ))
+                })?;
             for field in &variant.fields {
                 let field_type = get_metadata_type(metadata, field.ty.id);
                 decode_field(metadata, field_type, bytes, is_compact)?;
             }
         }
         scale_info::TypeDef::Sequence(sequence_type_def) => {
-            // get length (usize?)
-            let compact_length: Compact<u32> = Decode::decode(bytes)?;
-            let length = compact_length.0;
+            let length = decode_sequence_length(bytes)?;
             let sequence_type = get_metadata_type(metadata, sequence_type_def.type_param.id);
             for _ in 0..length {
                 decode_field(metadata, sequence_type, bytes, is_compact)?;
@@ -174,6 +180,268 @@ pub(crate) fn decode_field(
     Ok(())
 }
 
+/// Decodes a value of the given type into JSON, for the parts of the calls and events that
+/// are not decoded into SubVT types. 128-bit and wider integers are represented as strings,
+/// byte arrays and sequences as hex strings, and enum variants as single-key objects.
+pub fn decode_value(
+    metadata: &RuntimeMetadataV14,
+    value_type: &Type<PortableForm>,
+    bytes: &mut &[u8],
+    is_compact: bool,
+) -> anyhow::Result<serde_json::Value> {
+    decode_value_at_depth(metadata, value_type, bytes, is_compact, 0)
+}
+
+/// `depth` is the nesting depth of the value, including the depth of its container, such as a
+/// call. Recursive types, such as the calls and the XCM programs in the value, are nested as
+/// deep as the input allows, so the depth is limited to keep the recursion off the stack limit.
+pub(crate) fn decode_value_at_depth(
+    metadata: &RuntimeMetadataV14,
+    value_type: &Type<PortableForm>,
+    bytes: &mut &[u8],
+    is_compact: bool,
+    depth: usize,
+) -> anyhow::Result<serde_json::Value> {
+    if depth > MAX_VALUE_DEPTH {
+        return Err(DecodeError::Error(format!(
+            "Value is nested deeper than {MAX_VALUE_DEPTH} levels."
+        ))
+        .into());
+    }
+    let value = match &value_type.type_def {
+        scale_info::TypeDef::Primitive(primitive_type_def) => {
+            if is_compact {
+                decode_compact_primitive_value(primitive_type_def, bytes)?
+            } else {
+                decode_primitive_value(primitive_type_def, bytes)?
+            }
+        }
+        scale_info::TypeDef::Composite(composite_type_def) => decode_fields_value(
+            metadata,
+            &composite_type_def.fields,
+            bytes,
+            is_compact,
+            depth,
+        )?,
+        scale_info::TypeDef::Array(array_type_def) => {
+            let array_type = get_metadata_type(metadata, array_type_def.type_param.id);
+            if is_u8(array_type) {
+                let mut array_bytes = vec![0u8; array_type_def.len as usize];
+                parity_scale_codec::Input::read(bytes, &mut array_bytes)?;
+                serde_json::Value::String(format!("0x{}", hex::encode(array_bytes)))
+            } else {
+                let mut values = Vec::with_capacity(array_type_def.len as usize);
+                for _ in 0..array_type_def.len {
+                    values.push(decode_value_at_depth(
+                        metadata,
+                        array_type,
+                        bytes,
+                        is_compact,
+                        depth + 1,
+                    )?);
+                }
+                serde_json::Value::Array(values)
+            }
+        }
+        scale_info::TypeDef::Tuple(tuple_type_def) => {
+            let mut values = Vec::with_capacity(tuple_type_def.fields.len());
+            for field_type_id in &tuple_type_def.fields {
+                let field_type = get_metadata_type(metadata, field_type_id.id);
+                values.push(decode_value_at_depth(
+                    metadata,
+                    field_type,
+                    bytes,
+                    is_compact,
+                    depth + 1,
+                )?);
+            }
+            if values.is_empty() {
+                serde_json::Value::Null
+            } else {
+                serde_json::Value::Array(values)
+            }
+        }
+        scale_info::TypeDef::Compact(compact_type_def) => {
+            let compact_type = get_metadata_type(metadata, compact_type_def.type_param.id);
+            decode_value_at_depth(metadata, compact_type, bytes, true, depth + 1)?
+        }
+        scale_info::TypeDef::Variant(variant_type_def) => {
+            let index: u8 = Decode::decode(bytes)?;
+            let variant = variant_type_def
+                .variants
+                .iter()
+                .find(|v| v.index == index)
+                .ok_or_else(|| {
+                    DecodeError::Error(format!(
+                        "Variant with index {} not found in type {:?}.",
+                        index, value_type.path,
+                    ))
+                })?;
+            let is_option = value_type.path.segments == ["Option"];
+            if variant.fields.is_empty() {
+                if is_option {
+                    serde_json::Value::Null
+                } else {
+                    serde_json::Value::String(variant.name.clone())
+                }
+            } else {
+                let value =
+                    decode_fields_value(metadata, &variant.fields, bytes, is_compact, depth)?;
+                if is_option {
+                    value
+                } else {
+                    let mut object = serde_json::Map::new();
+                    object.insert(variant.name.clone(), value);
+                    serde_json::Value::Object(object)
+                }
+            }
+        }
+        scale_info::TypeDef::Sequence(sequence_type_def) => {
+            let length = decode_sequence_length(bytes)?;
+            let sequence_type = get_metadata_type(metadata, sequence_type_def.type_param.id);
+            if is_u8(sequence_type) {
+                let mut sequence_bytes = vec![0u8; length];
+                parity_scale_codec::Input::read(bytes, &mut sequence_bytes)?;
+                serde_json::Value::String(format!("0x{}", hex::encode(sequence_bytes)))
+            } else {
+                let mut values = Vec::with_capacity(length);
+                for _ in 0..length {
+                    values.push(decode_value_at_depth(
+                        metadata,
+                        sequence_type,
+                        bytes,
+                        is_compact,
+                        depth + 1,
+                    )?);
+                }
+                serde_json::Value::Array(values)
+            }
+        }
+        scale_info::TypeDef::BitSequence(bit_sequence) => {
+            let bit_store_type = &metadata.types.types[bit_sequence.bit_store_type.id as usize].ty;
+            let bit_order_type = &metadata.types.types[bit_sequence.bit_order_type.id as usize].ty;
+            decode_bit_sequence(bit_store_type, bit_order_type, bytes)?;
+            serde_json::Value::Null
+        }
+    };
+    Ok(value)
+}
+
+/// Decodes the compact length prefix of a sequence. The length comes from the input, so it's
+/// checked against the remaining bytes, at least one per element, before it's used to allocate
+/// or to loop.
+pub(crate) fn decode_sequence_length(bytes: &mut &[u8]) -> Result<usize, DecodeError> {
+    let length = <Compact<u32>>::decode(bytes)?.0 as usize;
+    if length > bytes.len() {
+        return Err(DecodeError::Error(format!(
+            "Sequence length {length} exceeds the remaining {} bytes.",
+            bytes.len(),
+        )));
+    }
+    Ok(length)
+}
+
+/// `char` is encoded as its `u32` code point.
+fn decode_char(bytes: &mut &[u8]) -> anyhow::Result<char> {
+    let code = u32::decode(bytes)?;
+    Ok(char::from_u32(code)
+        .ok_or_else(|| DecodeError::Error(format!("Invalid char code point {code}.")))?)
+}
+
+fn is_u8(value_type: &Type<PortableForm>) -> bool {
+    matches!(
+        value_type.type_def,
+        scale_info::TypeDef::Primitive(TypeDefPrimitive::U8)
+    )
+}
+
+/// Named fields are decoded into an object, a single unnamed field into its own value, and
+/// multiple unnamed fields into an array. `depth` is the depth of the value that has the fields.
+fn decode_fields_value(
+    metadata: &RuntimeMetadataV14,
+    fields: &[scale_info::Field<PortableForm>],
+    bytes: &mut &[u8],
+    is_compact: bool,
+    depth: usize,
+) -> anyhow::Result<serde_json::Value> {
+    if fields.is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+    let is_named = fields.iter().all(|field| field.name.is_some());
+    if is_named {
+        let mut object = serde_json::Map::new();
+        for field in fields {
+            let field_type = get_metadata_type(metadata, field.ty.id);
+            object.insert(
+                field.name.clone().unwrap_or_default(),
+                decode_value_at_depth(metadata, field_type, bytes, is_compact, depth + 1)?,
+            );
+        }
+        Ok(serde_json::Value::Object(object))
+    } else if fields.len() == 1 {
+        let field_type = get_metadata_type(metadata, fields[0].ty.id);
+        decode_value_at_depth(metadata, field_type, bytes, is_compact, depth + 1)
+    } else {
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            let field_type = get_metadata_type(metadata, field.ty.id);
+            values.push(decode_value_at_depth(
+                metadata,
+                field_type,
+                bytes,
+                is_compact,
+                depth + 1,
+            )?);
+        }
+        Ok(serde_json::Value::Array(values))
+    }
+}
+
+fn decode_primitive_value(
+    type_def: &TypeDefPrimitive,
+    bytes: &mut &[u8],
+) -> anyhow::Result<serde_json::Value> {
+    let value = match type_def {
+        TypeDefPrimitive::Bool => serde_json::Value::from(bool::decode(bytes)?),
+        TypeDefPrimitive::Str => serde_json::Value::from(String::decode(bytes)?),
+        TypeDefPrimitive::Char => serde_json::Value::from(decode_char(bytes)?.to_string()),
+        TypeDefPrimitive::U8 => serde_json::Value::from(u8::decode(bytes)?),
+        TypeDefPrimitive::U16 => serde_json::Value::from(u16::decode(bytes)?),
+        TypeDefPrimitive::U32 => serde_json::Value::from(u32::decode(bytes)?),
+        TypeDefPrimitive::U64 => serde_json::Value::from(u64::decode(bytes)?),
+        TypeDefPrimitive::U128 => serde_json::Value::from(u128::decode(bytes)?.to_string()),
+        TypeDefPrimitive::U256 => serde_json::Value::from(U256::decode(bytes)?.to_string()),
+        TypeDefPrimitive::I8 => serde_json::Value::from(i8::decode(bytes)?),
+        TypeDefPrimitive::I16 => serde_json::Value::from(i16::decode(bytes)?),
+        TypeDefPrimitive::I32 => serde_json::Value::from(i32::decode(bytes)?),
+        TypeDefPrimitive::I64 => serde_json::Value::from(i64::decode(bytes)?),
+        TypeDefPrimitive::I128 => serde_json::Value::from(i128::decode(bytes)?.to_string()),
+        TypeDefPrimitive::I256 => {
+            serde_json::Value::from(format!("0x{}", hex::encode(<[u8; 32]>::decode(bytes)?)))
+        }
+    };
+    Ok(value)
+}
+
+fn decode_compact_primitive_value(
+    type_def: &TypeDefPrimitive,
+    bytes: &mut &[u8],
+) -> anyhow::Result<serde_json::Value> {
+    let value = match type_def {
+        TypeDefPrimitive::U8 => serde_json::Value::from(<Compact<u8>>::decode(bytes)?.0),
+        TypeDefPrimitive::U16 => serde_json::Value::from(<Compact<u16>>::decode(bytes)?.0),
+        TypeDefPrimitive::U32 => serde_json::Value::from(<Compact<u32>>::decode(bytes)?.0),
+        TypeDefPrimitive::U64 => serde_json::Value::from(<Compact<u64>>::decode(bytes)?.0),
+        TypeDefPrimitive::U128 => {
+            serde_json::Value::from(<Compact<u128>>::decode(bytes)?.0.to_string())
+        }
+        _ => {
+            return Err(DecodeError::Error(format!("No compact for {type_def:?}.")).into());
+        }
+    };
+    Ok(value)
+}
+
 fn decode_bit_sequence(
     bit_store_type: &Type<PortableForm>,
     bit_order_type: &Type<PortableForm>,
@@ -249,7 +517,10 @@ fn decode_primitive(type_def: &TypeDefPrimitive, bytes: &mut &[u8]) -> anyhow::R
         TypeDefPrimitive::Str => {
             let _value: String = Decode::decode(bytes)?;
         }
-        TypeDefPrimitive::Char | TypeDefPrimitive::U8 => {
+        TypeDefPrimitive::Char => {
+            let _value = decode_char(bytes)?;
+        }
+        TypeDefPrimitive::U8 => {
             let _value: u8 = Decode::decode(bytes)?;
         }
         TypeDefPrimitive::U16 => {
@@ -294,7 +565,10 @@ fn decode_compact_primitive(type_def: &TypeDefPrimitive, bytes: &mut &[u8]) -> a
         TypeDefPrimitive::Bool => {
             return Err(DecodeError::Error("No compact for Bool.".to_string()).into());
         }
-        TypeDefPrimitive::Char | TypeDefPrimitive::U8 => {
+        TypeDefPrimitive::Char => {
+            return Err(DecodeError::Error("No compact for Char.".to_string()).into());
+        }
+        TypeDefPrimitive::U8 => {
             let _value: Compact<u8> = Decode::decode(bytes)?;
         }
         TypeDefPrimitive::Str => {
@@ -660,3 +934,132 @@ pub fn decode_weight(
         Ok(Decode::decode(bytes)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_value, get_metadata_type, MAX_VALUE_DEPTH};
+    use crate::substrate::error::DecodeError;
+    use frame_metadata::v14::ExtrinsicMetadata;
+    use frame_metadata::RuntimeMetadataV14;
+    use parity_scale_codec::{Compact, Encode};
+    use scale_info::{meta_type, Registry, TypeInfo};
+
+    #[derive(Encode, TypeInfo)]
+    enum Nested {
+        #[codec(index = 0)]
+        Leaf,
+        #[codec(index = 1)]
+        Node(Box<Nested>),
+    }
+
+    /// `Leaf` wrapped in `depth` nodes.
+    fn get_nested(depth: usize) -> Nested {
+        (0..depth).fold(Nested::Leaf, |nested, _| Nested::Node(Box::new(nested)))
+    }
+
+    /// Decodes the bytes using the metadata of the type `T`.
+    fn decode<T: TypeInfo + 'static>(bytes: &[u8]) -> anyhow::Result<serde_json::Value> {
+        let mut registry = Registry::new();
+        let type_id = registry.register_type(&meta_type::<T>()).id;
+        let unit_type = registry.register_type(&meta_type::<()>());
+        let metadata = RuntimeMetadataV14 {
+            types: registry.into(),
+            pallets: vec![],
+            extrinsic: ExtrinsicMetadata {
+                ty: unit_type,
+                version: 4,
+                signed_extensions: vec![],
+            },
+            ty: unit_type,
+        };
+        let mut bytes = bytes;
+        let value = decode_value(
+            &metadata,
+            get_metadata_type(&metadata, type_id),
+            &mut bytes,
+            false,
+        )?;
+        assert!(bytes.is_empty(), "{} bytes left undecoded", bytes.len());
+        Ok(value)
+    }
+
+    #[test]
+    fn test_decode_sequences() {
+        assert_eq!(
+            decode::<Vec<u32>>(&vec![1u32, 2, 3].encode()).unwrap(),
+            serde_json::json!([1, 2, 3])
+        );
+        assert_eq!(
+            decode::<Vec<u8>>(&vec![1u8, 2].encode()).unwrap(),
+            serde_json::json!("0x0102")
+        );
+        assert_eq!(
+            decode::<Vec<Vec<u16>>>(&vec![vec![1u16], vec![]].encode()).unwrap(),
+            serde_json::json!([[1], []])
+        );
+    }
+
+    #[test]
+    fn test_decode_compacts() {
+        assert_eq!(
+            decode::<Compact<u32>>(&Compact(1_000_000u32).encode()).unwrap(),
+            serde_json::json!(1_000_000)
+        );
+        assert_eq!(
+            decode::<Compact<u128>>(&Compact(u128::MAX).encode()).unwrap(),
+            serde_json::json!(u128::MAX.to_string())
+        );
+        assert_eq!(
+            decode::<Vec<Compact<u64>>>(&vec![Compact(1u64), Compact(u64::MAX)].encode()).unwrap(),
+            serde_json::json!([1, u64::MAX])
+        );
+    }
+
+    #[test]
+    fn test_decode_chars() {
+        assert_eq!(
+            decode::<char>(&('ö' as u32).encode()).unwrap(),
+            serde_json::json!("ö")
+        );
+        assert_eq!(
+            decode::<char>(&('🦀' as u32).encode()).unwrap(),
+            serde_json::json!("🦀")
+        );
+        // surrogate code points aren't chars
+        let error = decode::<char>(&0xD800u32.encode()).unwrap_err();
+        assert!(error.downcast_ref::<DecodeError>().is_some());
+        // a char takes 4 bytes
+        assert!(decode::<char>(b"a").is_err());
+    }
+
+    #[test]
+    fn test_decode_invalid_sequence_length() {
+        // length prefix larger than the input
+        let mut bytes = Compact(u32::MAX).encode();
+        bytes.extend_from_slice(&[1, 2, 3, 4]);
+        let error = decode::<Vec<u32>>(&bytes).unwrap_err();
+        assert!(error.downcast_ref::<DecodeError>().is_some());
+        let error = decode::<Vec<u8>>(&bytes).unwrap_err();
+        assert!(error.downcast_ref::<DecodeError>().is_some());
+        // truncated byte sequence
+        let mut bytes = vec![1u8, 2, 3, 4].encode();
+        bytes.truncate(3);
+        let error = decode::<Vec<u8>>(&bytes).unwrap_err();
+        assert!(error.downcast_ref::<DecodeError>().is_some());
+        // truncated sequence elements
+        let mut bytes = vec![1u32, 2].encode();
+        bytes.truncate(6);
+        assert!(decode::<Vec<u32>>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_decode_value_depth_limit() {
+        assert!(decode::<Nested>(&get_nested(MAX_VALUE_DEPTH).encode()).is_ok());
+        let error = decode::<Nested>(&get_nested(MAX_VALUE_DEPTH + 1).encode()).unwrap_err();
+        assert!(error.downcast_ref::<DecodeError>().is_some());
+        // input as deep as its length, a byte per level
+        let mut bytes = vec![1u8; 1_000_000];
+        bytes.push(0);
+        assert!(decode::<Nested>(&bytes).is_err());
+    }
+}
@@ -25,6 +25,7 @@ use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use subvt_utility::decode_hex_string;
 use subvt_utility::text::get_condensed_address;
+use utoipa::ToSchema;
 
 pub type CallHash = [u8; 32];
 pub type OpaqueTimeSlot = Vec<u8>;
@@ -126,7 +127,7 @@ impl MultiAddress {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct Account {
     pub id: AccountId,
     pub address: String,
@@ -135,6 +136,7 @@ pub struct Account {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_account_id: Option<AccountId>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Account>)]
     pub parent: Box<Option<Account>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub child_display: Option<String>,
@@ -347,7 +349,7 @@ struct SubstrateActiveEraInfo {
 }
 
 /// Era as represented in the SubVT domain.
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
 pub struct Era {
     pub index: u32,
     pub start_timestamp: u64,
@@ -385,7 +387,7 @@ impl Era {
 }
 
 /// Epoch as represented in the SubVT domain.
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
 pub struct Epoch {
     pub index: u64,
     pub start_block_number: u32,
@@ -410,7 +412,7 @@ impl Epoch {
 }
 
 /// A nominator's active stake on a validator.
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct NominatorStake {
     pub account: Account,
     pub stake: Balance,
@@ -418,7 +420,7 @@ pub struct NominatorStake {
 
 /// Active staking information for a single active validator. Contains the validator account id,
 /// self stake, total stake and each nominator's active stake on the validator.
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct ValidatorStake {
     pub account: Account,
     pub self_stake: Balance,
@@ -514,7 +516,7 @@ pub struct EraRewardPoints {
 }
 
 /// Validator commission and block preferences.
-#[derive(Clone, Debug, Encode, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Encode, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct ValidatorPreferences {
     pub commission_per_billion: u32,
     pub blocks_nominations: bool,
@@ -530,7 +532,7 @@ impl Decode for ValidatorPreferences {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct IdentityRegistration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display: Option<String>,
@@ -611,7 +613,7 @@ impl From<&IdentityRegistration> for IdentityRegistrationSummary {
 
 pub type SuperAccountId = (AccountId, Data);
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct NominationSummary {
     pub stash_account: Account,
     pub submission_era_index: u32,
@@ -654,7 +656,7 @@ impl Nomination {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct InactiveNominationsSummary {
     pub nomination_count: u16,
     pub total_amount: Balance,
@@ -680,7 +682,7 @@ struct StakingLedger {
     pub _claimed_rewards: BoundedVec<EraIndex, ConstU32<{ u32::MAX }>>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct Stake {
     pub stash_account_id: AccountId,
     pub total_amount: Balance,
@@ -701,7 +703,7 @@ impl Stake {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct StakeSummary {
     pub stash_account_id: AccountId,
     pub active_amount: Balance,
@@ -11,6 +11,7 @@ use crate::substrate::{
 use serde::{Deserialize, Serialize};
 use std::convert::From;
 use subvt_proc_macro::Diff;
+use utoipa::ToSchema;
 
 /// Represents the network's status that changes with every block.
 #[derive(Clone, Debug, Diff, Default, Deserialize, Serialize)]
@@ -45,7 +46,7 @@ pub struct NetworkStatusUpdate {
 }
 
 /// Represents an inactive validator, waiting to be in the active set.
-#[derive(Clone, Debug, Default, Deserialize, Diff, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Diff, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct ValidatorDetails {
     #[diff_key]
     pub account: Account,
@@ -53,6 +54,7 @@ pub struct ValidatorDetails {
     pub controller_account_id: AccountId,
     pub preferences: ValidatorPreferences,
     pub self_stake: Stake,
+    #[schema(value_type = Object)]
     pub reward_destination: RewardDestination,
     pub next_session_keys: String,
     pub queued_session_keys: Option<String>,
@@ -67,6 +69,7 @@ pub struct ValidatorDetails {
     pub unclaimed_era_indices: Vec<u32>,
     pub is_para_validator: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Object)]
     pub para_core_assignment: Option<ParaCoreAssignment>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_rate_per_billion: Option<u32>,
@@ -90,7 +93,7 @@ pub struct ValidatorDetails {
     pub onekv_offline_since: Option<u64>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Diff, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Diff, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct ValidatorSummary {
     #[diff_key]
     pub account_id: AccountId,
@@ -103,6 +106,9 @@ pub struct ValidatorSummary {
     pub parent_display: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub child_display: Option<String>,
+    /// Account id of the parent identity, if the validator has a sub-identity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_account_id: Option<AccountId>,
     pub confirmed: bool,
     pub preferences: ValidatorPreferences,
     pub self_stake: StakeSummary,
@@ -154,6 +160,7 @@ impl From<&ValidatorDetails> for ValidatorSummary {
             display: validator.account.get_display(),
             parent_display: validator.account.get_parent_display(),
             child_display: validator.account.child_display.clone(),
+            parent_account_id: validator.account.parent_account_id,
             confirmed: validator.account.get_confirmed(),
             preferences: validator.preferences.clone(),
             self_stake: StakeSummary::from(&validator.self_stake),
@@ -180,25 +187,68 @@ impl From<&ValidatorDetails> for ValidatorSummary {
     }
 }
 
+/// Whether all the characters of `query` appear in `text` in the same order, e.g. `kn` in `kaan`.
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut text_chars = text.chars();
+    query
+        .chars()
+        .all(|query_char| text_chars.any(|text_char| text_char == query_char))
+}
+
 impl ValidatorSummary {
     pub fn filter(&self, query: &str) -> bool {
-        let query = query.to_lowercase();
-        self.address.to_lowercase().contains(&query)
-            || self
-                .display
-                .as_ref()
-                .map(|display| display.to_lowercase().contains(&query))
-                .unwrap_or(false)
-            || self
-                .parent_display
-                .as_ref()
-                .map(|display| display.to_lowercase().contains(&query))
-                .unwrap_or(false)
+        self.get_search_score(query).is_some()
+    }
+
+    /// Relevance of the validator to the free-text search `query`, higher the better. `None` if
+    /// the validator doesn't match the query. Address prefix and identity (display and parent
+    /// display) matches rank higher than substring matches, which rank higher than the fuzzy
+    /// (in-order characters) matches on the identity.
+    pub fn get_search_score(&self, query: &str) -> Option<u32> {
+        let query = query.trim().to_lowercase();
+        let address = self.address.to_lowercase();
+        if address.starts_with(&query) {
+            return Some(100);
+        }
+        let identity_displays: Vec<String> = [&self.display, &self.parent_display]
+            .into_iter()
+            .flatten()
+            .map(|display| display.to_lowercase())
+            .collect();
+        let mut score = None;
+        for display in &identity_displays {
+            let display_score = if *display == query {
+                90
+            } else if display.starts_with(&query) {
+                80
+            } else if display.contains(&query) {
+                60
+            } else {
+                continue;
+            };
+            score = score.max(Some(display_score));
+        }
+        if score.is_some() {
+            return score;
+        }
+        if address.contains(&query)
             || self
                 .child_display
                 .as_ref()
                 .map(|display| display.to_lowercase().contains(&query))
                 .unwrap_or(false)
+        {
+            return Some(40);
+        }
+        let compact_query: String = query.split_whitespace().collect();
+        if compact_query.chars().count() > 1
+            && identity_displays
+                .iter()
+                .any(|display| is_subsequence(&compact_query, display))
+        {
+            return Some(20);
+        }
+        None
     }
 }
 
@@ -211,7 +261,7 @@ pub struct ValidatorListUpdate {
     pub remove_ids: Vec<AccountId>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
 pub struct ValidatorStakeSummary {
     pub self_stake: Balance,
     pub total_stake: Balance,
@@ -285,7 +335,7 @@ impl From<&ValidatorDetails> for ValidatorNominationSummary {
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
 pub struct ValidatorSearchSummary {
     pub account_id: AccountId,
     pub address: String,
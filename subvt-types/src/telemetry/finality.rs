@@ -0,0 +1,52 @@
+//! GRANDPA messages of the validator nodes. Telemetry reports the finalized blocks and the votes
+//! received by the nodes that have a validator (authority) address, identified by that address.
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TelemetryFinalityMessageKind {
+    Finalized,
+    Prevote,
+    Precommit,
+}
+
+impl Display for TelemetryFinalityMessageKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Finalized => "finalized",
+            Self::Prevote => "prevote",
+            Self::Precommit => "precommit",
+        };
+        write!(f, "{display}")
+    }
+}
+
+/// A GRANDPA message of a node, as received from a Telemetry feed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TelemetryFinalityMessage {
+    pub validator_address: String,
+    pub kind: TelemetryFinalityMessageKind,
+    pub block_number: u64,
+    pub block_hash: String,
+    /// Authority that has cast the received vote, `None` for the finalized messages.
+    pub voter: Option<String>,
+    /// The vote has been received after the node has finalized the voted block.
+    pub is_late: bool,
+    /// Time the message has been received from the feed, in milliseconds.
+    pub timestamp: u64,
+}
+
+/// Finality status of a node currently matched to a validator, used to detect the nodes that
+/// import blocks but don't participate in finality.
+#[derive(Clone, Debug)]
+pub struct TelemetryNodeFinalityStatus {
+    pub node_id: u64,
+    pub node_name: String,
+    pub validator_address: String,
+    pub best_block_number: Option<u64>,
+    /// Number of blocks the node's best block is behind the network's best block.
+    pub best_block_lag: Option<u64>,
+    /// Time of the last GRANDPA vote (prevote or precommit) of the node within the retention
+    /// period.
+    pub last_finality_timestamp: Option<u64>,
+    pub last_finality_block_number: Option<u64>,
+}
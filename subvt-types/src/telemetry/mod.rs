@@ -3,6 +3,10 @@ use anyhow::Context;
 use serde::Deserialize;
 use serde_json::value::RawValue;
 
+pub mod finality;
+pub mod validator;
+pub mod version;
+
 #[derive(Debug, Deserialize, Eq, PartialEq)]
 pub struct NodeStats {
     pub peer_count: u64,
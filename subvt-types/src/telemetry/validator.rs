@@ -0,0 +1,455 @@
+//! Mapping of the Telemetry nodes to the validators. Telemetry doesn't report which validator a
+//! node belongs to, so the nodes get matched to the validators by the user overrides of the
+//! network id, the session keys, the controller account, the user overrides of the node name,
+//! earlier matches of the same libp2p network id, and the node name, in the decreasing order of
+//! confidence.
+use crate::crypto::AccountId;
+use crate::err::ServiceError;
+use crate::subvt::ValidatorDetails;
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Node names shorter than this (after normalization) are not matched by containment.
+const MIN_NAME_CONTAINS_LENGTH: usize = 4;
+/// Size of a session public key in the session keys hex string.
+const SESSION_KEY_BYTE_COUNT: usize = 32;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryNodeMatchMethod {
+    /// Set by the owner of the validator in the app.
+    Override,
+    /// The authority address reported by the node is one of the validator's session keys.
+    SessionKeys,
+    /// The node reports the validator's controller or stash address.
+    Controller,
+    /// The network id of the node was matched to the validator before with a higher confidence.
+    NetworkId,
+    /// The node name is the validator's identity display.
+    NameExact,
+    /// The node name contains the validator's identity display.
+    NameContains,
+}
+
+impl TelemetryNodeMatchMethod {
+    /// Confidence percentage of the match.
+    pub fn confidence(&self) -> u8 {
+        match self {
+            Self::Override => 100,
+            Self::SessionKeys => 95,
+            Self::Controller => 90,
+            Self::NetworkId => 80,
+            Self::NameExact => 50,
+            Self::NameContains => 25,
+        }
+    }
+
+    /// Matches with this method are remembered by the network id of the node.
+    pub fn is_reliable(&self) -> bool {
+        self.confidence() >= Self::Controller.confidence()
+    }
+}
+
+impl Display for TelemetryNodeMatchMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Override => "override",
+            Self::SessionKeys => "session_keys",
+            Self::Controller => "controller",
+            Self::NetworkId => "network_id",
+            Self::NameExact => "name_exact",
+            Self::NameContains => "name_contains",
+        };
+        write!(f, "{display}")
+    }
+}
+
+impl FromStr for TelemetryNodeMatchMethod {
+    type Err = ServiceError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "override" => Ok(Self::Override),
+            "session_keys" => Ok(Self::SessionKeys),
+            "controller" => Ok(Self::Controller),
+            "network_id" => Ok(Self::NetworkId),
+            "name_exact" => Ok(Self::NameExact),
+            "name_contains" => Ok(Self::NameContains),
+            _ => Err(ServiceError::from("Unknown Telemetry node match method.")),
+        }
+    }
+}
+
+/// A Telemetry node as persisted by the Telemetry processor, with the fields used in matching.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TelemetryNode {
+    pub id: u64,
+    pub name: String,
+    pub network_id: Option<String>,
+    /// Address of the authority (BABE) key of the node, reported when the node is a validator.
+    pub validator_address: Option<String>,
+    pub controller_account_id: Option<AccountId>,
+}
+
+/// Validator-side data used in matching.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TelemetryNodeMatchCandidate {
+    pub account_id: AccountId,
+    pub controller_account_id: AccountId,
+    /// Session public keys of the next and the queued session keys.
+    pub session_keys: Vec<[u8; SESSION_KEY_BYTE_COUNT]>,
+    pub display: Option<String>,
+}
+
+fn get_session_keys(session_keys_hex: &str) -> Vec<[u8; SESSION_KEY_BYTE_COUNT]> {
+    let bytes = match hex::decode(session_keys_hex.trim_start_matches("0x")) {
+        Ok(bytes) => bytes,
+        Err(_) => return Vec::new(),
+    };
+    bytes
+        .chunks_exact(SESSION_KEY_BYTE_COUNT)
+        .filter_map(|chunk| chunk.try_into().ok())
+        .collect()
+}
+
+impl From<&ValidatorDetails> for TelemetryNodeMatchCandidate {
+    fn from(validator: &ValidatorDetails) -> Self {
+        let mut session_keys = get_session_keys(&validator.next_session_keys);
+        if let Some(queued_session_keys) = &validator.queued_session_keys {
+            for session_key in get_session_keys(queued_session_keys) {
+                if !session_keys.contains(&session_key) {
+                    session_keys.push(session_key);
+                }
+            }
+        }
+        Self {
+            account_id: validator.account.id,
+            controller_account_id: validator.controller_account_id,
+            session_keys,
+            display: validator.account.get_full_display(),
+        }
+    }
+}
+
+/// Node that a validator owner has assigned to the validator in the app, by network id or
+/// by node name.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub struct TelemetryNodeOverride {
+    pub validator_account_id: AccountId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub struct TelemetryNodeValidator {
+    pub node_id: u64,
+    pub validator_account_id: AccountId,
+    pub match_method: TelemetryNodeMatchMethod,
+    pub confidence: u8,
+}
+
+impl TelemetryNodeValidator {
+    fn new(node: &TelemetryNode, account_id: &AccountId, method: TelemetryNodeMatchMethod) -> Self {
+        Self {
+            node_id: node.id,
+            validator_account_id: *account_id,
+            match_method: method,
+            confidence: method.confidence(),
+        }
+    }
+}
+
+/// Lowercase alphanumeric characters of the name, for the name comparisons.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The only candidate that satisfies the predicate, if there's exactly one.
+fn find_unique<'a, F>(
+    candidates: &'a [TelemetryNodeMatchCandidate],
+    predicate: F,
+) -> Option<&'a TelemetryNodeMatchCandidate>
+where
+    F: Fn(&TelemetryNodeMatchCandidate) -> bool,
+{
+    let mut matches = candidates.iter().filter(|candidate| predicate(candidate));
+    match (matches.next(), matches.next()) {
+        (Some(candidate), None) => Some(candidate),
+        _ => None,
+    }
+}
+
+fn match_node(
+    node: &TelemetryNode,
+    candidates: &[TelemetryNodeMatchCandidate],
+    overrides: &[TelemetryNodeOverride],
+    known_network_ids: &HashMap<String, AccountId>,
+) -> Option<TelemetryNodeValidator> {
+    if let Some(node_override) = overrides.iter().find(|node_override| {
+        node_override.network_id.is_some() && node_override.network_id == node.network_id
+    }) {
+        return Some(TelemetryNodeValidator::new(
+            node,
+            &node_override.validator_account_id,
+            TelemetryNodeMatchMethod::Override,
+        ));
+    }
+    if let Some(authority_account_id) = node
+        .validator_address
+        .as_deref()
+        .and_then(|address| AccountId::from_str(address).ok())
+    {
+        let authority_key: &[u8] = authority_account_id.as_ref();
+        if let Some(candidate) = candidates.iter().find(|candidate| {
+            candidate
+                .session_keys
+                .iter()
+                .any(|session_key| session_key.as_slice() == authority_key)
+        }) {
+            return Some(TelemetryNodeValidator::new(
+                node,
+                &candidate.account_id,
+                TelemetryNodeMatchMethod::SessionKeys,
+            ));
+        }
+    }
+    if let Some(controller_account_id) = &node.controller_account_id {
+        if let Some(candidate) = candidates.iter().find(|candidate| {
+            candidate.controller_account_id == *controller_account_id
+                || candidate.account_id == *controller_account_id
+        }) {
+            return Some(TelemetryNodeValidator::new(
+                node,
+                &candidate.account_id,
+                TelemetryNodeMatchMethod::Controller,
+            ));
+        }
+    }
+    // node names aren't unique, so a name override doesn't take the node from the validator
+    // that the node's keys belong to
+    if let Some(node_override) = overrides
+        .iter()
+        .find(|node_override| node_override.node_name.as_deref() == Some(node.name.as_str()))
+    {
+        return Some(TelemetryNodeValidator::new(
+            node,
+            &node_override.validator_account_id,
+            TelemetryNodeMatchMethod::Override,
+        ));
+    }
+    if let Some(account_id) = node
+        .network_id
+        .as_ref()
+        .and_then(|network_id| known_network_ids.get(network_id))
+    {
+        return Some(TelemetryNodeValidator::new(
+            node,
+            account_id,
+            TelemetryNodeMatchMethod::NetworkId,
+        ));
+    }
+    let node_name = normalize_name(&node.name);
+    if node_name.is_empty() {
+        return None;
+    }
+    let candidate_name =
+        |candidate: &TelemetryNodeMatchCandidate| candidate.display.as_deref().map(normalize_name);
+    if let Some(candidate) = find_unique(candidates, |candidate| {
+        candidate_name(candidate).as_deref() == Some(node_name.as_str())
+    }) {
+        return Some(TelemetryNodeValidator::new(
+            node,
+            &candidate.account_id,
+            TelemetryNodeMatchMethod::NameExact,
+        ));
+    }
+    find_unique(candidates, |candidate| match candidate_name(candidate) {
+        Some(display) => {
+            display.chars().count() >= MIN_NAME_CONTAINS_LENGTH && node_name.contains(&display)
+        }
+        None => false,
+    })
+    .map(|candidate| {
+        TelemetryNodeValidator::new(
+            node,
+            &candidate.account_id,
+            TelemetryNodeMatchMethod::NameContains,
+        )
+    })
+}
+
+/// Matches each node to at most one validator with the most confident method available.
+/// `known_network_ids` are the earlier reliable matches of the network ids.
+pub fn match_nodes_to_validators(
+    nodes: &[TelemetryNode],
+    candidates: &[TelemetryNodeMatchCandidate],
+    overrides: &[TelemetryNodeOverride],
+    known_network_ids: &HashMap<String, AccountId>,
+) -> Vec<TelemetryNodeValidator> {
+    nodes
+        .iter()
+        .filter_map(|node| match_node(node, candidates, overrides, known_network_ids))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        match_nodes_to_validators, TelemetryNode, TelemetryNodeMatchCandidate,
+        TelemetryNodeMatchMethod, TelemetryNodeOverride,
+    };
+    use crate::crypto::AccountId;
+    use rustc_hash::FxHashMap as HashMap;
+
+    fn get_candidate(seed: u8, display: &str) -> TelemetryNodeMatchCandidate {
+        TelemetryNodeMatchCandidate {
+            account_id: AccountId::new([seed; 32]),
+            controller_account_id: AccountId::new([seed + 100; 32]),
+            session_keys: vec![[seed + 50; 32], [seed + 60; 32]],
+            display: Some(display.to_string()),
+        }
+    }
+
+    fn get_node(id: u64, name: &str) -> TelemetryNode {
+        TelemetryNode {
+            id,
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn match_method(
+        node: &TelemetryNode,
+        overrides: &[TelemetryNodeOverride],
+        known_network_ids: &HashMap<String, AccountId>,
+    ) -> Option<(AccountId, TelemetryNodeMatchMethod)> {
+        let candidates = vec![
+            get_candidate(1, "Helikon"),
+            get_candidate(2, "Other Validator"),
+            get_candidate(3, "Other Validator"),
+        ];
+        match_nodes_to_validators(&[node.clone()], &candidates, overrides, known_network_ids)
+            .first()
+            .map(|node_validator| {
+                (
+                    node_validator.validator_account_id,
+                    node_validator.match_method,
+                )
+            })
+    }
+
+    #[test]
+    fn test_match_by_session_keys_and_controller() {
+        let mut node = get_node(1, "unrelated");
+        node.validator_address = Some(AccountId::new([61; 32]).to_ss58_check());
+        assert_eq!(
+            match_method(&node, &[], &HashMap::default()),
+            Some((
+                AccountId::new([1; 32]),
+                TelemetryNodeMatchMethod::SessionKeys
+            ))
+        );
+        let mut node = get_node(2, "unrelated");
+        node.controller_account_id = Some(AccountId::new([101; 32]));
+        assert_eq!(
+            match_method(&node, &[], &HashMap::default()),
+            Some((
+                AccountId::new([1; 32]),
+                TelemetryNodeMatchMethod::Controller
+            ))
+        );
+    }
+
+    #[test]
+    fn test_match_by_network_id_and_override() {
+        let mut node = get_node(1, "helikon-1");
+        node.network_id = Some("12D3KooW".to_string());
+        let mut known_network_ids = HashMap::default();
+        known_network_ids.insert("12D3KooW".to_string(), AccountId::new([2; 32]));
+        assert_eq!(
+            match_method(&node, &[], &known_network_ids),
+            Some((AccountId::new([2; 32]), TelemetryNodeMatchMethod::NetworkId))
+        );
+        let overrides = vec![TelemetryNodeOverride {
+            validator_account_id: AccountId::new([3; 32]),
+            network_id: None,
+            node_name: Some("helikon-1".to_string()),
+        }];
+        assert_eq!(
+            match_method(&node, &overrides, &known_network_ids),
+            Some((AccountId::new([3; 32]), TelemetryNodeMatchMethod::Override))
+        );
+    }
+
+    #[test]
+    fn test_name_override_does_not_beat_keys() {
+        let name_override = TelemetryNodeOverride {
+            validator_account_id: AccountId::new([3; 32]),
+            network_id: None,
+            node_name: Some("helikon-1".to_string()),
+        };
+        let mut node = get_node(1, "helikon-1");
+        node.validator_address = Some(AccountId::new([61; 32]).to_ss58_check());
+        assert_eq!(
+            match_method(&node, &[name_override.clone()], &HashMap::default()),
+            Some((
+                AccountId::new([1; 32]),
+                TelemetryNodeMatchMethod::SessionKeys
+            ))
+        );
+        let mut node = get_node(2, "helikon-1");
+        node.controller_account_id = Some(AccountId::new([101; 32]));
+        assert_eq!(
+            match_method(&node, &[name_override], &HashMap::default()),
+            Some((
+                AccountId::new([1; 32]),
+                TelemetryNodeMatchMethod::Controller
+            ))
+        );
+        // network id override is exact, and beats the keys
+        let mut node = get_node(3, "helikon-1");
+        node.validator_address = Some(AccountId::new([61; 32]).to_ss58_check());
+        node.network_id = Some("12D3KooW".to_string());
+        let network_id_override = TelemetryNodeOverride {
+            validator_account_id: AccountId::new([3; 32]),
+            network_id: Some("12D3KooW".to_string()),
+            node_name: None,
+        };
+        assert_eq!(
+            match_method(&node, &[network_id_override], &HashMap::default()),
+            Some((AccountId::new([3; 32]), TelemetryNodeMatchMethod::Override))
+        );
+    }
+
+    #[test]
+    fn test_match_by_name() {
+        assert_eq!(
+            match_method(&get_node(1, "HELIKON"), &[], &HashMap::default()),
+            Some((AccountId::new([1; 32]), TelemetryNodeMatchMethod::NameExact))
+        );
+        assert_eq!(
+            match_method(&get_node(1, "helikon-backup-2"), &[], &HashMap::default()),
+            Some((
+                AccountId::new([1; 32]),
+                TelemetryNodeMatchMethod::NameContains
+            ))
+        );
+        // ambiguous display
+        assert_eq!(
+            match_method(&get_node(1, "Other Validator"), &[], &HashMap::default()),
+            None
+        );
+        assert_eq!(
+            match_method(&get_node(1, "unrelated"), &[], &HashMap::default()),
+            None
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Client versions reported by the Telemetry nodes, and the latest client release that the node
+//! versions get compared to.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Numeric part of a client version, e.g. `1.3.0` of `1.3.0-7c9fd83805c`. Commit hashes and
+/// other suffixes are ignored in comparisons.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ClientVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl FromStr for ClientVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let numeric = version
+            .trim()
+            .trim_start_matches('v')
+            .split(['-', '+', ' '])
+            .next()
+            .unwrap_or_default();
+        let mut parts = numeric.split('.');
+        let mut next_part = || -> anyhow::Result<u32> {
+            match parts.next() {
+                Some(part) => part
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid client version: {version}")),
+                None => Ok(0),
+            }
+        };
+        let client_version = ClientVersion {
+            major: next_part()?,
+            minor: next_part()?,
+            patch: next_part()?,
+        };
+        if parts.next().is_some() {
+            return Err(anyhow::anyhow!("Invalid client version: {version}"));
+        }
+        Ok(client_version)
+    }
+}
+
+impl Display for ClientVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A client release as listed in the release manifest.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct TelemetryRelease {
+    pub version: String,
+    /// Release time, e.g. `2023-11-01T12:00:00Z`. The time the release was first seen by the
+    /// Telemetry processor is used if not given.
+    pub released_at: Option<DateTime<Utc>>,
+}
+
+/// Local release manifest, i.e. a JSON file in the form of
+/// `{ "releases": [{ "version": "1.3.0", "released_at": "2023-11-01T12:00:00Z" }] }`.
+/// Releases don't need to be ordered.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TelemetryReleaseManifest {
+    pub releases: Vec<TelemetryRelease>,
+}
+
+impl TelemetryReleaseManifest {
+    /// Release with the greatest valid version.
+    pub fn get_latest_release(&self) -> Option<&TelemetryRelease> {
+        self.releases
+            .iter()
+            .filter_map(|release| {
+                ClientVersion::from_str(&release.version)
+                    .ok()
+                    .map(|version| (version, release))
+            })
+            .max_by_key(|(version, _)| *version)
+            .map(|(_, release)| release)
+    }
+}
+
+/// Latest client release as determined by the Telemetry processor.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, ToSchema)]
+pub struct TelemetryLatestVersion {
+    pub version: String,
+    pub released_timestamp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientVersion, TelemetryReleaseManifest};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_client_version() {
+        let version = ClientVersion::from_str("1.3.0-7c9fd83805c").unwrap();
+        assert_eq!(
+            version,
+            ClientVersion {
+                major: 1,
+                minor: 3,
+                patch: 0
+            }
+        );
+        assert_eq!(ClientVersion::from_str("v1.3").unwrap(), version);
+        assert!(ClientVersion::from_str("0.9.43-ba42b9ce51d").unwrap() < version);
+        assert!(ClientVersion::from_str("1.10.0").unwrap() > version);
+        assert!(ClientVersion::from_str("unknown").is_err());
+        assert!(ClientVersion::from_str("1.2.3.4").is_err());
+    }
+
+    #[test]
+    fn test_get_latest_release() {
+        let manifest: TelemetryReleaseManifest = serde_json::from_str(
+            r#"{"releases": [
+                {"version": "1.10.0", "released_at": "2024-04-01T12:00:00Z"},
+                {"version": "invalid"},
+                {"version": "1.9.0"}
+            ]}"#,
+        )
+        .unwrap();
+        let latest_release = manifest.get_latest_release().unwrap();
+        assert_eq!(latest_release.version, "1.10.0");
+        assert!(latest_release.released_at.is_some());
+    }
+}
@@ -19,6 +19,7 @@ use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use subvt_config::Config;
+use subvt_service_common::rate_limit::{self, RateLimiter};
 use subvt_service_common::Service;
 use subvt_types::crypto::AccountId;
 use subvt_types::subvt::{ValidatorDetails, ValidatorDetailsDiff};
@@ -30,6 +31,9 @@ lazy_static! {
     static ref LAST_FINALIZED_BLOCK_NUMBER: AtomicU64 = AtomicU64::new(0);
 }
 
+/// Rate limiting costs of the WebSocket methods. Other methods cost 1.
+const METHOD_COSTS: &[(&str, u32)] = &[("subscribe_validatorDetails", 2)];
+
 #[derive(Clone, Debug)]
 pub enum BusEvent {
     NewFinalizedBlock(u64),
@@ -85,9 +89,17 @@ impl ValidatorDetailsServer {
         port: u16,
         redis_client: &redis::Client,
         bus: Arc<Mutex<Bus<BusEvent>>>,
+        rate_limiter: &RateLimiter,
     ) -> anyhow::Result<ServerHandle> {
+        let (rate_limit_middleware, rate_limit_logger) =
+            rate_limit::rpc::build(rate_limiter, METHOD_COSTS);
         let rpc_ws_server = ServerBuilder::default()
             .max_request_body_size(u32::MAX)
+            .max_subscriptions_per_connection(
+                rate_limiter.config().max_subscriptions_per_connection,
+            )
+            .set_middleware(rate_limit_middleware)
+            .set_logger(rate_limit_logger)
             .build(format!("{host}:{port}"))
             .await?;
         let mut rpc_module = RpcModule::new(());
@@ -98,6 +110,9 @@ impl ValidatorDetailsServer {
             "subscribe_validatorDetails",
             "unsubscribe_validatorDetails",
             move |params, mut sink, _| {
+                if rate_limit::rpc::reject_if_rate_limited(&mut sink) {
+                    return Ok(());
+                }
                 let account_id = match params.one::<String>() {
                     Ok(param) => {
                         if let Ok(account_id) = AccountId::from_str(&param) {
@@ -282,11 +297,13 @@ impl Service for ValidatorDetailsServer {
             ))
             .await?;
         metrics::subscription_count().set(0);
+        let rate_limiter = RateLimiter::new(&CONFIG, "subvt_validator_details_server").await?;
         let server_stop_handle = ValidatorDetailsServer::run_rpc_server(
             &CONFIG.rpc.host,
             CONFIG.rpc.validator_details_port,
             &redis_client,
             bus.clone(),
+            &rate_limiter,
         )
         .await?;
         let mut pubsub_stream = pubsub_connection.on_message();
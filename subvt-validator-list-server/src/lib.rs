@@ -18,6 +18,7 @@ use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex, RwLock};
 use subvt_config::Config;
+use subvt_service_common::rate_limit::{self, RateLimiter};
 use subvt_service_common::Service;
 use subvt_types::{
     crypto::AccountId,
@@ -30,6 +31,9 @@ lazy_static! {
     static ref CONFIG: Config = Config::default();
 }
 
+/// Rate limiting costs of the WebSocket methods. Other methods cost 1.
+const METHOD_COSTS: &[(&str, u32)] = &[("subscribe_validatorList", 10)];
+
 #[derive(Clone, Debug)]
 pub enum BusEvent {
     Update(ValidatorListUpdate),
@@ -56,9 +60,17 @@ impl ValidatorListServer {
         port: u16,
         validator_map: &Arc<RwLock<HashMap<AccountId, ValidatorDetails>>>,
         bus: &Arc<Mutex<Bus<BusEvent>>>,
+        rate_limiter: &RateLimiter,
     ) -> anyhow::Result<ServerHandle> {
+        let (rate_limit_middleware, rate_limit_logger) =
+            rate_limit::rpc::build(rate_limiter, METHOD_COSTS);
         let rpc_ws_server = ServerBuilder::default()
             .max_request_body_size(u32::MAX)
+            .max_subscriptions_per_connection(
+                rate_limiter.config().max_subscriptions_per_connection,
+            )
+            .set_middleware(rate_limit_middleware)
+            .set_logger(rate_limit_logger)
             .build(format!("{host}:{port}"))
             .await?;
         let mut rpc_module = RpcModule::new(());
@@ -69,6 +81,9 @@ impl ValidatorListServer {
             "subscribe_validatorList",
             "unsubscribe_validatorList",
             move |_params, mut sink, _| {
+                if rate_limit::rpc::reject_if_rate_limited(&mut sink) {
+                    return Ok(());
+                }
                 log::info!("New subscription.");
                 metrics::subscription_count().inc();
                 let mut bus_receiver = bus.lock().unwrap().add_rx();
@@ -164,6 +179,15 @@ impl Service for ValidatorListServer {
             .await?;
         let mut data_connection = redis_client.get_connection()?;
         metrics::subscription_count().set(0);
+        let rate_limiter = RateLimiter::new(
+            &CONFIG,
+            if is_active_list {
+                "subvt_active_validator_list_server"
+            } else {
+                "subvt_inactive_validator_list_server"
+            },
+        )
+        .await?;
         let server_stop_handle = ValidatorListServer::run_rpc_server(
             &CONFIG.rpc.host,
             if is_active_list {
@@ -173,6 +197,7 @@ impl Service for ValidatorListServer {
             },
             &validator_map,
             &bus,
+            &rate_limiter,
         )
         .await?;
 
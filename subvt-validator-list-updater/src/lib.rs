@@ -10,7 +10,7 @@ use redis::Pipeline;
 use rustc_hash::{FxHashSet as HashSet, FxHasher};
 use std::hash::{Hash, Hasher};
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc,
 };
 use subvt_config::Config;
@@ -25,6 +25,16 @@ mod metrics;
 lazy_static! {
     static ref CONFIG: Config = Config::default();
     static ref IS_BUSY: AtomicBool = AtomicBool::new(false);
+    static ref LAST_SNAPSHOT_BLOCK_NUMBER: AtomicU64 = AtomicU64::new(0);
+}
+
+/// Snapshots are saved at most once in the configured number of blocks.
+fn is_snapshot_due(
+    last_snapshot_block_number: u64,
+    finalized_block_number: u64,
+    snapshot_period_blocks: u64,
+) -> bool {
+    finalized_block_number >= last_snapshot_block_number + snapshot_period_blocks
 }
 
 #[derive(Default)]
@@ -252,6 +262,32 @@ impl ValidatorListUpdater {
         .await?;
         let elapsed = start.elapsed();
         log::info!("Redis updated. Took {} ms.", elapsed.as_millis());
+        // downsampled history
+        if is_snapshot_due(
+            LAST_SNAPSHOT_BLOCK_NUMBER.load(Ordering::SeqCst),
+            finalized_block_number,
+            CONFIG.validator_list_updater.snapshot_period_blocks,
+        ) {
+            log::info!("Save validator snapshots.");
+            // snapshots are auxiliary history, a failure shouldn't fail the list update
+            match postgres
+                .save_validator_snapshots(
+                    finalized_block_number,
+                    finalized_block_timestamp,
+                    &validators,
+                )
+                .await
+            {
+                Ok(()) => {
+                    LAST_SNAPSHOT_BLOCK_NUMBER.store(finalized_block_number, Ordering::SeqCst)
+                }
+                Err(error) => log::error!(
+                    "Error while saving validator snapshots for block #{}: {:?}",
+                    finalized_block_number,
+                    error,
+                ),
+            }
+        }
         {
             let mut processed_block_numbers = processed_block_numbers.write().await;
             processed_block_numbers.push(finalized_block_number);
@@ -335,6 +371,10 @@ impl Service for ValidatorListUpdater {
             let postgres = Arc::new(
                 PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?,
             );
+            // continue the snapshot period after a restart instead of snapshotting right away
+            if let Some(block_number) = postgres.get_last_validator_snapshot_block_number().await? {
+                LAST_SNAPSHOT_BLOCK_NUMBER.fetch_max(block_number, Ordering::SeqCst);
+            }
             let substrate_client = Arc::new(SubstrateClient::new(&CONFIG).await?);
             let processed_block_numbers: Arc<RwLock<Vec<u64>>> = Arc::new(RwLock::new(
                 ValidatorListUpdater::fetch_processed_block_numbers().await?,
@@ -394,3 +434,19 @@ impl Service for ValidatorListUpdater {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::is_snapshot_due;
+
+    #[test]
+    fn test_snapshot_period() {
+        // no snapshot yet
+        assert!(is_snapshot_due(0, 1_000, 600));
+        assert!(!is_snapshot_due(1_000, 1_000, 600));
+        assert!(!is_snapshot_due(1_000, 1_599, 600));
+        assert!(is_snapshot_due(1_000, 1_600, 600));
+        // a block before the last snapshot, i.e. a past block
+        assert!(!is_snapshot_due(1_000, 900, 600));
+    }
+}